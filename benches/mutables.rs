@@ -0,0 +1,84 @@
+//! Compares the two storage modes for mutables: one entity per mutable, and the
+//! `MutableStore` arena.
+
+use bevy::prelude::*;
+use bevy_reactor::{Cx, Mutable, MutableStorage, RunContextSetup, TrackingScope};
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+
+const COUNT: usize = 10_000;
+
+const MODES: [(&str, MutableStorage); 2] = [
+    ("entities", MutableStorage::Entities),
+    ("store", MutableStorage::Store),
+];
+
+fn setup(storage: MutableStorage) -> (World, Entity, TrackingScope) {
+    let mut world = World::default();
+    world.insert_resource(storage);
+    let owner = world.spawn_empty().id();
+    let scope = TrackingScope::new(world.change_tick());
+    (world, owner, scope)
+}
+
+fn setup_mutables(storage: MutableStorage) -> (World, Vec<Mutable<usize>>) {
+    let (mut world, owner, mut scope) = setup(storage);
+    let mut cx = Cx::new(&mut world, owner, &mut scope);
+    let mutables = (0..COUNT).map(|i| cx.create_mutable(i)).collect();
+    world.entity_mut(owner).insert(scope);
+    (world, mutables)
+}
+
+fn create(c: &mut Criterion) {
+    let mut group = c.benchmark_group("create_mutables");
+    for (name, storage) in MODES {
+        group.bench_function(name, |b| {
+            b.iter_batched(
+                || setup(storage),
+                |(mut world, owner, mut scope)| {
+                    let mut cx = Cx::new(&mut world, owner, &mut scope);
+                    for i in 0..COUNT {
+                        black_box(cx.create_mutable(i));
+                    }
+                    world
+                },
+                BatchSize::LargeInput,
+            );
+        });
+    }
+    group.finish();
+}
+
+fn read(c: &mut Criterion) {
+    let mut group = c.benchmark_group("read_mutables");
+    for (name, storage) in MODES {
+        let (world, mutables) = setup_mutables(storage);
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                let sum: usize = mutables.iter().map(|m| m.get(&world)).sum();
+                black_box(sum)
+            });
+        });
+    }
+    group.finish();
+}
+
+fn write(c: &mut Criterion) {
+    let mut group = c.benchmark_group("write_mutables");
+    for (name, storage) in MODES {
+        let (mut world, mutables) = setup_mutables(storage);
+        let mut offset = 0;
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                offset += 1;
+                for (i, m) in mutables.iter().enumerate() {
+                    m.set(&mut world, i + offset);
+                }
+                world.flush_commands();
+            });
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, create, read, write);
+criterion_main!(benches);
@@ -0,0 +1,101 @@
+//! Benchmarks for the reactive core: dispatching reactions, and propagating changes through
+//! chains of derived signals.
+
+use bevy::prelude::*;
+use bevy_reactor::{update_reactor, Cx, Mutable, RunContextRead, RunContextSetup, TrackingScope};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+#[derive(Resource, Default)]
+struct Source(usize);
+
+#[derive(Resource, Default)]
+struct Sink(usize);
+
+/// Create a world whose reactions are set up by `setup`, with dependencies already indexed.
+fn setup_world<R>(setup: impl FnOnce(&mut Cx) -> R) -> (World, R) {
+    let mut world = World::default();
+    world.init_resource::<Source>();
+    world.init_resource::<Sink>();
+    let owner = world.spawn_empty().id();
+    let mut scope = TrackingScope::new(world.change_tick());
+    let result = setup(&mut Cx::new(&mut world, owner, &mut scope));
+    world.entity_mut(owner).insert(scope);
+    update_reactor(&mut world);
+    (world, result)
+}
+
+fn trigger_reactions(c: &mut Criterion) {
+    let mut group = c.benchmark_group("trigger_reactions");
+    for count in [100, 1_000, 10_000] {
+        // Every reaction depends on the resource which changes.
+        let (mut world, _) = setup_world(|cx| {
+            for _ in 0..count {
+                cx.create_effect(|cx| {
+                    let n = cx.use_resource::<Source>().0;
+                    cx.world_mut().resource_mut::<Sink>().0 = n;
+                });
+            }
+        });
+        group.bench_function(BenchmarkId::new("all", count), |b| {
+            b.iter(|| {
+                world.resource_mut::<Source>().0 += 1;
+                update_reactor(&mut world);
+            });
+        });
+
+        // Each reaction depends on its own mutable, and only one of them changes.
+        let (mut world, mutables) = setup_world(|cx| {
+            (0..count)
+                .map(|_| {
+                    let mutable: Mutable<usize> = cx.create_mutable(0);
+                    cx.create_effect(move |cx| {
+                        let n = mutable.get(cx);
+                        cx.world_mut().resource_mut::<Sink>().0 = n;
+                    });
+                    mutable
+                })
+                .collect::<Vec<_>>()
+        });
+        let mut n = 0;
+        group.bench_function(BenchmarkId::new("one", count), |b| {
+            b.iter(|| {
+                n += 1;
+                mutables[n % count].set(&mut world, n);
+                update_reactor(&mut world);
+            });
+        });
+    }
+    group.finish();
+}
+
+fn derived_chain(c: &mut Criterion) {
+    let mut group = c.benchmark_group("derived_chain");
+    for depth in [10, 100, 1_000] {
+        let (mut world, root) = setup_world(|cx| {
+            let root: Mutable<usize> = cx.create_mutable(0);
+            let mut tail = root.signal();
+            for _ in 0..depth {
+                let prev = tail;
+                tail = cx.create_derived(move |cx| prev.get(cx) + 1);
+            }
+            cx.create_effect(move |cx| {
+                let n = tail.get(cx);
+                cx.world_mut().resource_mut::<Sink>().0 = n;
+            });
+            root
+        });
+        let mut n = 0;
+        group.bench_function(BenchmarkId::from_parameter(depth), |b| {
+            b.iter(|| {
+                n += 1;
+                root.set(&mut world, n);
+                update_reactor(&mut world);
+            });
+        });
+        assert_eq!(world.resource::<Sink>().0, n + depth);
+    }
+    group.finish();
+}
+
+criterion_group!(benches, trigger_reactions, derived_chain);
+criterion_main!(benches);
@@ -0,0 +1,93 @@
+//! Benchmarks for views: diffing keyed lists, and razing and rebuilding a widget tree.
+
+use bevy::prelude::*;
+use bevy_reactor::{
+    text, update_reactor, DynamicKeyed, Element, For, IntoView, ParentView, RunContextRead,
+    ViewRef, ViewRoot,
+};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+#[derive(Resource, Default)]
+struct Items(Vec<usize>);
+
+#[derive(Resource, Default)]
+struct Generation(usize);
+
+fn list_world(len: usize) -> World {
+    let mut world = World::default();
+    world.insert_resource(Items((0..len).collect()));
+    world.spawn(ViewRoot::new(Element::<NodeBundle>::new().children(
+        For::each(
+            |cx| cx.use_resource::<Items>().0.clone().into_iter(),
+            |item| Element::<NodeBundle>::new().children(text(&item.to_string())),
+        ),
+    )));
+    update_reactor(&mut world);
+    world
+}
+
+fn keyed_list(c: &mut Criterion) {
+    let mut group = c.benchmark_group("keyed_list");
+    for len in [100, 1_000] {
+        // Move the last item to the front, which keeps all but one of the child views.
+        let mut world = list_world(len);
+        group.bench_function(BenchmarkId::new("rotate", len), |b| {
+            b.iter(|| {
+                world.resource_mut::<Items>().0.rotate_right(1);
+                update_reactor(&mut world);
+            });
+        });
+
+        // Reverse the list, which keeps only one child view in place.
+        let mut world = list_world(len);
+        group.bench_function(BenchmarkId::new("reverse", len), |b| {
+            b.iter(|| {
+                world.resource_mut::<Items>().0.reverse();
+                update_reactor(&mut world);
+            });
+        });
+    }
+    group.finish();
+}
+
+/// A tree of elements with `width` children per node, `depth` levels deep.
+fn widget_tree(width: usize, depth: usize) -> ViewRef {
+    let element = Element::<NodeBundle>::new();
+    if depth == 0 {
+        return element.children(text("Leaf")).into_view();
+    }
+    element
+        .children(
+            (0..width)
+                .map(|_| widget_tree(width, depth - 1))
+                .collect::<Vec<_>>(),
+        )
+        .into_view()
+}
+
+fn tree_rebuild(c: &mut Criterion) {
+    let mut group = c.benchmark_group("tree_rebuild");
+    for (width, depth) in [(10, 2), (10, 3)] {
+        let mut world = World::default();
+        world.init_resource::<Generation>();
+        world.spawn(ViewRoot::new(DynamicKeyed::new(
+            |cx| cx.use_resource::<Generation>().0,
+            move |_| widget_tree(width, depth),
+        )));
+        update_reactor(&mut world);
+        // Changing the key razes the whole tree and builds a new one.
+        group.bench_function(
+            BenchmarkId::new("rebuild", format!("{}x{}", width, depth)),
+            |b| {
+                b.iter(|| {
+                    world.resource_mut::<Generation>().0 += 1;
+                    update_reactor(&mut world);
+                });
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, keyed_list, tree_rebuild);
+criterion_main!(benches);
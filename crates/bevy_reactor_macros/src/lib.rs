@@ -0,0 +1,59 @@
+//! Procedural macros for `bevy_reactor`. These are re-exported by `bevy_reactor`, and should
+//! be used from there rather than depending on this crate directly.
+
+#![deny(missing_docs)]
+
+use proc_macro::TokenStream;
+use quote::ToTokens;
+use syn::parse_macro_input;
+
+mod node;
+
+use node::ViewNodes;
+
+/// Build a view from an element tree written in an HTML-like syntax. The macro expands into
+/// the same builder calls that would be written by hand, so it can be mixed freely with
+/// ordinary view code.
+///
+/// * `<Tag>...</Tag>` constructs a view with `<Tag>::new()`. The tag is a type path, which may
+///   include generic arguments, such as `Element<NodeBundle>`. Use `<Tag ... />` for a view
+///   with no children.
+/// * Attributes are converted into builder method calls: `name="Panel"` becomes
+///   `.name("Panel")`, `style={style_panel}` becomes `.style(style_panel)`, and a bare
+///   attribute such as `disabled` becomes `.disabled(true)`.
+/// * Children are passed to the `.children()` method of the parent, so any view type which
+///   accepts children works, including [`Element`](../bevy_reactor/struct.Element.html) and
+///   `ViewTemplate`s with a `children` builder method.
+/// * A string literal child is static text.
+/// * A braced child `{ ... }` is a Rust expression which produces a view.
+/// * `if test { ... } else { ... }` produces a `Cond`. `test` is the condition closure, for
+///   example `move |cx| open.get(cx)`. The `else` branch is optional, and may be another `if`.
+/// * `for item in items { ... } else { ... }` produces a `For::each` loop. `items` is the
+///   closure returning the item iterator, and `item` is a pattern bound to a reference to each
+///   item. The optional `else` branch is shown when there are no items.
+///
+/// Multiple views at the top level or inside a branch are combined into a `Fragment`.
+///
+/// ```ignore
+/// view! {
+///     <Element<NodeBundle> named="Dialog" style={style_dialog}>
+///         <DialogHeader>"Settings"</DialogHeader>
+///         if move |cx| loading.get(cx) {
+///             <Spinner />
+///         } else {
+///             for item in move |cx| items.get_clone(cx).into_iter() {
+///                 <ListRow label={item.name.clone()} />
+///             } else {
+///                 "No items"
+///             }
+///         }
+///         <Button variant={ButtonVariant::Primary} on_click={on_close}>"Close"</Button>
+///     </Element>
+/// }
+/// ```
+#[proc_macro]
+pub fn view(input: TokenStream) -> TokenStream {
+    parse_macro_input!(input as ViewNodes)
+        .into_token_stream()
+        .into()
+}
@@ -0,0 +1,409 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{
+    braced,
+    parse::{Parse, ParseStream},
+    spanned::Spanned,
+    Block, Expr, Ident, Lit, LitStr, Pat, Path, Token,
+};
+
+/// The maximum tuple length accepted by `ChildViewTuple`. Longer lists of children are split
+/// into nested tuples.
+const MAX_TUPLE_LEN: usize = 15;
+
+/// A sequence of sibling views, which expands to a single view.
+pub struct ViewNodes(Vec<Node>);
+
+/// A single view in the template.
+enum Node {
+    /// `<Tag attr=value>children</Tag>`
+    Element(ElementNode),
+    /// `"text"`
+    Text(LitStr),
+    /// `{ expression }`
+    Block(Block),
+    /// `if test { ... } else { ... }`
+    If(IfNode),
+    /// `for pattern in items { ... } else { ... }`
+    For(ForNode),
+}
+
+struct ElementNode {
+    path: Path,
+    attrs: Vec<Attr>,
+    children: Vec<Node>,
+}
+
+struct Attr {
+    name: Ident,
+    /// The value of the attribute, or `None` for a bare attribute.
+    value: Option<TokenStream>,
+}
+
+struct IfNode {
+    test: Expr,
+    then_branch: ViewNodes,
+    else_branch: Option<ElseBranch>,
+}
+
+enum ElseBranch {
+    If(Box<IfNode>),
+    Nodes(ViewNodes),
+}
+
+struct ForNode {
+    pat: Pat,
+    items: Expr,
+    body: ViewNodes,
+    fallback: Option<ViewNodes>,
+}
+
+impl Parse for ViewNodes {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut nodes = Vec::new();
+        while !input.is_empty() {
+            nodes.push(input.parse()?);
+        }
+        Ok(Self(nodes))
+    }
+}
+
+/// Parse the contents of a pair of braces as a list of views.
+fn parse_braced_nodes(input: ParseStream) -> syn::Result<ViewNodes> {
+    let content;
+    braced!(content in input);
+    content.parse()
+}
+
+impl Parse for Node {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(Token![<]) {
+            input.parse().map(Node::Element)
+        } else if input.peek(LitStr) {
+            input.parse().map(Node::Text)
+        } else if input.peek(syn::token::Brace) {
+            input.parse().map(Node::Block)
+        } else if input.peek(Token![if]) {
+            input.parse().map(Node::If)
+        } else if input.peek(Token![for]) {
+            input.parse().map(Node::For)
+        } else {
+            Err(input.error("expected an element, text, `{ expression }`, `if` or `for`"))
+        }
+    }
+}
+
+impl Parse for ElementNode {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        input.parse::<Token![<]>()?;
+        let path: Path = input.parse()?;
+
+        let mut attrs = Vec::new();
+        while !input.peek(Token![>]) && !input.peek(Token![/]) {
+            attrs.push(input.parse()?);
+        }
+
+        if input.peek(Token![/]) {
+            input.parse::<Token![/]>()?;
+            input.parse::<Token![>]>()?;
+            return Ok(Self {
+                path,
+                attrs,
+                children: Vec::new(),
+            });
+        }
+        input.parse::<Token![>]>()?;
+
+        let mut children = Vec::new();
+        loop {
+            if input.is_empty() {
+                return Err(syn::Error::new(
+                    path.span(),
+                    format!("missing closing tag `</{}>`", tag_name(&path)),
+                ));
+            }
+            if input.peek(Token![<]) && input.peek2(Token![/]) {
+                break;
+            }
+            children.push(input.parse()?);
+        }
+
+        input.parse::<Token![<]>()?;
+        input.parse::<Token![/]>()?;
+        let close: Path = input.parse()?;
+        input.parse::<Token![>]>()?;
+        if tag_name(&close) != tag_name(&path) {
+            return Err(syn::Error::new(
+                close.span(),
+                format!(
+                    "closing tag `</{}>` does not match `<{}>`",
+                    tag_name(&close),
+                    tag_name(&path)
+                ),
+            ));
+        }
+
+        Ok(Self {
+            path,
+            attrs,
+            children,
+        })
+    }
+}
+
+/// The name of a tag, without generic arguments, used to match opening and closing tags.
+fn tag_name(path: &Path) -> String {
+    path.segments
+        .iter()
+        .map(|segment| segment.ident.to_string())
+        .collect::<Vec<_>>()
+        .join("::")
+}
+
+impl Parse for Attr {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name: Ident = input.parse()?;
+        if !input.peek(Token![=]) {
+            return Ok(Self { name, value: None });
+        }
+        input.parse::<Token![=]>()?;
+        let value = if input.peek(syn::token::Brace) {
+            let block: Block = input.parse()?;
+            block.into_token_stream()
+        } else if input.peek(Lit) {
+            input.parse::<Lit>()?.into_token_stream()
+        } else {
+            return Err(input.error("expected a literal or `{ expression }` attribute value"));
+        };
+        Ok(Self {
+            name,
+            value: Some(value),
+        })
+    }
+}
+
+impl Parse for IfNode {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        input.parse::<Token![if]>()?;
+        let test = Expr::parse_without_eager_brace(input)?;
+        let then_branch = parse_braced_nodes(input)?;
+        let else_branch = if input.peek(Token![else]) {
+            input.parse::<Token![else]>()?;
+            if input.peek(Token![if]) {
+                Some(ElseBranch::If(Box::new(input.parse()?)))
+            } else {
+                Some(ElseBranch::Nodes(parse_braced_nodes(input)?))
+            }
+        } else {
+            None
+        };
+        Ok(Self {
+            test,
+            then_branch,
+            else_branch,
+        })
+    }
+}
+
+impl Parse for ForNode {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        input.parse::<Token![for]>()?;
+        let pat = Pat::parse_single(input)?;
+        input.parse::<Token![in]>()?;
+        let items = Expr::parse_without_eager_brace(input)?;
+        let body = parse_braced_nodes(input)?;
+        let fallback = if input.peek(Token![else]) {
+            input.parse::<Token![else]>()?;
+            Some(parse_braced_nodes(input)?)
+        } else {
+            None
+        };
+        Ok(Self {
+            pat,
+            items,
+            body,
+            fallback,
+        })
+    }
+}
+
+/// Combine a list of views into a tuple which implements `ChildViewTuple`, nesting tuples
+/// when there are too many views for a single one.
+fn child_tuple(mut views: Vec<TokenStream>) -> TokenStream {
+    while views.len() > MAX_TUPLE_LEN {
+        views = views
+            .chunks(MAX_TUPLE_LEN)
+            .map(|chunk| quote! { (#(#chunk,)*) })
+            .collect();
+    }
+    quote! { (#(#views,)*) }
+}
+
+impl ToTokens for ViewNodes {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        match self.0.as_slice() {
+            [] => tokens.extend(quote! { () }),
+            [node] => node.to_tokens(tokens),
+            nodes => {
+                let views = child_tuple(nodes.iter().map(Node::to_token_stream).collect());
+                tokens.extend(quote! { ::bevy_reactor::Fragment::new(#views) });
+            }
+        }
+    }
+}
+
+impl ToTokens for Node {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        match self {
+            Node::Element(element) => element.to_tokens(tokens),
+            Node::Text(text) => text.to_tokens(tokens),
+            Node::Block(block) => block.to_tokens(tokens),
+            Node::If(cond) => cond.to_tokens(tokens),
+            Node::For(each) => each.to_tokens(tokens),
+        }
+    }
+}
+
+impl ToTokens for ElementNode {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let path = &self.path;
+        let attrs = self.attrs.iter().map(|attr| {
+            let name = &attr.name;
+            match &attr.value {
+                Some(value) => quote! { .#name(#value) },
+                None => quote! { .#name(true) },
+            }
+        });
+        let children = match self.children.as_slice() {
+            [] => None,
+            [child] => Some(quote! { .children(#child) }),
+            children => {
+                let views = child_tuple(children.iter().map(Node::to_token_stream).collect());
+                Some(quote! { .children(#views) })
+            }
+        };
+        tokens.extend(quote! { <#path>::new() #(#attrs)* #children });
+    }
+}
+
+impl ToTokens for IfNode {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let test = &self.test;
+        let then_branch = &self.then_branch;
+        let else_branch = match &self.else_branch {
+            Some(ElseBranch::If(cond)) => cond.to_token_stream(),
+            Some(ElseBranch::Nodes(nodes)) => nodes.to_token_stream(),
+            None => quote! { () },
+        };
+        tokens.extend(quote! {
+            ::bevy_reactor::Cond::new(#test, move || #then_branch, move || #else_branch)
+        });
+    }
+}
+
+impl ToTokens for ForNode {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let pat = &self.pat;
+        let items = &self.items;
+        let body = &self.body;
+        let fallback = self
+            .fallback
+            .as_ref()
+            .map(|fallback| quote! { .with_fallback(#fallback) });
+        tokens.extend(quote! {
+            ::bevy_reactor::For::each(#items, move |#pat| #body) #fallback
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expand(input: &str) -> String {
+        syn::parse_str::<ViewNodes>(input)
+            .unwrap()
+            .into_token_stream()
+            .to_string()
+    }
+
+    fn parse_error(input: &str) -> String {
+        match syn::parse_str::<ViewNodes>(input) {
+            Ok(_) => panic!("expected a parse error"),
+            Err(err) => err.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_element() {
+        assert_eq!(
+            expand(r#"<Element<NodeBundle> named="Panel" style={style_panel} />"#),
+            quote! { <Element<NodeBundle> >::new().named("Panel").style({ style_panel }) }
+                .to_string()
+        );
+        assert_eq!(
+            expand("<Button disabled>\"Ok\"</Button>"),
+            quote! { <Button>::new().disabled(true).children("Ok") }.to_string()
+        );
+    }
+
+    #[test]
+    fn test_children() {
+        assert_eq!(
+            expand("<Row><A /> \"text\" {value}</Row>"),
+            quote! { <Row>::new().children((<A>::new(), "text", { value },)) }.to_string()
+        );
+        assert_eq!(
+            expand("<A /> <B />"),
+            quote! { ::bevy_reactor::Fragment::new((<A>::new(), <B>::new(),)) }.to_string()
+        );
+    }
+
+    #[test]
+    fn test_many_children() {
+        let children = "<A />".repeat(16);
+        let expanded = expand(&format!("<Row>{}</Row>", children));
+        assert!(expanded.starts_with("< Row > :: new () . children (((< A >"));
+        assert_eq!(expanded.matches("< A > :: new ()").count(), 16);
+    }
+
+    #[test]
+    fn test_cond() {
+        assert_eq!(
+            expand("if move |cx| open.get(cx) { <A /> } else if test { <B /> }"),
+            quote! {
+                ::bevy_reactor::Cond::new(
+                    move |cx| open.get(cx),
+                    move || <A>::new(),
+                    move || ::bevy_reactor::Cond::new(test, move || <B>::new(), move || ())
+                )
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn test_for() {
+        assert_eq!(
+            expand("for item in items { {item.label()} } else { \"Empty\" }"),
+            quote! {
+                ::bevy_reactor::For::each(items, move |item| { item.label() })
+                    .with_fallback("Empty")
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            parse_error("<Row><A /></Column>"),
+            "closing tag `</Column>` does not match `<Row>`"
+        );
+        assert_eq!(parse_error("<Row><A />"), "missing closing tag `</Row>`");
+        assert_eq!(
+            parse_error("<Row>42</Row>"),
+            "expected an element, text, `{ expression }`, `if` or `for`"
+        );
+    }
+}
@@ -0,0 +1,326 @@
+use bevy::{
+    color::LinearRgba,
+    pbr::{NotShadowCaster, NotShadowReceiver},
+    prelude::*,
+};
+use bevy_reactor::*;
+
+use crate::overlay_material::{LabelMaterial, LabelUnderlayMaterial};
+
+/// Component on the display entity of an [`OverlayLabel`], used to size and orient the quad
+/// which displays the rendered text.
+#[derive(Component)]
+pub(crate) struct LabelQuad {
+    /// Texture which the text is rendered into.
+    image: Handle<Image>,
+
+    /// Size of a texture pixel, in world units.
+    pixel_size: f32,
+}
+
+/// A text label in the 3d world, which always faces the camera. The text is rendered into a
+/// texture by a [`Compositor`], which is displayed on a quad.
+pub struct OverlayLabel {
+    /// Debug name for this element.
+    debug_name: String,
+
+    /// The visible entity for this label.
+    display: Option<Entity>,
+
+    /// The compositor which renders the text, and its view entity.
+    compositor: Option<(ViewRef, Entity)>,
+
+    /// List of effects to be added to the element.
+    effects: Vec<Box<dyn EntityEffect>>,
+
+    /// Text of the label.
+    text: Signal<String>,
+
+    /// Color of the label.
+    color: Signal<LinearRgba>,
+
+    /// Font size of the label, in texture pixels.
+    font_size: Signal<f32>,
+
+    /// Position of the center of the label.
+    position: Signal<Vec3>,
+
+    /// Size of a texture pixel, in world units.
+    pixel_size: f32,
+
+    /// Occlusion opacity, 0.0 to 1.0. This represents the opacity of the label when it is
+    /// occluded by other objects.
+    underlay: f32,
+}
+
+impl OverlayLabel {
+    /// Construct a new `OverlayLabel`.
+    pub fn new(text: impl IntoSignal<String>) -> Self {
+        Self {
+            debug_name: String::new(),
+            display: None,
+            compositor: None,
+            effects: Vec::new(),
+            text: text.into_signal(),
+            color: Signal::Constant(LinearRgba::WHITE),
+            font_size: Signal::Constant(24.),
+            position: Signal::Constant(Vec3::ZERO),
+            pixel_size: 0.01,
+            underlay: 0.3,
+        }
+    }
+
+    /// Set the debug name for this element.
+    pub fn named(mut self, name: &str) -> Self {
+        self.debug_name = name.to_string();
+        self
+    }
+
+    /// Set the color of the label.
+    pub fn with_color(mut self, color: impl Into<LinearRgba>) -> Self {
+        self.color = Signal::Constant(color.into());
+        self
+    }
+
+    /// Set the color of the label as a signal.
+    pub fn with_color_signal(mut self, color: impl Into<Signal<LinearRgba>>) -> Self {
+        self.color = color.into();
+        self
+    }
+
+    /// Set the font size of the label, in texture pixels. The size of the label in the world
+    /// is the font size multiplied by the pixel size.
+    pub fn with_font_size(mut self, font_size: impl IntoSignal<f32>) -> Self {
+        self.font_size = font_size.into_signal();
+        self
+    }
+
+    /// Set the position of the center of the label.
+    pub fn with_position(mut self, position: impl IntoSignal<Vec3>) -> Self {
+        self.position = position.into_signal();
+        self
+    }
+
+    /// Set the size of a pixel of the rendered text, in world units. The default is 0.01.
+    pub fn with_pixel_size(mut self, pixel_size: f32) -> Self {
+        self.pixel_size = pixel_size;
+        self
+    }
+
+    /// "Underlay" controls the opacity of the label when it is occluded by other objects.
+    /// A value of 0 means that occluded portions of the label are completely invisible,
+    /// while a value of 1 means that the label is completely visible even when occluded.
+    pub fn with_underlay(mut self, underlay: f32) -> Self {
+        self.underlay = underlay;
+        self
+    }
+}
+
+impl EffectTarget for OverlayLabel {
+    fn add_effect(&mut self, effect: Box<dyn EntityEffect>) {
+        self.effects.push(effect);
+    }
+}
+
+impl View for OverlayLabel {
+    fn nodes(&self) -> NodeSpan {
+        match self.display {
+            None => NodeSpan::Empty,
+            Some(node) => NodeSpan::Node(node),
+        }
+    }
+
+    fn build(&mut self, view_entity: Entity, world: &mut World) {
+        world
+            .entity_mut(view_entity)
+            .insert(Name::new("OverlayLabel"));
+
+        // Render the text into a texture.
+        let image = Compositor::new_texture(&mut world.resource_mut::<Assets<Image>>());
+        let text = self.text.clone();
+        let font_size = self.font_size;
+        let text_view = Element::<TextBundle>::new()
+            .named("OverlayLabel::Text")
+            .create_effect(move |cx, ent| {
+                let section = TextSection::new(
+                    text.get_clone(cx),
+                    TextStyle {
+                        font_size: font_size.get(cx),
+                        color: Color::WHITE,
+                        ..default()
+                    },
+                );
+                let mut entt = cx.world_mut().entity_mut(ent);
+                if let Some(mut text) = entt.get_mut::<Text>() {
+                    text.sections = vec![section];
+                }
+            });
+        let compositor = Compositor::new((text_view,))
+            .with_image(image.clone())
+            .clear_color(Color::NONE)
+            .offscreen()
+            .into_view();
+        let compositor_entity = ViewRef::spawn(&compositor, view_entity, world);
+        self.compositor = Some((compositor, compositor_entity));
+
+        let mesh = world
+            .resource_mut::<Assets<Mesh>>()
+            .add(Rectangle::default());
+        let material = world
+            .resource_mut::<Assets<LabelMaterial>>()
+            .add(LabelMaterial {
+                color: LinearRgba::WHITE,
+                texture: image.clone(),
+            });
+        let underlay_material =
+            world
+                .resource_mut::<Assets<LabelUnderlayMaterial>>()
+                .add(LabelUnderlayMaterial {
+                    color: LinearRgba::WHITE,
+                    texture: image.clone(),
+                });
+
+        let bundle = (
+            Name::new(self.debug_name.clone()),
+            MaterialMeshBundle::<LabelMaterial> {
+                material: material.clone(),
+                mesh,
+                ..default()
+            },
+            underlay_material.clone(),
+            LabelQuad {
+                image,
+                pixel_size: self.pixel_size,
+            },
+            NotShadowCaster,
+            NotShadowReceiver,
+        );
+        let display = world.spawn(bundle).id();
+        self.display = Some(display);
+
+        let mut tracking = TrackingScope::new(world.change_tick());
+        self.start_reaction(
+            LabelColorReaction {
+                color: self.color,
+                underlay: self.underlay,
+                material,
+                underlay_material,
+            },
+            view_entity,
+            display,
+            world,
+            &mut tracking,
+        );
+        self.start_reaction(
+            LabelPositionReaction {
+                label: display,
+                position: self.position,
+            },
+            view_entity,
+            display,
+            world,
+            &mut tracking,
+        );
+        for effect in self.effects.iter_mut() {
+            effect.start(view_entity, display, world, &mut tracking);
+        }
+        world.entity_mut(view_entity).insert(tracking);
+    }
+
+    fn raze(&mut self, view_entity: Entity, world: &mut World) {
+        assert!(self.display.is_some());
+        if let Some((compositor, entity)) = self.compositor.take() {
+            compositor.raze(entity, world);
+        }
+
+        // Delete the display node.
+        world.entity_mut(self.display.unwrap()).remove_parent();
+        world.entity_mut(self.display.unwrap()).despawn();
+        self.display = None;
+
+        // Delete all reactions.
+        world.despawn_owned_recursive(view_entity);
+    }
+
+    fn children_changed(&mut self, _view_entity: Entity, _world: &mut World) -> bool {
+        // The compositor has no display nodes.
+        true
+    }
+}
+
+impl IntoView for OverlayLabel {
+    fn into_view(self) -> ViewRef {
+        ViewRef::new(self)
+    }
+}
+
+/// Reactive effect which changes the color of the label.
+struct LabelColorReaction {
+    color: Signal<LinearRgba>,
+    underlay: f32,
+    material: Handle<LabelMaterial>,
+    underlay_material: Handle<LabelUnderlayMaterial>,
+}
+
+impl Reaction for LabelColorReaction {
+    fn react(&mut self, owner: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        let re = Rcx::new(world, owner, tracking);
+        let mut color = self.color.get(&re);
+
+        let mut materials = world.resource_mut::<Assets<LabelMaterial>>();
+        if let Some(material) = materials.get_mut(self.material.id()) {
+            material.color = color;
+        }
+
+        let mut underlay_materials = world.resource_mut::<Assets<LabelUnderlayMaterial>>();
+        if let Some(underlay_material) = underlay_materials.get_mut(self.underlay_material.id()) {
+            color.alpha *= self.underlay;
+            underlay_material.color = color;
+        }
+    }
+}
+
+/// Reactive effect which moves the label.
+struct LabelPositionReaction {
+    label: Entity,
+    position: Signal<Vec3>,
+}
+
+impl Reaction for LabelPositionReaction {
+    fn react(&mut self, owner: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        let re = Rcx::new(world, owner, tracking);
+        let position = self.position.get(&re);
+        if let Some(mut transform) = world.get_mut::<Transform>(self.label) {
+            transform.translation = position;
+        }
+    }
+}
+
+/// Turns each label to face the camera, and scales it to match the size of its texture.
+pub(crate) fn update_overlay_labels(
+    cameras: Query<(&Camera, &GlobalTransform), With<Camera3d>>,
+    mut labels: Query<(&LabelQuad, &mut Transform, Option<&Parent>)>,
+    parents: Query<&GlobalTransform>,
+    images: Res<Assets<Image>>,
+) {
+    let Some((_, camera_transform)) = cameras.iter().find(|(camera, _)| camera.is_active) else {
+        return;
+    };
+    let (_, camera_rotation, _) = camera_transform.to_scale_rotation_translation();
+    for (quad, mut transform, parent) in labels.iter_mut() {
+        let parent_rotation = parent
+            .and_then(|parent| parents.get(parent.get()).ok())
+            .map_or(Quat::IDENTITY, |parent| {
+                parent.to_scale_rotation_translation().1
+            });
+        let rotation = parent_rotation.inverse() * camera_rotation;
+        let scale = images
+            .get(quad.image.id())
+            .map_or(Vec2::ZERO, |image| image.size_f32() * quad.pixel_size)
+            .extend(1.);
+        if transform.rotation != rotation || transform.scale != scale {
+            transform.rotation = rotation;
+            transform.scale = scale;
+        }
+    }
+}
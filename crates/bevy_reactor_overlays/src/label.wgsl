@@ -0,0 +1,44 @@
+#import bevy_core_pipeline::tonemapping::tone_mapping
+#import bevy_pbr::{
+    mesh_view_bindings::view,
+    mesh_functions as mfns,
+    mesh_bindings::mesh,
+}
+
+@group(2) @binding(1)
+var<uniform> color: vec4<f32>;
+@group(2) @binding(2)
+var label_texture: texture_2d<f32>;
+@group(2) @binding(3)
+var label_sampler: sampler;
+
+struct Vertex {
+    @location(0) position: vec3<f32>,
+    @location(2) uv: vec2<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+};
+
+@vertex
+fn vertex(vertex: Vertex, @builtin(instance_index) instance_index: u32) -> VertexOutput {
+    var out: VertexOutput;
+    out.position = mfns::mesh_position_local_to_clip(
+        mfns::get_model_matrix(instance_index),
+        vec4<f32>(vertex.position, 1.0)
+    );
+    out.uv = vertex.uv;
+
+    return out;
+}
+
+@fragment
+fn fragment(
+    @builtin(front_facing) is_front: bool,
+    mesh: VertexOutput,
+) -> @location(0) vec4<f32> {
+    let texel = textureSample(label_texture, label_sampler, mesh.uv);
+    return tone_mapping(color * texel, view.color_grading);
+}
@@ -1,15 +1,23 @@
+mod label;
 mod mesh_builder;
 mod overlay;
 mod overlay_material;
 mod shape_builder;
 
-use bevy::{app::Plugin, asset::embedded_asset, pbr::MaterialPlugin};
+use bevy::{
+    app::{Plugin, PostUpdate},
+    asset::embedded_asset,
+    pbr::MaterialPlugin,
+    prelude::IntoSystemConfigs,
+    transform::TransformSystem,
+};
+pub use label::OverlayLabel;
 pub use overlay::Overlay;
 pub use shape_builder::{PolygonOptions, ShapeBuilder, StrokeMarker};
 
 use crate::overlay_material::OverlayMaterial;
 
-use self::overlay_material::UnderlayMaterial;
+use self::overlay_material::{LabelMaterial, LabelUnderlayMaterial, UnderlayMaterial};
 
 /// Plugin for the overlays module.
 pub struct OverlaysPlugin;
@@ -17,10 +25,17 @@ pub struct OverlaysPlugin;
 impl Plugin for OverlaysPlugin {
     fn build(&self, app: &mut bevy::app::App) {
         embedded_asset!(app, "overlay.wgsl");
+        embedded_asset!(app, "label.wgsl");
         app.add_plugins((
             MaterialPlugin::<OverlayMaterial>::default(),
             MaterialPlugin::<UnderlayMaterial>::default(),
-        ));
+            MaterialPlugin::<LabelMaterial>::default(),
+            MaterialPlugin::<LabelUnderlayMaterial>::default(),
+        ))
+        .add_systems(
+            PostUpdate,
+            label::update_overlay_labels.before(TransformSystem::TransformPropagate),
+        );
     }
 }
 
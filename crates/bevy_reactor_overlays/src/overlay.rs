@@ -52,8 +52,17 @@ where
     /// Whether the overlay is pickable.
     pickable: bool,
 
+    /// Invisible entity whose mesh is used for picking instead of the visible mesh.
+    pick_entity: Option<Entity>,
+
+    /// Mesh for the pick shape.
+    pick_mesh: Handle<Mesh>,
+
     /// Reactive drawing function
     draw: Box<dyn Fn(&Rcx, &mut SB) + Send + Sync>,
+
+    /// Reactive drawing function for the pick shape.
+    pick_draw: Option<Box<dyn Fn(&Rcx, &mut SB) + Send + Sync>>,
     // - blend_mode (signal)
     // - sides
 }
@@ -76,7 +85,10 @@ where
             transform: Signal::Constant(Transform::default()),
             underlay: 0.3,
             pickable: false,
+            pick_entity: None,
+            pick_mesh: Handle::default(),
             draw: Box::new(draw),
+            pick_draw: None,
         }
     }
 
@@ -94,7 +106,10 @@ where
             transform: Signal::Constant(Transform::default()),
             underlay: 0.3,
             pickable: false,
+            pick_entity: None,
+            pick_mesh: Handle::default(),
             draw: Box::new(draw),
+            pick_draw: None,
         }
     }
 
@@ -107,7 +122,8 @@ where
     /// Attach the children to the node. Note that each child view may produce multiple nodes,
     /// or none.
     fn attach_children(&self, world: &mut World) {
-        let flat = self.child_entities();
+        let mut flat = self.child_entities();
+        flat.extend(self.pick_entity);
         world
             .entity_mut(self.display.unwrap())
             .replace_children(&flat);
@@ -127,6 +143,16 @@ where
         self
     }
 
+    /// Use a separate, invisible shape for picking, instead of the visible shape. This is
+    /// typically a thicker version of the visible shape, so that thin lines and small handles
+    /// are easier to hit. Pointer events on the pick shape bubble up to the overlay, so
+    /// handlers and hover signals for the overlay's entity work as usual. Like the visible
+    /// shape, the pick shape is redrawn when its dependencies change.
+    pub fn with_pick_shape(mut self, draw: impl Fn(&Rcx, &mut SB) + Send + Sync + 'static) -> Self {
+        self.pick_draw = Some(Box::new(draw));
+        self
+    }
+
     /// Set the color for this overlay.
     pub fn with_color(mut self, color: impl Into<LinearRgba>) -> Self {
         self.color = Signal::Constant(color.into());
@@ -237,6 +263,30 @@ where
                     is_hoverable: true,
                 },
             ));
+        } else if self.pick_draw.is_some() {
+            // Only the pick shape should respond to the pointer.
+            world.entity_mut(display).insert(Pickable {
+                should_block_lower: false,
+                is_hoverable: false,
+            });
+        }
+
+        if self.pick_draw.is_some() {
+            let mesh = Mesh::new(SB::topology(), RenderAssetUsages::default());
+            self.pick_mesh = world.resource_mut::<Assets<Mesh>>().add(mesh);
+            let pick_entity = world
+                .spawn((
+                    Name::new(format!("{}::PickShape", self.debug_name)),
+                    self.pick_mesh.clone(),
+                    SpatialBundle::default(),
+                    RaycastPickable,
+                    Pickable {
+                        should_block_lower: true,
+                        is_hoverable: true,
+                    },
+                ))
+                .id();
+            self.pick_entity = Some(pick_entity);
         }
 
         // Build the overlay mesh the first time.
@@ -287,12 +337,27 @@ where
         let mut meshes = world.get_resource_mut::<Assets<Mesh>>().unwrap();
         let mesh = meshes.get_mut(self.mesh.id()).unwrap();
         builder.build(mesh);
+
+        // Rebuild the pick mesh.
+        if let Some(pick_draw) = self.pick_draw.as_ref() {
+            let re = Rcx::new(world, view_entity, tracking);
+            let mut builder = SB::default();
+            (pick_draw)(&re, &mut builder);
+            let mut meshes = world.get_resource_mut::<Assets<Mesh>>().unwrap();
+            let mesh = meshes.get_mut(self.pick_mesh.id()).unwrap();
+            builder.build(mesh);
+        }
     }
 
     fn raze(&mut self, view_entity: Entity, world: &mut World) {
         assert!(self.display.is_some());
         self.raze_children(world);
 
+        // Delete the pick shape.
+        if let Some(pick_entity) = self.pick_entity.take() {
+            world.entity_mut(pick_entity).despawn();
+        }
+
         // Delete the display node.
         world.entity_mut(self.display.unwrap()).remove_parent();
         world.entity_mut(self.display.unwrap()).despawn();
@@ -1,5 +1,5 @@
 use bevy::{
-    asset::Asset,
+    asset::{Asset, Handle},
     color::LinearRgba,
     pbr::{Material, MaterialPipeline, MaterialPipelineKey},
     reflect::TypePath,
@@ -10,6 +10,7 @@ use bevy::{
             AsBindGroup, CompareFunction, RenderPipelineDescriptor, ShaderRef,
             SpecializedMeshPipelineError,
         },
+        texture::Image,
     },
 };
 
@@ -82,3 +83,79 @@ impl Material for UnderlayMaterial {
         Ok(())
     }
 }
+
+/// Material for overlay labels, which are textured with their rendered text.
+#[derive(Debug, Clone, AsBindGroup, Asset, TypePath, Default)]
+pub struct LabelMaterial {
+    #[uniform(1)]
+    pub(crate) color: LinearRgba,
+    #[texture(2)]
+    #[sampler(3)]
+    pub(crate) texture: Handle<Image>,
+}
+
+#[allow(unused_variables)]
+impl Material for LabelMaterial {
+    fn vertex_shader() -> ShaderRef {
+        "embedded://bevy_reactor_overlays/label.wgsl".into()
+    }
+
+    fn fragment_shader() -> ShaderRef {
+        "embedded://bevy_reactor_overlays/label.wgsl".into()
+    }
+
+    fn alpha_mode(&self) -> AlphaMode {
+        AlphaMode::Blend
+    }
+
+    fn specialize(
+        pipeline: &MaterialPipeline<Self>,
+        descriptor: &mut RenderPipelineDescriptor,
+        layout: &MeshVertexBufferLayoutRef,
+        key: MaterialPipelineKey<Self>,
+    ) -> Result<(), SpecializedMeshPipelineError> {
+        if let Some(ref mut depth_stencil) = descriptor.depth_stencil {
+            depth_stencil.depth_write_enabled = false;
+            depth_stencil.depth_compare = CompareFunction::GreaterEqual;
+        }
+        Ok(())
+    }
+}
+
+/// Material for occluded overlay labels
+#[derive(Debug, Clone, AsBindGroup, Asset, TypePath, Default)]
+pub struct LabelUnderlayMaterial {
+    #[uniform(1)]
+    pub(crate) color: LinearRgba,
+    #[texture(2)]
+    #[sampler(3)]
+    pub(crate) texture: Handle<Image>,
+}
+
+#[allow(unused_variables)]
+impl Material for LabelUnderlayMaterial {
+    fn vertex_shader() -> ShaderRef {
+        "embedded://bevy_reactor_overlays/label.wgsl".into()
+    }
+
+    fn fragment_shader() -> ShaderRef {
+        "embedded://bevy_reactor_overlays/label.wgsl".into()
+    }
+
+    fn alpha_mode(&self) -> AlphaMode {
+        AlphaMode::Blend
+    }
+
+    fn specialize(
+        pipeline: &MaterialPipeline<Self>,
+        descriptor: &mut RenderPipelineDescriptor,
+        layout: &MeshVertexBufferLayoutRef,
+        key: MaterialPipelineKey<Self>,
+    ) -> Result<(), SpecializedMeshPipelineError> {
+        if let Some(ref mut depth_stencil) = descriptor.depth_stencil {
+            depth_stencil.depth_write_enabled = false;
+            depth_stencil.depth_compare = CompareFunction::Less;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,51 @@
+// This shader draws a blurred, tinted copy of the content behind a full-window node.
+#import bevy_ui::ui_vertex_output::UiVertexOutput
+
+@group(1) @binding(0)
+var<uniform> tint: vec4<f32>;
+
+@group(1) @binding(1)
+var<uniform> radius: f32;
+
+@group(1) @binding(2)
+var<uniform> intensity: f32;
+
+@group(1) @binding(3)
+var<uniform> has_backdrop: u32;
+
+@group(1) @binding(4)
+var backdrop_texture: texture_2d<f32>;
+
+@group(1) @binding(5)
+var backdrop_sampler: sampler;
+
+// Number of samples on each side of the center, in each direction.
+const TAPS: i32 = 3;
+
+@fragment
+fn fragment(in: UiVertexOutput) -> @location(0) vec4<f32> {
+    if has_backdrop == 0u {
+        return vec4<f32>(tint.rgb, tint.a * intensity);
+    }
+
+    // The node covers the window, so its pixel coordinates match the backdrop's.
+    let texture_size = vec2<f32>(textureDimensions(backdrop_texture));
+    let uv = in.uv * in.size / texture_size;
+    let step = radius * intensity / f32(TAPS) / texture_size;
+
+    // Gaussian-weighted grid of samples.
+    var sum = vec3<f32>(0.0);
+    var total = 0.0;
+    for (var y = -TAPS; y <= TAPS; y = y + 1) {
+        for (var x = -TAPS; x <= TAPS; x = x + 1) {
+            let offset = vec2<f32>(f32(x), f32(y));
+            let weight = exp(-dot(offset, offset) / f32(TAPS * TAPS));
+            sum = sum + textureSample(backdrop_texture, backdrop_sampler, uv + offset * step).rgb
+                * weight;
+            total = total + weight;
+        }
+    }
+
+    let color = mix(sum / total, tint.rgb, tint.a);
+    return vec4<f32>(color, intensity);
+}
@@ -1,3 +1,5 @@
+// This shader draws a grid of dots or lines, with optional major grid points. Grid levels
+// fade out as they become too dense on screen when zoomed out.
 #import bevy_ui::ui_vertex_output::UiVertexOutput
 
 @group(1) @binding(0)
@@ -6,9 +8,50 @@ var<uniform> color_bg: vec4<f32>;
 @group(1) @binding(1)
 var<uniform> color_fg: vec4<f32>;
 
+@group(1) @binding(2)
+var<uniform> color_major: vec4<f32>;
+
+@group(1) @binding(3)
+var<uniform> spacing: f32;
+
+@group(1) @binding(4)
+var<uniform> dot_size: f32;
+
+@group(1) @binding(5)
+var<uniform> major_every: u32;
+
+@group(1) @binding(6)
+var<uniform> zoom: f32;
+
+@group(1) @binding(7)
+var<uniform> offset: vec2<f32>;
+
+@group(1) @binding(8)
+var<uniform> lines: u32;
+
+// Grid levels are hidden when their points are closer than this many screen pixels, and fully
+// visible at twice this spacing.
+const MIN_SCREEN_SPACING: f32 = 4.0;
+
 @fragment
 fn fragment(in: UiVertexOutput) -> @location(0) vec4<f32> {
-    let size = vec2<f32>(in.size.x, in.size.y);
-    let cell = fract(in.uv * size /  16.) * 16.;
-    return select(color_bg, color_fg, cell.x <= 1.5 && cell.y <= 1.5);
+    // Position in grid space.
+    let pos = in.uv * in.size / zoom + offset;
+    var color = mix(color_bg, color_fg, grid_coverage(pos, spacing));
+    if major_every > 0u {
+        color = mix(color, color_major, grid_coverage(pos, spacing * f32(major_every)));
+    }
+    return color;
+}
+
+// Returns the coverage of a grid level with the given spacing at a point in grid space,
+// including the fade when the level is too dense.
+fn grid_coverage(pos: vec2<f32>, level_spacing: f32) -> f32 {
+    let screen_spacing = level_spacing * zoom;
+    let fade = clamp((screen_spacing - MIN_SCREEN_SPACING) / MIN_SCREEN_SPACING, 0.0, 1.0);
+    // Offset from the nearest grid point, in screen pixels.
+    let cell = (pos - level_spacing * round(pos / level_spacing)) * zoom;
+    let radius = dot_size * 0.5;
+    let dist = select(length(cell), min(abs(cell.x), abs(cell.y)), lines != 0u);
+    return smoothstep(radius + 0.5, radius - 0.5, dist) * fade;
 }
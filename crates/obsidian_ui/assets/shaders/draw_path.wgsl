@@ -4,63 +4,118 @@ const OP_MOVE_TO: u32 = 0u;
 const OP_LINE_TO: u32 = 1u;
 const OP_QUAD1: u32 = 2u;
 const OP_QUAD2: u32 = 3u;
+const OP_MASK: u32 = 0xffu;
+const FLAG_START: u32 = 0x100u;
+const FLAG_END: u32 = 0x200u;
+
+const CAP_ROUND: u32 = 0u;
+const CAP_BUTT: u32 = 1u;
 
 struct PathCommand {
     op: u32,
     pos: vec2<f32>,
+    distance: f32,
+    color: vec4<f32>,
 }
 
 @group(1) @binding(0)
-var<uniform> color: vec4<f32>;
+var<uniform> width: f32;
 
 @group(1) @binding(1)
-var<uniform> width: f32;
+var<uniform> dash: vec2<f32>;
 
 @group(1) @binding(2)
+var<uniform> cap: u32;
+
+@group(1) @binding(3)
 var<storage> commands: array<PathCommand>;
 
+// The point on the path closest to a fragment.
+struct Nearest {
+    dist_sq: f32,
+    // Distance along the sub-path.
+    along: f32,
+    color: vec4<f32>,
+}
+
 @fragment
 fn fragment(in: UiVertexOutput) -> @location(0) vec4<f32> {
     let pt = vec2<f32>(in.size.x, in.size.y) * in.uv;
-    let d = distance_to_path(pt);
-    let a = 1.0 - smoothstep(width * 0.5 - 0.3, width * 0.5 + 0.3, d);
-    return vec4<f32>(color.rgb, color.a * a);
+    let nearest = nearest_on_path(pt);
+    let d = sqrt(nearest.dist_sq);
+    var a = 1.0 - smoothstep(width * 0.5 - 0.3, width * 0.5 + 0.3, d);
+    if (dash.x > 0.0) {
+        let m = nearest.along % (dash.x + dash.y);
+        a = a * (1.0 - smoothstep(dash.x - 0.5, dash.x + 0.5, m));
+    }
+    return vec4<f32>(nearest.color.rgb, nearest.color.a * a);
 }
 
-fn distance_to_path(pt: vec2<f32>) -> f32 {
+fn nearest_on_path(pt: vec2<f32>) -> Nearest {
     var prev = vec2<f32>(0., 0.);
-    var dist: f32 = 10000000.0;
+    var prev_distance = 0.0;
+    var nearest = Nearest(10000000.0, 0.0, vec4<f32>(0.0));
     let n = arrayLength(&commands);
     for (var i = 0u; i < n; i = i + 1u) {
         let cmd = commands[i];
-        if (cmd.op == OP_MOVE_TO) {
+        let op = cmd.op & OP_MASK;
+        if (op == OP_MOVE_TO) {
             prev = cmd.pos;
-        } else if (cmd.op == OP_LINE_TO) {
+            prev_distance = cmd.distance;
+        } else if (op == OP_LINE_TO) {
             let next = cmd.pos;
-            dist = min(dist, distance_sq_to_line(pt, prev, next));
+            let hit = nearest_on_line(pt, prev, next);
+            nearest = closer(nearest, hit, cmd.op, prev_distance, cmd.distance, cmd.color);
             prev = next;
-        } else if (cmd.op == OP_QUAD1) {
+            prev_distance = cmd.distance;
+        } else if (op == OP_QUAD1) {
             let ctrl = cmd.pos;
-            let next = commands[i + 1].pos;
-            dist = min(dist, distance_sq_to_quadratic(pt, prev, ctrl, next));
+            let end = commands[i + 1];
+            let hit = nearest_on_quadratic(pt, prev, ctrl, end.pos);
+            nearest = closer(nearest, hit, cmd.op, cmd.distance, end.distance, cmd.color);
             i = i + 1u;
-            prev = next;
-        } else if (cmd.op == OP_QUAD2) {
+            prev = end.pos;
+            prev_distance = end.distance;
+        } else if (op == OP_QUAD2) {
             prev = cmd.pos;
+            prev_distance = cmd.distance;
         }
     }
-    return sqrt(dist);
+    return nearest;
+}
+
+// Returns `nearest`, or the point `hit` on a segment if it is closer. `hit` holds the squared
+// distance and the parameter along the segment, which is outside [0, 1] past its ends.
+fn closer(
+    nearest: Nearest,
+    hit: vec2<f32>,
+    op: u32,
+    start_distance: f32,
+    end_distance: f32,
+    color: vec4<f32>,
+) -> Nearest {
+    if (cap == CAP_BUTT) {
+        if ((hit.y <= 0.0 && (op & FLAG_START) != 0u) || (hit.y >= 1.0 && (op & FLAG_END) != 0u)) {
+            return nearest;
+        }
+    }
+    if (hit.x < nearest.dist_sq) {
+        let t = clamp(hit.y, 0.0, 1.0);
+        return Nearest(hit.x, mix(start_distance, end_distance, t), color);
+    }
+    return nearest;
 }
 
-fn distance_sq_to_line(pt: vec2<f32>, a: vec2<f32>, b: vec2<f32>) -> f32 {
+fn nearest_on_line(pt: vec2<f32>, a: vec2<f32>, b: vec2<f32>) -> vec2<f32> {
     let pa = pt - a;
     let ba = b - a;
-    let h = clamp(dot(pa, ba) / dot(ba, ba), 0.0, 1.0);
-    return dot2(pa - ba * h);
+    let h = dot(pa, ba) / max(dot(ba, ba), 0.000001);
+    return vec2<f32>(dot2(pa - ba * clamp(h, 0.0, 1.0)), h);
 }
 
-// From https://iquilezles.org/articles/distfunctions2d/
-fn distance_sq_to_quadratic(pos: vec2<f32>, A: vec2<f32>, B: vec2<f32>, C: vec2<f32>) -> f32 {
+// From https://iquilezles.org/articles/distfunctions2d/, extended to return the curve
+// parameter of the closest point along with the squared distance.
+fn nearest_on_quadratic(pos: vec2<f32>, A: vec2<f32>, B: vec2<f32>, C: vec2<f32>) -> vec2<f32> {
     let a = B - A;
     let b = A - 2.0 * B + C;
     let c = a * 2.0;
@@ -69,7 +124,7 @@ fn distance_sq_to_quadratic(pos: vec2<f32>, A: vec2<f32>, B: vec2<f32>, C: vec2<
     let kx = kk * dot(a, b);
     let ky = kk * (2.0 * dot(a, a)+dot(d, b)) / 3.0;
     let kz = kk * dot(d, a);
-    var res = 0.0;
+    var res = vec2<f32>(0.0);
     let p = ky - kx * kx;
     let p3 = p * p * p;
     let q = kx * (2.0 * kx * kx - 3.0 * ky) + kz;
@@ -79,15 +134,16 @@ fn distance_sq_to_quadratic(pos: vec2<f32>, A: vec2<f32>, B: vec2<f32>, C: vec2<
         let x = (vec2<f32>(h, -h) - q) / 2.0;
         let uv = sign(x) * pow(abs(x), vec2(1.0 / 3.0));
         let t = clamp(uv.x + uv.y - kx, 0.0, 1.0);
-        res = dot2(d + (c + b * t) * t);
+        res = vec2<f32>(dot2(d + (c + b * t) * t), t);
     } else {
         let z = sqrt(-p);
         let v = acos( q/(p * z * 2.0) ) / 3.0;
         let m = cos(v);
         let n = sin(v) * 1.732050808;
         let t = clamp(vec3<f32>(m + m,-n - m,n - m) * z - kx, vec3<f32>(0.0), vec3<f32>(1.0));
-        res = min(dot2(d + (c + b * t.x) * t.x),
-                  dot2(d + (c + b * t.y) * t.y));
+        let dx = dot2(d + (c + b * t.x) * t.x);
+        let dy = dot2(d + (c + b * t.y) * t.y);
+        res = select(vec2<f32>(dy, t.y), vec2<f32>(dx, t.x), dx < dy);
         // the third root cannot be the closest
         // res = min(res,dot2(d+(c+b*t.z)*t.z));
     }
@@ -7,31 +7,152 @@ var<uniform> num_color_stops: i32;
 @group(1) @binding(1)
 var<uniform> color_stops: array<vec4<f32>, 8>;
 
+@group(1) @binding(2)
+var<uniform> stop_positions: array<vec4<f32>, 2>;
+
 @group(1) @binding(3)
 var<uniform> cap_size: f32;
 
+@group(1) @binding(4)
+var<uniform> flags: u32;
+
+@group(1) @binding(5)
+var<uniform> exposure: f32;
+
+const FLAG_LINEAR: u32 = 1u;
+const FLAG_DISPLAY_P3: u32 = 2u;
+const FLAG_GAMUT_WARNING: u32 = 4u;
+const FLAG_VERTICAL: u32 = 8u;
+const FLAG_OKLAB: u32 = 16u;
+const FLAG_DITHER: u32 = 32u;
+
+// 4x4 Bayer matrix for ordered dithering, with values in [0, 16).
+const BAYER = array<f32, 16>(
+    0.0, 8.0, 2.0, 10.0,
+    12.0, 4.0, 14.0, 6.0,
+    3.0, 11.0, 1.0, 9.0,
+    15.0, 7.0, 13.0, 5.0,
+);
+
+// Linear sRGB to linear Display P3.
+const SRGB_TO_P3 = mat3x3<f32>(
+    vec3<f32>(0.8225, 0.0332, 0.0171),
+    vec3<f32>(0.1774, 0.9669, 0.0724),
+    vec3<f32>(0.0000, 0.0000, 0.9108),
+);
+
 @fragment
 fn fragment(in: UiVertexOutput) -> @location(0) vec4<f32> {
-    let t = (in.uv.x - 0.1) * 1.0 / 0.8 * f32(num_color_stops - 1);
-    let color_index_lo = clamp(i32(floor(t)), 0, num_color_stops - 1);
-    let color_index_hi = clamp(i32(ceil(t)), 0, num_color_stops - 1);
-    let color_lo = color_stops[color_index_lo];
-    let color_hi = color_stops[color_index_hi];
-    let color = mix(color_lo, color_hi, t - f32(color_index_lo));
+    let axis = select(in.uv.x, 1.0 - in.uv.y, (flags & FLAG_VERTICAL) != 0u);
+    let t = (axis - 0.1) * 1.0 / 0.8;
+    let color = gradient_color(t);
 
     let uv = (in.uv - vec2<f32>(0.5, 0.5)) * in.size / 8.;
     let check = select(0.0, 1.0, (fract(uv.x) < 0.5) != (fract(uv.y) < 0.5));
     let bg = mix(vec3<f32>(0.4, 0.4, 0.4), vec3<f32>(0.6, 0.6, 0.6), check);
-    let c = srgb_to_linear(mix(bg, color.rgb, color.w));
+
+    // Scene-referred linear color, with exposure applied.
+    var rgb: vec3<f32>;
+    if (flags & FLAG_LINEAR) != 0u {
+        rgb = color.rgb;
+    } else {
+        rgb = srgb_to_linear(color.rgb);
+    }
+    rgb = rgb * exp2(exposure);
+
+    // Convert to the display's primaries and clip, remembering whether clipping was needed.
+    // Display P3 values are written out as-is: converting them back to sRGB would clip them
+    // to the sRGB gamut again. The gray background is the same in both.
+    let wide = (flags & FLAG_DISPLAY_P3) != 0u;
+    let display = select(rgb, SRGB_TO_P3 * rgb, wide);
+    let out_of_gamut = any(display < vec3<f32>(-0.001)) || any(display > vec3<f32>(1.001));
+    var c = clamp(display, vec3<f32>(0.0), vec3<f32>(1.0));
+    c = mix(srgb_to_linear(bg), c, color.w);
+
+    // Diagonal stripes over out-of-gamut colors.
+    if out_of_gamut && (flags & FLAG_GAMUT_WARNING) != 0u {
+        let stripe = fract((in.uv.x * in.size.x + in.uv.y * in.size.y) / 6.0) < 0.5;
+        c = select(c, mix(c, vec3<f32>(0.0), 0.6), stripe);
+    }
+
+    // Ordered dithering of +/- half a code value in the 8-bit sRGB output.
+    if (flags & FLAG_DITHER) != 0u {
+        let pixel = vec2<u32>(in.position.xy) % 4u;
+        var bayer = BAYER;
+        let threshold = (bayer[pixel.y * 4u + pixel.x] + 0.5) / 16.0 - 0.5;
+        let encoded = linear_to_srgb(c) + vec3<f32>(threshold / 255.0);
+        c = srgb_to_linear(clamp(encoded, vec3<f32>(0.0), vec3<f32>(1.0)));
+    }
 
     let size = vec2<f32>(in.size.x, in.size.y);
-    let external_distance = sd_rounded_box((in.uv - 0.5) * size, size, vec4<f32>(size.y * 0.5));
+    let external_distance = sd_rounded_box((in.uv - 0.5) * size, size, vec4<f32>(min(size.x, size.y) * 0.5));
     let alpha = smoothstep(0.5, -0.5, external_distance);
 
     return vec4<f32>(c, alpha);
 }
 
-// Convert sRGB to linear color space because we interpolate in sRGB space.
+// Interpolate the color stops at position `t` along the gradient.
+fn gradient_color(t: f32) -> vec4<f32> {
+    var lo = 0;
+    for (var i = 1; i < num_color_stops - 1; i = i + 1) {
+        if stop_position(i) <= t {
+            lo = i;
+        }
+    }
+    let hi = min(lo + 1, num_color_stops - 1);
+    let span = stop_position(hi) - stop_position(lo);
+    let f = select(0.0, clamp((t - stop_position(lo)) / span, 0.0, 1.0), span > 0.0);
+    let color_lo = color_stops[lo];
+    let color_hi = color_stops[hi];
+    if (flags & FLAG_OKLAB) != 0u {
+        let lab = mix(linear_to_oklab(color_lo.rgb), linear_to_oklab(color_hi.rgb), f);
+        return vec4<f32>(oklab_to_linear(lab), mix(color_lo.a, color_hi.a, f));
+    }
+    return mix(color_lo, color_hi, f);
+}
+
+fn stop_position(index: i32) -> f32 {
+    return stop_positions[index / 4][index % 4];
+}
+
+// From https://bottosson.github.io/posts/oklab/. The cube root is extended to negative
+// values so that wide-gamut and HDR colors round-trip.
+fn linear_to_oklab(c: vec3<f32>) -> vec3<f32> {
+    let lms = vec3<f32>(
+        0.4122214708 * c.r + 0.5363325363 * c.g + 0.0514459929 * c.b,
+        0.2119034982 * c.r + 0.6806995451 * c.g + 0.1073969566 * c.b,
+        0.0883024619 * c.r + 0.2817188376 * c.g + 0.6299787005 * c.b,
+    );
+    let l = sign(lms) * pow(abs(lms), vec3<f32>(1.0 / 3.0));
+    return vec3<f32>(
+        0.2104542553 * l.x + 0.7936177850 * l.y - 0.0040720468 * l.z,
+        1.9779984951 * l.x - 2.4285922050 * l.y + 0.4505937099 * l.z,
+        0.0259040371 * l.x + 0.7827717662 * l.y - 0.8086757660 * l.z,
+    );
+}
+
+fn oklab_to_linear(lab: vec3<f32>) -> vec3<f32> {
+    let l = vec3<f32>(
+        lab.x + 0.3963377774 * lab.y + 0.2158037573 * lab.z,
+        lab.x - 0.1055613458 * lab.y - 0.0638541728 * lab.z,
+        lab.x - 0.0894841775 * lab.y - 1.2914855480 * lab.z,
+    );
+    let lms = l * l * l;
+    return vec3<f32>(
+        4.0767416621 * lms.x - 3.3077115913 * lms.y + 0.2309699292 * lms.z,
+        -1.2684380046 * lms.x + 2.6097574011 * lms.y - 0.3413193965 * lms.z,
+        -0.0041960863 * lms.x - 0.7034186147 * lms.y + 1.7076147010 * lms.z,
+    );
+}
+
+// Convert linear color to sRGB encoding.
+fn linear_to_srgb(linear: vec3<f32>) -> vec3<f32> {
+    let low = linear * 12.92;
+    let high = 1.055 * pow(max(linear, vec3<f32>(0.0)), vec3<f32>(1.0 / 2.4)) - 0.055;
+    return mix(low, high, step(vec3<f32>(0.0031308), linear));
+}
+
+// Convert sRGB to linear color space, for gradients which interpolate in sRGB space.
 fn srgb_to_linear(srgb: vec3<f32>) -> vec3<f32> {
     let a = 0.055;
     let srgbLow = srgb / 12.92;
@@ -1,4 +1,5 @@
-// This shader draws a two-toned rounded rect for a slider widget.
+// This shader draws a two-toned rounded rect for a slider widget. The range between `start`
+// and `value` is drawn in `color_hi`.
 #import bevy_ui::ui_vertex_output::UiVertexOutput
 
 @group(1) @binding(0)
@@ -13,11 +14,19 @@ var<uniform> value: f32;
 @group(1) @binding(3)
 var<uniform> radius: vec4<f32>;
 
+@group(1) @binding(4)
+var<uniform> start: f32;
+
+@group(1) @binding(5)
+var<uniform> vertical: u32;
+
 @fragment
 fn fragment(in: UiVertexOutput) -> @location(0) vec4<f32> {
     let uv = in.uv - 0.5;
     let size = vec2<f32>(in.size.x, in.size.y);
-    let color = select(color_lo, color_hi, in.uv.x <= value);
+    // Position along the slider axis; vertical sliders increase from bottom to top.
+    let t = select(in.uv.x, 1.0 - in.uv.y, vertical != 0u);
+    let color = select(color_lo, color_hi, t >= start && t <= value);
     let external_distance = sd_rounded_box((in.uv - 0.5) * size, size, vec4<f32>(radius));
     let alpha = smoothstep(0.5, -0.5, external_distance);
 
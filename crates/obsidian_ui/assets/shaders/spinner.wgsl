@@ -0,0 +1,32 @@
+// This shader draws a rotating arc, used to indicate indeterminate progress. The rotation is
+// driven by the global time, so no per-frame updates are needed.
+#import bevy_ui::ui_vertex_output::UiVertexOutput
+#import bevy_render::globals::Globals
+
+@group(0) @binding(1)
+var<uniform> globals: Globals;
+
+@group(1) @binding(0)
+var<uniform> color: vec4<f32>;
+
+const PI: f32 = 3.14159265;
+
+@fragment
+fn fragment(in: UiVertexOutput) -> @location(0) vec4<f32> {
+    let size = min(in.size.x, in.size.y);
+    let p = (in.uv - vec2<f32>(0.5, 0.5)) * in.size;
+    let thickness = max(size * 0.12, 1.5);
+    let outer = size * 0.5;
+    let inner = outer - thickness;
+
+    // Distance to the ring.
+    let d = length(p);
+    let ring = smoothstep(outer + 0.5, outer - 0.5, d) * smoothstep(inner - 0.5, inner + 0.5, d);
+
+    // Angle around the ring, rotating once per second; the arc covers three quarters of the
+    // ring and fades towards its tail.
+    let angle = fract((atan2(p.y, p.x) / (2.0 * PI)) - globals.time);
+    let arc = smoothstep(0.0, 0.75, angle) * step(angle, 0.75);
+
+    return vec4<f32>(color.rgb, color.a * ring * arc);
+}
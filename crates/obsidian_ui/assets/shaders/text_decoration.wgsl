@@ -0,0 +1,32 @@
+// This shader draws the caret and selection highlight for a text input. Rectangles are given
+// in pixels relative to the top-left corner of the node.
+#import bevy_ui::ui_vertex_output::UiVertexOutput
+
+@group(1) @binding(0)
+var<uniform> selection: vec4<f32>;
+
+@group(1) @binding(1)
+var<uniform> selection_color: vec4<f32>;
+
+@group(1) @binding(2)
+var<uniform> caret: vec4<f32>;
+
+@group(1) @binding(3)
+var<uniform> caret_color: vec4<f32>;
+
+@fragment
+fn fragment(in: UiVertexOutput) -> @location(0) vec4<f32> {
+    let point = in.uv * in.size;
+    if in_rect(point, caret) {
+        return caret_color;
+    }
+    if in_rect(point, selection) {
+        return selection_color;
+    }
+    return vec4<f32>(0.0);
+}
+
+// Returns true if the point lies within the rectangle (min x, min y, max x, max y).
+fn in_rect(point: vec2<f32>, rect: vec4<f32>) -> bool {
+    return point.x >= rect.x && point.x < rect.z && point.y >= rect.y && point.y < rect.w;
+}
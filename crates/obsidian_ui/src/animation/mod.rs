@@ -5,6 +5,9 @@ use bevy::{
     math::{cubic_splines::CubicSegment, Vec2},
     ui::{self, BackgroundColor, BorderColor, Style},
 };
+use bevy_reactor::{AccessibilityPreferences, Opacity};
+
+use crate::backdrop::BackdropIntensity;
 
 // mod transition;
 
@@ -137,6 +140,36 @@ impl AnimatableProperty for AnimatedTranslation {
     }
 }
 
+/// Animated strength of a backdrop blur.
+pub struct AnimatedBackdropIntensity;
+impl AnimatableProperty for AnimatedBackdropIntensity {
+    type ValueType = f32;
+    type ComponentType = BackdropIntensity;
+
+    fn current(component: &Self::ComponentType) -> Self::ValueType {
+        component.0
+    }
+
+    fn update(component: &mut Self::ComponentType, t: f32, origin: f32, target: f32) {
+        component.0 = origin.lerp(target, t);
+    }
+}
+
+/// Animated opacity of a UI node and its descendants, for fading views in and out.
+pub struct AnimatedOpacity;
+impl AnimatableProperty for AnimatedOpacity {
+    type ValueType = f32;
+    type ComponentType = Opacity;
+
+    fn current(component: &Self::ComponentType) -> Self::ValueType {
+        component.0
+    }
+
+    fn update(component: &mut Self::ComponentType, t: f32, origin: f32, target: f32) {
+        component.0 = origin.lerp(target, t);
+    }
+}
+
 /// ECS component that animates a visual property of a UI node.
 #[derive(Component)]
 pub struct AnimatedTransition<T>
@@ -220,9 +253,16 @@ where
         mut commands: Commands,
         mut query: Query<(Entity, &mut AnimatedTransition<T>, &mut T::ComponentType)>,
         time: Res<Time>,
+        preferences: Option<Res<AccessibilityPreferences>>,
     ) {
+        let reduced_motion = preferences.map_or(false, |p| p.reduced_motion);
         for (entity, mut transition, mut cmp) in query.iter_mut() {
-            transition.advance(&mut cmp, time.delta_seconds());
+            // With reduced motion, skip straight to the end of the transition.
+            let step = match reduced_motion {
+                true => transition.delay + transition.duration,
+                false => time.delta_seconds(),
+            };
+            transition.advance(&mut cmp, step);
             if transition.clock >= transition.delay + transition.duration {
                 commands.entity(entity).remove::<AnimatedTransition<T>>();
             }
@@ -244,6 +284,8 @@ impl Plugin for AnimatedTransitionPlugin {
                 AnimatedTransition::<AnimatedPxHeight>::run_animations,
                 AnimatedTransition::<AnimatedScale>::run_animations,
                 AnimatedTransition::<AnimatedTranslation>::run_animations,
+                AnimatedTransition::<AnimatedBackdropIntensity>::run_animations,
+                AnimatedTransition::<AnimatedOpacity>::run_animations,
             ),
         );
     }
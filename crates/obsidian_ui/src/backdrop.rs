@@ -0,0 +1,150 @@
+use bevy::{
+    color::{Alpha, LinearRgba},
+    prelude::*,
+    ui,
+};
+use bevy_mod_picking::prelude::*;
+use bevy_reactor::*;
+
+use crate::{
+    animation::{AnimatedBackdropIntensity, AnimatedTransition},
+    colors,
+    materials::BackdropBlurMaterial,
+};
+
+/// Resource holding an image of the content behind the UI, which [`BackdropBlur`] blurs. This
+/// is typically the render target of a [`Compositor`] (see [`Compositor::with_image`]) or of a
+/// camera which renders the scene to an image. The image should be the size of the window, in
+/// logical pixels. Without an image, backdrops are only tinted.
+#[derive(Resource, Default, Clone)]
+pub struct BackdropImage(pub Option<Handle<Image>>);
+
+/// Component which holds the current strength of a [`BackdropBlur`], from 0 to 1. This is
+/// animated towards the value of the `intensity` signal.
+#[derive(Component, Clone, Copy, Default, Debug, PartialEq)]
+pub struct BackdropIntensity(pub f32);
+
+fn style_backdrop(ss: &mut StyleBuilder) {
+    ss.position(ui::PositionType::Absolute)
+        .left(0)
+        .top(0)
+        .right(0)
+        .bottom(0);
+}
+
+/// A frosted-glass backdrop which blurs and tints the content behind it, for use behind dialogs
+/// and menus. It should cover the whole window, and ignores pointer events, so it can be placed
+/// as the first child of a modal barrier.
+#[derive(Clone)]
+pub struct BackdropBlur {
+    /// Strength of the effect, from 0 (invisible) to 1. Changes are animated over
+    /// `duration`, so this can follow the open state of a dialog.
+    pub intensity: Signal<f32>,
+
+    /// Duration of the transition when `intensity` changes, in seconds.
+    pub duration: f32,
+
+    /// Blur radius in pixels, at full intensity.
+    pub radius: f32,
+
+    /// Color blended over the blurred content. The alpha channel controls how much of the tint
+    /// is mixed in.
+    pub tint: Color,
+}
+
+impl Default for BackdropBlur {
+    fn default() -> Self {
+        Self {
+            intensity: Signal::Constant(1.),
+            duration: 0.,
+            radius: 8.,
+            tint: colors::U2.with_alpha(0.5).into(),
+        }
+    }
+}
+
+impl BackdropBlur {
+    /// Create a new backdrop blur.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the strength of the effect.
+    pub fn intensity(mut self, intensity: impl IntoSignal<f32>) -> Self {
+        self.intensity = intensity.into_signal();
+        self
+    }
+
+    /// Set the duration of the transition when the intensity changes.
+    pub fn duration(mut self, duration: f32) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    /// Set the blur radius.
+    pub fn radius(mut self, radius: f32) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    /// Set the tint color.
+    pub fn tint(mut self, tint: impl Into<Color>) -> Self {
+        self.tint = tint.into();
+        self
+    }
+}
+
+impl ViewTemplate for BackdropBlur {
+    fn create(&self, cx: &mut Cx) -> impl IntoView {
+        let intensity = self.intensity;
+        let duration = self.duration;
+        let backdrop = cx.world().resource::<BackdropImage>().0.clone();
+        let material = cx
+            .world_mut()
+            .get_resource_mut::<Assets<BackdropBlurMaterial>>()
+            .unwrap()
+            .add(BackdropBlurMaterial {
+                tint: LinearRgba::from(self.tint).to_vec4(),
+                radius: self.radius,
+                intensity: 0.,
+                has_backdrop: backdrop.is_some() as u32,
+                backdrop: backdrop.unwrap_or_default(),
+            });
+
+        Element::<MaterialNodeBundle<BackdropBlurMaterial>>::new()
+            .named("BackdropBlur")
+            .style(style_backdrop)
+            .insert((material, BackdropIntensity(0.), Pickable::IGNORE))
+            .create_effect(move |cx, ent| {
+                let intensity = intensity.get(cx).clamp(0., 1.);
+                let mut entt = cx.world_mut().entity_mut(ent);
+                AnimatedTransition::<AnimatedBackdropIntensity>::start(
+                    &mut entt, intensity, duration,
+                );
+            })
+    }
+}
+
+/// Copy the animated intensity of each backdrop, and the current backdrop image, into the
+/// backdrop's material.
+pub(crate) fn update_backdrop_materials(
+    backdrop: Res<BackdropImage>,
+    query: Query<(Ref<BackdropIntensity>, &Handle<BackdropBlurMaterial>)>,
+    mut materials: ResMut<Assets<BackdropBlurMaterial>>,
+) {
+    let backdrop_changed = backdrop.is_changed();
+    for (intensity, handle) in query.iter() {
+        if !intensity.is_changed() && !backdrop_changed {
+            continue;
+        }
+        let Some(material) = materials.get_mut(handle) else {
+            continue;
+        };
+        material.intensity = intensity.0;
+        if backdrop_changed {
+            let image = backdrop.0.clone();
+            material.has_backdrop = image.is_some() as u32;
+            material.backdrop = image.unwrap_or_default();
+        }
+    }
+}
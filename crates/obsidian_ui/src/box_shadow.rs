@@ -0,0 +1,277 @@
+use bevy::{color::LinearRgba, prelude::*, ui, window::PrimaryWindow};
+use bevy_mod_picking::prelude::*;
+use bevy_reactor::{BoxShadow, Underlay};
+
+use crate::materials::BoxShadowMaterial;
+
+/// Component on a node with a [`BoxShadow`], which holds the entity that draws the shadow.
+#[derive(Component)]
+pub(crate) struct BoxShadowNode(Entity);
+
+/// Component on a shadow node, which holds the entity that casts the shadow.
+#[derive(Component)]
+pub(crate) struct ShadowOf(Entity);
+
+/// Resolve a length in pixels. Percentages are relative to `basis`; `auto` resolves to zero.
+fn resolve(val: ui::Val, basis: f32, viewport: Vec2) -> f32 {
+    val.resolve(basis, viewport).unwrap_or(0.)
+}
+
+/// Position the shadow relative to the padding box of the shadowed node, which is what
+/// absolute positioning is relative to. Border widths in percent are relative to the width
+/// of the shadowed node's parent, as in layout.
+fn shadow_style(shadow: &BoxShadow, style: &Style, parent_width: f32, viewport: Vec2) -> Style {
+    let extent = shadow.blur;
+    let border = |val: ui::Val| resolve(val, parent_width, viewport);
+    Style {
+        position_type: ui::PositionType::Absolute,
+        left: ui::Val::Px(shadow.offset.x - extent - border(style.border.left)),
+        right: ui::Val::Px(-shadow.offset.x - extent - border(style.border.right)),
+        top: ui::Val::Px(shadow.offset.y - extent - border(style.border.top)),
+        bottom: ui::Val::Px(-shadow.offset.y - extent - border(style.border.bottom)),
+        ..default()
+    }
+}
+
+/// Resolve the corner radii of a node of the given size, in the order used by the shaders:
+/// top left, top right, bottom right, bottom left. Radii in percent are relative to the
+/// shorter side of the node, and all radii are limited to half of it, the same as when the
+/// node itself is drawn.
+pub(crate) fn resolve_corner_radii(
+    radius: Option<&BorderRadius>,
+    size: Vec2,
+    viewport: Vec2,
+) -> Vec4 {
+    let min_side = size.min_element();
+    let corner = |val: ui::Val| resolve(val, min_side, viewport).clamp(0., min_side * 0.5);
+    radius.map_or(Vec4::ZERO, |radius| {
+        Vec4::new(
+            corner(radius.top_left),
+            corner(radius.top_right),
+            corner(radius.bottom_right),
+            corner(radius.bottom_left),
+        )
+    })
+}
+
+fn shadow_material(
+    shadow: &BoxShadow,
+    radius: Option<&BorderRadius>,
+    size: Vec2,
+    viewport: Vec2,
+) -> BoxShadowMaterial {
+    BoxShadowMaterial {
+        color: LinearRgba::from(shadow.color).to_vec4(),
+        radius: resolve_corner_radii(radius, size, viewport),
+        offset: shadow.offset,
+        blur: shadow.blur,
+    }
+}
+
+/// Creates and updates the nodes which draw [`BoxShadow`]s. Bevy UI draws children on top of
+/// their parent, so the shadow is the first child of the shadowed node, and only paints the
+/// area outside of it. The shadow is an [`Underlay`], so elements keep it in place when their
+/// children change. Percentages are resolved using the sizes from the previous layout.
+#[allow(clippy::type_complexity)]
+pub(crate) fn update_box_shadows(
+    mut commands: Commands,
+    mut materials: ResMut<Assets<BoxShadowMaterial>>,
+    targets: Query<
+        (
+            Entity,
+            &BoxShadow,
+            &Style,
+            Option<&Node>,
+            Option<&Parent>,
+            Option<&BorderRadius>,
+            Option<&Children>,
+            Option<&BoxShadowNode>,
+        ),
+        Or<(
+            Changed<BoxShadow>,
+            Changed<Style>,
+            Changed<Node>,
+            Changed<BorderRadius>,
+            Changed<Children>,
+        )>,
+    >,
+    nodes: Query<&Node>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut shadows: Query<(&Handle<BoxShadowMaterial>, &mut Style), Without<BoxShadow>>,
+    shadow_nodes: Query<&BoxShadowNode>,
+    orphans: Query<(Entity, &ShadowOf), Without<Parent>>,
+    mut removed: RemovedComponents<BoxShadow>,
+) {
+    for entity in removed.read() {
+        if let Ok(BoxShadowNode(shadow)) = shadow_nodes.get(entity) {
+            commands.entity(*shadow).despawn_recursive();
+            commands.entity(entity).remove::<BoxShadowNode>();
+        }
+    }
+
+    let viewport = windows
+        .get_single()
+        .map_or(Vec2::ZERO, |window| window.size());
+    for (entity, shadow, style, node, parent, radius, children, shadow_node) in targets.iter() {
+        let size = node.map_or(Vec2::ZERO, |node| node.size());
+        let parent_width = parent
+            .and_then(|parent| nodes.get(parent.get()).ok())
+            .map_or(viewport.x, |parent| parent.size().x);
+        let shadow_entity = match shadow_node {
+            Some(BoxShadowNode(shadow_entity)) => {
+                if let Ok((material, mut shadow_node_style)) = shadows.get_mut(*shadow_entity) {
+                    if let Some(material) = materials.get_mut(material) {
+                        *material = shadow_material(shadow, radius, size, viewport);
+                    }
+                    *shadow_node_style = shadow_style(shadow, style, parent_width, viewport);
+                }
+                *shadow_entity
+            }
+            None => {
+                let shadow_entity = commands
+                    .spawn((
+                        MaterialNodeBundle {
+                            style: shadow_style(shadow, style, parent_width, viewport),
+                            material: materials
+                                .add(shadow_material(shadow, radius, size, viewport)),
+                            ..default()
+                        },
+                        Name::new("BoxShadow"),
+                        ShadowOf(entity),
+                        Underlay,
+                        Pickable::IGNORE,
+                    ))
+                    .id();
+                commands.entity(entity).insert(BoxShadowNode(shadow_entity));
+                shadow_entity
+            }
+        };
+
+        // Keep the shadow first, so it is drawn below the other children. Elements keep
+        // underlays in place, but other code which manages the children may have detached it.
+        if children.and_then(|children| children.first()) != Some(&shadow_entity) {
+            commands.entity(entity).insert_children(0, &[shadow_entity]);
+        }
+    }
+
+    // A shadow which was detached from a node with no other children wasn't caught above,
+    // since the node's `Children` was removed rather than changed.
+    for (shadow, ShadowOf(entity)) in orphans.iter() {
+        match shadow_nodes.get(*entity) {
+            Ok(BoxShadowNode(node)) if *node == shadow => {
+                commands.entity(*entity).insert_children(0, &[shadow]);
+            }
+            _ => commands.entity(shadow).despawn_recursive(),
+        }
+    }
+}
+
+/// Give each shadow the clip of the node which casts it, rather than the clip of the node's
+/// own contents, so that a node which clips its overflow doesn't also clip away its shadow.
+/// This runs after Bevy computes the clips for the frame.
+pub(crate) fn unclip_box_shadows(
+    mut commands: Commands,
+    shadows: Query<(Entity, &ShadowOf, Option<&CalculatedClip>)>,
+    clips: Query<&CalculatedClip, Without<ShadowOf>>,
+) {
+    for (shadow, ShadowOf(entity), shadow_clip) in shadows.iter() {
+        match (clips.get(*entity).ok(), shadow_clip) {
+            (Some(clip), Some(shadow_clip)) if clip.clip == shadow_clip.clip => {}
+            (Some(clip), _) => {
+                commands
+                    .entity(shadow)
+                    .insert(CalculatedClip { clip: clip.clip });
+            }
+            (None, Some(_)) => {
+                commands.entity(shadow).remove::<CalculatedClip>();
+            }
+            (None, None) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::RunSystemOnce;
+
+    use super::*;
+
+    const SHADOW: BoxShadow = BoxShadow {
+        offset: Vec2::new(1., 3.),
+        blur: 4.,
+        color: Color::BLACK,
+    };
+
+    #[test]
+    fn test_resolve_lengths() {
+        let style = Style {
+            border: UiRect::new(
+                ui::Val::Percent(10.),
+                ui::Val::Px(2.),
+                ui::Val::Auto,
+                ui::Val::Vw(1.),
+            ),
+            ..default()
+        };
+        let shadow_style = shadow_style(&SHADOW, &style, 200., Vec2::new(500., 300.));
+        assert_eq!(shadow_style.left, ui::Val::Px(1. - 4. - 20.));
+        assert_eq!(shadow_style.right, ui::Val::Px(-1. - 4. - 2.));
+        assert_eq!(shadow_style.top, ui::Val::Px(3. - 4.));
+        assert_eq!(shadow_style.bottom, ui::Val::Px(-3. - 4. - 5.));
+
+        let radius = BorderRadius::new(
+            ui::Val::Percent(25.),
+            ui::Val::Px(6.),
+            ui::Val::Px(100.),
+            ui::Val::Auto,
+        );
+        let material = shadow_material(&SHADOW, Some(&radius), Vec2::new(40., 100.), Vec2::ZERO);
+        assert_eq!(material.radius, Vec4::new(10., 6., 20., 0.));
+    }
+
+    #[test]
+    fn test_update_box_shadows() {
+        let mut world = World::new();
+        world.init_resource::<Assets<BoxShadowMaterial>>();
+        let target = world.spawn((NodeBundle::default(), SHADOW)).id();
+        let child = world.spawn(NodeBundle::default()).set_parent(target).id();
+
+        world.run_system_once(update_box_shadows);
+        let shadow = world.get::<BoxShadowNode>(target).unwrap().0;
+        assert!(world.get::<Underlay>(shadow).is_some());
+        assert_eq!(
+            world.get::<Children>(target).unwrap().to_vec(),
+            vec![shadow, child]
+        );
+
+        // Removing the shadow despawns the node which draws it.
+        world.entity_mut(target).remove::<BoxShadow>();
+        world.run_system_once(update_box_shadows);
+        assert!(world.get_entity(shadow).is_none());
+        assert!(world.get::<BoxShadowNode>(target).is_none());
+    }
+
+    #[test]
+    fn test_unclip_box_shadows() {
+        let mut world = World::new();
+        let clip = Rect::new(0., 0., 100., 100.);
+        let target = world.spawn(CalculatedClip { clip }).id();
+        let shadow = world
+            .spawn((
+                ShadowOf(target),
+                CalculatedClip {
+                    clip: Rect::new(10., 10., 20., 20.),
+                },
+            ))
+            .id();
+
+        // The shadow takes the clip of the node which casts it.
+        world.run_system_once(unclip_box_shadows);
+        assert_eq!(world.get::<CalculatedClip>(shadow).unwrap().clip, clip);
+
+        // The shadow is unclipped when the node is.
+        world.entity_mut(target).remove::<CalculatedClip>();
+        world.run_system_once(unclip_box_shadows);
+        assert!(world.get::<CalculatedClip>(shadow).is_none());
+    }
+}
@@ -0,0 +1,184 @@
+use std::collections::HashMap;
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::Mutex;
+
+use bevy::{
+    a11y::Focus,
+    app::{App, Plugin, Update},
+    ecs::{
+        entity::Entity,
+        event::{Event, EventReader, EventWriter},
+        system::{Res, Resource},
+    },
+    input::{
+        keyboard::{KeyCode, KeyboardInput},
+        ButtonInput, ButtonState,
+    },
+    log::*,
+};
+use bevy_mod_picking::prelude::{EntityEvent, EventListenerPlugin};
+
+/// Bubbling event sent to the focused widget when the user presses the copy shortcut
+/// (Ctrl+C, or Cmd+C on macOS).
+#[derive(Clone, Event, EntityEvent)]
+#[can_bubble]
+pub struct CopyEvent {
+    /// The target of the event
+    #[target]
+    pub target: Entity,
+}
+
+/// Bubbling event sent to the focused widget when the user presses the paste shortcut
+/// (Ctrl+V, or Cmd+V on macOS).
+#[derive(Clone, Event, EntityEvent)]
+#[can_bubble]
+pub struct PasteEvent {
+    /// The target of the event
+    #[target]
+    pub target: Entity,
+}
+
+/// Resource which provides access to the clipboard.
+///
+/// Text is read from and written to the platform clipboard, where one is available. Payloads
+/// with custom MIME types, such as serialized graph nodes, are kept in-process alongside a
+/// plain-text representation which is placed on the platform clipboard; they are discarded
+/// as soon as another application replaces the clipboard contents.
+#[derive(Resource)]
+pub struct Clipboard {
+    /// Connection to the platform clipboard, opened once when the resource is created. On
+    /// some platforms clipboard contents are only served while this is alive.
+    #[cfg(not(target_arch = "wasm32"))]
+    system: Option<Mutex<arboard::Clipboard>>,
+
+    /// The text most recently written by this application.
+    text: Option<String>,
+
+    /// Custom payloads written along with `text`, keyed by MIME type.
+    data: HashMap<String, Vec<u8>>,
+}
+
+impl Default for Clipboard {
+    fn default() -> Self {
+        Self {
+            #[cfg(not(target_arch = "wasm32"))]
+            system: arboard::Clipboard::new()
+                .map_err(|err| warn!("Clipboard unavailable: {}", err))
+                .ok()
+                .map(Mutex::new),
+            text: None,
+            data: HashMap::new(),
+        }
+    }
+}
+
+impl Clipboard {
+    /// Place text on the clipboard, replacing any previous contents.
+    pub fn set_text(&mut self, text: impl Into<String>) {
+        self.data.clear();
+        self.write_text(text.into());
+    }
+
+    /// Returns the text on the clipboard, if any.
+    pub fn text(&self) -> Option<String> {
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(system) = &self.system {
+            return system.lock().unwrap().get_text().ok();
+        }
+        self.text.clone()
+    }
+
+    /// Place a payload with a custom MIME type on the clipboard, replacing any previous
+    /// contents. `text` is the plain-text representation seen by other applications.
+    pub fn set_data(&mut self, mime: impl Into<String>, data: Vec<u8>, text: impl Into<String>) {
+        self.data.clear();
+        self.data.insert(mime.into(), data);
+        self.write_text(text.into());
+    }
+
+    /// Add a payload with another MIME type to the current clipboard contents, for example
+    /// to offer the same content in several formats.
+    pub fn add_data(&mut self, mime: impl Into<String>, data: Vec<u8>) {
+        if self.text.is_some() {
+            self.data.insert(mime.into(), data);
+        }
+    }
+
+    /// Returns the payload with the given MIME type, if the clipboard still holds the content
+    /// it was written with.
+    pub fn data(&self, mime: &str) -> Option<Vec<u8>> {
+        let payload = self.data.get(mime)?;
+        if self.text() != self.text {
+            return None;
+        }
+        Some(payload.clone())
+    }
+
+    /// True if the clipboard holds a payload with the given MIME type.
+    pub fn has_data(&self, mime: &str) -> bool {
+        self.data.contains_key(mime) && self.text() == self.text
+    }
+
+    fn write_text(&mut self, text: String) {
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(system) = &self.system {
+            if let Err(err) = system.lock().unwrap().set_text(text.clone()) {
+                warn!("Failed to write to clipboard: {}", err);
+            }
+        }
+        self.text = Some(text);
+    }
+}
+
+/// True if the platform's shortcut modifier is held: Ctrl, or Cmd on macOS. Ctrl+Alt is
+/// excluded since that is how AltGr is reported on some platforms.
+pub(crate) fn shortcut_modifier(keys: &ButtonInput<KeyCode>) -> bool {
+    keys.any_pressed([
+        KeyCode::ControlLeft,
+        KeyCode::ControlRight,
+        KeyCode::SuperLeft,
+        KeyCode::SuperRight,
+    ]) && !keys.any_pressed([KeyCode::AltLeft, KeyCode::AltRight])
+}
+
+fn handle_clipboard_shortcuts(
+    mut key_events: EventReader<KeyboardInput>,
+    keys: Res<ButtonInput<KeyCode>>,
+    focus: Res<Focus>,
+    mut copy_writer: EventWriter<CopyEvent>,
+    mut paste_writer: EventWriter<PasteEvent>,
+) {
+    for ev in key_events.read() {
+        let Some(target) = focus.0 else {
+            continue;
+        };
+        if ev.state != ButtonState::Pressed || !shortcut_modifier(&keys) {
+            continue;
+        }
+        match ev.key_code {
+            KeyCode::KeyC | KeyCode::Copy => {
+                copy_writer.send(CopyEvent { target });
+            }
+            KeyCode::KeyV | KeyCode::Paste => {
+                paste_writer.send(PasteEvent { target });
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Plugin which provides the [`Clipboard`] resource and clipboard shortcut events.
+pub struct ClipboardPlugin;
+
+impl Plugin for ClipboardPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((
+            EventListenerPlugin::<CopyEvent>::default(),
+            EventListenerPlugin::<PasteEvent>::default(),
+        ))
+        .init_resource::<Clipboard>()
+        .add_event::<CopyEvent>()
+        .add_event::<PasteEvent>()
+        .add_systems(Update, handle_clipboard_shortcuts);
+    }
+}
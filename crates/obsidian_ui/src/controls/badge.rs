@@ -0,0 +1,169 @@
+use bevy::{prelude::*, ui};
+use bevy_reactor::*;
+
+use crate::colors;
+
+fn style_badge(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .position(ui::PositionType::Relative);
+}
+
+fn style_badge_count(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .position(ui::PositionType::Absolute)
+        .top(-5)
+        .right(-5)
+        .min_width(14)
+        .height(14)
+        .padding((3, 0))
+        .border_radius(7.)
+        .align_items(ui::AlignItems::Center)
+        .justify_content(ui::JustifyContent::Center)
+        .font("obsidian_ui://fonts/Open_Sans/static/OpenSans-Bold.ttf")
+        .font_size(11)
+        .color(colors::FOREGROUND)
+        .pointer_events(false);
+}
+
+fn style_badge_dot(ss: &mut StyleBuilder) {
+    ss.position(ui::PositionType::Absolute)
+        .top(-2)
+        .right(-2)
+        .width(8)
+        .height(8)
+        .border_radius(4.)
+        .pointer_events(false);
+}
+
+/// Displays a count or a status dot over the top-right corner of its child view, for example
+/// to show the number of unread messages on a button.
+pub struct Badge {
+    /// The count to display. The badge is hidden when the count is zero.
+    pub count: Signal<u32>,
+
+    /// Counts larger than this are displayed as `"{max}+"`.
+    pub max: u32,
+
+    /// If true, display a small dot instead of a count.
+    pub dot: Signal<bool>,
+
+    /// Background color of the badge.
+    pub color: Signal<Srgba>,
+
+    /// The view the badge is attached to.
+    pub children: ChildArray,
+
+    /// Additional styles to be applied to the badge.
+    pub style: StyleHandle,
+}
+
+impl Badge {
+    /// Create a new badge.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the count to display.
+    pub fn count(mut self, count: impl IntoSignal<u32>) -> Self {
+        self.count = count.into_signal();
+        self
+    }
+
+    /// Set the maximum count to display.
+    pub fn max(mut self, max: u32) -> Self {
+        self.max = max;
+        self
+    }
+
+    /// Set whether to display a status dot instead of a count.
+    pub fn dot(mut self, dot: impl IntoSignal<bool>) -> Self {
+        self.dot = dot.into_signal();
+        self
+    }
+
+    /// Set the background color of the badge.
+    pub fn color(mut self, color: impl IntoSignal<Srgba>) -> Self {
+        self.color = color.into_signal();
+        self
+    }
+
+    /// Set the view the badge is attached to.
+    pub fn children<V: ChildViewTuple>(mut self, children: V) -> Self {
+        self.children = children.to_child_array();
+        self
+    }
+
+    /// Set additional styles to be applied to the badge.
+    pub fn style<S: StyleTuple + 'static>(mut self, style: S) -> Self {
+        self.style = style.into_handle();
+        self
+    }
+}
+
+impl Default for Badge {
+    fn default() -> Self {
+        Self {
+            count: Signal::Constant(0),
+            max: 99,
+            dot: Signal::Constant(false),
+            color: Signal::Constant(colors::DESTRUCTIVE_ACC),
+            children: ChildArray::default(),
+            style: StyleHandle::default(),
+        }
+    }
+}
+
+impl ViewTemplate for Badge {
+    fn create(&self, _cx: &mut Cx) -> impl IntoView {
+        let count = self.count;
+        let max = self.max;
+        let dot = self.dot;
+        let color = self.color;
+
+        Element::<NodeBundle>::new()
+            .named("Badge")
+            .style((style_badge, self.style.clone()))
+            .children((
+                self.children.clone(),
+                Cond::new(
+                    move |cx| dot.get(cx),
+                    move || {
+                        Element::<NodeBundle>::new()
+                            .named("Badge::Dot")
+                            .style(style_badge_dot)
+                            .create_effect(move |cx, ent| {
+                                let color = color.get(cx);
+                                let mut bg =
+                                    cx.world_mut().get_mut::<BackgroundColor>(ent).unwrap();
+                                bg.0 = color.into();
+                            })
+                    },
+                    move || {
+                        Cond::new(
+                            move |cx| count.get(cx) > 0,
+                            move || {
+                                Element::<NodeBundle>::new()
+                                    .named("Badge::Count")
+                                    .style(style_badge_count)
+                                    .create_effect(move |cx, ent| {
+                                        let color = color.get(cx);
+                                        let mut bg =
+                                            cx.world_mut().get_mut::<BackgroundColor>(ent).unwrap();
+                                        bg.0 = color.into();
+                                    })
+                                    .children(text_computed(move |cx| {
+                                        let count = count.get(cx);
+                                        if count > max {
+                                            format!("{}+", max)
+                                        } else {
+                                            count.to_string()
+                                        }
+                                    }))
+                            },
+                            || (),
+                        )
+                    },
+                ),
+            ))
+    }
+}
@@ -1,8 +1,10 @@
 use crate::{
     colors,
+    controls::Spinner,
     focus::{AutoFocus, KeyPressEvent, TabIndex},
-    hooks::CreateFocusSignal,
+    hooks::{CreateDisabledSignal, CreateFocusSignal, CreateThemeSignal, ThemeToken},
     size::Size,
+    touch_target::{click_was_drag, TouchTarget},
     typography, RoundedCorners,
 };
 use bevy::{
@@ -12,13 +14,15 @@ use bevy::{
     },
     color::Luminance,
     prelude::*,
+    tasks::{block_on, futures_lite::future, Task},
     ui,
 };
 use bevy_mod_picking::{events::PointerCancel, prelude::*};
 use bevy_reactor::*;
 
 /// The variant determines the button's color scheme
-#[derive(Clone, Copy, PartialEq, Default, Debug)]
+#[derive(Clone, Copy, PartialEq, Default, Debug, Reflect)]
+#[reflect(Default)]
 pub enum ButtonVariant {
     /// The default apperance.
     #[default]
@@ -55,8 +59,34 @@ pub(crate) fn style_button_bg(ss: &mut StyleBuilder) {
         .bottom(0);
 }
 
+/// Component which holds the async task started by a button's `on_click_task` callback. The
+/// button shows its pending state until the task completes.
+#[derive(Component)]
+pub(crate) struct PendingTask {
+    task: Task<()>,
+    pending: Mutable<bool>,
+}
+
+/// System which polls the tasks started by buttons, and clears the pending state of each
+/// button whose task has finished.
+pub(crate) fn poll_pending_tasks(world: &mut World) {
+    let mut finished: Vec<(Entity, Mutable<bool>)> = Vec::new();
+    let mut query = world.query::<(Entity, &mut PendingTask)>();
+    for (entity, mut pending) in query.iter_mut(world) {
+        if block_on(future::poll_once(&mut pending.task)).is_some() {
+            finished.push((entity, pending.pending));
+        }
+    }
+    for (entity, pending) in finished {
+        world.entity_mut(entity).remove::<PendingTask>();
+        pending.set(world, false);
+    }
+}
+
 /// Button widget
-#[derive(Default)]
+///
+/// Styleable parts:
+/// * `"background"` - the element which renders the button's background color.
 pub struct Button {
     /// Color variant - default, primary or danger.
     pub variant: Signal<ButtonVariant>,
@@ -87,6 +117,47 @@ pub struct Button {
 
     /// If true, render the button in a 'minimal' style with no background and reduced padding.
     pub minimal: bool,
+
+    /// Style overrides for the internal parts of the button.
+    pub part_styles: PartStyles,
+
+    /// If true (the default), expand the hit area of the button to the minimum touch target
+    /// size.
+    pub touch_target: bool,
+
+    /// Whether the button is waiting for an operation to complete. While pending, the button
+    /// shows a spinner and cannot be activated.
+    pub pending: Signal<bool>,
+
+    /// Callback called when clicked, which may start an async task. The button is pending
+    /// until the returned task completes.
+    pub on_click_task: Option<Callback<(), Option<Task<()>>>>,
+
+    /// Minimum time, in seconds, between activations. Activations which occur sooner than
+    /// this after the previous one are ignored, which prevents accidental double-submits.
+    pub debounce: f32,
+}
+
+impl Default for Button {
+    fn default() -> Self {
+        Self {
+            variant: Default::default(),
+            size: Default::default(),
+            disabled: Default::default(),
+            children: Default::default(),
+            style: Default::default(),
+            on_click: None,
+            tab_index: 0,
+            corners: Default::default(),
+            autofocus: false,
+            minimal: false,
+            part_styles: Default::default(),
+            touch_target: true,
+            pending: Default::default(),
+            on_click_task: None,
+            debounce: 0.,
+        }
+    }
 }
 
 impl Button {
@@ -132,6 +203,12 @@ impl Button {
         self
     }
 
+    /// Set additional styles for one of the button's internal parts.
+    pub fn part_style<S: StyleTuple + 'static>(mut self, part: &'static str, style: S) -> Self {
+        self.part_styles.insert(part, style);
+        self
+    }
+
     /// Set callback when clicked
     pub fn on_click(mut self, callback: Callback) -> Self {
         self.on_click = Some(callback);
@@ -155,6 +232,32 @@ impl Button {
         self.autofocus = autofocus;
         self
     }
+
+    /// Set whether to expand the hit area of the button to the minimum touch target size
+    /// (default true).
+    pub fn touch_target(mut self, touch_target: bool) -> Self {
+        self.touch_target = touch_target;
+        self
+    }
+
+    /// Set the button pending state.
+    pub fn pending(mut self, pending: impl IntoSignal<bool>) -> Self {
+        self.pending = pending.into_signal();
+        self
+    }
+
+    /// Set callback when clicked, which returns an optional async task. The button shows
+    /// its pending state until the task completes.
+    pub fn on_click_task(mut self, callback: Callback<(), Option<Task<()>>>) -> Self {
+        self.on_click_task = Some(callback);
+        self
+    }
+
+    /// Set the minimum time, in seconds, between activations.
+    pub fn debounce(mut self, debounce: f32) -> Self {
+        self.debounce = debounce;
+        self
+    }
 }
 
 impl ViewTemplate for Button {
@@ -165,12 +268,48 @@ impl ViewTemplate for Button {
         let hovering = cx.create_hover_signal(id);
         let focused = cx.create_focus_visible_signal(id);
 
-        let disabled = self.disabled;
+        let disabled = cx.create_disabled_signal(self.disabled);
+        let enabled = cx.create_derived(move |rcx| !disabled.get(rcx));
+        let color_default = cx.create_theme_color(ThemeToken::Button);
+        let color_primary = cx.create_theme_color(ThemeToken::ButtonPrimary);
+        let color_danger = cx.create_theme_color(ThemeToken::ButtonDanger);
+        let color_selected = cx.create_theme_color(ThemeToken::ButtonSelected);
+        let color_focus = cx.create_theme_color(ThemeToken::Focus);
         let corners = self.corners;
         let minimal = self.minimal;
 
         let size = self.size;
 
+        let pending = self.pending;
+        let task_pending = cx.create_mutable::<bool>(false);
+        let busy = cx.create_derived(move |rcx| pending.get(rcx) || task_pending.get(rcx));
+        let last_activation = cx.create_mutable::<f32>(f32::NEG_INFINITY);
+        let debounce = self.debounce;
+        let on_click = self.on_click;
+        let on_click_task = self.on_click_task;
+        let activate = cx.create_callback(move |cx: &mut Cx, _| {
+            if disabled.get(cx) || busy.get(cx) {
+                return;
+            }
+            let now = cx.world().resource::<Time>().elapsed_seconds();
+            if now - last_activation.get(cx) < debounce {
+                return;
+            }
+            last_activation.set(cx, now);
+            if let Some(on_click) = on_click {
+                cx.run_callback(on_click, ());
+            }
+            if let Some(on_click_task) = on_click_task {
+                if let Some(task) = cx.run_callback(on_click_task, ()) {
+                    task_pending.set(cx, true);
+                    cx.world_mut().entity_mut(id).insert(PendingTask {
+                        task,
+                        pending: task_pending,
+                    });
+                }
+            }
+        });
+
         Element::<NodeBundle>::for_entity(id)
             .named("Button")
             .style((
@@ -187,82 +326,71 @@ impl ViewTemplate for Button {
             .insert((
                 TabIndex(self.tab_index),
                 AccessibilityNode::from(NodeBuilder::new(Role::Button)),
-                {
-                    let on_click = self.on_click;
-                    On::<Pointer<Click>>::run(move |world: &mut World| {
-                        let mut focus = world.get_resource_mut::<Focus>().unwrap();
-                        focus.0 = Some(id);
-                        if !disabled.get(world) {
-                            let mut event = world
-                                .get_resource_mut::<ListenerInput<Pointer<Click>>>()
-                                .unwrap();
-                            event.stop_propagation();
-                            if let Some(on_click) = on_click {
-                                world.run_callback(on_click, ());
-                            }
-                        }
-                    })
-                },
-                On::<Pointer<DragStart>>::run(move |world: &mut World| {
+                On::<Pointer<Click>>::run(move |world: &mut World| {
+                    let mut focus = world.get_resource_mut::<Focus>().unwrap();
+                    focus.0 = Some(id);
                     if !disabled.get(world) {
-                        pressed.set(world, true);
+                        let mut event = world
+                            .get_resource_mut::<ListenerInput<Pointer<Click>>>()
+                            .unwrap();
+                        event.stop_propagation();
+                        // A touch which turned into a drag (e.g. to scroll) is not a tap.
+                        if click_was_drag(world) {
+                            return;
+                        }
+                        world.run_callback(activate, ());
                     }
                 }),
-                On::<Pointer<DragEnd>>::run(move |world: &mut World| {
+                On::<KeyPressEvent>::run(move |world: &mut World| {
                     if !disabled.get(world) {
-                        pressed.set(world, false);
+                        let mut event = world
+                            .get_resource_mut::<ListenerInput<KeyPressEvent>>()
+                            .unwrap();
+                        if !event.repeat
+                            && (event.key_code == KeyCode::Enter
+                                || event.key_code == KeyCode::Space)
+                        {
+                            event.stop_propagation();
+                            world.run_callback(activate, ());
+                        }
                     }
                 }),
-                On::<Pointer<DragEnter>>::run(move |world: &mut World| {
-                    if !disabled.get(world) {
+            ))
+            // A disabled button drops its press handlers entirely.
+            .insert_listener_if(enabled, move || {
+                (
+                    On::<Pointer<DragStart>>::run(move |world: &mut World| {
                         pressed.set(world, true);
-                    }
-                }),
-                On::<Pointer<DragLeave>>::run(move |world: &mut World| {
-                    if !disabled.get(world) {
+                    }),
+                    On::<Pointer<DragEnd>>::run(move |world: &mut World| {
                         pressed.set(world, false);
-                    }
-                }),
-                On::<Pointer<PointerCancel>>::run(move |world: &mut World| {
-                    println!("PointerCancel");
-                    if !disabled.get(world) {
+                    }),
+                    On::<Pointer<DragEnter>>::run(move |world: &mut World| {
+                        pressed.set(world, true);
+                    }),
+                    On::<Pointer<DragLeave>>::run(move |world: &mut World| {
                         pressed.set(world, false);
-                    }
-                }),
-                On::<KeyPressEvent>::run({
-                    let on_click = self.on_click;
-                    move |world: &mut World| {
-                        if !disabled.get(world) {
-                            let mut event = world
-                                .get_resource_mut::<ListenerInput<KeyPressEvent>>()
-                                .unwrap();
-                            if !event.repeat
-                                && (event.key_code == KeyCode::Enter
-                                    || event.key_code == KeyCode::Space)
-                            {
-                                event.stop_propagation();
-                                if let Some(on_click) = on_click {
-                                    world.run_callback(on_click, ());
-                                }
-                            }
-                        }
-                    }
-                }),
-            ))
+                    }),
+                    On::<Pointer<PointerCancel>>::run(move |world: &mut World| {
+                        pressed.set(world, false);
+                    }),
+                )
+            })
             .insert_if(self.autofocus, AutoFocus)
+            .insert_if(self.touch_target, TouchTarget::default())
             .children((
                 Element::<NodeBundle>::new()
                     .named("Button::Background")
-                    .style(style_button_bg)
+                    .style((style_button_bg, self.part_styles.get("background")))
                     .insert(corners.to_border_radius(self.size.border_radius()))
                     .create_effect(move |cx, ent| {
                         let is_pressed = pressed.get(cx);
                         let is_hovering = hovering.get(cx);
                         let base_color = match variant.get(cx) {
-                            ButtonVariant::Default => colors::U3,
-                            ButtonVariant::Primary => colors::PRIMARY,
-                            ButtonVariant::Danger => colors::DESTRUCTIVE,
-                            ButtonVariant::Selected => colors::U4,
+                            ButtonVariant::Default => color_default.get(cx),
+                            ButtonVariant::Primary => color_primary.get(cx),
+                            ButtonVariant::Danger => color_danger.get(cx),
+                            ButtonVariant::Selected => color_selected.get(cx),
                         };
                         let color = match (is_pressed, is_hovering) {
                             (true, _) => base_color.lighter(0.05),
@@ -280,11 +408,12 @@ impl ViewTemplate for Button {
                     })
                     .create_effect(move |cx, entt| {
                         let is_focused = focused.get(cx);
+                        let focus = color_focus.get(cx);
                         let mut entt = cx.world_mut().entity_mut(entt);
                         match is_focused {
                             true => {
                                 entt.insert(Outline {
-                                    color: colors::FOCUS.into(),
+                                    color: focus.into(),
                                     offset: ui::Val::Px(2.0),
                                     width: ui::Val::Px(2.0),
                                 });
@@ -294,6 +423,15 @@ impl ViewTemplate for Button {
                             }
                         };
                     }),
+                Cond::new(
+                    move |rcx| busy.get(rcx),
+                    move || {
+                        Spinner::new().size(size).style(|ss: &mut StyleBuilder| {
+                            ss.margin_right(6);
+                        })
+                    },
+                    || (),
+                ),
                 self.children.clone(),
             ))
     }
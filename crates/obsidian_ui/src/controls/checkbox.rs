@@ -1,6 +1,6 @@
 use bevy::{
     a11y::{
-        accesskit::{NodeBuilder, Role},
+        accesskit::{Checked, NodeBuilder, Role},
         AccessibilityNode, Focus,
     },
     color::{LinearRgba, Luminance},
@@ -13,7 +13,8 @@ use bevy_reactor::*;
 use crate::{
     colors,
     focus::{KeyPressEvent, TabIndex},
-    hooks::CreateFocusSignal,
+    hooks::{CreateDisabledSignal, CreateFocusSignal, CreateThemeSignal, ThemeToken},
+    touch_target::{click_was_drag, TouchTarget},
 };
 
 fn style_checkbox(ss: &mut StyleBuilder) {
@@ -44,6 +45,17 @@ fn style_checkbox_inner(ss: &mut StyleBuilder) {
         .height(12);
 }
 
+fn style_checkbox_dash(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .background_color(colors::FOREGROUND)
+        .position(ui::PositionType::Absolute)
+        .left(4)
+        .top(7)
+        .width(8)
+        .height(2)
+        .border_radius(1.0);
+}
+
 fn style_checkbox_label(ss: &mut StyleBuilder) {
     ss.display(ui::Display::Flex)
         .flex_direction(ui::FlexDirection::Row)
@@ -52,12 +64,44 @@ fn style_checkbox_label(ss: &mut StyleBuilder) {
         .color(colors::FOREGROUND);
 }
 
+/// The state of a tri-state checkbox.
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum CheckState {
+    /// The checkbox is not checked.
+    #[default]
+    Unchecked,
+    /// The checkbox is checked.
+    Checked,
+    /// The checkbox is partially checked, e.g. a "select all" checkbox when only some of the
+    /// items are selected.
+    Indeterminate,
+}
+
+impl From<bool> for CheckState {
+    fn from(checked: bool) -> Self {
+        if checked {
+            CheckState::Checked
+        } else {
+            CheckState::Unchecked
+        }
+    }
+}
+
 /// A checkbox widget.
-#[derive(Default)]
+///
+/// Styleable parts:
+/// * `"border"` - the square box surrounding the checkmark.
+/// * `"checkmark"` - the checkmark glyph, shown when checked.
+/// * `"dash"` - the dash glyph, shown when indeterminate.
+/// * `"label"` - the container for the label content.
 pub struct Checkbox {
     /// Whether the checkbox is checked.
     pub checked: Signal<bool>,
 
+    /// Tri-state checked state. If present, this is used instead of `checked`. Clicking an
+    /// indeterminate checkbox calls `on_change` with `true`.
+    pub state: Option<Signal<CheckState>>,
+
     /// Whether the checkbox is disabled.
     pub disabled: Signal<bool>,
 
@@ -72,6 +116,28 @@ pub struct Checkbox {
 
     /// The tab index of the checkbox (default 0).
     pub tab_index: i32,
+
+    /// Style overrides for the internal parts of the checkbox.
+    pub part_styles: PartStyles,
+
+    /// If true (the default), expand the hit area to the minimum touch target size.
+    pub touch_target: bool,
+}
+
+impl Default for Checkbox {
+    fn default() -> Self {
+        Self {
+            checked: Default::default(),
+            state: None,
+            disabled: Default::default(),
+            label: Default::default(),
+            style: Default::default(),
+            on_change: None,
+            tab_index: 0,
+            part_styles: Default::default(),
+            touch_target: true,
+        }
+    }
 }
 
 impl Checkbox {
@@ -86,6 +152,12 @@ impl Checkbox {
         self
     }
 
+    /// Set the tri-state checked state of the checkbox, which allows it to be indeterminate.
+    pub fn state(mut self, state: impl IntoSignal<CheckState>) -> Self {
+        self.state = Some(state.into_signal());
+        self
+    }
+
     /// Set the disabled state of the checkbox.
     pub fn disabled(mut self, disabled: impl IntoSignal<bool>) -> Self {
         self.disabled = disabled.into_signal();
@@ -104,6 +176,12 @@ impl Checkbox {
         self
     }
 
+    /// Set additional styles for one of the checkbox's internal parts.
+    pub fn part_style<S: StyleTuple + 'static>(mut self, part: &'static str, style: S) -> Self {
+        self.part_styles.insert(part, style);
+        self
+    }
+
     /// Set the on_change callback of the checkbox.
     pub fn on_change(mut self, on_change: Callback<bool>) -> Self {
         self.on_change = Some(on_change);
@@ -115,6 +193,12 @@ impl Checkbox {
         self.tab_index = tab_index;
         self
     }
+
+    /// Set whether to expand the hit area to the minimum touch target size (default true).
+    pub fn touch_target(mut self, touch_target: bool) -> Self {
+        self.touch_target = touch_target;
+        self
+    }
 }
 
 impl ViewTemplate for Checkbox {
@@ -125,24 +209,34 @@ impl ViewTemplate for Checkbox {
         let hovering = cx.create_hover_signal(id);
         let focused = cx.create_focus_visible_signal(id);
 
-        let disabled = self.disabled;
-        let checked = self.checked;
+        let disabled = cx.create_disabled_signal(self.disabled);
+        let color_accent = cx.create_theme_color(ThemeToken::Accent);
+        let color_focus = cx.create_theme_color(ThemeToken::Focus);
+        let state = match self.state {
+            Some(state) => state,
+            None => {
+                let checked = self.checked;
+                cx.create_derived(move |cx| CheckState::from(checked.get(cx)))
+            }
+        };
 
         Element::<NodeBundle>::for_entity(id)
             .named("Checkbox")
             .style((style_checkbox, self.style.clone()))
             .insert((
                 TabIndex(self.tab_index),
-                AccessibilityNode::from(NodeBuilder::new(Role::CheckBox)),
                 {
                     let on_change = self.on_change;
                     On::<Pointer<Click>>::run(move |world: &mut World| {
+                        if click_was_drag(world) {
+                            return;
+                        }
                         let mut focus = world.get_resource_mut::<Focus>().unwrap();
                         focus.0 = Some(id);
                         if !disabled.get(world) {
-                            let next_checked = checked.get(world);
+                            let is_checked = state.get(world) == CheckState::Checked;
                             if let Some(on_click) = on_change {
-                                world.run_callback(on_click, !next_checked);
+                                world.run_callback(on_click, !is_checked);
                             }
                         }
                     })
@@ -185,27 +279,40 @@ impl ViewTemplate for Checkbox {
                                     || event.key_code == KeyCode::Space)
                             {
                                 event.stop_propagation();
-                                let next_checked = checked.get(world);
+                                let is_checked = state.get(world) == CheckState::Checked;
                                 if let Some(on_click) = on_change {
-                                    world.run_callback(on_click, !next_checked);
+                                    world.run_callback(on_click, !is_checked);
                                 }
                             }
                         }
                     }
                 }),
             ))
+            .insert_computed(move |cx| {
+                AccessibilityNode::from({
+                    let mut builder = NodeBuilder::new(Role::CheckBox);
+                    builder.set_checked(match state.get(cx) {
+                        CheckState::Unchecked => Checked::False,
+                        CheckState::Checked => Checked::True,
+                        CheckState::Indeterminate => Checked::Mixed,
+                    });
+                    builder
+                })
+            })
+            .insert_if(self.touch_target, TouchTarget::default())
             .children((
                 Element::<NodeBundle>::new()
                     .named("Checkbox::Border")
-                    .style(style_checkbox_border)
+                    .style((style_checkbox_border, self.part_styles.get("border")))
                     .create_effect(move |cx, ent| {
-                        let is_checked = checked.get(cx);
+                        let is_checked = state.get(cx) != CheckState::Unchecked;
                         let is_pressed = pressed.get(cx);
                         let is_hovering = hovering.get(cx);
+                        let accent = color_accent.get(cx);
                         let color = match (is_checked, is_pressed, is_hovering) {
-                            (true, true, _) => colors::ACCENT.darker(0.1),
-                            (true, false, true) => colors::ACCENT.darker(0.15),
-                            (true, _, _) => colors::ACCENT.darker(0.2),
+                            (true, true, _) => accent.darker(0.1),
+                            (true, false, true) => accent.darker(0.15),
+                            (true, _, _) => accent.darker(0.2),
                             (false, true, _) => colors::U1.lighter(0.005),
                             (false, false, true) => colors::U1.lighter(0.002),
                             (false, false, false) => colors::U1,
@@ -215,11 +322,12 @@ impl ViewTemplate for Checkbox {
                     })
                     .create_effect(move |cx, entt| {
                         let is_focused = focused.get(cx);
+                        let focus = color_focus.get(cx);
                         let mut entt = cx.world_mut().entity_mut(entt);
                         match is_focused {
                             true => {
                                 entt.insert(Outline {
-                                    color: colors::FOCUS.into(),
+                                    color: focus.into(),
                                     offset: ui::Val::Px(2.0),
                                     width: ui::Val::Px(2.0),
                                 });
@@ -230,12 +338,31 @@ impl ViewTemplate for Checkbox {
                         };
                     })
                     .children(Cond::new(
-                        move |cx| checked.get(cx),
-                        move || Element::<NodeBundle>::new().style(style_checkbox_inner),
-                        || (),
+                        move |cx| state.get(cx) == CheckState::Checked,
+                        {
+                            let checkmark_style = self.part_styles.get("checkmark");
+                            move || {
+                                Element::<NodeBundle>::new()
+                                    .style((style_checkbox_inner, checkmark_style.clone()))
+                            }
+                        },
+                        {
+                            let dash_style = self.part_styles.get("dash");
+                            move || {
+                                let dash_style = dash_style.clone();
+                                Cond::new(
+                                    move |cx| state.get(cx) == CheckState::Indeterminate,
+                                    move || {
+                                        Element::<NodeBundle>::new()
+                                            .style((style_checkbox_dash, dash_style.clone()))
+                                    },
+                                    || (),
+                                )
+                            }
+                        },
                     )),
                 Element::<NodeBundle>::new()
-                    .style(style_checkbox_label)
+                    .style((style_checkbox_label, self.part_styles.get("label")))
                     .children(self.label.clone()),
             ))
     }
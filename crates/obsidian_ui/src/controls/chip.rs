@@ -0,0 +1,169 @@
+use bevy::{color::Luminance, prelude::*, ui};
+use bevy_mod_picking::prelude::*;
+use bevy_reactor::*;
+
+use crate::{colors, controls::Icon, size::Size};
+
+fn style_chip(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Row)
+        .align_items(ui::AlignItems::Center)
+        .flex_shrink(0.)
+        .gap(2)
+        .padding((8, 0))
+        .font("obsidian_ui://fonts/Open_Sans/static/OpenSans-Medium.ttf")
+        .color(colors::FOREGROUND);
+}
+
+fn style_chip_close(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .align_items(ui::AlignItems::Center)
+        .justify_content(ui::JustifyContent::Center)
+        .width(14)
+        .height(14)
+        .margin_right(-4)
+        .border_radius(7.);
+}
+
+/// A compact token displaying a short piece of text, such as a tag or a filter term, with
+/// an optional close button.
+pub struct Chip {
+    /// The text to display.
+    pub label: Signal<String>,
+
+    /// Chip size.
+    pub size: Size,
+
+    /// Background color of the chip.
+    pub color: Signal<Srgba>,
+
+    /// Additional styles to be applied to the chip.
+    pub style: StyleHandle,
+
+    /// Callback called when the close button is clicked. If `None`, no close button is shown.
+    pub on_remove: Option<Callback>,
+}
+
+impl Chip {
+    /// Create a new chip with the given label.
+    pub fn new(label: impl IntoSignal<String>) -> Self {
+        Self {
+            label: label.into_signal(),
+            ..default()
+        }
+    }
+
+    /// Set the chip size.
+    pub fn size(mut self, size: Size) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Set the background color of the chip.
+    pub fn color(mut self, color: impl IntoSignal<Srgba>) -> Self {
+        self.color = color.into_signal();
+        self
+    }
+
+    /// Set additional styles to be applied to the chip.
+    pub fn style<S: StyleTuple + 'static>(mut self, style: S) -> Self {
+        self.style = style.into_handle();
+        self
+    }
+
+    /// Set the callback called when the close button is clicked.
+    pub fn on_remove(mut self, on_remove: Callback) -> Self {
+        self.on_remove = Some(on_remove);
+        self
+    }
+}
+
+impl Default for Chip {
+    fn default() -> Self {
+        Self {
+            label: Signal::default(),
+            size: Size::Sm,
+            color: Signal::Constant(colors::U3),
+            style: StyleHandle::default(),
+            on_remove: None,
+        }
+    }
+}
+
+impl ViewTemplate for Chip {
+    fn create(&self, _cx: &mut Cx) -> impl IntoView {
+        let label = self.label.clone();
+        let color = self.color;
+        let size = self.size;
+        let on_remove = self.on_remove;
+
+        Element::<NodeBundle>::new()
+            .named("Chip")
+            .style((
+                style_chip,
+                move |ss: &mut StyleBuilder| {
+                    ss.height(size.height())
+                        .font_size(size.font_size())
+                        .border_radius(size.height() * 0.5);
+                },
+                self.style.clone(),
+            ))
+            .create_effect(move |cx, ent| {
+                let color = color.get(cx);
+                let mut bg = cx.world_mut().get_mut::<BackgroundColor>(ent).unwrap();
+                bg.0 = color.into();
+            })
+            .children((
+                text_computed(move |cx| label.get_clone(cx)),
+                Cond::new(
+                    move |_cx| on_remove.is_some(),
+                    move || ChipClose {
+                        color,
+                        on_remove: on_remove.unwrap(),
+                    },
+                    || (),
+                ),
+            ))
+    }
+}
+
+struct ChipClose {
+    color: Signal<Srgba>,
+    on_remove: Callback,
+}
+
+impl ViewTemplate for ChipClose {
+    fn create(&self, cx: &mut Cx) -> impl IntoView {
+        let id = cx.create_entity();
+        let hovering = cx.create_hover_signal(id);
+        let color = self.color;
+        let on_remove = self.on_remove;
+
+        Element::<NodeBundle>::for_entity(id)
+            .named("Chip::Close")
+            .style(style_chip_close)
+            .insert(On::<Pointer<Click>>::run(move |world: &mut World| {
+                let mut event = world
+                    .get_resource_mut::<ListenerInput<Pointer<Click>>>()
+                    .unwrap();
+                event.stop_propagation();
+                world.run_callback(on_remove, ());
+            }))
+            .create_effect(move |cx, ent| {
+                let bg_color = if hovering.get(cx) {
+                    color.get(cx).lighter(0.1)
+                } else {
+                    Srgba::NONE
+                };
+                let mut bg = cx.world_mut().get_mut::<BackgroundColor>(ent).unwrap();
+                bg.0 = bg_color.into();
+            })
+            .children(
+                Icon::new("obsidian_ui://icons/close.png")
+                    .size(Vec2::splat(10.))
+                    .style(|ss: &mut StyleBuilder| {
+                        ss.pointer_events(false);
+                    }),
+            )
+    }
+}
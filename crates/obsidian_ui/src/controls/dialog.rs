@@ -5,15 +5,17 @@ use bevy::{
 };
 use bevy_mod_picking::{
     events::{Click, Pointer},
-    prelude::{ListenerInput, On},
+    prelude::On,
 };
 use bevy_reactor::*;
 
 use crate::{
     animation::{AnimatedBackgroundColor, AnimatedScale, AnimatedTransition},
-    colors,
-    focus::{KeyPressEvent, TabGroup},
-    hooks::{BistableTransitionState, CreateBistableTransition},
+    backdrop::BackdropBlur,
+    colors, elevation,
+    focus::{FocusScope, TabGroup},
+    hooks::{BistableTransitionState, CreateBistableTransition, CreateDisabledSignal},
+    popup_stack::Popup,
     typography::text_default,
 };
 
@@ -42,6 +44,7 @@ fn style_dialog(ss: &mut StyleBuilder) {
         .border_color(colors::U1)
         .width(400)
         .border(3);
+    elevation::elevation_3(ss);
     // .scale(0.5)
     // .transition(&[Transition {
     //     property: TransitionProperty::Transform,
@@ -73,6 +76,13 @@ pub struct Dialog {
 
     /// Callback called when the dialog has completed it's closing animation.
     pub on_exited: Option<Callback>,
+
+    /// Whether to blur the content behind the dialog. See [`BackdropBlur`].
+    pub backdrop_blur: bool,
+
+    /// Signal which is true while the dialog is busy, for example while it is saving. All of
+    /// the controls within the dialog are disabled while it is busy.
+    pub busy: Signal<bool>,
 }
 
 impl Dialog {
@@ -110,6 +120,18 @@ impl Dialog {
         self.on_exited = Some(on_exited);
         self
     }
+
+    /// Sets whether to blur the content behind the dialog.
+    pub fn backdrop_blur(mut self, backdrop_blur: bool) -> Self {
+        self.backdrop_blur = backdrop_blur;
+        self
+    }
+
+    /// Sets the signal which disables the controls within the dialog while it is busy.
+    pub fn busy(mut self, busy: impl IntoSignal<bool>) -> Self {
+        self.busy = busy.into_signal();
+        self
+    }
 }
 
 impl ViewTemplate for Dialog {
@@ -117,8 +139,18 @@ impl ViewTemplate for Dialog {
         let on_close = self.on_close;
         let on_exited = self.on_exited;
         let state = cx.create_bistable_transition(self.open, TRANSITION_DURATION);
+        cx.provide_disabled(self.busy);
         let children = self.children.clone();
         let width = self.width;
+        let backdrop_blur = self.backdrop_blur;
+        let backdrop_intensity = cx.create_derived(move |cx| match state.get(cx) {
+            BistableTransitionState::Entering
+            | BistableTransitionState::Entered
+            | BistableTransitionState::ExitStart => 1.,
+            BistableTransitionState::EnterStart
+            | BistableTransitionState::Exiting
+            | BistableTransitionState::Exited => 0.,
+        });
 
         cx.create_effect(move |ve| {
             let state = state.get(ve);
@@ -129,87 +161,90 @@ impl ViewTemplate for Dialog {
             }
         });
 
-        Cond::new(
-            move |cx| state.get(cx) != BistableTransitionState::Exited,
-            move || {
-                Portal::new(
-                    Element::<NodeBundle>::new()
-                        .named("Dialog::Overlay")
-                        .style(style_dialog_overlay)
-                        .insert((
-                            // Click on backdrop sends close signal.
-                            On::<Pointer<Click>>::run(move |world: &mut World| {
-                                if let Some(on_close) = on_close {
-                                    world.run_callback(on_close, ());
-                                }
-                            }),
-                            On::<KeyPressEvent>::run({
-                                move |world: &mut World| {
-                                    let mut event = world
-                                        .get_resource_mut::<ListenerInput<KeyPressEvent>>()
-                                        .unwrap();
-                                    if !event.repeat && event.key_code == KeyCode::Escape {
-                                        event.stop_propagation();
-                                        if let Some(on_close) = on_close {
-                                            world.run_callback(on_close, ());
-                                        }
-                                    }
-                                }
-                            }),
-                        ))
-                        .create_effect(move |cx, ent| {
+        // The overlay covers the window, fading in behind the dialog frame, which scales up from
+        // the center.
+        let overlay = move || {
+            view! {
+                <Element<NodeBundle>
+                    named="Dialog::Overlay"
+                    style={style_dialog_overlay}
+                    insert={(
+                        // Click on backdrop sends close signal.
+                        On::<Pointer<Click>>::run(move |world: &mut World| {
+                            if let Some(on_close) = on_close {
+                                world.run_callback(on_close, ());
+                            }
+                        }),
+                        // Escape closes the dialog when it is the topmost popup.
+                        Popup { on_close },
+                    )}
+                    create_effect={move |cx, ent| {
+                        let state = state.get(cx);
+                        let mut entt = cx.world_mut().entity_mut(ent);
+                        let target = match state {
+                            BistableTransitionState::Entering
+                            | BistableTransitionState::Entered
+                            | BistableTransitionState::ExitStart => colors::U2.with_alpha(0.7),
+                            BistableTransitionState::EnterStart
+                            | BistableTransitionState::Exiting
+                            | BistableTransitionState::Exited => colors::U2.with_alpha(0.0),
+                        };
+                        AnimatedTransition::<AnimatedBackgroundColor>::start(
+                            &mut entt,
+                            target,
+                            TRANSITION_DURATION,
+                        );
+                    }}
+                >
+                    {backdrop_blur.then(|| {
+                        BackdropBlur::new()
+                            .intensity(backdrop_intensity)
+                            .duration(TRANSITION_DURATION)
+                    })}
+                    <Element<NodeBundle>
+                        insert={(
+                            TabGroup {
+                                order: 0,
+                                modal: true,
+                            },
+                            FocusScope,
+                        )}
+                        style={(
+                            text_default,
+                            style_dialog,
+                            move |ss: &mut StyleBuilder| {
+                                ss.width(width);
+                            },
+                        )}
+                        create_effect={move |cx, ent| {
                             let state = state.get(cx);
                             let mut entt = cx.world_mut().entity_mut(ent);
                             let target = match state {
-                                BistableTransitionState::Entering
-                                | BistableTransitionState::Entered
-                                | BistableTransitionState::ExitStart => colors::U2.with_alpha(0.7),
                                 BistableTransitionState::EnterStart
                                 | BistableTransitionState::Exiting
-                                | BistableTransitionState::Exited => colors::U2.with_alpha(0.0),
+                                | BistableTransitionState::Exited => Vec3::splat(0.0),
+                                BistableTransitionState::Entering
+                                | BistableTransitionState::Entered
+                                | BistableTransitionState::ExitStart => Vec3::splat(1.0),
                             };
-                            AnimatedTransition::<AnimatedBackgroundColor>::start(
+                            AnimatedTransition::<AnimatedScale>::start(
                                 &mut entt,
                                 target,
                                 TRANSITION_DURATION,
                             );
-                        })
-                        .children(
-                            Element::<NodeBundle>::new()
-                                .insert(TabGroup {
-                                    order: 0,
-                                    modal: true,
-                                })
-                                .style((
-                                    text_default,
-                                    style_dialog,
-                                    move |ss: &mut StyleBuilder| {
-                                        ss.width(width);
-                                    },
-                                ))
-                                .create_effect(move |cx, ent| {
-                                    let state = state.get(cx);
-                                    let mut entt = cx.world_mut().entity_mut(ent);
-                                    let target = match state {
-                                        BistableTransitionState::EnterStart
-                                        | BistableTransitionState::Exiting
-                                        | BistableTransitionState::Exited => Vec3::splat(0.0),
-                                        BistableTransitionState::Entering
-                                        | BistableTransitionState::Entered
-                                        | BistableTransitionState::ExitStart => Vec3::splat(1.0),
-                                    };
-                                    AnimatedTransition::<AnimatedScale>::start(
-                                        &mut entt,
-                                        target,
-                                        TRANSITION_DURATION,
-                                    );
-                                })
-                                .children(children.clone()),
-                        ),
-                )
-            },
-            || (),
-        )
+                        }}
+                    >
+                        {children.clone()}
+                    </Element>
+                </Element>
+            }
+        };
+
+        view! {
+            if move |cx| state.get(cx) != BistableTransitionState::Exited {
+                {Portal::new(overlay()).layer("dialogs")}
+            }
+        }
     }
 }
 
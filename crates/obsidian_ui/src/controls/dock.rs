@@ -0,0 +1,1025 @@
+use std::sync::Arc;
+
+use bevy::{color::Luminance, prelude::*, ui, utils::HashMap};
+use bevy_mod_picking::prelude::*;
+use bevy_reactor::*;
+
+use crate::colors;
+
+use super::{Splitter, SplitterDirection};
+
+/// Size of a newly floated panel.
+const FLOATING_PANEL_SIZE: Vec2 = Vec2::new(300., 200.);
+
+/// Offset from the pointer to the upper-left corner of a panel when it is floated.
+const FLOATING_GRAB_OFFSET: Vec2 = Vec2::new(20., 10.);
+
+/// Fraction of a tab group's width or height, measured from each edge, which docks beside
+/// the group rather than into it.
+const DOCK_EDGE_FRACTION: f32 = 0.25;
+
+/// The axis along which a [`DockNode::Split`] arranges its children.
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DockAxis {
+    /// Children are placed side by side.
+    #[default]
+    Horizontal,
+    /// Children are stacked top to bottom.
+    Vertical,
+}
+
+/// A node in the [`DockLayout`] tree.
+#[derive(Reflect, Debug, Clone, PartialEq)]
+pub enum DockNode {
+    /// An unused slot.
+    Empty,
+    /// Two child nodes separated by a splitter.
+    Split {
+        /// The direction in which the children are arranged.
+        axis: DockAxis,
+        /// The fraction of the available space given to the first child.
+        ratio: f32,
+        /// Index of the first (left or top) child.
+        first: usize,
+        /// Index of the second (right or bottom) child.
+        second: usize,
+    },
+    /// A group of panels, of which one is shown at a time.
+    Tabs {
+        /// Ids of the panels in this group, in tab order.
+        panels: Vec<String>,
+        /// Index of the visible panel.
+        active: usize,
+    },
+}
+
+/// A panel which has been floated out of the layout tree into its own window.
+#[derive(Reflect, Debug, Clone, PartialEq)]
+pub struct FloatingPanel {
+    /// Id of the panel.
+    pub panel: String,
+    /// Position of the window's upper-left corner.
+    pub position: Vec2,
+    /// Size of the window.
+    pub size: Vec2,
+}
+
+/// Where a dragged panel will be docked relative to a tab group.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DockZone {
+    /// Split the group, placing the panel on the left.
+    Left,
+    /// Split the group, placing the panel on the right.
+    Right,
+    /// Split the group, placing the panel above.
+    Top,
+    /// Split the group, placing the panel below.
+    Bottom,
+    /// Add the panel as a tab of the group.
+    Center,
+}
+
+impl DockZone {
+    /// Determine the drop zone for a pointer `position` within a tab group's `rect`.
+    pub fn from_position(position: Vec2, rect: Rect) -> Self {
+        let rel = (position - rect.min) / rect.size().max(Vec2::ONE);
+        let (distance, zone) = [
+            (rel.x, DockZone::Left),
+            (1. - rel.x, DockZone::Right),
+            (rel.y, DockZone::Top),
+            (1. - rel.y, DockZone::Bottom),
+        ]
+        .into_iter()
+        .fold((f32::MAX, DockZone::Center), |nearest, edge| {
+            if edge.0 < nearest.0 {
+                edge
+            } else {
+                nearest
+            }
+        });
+        if distance < DOCK_EDGE_FRACTION {
+            zone
+        } else {
+            DockZone::Center
+        }
+    }
+
+    /// The area of the tab group highlighted for this zone, as percentages
+    /// (left, top, width, height).
+    fn highlight(&self) -> (f32, f32, f32, f32) {
+        match self {
+            DockZone::Left => (0., 0., 50., 100.),
+            DockZone::Right => (50., 0., 50., 100.),
+            DockZone::Top => (0., 0., 100., 50.),
+            DockZone::Bottom => (0., 50., 100., 50.),
+            DockZone::Center => (0., 0., 100., 100.),
+        }
+    }
+}
+
+/// Resource containing the layout of a [`DockArea`]: a tree of splits and tab groups, plus a
+/// list of floating panels. Panels are identified by the ids they were registered with in
+/// [`DockArea::panel`]. This resource is registered for reflection so that it can be saved
+/// and restored along with the app's settings.
+#[derive(Resource, Reflect, Debug, Clone, PartialEq)]
+#[reflect(Resource, Default)]
+pub struct DockLayout {
+    nodes: Vec<DockNode>,
+    root: usize,
+    floating: Vec<FloatingPanel>,
+}
+
+impl Default for DockLayout {
+    fn default() -> Self {
+        Self::new(Vec::<String>::new())
+    }
+}
+
+impl DockLayout {
+    /// Construct a new layout with a single tab group containing the given panels.
+    pub fn new(panels: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            nodes: vec![DockNode::Tabs {
+                panels: panels.into_iter().map(|p| p.into()).collect(),
+                active: 0,
+            }],
+            root: 0,
+            floating: Vec::new(),
+        }
+    }
+
+    /// Index of the root node.
+    pub fn root(&self) -> usize {
+        self.root
+    }
+
+    /// Return the node at the given index.
+    pub fn node(&self, index: usize) -> Option<&DockNode> {
+        self.nodes.get(index)
+    }
+
+    /// The list of floating panels.
+    pub fn floating(&self) -> &[FloatingPanel] {
+        &self.floating
+    }
+
+    /// Return the index of the tab group containing the given panel.
+    pub fn find_panel(&self, panel: &str) -> Option<usize> {
+        self.nodes.iter().position(
+            |node| matches!(node, DockNode::Tabs { panels, .. } if panels.iter().any(|p| p == panel)),
+        )
+    }
+
+    /// Make the given panel the visible tab of its group.
+    pub fn set_active_panel(&mut self, panel: &str) {
+        if let Some(index) = self.find_panel(panel) {
+            if let DockNode::Tabs { panels, active } = &mut self.nodes[index] {
+                *active = panels.iter().position(|p| p == panel).unwrap();
+            }
+        }
+    }
+
+    /// Set the fraction of space given to the first child of a split.
+    pub fn set_ratio(&mut self, index: usize, value: f32) {
+        if let Some(DockNode::Split { ratio, .. }) = self.nodes.get_mut(index) {
+            *ratio = value;
+        }
+    }
+
+    /// Remove a panel from the layout, whether docked or floating. Splits left with an empty
+    /// tab group are collapsed. Returns false if the panel was not in the layout.
+    pub fn remove_panel(&mut self, panel: &str) -> bool {
+        if let Some(pos) = self.floating.iter().position(|f| f.panel == panel) {
+            self.floating.remove(pos);
+            return true;
+        }
+
+        let Some(index) = self.find_panel(panel) else {
+            return false;
+        };
+        let DockNode::Tabs { panels, active } = &mut self.nodes[index] else {
+            unreachable!();
+        };
+        let pos = panels.iter().position(|p| p == panel).unwrap();
+        panels.remove(pos);
+        if pos < *active || *active >= panels.len() {
+            *active = active.saturating_sub(1);
+        }
+        if panels.is_empty() && index != self.root {
+            self.collapse(index);
+        }
+        true
+    }
+
+    /// Dock a panel into the tab group at index `target`. If the panel is already in the
+    /// layout, it is moved.
+    pub fn dock(&mut self, panel: impl Into<String>, target: usize, zone: DockZone) {
+        let panel = panel.into();
+        match self.nodes.get(target) {
+            // Docking a panel beside itself would leave an empty group.
+            Some(DockNode::Tabs { panels, .. }) if panels.len() == 1 && panels[0] == panel => {
+                return
+            }
+            Some(DockNode::Tabs { .. }) => {}
+            _ => {
+                warn!("Dock target {} is not a tab group", target);
+                return;
+            }
+        }
+
+        self.remove_panel(&panel);
+        match zone {
+            DockZone::Center => {
+                if let DockNode::Tabs { panels, active } = &mut self.nodes[target] {
+                    panels.push(panel);
+                    *active = panels.len() - 1;
+                }
+            }
+            _ => {
+                // Move the existing group to a new slot, and replace it with a split so that
+                // the parent's reference to `target` remains valid.
+                let moved = self.alloc(self.nodes[target].clone());
+                let tabs = self.alloc(DockNode::Tabs {
+                    panels: vec![panel],
+                    active: 0,
+                });
+                let (axis, first, second) = match zone {
+                    DockZone::Left => (DockAxis::Horizontal, tabs, moved),
+                    DockZone::Right => (DockAxis::Horizontal, moved, tabs),
+                    DockZone::Top => (DockAxis::Vertical, tabs, moved),
+                    _ => (DockAxis::Vertical, moved, tabs),
+                };
+                self.nodes[target] = DockNode::Split {
+                    axis,
+                    ratio: 0.5,
+                    first,
+                    second,
+                };
+            }
+        }
+    }
+
+    /// Float a panel in its own window at the given position. If the panel is already
+    /// floating, it is moved.
+    pub fn float(&mut self, panel: impl Into<String>, position: Vec2) {
+        let panel = panel.into();
+        let size = self
+            .floating
+            .iter()
+            .find(|f| f.panel == panel)
+            .map_or(FLOATING_PANEL_SIZE, |f| f.size);
+        self.remove_panel(&panel);
+        self.floating.push(FloatingPanel {
+            panel,
+            position,
+            size,
+        });
+    }
+
+    /// Return the index of the split containing the given node.
+    fn parent(&self, index: usize) -> Option<usize> {
+        self.nodes.iter().position(|node| {
+            matches!(node, DockNode::Split { first, second, .. } if *first == index || *second == index)
+        })
+    }
+
+    /// Replace the parent split of the node at `index` with the node's sibling.
+    fn collapse(&mut self, index: usize) {
+        let Some(parent) = self.parent(index) else {
+            return;
+        };
+        let DockNode::Split { first, second, .. } = self.nodes[parent] else {
+            return;
+        };
+        let sibling = if first == index { second } else { first };
+        match self.parent(parent) {
+            Some(grandparent) => {
+                if let DockNode::Split { first, second, .. } = &mut self.nodes[grandparent] {
+                    if *first == parent {
+                        *first = sibling;
+                    } else {
+                        *second = sibling;
+                    }
+                }
+            }
+            None => self.root = sibling,
+        }
+        self.nodes[index] = DockNode::Empty;
+        self.nodes[parent] = DockNode::Empty;
+    }
+
+    /// Store a node in an unused slot, returning its index.
+    fn alloc(&mut self, node: DockNode) -> usize {
+        match self.nodes.iter().position(|n| *n == DockNode::Empty) {
+            Some(index) => {
+                self.nodes[index] = node;
+                index
+            }
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        }
+    }
+}
+
+/// Resource which tracks a panel tab being dragged within a [`DockArea`].
+#[derive(Resource, Debug, Default, Clone, PartialEq)]
+pub struct DockDragState {
+    /// The panel being dragged.
+    pub panel: Option<String>,
+    /// The tab group and zone under the pointer.
+    pub target: Option<(usize, DockZone)>,
+}
+
+#[derive(Clone)]
+struct PanelDef {
+    title: String,
+    factory: Arc<dyn Fn() -> ViewRef + Send + Sync>,
+}
+
+type PanelRegistry = Arc<HashMap<String, PanelDef>>;
+
+fn style_dock_area(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Row)
+        .align_items(ui::AlignItems::Stretch)
+        .flex_grow(1.);
+}
+
+fn style_dock_split_row(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Row)
+        .align_items(ui::AlignItems::Stretch)
+        .flex_grow(1.);
+}
+
+fn style_dock_split_column(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Column)
+        .align_items(ui::AlignItems::Stretch)
+        .flex_grow(1.);
+}
+
+fn style_dock_slot(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_shrink(1.)
+        .min_width(0)
+        .min_height(0);
+}
+
+fn style_dock_slot_grow(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_grow(1.)
+        .flex_shrink(1.)
+        .min_width(0)
+        .min_height(0);
+}
+
+fn style_dock_tabs(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Column)
+        .align_items(ui::AlignItems::Stretch)
+        .flex_grow(1.)
+        .background_color(colors::U2);
+}
+
+fn style_dock_tab_bar(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Row)
+        .align_items(ui::AlignItems::Center)
+        .height(24)
+        .background_color(colors::U1);
+}
+
+fn style_dock_tab(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .align_items(ui::AlignItems::Center)
+        .height(ui::Val::Percent(100.))
+        .padding((8, 0))
+        .text_overflow(TextOverflow::Ellipsis);
+}
+
+fn style_dock_content(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Column)
+        .flex_grow(1.);
+}
+
+fn style_dock_drop_zone(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::None)
+        .position(ui::PositionType::Absolute)
+        .background_color(colors::FOCUS)
+        .border(2)
+        .border_color(colors::ACCENT)
+        .pointer_events(false);
+}
+
+fn style_dock_floating(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Column)
+        .position(ui::PositionType::Absolute)
+        .border(1)
+        .border_color(colors::U3)
+        .background_color(colors::U2);
+}
+
+/// An editor-style docking area. Panels are arranged according to the [`DockLayout`]
+/// resource: they can be rearranged by dragging their tabs onto another tab group (dropping
+/// on an edge splits the group), or floated into a separate window by dropping them anywhere
+/// else.
+#[derive(Clone, Default)]
+pub struct DockArea {
+    /// Additional styles to be applied to the dock area.
+    pub style: StyleHandle,
+
+    panels: PanelRegistry,
+}
+
+impl DockArea {
+    /// Create a new dock area.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set additional styles to be applied to the dock area.
+    pub fn style<S: StyleTuple + 'static>(mut self, style: S) -> Self {
+        self.style = style.into_handle();
+        self
+    }
+
+    /// Register a panel which can appear in the layout. The `id` is used to refer to the
+    /// panel in the [`DockLayout`], and `factory` constructs its content.
+    pub fn panel<V: IntoView, F: Fn() -> V + Send + Sync + 'static>(
+        mut self,
+        id: impl Into<String>,
+        title: impl Into<String>,
+        factory: F,
+    ) -> Self {
+        Arc::make_mut(&mut self.panels).insert(
+            id.into(),
+            PanelDef {
+                title: title.into(),
+                factory: Arc::new(move || factory().into_view()),
+            },
+        );
+        self
+    }
+}
+
+impl ViewTemplate for DockArea {
+    fn create(&self, cx: &mut Cx) -> impl IntoView {
+        let root = cx.create_memo(|cx| cx.use_resource::<DockLayout>().root());
+        let panels = self.panels.clone();
+        let floating_panels = self.panels.clone();
+
+        Element::<NodeBundle>::new()
+            .named("DockArea")
+            .style((style_dock_area, self.style.clone()))
+            .children((
+                DynamicKeyed::new(
+                    move |cx| root.get(cx),
+                    move |index| DockNodeView {
+                        index,
+                        panels: panels.clone(),
+                    },
+                ),
+                For::each(
+                    |rcx| {
+                        rcx.use_resource::<DockLayout>()
+                            .floating()
+                            .iter()
+                            .map(|f| f.panel.clone())
+                            .collect::<Vec<_>>()
+                            .into_iter()
+                    },
+                    move |panel| FloatingPanelView {
+                        panel: panel.clone(),
+                        panels: floating_panels.clone(),
+                    },
+                ),
+            ))
+    }
+}
+
+/// The parts of a node which require its view to be rebuilt when they change.
+#[derive(Clone, PartialEq)]
+enum NodeShape {
+    Empty,
+    Split(DockAxis, usize, usize),
+    Tabs(Vec<String>),
+}
+
+/// Displays a node of the layout tree, rebuilding when the node's structure changes.
+struct DockNodeView {
+    index: usize,
+    panels: PanelRegistry,
+}
+
+impl ViewTemplate for DockNodeView {
+    fn create(&self, cx: &mut Cx) -> impl IntoView {
+        let index = self.index;
+        let shape = cx.create_memo(
+            move |cx| match cx.use_resource::<DockLayout>().node(index) {
+                Some(DockNode::Split {
+                    axis,
+                    first,
+                    second,
+                    ..
+                }) => NodeShape::Split(*axis, *first, *second),
+                Some(DockNode::Tabs { panels, .. }) => NodeShape::Tabs(panels.clone()),
+                _ => NodeShape::Empty,
+            },
+        );
+        let panels = self.panels.clone();
+
+        DynamicKeyed::new(
+            move |cx| shape.get_clone(cx),
+            move |shape| match shape {
+                NodeShape::Split(axis, first, second) => DockSplitView {
+                    index,
+                    axis,
+                    first,
+                    second,
+                    panels: panels.clone(),
+                }
+                .into_view(),
+                NodeShape::Tabs(ids) => DockTabsView {
+                    index,
+                    ids,
+                    panels: panels.clone(),
+                }
+                .into_view(),
+                NodeShape::Empty => ().into_view(),
+            },
+        )
+    }
+}
+
+struct DockSplitView {
+    index: usize,
+    axis: DockAxis,
+    first: usize,
+    second: usize,
+    panels: PanelRegistry,
+}
+
+impl ViewTemplate for DockSplitView {
+    fn create(&self, cx: &mut Cx) -> impl IntoView {
+        let id = cx.create_entity();
+        let index = self.index;
+        let axis = self.axis;
+        let ratio = cx.create_memo(
+            move |cx| match cx.use_resource::<DockLayout>().node(index) {
+                Some(DockNode::Split { ratio, .. }) => *ratio,
+                _ => 0.5,
+            },
+        );
+
+        // Size of the split along its axis.
+        let extent = cx.create_derived(move |cx| {
+            cx.use_component::<Node>(id).map_or(0., |node| match axis {
+                DockAxis::Horizontal => node.size().x,
+                DockAxis::Vertical => node.size().y,
+            })
+        });
+
+        // A vertical splitter measures the size of the first child; a horizontal splitter
+        // measures the size of the second.
+        let value = cx.create_derived(move |cx| match axis {
+            DockAxis::Horizontal => ratio.get(cx) * extent.get(cx),
+            DockAxis::Vertical => (1. - ratio.get(cx)) * extent.get(cx),
+        });
+        let on_change = cx.create_callback(move |cx: &mut Cx, value: f32| {
+            let extent = extent.get(cx);
+            if extent > 0. {
+                let ratio = match axis {
+                    DockAxis::Horizontal => value / extent,
+                    DockAxis::Vertical => 1. - value / extent,
+                };
+                cx.world_mut()
+                    .resource_mut::<DockLayout>()
+                    .set_ratio(index, ratio.clamp(0.05, 0.95));
+            }
+        });
+
+        let (style_split, direction): (fn(&mut StyleBuilder), _) = match axis {
+            DockAxis::Horizontal => (style_dock_split_row, SplitterDirection::Vertical),
+            DockAxis::Vertical => (style_dock_split_column, SplitterDirection::Horizontal),
+        };
+
+        Element::<NodeBundle>::for_entity(id)
+            .named("DockSplit")
+            .style(style_split)
+            .children((
+                Element::<NodeBundle>::new()
+                    .named("DockSplit::First")
+                    .style(style_dock_slot)
+                    .create_effect(move |cx, ent| {
+                        let size = ui::Val::Percent(ratio.get(cx) * 100.);
+                        let mut style = cx.world_mut().get_mut::<Style>(ent).unwrap();
+                        match axis {
+                            DockAxis::Horizontal => style.width = size,
+                            DockAxis::Vertical => style.height = size,
+                        }
+                    })
+                    .children(DockNodeView {
+                        index: self.first,
+                        panels: self.panels.clone(),
+                    }),
+                Splitter::new()
+                    .direction(direction)
+                    .value(value)
+                    .on_change(on_change),
+                Element::<NodeBundle>::new()
+                    .named("DockSplit::Second")
+                    .style(style_dock_slot_grow)
+                    .children(DockNodeView {
+                        index: self.second,
+                        panels: self.panels.clone(),
+                    }),
+            ))
+    }
+}
+
+/// Return the drop zone under the pointer for the tab group displayed by `entity`.
+fn zone_at(world: &World, entity: Entity, position: Vec2) -> Option<DockZone> {
+    let ent = world.entity(entity);
+    let node = ent.get::<Node>()?;
+    let transform = ent.get::<GlobalTransform>()?;
+    Some(DockZone::from_position(
+        position,
+        node.logical_rect(transform),
+    ))
+}
+
+struct DockTabsView {
+    index: usize,
+    ids: Vec<String>,
+    panels: PanelRegistry,
+}
+
+impl ViewTemplate for DockTabsView {
+    fn create(&self, cx: &mut Cx) -> impl IntoView {
+        let id = cx.create_entity();
+        let index = self.index;
+        let active = cx.create_memo(
+            move |cx| match cx.use_resource::<DockLayout>().node(index) {
+                Some(DockNode::Tabs { panels, active }) => panels.get(*active).cloned(),
+                _ => None,
+            },
+        );
+        let hover_zone =
+            cx.create_memo(move |cx| match cx.use_resource::<DockDragState>().target {
+                Some((target, zone)) if target == index => Some(zone),
+                _ => None,
+            });
+        let selected = self
+            .ids
+            .iter()
+            .map(|panel| {
+                let panel = panel.clone();
+                cx.create_memo(move |cx| active.get_clone(cx).as_ref() == Some(&panel))
+            })
+            .collect::<Vec<_>>();
+        let tabs = self
+            .ids
+            .iter()
+            .zip(selected.iter())
+            .map(|(panel, selected)| {
+                DockTab {
+                    panel: panel.clone(),
+                    title: self
+                        .panels
+                        .get(panel)
+                        .map_or(panel.clone(), |p| p.title.clone()),
+                    selected: *selected,
+                }
+                .into_view()
+            })
+            .collect::<Vec<_>>();
+
+        // The content of each panel is built the first time its tab is selected, and is then
+        // hidden rather than despawned while another tab is selected, so that it keeps its
+        // state, such as scroll positions and text being edited.
+        let contents = self
+            .ids
+            .iter()
+            .zip(selected.iter())
+            .map(|(panel, selected)| {
+                let selected = *selected;
+                let visited = cx.create_mutable(false);
+                let factory = self.panels.get(panel).map(|def| def.factory.clone());
+                Element::<NodeBundle>::new()
+                    .named("DockTabs::Panel")
+                    .style(style_dock_content)
+                    .create_effect(move |cx, ent| {
+                        let shown = selected.get(cx);
+                        if shown {
+                            visited.set(cx, true);
+                        }
+                        let mut style = cx.world_mut().get_mut::<Style>(ent).unwrap();
+                        style.display = match shown {
+                            true => ui::Display::Flex,
+                            false => ui::Display::None,
+                        };
+                    })
+                    .children(Cond::new(
+                        move |cx| visited.get(cx),
+                        move || match &factory {
+                            Some(factory) => factory(),
+                            None => ().into_view(),
+                        },
+                        || (),
+                    ))
+                    .into_view()
+            })
+            .collect::<Vec<_>>();
+
+        Element::<NodeBundle>::for_entity(id)
+            .named("DockTabs")
+            .style(style_dock_tabs)
+            .insert((
+                On::<Pointer<DragOver>>::run(move |world: &mut World| {
+                    if world.resource::<DockDragState>().panel.is_none() {
+                        return;
+                    }
+                    let position = world
+                        .resource::<ListenerInput<Pointer<DragOver>>>()
+                        .pointer_location
+                        .position;
+                    let target = zone_at(world, id, position).map(|zone| (index, zone));
+                    if world.resource::<DockDragState>().target != target {
+                        world.resource_mut::<DockDragState>().target = target;
+                    }
+                }),
+                On::<Pointer<DragLeave>>::run(move |world: &mut World| {
+                    let mut state = world.resource_mut::<DockDragState>();
+                    if matches!(state.target, Some((target, _)) if target == index) {
+                        state.target = None;
+                    }
+                }),
+                On::<Pointer<DragDrop>>::run(move |world: &mut World| {
+                    let position = world
+                        .resource::<ListenerInput<Pointer<DragDrop>>>()
+                        .pointer_location
+                        .position;
+                    let zone = zone_at(world, id, position);
+                    // Taking the panel here prevents the tab's `DragEnd` handler, which runs
+                    // after this, from floating it.
+                    let mut state = world.resource_mut::<DockDragState>();
+                    let panel = state.panel.take();
+                    state.target = None;
+                    if let (Some(panel), Some(zone)) = (panel, zone) {
+                        world.resource_mut::<DockLayout>().dock(panel, index, zone);
+                    }
+                }),
+            ))
+            .children((
+                Element::<NodeBundle>::new()
+                    .named("DockTabs::Bar")
+                    .style(style_dock_tab_bar)
+                    .children(tabs.to_child_array()),
+                Element::<NodeBundle>::new()
+                    .named("DockTabs::Content")
+                    .style(style_dock_content)
+                    .children(contents.to_child_array()),
+                Element::<NodeBundle>::new()
+                    .named("DockTabs::DropZone")
+                    .style(style_dock_drop_zone)
+                    .create_effect(move |cx, ent| {
+                        let zone = hover_zone.get(cx);
+                        let mut style = cx.world_mut().get_mut::<Style>(ent).unwrap();
+                        match zone {
+                            Some(zone) => {
+                                let (left, top, width, height) = zone.highlight();
+                                style.display = ui::Display::Flex;
+                                style.left = ui::Val::Percent(left);
+                                style.top = ui::Val::Percent(top);
+                                style.width = ui::Val::Percent(width);
+                                style.height = ui::Val::Percent(height);
+                            }
+                            None => style.display = ui::Display::None,
+                        }
+                    }),
+            ))
+    }
+}
+
+/// A draggable tab representing a panel.
+struct DockTab {
+    panel: String,
+    title: String,
+    selected: Signal<bool>,
+}
+
+impl ViewTemplate for DockTab {
+    fn create(&self, cx: &mut Cx) -> impl IntoView {
+        let id = cx.create_entity();
+        let hovering = cx.create_hover_signal(id);
+        let selected = self.selected.clone();
+        let panel = self.panel.clone();
+
+        Element::<NodeBundle>::for_entity(id)
+            .named("DockTab")
+            .style(style_dock_tab)
+            .insert((
+                On::<Pointer<Click>>::run({
+                    let panel = panel.clone();
+                    move |world: &mut World| {
+                        world.resource_mut::<DockLayout>().set_active_panel(&panel);
+                    }
+                }),
+                On::<Pointer<DragStart>>::run({
+                    let panel = panel.clone();
+                    move |world: &mut World| {
+                        world.resource_mut::<DockDragState>().panel = Some(panel.clone());
+                    }
+                }),
+                On::<Pointer<DragEnd>>::run(move |world: &mut World| {
+                    let position = world
+                        .resource::<ListenerInput<Pointer<DragEnd>>>()
+                        .pointer_location
+                        .position;
+                    let mut state = world.resource_mut::<DockDragState>();
+                    let panel = state.panel.take();
+                    state.target = None;
+                    // The panel was not dropped on a tab group, so float it.
+                    if let Some(panel) = panel {
+                        world
+                            .resource_mut::<DockLayout>()
+                            .float(panel, position - FLOATING_GRAB_OFFSET);
+                    }
+                }),
+            ))
+            .create_effect(move |cx, ent| {
+                let color = match (selected.get(cx), hovering.get(cx)) {
+                    (true, _) => colors::U2,
+                    (false, true) => colors::U1.lighter(0.02),
+                    (false, false) => colors::U1,
+                };
+                let mut bg = cx.world_mut().get_mut::<BackgroundColor>(ent).unwrap();
+                bg.0 = color.into();
+            })
+            .children(self.title.clone())
+    }
+}
+
+/// A panel floating in its own window.
+struct FloatingPanelView {
+    panel: String,
+    panels: PanelRegistry,
+}
+
+impl ViewTemplate for FloatingPanelView {
+    fn create(&self, cx: &mut Cx) -> impl IntoView {
+        let rect = cx.create_memo({
+            let panel = self.panel.clone();
+            move |cx| {
+                cx.use_resource::<DockLayout>()
+                    .floating()
+                    .iter()
+                    .find(|f| f.panel == panel)
+                    .map(|f| (f.position, f.size))
+            }
+        });
+        let def = self.panels.get(&self.panel);
+
+        Portal::new(
+            Element::<NodeBundle>::new()
+                .named("DockFloating")
+                .style(style_dock_floating)
+                .create_effect(move |cx, ent| {
+                    if let Some((position, size)) = rect.get(cx) {
+                        let mut style = cx.world_mut().get_mut::<Style>(ent).unwrap();
+                        style.left = ui::Val::Px(position.x);
+                        style.top = ui::Val::Px(position.y);
+                        style.width = ui::Val::Px(size.x);
+                        style.height = ui::Val::Px(size.y);
+                    }
+                })
+                .children((
+                    Element::<NodeBundle>::new()
+                        .named("DockFloating::Bar")
+                        .style(style_dock_tab_bar)
+                        .children(DockTab {
+                            panel: self.panel.clone(),
+                            title: def.map_or(self.panel.clone(), |def| def.title.clone()),
+                            selected: Signal::Constant(true),
+                        }),
+                    Element::<NodeBundle>::new()
+                        .named("DockFloating::Content")
+                        .style(style_dock_content)
+                        .children(match def {
+                            Some(def) => (def.factory)(),
+                            None => ().into_view(),
+                        }),
+                )),
+        )
+        .layer("overlays")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tabs(layout: &DockLayout, index: usize) -> (Vec<&str>, usize) {
+        match layout.node(index) {
+            Some(DockNode::Tabs { panels, active }) => {
+                (panels.iter().map(String::as_str).collect(), *active)
+            }
+            node => panic!("Expected a tab group, found {:?}", node),
+        }
+    }
+
+    #[test]
+    fn test_zone_from_position() {
+        let rect = Rect::new(0., 0., 200., 100.);
+        assert_eq!(
+            DockZone::from_position(Vec2::new(10., 50.), rect),
+            DockZone::Left
+        );
+        assert_eq!(
+            DockZone::from_position(Vec2::new(190., 50.), rect),
+            DockZone::Right
+        );
+        assert_eq!(
+            DockZone::from_position(Vec2::new(100., 5.), rect),
+            DockZone::Top
+        );
+        assert_eq!(
+            DockZone::from_position(Vec2::new(100., 95.), rect),
+            DockZone::Bottom
+        );
+        assert_eq!(
+            DockZone::from_position(Vec2::new(100., 50.), rect),
+            DockZone::Center
+        );
+    }
+
+    #[test]
+    fn test_dock_and_remove() {
+        let mut layout = DockLayout::new(["scene", "assets", "log"]);
+        let root = layout.root();
+        layout.set_active_panel("log");
+        assert_eq!(tabs(&layout, root), (vec!["scene", "assets", "log"], 2));
+
+        // Docking at an edge splits the group in place, so the root index is unchanged.
+        layout.dock("log", root, DockZone::Right);
+        assert_eq!(layout.root(), root);
+        let Some(DockNode::Split {
+            axis,
+            first,
+            second,
+            ..
+        }) = layout.node(root).cloned()
+        else {
+            panic!("Expected a split");
+        };
+        assert_eq!(axis, DockAxis::Horizontal);
+        assert_eq!(tabs(&layout, first), (vec!["scene", "assets"], 1));
+        assert_eq!(tabs(&layout, second), (vec!["log"], 0));
+        assert_eq!(layout.find_panel("log"), Some(second));
+
+        // Docking a panel beside itself does nothing.
+        let before = layout.clone();
+        layout.dock("log", second, DockZone::Bottom);
+        assert_eq!(layout, before);
+
+        // Docking into the center adds a tab and selects it.
+        layout.dock("log", first, DockZone::Center);
+        assert_eq!(
+            tabs(&layout, layout.root()),
+            (vec!["scene", "assets", "log"], 2)
+        );
+
+        // Emptying a group collapses its split.
+        assert!(!layout.remove_panel("missing"));
+        layout.remove_panel("assets");
+        assert_eq!(tabs(&layout, layout.root()), (vec!["scene", "log"], 1));
+    }
+
+    #[test]
+    fn test_float() {
+        let mut layout = DockLayout::new(["scene", "log"]);
+        layout.float("log", Vec2::new(10., 20.));
+        assert_eq!(layout.find_panel("log"), None);
+        assert_eq!(
+            layout.floating(),
+            [FloatingPanel {
+                panel: "log".into(),
+                position: Vec2::new(10., 20.),
+                size: FLOATING_PANEL_SIZE,
+            }]
+        );
+
+        // Moving a floating panel keeps its size, and docking it removes the window.
+        layout.float("log", Vec2::new(50., 60.));
+        assert_eq!(layout.floating().len(), 1);
+        assert_eq!(layout.floating()[0].position, Vec2::new(50., 60.));
+        layout.dock("log", layout.root(), DockZone::Center);
+        assert!(layout.floating().is_empty());
+        assert_eq!(tabs(&layout, layout.root()), (vec!["scene", "log"], 1));
+    }
+}
@@ -0,0 +1,186 @@
+use bevy::{color::LinearRgba, prelude::*};
+use bevy_reactor::*;
+
+use crate::{colors, materials::DotGridMaterial};
+
+/// Whether a [`DotGrid`] draws dots at the grid points, or lines between them.
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum DotGridMode {
+    /// Draw a dot at each grid point.
+    #[default]
+    Dots,
+
+    /// Draw grid lines.
+    Lines,
+}
+
+/// A background grid of dots or lines, such as the backdrop of a node graph. The grid
+/// follows the `zoom` and `offset` signals, and each grid level fades out as it becomes too
+/// dense on screen, so zooming out doesn't produce a moiré of tiny dots.
+pub struct DotGrid {
+    /// Distance between minor grid points, in pixels at a zoom of 1.
+    pub spacing: Signal<f32>,
+
+    /// Diameter of the dots, or width of the lines, in screen pixels.
+    pub dot_size: Signal<f32>,
+
+    /// Number of minor cells per major cell, or 0 for no major grid.
+    pub major_every: Signal<u32>,
+
+    /// Zoom level of the content the grid is behind.
+    pub zoom: Signal<f32>,
+
+    /// Position in grid space of the top-left corner of the element.
+    pub offset: Signal<Vec2>,
+
+    /// Whether to draw dots or lines.
+    pub mode: Signal<DotGridMode>,
+
+    /// Background color.
+    pub color_bg: Color,
+
+    /// Color of the minor grid.
+    pub color_fg: Color,
+
+    /// Color of the major grid.
+    pub color_major: Color,
+
+    /// Content displayed over the grid.
+    pub children: ChildArray,
+
+    /// Additional styles to be applied to the grid element.
+    pub style: StyleHandle,
+}
+
+impl Default for DotGrid {
+    fn default() -> Self {
+        Self {
+            spacing: Signal::Constant(16.),
+            dot_size: Signal::Constant(1.5),
+            major_every: Signal::Constant(0),
+            zoom: Signal::Constant(1.),
+            offset: Signal::Constant(Vec2::ZERO),
+            mode: Signal::Constant(DotGridMode::Dots),
+            color_bg: colors::U1.into(),
+            color_fg: colors::U3.into(),
+            color_major: colors::U4.into(),
+            children: ChildArray::default(),
+            style: StyleHandle::default(),
+        }
+    }
+}
+
+impl DotGrid {
+    /// Create a new dot grid.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the distance between minor grid points.
+    pub fn spacing(mut self, spacing: impl IntoSignal<f32>) -> Self {
+        self.spacing = spacing.into_signal();
+        self
+    }
+
+    /// Set the diameter of the dots, or the width of the lines.
+    pub fn dot_size(mut self, dot_size: impl IntoSignal<f32>) -> Self {
+        self.dot_size = dot_size.into_signal();
+        self
+    }
+
+    /// Set the number of minor cells per major cell.
+    pub fn major_every(mut self, major_every: impl IntoSignal<u32>) -> Self {
+        self.major_every = major_every.into_signal();
+        self
+    }
+
+    /// Set the zoom level.
+    pub fn zoom(mut self, zoom: impl IntoSignal<f32>) -> Self {
+        self.zoom = zoom.into_signal();
+        self
+    }
+
+    /// Set the grid position of the top-left corner.
+    pub fn offset(mut self, offset: impl IntoSignal<Vec2>) -> Self {
+        self.offset = offset.into_signal();
+        self
+    }
+
+    /// Set whether to draw dots or lines.
+    pub fn mode(mut self, mode: impl IntoSignal<DotGridMode>) -> Self {
+        self.mode = mode.into_signal();
+        self
+    }
+
+    /// Set the background, minor and major grid colors.
+    pub fn colors(
+        mut self,
+        bg: impl Into<Color>,
+        fg: impl Into<Color>,
+        major: impl Into<Color>,
+    ) -> Self {
+        self.color_bg = bg.into();
+        self.color_fg = fg.into();
+        self.color_major = major.into();
+        self
+    }
+
+    /// Set the child views for this element.
+    pub fn children<V: ChildViewTuple>(mut self, children: V) -> Self {
+        self.children = children.to_child_array();
+        self
+    }
+
+    /// Set the additional styles for the grid element.
+    pub fn style<S: StyleTuple + 'static>(mut self, style: S) -> Self {
+        self.style = style.into_handle();
+        self
+    }
+}
+
+impl ViewTemplate for DotGrid {
+    fn create(&self, cx: &mut Cx) -> impl IntoView {
+        let spacing = self.spacing;
+        let dot_size = self.dot_size;
+        let major_every = self.major_every;
+        let zoom = self.zoom;
+        let offset = self.offset;
+        let mode = self.mode;
+
+        let mut ui_materials = cx
+            .world_mut()
+            .get_resource_mut::<Assets<DotGridMaterial>>()
+            .unwrap();
+        let material = ui_materials.add(DotGridMaterial {
+            color_bg: LinearRgba::from(self.color_bg).to_vec4(),
+            color_fg: LinearRgba::from(self.color_fg).to_vec4(),
+            color_major: LinearRgba::from(self.color_major).to_vec4(),
+            ..default()
+        });
+
+        Element::<MaterialNodeBundle<DotGridMaterial>>::new()
+            .named("DotGrid")
+            .insert(material.clone())
+            .style(self.style.clone())
+            .create_effect(move |cx, _| {
+                let spacing = spacing.get(cx);
+                let dot_size = dot_size.get(cx);
+                let major_every = major_every.get(cx);
+                let zoom = zoom.get(cx);
+                let offset = offset.get(cx);
+                let mode = mode.get(cx);
+                let mut ui_materials = cx
+                    .world_mut()
+                    .get_resource_mut::<Assets<DotGridMaterial>>()
+                    .unwrap();
+                let material = ui_materials.get_mut(material.id()).unwrap();
+                material.spacing = spacing.max(1.);
+                material.dot_size = dot_size;
+                material.major_every = major_every;
+                material.zoom = zoom.max(f32::EPSILON);
+                material.offset = offset;
+                material.lines = (mode == DotGridMode::Lines) as u32;
+            })
+            .children(self.children.clone())
+    }
+}
@@ -0,0 +1,528 @@
+use bevy::{color::Alpha, prelude::*, ui};
+use bevy_mod_picking::prelude::*;
+use bevy_reactor::*;
+
+use crate::{
+    colors,
+    controls::Icon,
+    elevation,
+    focus::{FocusScope, TabGroup},
+    pointer_capture::CapturePointer,
+    popup_stack::{Popup, PopupStack, StackedWindow, WindowStack},
+    typography::text_default,
+};
+
+fn style_window_overlay(ss: &mut StyleBuilder) {
+    ss.position(ui::PositionType::Absolute)
+        .left(0)
+        .top(0)
+        .right(0)
+        .bottom(0)
+        .background_color(colors::U2.with_alpha(0.5));
+}
+
+fn style_window(ss: &mut StyleBuilder) {
+    ss.position(ui::PositionType::Absolute)
+        .display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Column)
+        .align_items(ui::AlignItems::Stretch)
+        .background_color(colors::U2)
+        .border_color(colors::U1)
+        .border(1)
+        .border_radius(6.0);
+    elevation::elevation_3(ss);
+}
+
+fn style_window_title_bar(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Row)
+        .align_items(ui::AlignItems::Center)
+        .flex_shrink(0.)
+        .height(24)
+        .padding((8, 0))
+        .gap(4)
+        .background_color(colors::U3)
+        .border_radius(ui::BorderRadius::top(ui::Val::Px(5.0)));
+}
+
+fn style_window_title(ss: &mut StyleBuilder) {
+    ss.flex_grow(1.)
+        .font_size(14)
+        .overflow(ui::OverflowAxis::Clip)
+        .pointer_events(false);
+}
+
+fn style_window_close(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .align_items(ui::AlignItems::Center)
+        .justify_content(ui::JustifyContent::Center)
+        .width(16)
+        .height(16)
+        .margin_right(-4);
+}
+
+fn style_window_content(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Column)
+        .flex_grow(1.)
+        .min_height(0)
+        .overflow(ui::OverflowAxis::Clip);
+}
+
+fn style_resize_handle(ss: &mut StyleBuilder) {
+    ss.position(ui::PositionType::Absolute);
+}
+
+/// Thickness of the resize handles along the window edges.
+const HANDLE_SIZE: f32 = 6.;
+
+/// Size of the resize handles at the window corners.
+const CORNER_SIZE: f32 = 10.;
+
+/// Which edges of the window a resize handle moves.
+#[derive(Clone, Copy, PartialEq, Default)]
+struct ResizeEdges {
+    left: bool,
+    right: bool,
+    top: bool,
+    bottom: bool,
+}
+
+impl ResizeEdges {
+    const ALL: [ResizeEdges; 8] = [
+        ResizeEdges::new(true, false, false, false),
+        ResizeEdges::new(false, true, false, false),
+        ResizeEdges::new(false, false, true, false),
+        ResizeEdges::new(false, false, false, true),
+        ResizeEdges::new(true, false, true, false),
+        ResizeEdges::new(false, true, true, false),
+        ResizeEdges::new(true, false, false, true),
+        ResizeEdges::new(false, true, false, true),
+    ];
+
+    const fn new(left: bool, right: bool, top: bool, bottom: bool) -> Self {
+        Self {
+            left,
+            right,
+            top,
+            bottom,
+        }
+    }
+
+    /// Apply a drag of `delta` to the window rect `start`, keeping the window at least
+    /// `min_size` and leaving the opposite edges in place.
+    fn apply(&self, start: Rect, delta: Vec2, min_size: Vec2) -> Rect {
+        let mut rect = start;
+        if self.left {
+            rect.min.x = (start.min.x + delta.x).min(start.max.x - min_size.x);
+        }
+        if self.right {
+            rect.max.x = (start.max.x + delta.x).max(start.min.x + min_size.x);
+        }
+        if self.top {
+            rect.min.y = (start.min.y + delta.y).min(start.max.y - min_size.y);
+        }
+        if self.bottom {
+            rect.max.y = (start.max.y + delta.y).max(start.min.y + min_size.y);
+        }
+        rect
+    }
+
+    fn style(&self, ss: &mut StyleBuilder) {
+        let offset = ui::Val::Px(-HANDLE_SIZE * 0.5);
+        let corner = (self.left || self.right) && (self.top || self.bottom);
+        let size = if corner { CORNER_SIZE } else { HANDLE_SIZE };
+        if self.left {
+            ss.left(offset).width(size);
+        } else if self.right {
+            ss.right(offset).width(size);
+        } else {
+            ss.left(CORNER_SIZE).right(CORNER_SIZE);
+        }
+        if self.top {
+            ss.top(offset).height(size);
+        } else if self.bottom {
+            ss.bottom(offset).height(size);
+        } else {
+            ss.top(CORNER_SIZE).bottom(CORNER_SIZE);
+        }
+    }
+}
+
+/// A free-floating panel with a title bar, which can be dragged and resized. Clicking on the
+/// window brings it to the front of the popup stack. Modal windows block interaction with the
+/// rest of the UI and trap keyboard focus.
+pub struct FloatingWindow {
+    /// Signal that controls whether the window is open.
+    pub open: Signal<bool>,
+
+    /// The title displayed in the title bar.
+    pub title: Signal<String>,
+
+    /// Initial position of the top-left corner of the window, in logical pixels.
+    pub position: Vec2,
+
+    /// Initial size of the window, in logical pixels.
+    pub size: Vec2,
+
+    /// Minimum size of the window when resizing.
+    pub min_size: Vec2,
+
+    /// Whether the window can be resized by dragging its edges and corners.
+    pub resizable: bool,
+
+    /// Whether the window is modal.
+    pub modal: bool,
+
+    /// The content of the window.
+    pub children: ChildArray,
+
+    /// Additional styles to be applied to the window.
+    pub style: StyleHandle,
+
+    /// Callback called when the close button is clicked. A modal window is also closed when
+    /// Escape is pressed while it is the topmost popup. If `None`, no close button is shown.
+    ///
+    /// Non-modal windows are kept in the [`WindowStack`], below dialogs and menus, and are not
+    /// closed by Escape.
+    pub on_close: Option<Callback>,
+}
+
+impl FloatingWindow {
+    /// Create a new floating window.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the signal that controls whether the window is open.
+    pub fn open(mut self, open: impl IntoSignal<bool>) -> Self {
+        self.open = open.into_signal();
+        self
+    }
+
+    /// Set the title displayed in the title bar.
+    pub fn title(mut self, title: impl IntoSignal<String>) -> Self {
+        self.title = title.into_signal();
+        self
+    }
+
+    /// Set the initial position of the window.
+    pub fn position(mut self, position: Vec2) -> Self {
+        self.position = position;
+        self
+    }
+
+    /// Set the initial size of the window.
+    pub fn size(mut self, size: Vec2) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Set the minimum size of the window.
+    pub fn min_size(mut self, min_size: Vec2) -> Self {
+        self.min_size = min_size;
+        self
+    }
+
+    /// Set whether the window can be resized.
+    pub fn resizable(mut self, resizable: bool) -> Self {
+        self.resizable = resizable;
+        self
+    }
+
+    /// Set whether the window is modal.
+    pub fn modal(mut self, modal: bool) -> Self {
+        self.modal = modal;
+        self
+    }
+
+    /// Set the content of the window.
+    pub fn children<V: ChildViewTuple>(mut self, children: V) -> Self {
+        self.children = children.to_child_array();
+        self
+    }
+
+    /// Set additional styles to be applied to the window.
+    pub fn style<S: StyleTuple + 'static>(mut self, style: S) -> Self {
+        self.style = style.into_handle();
+        self
+    }
+
+    /// Set the callback called when the window is closed.
+    pub fn on_close(mut self, on_close: Callback) -> Self {
+        self.on_close = Some(on_close);
+        self
+    }
+}
+
+impl Default for FloatingWindow {
+    fn default() -> Self {
+        Self {
+            open: Signal::Constant(true),
+            title: Signal::default(),
+            position: Vec2::new(100., 100.),
+            size: Vec2::new(300., 200.),
+            min_size: Vec2::new(120., 80.),
+            resizable: true,
+            modal: false,
+            children: ChildArray::default(),
+            style: StyleHandle::default(),
+            on_close: None,
+        }
+    }
+}
+
+impl ViewTemplate for FloatingWindow {
+    fn create(&self, cx: &mut Cx) -> impl IntoView {
+        let open = self.open;
+        let rect = cx.create_mutable(Rect::from_corners(self.position, self.position + self.size));
+
+        let title = self.title.clone();
+        let min_size = self.min_size;
+        let resizable = self.resizable;
+        let modal = self.modal;
+        let children = self.children.clone();
+        let style = self.style.clone();
+        let on_close = self.on_close;
+
+        Cond::new(
+            move |cx| open.get(cx),
+            move || WindowFrame {
+                title: title.clone(),
+                rect,
+                min_size,
+                resizable,
+                modal,
+                children: children.clone(),
+                style: style.clone(),
+                on_close,
+            },
+            || (),
+        )
+    }
+}
+
+/// The contents of an open [`FloatingWindow`]. The window rect lives in the parent so that
+/// it is preserved when the window is closed and re-opened.
+struct WindowFrame {
+    title: Signal<String>,
+    rect: Mutable<Rect>,
+    min_size: Vec2,
+    resizable: bool,
+    modal: bool,
+    children: ChildArray,
+    style: StyleHandle,
+    on_close: Option<Callback>,
+}
+
+impl ViewTemplate for WindowFrame {
+    fn create(&self, cx: &mut Cx) -> impl IntoView {
+        // The outermost node: the overlay for modal windows, otherwise the window itself.
+        let root_id = cx.create_entity();
+        let rect = self.rect;
+        // The window rect at the start of the current move or resize.
+        let drag_start = cx.create_mutable::<Option<Rect>>(None);
+        let min_size = self.min_size;
+        let resizable = self.resizable;
+        let on_close = self.on_close;
+
+        let window = match self.modal {
+            true => Element::<NodeBundle>::new(),
+            false => Element::<NodeBundle>::for_entity(root_id),
+        }
+        .named("FloatingWindow")
+        .style((text_default, style_window, self.style.clone()))
+        .create_effect(move |cx, ent| {
+            let rect = rect.get(cx);
+            let mut style = cx.world_mut().get_mut::<Style>(ent).unwrap();
+            style.left = ui::Val::Px(rect.min.x);
+            style.top = ui::Val::Px(rect.min.y);
+            style.width = ui::Val::Px(rect.width());
+            style.height = ui::Val::Px(rect.height());
+        })
+        .children((
+            WindowTitleBar {
+                title: self.title.clone(),
+                rect,
+                drag_start,
+                on_close,
+            },
+            Element::<NodeBundle>::new()
+                .named("FloatingWindow::Content")
+                .style(style_window_content)
+                .children(self.children.clone()),
+            Cond::new(
+                move |_cx| resizable,
+                move || {
+                    Fragment::new(
+                        ResizeEdges::ALL
+                            .iter()
+                            .map(|edges| {
+                                ResizeHandle {
+                                    edges: *edges,
+                                    rect,
+                                    drag_start,
+                                    min_size,
+                                }
+                                .into_view()
+                            })
+                            .collect::<Vec<_>>(),
+                    )
+                },
+                || (),
+            ),
+        ));
+
+        match self.modal {
+            true => Portal::new(
+                Element::<NodeBundle>::for_entity(root_id)
+                    .named("FloatingWindow::Overlay")
+                    .style(style_window_overlay)
+                    .insert((
+                        Popup { on_close },
+                        // Clicking anywhere in the window brings it to the front.
+                        On::<Pointer<Down>>::run(move |world: &mut World| {
+                            world.resource_mut::<PopupStack>().raise(root_id);
+                        }),
+                    ))
+                    .children(window.insert((
+                        TabGroup {
+                            order: 0,
+                            modal: true,
+                        },
+                        FocusScope,
+                    ))),
+            )
+            .layer("dialogs"),
+            false => Portal::new(window.insert((
+                StackedWindow,
+                On::<Pointer<Down>>::run(move |world: &mut World| {
+                    world.resource_mut::<WindowStack>().raise(root_id);
+                }),
+            )))
+            .layer("windows"),
+        }
+    }
+}
+
+struct WindowTitleBar {
+    title: Signal<String>,
+    rect: Mutable<Rect>,
+    drag_start: Mutable<Option<Rect>>,
+    on_close: Option<Callback>,
+}
+
+impl ViewTemplate for WindowTitleBar {
+    fn create(&self, _cx: &mut Cx) -> impl IntoView {
+        let title = self.title.clone();
+        let rect = self.rect;
+        let drag_start = self.drag_start;
+        let on_close = self.on_close;
+
+        Element::<NodeBundle>::new()
+            .named("FloatingWindow::TitleBar")
+            .style(style_window_title_bar)
+            .insert((
+                CapturePointer,
+                On::<Pointer<DragStart>>::run(move |world: &mut World| {
+                    let mut event = world
+                        .get_resource_mut::<ListenerInput<Pointer<DragStart>>>()
+                        .unwrap();
+                    event.stop_propagation();
+                    let start = rect.get(world);
+                    drag_start.set(world, Some(start));
+                }),
+                On::<Pointer<Drag>>::run(move |world: &mut World| {
+                    if let Some(start) = drag_start.get(world) {
+                        let event = world
+                            .get_resource::<ListenerInput<Pointer<Drag>>>()
+                            .unwrap();
+                        let delta = event.distance;
+                        rect.set(
+                            world,
+                            Rect::from_corners(start.min + delta, start.max + delta),
+                        );
+                    }
+                }),
+                On::<Pointer<DragEnd>>::run(move |world: &mut World| {
+                    drag_start.set(world, None);
+                }),
+            ))
+            .children((
+                Element::<NodeBundle>::new()
+                    .style(style_window_title)
+                    .children(text_computed(move |cx| title.get_clone(cx))),
+                Cond::new(
+                    move |_cx| on_close.is_some(),
+                    move || {
+                        Element::<NodeBundle>::new()
+                            .named("FloatingWindow::Close")
+                            .style(style_window_close)
+                            .insert(On::<Pointer<Click>>::run(move |world: &mut World| {
+                                let mut event = world
+                                    .get_resource_mut::<ListenerInput<Pointer<Click>>>()
+                                    .unwrap();
+                                event.stop_propagation();
+                                if let Some(on_close) = on_close {
+                                    world.run_callback(on_close, ());
+                                }
+                            }))
+                            .children(
+                                Icon::new("obsidian_ui://icons/close.png")
+                                    .size(Vec2::splat(12.))
+                                    .style(|ss: &mut StyleBuilder| {
+                                        ss.pointer_events(false);
+                                    }),
+                            )
+                    },
+                    || (),
+                ),
+            ))
+    }
+}
+
+struct ResizeHandle {
+    edges: ResizeEdges,
+    rect: Mutable<Rect>,
+    drag_start: Mutable<Option<Rect>>,
+    min_size: Vec2,
+}
+
+impl ViewTemplate for ResizeHandle {
+    fn create(&self, _cx: &mut Cx) -> impl IntoView {
+        let edges = self.edges;
+        let rect = self.rect;
+        let drag_start = self.drag_start;
+        let min_size = self.min_size;
+
+        Element::<NodeBundle>::new()
+            .named("FloatingWindow::ResizeHandle")
+            .style((style_resize_handle, move |ss: &mut StyleBuilder| {
+                edges.style(ss);
+            }))
+            .insert((
+                CapturePointer,
+                On::<Pointer<DragStart>>::run(move |world: &mut World| {
+                    let mut event = world
+                        .get_resource_mut::<ListenerInput<Pointer<DragStart>>>()
+                        .unwrap();
+                    event.stop_propagation();
+                    let start = rect.get(world);
+                    drag_start.set(world, Some(start));
+                }),
+                On::<Pointer<Drag>>::run(move |world: &mut World| {
+                    if let Some(start) = drag_start.get(world) {
+                        let mut event = world
+                            .get_resource_mut::<ListenerInput<Pointer<Drag>>>()
+                            .unwrap();
+                        event.stop_propagation();
+                        let delta = event.distance;
+                        rect.set(world, edges.apply(start, delta, min_size));
+                    }
+                }),
+                On::<Pointer<DragEnd>>::run(move |world: &mut World| {
+                    drag_start.set(world, None);
+                }),
+            ))
+    }
+}
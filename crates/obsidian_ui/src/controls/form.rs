@@ -0,0 +1,718 @@
+use std::sync::Arc;
+
+use bevy::{prelude::*, ui};
+use bevy_reactor::*;
+
+use crate::{
+    colors,
+    controls::{Button, ButtonVariant, Checkbox, TextInput, TextInputProps},
+    hooks::{CreateDisabledSignal, CreateFocusSignal},
+    size::Size,
+};
+
+fn style_form(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Column)
+        .align_items(ui::AlignItems::Stretch)
+        .gap(8);
+}
+
+fn style_form_field(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Column)
+        .align_items(ui::AlignItems::Stretch)
+        .gap(2);
+}
+
+fn style_form_field_label(ss: &mut StyleBuilder) {
+    ss.font_size(14).color(colors::DIM);
+}
+
+fn style_form_field_error(ss: &mut StyleBuilder) {
+    ss.font_size(12).color(colors::DESTRUCTIVE_ACC);
+}
+
+/// A typed value held by a form field.
+#[derive(Clone, PartialEq, Debug)]
+pub enum FormValue {
+    /// A text value, e.g. from a [`TextInput`].
+    Text(String),
+    /// A boolean value, e.g. from a [`Checkbox`].
+    Bool(bool),
+    /// A numeric value.
+    Number(f32),
+}
+
+impl FormValue {
+    /// Returns the text value, or `None` if this is not a text value.
+    pub fn as_text(&self) -> Option<&str> {
+        match self {
+            FormValue::Text(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    /// Returns the boolean value, or `None` if this is not a boolean value.
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            FormValue::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Returns the numeric value, or `None` if this is not a numeric value.
+    pub fn as_number(&self) -> Option<f32> {
+        match self {
+            FormValue::Number(value) => Some(*value),
+            _ => None,
+        }
+    }
+}
+
+/// A validation rule for a form field. The rule returns an error message if the value is
+/// invalid.
+#[derive(Clone)]
+pub struct Validator(Arc<dyn Fn(&FormValue) -> Option<String> + Send + Sync>);
+
+impl Validator {
+    /// Create a validator from a function which returns an error message for invalid values.
+    pub fn new(rule: impl Fn(&FormValue) -> Option<String> + Send + Sync + 'static) -> Self {
+        Self(Arc::new(rule))
+    }
+
+    /// A validator which rejects empty (or all-whitespace) text and unchecked checkboxes.
+    pub fn required(message: impl Into<String>) -> Self {
+        let message = message.into();
+        Self::new(move |value| {
+            let present = match value {
+                FormValue::Text(text) => !text.trim().is_empty(),
+                FormValue::Bool(checked) => *checked,
+                FormValue::Number(_) => true,
+            };
+            (!present).then(|| message.clone())
+        })
+    }
+
+    /// Check a value against this rule, returning an error message if it is invalid.
+    pub fn validate(&self, value: &FormValue) -> Option<String> {
+        (self.0)(value)
+    }
+}
+
+/// Run a list of validators, returning the first error.
+fn validate_all(validators: &[Validator], value: &FormValue) -> Option<String> {
+    validators.iter().find_map(|v| v.validate(value))
+}
+
+/// The state of a single field registered with a [`Form`].
+#[derive(Clone, PartialEq, Debug)]
+pub struct FieldState {
+    /// The name the field was registered under.
+    pub name: String,
+
+    /// The current value of the field.
+    pub value: FormValue,
+
+    /// The value of the field when it was registered.
+    pub initial: FormValue,
+
+    /// True once the user has left the field after focusing it, or has attempted to submit
+    /// the form. Validation errors are only displayed for touched fields.
+    pub touched: bool,
+
+    /// The validation error for the current value, if any.
+    pub error: Option<String>,
+}
+
+impl FieldState {
+    /// True if the value has changed since the field was registered.
+    pub fn is_dirty(&self) -> bool {
+        self.value != self.initial
+    }
+
+    /// True if the current value failed validation.
+    pub fn is_invalid(&self) -> bool {
+        self.error.is_some()
+    }
+}
+
+/// The values of all of the fields in a form, passed to the form's `on_submit` callback.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct FormValues(Vec<(String, FormValue)>);
+
+impl FormValues {
+    /// Returns the value of the named field.
+    pub fn get(&self, name: &str) -> Option<&FormValue> {
+        self.0.iter().find(|(n, _)| n == name).map(|(_, v)| v)
+    }
+
+    /// Returns the value of the named text field.
+    pub fn text(&self, name: &str) -> Option<&str> {
+        self.get(name).and_then(FormValue::as_text)
+    }
+
+    /// Returns the value of the named boolean field.
+    pub fn bool(&self, name: &str) -> Option<bool> {
+        self.get(name).and_then(FormValue::as_bool)
+    }
+
+    /// Returns the value of the named numeric field.
+    pub fn number(&self, name: &str) -> Option<f32> {
+        self.get(name).and_then(FormValue::as_number)
+    }
+
+    /// Iterate over the field names and values, in registration order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &FormValue)> {
+        self.0.iter().map(|(n, v)| (n.as_str(), v))
+    }
+}
+
+/// Context component inserted by a [`Form`], which fields use to register themselves and
+/// report changes. Custom field widgets can access it via `use_inherited_component`.
+#[derive(Component, Clone, Copy)]
+pub struct FormContext {
+    fields: Mutable<Vec<FieldState>>,
+    on_submit: Option<Callback<FormValues>>,
+}
+
+impl FormContext {
+    /// Register a field with the form. `error` is the result of validating the initial value.
+    pub fn register(
+        &self,
+        world: &mut World,
+        name: &str,
+        initial: FormValue,
+        error: Option<String>,
+    ) {
+        self.fields.update(world, |mut fields| {
+            fields.retain(|f| f.name != name);
+            fields.push(FieldState {
+                name: name.to_string(),
+                value: initial.clone(),
+                initial,
+                touched: false,
+                error,
+            });
+        });
+    }
+
+    /// Remove a field from the form.
+    pub fn unregister(&self, world: &mut World, name: &str) {
+        self.fields.update(world, |mut fields| {
+            fields.retain(|f| f.name != name);
+        });
+    }
+
+    /// Update the value of a field, along with the result of validating it.
+    pub fn set_value(
+        &self,
+        world: &mut World,
+        name: &str,
+        value: FormValue,
+        error: Option<String>,
+    ) {
+        self.fields.update(world, |mut fields| {
+            if let Some(field) = fields.iter_mut().find(|f| f.name == name) {
+                field.value = value;
+                field.error = error;
+            }
+        });
+    }
+
+    /// Mark a field as touched, so that its validation errors are displayed.
+    pub fn touch(&self, world: &mut World, name: &str) {
+        self.fields.update(world, |mut fields| {
+            // Only trigger a change if the field wasn't already touched.
+            if let Some(index) = fields.iter().position(|f| f.name == name && !f.touched) {
+                fields[index].touched = true;
+            }
+        });
+    }
+
+    /// Returns the state of the named field.
+    pub fn field<R: ReadMutable + ReadDerived>(&self, rc: &R, name: &str) -> Option<FieldState> {
+        self.fields
+            .signal()
+            .map(rc, |fields| fields.iter().find(|f| f.name == name).cloned())
+    }
+
+    /// True if none of the fields have validation errors.
+    pub fn is_valid<R: ReadMutable + ReadDerived>(&self, rc: &R) -> bool {
+        self.fields
+            .signal()
+            .map(rc, |fields| fields.iter().all(|f| !f.is_invalid()))
+    }
+
+    /// True if any of the fields have changed since they were registered.
+    pub fn is_dirty<R: ReadMutable + ReadDerived>(&self, rc: &R) -> bool {
+        self.fields
+            .signal()
+            .map(rc, |fields| fields.iter().any(|f| f.is_dirty()))
+    }
+
+    /// Returns the current values of all fields.
+    pub fn values<R: ReadMutable + ReadDerived>(&self, rc: &R) -> FormValues {
+        self.fields.signal().map(rc, |fields| {
+            FormValues(
+                fields
+                    .iter()
+                    .map(|f| (f.name.clone(), f.value.clone()))
+                    .collect(),
+            )
+        })
+    }
+
+    /// Submit the form. All fields are marked as touched so that any errors are displayed;
+    /// if the form is valid, the form's `on_submit` callback is called with the field values.
+    pub fn submit(&self, world: &mut World) {
+        self.fields.update(world, |mut fields| {
+            for field in fields.iter_mut() {
+                field.touched = true;
+            }
+        });
+        if self.is_valid(world) {
+            if let Some(on_submit) = self.on_submit {
+                let values = self.values(world);
+                world.run_callback(on_submit, values);
+            }
+        }
+    }
+}
+
+/// A container which collects the values of the fields within it, such as [`FormTextInput`]
+/// and [`FormCheckbox`], tracks their validation state, and submits them together. Use
+/// [`FormSubmit`] for a submit button which is disabled while the form is invalid.
+#[derive(Default)]
+pub struct Form {
+    /// The fields and other content of the form.
+    pub children: ChildArray,
+
+    /// Additional styles to be applied to the form.
+    pub style: StyleHandle,
+
+    /// Callback called with the field values when a valid form is submitted.
+    pub on_submit: Option<Callback<FormValues>>,
+
+    /// Whether all of the controls within the form are disabled.
+    pub disabled: Signal<bool>,
+}
+
+impl Form {
+    /// Create a new form.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the fields and other content of the form.
+    pub fn children<V: ChildViewTuple>(mut self, children: V) -> Self {
+        self.children = children.to_child_array();
+        self
+    }
+
+    /// Set additional styles to be applied to the form.
+    pub fn style<S: StyleTuple + 'static>(mut self, style: S) -> Self {
+        self.style = style.into_handle();
+        self
+    }
+
+    /// Set the callback called when a valid form is submitted.
+    pub fn on_submit(mut self, on_submit: Callback<FormValues>) -> Self {
+        self.on_submit = Some(on_submit);
+        self
+    }
+
+    /// Set whether all of the controls within the form are disabled, for example while it is
+    /// being submitted.
+    pub fn disabled(mut self, disabled: impl IntoSignal<bool>) -> Self {
+        self.disabled = disabled.into_signal();
+        self
+    }
+}
+
+impl ViewTemplate for Form {
+    fn create(&self, cx: &mut Cx) -> impl IntoView {
+        let fields = cx.create_mutable(Vec::<FieldState>::new());
+        cx.insert(FormContext {
+            fields,
+            on_submit: self.on_submit,
+        });
+        cx.provide_disabled(self.disabled);
+
+        Element::<NodeBundle>::new()
+            .named("Form")
+            .style((style_form, self.style.clone()))
+            .children(self.children.clone())
+    }
+}
+
+/// A button which submits the enclosing [`Form`], and is disabled while the form is invalid.
+pub struct FormSubmit {
+    /// Color variant of the button (default primary).
+    pub variant: ButtonVariant,
+
+    /// Button size.
+    pub size: Size,
+
+    /// The content to display inside the button.
+    pub children: ChildArray,
+
+    /// Additional styles to be applied to the button.
+    pub style: StyleHandle,
+}
+
+impl Default for FormSubmit {
+    fn default() -> Self {
+        Self {
+            variant: ButtonVariant::Primary,
+            size: Size::default(),
+            children: ChildArray::default(),
+            style: StyleHandle::default(),
+        }
+    }
+}
+
+impl FormSubmit {
+    /// Create a new submit button.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the color variant of the button.
+    pub fn variant(mut self, variant: ButtonVariant) -> Self {
+        self.variant = variant;
+        self
+    }
+
+    /// Set the button size.
+    pub fn size(mut self, size: Size) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Set the content to display inside the button.
+    pub fn children<V: ChildViewTuple>(mut self, children: V) -> Self {
+        self.children = children.to_child_array();
+        self
+    }
+
+    /// Set additional styles to be applied to the button.
+    pub fn style<S: StyleTuple + 'static>(mut self, style: S) -> Self {
+        self.style = style.into_handle();
+        self
+    }
+}
+
+impl ViewTemplate for FormSubmit {
+    fn create(&self, cx: &mut Cx) -> impl IntoView {
+        let form = *cx
+            .use_inherited_component::<FormContext>()
+            .expect("FormSubmit must be placed inside a Form");
+        let invalid = cx.create_derived(move |cx| !form.is_valid(cx));
+        let on_click = cx.create_callback(move |cx: &mut Cx, _| {
+            form.submit(cx.world_mut());
+        });
+
+        Button::new()
+            .variant(self.variant)
+            .size(self.size)
+            .disabled(invalid)
+            .style(self.style.clone())
+            .children(self.children.clone())
+            .on_click(on_click)
+    }
+}
+
+/// A text input which registers itself with the enclosing [`Form`].
+#[derive(Default)]
+pub struct FormTextInput {
+    /// The name under which the value is reported to the form.
+    pub name: String,
+
+    /// Optional label displayed above the input.
+    pub label: Option<String>,
+
+    /// The initial text.
+    pub initial: String,
+
+    /// Text input size.
+    pub size: Size,
+
+    /// Validation rules, checked in order.
+    pub validators: Vec<Validator>,
+
+    /// Additional styles to be applied to the text input.
+    pub style: StyleHandle,
+}
+
+impl FormTextInput {
+    /// Create a new text field with the given name.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ..default()
+        }
+    }
+
+    /// Set the label displayed above the input.
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Set the initial text.
+    pub fn initial(mut self, initial: impl Into<String>) -> Self {
+        self.initial = initial.into();
+        self
+    }
+
+    /// Set the text input size.
+    pub fn size(mut self, size: Size) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Add a validation rule.
+    pub fn validate(mut self, validator: Validator) -> Self {
+        self.validators.push(validator);
+        self
+    }
+
+    /// Set additional styles to be applied to the text input.
+    pub fn style<S: StyleTuple + 'static>(mut self, style: S) -> Self {
+        self.style = style.into_handle();
+        self
+    }
+}
+
+impl ViewTemplate for FormTextInput {
+    fn create(&self, cx: &mut Cx) -> impl IntoView {
+        let id = cx.create_entity();
+        let form = *cx
+            .use_inherited_component::<FormContext>()
+            .expect("FormTextInput must be placed inside a Form");
+        let name = self.name.clone();
+        let validators = self.validators.clone();
+
+        let initial = FormValue::Text(self.initial.clone());
+        let error = validate_all(&validators, &initial);
+        form.register(cx.world_mut(), &name, initial, error);
+        cx.on_cleanup({
+            let name = name.clone();
+            move |world| form.unregister(world, &name)
+        });
+        create_touch_on_blur(cx, form, id, name.clone());
+
+        let value = cx.create_derived({
+            let name = name.clone();
+            move |cx| {
+                form.field(cx, &name)
+                    .and_then(|f| f.value.as_text().map(str::to_string))
+                    .unwrap_or_default()
+            }
+        });
+
+        let on_change = cx.create_callback({
+            let name = name.clone();
+            move |cx: &mut Cx, text: String| {
+                let value = FormValue::Text(text);
+                let error = validate_all(&validators, &value);
+                form.set_value(cx.world_mut(), &name, value, error);
+            }
+        });
+
+        let on_submit = cx.create_callback(move |cx: &mut Cx, _: String| {
+            form.submit(cx.world_mut());
+        });
+
+        Element::<NodeBundle>::for_entity(id)
+            .named("FormTextInput")
+            .style(style_form_field)
+            .children((
+                FieldLabel(self.label.clone()),
+                TextInput::new(TextInputProps {
+                    size: self.size,
+                    value,
+                    style: self.style.clone(),
+                    on_change: Some(on_change),
+                    on_submit: Some(on_submit),
+                    ..default()
+                }),
+                FieldError { form, name },
+            ))
+    }
+}
+
+/// A checkbox which registers itself with the enclosing [`Form`].
+#[derive(Default)]
+pub struct FormCheckbox {
+    /// The name under which the value is reported to the form.
+    pub name: String,
+
+    /// The content to display next to the checkbox.
+    pub label: ChildArray,
+
+    /// Whether the checkbox is initially checked.
+    pub initial: bool,
+
+    /// Validation rules, checked in order.
+    pub validators: Vec<Validator>,
+
+    /// Additional styles to be applied to the checkbox.
+    pub style: StyleHandle,
+}
+
+impl FormCheckbox {
+    /// Create a new checkbox field with the given name.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ..default()
+        }
+    }
+
+    /// Set the content to display next to the checkbox.
+    pub fn label<V: ChildViewTuple>(mut self, label: V) -> Self {
+        self.label = label.to_child_array();
+        self
+    }
+
+    /// Set whether the checkbox is initially checked.
+    pub fn initial(mut self, initial: bool) -> Self {
+        self.initial = initial;
+        self
+    }
+
+    /// Add a validation rule.
+    pub fn validate(mut self, validator: Validator) -> Self {
+        self.validators.push(validator);
+        self
+    }
+
+    /// Set additional styles to be applied to the checkbox.
+    pub fn style<S: StyleTuple + 'static>(mut self, style: S) -> Self {
+        self.style = style.into_handle();
+        self
+    }
+}
+
+impl ViewTemplate for FormCheckbox {
+    fn create(&self, cx: &mut Cx) -> impl IntoView {
+        let form = *cx
+            .use_inherited_component::<FormContext>()
+            .expect("FormCheckbox must be placed inside a Form");
+        let name = self.name.clone();
+        let validators = self.validators.clone();
+
+        let initial = FormValue::Bool(self.initial);
+        let error = validate_all(&validators, &initial);
+        form.register(cx.world_mut(), &name, initial, error);
+        cx.on_cleanup({
+            let name = name.clone();
+            move |world| form.unregister(world, &name)
+        });
+
+        let checked = cx.create_derived({
+            let name = name.clone();
+            move |cx| {
+                form.field(cx, &name)
+                    .and_then(|f| f.value.as_bool())
+                    .unwrap_or_default()
+            }
+        });
+
+        // Toggling a checkbox counts as touching it.
+        let on_change = cx.create_callback({
+            let name = name.clone();
+            move |cx: &mut Cx, checked: bool| {
+                let value = FormValue::Bool(checked);
+                let error = validate_all(&validators, &value);
+                let world = cx.world_mut();
+                form.set_value(world, &name, value, error);
+                form.touch(world, &name);
+            }
+        });
+
+        Element::<NodeBundle>::new()
+            .named("FormCheckbox")
+            .style(style_form_field)
+            .children((
+                Checkbox::new()
+                    .checked(checked)
+                    .label(self.label.clone())
+                    .style(self.style.clone())
+                    .on_change(on_change),
+                FieldError { form, name },
+            ))
+    }
+}
+
+/// Mark a field as touched when focus leaves the field's element, after having been within it.
+fn create_touch_on_blur(cx: &mut Cx, form: FormContext, id: Entity, name: String) {
+    let focused = cx.create_focus_within_signal(id);
+    let was_focused = cx.create_mutable(false);
+    cx.create_effect(move |cx| {
+        if focused.get(cx) {
+            was_focused.set(cx, true);
+        } else if was_focused.get(cx) {
+            was_focused.set(cx, false);
+            form.touch(cx.world_mut(), &name);
+        }
+    });
+}
+
+/// Label displayed above a form field.
+struct FieldLabel(Option<String>);
+
+impl ViewTemplate for FieldLabel {
+    fn create(&self, _cx: &mut Cx) -> impl IntoView {
+        let label = self.0.clone();
+        Cond::new(
+            move |_cx| label.is_some(),
+            {
+                let label = self.0.clone();
+                move || {
+                    Element::<NodeBundle>::new()
+                        .named("FormField::Label")
+                        .style(style_form_field_label)
+                        .children(label.clone().unwrap())
+                }
+            },
+            || (),
+        )
+    }
+}
+
+/// Validation error displayed below a form field, once the field has been touched.
+struct FieldError {
+    form: FormContext,
+    name: String,
+}
+
+impl ViewTemplate for FieldError {
+    fn create(&self, cx: &mut Cx) -> impl IntoView {
+        let form = self.form;
+        let name = self.name.clone();
+        let error = cx.create_derived(move |cx| {
+            form.field(cx, &name)
+                .filter(|f| f.touched)
+                .and_then(|f| f.error)
+        });
+
+        Cond::new(
+            {
+                let error = error.clone();
+                move |cx| error.map(cx, |e| e.is_some())
+            },
+            move || {
+                let error = error.clone();
+                Element::<NodeBundle>::new()
+                    .named("FormField::Error")
+                    .style(style_form_field_error)
+                    .children(text_computed(move |cx| {
+                        error.get_clone(cx).unwrap_or_default()
+                    }))
+            },
+            || (),
+        )
+    }
+}
@@ -1,12 +1,36 @@
-use bevy::{color::Srgba, prelude::*, ui};
+use bevy::{
+    color::{LinearRgba, Mix, Oklaba, Srgba},
+    prelude::*,
+    ui,
+};
 use bevy_mod_picking::prelude::*;
 use bevy_reactor::*;
 
-use crate::materials::GradientRectMaterial;
+use crate::{
+    colors, controls::SliderOrientation, hooks::CreateDisabledSignal,
+    materials::GradientRectMaterial, pointer_capture::CapturePointer,
+};
 
 const THUMB_WIDTH: f32 = 12.;
 
-/// Struct representing a sequence of color stops, evenly spaced. Up to 8 stops are supported.
+/// The color space in which a [`ColorGradient`] is interpolated between its stops.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum GradientInterpolation {
+    /// Interpolate sRGB-encoded values. This matches most color pickers.
+    #[default]
+    Srgb,
+    /// Interpolate linear values. This is the appropriate choice for physical quantities
+    /// such as light intensity.
+    Linear,
+    /// Interpolate in the perceptually uniform Oklab space, which avoids the dark or desaturated
+    /// midpoints of the other spaces. HDR values are supported.
+    Oklab,
+}
+
+/// Struct representing a sequence of color stops. Up to 8 stops are supported. Stops are
+/// evenly spaced unless positions are given with [`ColorGradient::with_positions`].
+///
+/// Color stops may have components greater than 1.0, which represent HDR values.
 #[derive(Debug, Copy, Clone)]
 pub struct ColorGradient {
     /// Number of color stops.
@@ -14,6 +38,12 @@ pub struct ColorGradient {
 
     /// Array of color stops.
     pub colors: [Srgba; 8],
+
+    /// Position of each color stop along the gradient, from 0 to 1, in increasing order.
+    pub positions: [f32; 8],
+
+    /// The color space to interpolate between stops in.
+    pub interpolation: GradientInterpolation,
 }
 
 impl ColorGradient {
@@ -23,6 +53,8 @@ impl ColorGradient {
         let mut result = Self {
             num_colors: colors.len(),
             colors: [Srgba::default(); 8],
+            positions: even_positions(colors.len()),
+            interpolation: GradientInterpolation::Srgb,
         };
         for (i, color) in colors.iter().enumerate() {
             result.colors[i] = *color;
@@ -30,6 +62,42 @@ impl ColorGradient {
         result
     }
 
+    /// Construct a new color gradient from an array of linear colors, which will be
+    /// interpolated in linear space. Components may be greater than 1.0.
+    pub fn new_linear(colors: &[LinearRgba]) -> Self {
+        assert!(colors.len() <= 8);
+        let mut result = Self {
+            num_colors: colors.len(),
+            colors: [Srgba::default(); 8],
+            positions: even_positions(colors.len()),
+            interpolation: GradientInterpolation::Linear,
+        };
+        for (i, color) in colors.iter().enumerate() {
+            result.colors[i] = Srgba::from(*color);
+        }
+        result
+    }
+
+    /// Set the color space to interpolate between stops in.
+    pub fn with_interpolation(mut self, interpolation: GradientInterpolation) -> Self {
+        self.interpolation = interpolation;
+        self
+    }
+
+    /// Set the positions of the color stops, from 0 to 1. There must be one position for each
+    /// color. Positions are clamped to the range 0 to 1, and a position which is less than the
+    /// one before it (or NaN) is moved up to it, so the stops are always in order.
+    pub fn with_positions(mut self, positions: &[f32]) -> Self {
+        assert_eq!(positions.len(), self.num_colors);
+        let mut previous = 0f32;
+        for (stop, position) in self.positions.iter_mut().zip(positions) {
+            // `max` ignores NaN.
+            previous = position.clamp(0., 1.).max(previous);
+            *stop = previous;
+        }
+        self
+    }
+
     /// Return the first color in the gradient, if any.
     pub fn first(&self) -> Option<Srgba> {
         if self.num_colors > 0 {
@@ -57,6 +125,36 @@ impl ColorGradient {
     pub fn is_empty(&self) -> bool {
         self.num_colors == 0
     }
+
+    /// Return the linear color at position `t` along the gradient, interpolated the same way
+    /// as the gradient is drawn.
+    pub fn sample(&self, t: f32) -> LinearRgba {
+        if self.num_colors == 0 {
+            return LinearRgba::NONE;
+        }
+        let positions = &self.positions[..self.num_colors];
+        let hi = positions
+            .iter()
+            .position(|p| *p > t)
+            .unwrap_or(self.num_colors - 1)
+            .max(1)
+            .min(self.num_colors - 1);
+        let lo = hi.saturating_sub(1);
+        let span = positions[hi] - positions[lo];
+        let f = if span > 0. {
+            ((t - positions[lo]) / span).clamp(0., 1.)
+        } else {
+            0.
+        };
+        let (a, b) = (self.colors[lo], self.colors[hi]);
+        match self.interpolation {
+            GradientInterpolation::Srgb => LinearRgba::from(a.mix(&b, f)),
+            GradientInterpolation::Linear => LinearRgba::from(a).mix(&LinearRgba::from(b), f),
+            GradientInterpolation::Oklab => {
+                LinearRgba::from(Oklaba::from(a).mix(&Oklaba::from(b), f))
+            }
+        }
+    }
 }
 
 impl Default for ColorGradient {
@@ -64,10 +162,58 @@ impl Default for ColorGradient {
         Self {
             num_colors: 1,
             colors: [Srgba::BLACK; 8],
+            positions: even_positions(1),
+            interpolation: GradientInterpolation::Srgb,
         }
     }
 }
 
+/// Positions for `count` evenly spaced color stops.
+fn even_positions(count: usize) -> [f32; 8] {
+    let mut positions = [0.; 8];
+    for (i, position) in positions.iter_mut().enumerate().take(count) {
+        *position = i as f32 / (count.max(2) - 1) as f32;
+    }
+    positions
+}
+
+/// The color gamut of the display, used when converting colors for display and when
+/// deciding whether a color can be shown accurately.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum DisplayGamut {
+    /// Standard sRGB / Rec. 709 primaries.
+    #[default]
+    Srgb,
+    /// Display P3 primaries, as used by many wide-gamut monitors. Colors are written out in P3
+    /// primaries, so this should only be used when the window's output is shown on a P3
+    /// display without being converted from sRGB.
+    DisplayP3,
+}
+
+impl DisplayGamut {
+    /// Convert a linear sRGB color to linear RGB in this gamut's primaries.
+    pub fn from_linear_srgb(self, color: LinearRgba) -> Vec3 {
+        let rgb = Vec3::new(color.red, color.green, color.blue);
+        match self {
+            DisplayGamut::Srgb => rgb,
+            DisplayGamut::DisplayP3 => Mat3::from_cols(
+                Vec3::new(0.8225, 0.0332, 0.0171),
+                Vec3::new(0.1774, 0.9669, 0.0724),
+                Vec3::new(0.0000, 0.0000, 0.9108),
+            )
+            .mul_vec3(rgb),
+        }
+    }
+
+    /// Returns true if the color can be displayed in this gamut without clipping. Colors
+    /// brighter than 1.0 are considered out of gamut.
+    pub fn contains(self, color: LinearRgba) -> bool {
+        const EPSILON: f32 = 0.001;
+        let rgb = self.from_linear_srgb(color);
+        rgb.min_element() >= -EPSILON && rgb.max_element() <= 1. + EPSILON
+    }
+}
+
 #[derive(Clone, PartialEq, Default, Copy)]
 struct DragState {
     dragging: bool,
@@ -82,6 +228,14 @@ fn style_slider(ss: &mut StyleBuilder) {
         .align_items(ui::AlignItems::Stretch);
 }
 
+fn style_slider_vertical(ss: &mut StyleBuilder) {
+    ss.min_height(32)
+        .width(14)
+        .display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Column)
+        .align_items(ui::AlignItems::Stretch);
+}
+
 fn style_gradient(ss: &mut StyleBuilder) {
     ss.flex_grow(1.);
 }
@@ -94,6 +248,14 @@ fn style_track(ss: &mut StyleBuilder) {
         .right(THUMB_WIDTH + 1.);
 }
 
+fn style_track_vertical(ss: &mut StyleBuilder) {
+    ss.position(ui::PositionType::Absolute)
+        .top(THUMB_WIDTH + 1.)
+        .bottom(1)
+        .left(1)
+        .right(1);
+}
+
 fn style_thumb(ss: &mut StyleBuilder) {
     ss.background_image("obsidian_ui://textures/gradient_thumb.png")
         .position(ui::PositionType::Absolute)
@@ -102,7 +264,16 @@ fn style_thumb(ss: &mut StyleBuilder) {
         .width(THUMB_WIDTH);
 }
 
-/// Horizontal slider widget that displays a gradient bar and a draggable button.
+fn style_thumb_vertical(ss: &mut StyleBuilder) {
+    ss.background_image("obsidian_ui://textures/gradient_thumb.png")
+        .position(ui::PositionType::Absolute)
+        .left(0)
+        .right(0)
+        .height(THUMB_WIDTH);
+}
+
+/// Slider widget that displays a gradient bar and a draggable button. The slider can be
+/// horizontal or vertical.
 pub struct GradientSlider {
     /// Gradient to display.
     pub gradient: Signal<ColorGradient>,
@@ -122,11 +293,27 @@ pub struct GradientSlider {
     /// Whether the slider is disabled.
     pub disabled: Signal<bool>,
 
+    /// Exposure adjustment, in stops, applied to the gradient preview. This allows HDR
+    /// gradients with values above 1.0 to be inspected.
+    pub exposure: Signal<f32>,
+
+    /// Gamut of the display, used to convert and clip gradient colors.
+    pub gamut: DisplayGamut,
+
+    /// Whether to draw a warning pattern over colors which are outside of the display gamut.
+    pub show_out_of_gamut: bool,
+
+    /// Whether to dither the gradient preview, which hides banding in subtle gradients.
+    pub dither: bool,
+
     /// Style handle for slider root element.
     pub style: StyleHandle,
 
     /// Callback called when value changes
     pub on_change: Option<Callback<f32>>,
+
+    /// Whether the slider is horizontal or vertical.
+    pub orientation: SliderOrientation,
 }
 
 impl GradientSlider {
@@ -171,6 +358,30 @@ impl GradientSlider {
         self
     }
 
+    /// Set the exposure adjustment, in stops, for the gradient preview.
+    pub fn exposure(mut self, exposure: impl IntoSignal<f32>) -> Self {
+        self.exposure = exposure.into_signal();
+        self
+    }
+
+    /// Set the gamut of the display.
+    pub fn gamut(mut self, gamut: DisplayGamut) -> Self {
+        self.gamut = gamut;
+        self
+    }
+
+    /// Set whether to indicate colors which are outside of the display gamut.
+    pub fn show_out_of_gamut(mut self, show: bool) -> Self {
+        self.show_out_of_gamut = show;
+        self
+    }
+
+    /// Set whether to dither the gradient preview.
+    pub fn dither(mut self, dither: bool) -> Self {
+        self.dither = dither;
+        self
+    }
+
     /// Set the style handle for the slider root element.
     pub fn style<S: StyleTuple + 'static>(mut self, style: S) -> Self {
         self.style = style.into_handle();
@@ -182,6 +393,12 @@ impl GradientSlider {
         self.on_change = Some(on_change);
         self
     }
+
+    /// Set whether the slider is horizontal or vertical.
+    pub fn orientation(mut self, orientation: SliderOrientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
 }
 
 impl Default for GradientSlider {
@@ -193,8 +410,13 @@ impl Default for GradientSlider {
             max: Signal::Constant(1.),
             precision: 0,
             disabled: Signal::Constant(false),
+            exposure: Signal::Constant(0.),
+            gamut: DisplayGamut::Srgb,
+            show_out_of_gamut: true,
+            dither: true,
             style: StyleHandle::default(),
             on_change: None,
+            orientation: SliderOrientation::default(),
         }
     }
 }
@@ -211,23 +433,49 @@ impl ViewTemplate for GradientSlider {
         let value = self.value;
         let precision = self.precision;
         let on_change = self.on_change;
+        let orientation = self.orientation;
+        let vertical = orientation == SliderOrientation::Vertical;
+        let disabled = cx.create_disabled_signal(self.disabled);
 
         // This should really be an effect.
-        let color_stops: Signal<(usize, [Vec4; 8])> = {
+        let color_stops: Signal<(usize, [Vec4; 8], [f32; 8], GradientInterpolation)> = {
             let gradient = self.gradient;
             cx.create_derived(move |cc| {
                 gradient.map(cc, |g| {
                     let mut result: [Vec4; 8] = [Vec4::default(); 8];
                     let num_color_stops = g.len();
                     for (i, color) in g.colors[0..num_color_stops].iter().enumerate() {
-                        // Note that we do *not* convert to linear here, because interpolating
-                        // linear looks bad. That gets done in the shader.
-                        result[i] = Vec4::new(color.red, color.green, color.blue, color.alpha);
+                        // Note that for sRGB gradients we do *not* convert to linear here,
+                        // because interpolating linear looks bad for perceptual gradients.
+                        // That gets done in the shader.
+                        result[i] = match g.interpolation {
+                            GradientInterpolation::Srgb => color.to_vec4(),
+                            GradientInterpolation::Linear | GradientInterpolation::Oklab => {
+                                LinearRgba::from(*color).to_vec4()
+                            }
+                        };
                     }
-                    (g.len(), result)
+                    (g.len(), result, g.positions, g.interpolation)
                 })
             })
         };
+        let exposure = self.exposure;
+        let gradient = self.gradient;
+        let gamut = self.gamut;
+        let show_out_of_gamut = self.show_out_of_gamut;
+        let mut flags = 0;
+        if self.gamut == DisplayGamut::DisplayP3 {
+            flags |= GradientRectMaterial::DISPLAY_P3;
+        }
+        if self.show_out_of_gamut {
+            flags |= GradientRectMaterial::GAMUT_WARNING;
+        }
+        if vertical {
+            flags |= GradientRectMaterial::VERTICAL;
+        }
+        if self.dither {
+            flags |= GradientRectMaterial::DITHER;
+        }
 
         let mut gradient_material_assets = cx
             .world_mut()
@@ -235,15 +483,19 @@ impl ViewTemplate for GradientSlider {
             .unwrap();
         let gradient_material = gradient_material_assets.add(GradientRectMaterial {
             color_stops: [Srgba::default().to_vec4(); 8],
+            stop_positions: GradientRectMaterial::pack_positions(&even_positions(2)),
             num_color_stops: 2,
             cap_size: THUMB_WIDTH * 0.5,
+            flags,
+            exposure: 0.,
         });
 
         // Effect to update the material handle.
         cx.create_effect({
             let material = gradient_material.clone();
             move |cx| {
-                let (num_color_stops, color_stops) = color_stops.get(cx);
+                let (num_color_stops, color_stops, positions, interpolation) = color_stops.get(cx);
+                let exposure = exposure.get(cx);
                 let mut ui_materials = cx
                     .world_mut()
                     .get_resource_mut::<Assets<GradientRectMaterial>>()
@@ -251,34 +503,58 @@ impl ViewTemplate for GradientSlider {
                 let material = ui_materials.get_mut(material.id()).unwrap();
                 material.num_color_stops = num_color_stops as i32;
                 material.color_stops = color_stops;
+                material.stop_positions = GradientRectMaterial::pack_positions(&positions);
+                material.exposure = exposure;
+                material.flags = match interpolation {
+                    GradientInterpolation::Srgb => flags,
+                    GradientInterpolation::Linear => flags | GradientRectMaterial::LINEAR,
+                    GradientInterpolation::Oklab => {
+                        flags | GradientRectMaterial::LINEAR | GradientRectMaterial::OKLAB
+                    }
+                };
             }
         });
 
         Element::<NodeBundle>::for_entity(slider_id)
             .named("GradientSlider")
-            .style((style_slider, self.style.clone()))
+            .style((
+                match orientation {
+                    SliderOrientation::Horizontal => style_slider,
+                    SliderOrientation::Vertical => style_slider_vertical,
+                },
+                self.style.clone(),
+            ))
             .insert((
+                CapturePointer,
                 On::<Pointer<Down>>::run(move |world: &mut World| {
+                    if disabled.get(world) {
+                        return;
+                    }
                     let min = min.get(world);
                     let max = max.get(world);
                     let mut event = world
                         .get_resource_mut::<ListenerInput<Pointer<Down>>>()
                         .unwrap();
                     event.stop_propagation();
-                    let hit_x = event.pointer_location.position.x;
+                    let hit = event.pointer_location.position;
                     let ent = world.entity(slider_id);
                     let node = ent.get::<Node>();
                     let transform = ent.get::<GlobalTransform>();
                     if let (Some(node), Some(transform)) = (node, transform) {
                         // If not clicking on thumb, then snap thumb to new location.
                         let rect = node.logical_rect(transform);
-                        let slider_width = rect.width() - THUMB_WIDTH;
+                        let slider_length = orientation.length(rect) - THUMB_WIDTH;
                         let range = max - min;
-                        let pointer_pos = hit_x - rect.min.x - THUMB_WIDTH / 2.;
-                        let thumb_pos =
-                            value.get(world) - min * slider_width / range + THUMB_WIDTH / 2.;
+                        // Distance from the start of the slider, in the direction of
+                        // increasing value.
+                        let hit_pos = match orientation {
+                            SliderOrientation::Horizontal => hit.x - rect.min.x,
+                            SliderOrientation::Vertical => rect.max.y - hit.y,
+                        };
+                        let pointer_pos = hit_pos - THUMB_WIDTH / 2.;
+                        let thumb_pos = (value.get(world) - min) * slider_length / range;
                         if range > 0. && (pointer_pos - thumb_pos).abs() >= THUMB_WIDTH / 2. {
-                            let new_value = min + (pointer_pos * range) / slider_width;
+                            let new_value = min + (pointer_pos * range) / slider_length;
                             if let Some(on_change) = on_change {
                                 world.run_callback(on_change, new_value.clamp(min, max));
                             }
@@ -286,6 +562,9 @@ impl ViewTemplate for GradientSlider {
                     }
                 }),
                 On::<Pointer<DragStart>>::run(move |world: &mut World| {
+                    if disabled.get(world) {
+                        return;
+                    }
                     // Save initial value to use as drag offset.
                     let mut event = world
                         .get_resource_mut::<ListenerInput<Pointer<DragStart>>>()
@@ -322,12 +601,14 @@ impl ViewTemplate for GradientSlider {
                         let transform = ent.get::<GlobalTransform>();
                         if let (Some(node), Some(transform)) = (node, transform) {
                             // Measure node width and slider value.
-                            let slider_width = node.logical_rect(transform).width();
+                            let slider_length = orientation.length(node.logical_rect(transform));
                             let min = min.get(world);
                             let max = max.get(world);
                             let range = max - min;
                             let new_value = if range > 0. {
-                                ds.offset + (event.distance.x * range) / slider_width
+                                ds.offset
+                                    + (orientation.drag_distance(event.distance) * range)
+                                        / slider_length
                             } else {
                                 min + range * 0.5
                             };
@@ -346,11 +627,17 @@ impl ViewTemplate for GradientSlider {
                     .style(style_gradient),
                 Element::<NodeBundle>::new()
                     .named("GradientSlider::Track")
-                    .style(style_track)
+                    .style(match orientation {
+                        SliderOrientation::Horizontal => style_track,
+                        SliderOrientation::Vertical => style_track_vertical,
+                    })
                     .children(
                         Element::<NodeBundle>::new()
                             .named("GradientSlider::Thumb")
-                            .style(style_thumb)
+                            .style(match orientation {
+                                SliderOrientation::Horizontal => style_thumb,
+                                SliderOrientation::Vertical => style_thumb_vertical,
+                            })
                             .create_effect(move |cx, ent| {
                                 let min = min.get(cx);
                                 let max = max.get(cx);
@@ -362,9 +649,110 @@ impl ViewTemplate for GradientSlider {
                                 };
 
                                 let mut style = cx.world_mut().get_mut::<Style>(ent).unwrap();
-                                style.left = ui::Val::Percent(percent * 100.);
+                                match orientation {
+                                    SliderOrientation::Horizontal => {
+                                        style.left = ui::Val::Percent(percent * 100.)
+                                    }
+                                    SliderOrientation::Vertical => {
+                                        style.bottom = ui::Val::Percent(percent * 100.)
+                                    }
+                                }
+                            })
+                            // Tint the thumb when the selected color can't be displayed.
+                            .create_effect(move |cx, ent| {
+                                if !show_out_of_gamut {
+                                    return;
+                                }
+                                let min = min.get(cx);
+                                let max = max.get(cx);
+                                let t = if max > min {
+                                    ((value.get(cx) - min) / (max - min)).clamp(0., 1.)
+                                } else {
+                                    0.
+                                };
+                                let color = gradient.get(cx).sample(t);
+                                let scale = exposure.get(cx).exp2();
+                                let exposed = LinearRgba::rgb(
+                                    color.red * scale,
+                                    color.green * scale,
+                                    color.blue * scale,
+                                );
+                                let tint = if gamut.contains(exposed) {
+                                    colors::U5
+                                } else {
+                                    colors::CODE
+                                };
+                                if let Some(mut image) = cx.world_mut().get_mut::<UiImage>(ent) {
+                                    image.color = tint.into();
+                                }
                             }),
                     ),
             ))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_near(a: LinearRgba, b: LinearRgba) {
+        let d = a.to_vec4() - b.to_vec4();
+        assert!(d.abs().max_element() < 0.001, "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn test_sample() {
+        let gradient = ColorGradient::new_linear(&[
+            LinearRgba::BLACK,
+            LinearRgba::rgb(1., 0., 0.),
+            LinearRgba::rgb(1., 1., 1.),
+        ])
+        .with_positions(&[0., 0.25, 1.]);
+        assert_near(gradient.sample(-1.), LinearRgba::BLACK);
+        assert_near(gradient.sample(0.125), LinearRgba::rgb(0.5, 0., 0.));
+        assert_near(gradient.sample(0.25), LinearRgba::rgb(1., 0., 0.));
+        assert_near(gradient.sample(0.625), LinearRgba::rgb(1., 0.5, 0.5));
+        assert_near(gradient.sample(2.), LinearRgba::WHITE);
+
+        // Interpolating sRGB values gives a lighter midpoint than interpolating linear ones.
+        let srgb = ColorGradient::new(&[Srgba::BLACK, Srgba::WHITE]);
+        assert_near(
+            srgb.sample(0.5),
+            LinearRgba::from(Srgba::rgb(0.5, 0.5, 0.5)),
+        );
+
+        let single = ColorGradient::new(&[Srgba::WHITE]);
+        assert_near(single.sample(0.5), LinearRgba::WHITE);
+    }
+
+    #[test]
+    fn test_with_positions() {
+        let colors = [
+            Srgba::BLACK,
+            Srgba::rgb(1., 0., 0.),
+            Srgba::rgb(0., 1., 0.),
+            Srgba::WHITE,
+        ];
+        let gradient = ColorGradient::new(&colors).with_positions(&[-1., 0.6, 0.4, 2.]);
+        assert_eq!(gradient.positions[..4], [0., 0.6, 0.6, 1.]);
+        let gradient = ColorGradient::new(&colors).with_positions(&[0., f32::NAN, 0.5, 1.]);
+        assert_eq!(gradient.positions[..4], [0., 0., 0.5, 1.]);
+    }
+
+    #[test]
+    fn test_gamut_contains() {
+        let red = LinearRgba::rgb(1., 0., 0.);
+        assert!(DisplayGamut::Srgb.contains(red));
+        assert!(DisplayGamut::DisplayP3.contains(red));
+
+        // A color more saturated than sRGB red, which P3 can still show.
+        let p3_red = LinearRgba::rgb(1.2, -0.04, -0.015);
+        assert!(!DisplayGamut::Srgb.contains(p3_red));
+        assert!(DisplayGamut::DisplayP3.contains(p3_red));
+
+        // HDR values are out of gamut for both.
+        let bright = LinearRgba::rgb(2., 2., 2.);
+        assert!(!DisplayGamut::Srgb.contains(bright));
+        assert!(!DisplayGamut::DisplayP3.contains(bright));
+    }
+}
@@ -0,0 +1,261 @@
+use std::{collections::VecDeque, hash::Hash};
+
+use bevy::{prelude::*, utils::HashMap};
+
+/// Computes positions for the nodes of a graph from its connections, using a layered
+/// (Sugiyama-style) layout. Nodes are arranged in columns, so that edges flow from left to
+/// right; each node is placed in the column after the furthest of its inputs. Within each
+/// column, nodes are ordered so as to reduce the number of crossing edges.
+///
+/// The layout is computed from the graph topology alone, and does not depend on the current
+/// node positions. Cycles are tolerated: the edges which close them are ignored.
+#[derive(Clone, Debug)]
+pub struct LayeredGraphLayout {
+    /// The gap between columns (x) and between nodes in the same column (y).
+    pub spacing: Vec2,
+
+    /// The position of the upper-left corner of the layout.
+    pub origin: Vec2,
+
+    /// Number of passes used to reduce edge crossings.
+    pub sweeps: usize,
+}
+
+impl Default for LayeredGraphLayout {
+    fn default() -> Self {
+        Self {
+            spacing: Vec2::new(80., 30.),
+            origin: Vec2::new(40., 40.),
+            sweeps: 4,
+        }
+    }
+}
+
+impl LayeredGraphLayout {
+    /// Create a new layout with the default spacing.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the gap between columns (x) and between nodes in the same column (y).
+    pub fn spacing(mut self, spacing: Vec2) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    /// Set the position of the upper-left corner of the layout.
+    pub fn origin(mut self, origin: Vec2) -> Self {
+        self.origin = origin;
+        self
+    }
+
+    /// Compute the position of each node.
+    ///
+    /// # Arguments
+    /// * `nodes` - The key and size of each node. The order of the nodes is used as the initial
+    ///   order within each column.
+    /// * `edges` - The connections between nodes, as pairs of (source, destination) keys.
+    ///   Edges which refer to unknown nodes are ignored.
+    ///
+    /// Returns the position of the upper-left corner of each node.
+    pub fn compute<K: Copy + Eq + Hash>(
+        &self,
+        nodes: &[(K, Vec2)],
+        edges: &[(K, K)],
+    ) -> HashMap<K, Vec2> {
+        let count = nodes.len();
+        let index: HashMap<K, usize> = nodes
+            .iter()
+            .enumerate()
+            .map(|(i, (key, _))| (*key, i))
+            .collect();
+        let mut outputs: Vec<Vec<usize>> = vec![Vec::new(); count];
+        let mut inputs: Vec<Vec<usize>> = vec![Vec::new(); count];
+        for (src, dst) in edges {
+            if let (Some(&src), Some(&dst)) = (index.get(src), index.get(dst)) {
+                if src != dst && !outputs[src].contains(&dst) {
+                    outputs[src].push(dst);
+                    inputs[dst].push(src);
+                }
+            }
+        }
+
+        let column = assign_columns(&inputs, &outputs);
+        let mut columns: Vec<Vec<usize>> =
+            vec![Vec::new(); column.iter().max().map_or(0, |max| max + 1)];
+        for (node, col) in column.iter().enumerate() {
+            columns[*col].push(node);
+        }
+        self.reduce_crossings(&mut columns, &inputs, &outputs);
+
+        // Assign coordinates. Columns are as wide as their widest node, and are centered
+        // vertically relative to the tallest column.
+        let column_height = |col: &Vec<usize>| {
+            col.iter().map(|node| nodes[*node].1.y).sum::<f32>()
+                + self.spacing.y * col.len().saturating_sub(1) as f32
+        };
+        let max_height = columns.iter().map(column_height).fold(0., f32::max);
+        let mut result = HashMap::with_capacity(count);
+        let mut x = self.origin.x;
+        for col in columns.iter() {
+            let mut y = self.origin.y + (max_height - column_height(col)) * 0.5;
+            let mut width: f32 = 0.;
+            for node in col {
+                let (key, size) = nodes[*node];
+                result.insert(key, Vec2::new(x, y));
+                y += size.y + self.spacing.y;
+                width = width.max(size.x);
+            }
+            x += width + self.spacing.x;
+        }
+        result
+    }
+
+    /// Reorder the nodes within each column by the average position of their neighbors,
+    /// alternating between left-to-right and right-to-left passes.
+    fn reduce_crossings(
+        &self,
+        columns: &mut [Vec<usize>],
+        inputs: &[Vec<usize>],
+        outputs: &[Vec<usize>],
+    ) {
+        let mut rank = vec![0.; inputs.len()];
+        let update_rank = |columns: &[Vec<usize>], rank: &mut Vec<f32>| {
+            for col in columns {
+                for (i, node) in col.iter().enumerate() {
+                    rank[*node] = i as f32;
+                }
+            }
+        };
+        update_rank(columns, &mut rank);
+        for sweep in 0..self.sweeps {
+            let forward = sweep % 2 == 0;
+            let neighbors = if forward { inputs } else { outputs };
+            let order: Vec<usize> = if forward {
+                (1..columns.len()).collect()
+            } else {
+                (0..columns.len().saturating_sub(1)).rev().collect()
+            };
+            for c in order {
+                let barycenter = |node: usize| {
+                    let adjacent = &neighbors[node];
+                    if adjacent.is_empty() {
+                        rank[node]
+                    } else {
+                        adjacent.iter().map(|n| rank[*n]).sum::<f32>() / adjacent.len() as f32
+                    }
+                };
+                columns[c].sort_by(|a, b| barycenter(*a).total_cmp(&barycenter(*b)));
+                for (i, node) in columns[c].iter().enumerate() {
+                    rank[*node] = i as f32;
+                }
+            }
+            update_rank(columns, &mut rank);
+        }
+    }
+}
+
+/// Assign each node to the column after the furthest of its inputs (longest-path layering).
+/// When the remaining nodes all lie on cycles, the one with the fewest unplaced inputs is
+/// placed next, ignoring those inputs.
+fn assign_columns(inputs: &[Vec<usize>], outputs: &[Vec<usize>]) -> Vec<usize> {
+    let count = inputs.len();
+    let mut pending: Vec<usize> = inputs.iter().map(|i| i.len()).collect();
+    let mut column = vec![0; count];
+    let mut placed = vec![false; count];
+    let mut queue: VecDeque<usize> = (0..count).filter(|n| pending[*n] == 0).collect();
+    loop {
+        while let Some(node) = queue.pop_front() {
+            placed[node] = true;
+            for next in outputs[node].iter() {
+                if placed[*next] || pending[*next] == 0 {
+                    continue;
+                }
+                column[*next] = column[*next].max(column[node] + 1);
+                pending[*next] -= 1;
+                if pending[*next] == 0 {
+                    queue.push_back(*next);
+                }
+            }
+        }
+
+        // Break a cycle.
+        match (0..count)
+            .filter(|n| !placed[*n])
+            .min_by_key(|n| pending[*n])
+        {
+            Some(node) => {
+                pending[node] = 0;
+                queue.push_back(node);
+            }
+            None => break,
+        }
+    }
+    column
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SIZE: Vec2 = Vec2::new(40., 20.);
+
+    fn layout(
+        nodes: &[&'static str],
+        edges: &[(&'static str, &'static str)],
+    ) -> HashMap<&'static str, Vec2> {
+        let nodes: Vec<(&str, Vec2)> = nodes.iter().map(|key| (*key, SIZE)).collect();
+        LayeredGraphLayout::new().compute(&nodes, edges)
+    }
+
+    #[test]
+    fn test_chain() {
+        let pos = layout(&["a", "b", "c"], &[("a", "b"), ("b", "c")]);
+        assert_eq!(pos["a"], Vec2::new(40., 40.));
+        assert_eq!(pos["b"], Vec2::new(160., 40.));
+        assert_eq!(pos["c"], Vec2::new(280., 40.));
+    }
+
+    #[test]
+    fn test_diamond() {
+        let pos = layout(
+            &["a", "b", "c", "d"],
+            &[("a", "b"), ("a", "c"), ("b", "d"), ("c", "d")],
+        );
+        // Shorter columns are centered on the tallest one.
+        assert_eq!(pos["a"], Vec2::new(40., 65.));
+        assert_eq!(pos["b"], Vec2::new(160., 40.));
+        assert_eq!(pos["c"], Vec2::new(160., 90.));
+        assert_eq!(pos["d"], Vec2::new(280., 65.));
+    }
+
+    #[test]
+    fn test_longest_path() {
+        // "c" goes after "b", even though "a" connects to it directly.
+        let pos = layout(&["a", "b", "c"], &[("a", "c"), ("a", "b"), ("b", "c")]);
+        assert_eq!(pos["c"].x, 280.);
+    }
+
+    #[test]
+    fn test_ignored_edges() {
+        let pos = layout(&["a", "b"], &[("a", "a"), ("a", "z"), ("z", "b")]);
+        assert_eq!(pos.len(), 2);
+        assert_eq!(pos["a"].x, pos["b"].x);
+    }
+
+    #[test]
+    fn test_cycle() {
+        let pos = layout(&["a", "b", "c"], &[("a", "b"), ("b", "a"), ("c", "a")]);
+        assert_eq!(pos.len(), 3);
+        assert!(pos["c"].x < pos["a"].x);
+        assert!(pos["a"].x < pos["b"].x);
+    }
+
+    #[test]
+    fn test_reduce_crossings() {
+        let pos = layout(&["a", "b", "c", "d"], &[("a", "d"), ("b", "c")]);
+        assert!(pos["a"].y < pos["b"].y);
+        // The sinks are swapped so that the edges don't cross.
+        assert!(pos["d"].y < pos["c"].y);
+    }
+}
@@ -0,0 +1,305 @@
+use bevy::{prelude::*, ui, utils::HashMap};
+use bevy_reactor::*;
+
+use crate::{
+    colors,
+    graph_model::{
+        GraphCommand, GraphConnection, GraphModel, GraphNode, GraphNodeId, GraphPort, GraphPortRef,
+    },
+};
+
+use super::{
+    EdgeDisplay, GraphDisplay, GraphNodeMarker, InputTerminalDisplay, NodeDisplay,
+    OutputTerminalDisplay,
+};
+
+fn style_port_label(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Row)
+        .justify_content(ui::JustifyContent::FlexStart)
+        .align_items(ui::AlignItems::Center)
+        .min_height(20.);
+}
+
+/// Map from each port (and whether it is an output) to the entity which displays it, used to
+/// find the endpoints of connections.
+#[derive(Component, Default)]
+struct GraphPortDisplays(HashMap<(GraphPortRef, bool), Entity>);
+
+/// Returns the entity which displays a port, allocating it if the port has not been displayed
+/// yet. Both the port and the connections to it call this, so whichever is built first
+/// creates the entity and the other finds it, regardless of the order they are built in.
+fn port_display(world: &mut World, displays: Entity, port: GraphPortRef, output: bool) -> Entity {
+    let existing = world
+        .get::<GraphPortDisplays>(displays)
+        .and_then(|displays| displays.0.get(&(port, output)).copied())
+        .filter(|display| world.get_entity(*display).is_some());
+    if let Some(display) = existing {
+        return display;
+    }
+    let display = world.spawn_empty().id();
+    if let Some(mut displays) = world.get_mut::<GraphPortDisplays>(displays) {
+        displays.0.insert((port, output), display);
+    }
+    display
+}
+
+/// Displays the [`GraphModel`] on an entity as an editable node graph. The view is rebuilt
+/// reactively as the model changes, and dragging a node edits the model with an undoable
+/// [`GraphCommand::MoveNode`], so that the whole drag is a single step of the undo history.
+pub struct GraphModelDisplay {
+    /// The entity which holds the [`GraphModel`].
+    pub model: Entity,
+
+    /// Additional styles to be applied to the graph element.
+    pub style: StyleHandle,
+
+    /// Whether to show a minimap of the graph.
+    pub minimap: bool,
+}
+
+impl GraphModelDisplay {
+    /// Create a new display for the [`GraphModel`] on the `model` entity.
+    pub fn new(model: Entity) -> Self {
+        Self {
+            model,
+            style: StyleHandle::default(),
+            minimap: false,
+        }
+    }
+
+    /// Set the additional styles for the graph.
+    pub fn style<S: StyleTuple + 'static>(mut self, style: S) -> Self {
+        self.style = style.into_handle();
+        self
+    }
+
+    /// Show a minimap in the corner of the graph.
+    pub fn minimap(mut self, minimap: bool) -> Self {
+        self.minimap = minimap;
+        self
+    }
+}
+
+impl ViewTemplate for GraphModelDisplay {
+    fn create(&self, cx: &mut Cx) -> impl IntoView {
+        let model = self.model;
+        let displays = cx.create_owned_entity();
+        cx.world_mut()
+            .entity_mut(displays)
+            .insert(GraphPortDisplays::default());
+
+        // Memoized so that moving a node doesn't re-diff the lists of nodes and connections.
+        let node_ids = cx.create_memo(move |cx| {
+            cx.use_component::<GraphModel>(model)
+                .map_or(Vec::new(), |model| model.node_ids())
+        });
+        let connections = cx.create_memo(move |cx| {
+            cx.use_component::<GraphModel>(model)
+                .map_or(Vec::new(), |model| model.connections().to_vec())
+        });
+
+        // Forget the displays of ports whose nodes have been removed, or which have been
+        // despawned.
+        cx.create_effect(move |cx| {
+            node_ids.get_clone(cx);
+            let world = cx.world_mut();
+            let (Some(graph), Some(port_displays)) = (
+                world.get::<GraphModel>(model),
+                world.get::<GraphPortDisplays>(displays),
+            ) else {
+                return;
+            };
+            let stale: Vec<(GraphPortRef, bool)> = port_displays
+                .0
+                .iter()
+                .filter(|((port, output), display)| {
+                    let ports = graph.node(port.node).map_or(0, |node| match output {
+                        true => node.outputs.len(),
+                        false => node.inputs.len(),
+                    });
+                    port.port >= ports || world.get_entity(**display).is_none()
+                })
+                .map(|(key, _)| *key)
+                .collect();
+            if !stale.is_empty() {
+                let mut port_displays = world.get_mut::<GraphPortDisplays>(displays).unwrap();
+                for key in stale {
+                    port_displays.0.remove(&key);
+                }
+            }
+        });
+
+        GraphDisplay::new()
+            .minimap(self.minimap)
+            .style(self.style.clone())
+            .children((
+                For::each(
+                    move |cx| connections.get_clone(cx).into_iter(),
+                    move |connection| GraphModelEdge {
+                        connection: *connection,
+                        displays,
+                    },
+                ),
+                For::each(
+                    move |cx| node_ids.get_clone(cx).into_iter(),
+                    move |id| GraphModelNode {
+                        model,
+                        id: *id,
+                        displays,
+                    },
+                ),
+            ))
+    }
+}
+
+/// Displays a connection of a [`GraphModel`].
+struct GraphModelEdge {
+    connection: GraphConnection,
+    displays: Entity,
+}
+
+impl ViewTemplate for GraphModelEdge {
+    fn create(&self, cx: &mut Cx) -> impl IntoView {
+        let displays = self.displays;
+        let port_position_signal = |cx: &mut Cx, port: GraphPortRef, output: bool| {
+            port_display(cx.world_mut(), displays, port, output);
+            cx.create_derived(move |cx| {
+                cx.use_component::<GraphPortDisplays>(displays)
+                    .and_then(|displays| displays.0.get(&(port, output)).copied())
+                    .and_then(|display| port_position(cx, display, output))
+                    .unwrap_or_default()
+            })
+        };
+        EdgeDisplay {
+            src_pos: port_position_signal(cx, self.connection.src, true),
+            dst_pos: port_position_signal(cx, self.connection.dst, false),
+        }
+    }
+}
+
+/// Find the position of a port's connector, relative to the canvas which contains its node.
+fn port_position(cx: &Rcx, display: Entity, output: bool) -> Option<Vec2> {
+    cx.world().get_entity(display)?;
+    let node = cx.use_component::<Node>(display)?;
+    let transform = cx.use_component::<GlobalTransform>(display)?;
+    let rect = node.logical_rect(transform);
+
+    // The canvas is the parent of the node which contains the port.
+    let mut current = display;
+    while cx.use_component::<GraphNodeMarker>(current).is_none() {
+        current = cx.use_component::<Parent>(current)?.get();
+    }
+    let canvas = cx.use_component::<Parent>(current)?.get();
+    let node = cx.use_component::<Node>(canvas)?;
+    let transform = cx.use_component::<GlobalTransform>(canvas)?;
+    let origin = node.logical_rect(transform).min;
+
+    let x = if output { rect.max.x } else { rect.min.x };
+    Some(Vec2::new(x, rect.center().y) - origin)
+}
+
+/// Displays a single node of a [`GraphModel`].
+struct GraphModelNode {
+    model: Entity,
+    id: GraphNodeId,
+    displays: Entity,
+}
+
+impl ViewTemplate for GraphModelNode {
+    fn create(&self, cx: &mut Cx) -> impl IntoView {
+        let model = self.model;
+        let id = self.id;
+        let displays = self.displays;
+
+        // Each part of the node is memoized, so that editing one node doesn't update the
+        // displays of all the others.
+        let position = node_memo(cx, model, id, |node| node.position);
+        let title = node_memo(cx, model, id, |node| node.title.clone());
+        let outputs = node_memo(cx, model, id, |node| node.outputs.clone());
+        let inputs = node_memo(cx, model, id, |node| node.inputs.clone());
+        NodeDisplay::new()
+            .position(position)
+            .title(title)
+            .animate(0.2)
+            .on_drag(cx.create_callback(move |cx, position: Vec2| {
+                if let Some(mut graph) = cx.world_mut().get_mut::<GraphModel>(model) {
+                    graph.execute_merged(GraphCommand::MoveNode(id, position));
+                }
+            }))
+            .on_drag_end(cx.create_callback(move |cx, _| {
+                if let Some(mut graph) = cx.world_mut().get_mut::<GraphModel>(model) {
+                    graph.end_merge();
+                }
+            }))
+            .children((
+                For::index(
+                    move |cx| outputs.get_clone(cx).into_iter(),
+                    move |port, index| GraphModelPort {
+                        port: GraphPortRef::new(id, index),
+                        output: true,
+                        info: port.clone(),
+                        displays,
+                    },
+                ),
+                For::index(
+                    move |cx| inputs.get_clone(cx).into_iter(),
+                    move |port, index| GraphModelPort {
+                        port: GraphPortRef::new(id, index),
+                        output: false,
+                        info: port.clone(),
+                        displays,
+                    },
+                ),
+            ))
+    }
+}
+
+/// Create a memo which reads part of a node of a [`GraphModel`], or the default value if the
+/// node doesn't exist.
+fn node_memo<R: PartialEq + Clone + Default + Send + Sync + 'static>(
+    cx: &mut Cx,
+    model: Entity,
+    id: GraphNodeId,
+    part: fn(&GraphNode) -> R,
+) -> Signal<R> {
+    cx.create_memo(move |cx| {
+        cx.use_component::<GraphModel>(model)
+            .and_then(|model| model.node(id))
+            .map(part)
+            .unwrap_or_default()
+    })
+}
+
+/// Displays an input or output port of a [`GraphModel`] node.
+struct GraphModelPort {
+    port: GraphPortRef,
+    output: bool,
+    info: GraphPort,
+    displays: Entity,
+}
+
+impl ViewTemplate for GraphModelPort {
+    fn create(&self, cx: &mut Cx) -> impl IntoView {
+        let display = port_display(cx.world_mut(), self.displays, self.port, self.output);
+
+        if self.output {
+            OutputTerminalDisplay {
+                id: display,
+                color: colors::U4,
+                label: self.info.name.clone(),
+            }
+            .into_view()
+        } else {
+            InputTerminalDisplay {
+                id: display,
+                color: colors::U4,
+                control: Element::<NodeBundle>::new()
+                    .style(style_port_label)
+                    .children(self.info.name.clone())
+                    .into_view(),
+            }
+            .into_view()
+        }
+    }
+}
@@ -1,18 +1,27 @@
 use bevy::{asset::AssetPath, prelude::*};
 use bevy_reactor::*;
 
-use crate::colors;
+use crate::{
+    hooks::{CreateThemeSignal, ThemeToken},
+    icon_atlas::IconAtlases,
+    svg_icon::SvgIcon,
+};
 
-/// Control that displays an icon.
+/// Control that displays an icon. Icons may be bitmap images, SVG files (with the `.svg`
+/// extension) which are rasterized to match the icon size and display scale, or named
+/// entries in a registered [`IconAtlas`](crate::icon_atlas::IconAtlas).
 pub struct Icon {
     /// Asset path for the icon
     pub icon: String,
 
+    /// Name of an icon in a registered icon atlas. When set, this is used instead of `icon`.
+    pub name: Option<String>,
+
     /// Size of the icon in pixels.
     pub size: Vec2,
 
-    /// Color of the icon.
-    pub color: Signal<Color>,
+    /// Color of the icon. Defaults to the theme's foreground color.
+    pub color: Option<Signal<Color>>,
 
     /// Additional styles to apply to the icon
     pub style: StyleHandle,
@@ -27,6 +36,14 @@ impl Icon {
         }
     }
 
+    /// Create a new icon which displays the icon registered under `name` in an icon atlas.
+    pub fn named(name: &str) -> Self {
+        Self {
+            name: Some(name.to_string()),
+            ..default()
+        }
+    }
+
     /// Set the size of the icon.
     pub fn size(mut self, size: Vec2) -> Self {
         self.size = size;
@@ -35,7 +52,7 @@ impl Icon {
 
     /// Set the color of the icon.
     pub fn color(mut self, color: impl IntoSignal<Color>) -> Self {
-        self.color = color.into_signal();
+        self.color = Some(color.into_signal());
         self
     }
 
@@ -50,33 +67,73 @@ impl Default for Icon {
     fn default() -> Self {
         Self {
             icon: "".to_string(),
+            name: None,
             size: Vec2::splat(12.0),
-            color: Signal::Constant(colors::FOREGROUND.into()),
+            color: None,
             style: StyleHandle::default(),
         }
     }
 }
 
 impl ViewTemplate for Icon {
-    fn create(&self, _cx: &mut Cx) -> impl IntoView {
-        let color = self.color;
+    fn create(&self, cx: &mut Cx) -> impl IntoView {
+        let color = match self.color {
+            Some(color) => color,
+            None => {
+                let foreground = cx.create_theme_color(ThemeToken::Foreground);
+                cx.create_derived(move |cx| Color::from(foreground.get(cx)))
+            }
+        };
         let icon = self.icon.clone();
         let size = self.size;
+        let path = AssetPath::parse(&icon);
+        let svg = match path.get_full_extension() {
+            Some(ext) if self.name.is_none() && ext.eq_ignore_ascii_case("svg") => {
+                let server = cx.world().resource::<AssetServer>();
+                Some(SvgIcon::new(server.load(path), size))
+            }
+            _ => None,
+        };
+        let is_bitmap = self.name.is_none() && svg.is_none();
+
+        let mut element = Element::<NodeBundle>::new().style((
+            move |sb: &mut StyleBuilder| {
+                sb.width(size.x).height(size.y);
+                if is_bitmap {
+                    sb.background_image(AssetPath::parse(&icon));
+                }
+            },
+            self.style.clone(),
+        ));
+        if let Some(svg) = svg {
+            // The image is transparent until the SVG has been rasterized.
+            element = element.insert((svg, UiImage::default()));
+        } else if let Some(name) = &self.name {
+            let atlas_icon = cx
+                .world()
+                .get_resource::<IconAtlases>()
+                .and_then(|atlases| atlases.get(name))
+                .cloned();
+            element = match atlas_icon {
+                Some(atlas_icon) => element.insert((
+                    UiImage::new(atlas_icon.texture),
+                    TextureAtlas {
+                        layout: atlas_icon.layout,
+                        index: atlas_icon.index,
+                    },
+                )),
+                None => {
+                    warn!("Unknown icon: {}", name);
+                    element.insert(UiImage::default())
+                }
+            };
+        }
 
-        Element::<NodeBundle>::new()
-            .style((
-                move |sb: &mut StyleBuilder| {
-                    sb.width(size.x)
-                        .height(size.y)
-                        .background_image(AssetPath::parse(&icon));
-                },
-                self.style.clone(),
-            ))
-            .create_effect(move |cx, ent| {
-                let color = color.get(cx);
-                let mut ent = cx.world_mut().entity_mut(ent);
-                let mut uii = ent.get_mut::<UiImage>().unwrap();
-                uii.color = color;
-            })
+        element.create_effect(move |cx, ent| {
+            let color = color.get(cx);
+            let mut ent = cx.world_mut().entity_mut(ent);
+            let mut uii = ent.get_mut::<UiImage>().unwrap();
+            uii.color = color;
+        })
     }
 }
@@ -1,30 +1,104 @@
 use bevy::{prelude::*, ui};
+use bevy_mod_picking::prelude::*;
 use bevy_reactor::*;
 
-use crate::colors;
+use crate::{
+    colors,
+    scrolling::{ScrollArea, ScrollContent},
+    selection::Selection,
+    touch_target::click_was_drag,
+    typography,
+};
 
 use super::ScrollView;
 
-fn style_listview(ss: &mut StyleBuilder) {
+pub(crate) fn style_listview(ss: &mut StyleBuilder) {
     ss.background_color(colors::U1)
         .border_radius(5.0)
         .padding(3);
 }
 
-fn style_listview_inner(ss: &mut StyleBuilder) {
+fn style_listview_item(ss: &mut StyleBuilder) {
+    ss.border_radius(3.0).text_overflow(TextOverflow::Ellipsis);
+}
+
+pub(crate) fn style_listview_inner(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Column)
+        .align_items(ui::AlignItems::Stretch);
+}
+
+fn style_listview_section(ss: &mut StyleBuilder) {
     ss.display(ui::Display::Flex)
         .flex_direction(ui::FlexDirection::Column)
         .align_items(ui::AlignItems::Stretch);
 }
 
+fn style_listview_section_header(ss: &mut StyleBuilder) {
+    ss.position(ui::PositionType::Relative)
+        .z_index(ZIndex::Local(1))
+        .padding((4, 2))
+        .font_size(13)
+        .color(colors::DIM)
+        .background_color(colors::U1);
+}
+
+/// A section of a [`ListView`], with a header which sticks to the top of the list while any
+/// part of the section is visible.
+#[derive(Clone, Default)]
+pub struct ListSection {
+    /// The content of the section header.
+    pub header: ChildArray,
+
+    /// The items in the section.
+    pub children: ChildArray,
+}
+
+impl ListSection {
+    /// Create a new, empty list section.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the content of the section header.
+    pub fn header<V: ChildViewTuple>(mut self, header: V) -> Self {
+        self.header = header.to_child_array();
+        self
+    }
+
+    /// Set the items in the section.
+    pub fn children<V: ChildViewTuple>(mut self, children: V) -> Self {
+        self.children = children.to_child_array();
+        self
+    }
+}
+
+/// Component which makes a section header stick to the top of the scroll area.
+#[derive(Component)]
+pub(crate) struct StickyHeader {
+    /// The scroll area which the list is displayed in.
+    scroll_area: Entity,
+}
+
 /// A scrollable list of items.
 #[derive(Clone, Default)]
 pub struct ListView {
     /// Additional styles to be applied to the list view.
     pub style: StyleHandle,
 
-    /// The content of the dialog header.
+    /// The items in the list.
     pub children: ChildArray,
+
+    /// Sections of items with sticky headers, shown after `children`. Items are numbered
+    /// consecutively across sections for selection.
+    pub sections: Vec<ListSection>,
+
+    /// Callback called when an item is clicked, with the index of the item.
+    pub on_select: Option<Callback<usize>>,
+
+    /// Selection model, indexed by item position. When present, clicking an item updates
+    /// the selection and selected items are highlighted.
+    pub selection: Option<Selection<usize>>,
 }
 
 impl ListView {
@@ -44,18 +118,158 @@ impl ListView {
         self.children = children.to_child_array();
         self
     }
+
+    /// Add a section of items with a sticky header.
+    pub fn section(mut self, section: ListSection) -> Self {
+        self.sections.push(section);
+        self
+    }
+
+    /// Set the callback called when an item is clicked.
+    pub fn on_select(mut self, on_select: Callback<usize>) -> Self {
+        self.on_select = Some(on_select);
+        self
+    }
+
+    /// Set the selection model used to select items.
+    pub fn selection(mut self, selection: Selection<usize>) -> Self {
+        self.selection = Some(selection);
+        self
+    }
 }
 
 impl ViewTemplate for ListView {
-    fn create(&self, _cx: &mut Cx) -> impl IntoView {
+    fn create(&self, cx: &mut Cx) -> impl IntoView {
+        let on_select = self.on_select;
+        let selection = self.selection;
+        let count = self.children.len()
+            + self
+                .sections
+                .iter()
+                .map(|section| section.children.len())
+                .sum::<usize>();
+        let selectable = on_select.is_some() || selection.is_some();
+        let items = |children: &ChildArray, first: usize| {
+            if selectable {
+                children
+                    .iter()
+                    .enumerate()
+                    .map(|(index, child)| {
+                        list_item(child.clone(), first + index, count, on_select, selection)
+                    })
+                    .collect::<Vec<_>>()
+                    .to_child_array()
+            } else {
+                children.clone()
+            }
+        };
+
+        let id_scroll_area = cx.create_entity();
+        let mut first = self.children.len();
+        let sections = self
+            .sections
+            .iter()
+            .map(|section| {
+                let section_items = items(&section.children, first);
+                first += section.children.len();
+                Element::<NodeBundle>::new()
+                    .named("ListView::Section")
+                    .style(style_listview_section)
+                    .children((
+                        Element::<NodeBundle>::new()
+                            .named("ListView::SectionHeader")
+                            .style((typography::text_default, style_listview_section_header))
+                            .insert(StickyHeader {
+                                scroll_area: id_scroll_area,
+                            })
+                            .children(section.header.clone()),
+                        section_items,
+                    ))
+                    .into_view()
+            })
+            .collect::<Vec<_>>();
+
         ScrollView::new()
+            .scroll_area_id(id_scroll_area)
             .children(
                 Element::<NodeBundle>::new()
                     .named("ListView")
                     .style(style_listview_inner)
-                    .children(self.children.clone()),
+                    .children((items(&self.children, 0), sections.to_child_array())),
             )
             .style((style_listview, self.style.clone()))
             .scroll_enable_y(true)
     }
 }
+
+/// Wrap a list item so that it can be clicked and selected.
+fn list_item(
+    child: ViewRef,
+    index: usize,
+    count: usize,
+    on_select: Option<Callback<usize>>,
+    selection: Option<Selection<usize>>,
+) -> ViewRef {
+    Element::<NodeBundle>::new()
+        .named("ListView::Item")
+        .style(style_listview_item)
+        .insert(On::<Pointer<Click>>::run(move |world: &mut World| {
+            // Dragging a touch across the list scrolls it without selecting anything.
+            if click_was_drag(world) {
+                return;
+            }
+            if let Some(selection) = selection {
+                let items: Vec<usize> = (0..count).collect();
+                selection.click(world, index, &items);
+            }
+            if let Some(on_select) = on_select {
+                world.run_callback(on_select, index);
+            }
+        }))
+        .create_effect(move |cx, ent| {
+            let selected = selection.map_or(false, |sel| sel.is_selected(cx, &index));
+            let mut bg = cx.world_mut().get_mut::<BackgroundColor>(ent).unwrap();
+            bg.0 = match selected {
+                true => colors::TEXT_SELECT.into(),
+                false => Srgba::NONE.into(),
+            };
+        })
+        .children(child)
+        .into_view()
+}
+
+/// Offset each sticky header so that it stays at the top of the visible area while its
+/// section is scrolled past, until the bottom of the section pushes it out of view.
+pub(crate) fn update_sticky_headers(
+    mut query_headers: Query<(&StickyHeader, &Node, &Parent, &mut Style)>,
+    query_scroll_area: Query<(&ScrollArea, &Children)>,
+    query_nodes: Query<(&Node, &GlobalTransform)>,
+    query_content: Query<(), With<ScrollContent>>,
+) {
+    for (header, header_node, parent, mut style) in query_headers.iter_mut() {
+        let Ok((scroll_area, children)) = query_scroll_area.get(header.scroll_area) else {
+            continue;
+        };
+        let Some(content) = children
+            .iter()
+            .find(|child| query_content.contains(**child))
+        else {
+            continue;
+        };
+        let (Ok((content_node, content_gt)), Ok((section_node, section_gt))) =
+            (query_nodes.get(*content), query_nodes.get(parent.get()))
+        else {
+            continue;
+        };
+
+        // Measure the section relative to the scrolling content, which doesn't depend on
+        // the scroll position at the time of the last layout.
+        let section_rect = section_node.logical_rect(section_gt);
+        let section_top = section_rect.min.y - content_node.logical_rect(content_gt).min.y;
+        let max_offset = (section_rect.height() - header_node.size().y).max(0.);
+        let offset = (scroll_area.scroll_top - section_top).clamp(0., max_offset);
+        if style.top != ui::Val::Px(offset) {
+            style.top = ui::Val::Px(offset);
+        }
+    }
+}
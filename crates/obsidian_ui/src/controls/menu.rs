@@ -1,9 +1,19 @@
 use crate::{
-    colors,
+    backdrop::BackdropBlur,
+    colors, elevation,
     floating::{FloatAlign, FloatPosition, FloatSide, Floating},
-    focus::{AutoFocus, KeyPressEvent, NavAction, TabGroup, TabIndex, TabNavigation},
-    hooks::{BistableTransitionState, CreateBistableTransition, CreateFocusSignal},
+    focus::{
+        AutoFocus, FocusScope, FocusVisible, KeyPressEvent, NavAction, TabGroup, TabIndex,
+        TabNavigation,
+    },
+    hooks::{
+        BistableTransitionState, CreateBistableTransition, CreateDisabledSignal, CreateFocusSignal,
+        CreateThemeSignal, ThemeToken,
+    },
+    hotkeys::Hotkeys,
+    popup_stack::Popup,
     size::Size,
+    touch_target::click_was_drag,
     typography, RoundedCorners,
 };
 use bevy::{
@@ -14,6 +24,7 @@ use bevy::{
     ecs::system::SystemState,
     prelude::*,
     ui,
+    utils::HashMap,
 };
 use bevy_mod_picking::{events::PointerCancel, prelude::*};
 use bevy_reactor::*;
@@ -24,6 +35,110 @@ use super::{style_button, style_button_bg, ButtonVariant, Icon, Spacer};
 #[derive(Component)]
 struct MenuAnchor(Entity);
 
+/// Which of the optional leading columns are used by the items in a [`MenuPopup`]. If any item
+/// uses a column, then all of the items reserve space for it, so that their labels line up.
+#[derive(Clone, Copy, Default, PartialEq)]
+struct MenuColumns {
+    check: bool,
+    icon: bool,
+}
+
+/// View context component inserted by a [`MenuPopup`], which its items use to coordinate
+/// their columns.
+#[derive(Component, Clone, Copy)]
+struct MenuPopupContext {
+    columns: Mutable<MenuColumns>,
+}
+
+/// Marker for a menu popup node. The shortcut labels of the items within it are given a
+/// common width, so that they line up.
+#[derive(Component)]
+pub(crate) struct MenuShortcutColumn;
+
+/// Marker for the shortcut label of a menu item.
+#[derive(Component)]
+pub(crate) struct MenuShortcut;
+
+/// View context component inserted by a [`MenuBar`], which coordinates the menu buttons
+/// within it.
+#[derive(Component, Clone, Copy)]
+pub(crate) struct MenuBarContext {
+    /// Root node of the bar.
+    node: Entity,
+
+    /// Anchors of the menu buttons in the bar, in order.
+    buttons: Mutable<Vec<Entity>>,
+
+    /// Anchor of the menu button whose menu is open.
+    active: Mutable<Option<Entity>>,
+
+    /// Widget which had focus before the bar was focused with the Alt key.
+    restore: Mutable<Option<Entity>>,
+}
+
+impl MenuBarContext {
+    /// The menu button `delta` places after `button`, wrapping around at the ends of the bar.
+    fn neighbor(&self, world: &mut World, button: Entity, delta: isize) -> Option<Entity> {
+        let buttons = self.buttons.get_clone(world);
+        let index = buttons.iter().position(|b| *b == button)?;
+        let len = buttons.len() as isize;
+        Some(buttons[(index as isize + delta).rem_euclid(len) as usize])
+    }
+
+    /// Move the focus to the menu button `delta` places after `button`. If a menu is open,
+    /// then the menu of the new button is opened in its place.
+    fn step(&self, world: &mut World, button: Entity, delta: isize) {
+        if let Some(next) = self.neighbor(world, button, delta) {
+            if self.active.get(world).is_some() {
+                self.active.set(world, Some(next));
+            }
+            world.resource_mut::<Focus>().0 = Some(next);
+        }
+    }
+
+    /// While a menu is open, switch to the menu of the button under the pointer, if any.
+    fn hover(&self, world: &mut World, position: Vec2) {
+        let active = self.active.get(world);
+        for button in self.buttons.get_clone(world) {
+            let Some(entt) = world.get_entity(button) else {
+                continue;
+            };
+            let (Some(node), Some(transform)) = (entt.get::<Node>(), entt.get::<GlobalTransform>())
+            else {
+                continue;
+            };
+            if node.logical_rect(transform).contains(position) {
+                if active.is_some() && active != Some(button) {
+                    self.active.set(world, Some(button));
+                    world.resource_mut::<Focus>().0 = Some(button);
+                }
+                return;
+            }
+        }
+    }
+
+    /// Close any open menu, and return the focus to where it was before the bar was focused.
+    fn leave(&self, world: &mut World) {
+        self.active.set(world, None);
+        let restore = self.restore.get(world);
+        self.restore.set(world, None);
+        world.resource_mut::<Focus>().0 = restore;
+    }
+
+    /// Focus the first menu button in the bar, or leave the bar if it already has focus.
+    fn toggle_focus(&self, world: &mut World) {
+        let buttons = self.buttons.get_clone(world);
+        let focus = world.resource::<Focus>().0;
+        if self.active.get(world).is_some() || focus.is_some_and(|f| buttons.contains(&f)) {
+            self.leave(world);
+        } else if let Some(first) = buttons.first() {
+            self.restore.set(world, focus);
+            world.resource_mut::<Focus>().0 = Some(*first);
+            world.resource_mut::<FocusVisible>().0 = true;
+        }
+    }
+}
+
 #[derive(Clone, Event, EntityEvent)]
 #[can_bubble]
 pub(crate) struct MenuCloseEvent {
@@ -46,6 +161,9 @@ fn style_menu_barrier(ss: &mut StyleBuilder) {
         .background_color(colors::U2.with_alpha(0.0));
 }
 
+/// How long the backdrop blur takes to fade in when a menu opens.
+const BACKDROP_BLUR_DURATION: f32 = 0.15;
+
 /// A widget that displays a drop-down menu when clicked.
 #[derive(Default)]
 pub struct MenuButton {
@@ -84,6 +202,9 @@ pub struct MenuButton {
 
     /// The tab index of the button (default 0).
     pub tab_index: i32,
+
+    /// Whether to blur the content behind the menu. See [`BackdropBlur`].
+    pub backdrop_blur: bool,
 }
 
 impl MenuButton {
@@ -157,6 +278,12 @@ impl MenuButton {
         self.tab_index = tab_index;
         self
     }
+
+    /// Sets whether to blur the content behind the menu while it is open.
+    pub fn backdrop_blur(mut self, backdrop_blur: bool) -> Self {
+        self.backdrop_blur = backdrop_blur;
+        self
+    }
 }
 
 impl ViewTemplate for MenuButton {
@@ -167,12 +294,46 @@ impl ViewTemplate for MenuButton {
         let hovering = cx.create_hover_signal(id_anchor);
         let focused = cx.create_focus_visible_signal(id_anchor);
 
-        let disabled = self.disabled;
+        let disabled = cx.create_disabled_signal(self.disabled);
+        let color_default = cx.create_theme_color(ThemeToken::Button);
+        let color_primary = cx.create_theme_color(ThemeToken::ButtonPrimary);
+        let color_danger = cx.create_theme_color(ThemeToken::ButtonDanger);
+        let color_selected = cx.create_theme_color(ThemeToken::ButtonSelected);
+        let color_focus = cx.create_theme_color(ThemeToken::Focus);
         let corners = self.corners;
         let minimal = self.minimal;
 
         let size = self.size;
         let popup = self.popup.clone();
+        let backdrop_blur = self.backdrop_blur;
+
+        let close = cx.create_callback(move |cx, ()| {
+            open.set(cx, false);
+        });
+
+        // Within a menu bar, only one menu is open at a time.
+        let bar = cx.use_inherited_component::<MenuBarContext>().copied();
+        if let Some(bar) = bar {
+            bar.buttons
+                .update(cx, |mut buttons| buttons.push(id_anchor));
+            cx.on_cleanup(move |world| {
+                if bar.buttons.is_alive(world) {
+                    bar.buttons
+                        .update(world, |mut buttons| buttons.retain(|b| *b != id_anchor));
+                }
+            });
+            bar.active.on_change(cx, move |_, active, world| {
+                let is_active = *active == Some(id_anchor) && !disabled.get(world);
+                open.set(world, is_active);
+            });
+            open.on_change(cx, move |_, is_open, world| {
+                if *is_open {
+                    bar.active.set(world, Some(id_anchor));
+                } else if bar.active.get(world) == Some(id_anchor) {
+                    bar.active.set(world, None);
+                }
+            });
+        }
 
         cx.insert(MenuAnchor(id_anchor));
         cx.insert(On::<MenuCloseEvent>::run(move |world: &mut World| {
@@ -213,19 +374,42 @@ impl ViewTemplate for MenuButton {
                 }),
                 On::<KeyPressEvent>::run({
                     move |world: &mut World| {
-                        if !disabled.get(world) {
-                            let mut event = world
-                                .get_resource_mut::<ListenerInput<KeyPressEvent>>()
-                                .unwrap();
-                            if !event.repeat
-                                && (event.key_code == KeyCode::Enter
-                                    || event.key_code == KeyCode::Space)
-                            {
-                                event.stop_propagation();
+                        if disabled.get(world) {
+                            return;
+                        }
+                        let event = world.resource::<ListenerInput<KeyPressEvent>>();
+                        if event.repeat {
+                            return;
+                        }
+                        let handled = match (event.key_code, bar) {
+                            (KeyCode::Enter | KeyCode::Space, _) => {
                                 open.update(world, |mut state| {
                                     *state = !*state;
                                 });
+                                true
+                            }
+                            (KeyCode::ArrowDown, Some(_)) => {
+                                open.set(world, true);
+                                true
+                            }
+                            (KeyCode::ArrowLeft, Some(bar)) => {
+                                bar.step(world, id_anchor, -1);
+                                true
+                            }
+                            (KeyCode::ArrowRight, Some(bar)) => {
+                                bar.step(world, id_anchor, 1);
+                                true
                             }
+                            (KeyCode::Escape, Some(bar)) => {
+                                bar.leave(world);
+                                true
+                            }
+                            _ => false,
+                        };
+                        if handled {
+                            world
+                                .resource_mut::<ListenerInput<KeyPressEvent>>()
+                                .stop_propagation();
                         }
                     }
                 }),
@@ -248,10 +432,10 @@ impl ViewTemplate for MenuButton {
                         let is_pressed = open.get(cx);
                         let is_hovering = hovering.get(cx);
                         let base_color = match variant.get(cx) {
-                            ButtonVariant::Default => colors::U3,
-                            ButtonVariant::Primary => colors::PRIMARY,
-                            ButtonVariant::Danger => colors::DESTRUCTIVE,
-                            ButtonVariant::Selected => colors::U4,
+                            ButtonVariant::Default => color_default.get(cx),
+                            ButtonVariant::Primary => color_primary.get(cx),
+                            ButtonVariant::Danger => color_danger.get(cx),
+                            ButtonVariant::Selected => color_selected.get(cx),
                         };
                         let color = match (is_pressed, is_hovering) {
                             (true, _) => base_color.lighter(0.05),
@@ -269,11 +453,12 @@ impl ViewTemplate for MenuButton {
                     })
                     .create_effect(move |cx, entt| {
                         let is_focused = focused.get(cx);
+                        let focus = color_focus.get(cx);
                         let mut entt = cx.world_mut().entity_mut(entt);
                         match is_focused {
                             true => {
                                 entt.insert(Outline {
-                                    color: colors::FOCUS.into(),
+                                    color: focus.into(),
                                     offset: ui::Val::Px(2.0),
                                     width: ui::Val::Px(2.0),
                                 });
@@ -317,10 +502,27 @@ impl ViewTemplate for MenuButton {
                                             });
                                         }
                                     }),
-                                    ZIndex::Global(100),
+                                    // Within a menu bar, hovering another menu button
+                                    // switches to its menu.
+                                    On::<Pointer<Move>>::run(move |world: &mut World| {
+                                        if let Some(bar) = bar {
+                                            let position = world
+                                                .resource::<ListenerInput<Pointer<Move>>>()
+                                                .pointer_location
+                                                .position;
+                                            bar.hover(world, position);
+                                        }
+                                    }),
+                                    Popup::new().on_close(close),
                                 ))
-                                .children(popup.clone()),
+                                .children((
+                                    backdrop_blur.then(|| {
+                                        BackdropBlur::new().duration(BACKDROP_BLUR_DURATION)
+                                    }),
+                                    popup.clone(),
+                                )),
                         )
+                        .layer("menus")
                     },
                     || (),
                 ),
@@ -339,6 +541,7 @@ fn style_popup(ss: &mut StyleBuilder) {
         .border_color(Srgba::BLACK)
         .border(1)
         .padding((0, 2));
+    elevation::elevation_2(ss);
 }
 
 /// UI component representing the popup menu.
@@ -404,18 +607,24 @@ impl ViewTemplate for MenuPopup {
         // Adds a delay to ensure the menu items are created before setting focus.
         let state = cx.create_bistable_transition(Signal::Constant(true), 0.01);
         let context = cx.use_inherited_component::<MenuAnchor>().unwrap();
+        let anchor = context.0;
+        let bar = cx.use_inherited_component::<MenuBarContext>().copied();
         let owner_id = cx.owner();
+        let columns = cx.create_mutable(MenuColumns::default());
+        cx.insert(MenuPopupContext { columns });
 
         Element::<NodeBundle>::new()
             .named("MenuPopup")
             .style((typography::text_default, style_popup, self.style.clone()))
             .insert((
+                MenuShortcutColumn,
                 TabGroup {
                     order: 1,
                     modal: true,
                 },
+                FocusScope,
                 Floating {
-                    anchor: context.0,
+                    anchor: anchor.into(),
                     position: vec![
                         FloatPosition {
                             side: self.side,
@@ -438,6 +647,22 @@ impl ViewTemplate for MenuPopup {
                     event.stop_propagation();
                 }),
                 On::<KeyPressEvent>::run(move |world: &mut World| {
+                    // Within a menu bar, the left and right arrows switch between menus.
+                    if let Some(bar) = bar {
+                        let event = world.resource::<ListenerInput<KeyPressEvent>>();
+                        let delta = match event.key_code {
+                            KeyCode::ArrowLeft if !event.repeat => -1,
+                            KeyCode::ArrowRight if !event.repeat => 1,
+                            _ => 0,
+                        };
+                        if delta != 0 {
+                            world
+                                .resource_mut::<ListenerInput<KeyPressEvent>>()
+                                .stop_propagation();
+                            bar.step(world, anchor, delta);
+                            return;
+                        }
+                    }
                     let mut st: SystemState<(
                         ResMut<ListenerInput<KeyPressEvent>>,
                         ResMut<Focus>,
@@ -483,6 +708,140 @@ impl ViewTemplate for MenuPopup {
     }
 }
 
+fn style_menu_bar(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Row)
+        .align_items(ui::AlignItems::Center)
+        .gap(2)
+        .padding(2);
+}
+
+/// A horizontal bar of [`MenuButton`]s, such as the main menu of an application. Once one menu
+/// is open, hovering another button in the bar switches to its menu without clicking, and the
+/// left and right arrow keys move between menus. Pressing and releasing Alt moves the focus
+/// to the bar, and back again.
+///
+/// The buttons are typically `minimal` and have `no_caret` set.
+#[derive(Default)]
+pub struct MenuBar {
+    /// The menu buttons in the bar.
+    pub children: ChildArray,
+
+    /// Additional styles to apply to the bar.
+    pub style: StyleHandle,
+}
+
+impl MenuBar {
+    /// Create a new menu bar.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the menu buttons in the bar.
+    pub fn children<V: ChildViewTuple>(mut self, children: V) -> Self {
+        self.children = children.to_child_array();
+        self
+    }
+
+    /// Set additional styles to apply to the bar.
+    pub fn style<S: StyleTuple + 'static>(mut self, style: S) -> Self {
+        self.style = style.into_handle();
+        self
+    }
+}
+
+impl ViewTemplate for MenuBar {
+    fn create(&self, cx: &mut Cx) -> impl IntoView {
+        let buttons = cx.create_mutable(Vec::new());
+        let active = cx.create_mutable(None);
+        let restore = cx.create_mutable(None);
+        let node = cx.create_entity();
+        cx.insert(MenuBarContext {
+            node,
+            buttons,
+            active,
+            restore,
+        });
+
+        Element::<NodeBundle>::for_entity(node)
+            .named("MenuBar")
+            .style((style_menu_bar, self.style.clone()))
+            .insert(AccessibilityNode::from(NodeBuilder::new(Role::MenuBar)))
+            .children(self.children.clone())
+    }
+}
+
+/// System which moves the focus to a [`MenuBar`], or back to where it was, when Alt is pressed
+/// and released on its own. If there are several bars, the one which is engaged or nearest to
+/// the focused widget is toggled; see `nearest_menu_bar`.
+pub(crate) fn toggle_menu_bar_focus(world: &mut World, mut armed: Local<bool>) {
+    const ALT: [KeyCode; 2] = [KeyCode::AltLeft, KeyCode::AltRight];
+    let Some(keys) = world.get_resource::<ButtonInput<KeyCode>>() else {
+        return;
+    };
+    if keys.any_just_pressed(ALT) {
+        *armed = true;
+    } else if keys.get_just_pressed().any(|key| !ALT.contains(key)) {
+        // Alt was used as a modifier.
+        *armed = false;
+    }
+    if !*armed || !keys.any_just_released(ALT) {
+        return;
+    }
+    *armed = false;
+    let bars: Vec<MenuBarContext> = world
+        .query::<&MenuBarContext>()
+        .iter(world)
+        .copied()
+        .collect();
+    let focus = world.get_resource::<Focus>().and_then(|focus| focus.0);
+    let engaged = bars.iter().position(|bar| {
+        bar.active.get(world).is_some()
+            || focus.is_some_and(|f| bar.buttons.get_clone(world).contains(&f))
+    });
+    let index = engaged.or_else(|| {
+        let nodes: Vec<Entity> = bars.iter().map(|bar| bar.node).collect();
+        nearest_menu_bar(world, &nodes, focus)
+    });
+    if let Some(index) = index {
+        bars[index].toggle_focus(world);
+    }
+}
+
+/// Choose which of several menu bars, given by their root nodes, the Alt key applies to: the
+/// bar which shares the innermost ancestor with the focused widget, such as the bar of the
+/// window containing it. Without focus, the topmost bar on screen is chosen.
+fn nearest_menu_bar(world: &World, nodes: &[Entity], focus: Option<Entity>) -> Option<usize> {
+    let mut ancestor = focus;
+    while let Some(entity) = ancestor {
+        if let Some(index) = nodes
+            .iter()
+            .position(|node| is_descendant(world, *node, entity))
+        {
+            return Some(index);
+        }
+        ancestor = world.get::<Parent>(entity).map(Parent::get);
+    }
+    let top = |node: &Entity| {
+        world
+            .get::<GlobalTransform>(*node)
+            .map_or(f32::MAX, |transform| transform.translation().y)
+    };
+    (0..nodes.len()).min_by(|a, b| top(&nodes[*a]).total_cmp(&top(&nodes[*b])))
+}
+
+/// True if `entity` is `ancestor` or one of its descendants.
+fn is_descendant(world: &World, entity: Entity, ancestor: Entity) -> bool {
+    let mut current = Some(entity);
+    while let Some(e) = current {
+        if e == ancestor {
+            return true;
+        }
+        current = world.get::<Parent>(e).map(Parent::get);
+    }
+    false
+}
+
 fn style_menu_item(ss: &mut StyleBuilder) {
     ss.height(24)
         .display(ui::Display::Flex)
@@ -493,6 +852,46 @@ fn style_menu_item(ss: &mut StyleBuilder) {
         .margin((2, 0));
 }
 
+fn style_menu_item_check(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_shrink(0.)
+        .width(12)
+        .height(12)
+        .margin_right(6);
+}
+
+fn style_menu_item_checkmark(ss: &mut StyleBuilder) {
+    ss.background_image("obsidian_ui://textures/checkmark.png")
+        .width(12)
+        .height(12);
+}
+
+fn style_menu_item_icon(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .justify_content(ui::JustifyContent::Center)
+        .align_items(ui::AlignItems::Center)
+        .flex_shrink(0.)
+        .width(16)
+        .height(16)
+        .margin_right(6);
+}
+
+fn style_menu_item_label(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Row)
+        .align_items(ui::AlignItems::Center)
+        .flex_grow(1.)
+        .text_overflow(TextOverflow::Ellipsis);
+}
+
+fn style_menu_item_shortcut(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Row)
+        .flex_shrink(0.)
+        .margin_left(24)
+        .color(colors::DIM);
+}
+
 /// UI component representing a menu item.
 #[derive(Default)]
 pub struct MenuItem {
@@ -510,8 +909,17 @@ pub struct MenuItem {
 
     /// Callback called when clicked
     pub on_click: Option<Callback>,
-    // icon
-    // shortcut
+
+    /// Path of an optional icon displayed before the label.
+    pub icon: Option<String>,
+
+    /// Optional keyboard shortcut displayed after the label, such as `"Ctrl+S"`. This is only
+    /// a label; the shortcut itself must be handled elsewhere.
+    pub shortcut: Option<String>,
+
+    /// Optional name of the [`Hotkeys`] action performed by the item. The key chord bound to
+    /// the action is displayed after the label, unless `shortcut` is set.
+    pub hotkey: Option<String>,
 }
 
 impl MenuItem {
@@ -532,7 +940,9 @@ impl MenuItem {
         self
     }
 
-    /// Set the checked state of the menu item.
+    /// Set the checked state of the menu item. A checkmark is displayed before the label while
+    /// the item is checked. Unless this is a constant `false`, all of the items in the popup
+    /// reserve space for the checkmark.
     pub fn checked(mut self, checked: impl IntoSignal<bool>) -> Self {
         self.checked = checked.into_signal();
         self
@@ -549,6 +959,25 @@ impl MenuItem {
         self.on_click = Some(on_click);
         self
     }
+
+    /// Set the icon displayed before the label.
+    pub fn icon(mut self, icon: &str) -> Self {
+        self.icon = Some(icon.to_string());
+        self
+    }
+
+    /// Set the keyboard shortcut label displayed after the label.
+    pub fn shortcut(mut self, shortcut: &str) -> Self {
+        self.shortcut = Some(shortcut.to_string());
+        self
+    }
+
+    /// Set the [`Hotkeys`] action performed by the item, whose key chord is displayed after
+    /// the label. The label follows changes to the binding.
+    pub fn hotkey(mut self, action: &str) -> Self {
+        self.hotkey = Some(action.to_string());
+        self
+    }
 }
 
 impl ViewTemplate for MenuItem {
@@ -559,7 +988,29 @@ impl ViewTemplate for MenuItem {
         let hovering = cx.create_hover_signal(id);
         let focused = cx.create_focus_signal(id);
 
-        let disabled = self.disabled;
+        let disabled = cx.create_disabled_signal(self.disabled);
+
+        // Register the columns used by this item with the popup, so that all items reserve
+        // space for them.
+        let checked = self.checked;
+        let needs = MenuColumns {
+            check: !matches!(checked, Signal::Constant(false)),
+            icon: self.icon.is_some(),
+        };
+        let columns = match cx.use_inherited_component::<MenuPopupContext>() {
+            Some(context) => {
+                let columns = context.columns;
+                let current = columns.get(cx.world());
+                if (needs.check && !current.check) || (needs.icon && !current.icon) {
+                    columns.update(cx, |mut columns| {
+                        columns.check |= needs.check;
+                        columns.icon |= needs.icon;
+                    });
+                }
+                columns.signal()
+            }
+            None => Signal::Constant(needs),
+        };
 
         Element::<NodeBundle>::for_entity(id)
             .named("MenuItem")
@@ -572,7 +1023,7 @@ impl ViewTemplate for MenuItem {
                     On::<Pointer<Click>>::run(move |world: &mut World| {
                         let mut st: SystemState<(EventWriter<MenuCloseEvent>, ResMut<Focus>)> =
                             SystemState::new(world);
-                        if !disabled.get(world) {
+                        if !disabled.get(world) && !click_was_drag(world) {
                             let (mut writer, mut focus) = st.get_mut(world);
                             focus.0 = Some(id);
                             if let Some(on_click) = on_click {
@@ -643,7 +1094,103 @@ impl ViewTemplate for MenuItem {
                 let mut bg = cx.world_mut().get_mut::<BackgroundColor>(ent).unwrap();
                 bg.0 = color.into();
             })
-            .children(self.label.clone())
+            .children((
+                Cond::new(
+                    move |cx| columns.get(cx).check,
+                    move || {
+                        Element::<NodeBundle>::new()
+                            .named("MenuItem::Check")
+                            .style(style_menu_item_check)
+                            .children(Cond::new(
+                                move |cx| checked.get(cx),
+                                || Element::<NodeBundle>::new().style(style_menu_item_checkmark),
+                                || (),
+                            ))
+                    },
+                    || (),
+                ),
+                Cond::new(
+                    move |cx| columns.get(cx).icon,
+                    {
+                        let icon = self.icon.clone();
+                        move || {
+                            Element::<NodeBundle>::new()
+                                .named("MenuItem::Icon")
+                                .style(style_menu_item_icon)
+                                .children(icon.as_deref().map(Icon::new))
+                        }
+                    },
+                    || (),
+                ),
+                Element::<NodeBundle>::new()
+                    .named("MenuItem::Label")
+                    .style(style_menu_item_label)
+                    .children(self.label.clone()),
+                {
+                    let shortcut = self.shortcut.clone();
+                    let hotkey = self.hotkey.clone();
+                    let label = move |rcx: &Rcx| match (&shortcut, &hotkey) {
+                        (Some(shortcut), _) => Some(shortcut.clone()),
+                        (None, Some(action)) => rcx
+                            .use_resource::<Hotkeys>()
+                            .chord(action)
+                            .map(|chord| chord.to_string()),
+                        (None, None) => None,
+                    };
+                    Cond::new(
+                        {
+                            let label = label.clone();
+                            move |rcx| label(rcx).is_some()
+                        },
+                        move || {
+                            let label = label.clone();
+                            Element::<NodeBundle>::new()
+                                .named("MenuItem::Shortcut")
+                                .style(style_menu_item_shortcut)
+                                .insert(MenuShortcut)
+                                .children(text_computed(move |rcx| label(rcx).unwrap_or_default()))
+                        },
+                        || (),
+                    )
+                },
+            ))
+    }
+}
+
+/// Gives the shortcut labels of the items in each menu popup the width of the widest one, so
+/// that they line up. Labels are measured by the size of their content, which is not affected
+/// by the minimum width set here.
+pub(crate) fn align_menu_shortcuts(
+    mut shortcuts: Query<(Entity, &Children, &mut Style), With<MenuShortcut>>,
+    nodes: Query<&Node>,
+    parents: Query<&Parent>,
+    popups: Query<(), With<MenuShortcutColumn>>,
+) {
+    let mut widths: HashMap<Entity, f32> = HashMap::new();
+    let mut popup_of: Vec<(Entity, Entity)> = Vec::new();
+    for (entity, children, _) in shortcuts.iter() {
+        let Some(popup) = parents
+            .iter_ancestors(entity)
+            .find(|ancestor| popups.contains(*ancestor))
+        else {
+            continue;
+        };
+        let width: f32 = nodes
+            .iter_many(children.iter())
+            .map(|node| node.size().x)
+            .sum();
+        let max = widths.entry(popup).or_default();
+        *max = max.max(width);
+        popup_of.push((entity, popup));
+    }
+
+    for (entity, popup) in popup_of {
+        let min_width = ui::Val::Px(widths[&popup]);
+        if let Ok((_, _, mut style)) = shortcuts.get_mut(entity) {
+            if style.min_width != min_width {
+                style.min_width = min_width;
+            }
+        }
     }
 }
 
@@ -662,3 +1209,40 @@ impl ViewTemplate for MenuDivider {
             .style(style_menu_divider)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nearest_menu_bar() {
+        let mut world = World::new();
+        let spawn_at =
+            |world: &mut World, y: f32| world.spawn(GlobalTransform::from_xyz(0., y, 0.)).id();
+
+        // A main window with a bar and a panel, and a floating window with its own bar.
+        let main_bar = spawn_at(&mut world, 10.);
+        let widget = spawn_at(&mut world, 100.);
+        let panel = world.spawn_empty().add_child(widget).id();
+        world.spawn_empty().push_children(&[main_bar, panel]);
+        let window_bar = spawn_at(&mut world, 200.);
+        let window_widget = spawn_at(&mut world, 220.);
+        world
+            .spawn_empty()
+            .push_children(&[window_bar, window_widget]);
+
+        let nodes = [window_bar, main_bar];
+        assert_eq!(nearest_menu_bar(&world, &nodes, Some(widget)), Some(1));
+        assert_eq!(
+            nearest_menu_bar(&world, &nodes, Some(window_widget)),
+            Some(0)
+        );
+        assert_eq!(nearest_menu_bar(&world, &nodes, Some(window_bar)), Some(0));
+
+        // Without focus, or with focus outside of any bar's tree, the topmost bar wins.
+        assert_eq!(nearest_menu_bar(&world, &nodes, None), Some(1));
+        let stray = world.spawn_empty().id();
+        assert_eq!(nearest_menu_bar(&world, &nodes, Some(stray)), Some(1));
+        assert_eq!(nearest_menu_bar(&world, &[], None), None);
+    }
+}
@@ -1,33 +1,65 @@
+mod badge;
 mod button;
 mod checkbox;
+mod chip;
 mod dialog;
+mod dock;
+mod dot_grid;
+mod floating_window;
+mod form;
 mod gradient_slider;
+mod graph_layout;
+mod graph_model_display;
 mod icon;
 mod listview;
 mod menu;
 mod node_graph;
+mod paged_listview;
+mod progress_bar;
+mod range_slider;
+mod rounded_clip;
 mod scrollview;
 mod slider;
 mod spacer;
+mod spinner;
 mod splitter;
 mod swatch;
 mod swatch_grid;
+mod table;
+mod tag_input;
 mod text_input;
+mod theme_override;
 mod tool_palette;
 
+pub use badge::*;
 pub use button::*;
 pub use checkbox::*;
+pub use chip::*;
 pub use dialog::*;
+pub use dock::*;
+pub use dot_grid::*;
+pub use floating_window::*;
+pub use form::*;
 pub use gradient_slider::*;
+pub use graph_layout::*;
+pub use graph_model_display::*;
 pub use icon::*;
 pub use listview::*;
 pub use menu::*;
 pub use node_graph::*;
+pub use paged_listview::*;
+pub use progress_bar::*;
+pub use range_slider::*;
+pub use rounded_clip::*;
 pub use scrollview::{ScrollView, Scrollbar, ScrollbarProps};
 pub use slider::*;
 pub use spacer::*;
+pub use spinner::*;
 pub use splitter::*;
 pub use swatch::*;
 pub use swatch_grid::*;
+pub use table::*;
+pub use tag_input::*;
 pub use text_input::*;
+pub use theme_override::*;
 pub use tool_palette::*;
@@ -1,15 +1,19 @@
 use std::ops::Mul;
 
-use bevy::{prelude::*, ui};
-use bevy_mod_picking::prelude::*;
+use bevy::{prelude::*, ui, utils::HashMap, window::CursorIcon};
+use bevy_mod_picking::{events::PointerCancel, pointer::PointerId, prelude::*};
 use bevy_reactor::*;
 
 use crate::{
     colors,
-    materials::{DotGridMaterial, DrawPathMaterial, DrawablePath},
+    hooks::CreateTweenedSignal,
+    materials::{DrawPathMaterial, DrawablePath},
+    pointer_capture::CapturePointer,
+    scrolling::ScrollArea,
+    selection::Selection,
 };
 
-use super::ScrollView;
+use super::{DotGrid, ScrollView};
 
 fn style_node_graph(ss: &mut StyleBuilder) {
     ss.background_color(colors::U1);
@@ -22,11 +26,58 @@ fn style_node_graph_content(ss: &mut StyleBuilder) {
         .min_height(ui::Val::Percent(100.));
 }
 
+fn style_node_graph_canvas(ss: &mut StyleBuilder) {
+    ss.cursor(CursorIcon::Grab);
+}
+
 fn style_node_graph_scroll(ss: &mut StyleBuilder) {
     ss.min_width(ui::Val::Px(2000.0));
 }
 
-/// An editable graph of nodes, connected by edges.
+fn style_node_graph_frame(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Column)
+        .align_items(ui::AlignItems::Stretch)
+        .position(ui::PositionType::Relative);
+}
+
+fn style_node_graph_fill(ss: &mut StyleBuilder) {
+    ss.flex_grow(1.).min_height(0);
+}
+
+/// Limits of the zoom factor which can be set by pinching the graph.
+const MIN_ZOOM: f32 = 0.25;
+const MAX_ZOOM: f32 = 4.;
+
+fn style_minimap(ss: &mut StyleBuilder) {
+    ss.position(ui::PositionType::Absolute)
+        .right(16)
+        .bottom(16)
+        .width(160)
+        .height(120)
+        .border(1)
+        .border_color(colors::U4)
+        .border_radius(3.0)
+        .background_color(colors::U2)
+        .overflow(ui::OverflowAxis::Clip);
+}
+
+fn style_minimap_node(ss: &mut StyleBuilder) {
+    ss.position(ui::PositionType::Absolute)
+        .background_color(colors::U4)
+        .pointer_events(false);
+}
+
+fn style_minimap_viewport(ss: &mut StyleBuilder) {
+    ss.position(ui::PositionType::Absolute)
+        .border(1)
+        .border_color(colors::ACCENT)
+        .background_color(colors::FOCUS)
+        .pointer_events(false);
+}
+
+/// An editable graph of nodes, connected by edges. On touch screens, the graph can be zoomed
+/// by pinching it with two fingers.
 #[derive(Default)]
 pub struct GraphDisplay {
     /// Nodes within the node graph.
@@ -34,6 +85,9 @@ pub struct GraphDisplay {
 
     /// Additional styles to be applied to the graph element.
     pub style: StyleHandle,
+
+    /// Whether to show a minimap of the graph in the corner of the view.
+    pub minimap: bool,
 }
 
 impl GraphDisplay {
@@ -53,31 +107,308 @@ impl GraphDisplay {
         self.style = style.into_handle();
         self
     }
+
+    /// Show a minimap in the corner of the graph, which displays the nodes and the visible
+    /// region. Clicking or dragging on the minimap pans the graph.
+    pub fn minimap(mut self, minimap: bool) -> Self {
+        self.minimap = minimap;
+        self
+    }
 }
 
 impl ViewTemplate for GraphDisplay {
     fn create(&self, cx: &mut Cx) -> impl IntoView {
-        let mut ui_materials = cx
-            .world_mut()
-            .get_resource_mut::<Assets<DotGridMaterial>>()
-            .unwrap();
-        let material = ui_materials.add(DotGridMaterial {
-            color_bg: LinearRgba::from(colors::U1).to_vec4(),
-            color_fg: LinearRgba::from(colors::U3).to_vec4(),
-        });
-
-        ScrollView::new()
+        let id_scroll_area = cx.create_entity();
+        let id_canvas = cx.create_entity();
+        let zoom = cx.create_mutable::<f32>(1.);
+        let scroll_view = ScrollView::new()
             .children(
-                Element::<MaterialNodeBundle<DotGridMaterial>>::new()
-                    .named("NodeGraph::Scroll")
-                    .insert(material.clone())
-                    .style(style_node_graph_scroll)
-                    .children(self.children.clone()),
+                Element::<NodeBundle>::for_entity(id_canvas)
+                    .named("NodeGraph::Canvas")
+                    .style(style_node_graph_canvas)
+                    .zoom(zoom)
+                    .insert((
+                        PinchGesture::default(),
+                        On::<Pointer<Down>>::run(move |world: &mut World| {
+                            let event = world
+                                .get_resource::<ListenerInput<Pointer<Down>>>()
+                                .unwrap();
+                            if !event.pointer_id.is_touch() {
+                                return;
+                            }
+                            let (pointer_id, position) =
+                                (event.pointer_id, event.pointer_location.position);
+                            let mut pinch = world.get_mut::<PinchGesture>(id_canvas).unwrap();
+                            pinch.touches.insert(pointer_id, position);
+                            pinch.start = None;
+                        }),
+                        On::<Pointer<Drag>>::run(move |world: &mut World| {
+                            let event = world
+                                .get_resource::<ListenerInput<Pointer<Drag>>>()
+                                .unwrap();
+                            let (pointer_id, position) =
+                                (event.pointer_id, event.pointer_location.position);
+                            let current_zoom = zoom.get(world);
+                            let mut pinch = world.get_mut::<PinchGesture>(id_canvas).unwrap();
+                            if !pinch.touches.contains_key(&pointer_id) {
+                                return;
+                            }
+                            pinch.touches.insert(pointer_id, position);
+                            let Some(distance) = pinch.distance() else {
+                                return;
+                            };
+                            // While pinching, the touches zoom the canvas rather than scroll it.
+                            let (start_distance, start_zoom) =
+                                *pinch.start.get_or_insert((distance, current_zoom));
+                            world
+                                .get_resource_mut::<ListenerInput<Pointer<Drag>>>()
+                                .unwrap()
+                                .stop_propagation();
+                            if start_distance > 0. {
+                                let new_zoom = (start_zoom * distance / start_distance)
+                                    .clamp(MIN_ZOOM, MAX_ZOOM);
+                                if new_zoom != current_zoom {
+                                    zoom.set(world, new_zoom);
+                                }
+                            }
+                        }),
+                    ))
+                    .children(
+                        DotGrid::new()
+                            .style(style_node_graph_scroll)
+                            .children(self.children.clone()),
+                    ),
             )
-            .style((style_node_graph, self.style.clone()))
             .content_style(style_node_graph_content)
+            .scroll_area_id(id_scroll_area)
             .scroll_enable_x(true)
-            .scroll_enable_y(true)
+            .scroll_enable_y(true);
+
+        if !self.minimap {
+            return scroll_view
+                .style((style_node_graph, self.style.clone()))
+                .into_view();
+        }
+
+        Element::<NodeBundle>::new()
+            .named("NodeGraph")
+            .style((style_node_graph_frame, self.style.clone()))
+            .children((
+                scroll_view.style((style_node_graph, style_node_graph_fill)),
+                GraphMinimapView {
+                    scroll_area: id_scroll_area,
+                },
+            ))
+            .into_view()
+    }
+}
+
+/// Component which tracks the touches on a graph canvas, used to zoom it by pinching.
+#[derive(Component, Default)]
+pub(crate) struct PinchGesture {
+    /// Current positions of the touches which are pressed on the canvas.
+    touches: HashMap<PointerId, Vec2>,
+    /// The distance between the two touches, and the zoom, when the pinch began.
+    start: Option<(f32, f32)>,
+}
+
+impl PinchGesture {
+    /// Stop tracking a touch, ending any pinch which it was part of.
+    fn release(&mut self, pointer_id: PointerId) {
+        if self.touches.remove(&pointer_id).is_some() {
+            self.start = None;
+        }
+    }
+
+    /// The distance between the touches, if exactly two are pressed.
+    fn distance(&self) -> Option<f32> {
+        let mut positions = self.touches.values();
+        match (positions.next(), positions.next(), positions.next()) {
+            (Some(a), Some(b), None) => Some(a.distance(*b)),
+            _ => None,
+        }
+    }
+}
+
+/// Removes touches from the pinch gestures when they are lifted or cancelled. This reads the
+/// events globally rather than listening on the canvas, since a touch can be lifted after it
+/// has moved off the canvas.
+pub(crate) fn release_pinch_touches(
+    mut ups: EventReader<Pointer<Up>>,
+    mut cancels: EventReader<Pointer<PointerCancel>>,
+    mut pinches: Query<&mut PinchGesture>,
+) {
+    let released = ups
+        .read()
+        .map(|ev| ev.pointer_id)
+        .chain(cancels.read().map(|ev| ev.pointer_id));
+    for pointer_id in released {
+        for mut pinch in pinches.iter_mut() {
+            pinch.release(pointer_id);
+        }
+    }
+}
+
+/// Marker component for the display nodes of a graph, used to locate them for the minimap.
+#[derive(Component, Default, Clone, Copy)]
+pub(crate) struct GraphNodeMarker;
+
+/// Component on a graph minimap which holds the rectangles it displays. Rectangles are
+/// normalized to the range 0..1, relative to the size of the scrolling content.
+#[derive(Component, Clone, PartialEq)]
+pub(crate) struct GraphMinimap {
+    scroll_area: Entity,
+    viewport: Rect,
+    nodes: Vec<Rect>,
+}
+
+/// Set the position and size of an absolutely-positioned node from a normalized rect.
+fn set_minimap_rect(style: &mut Style, rect: Rect) {
+    style.left = ui::Val::Percent(rect.min.x * 100.);
+    style.top = ui::Val::Percent(rect.min.y * 100.);
+    style.width = ui::Val::Percent(rect.width() * 100.);
+    style.height = ui::Val::Percent(rect.height() * 100.);
+}
+
+/// Scroll the graph so that the point under `position` on the minimap is centered.
+fn pan_to_minimap_point(scroll_area: &mut ScrollArea, position: Vec2, minimap: Rect) {
+    if minimap.width() <= 0. || minimap.height() <= 0. {
+        return;
+    }
+    let center = (position - minimap.min) / minimap.size() * scroll_area.content_size;
+    let origin = center - scroll_area.visible_size * 0.5;
+    scroll_area.scroll_to(origin.x, origin.y);
+}
+
+/// Small overview of a node graph, displayed in the corner of a [`GraphDisplay`].
+struct GraphMinimapView {
+    scroll_area: Entity,
+}
+
+impl ViewTemplate for GraphMinimapView {
+    fn create(&self, cx: &mut Cx) -> impl IntoView {
+        let id = cx.create_entity();
+        let id_scroll_area = self.scroll_area;
+        Element::<NodeBundle>::for_entity(id)
+            .named("NodeGraph::Minimap")
+            .style(style_minimap)
+            .insert((
+                GraphMinimap {
+                    scroll_area: id_scroll_area,
+                    viewport: Rect::default(),
+                    nodes: Vec::new(),
+                },
+                CapturePointer,
+                On::<Pointer<Down>>::run(
+                    move |mut ev: ListenerMut<Pointer<Down>>,
+                          query: Query<(&Node, &GlobalTransform)>,
+                          mut scroll_areas: Query<&mut ScrollArea>| {
+                        ev.stop_propagation();
+                        if let (Ok((node, transform)), Ok(mut scroll_area)) =
+                            (query.get(id), scroll_areas.get_mut(id_scroll_area))
+                        {
+                            let rect = node.logical_rect(transform);
+                            pan_to_minimap_point(
+                                &mut scroll_area,
+                                ev.pointer_location.position,
+                                rect,
+                            );
+                        }
+                    },
+                ),
+                On::<Pointer<Drag>>::run(
+                    move |mut ev: ListenerMut<Pointer<Drag>>,
+                          query: Query<(&Node, &GlobalTransform)>,
+                          mut scroll_areas: Query<&mut ScrollArea>| {
+                        ev.stop_propagation();
+                        if let (Ok((node, transform)), Ok(mut scroll_area)) =
+                            (query.get(id), scroll_areas.get_mut(id_scroll_area))
+                        {
+                            let rect = node.logical_rect(transform);
+                            pan_to_minimap_point(
+                                &mut scroll_area,
+                                ev.pointer_location.position,
+                                rect,
+                            );
+                        }
+                    },
+                ),
+            ))
+            .children((
+                For::index(
+                    move |cx| {
+                        let count = cx
+                            .use_component::<GraphMinimap>(id)
+                            .map_or(0, |minimap| minimap.nodes.len());
+                        0..count
+                    },
+                    move |index, _| {
+                        let index = *index;
+                        Element::<NodeBundle>::new()
+                            .named("NodeGraph::Minimap::Node")
+                            .style(style_minimap_node)
+                            .create_effect(move |cx, ent| {
+                                let rect = cx
+                                    .use_component::<GraphMinimap>(id)
+                                    .and_then(|minimap| minimap.nodes.get(index).copied());
+                                if let Some(rect) = rect {
+                                    let mut style = cx.world_mut().get_mut::<Style>(ent).unwrap();
+                                    set_minimap_rect(&mut style, rect);
+                                }
+                            })
+                    },
+                ),
+                Element::<NodeBundle>::new()
+                    .named("NodeGraph::Minimap::Viewport")
+                    .style(style_minimap_viewport)
+                    .create_effect(move |cx, ent| {
+                        let rect = cx
+                            .use_component::<GraphMinimap>(id)
+                            .map(|minimap| minimap.viewport);
+                        if let Some(rect) = rect {
+                            let mut style = cx.world_mut().get_mut::<Style>(ent).unwrap();
+                            set_minimap_rect(&mut style, rect);
+                        }
+                    }),
+            ))
+    }
+}
+
+/// Updates the rectangles displayed by each graph minimap from the positions of the graph's
+/// nodes and the scroll position of its view.
+pub(crate) fn update_graph_minimaps(
+    mut minimaps: Query<&mut GraphMinimap>,
+    scroll_areas: Query<&ScrollArea>,
+    children: Query<&Children>,
+    nodes: Query<(&Node, &Style), With<GraphNodeMarker>>,
+) {
+    for mut minimap in minimaps.iter_mut() {
+        let Ok(scroll_area) = scroll_areas.get(minimap.scroll_area) else {
+            continue;
+        };
+        let content_size = scroll_area.content_size.max(Vec2::ONE);
+        let normalize = |min: Vec2, size: Vec2| {
+            Rect::from_corners(min / content_size, (min + size) / content_size)
+        };
+        let viewport = normalize(
+            Vec2::new(scroll_area.scroll_left, scroll_area.scroll_top),
+            scroll_area.visible_size,
+        );
+        let rects = children
+            .iter_descendants(minimap.scroll_area)
+            .filter_map(|entity| nodes.get(entity).ok())
+            .map(|(node, style)| {
+                let position = match (style.left, style.top) {
+                    (ui::Val::Px(left), ui::Val::Px(top)) => Vec2::new(left, top),
+                    _ => Vec2::ZERO,
+                };
+                normalize(position, node.size())
+            })
+            .collect::<Vec<_>>();
+        if minimap.viewport != viewport || minimap.nodes != rects {
+            minimap.viewport = viewport;
+            minimap.nodes = rects;
+        }
     }
 }
 
@@ -176,6 +507,17 @@ pub struct NodeDisplay {
 
     /// Callback called when the title bar is dragged.
     pub on_drag: Option<Callback<Vec2>>,
+
+    /// Callback called when a drag of the title bar ends.
+    pub on_drag_end: Option<Callback>,
+
+    /// Shared selection model, and the key identifying this node within it. When present,
+    /// pressing on the title bar updates the selection, and the `selected` signal is ignored.
+    pub selection: Option<(Selection<Entity>, Entity)>,
+
+    /// Duration, in seconds, of the animation when the position of the node changes. Zero
+    /// disables the animation.
+    pub animation_duration: f32,
 }
 
 impl NodeDisplay {
@@ -213,17 +555,51 @@ impl NodeDisplay {
         self.on_drag = Some(on_drag);
         self
     }
+
+    /// Set the callback called when a drag of the title bar ends.
+    pub fn on_drag_end(mut self, on_drag_end: Callback) -> Self {
+        self.on_drag_end = Some(on_drag_end);
+        self
+    }
+
+    /// Set the selection model used to select this node, and the key identifying the node.
+    pub fn selection(mut self, selection: Selection<Entity>, key: Entity) -> Self {
+        self.selection = Some((selection, key));
+        self
+    }
+
+    /// Animate the node to its new position over `duration` seconds whenever the position
+    /// changes, such as after an automatic layout. Dragging the node is never animated.
+    pub fn animate(mut self, duration: f32) -> Self {
+        self.animation_duration = duration;
+        self
+    }
 }
 
 impl ViewTemplate for NodeDisplay {
     fn create(&self, cx: &mut Cx) -> impl IntoView {
-        let position = self.position;
         let id = cx.create_entity();
         let hovering = cx.create_hover_signal(id);
         let drag_state = cx.create_mutable::<DragState>(DragState::default());
+        let animation_duration = self.animation_duration;
+        let position = if animation_duration > 0. {
+            let duration = cx.create_derived(move |cx| match drag_state.get(cx).dragging {
+                true => 0.,
+                false => animation_duration,
+            });
+            cx.create_tweened_signal(self.position, duration)
+        } else {
+            self.position
+        };
+        let selection = self.selection;
+        let selected = match selection {
+            Some((selection, key)) => cx.create_derived(move |cx| selection.is_selected(cx, &key)),
+            None => self.selected.clone(),
+        };
 
         Element::<NodeBundle>::for_entity(id)
             .named("NodeGraph::Node")
+            .insert(GraphNodeMarker)
             .style(style_node_graph_node)
             .create_effect(move |cx, ent| {
                 // Update node position.
@@ -240,6 +616,12 @@ impl ViewTemplate for NodeDisplay {
                     .named("NodeGraph::Node::Title")
                     .style(style_node_graph_node_title)
                     .insert((
+                        CapturePointer,
+                        On::<Pointer<Down>>::run(move |world: &mut World| {
+                            if let Some((selection, key)) = selection {
+                                selection.click(world, key, &[]);
+                            }
+                        }),
                         On::<Pointer<DragStart>>::run(move |world: &mut World| {
                             // Save initial value to use as drag offset.
                             drag_state.set(
@@ -250,21 +632,28 @@ impl ViewTemplate for NodeDisplay {
                                 },
                             );
                         }),
-                        On::<Pointer<DragEnd>>::run(move |world: &mut World| {
-                            drag_state.set(
-                                world,
-                                DragState {
-                                    dragging: false,
-                                    offset: position.get(world),
-                                },
-                            );
+                        On::<Pointer<DragEnd>>::run({
+                            let on_drag_end = self.on_drag_end;
+                            move |world: &mut World| {
+                                drag_state.set(
+                                    world,
+                                    DragState {
+                                        dragging: false,
+                                        offset: position.get(world),
+                                    },
+                                );
+                                if let Some(on_drag_end) = on_drag_end {
+                                    world.run_callback(on_drag_end, ());
+                                }
+                            }
                         }),
                         On::<Pointer<Drag>>::run({
                             let on_drag = self.on_drag.unwrap();
                             move |world: &mut World| {
-                                let event = world
-                                    .get_resource::<ListenerInput<Pointer<Drag>>>()
+                                let mut event = world
+                                    .get_resource_mut::<ListenerInput<Pointer<Drag>>>()
                                     .unwrap();
+                                event.stop_propagation();
                                 let ev = event.distance;
                                 let ds = drag_state.get(world);
                                 if ds.dragging {
@@ -278,7 +667,7 @@ impl ViewTemplate for NodeDisplay {
                     .style(style_node_graph_node_content)
                     .children(self.children.clone()),
                 Cond::new(
-                    move |cx| hovering.get(cx),
+                    move |cx| hovering.get(cx) || selected.get(cx),
                     || {
                         Element::<NodeBundle>::new()
                             .named("NodeGraph::Node::Outline")
@@ -383,6 +772,11 @@ impl ViewTemplate for OutputTerminalDisplay {
     }
 }
 
+/// Component which holds the path of an [`EdgeDisplay`], in the coordinates of the graph
+/// content. Use [`DrawablePath::hit_test`] to find the edge under the pointer.
+#[derive(Component, Clone, Debug)]
+pub struct EdgePath(pub DrawablePath);
+
 /// Displays a stroked path between two nodes.
 pub struct EdgeDisplay {
     /// Pixel position of the source terminal.
@@ -441,6 +835,41 @@ impl ViewTemplate for EdgeDisplay {
                     .unwrap();
                 let material = materials.get_mut(material_id).unwrap();
                 material.update(&path);
+                cx.world_mut().entity_mut(ent).insert(EdgePath(path));
             })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pinch_gesture() {
+        let mut pinch = PinchGesture::default();
+        pinch.touches.insert(PointerId::Touch(0), Vec2::new(0., 0.));
+        assert_eq!(pinch.distance(), None);
+
+        pinch
+            .touches
+            .insert(PointerId::Touch(1), Vec2::new(30., 40.));
+        assert_eq!(pinch.distance(), Some(50.));
+        pinch.start = Some((50., 1.));
+
+        // A third finger is not a pinch.
+        pinch
+            .touches
+            .insert(PointerId::Touch(2), Vec2::new(10., 10.));
+        assert_eq!(pinch.distance(), None);
+
+        // Releasing a touch ends the pinch, so that the next one measures from scratch.
+        pinch.release(PointerId::Touch(2));
+        assert_eq!(pinch.start, None);
+        assert_eq!(pinch.distance(), Some(50.));
+
+        // Releasing a pointer which isn't on the canvas leaves the pinch alone.
+        pinch.start = Some((50., 1.));
+        pinch.release(PointerId::Mouse);
+        assert_eq!(pinch.start, Some((50., 1.)));
+    }
+}
@@ -0,0 +1,418 @@
+use std::sync::Arc;
+
+use bevy::{
+    prelude::*,
+    tasks::{block_on, futures_lite::future, Task},
+    ui,
+};
+use bevy_reactor::*;
+
+use crate::{colors, scrolling::ScrollArea, size::Size, typography};
+
+use super::{style_listview, style_listview_inner, Button, ScrollView, Spacer, Spinner};
+
+fn style_status_row(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Row)
+        .align_items(ui::AlignItems::Center)
+        .gap(6)
+        .padding((4, 2))
+        .min_height(24)
+        .color(colors::DIM);
+}
+
+fn style_error_row(ss: &mut StyleBuilder) {
+    ss.color(colors::DESTRUCTIVE_ACC);
+}
+
+/// A page of items returned by a [`ListDataSource`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct ListPage<T> {
+    /// The items in the page.
+    pub items: Vec<T>,
+
+    /// Whether there are more items after this page.
+    pub has_more: bool,
+}
+
+/// The result of requesting a page from a [`ListDataSource`]: either the page itself, or a
+/// task which will produce it. Errors are reported as a message, which is shown in the list
+/// along with a button to retry.
+pub enum ListFetch<T> {
+    /// The page is available immediately.
+    Ready(Result<ListPage<T>, String>),
+
+    /// The page is being fetched asynchronously.
+    Pending(Task<Result<ListPage<T>, String>>),
+}
+
+/// A source of items for a [`PagedListView`], which are fetched a page at a time as the user
+/// scrolls through the list. Implement this to browse collections which are too large to load
+/// all at once, such as an asset database.
+pub trait ListDataSource: Send + Sync + 'static {
+    /// The type of the items in the list.
+    type Item: Clone + PartialEq + Send + Sync + 'static;
+
+    /// Fetch up to `count` items, starting at `offset`. Pages are requested in order, and
+    /// only one page is requested at a time.
+    fn fetch(&self, world: &mut World, offset: usize, count: usize) -> ListFetch<Self::Item>;
+}
+
+/// The loading state of a [`PagedListView`].
+#[derive(Clone, Debug, PartialEq, Default)]
+enum LoadStatus {
+    /// Waiting for the user to scroll near the end of the list.
+    #[default]
+    Idle,
+    /// A page is being fetched.
+    Loading,
+    /// A page was added while the content was the given height. The next page is not
+    /// requested until the layout has measured the new content, since until then the end of
+    /// the list still appears to be in view.
+    Measuring(f32),
+    /// The last request failed with the given message.
+    Failed(String),
+    /// All items have been fetched.
+    Complete,
+}
+
+/// Component which holds the poll function for a page which is being fetched. The function
+/// returns true once the page has been added to the list.
+#[derive(Component)]
+pub(crate) struct PendingListPage(Box<dyn FnMut(&mut World) -> bool + Send + Sync>);
+
+/// System which polls the pages being fetched by paged list views.
+pub(crate) fn poll_list_pages(world: &mut World) {
+    let entities: Vec<Entity> = world
+        .query_filtered::<Entity, With<PendingListPage>>()
+        .iter(world)
+        .collect();
+    for entity in entities {
+        let Some(mut page) = world.entity_mut(entity).take::<PendingListPage>() else {
+            continue;
+        };
+        if !(page.0)(world) {
+            world.entity_mut(entity).insert(page);
+        }
+    }
+}
+
+/// A scrollable list whose items are fetched from a [`ListDataSource`] a page at a time. The
+/// next page is requested when the user scrolls near the end of the list, and a placeholder
+/// row is shown while it loads.
+pub struct PagedListView<S: ListDataSource> {
+    /// The source of the items.
+    pub source: Arc<S>,
+
+    /// Function which creates the view for an item, given the item and its index.
+    pub item: Arc<dyn Fn(&S::Item, usize) -> ViewRef + Send + Sync>,
+
+    /// The number of items to request at a time.
+    pub page_size: usize,
+
+    /// How close to the end of the list, in pixels, the user must scroll before the next
+    /// page is requested.
+    pub threshold: f32,
+
+    /// Additional styles to be applied to the list view.
+    pub style: StyleHandle,
+}
+
+impl<S: ListDataSource> PagedListView<S> {
+    /// Create a new paged list view.
+    ///
+    /// # Arguments
+    /// * `source` - The source of the items.
+    /// * `item` - Function which creates the view for an item, given the item and its index.
+    pub fn new<V: IntoView>(
+        source: S,
+        item: impl Fn(&S::Item, usize) -> V + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            source: Arc::new(source),
+            item: Arc::new(move |value, index| item(value, index).into_view()),
+            page_size: 50,
+            threshold: 100.,
+            style: StyleHandle::default(),
+        }
+    }
+
+    /// Set the number of items to request at a time.
+    pub fn page_size(mut self, page_size: usize) -> Self {
+        self.page_size = page_size.max(1);
+        self
+    }
+
+    /// Set how close to the end of the list the user must scroll before the next page is
+    /// requested.
+    pub fn threshold(mut self, threshold: f32) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    /// Set additional styles to be applied to the list view.
+    pub fn style<T: StyleTuple + 'static>(mut self, style: T) -> Self {
+        self.style = style.into_handle();
+        self
+    }
+}
+
+impl<S: ListDataSource> ViewTemplate for PagedListView<S> {
+    fn create(&self, cx: &mut Cx) -> impl IntoView {
+        let items = cx.create_mutable::<Vec<S::Item>>(Vec::new());
+        let status = cx.create_mutable(LoadStatus::Idle);
+        let holder = cx.create_owned_entity();
+        let id_scroll_area = cx.create_entity();
+
+        // Request the next page whenever the end of the list is close to being visible,
+        // including when the list doesn't yet fill the view.
+        let source = self.source.clone();
+        let page_size = self.page_size;
+        let threshold = self.threshold;
+        cx.create_effect(move |cx| {
+            let Some(scroll_area) = cx.use_component::<ScrollArea>(id_scroll_area) else {
+                return;
+            };
+            let height = scroll_area.content_size.y;
+            if wants_page(&status.get_clone(cx), scroll_area, threshold) {
+                let world = cx.world_mut();
+                fetch_page(world, &*source, items, status, holder, page_size, height);
+            }
+        });
+
+        // Retrying clears the error, which causes the effect to request the page again.
+        let retry = cx.create_callback(move |cx, _| {
+            status.set_clone(cx, LoadStatus::Idle);
+        });
+
+        let item = self.item.clone();
+        let items = items.signal();
+        let status = status.signal();
+        ScrollView::new()
+            .scroll_area_id(id_scroll_area)
+            .children(
+                Element::<NodeBundle>::new()
+                    .named("PagedListView")
+                    .style(style_listview_inner)
+                    .children((
+                        For::index(
+                            move |cx| items.get_clone(cx).into_iter(),
+                            move |value, index| item(value, index),
+                        ),
+                        Dynamic::new(move |cx| match status.get_clone(cx) {
+                            LoadStatus::Loading => Element::<NodeBundle>::new()
+                                .named("PagedListView::Loading")
+                                .style((typography::text_default, style_status_row))
+                                .children((Spinner::new().size(Size::Xs), "Loading..."))
+                                .into_view(),
+                            LoadStatus::Failed(message) => Element::<NodeBundle>::new()
+                                .named("PagedListView::Error")
+                                .style((
+                                    typography::text_default,
+                                    style_status_row,
+                                    style_error_row,
+                                ))
+                                .children((
+                                    message,
+                                    Spacer,
+                                    Button::new()
+                                        .size(Size::Xs)
+                                        .children("Retry")
+                                        .on_click(retry),
+                                ))
+                                .into_view(),
+                            LoadStatus::Idle | LoadStatus::Measuring(_) | LoadStatus::Complete => {
+                                ().into_view()
+                            }
+                        }),
+                    )),
+            )
+            .style((style_listview, self.style.clone()))
+            .scroll_enable_y(true)
+    }
+}
+
+/// True if the next page should be requested: the end of the list is within `threshold`
+/// pixels of being visible, and the size of the content reflects the pages added so far.
+fn wants_page(status: &LoadStatus, scroll_area: &ScrollArea, threshold: f32) -> bool {
+    let ready = match status {
+        LoadStatus::Idle => true,
+        LoadStatus::Measuring(height) => scroll_area.content_size.y != *height,
+        LoadStatus::Loading | LoadStatus::Failed(_) | LoadStatus::Complete => false,
+    };
+    let remaining =
+        scroll_area.content_size.y - scroll_area.visible_size.y - scroll_area.scroll_top;
+    ready && remaining <= threshold
+}
+
+/// Request the next page from `source`, and add it to the list when it arrives. `height` is
+/// the current height of the list content.
+fn fetch_page<S: ListDataSource>(
+    world: &mut World,
+    source: &S,
+    items: Mutable<Vec<S::Item>>,
+    status: Mutable<LoadStatus>,
+    holder: Entity,
+    page_size: usize,
+    height: f32,
+) {
+    status.set_clone(world, LoadStatus::Loading);
+    let offset = items.as_ref(world).len();
+    match source.fetch(world, offset, page_size) {
+        ListFetch::Ready(result) => add_page(world, result, items, status, height),
+        ListFetch::Pending(mut task) => {
+            world.entity_mut(holder).insert(PendingListPage(Box::new(
+                move |world| match block_on(future::poll_once(&mut task)) {
+                    Some(result) => {
+                        add_page(world, result, items, status, height);
+                        true
+                    }
+                    None => false,
+                },
+            )));
+        }
+    }
+}
+
+fn add_page<T: Clone + PartialEq + Send + Sync + 'static>(
+    world: &mut World,
+    result: Result<ListPage<T>, String>,
+    items: Mutable<Vec<T>>,
+    status: Mutable<LoadStatus>,
+    height: f32,
+) {
+    match result {
+        Ok(page) => {
+            let next = match (page.has_more, page.items.is_empty()) {
+                (false, _) => LoadStatus::Complete,
+                // An empty page doesn't change the layout, so there is nothing to wait for.
+                (true, true) => LoadStatus::Idle,
+                (true, false) => LoadStatus::Measuring(height),
+            };
+            items.update(world, |mut items| items.extend(page.items));
+            status.set_clone(world, next);
+        }
+        Err(message) => status.set_clone(world, LoadStatus::Failed(message)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use bevy_reactor::TrackingScope;
+
+    use super::*;
+
+    /// A source of the numbers from 0 to `len`, which records the pages requested from it.
+    struct Numbers {
+        len: usize,
+        requests: Mutex<Vec<(usize, usize)>>,
+    }
+
+    impl ListDataSource for Numbers {
+        type Item = usize;
+
+        fn fetch(&self, _world: &mut World, offset: usize, count: usize) -> ListFetch<usize> {
+            self.requests.lock().unwrap().push((offset, count));
+            if offset >= self.len {
+                return ListFetch::Ready(Err("Out of range".to_string()));
+            }
+            let end = (offset + count).min(self.len);
+            ListFetch::Ready(Ok(ListPage {
+                items: (offset..end).collect(),
+                has_more: end < self.len,
+            }))
+        }
+    }
+
+    fn scroll_area(content: f32, visible: f32, scroll_top: f32) -> ScrollArea {
+        ScrollArea {
+            scroll_top,
+            content_size: Vec2::new(100., content),
+            visible_size: Vec2::new(100., visible),
+            ..default()
+        }
+    }
+
+    #[test]
+    fn test_wants_page() {
+        assert!(wants_page(
+            &LoadStatus::Idle,
+            &scroll_area(0., 200., 0.),
+            50.
+        ));
+        assert!(wants_page(
+            &LoadStatus::Idle,
+            &scroll_area(1000., 200., 760.),
+            50.
+        ));
+        assert!(!wants_page(
+            &LoadStatus::Idle,
+            &scroll_area(1000., 200., 700.),
+            50.
+        ));
+        assert!(!wants_page(
+            &LoadStatus::Loading,
+            &scroll_area(0., 200., 0.),
+            50.
+        ));
+        assert!(!wants_page(
+            &LoadStatus::Complete,
+            &scroll_area(0., 200., 0.),
+            50.
+        ));
+        assert!(!wants_page(
+            &LoadStatus::Failed("Error".to_string()),
+            &scroll_area(0., 200., 0.),
+            50.
+        ));
+
+        // After a page is added, wait until the layout has measured it.
+        assert!(!wants_page(
+            &LoadStatus::Measuring(0.),
+            &scroll_area(0., 200., 0.),
+            50.
+        ));
+        assert!(wants_page(
+            &LoadStatus::Measuring(0.),
+            &scroll_area(120., 200., 0.),
+            50.
+        ));
+    }
+
+    #[test]
+    fn test_fetch_pages() {
+        let mut world = World::default();
+        let owner = world.spawn_empty().id();
+        let holder = world.spawn_empty().id();
+        let mut scope = TrackingScope::new(world.change_tick());
+        let mut cx = Cx::new(&mut world, owner, &mut scope);
+        let items = cx.create_mutable::<Vec<usize>>(Vec::new());
+        let status = cx.create_mutable(LoadStatus::Idle);
+        let source = Numbers {
+            len: 5,
+            requests: Mutex::new(Vec::new()),
+        };
+
+        fetch_page(&mut world, &source, items, status, holder, 3, 0.);
+        world.flush_commands();
+        assert_eq!(items.get_clone(&world), [0, 1, 2]);
+        assert_eq!(status.get_clone(&world), LoadStatus::Measuring(0.));
+
+        fetch_page(&mut world, &source, items, status, holder, 3, 60.);
+        world.flush_commands();
+        assert_eq!(items.get_clone(&world), [0, 1, 2, 3, 4]);
+        assert_eq!(status.get_clone(&world), LoadStatus::Complete);
+        assert_eq!(*source.requests.lock().unwrap(), [(0, 3), (3, 3)]);
+
+        // Errors are reported in the status, and the items are kept.
+        fetch_page(&mut world, &source, items, status, holder, 3, 100.);
+        world.flush_commands();
+        assert_eq!(items.get_clone(&world).len(), 5);
+        assert_eq!(
+            status.get_clone(&world),
+            LoadStatus::Failed("Out of range".to_string())
+        );
+    }
+}
@@ -0,0 +1,264 @@
+use bevy::{color::LinearRgba, prelude::*, ui};
+use bevy_reactor::*;
+
+use crate::{
+    colors,
+    hooks::{CreateThemeSignal, ThemeToken},
+    materials::ProgressBarMaterial,
+    typography, RoundedCorners,
+};
+
+/// The variant determines the color of the progress bar's fill.
+#[derive(Clone, Copy, PartialEq, Default, Debug)]
+pub enum ProgressBarVariant {
+    /// The default appearance.
+    #[default]
+    Default,
+
+    /// A more muted appearance, matching primary buttons.
+    Primary,
+
+    /// An appearance indicating successful completion.
+    Success,
+
+    /// An appearance indicating an error or a dangerous condition.
+    Danger,
+}
+
+impl ProgressBarVariant {
+    /// The fill color of the variant, where `accent` is the theme's accent color.
+    fn color(&self, accent: Srgba) -> Srgba {
+        match self {
+            ProgressBarVariant::Default => accent,
+            ProgressBarVariant::Primary => colors::PRIMARY_ACC,
+            ProgressBarVariant::Success => colors::RESOURCE,
+            ProgressBarVariant::Danger => colors::DESTRUCTIVE_ACC,
+        }
+    }
+}
+
+fn style_progress(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Column)
+        .align_items(ui::AlignItems::Stretch)
+        .min_width(64)
+        .gap(2);
+}
+
+fn style_progress_header(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Row)
+        .align_items(ui::AlignItems::Center)
+        .color(colors::DIM)
+        .font_size(14);
+}
+
+fn style_progress_label(ss: &mut StyleBuilder) {
+    ss.flex_grow(1.);
+}
+
+fn style_progress_track(ss: &mut StyleBuilder) {
+    ss.height(6);
+}
+
+/// Determinate progress bar widget. The fill animates smoothly when the value changes.
+///
+/// Styleable parts:
+/// * `"header"` - the row containing the label and percentage text.
+/// * `"track"` - the element which renders the bar.
+pub struct ProgressBar {
+    /// Current value.
+    pub value: Signal<f32>,
+
+    /// Minimum value.
+    pub min: Signal<f32>,
+
+    /// Maximum value.
+    pub max: Signal<f32>,
+
+    /// Color variant of the fill.
+    pub variant: Signal<ProgressBarVariant>,
+
+    /// Optional label to be displayed above the bar.
+    pub label: Option<Signal<String>>,
+
+    /// Whether to display the percentage complete above the bar.
+    pub show_percent: bool,
+
+    /// Style handle for the progress bar root element.
+    pub style: StyleHandle,
+
+    /// Style overrides for the internal parts of the progress bar.
+    pub part_styles: PartStyles,
+}
+
+impl ProgressBar {
+    /// Create a new progress bar.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the current value.
+    pub fn value(mut self, value: impl IntoSignal<f32>) -> Self {
+        self.value = value.into_signal();
+        self
+    }
+
+    /// Set the minimum value.
+    pub fn min(mut self, min: impl IntoSignal<f32>) -> Self {
+        self.min = min.into_signal();
+        self
+    }
+
+    /// Set the maximum value.
+    pub fn max(mut self, max: impl IntoSignal<f32>) -> Self {
+        self.max = max.into_signal();
+        self
+    }
+
+    /// Set the color variant of the fill.
+    pub fn variant(mut self, variant: impl IntoSignal<ProgressBarVariant>) -> Self {
+        self.variant = variant.into_signal();
+        self
+    }
+
+    /// Set the optional label to be displayed above the bar.
+    pub fn label(mut self, label: impl IntoSignal<String>) -> Self {
+        self.label = Some(label.into_signal());
+        self
+    }
+
+    /// Set whether to display the percentage complete above the bar.
+    pub fn show_percent(mut self, show_percent: bool) -> Self {
+        self.show_percent = show_percent;
+        self
+    }
+
+    /// Set the style handle for the progress bar root element.
+    pub fn style<S: StyleTuple + 'static>(mut self, style: S) -> Self {
+        self.style = style.into_handle();
+        self
+    }
+
+    /// Set additional styles for one of the progress bar's internal parts.
+    pub fn part_style<S: StyleTuple + 'static>(mut self, part: &'static str, style: S) -> Self {
+        self.part_styles.insert(part, style);
+        self
+    }
+}
+
+impl Default for ProgressBar {
+    fn default() -> Self {
+        Self {
+            value: Signal::Constant(0.),
+            min: Signal::Constant(0.),
+            max: Signal::Constant(1.),
+            variant: Signal::default(),
+            label: None,
+            show_percent: false,
+            style: StyleHandle::default(),
+            part_styles: PartStyles::default(),
+        }
+    }
+}
+
+impl ViewTemplate for ProgressBar {
+    fn create(&self, cx: &mut Cx) -> impl IntoView {
+        let min = self.min;
+        let max = self.max;
+        let value = self.value;
+        let variant = self.variant;
+        let label = self.label.clone();
+        let show_percent = self.show_percent;
+        let accent = cx.create_theme_color(ThemeToken::Accent);
+        let fill = cx.create_derived(move |cx| variant.get(cx).color(accent.get(cx)));
+
+        let fraction = cx.create_memo(move |cx| {
+            let min = min.get(cx);
+            let max = max.get(cx);
+            if max > min {
+                ((value.get(cx) - min) / (max - min)).clamp(0., 1.)
+            } else {
+                0.
+            }
+        });
+
+        // Start at the initial value, so that the bar doesn't animate when first displayed.
+        let initial = fraction.get(cx);
+        let mut ui_materials = cx
+            .world_mut()
+            .get_resource_mut::<Assets<ProgressBarMaterial>>()
+            .unwrap();
+        let material = ui_materials.add(ProgressBarMaterial {
+            color_track: LinearRgba::from(colors::U1).to_vec4(),
+            color_fill: LinearRgba::from(fill.get(cx)).to_vec4(),
+            value: initial,
+            start_value: initial,
+            start_time: 0.,
+            radius: RoundedCorners::All.to_vec(3.),
+        });
+
+        Element::<NodeBundle>::new()
+            .named("ProgressBar")
+            .style((typography::text_default, style_progress, self.style.clone()))
+            .children((
+                Cond::new(
+                    {
+                        let has_label = label.is_some();
+                        move |_cx| has_label || show_percent
+                    },
+                    {
+                        let header_style = self.part_styles.get("header");
+                        move || {
+                            let label = label.clone();
+                            Element::<NodeBundle>::new()
+                                .style((style_progress_header, header_style.clone()))
+                                .children((
+                                    Element::<NodeBundle>::new()
+                                        .style(style_progress_label)
+                                        .children(text_computed(move |cx| {
+                                            label
+                                                .as_ref()
+                                                .map(|label| label.get_clone(cx))
+                                                .unwrap_or_default()
+                                        })),
+                                    Cond::new(
+                                        move |_cx| show_percent,
+                                        move || {
+                                            text_computed(move |cx| {
+                                                format!("{:.0}%", fraction.get(cx) * 100.)
+                                            })
+                                        },
+                                        || (),
+                                    ),
+                                ))
+                        }
+                    },
+                    || (),
+                ),
+                Element::<MaterialNodeBundle<ProgressBarMaterial>>::new()
+                    .named("ProgressBar::Track")
+                    .style((style_progress_track, self.part_styles.get("track")))
+                    .insert(material.clone())
+                    .material_param(
+                        |material: &mut ProgressBarMaterial, fill: Srgba| {
+                            material.color_fill = LinearRgba::from(fill).to_vec4();
+                        },
+                        fill,
+                    )
+                    .create_effect(move |cx, _ent| {
+                        let fraction = fraction.get(cx);
+                        // Start a transition from the currently displayed value.
+                        let time = cx.world().resource::<Time>().elapsed_seconds_wrapped();
+                        let mut ui_materials = cx
+                            .world_mut()
+                            .get_resource_mut::<Assets<ProgressBarMaterial>>()
+                            .unwrap();
+                        let material = ui_materials.get_mut(material.id()).unwrap();
+                        material.start_value = material.displayed_value(time);
+                        material.start_time = time;
+                        material.value = fraction;
+                    }),
+            ))
+    }
+}
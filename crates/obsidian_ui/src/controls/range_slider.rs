@@ -0,0 +1,451 @@
+use bevy::{
+    a11y::{
+        accesskit::{NodeBuilder, Role},
+        AccessibilityNode, Focus,
+    },
+    color::{LinearRgba, Luminance},
+    prelude::*,
+    ui,
+};
+use bevy_mod_picking::prelude::*;
+use bevy_reactor::*;
+
+use crate::{
+    colors,
+    focus::{KeyPressEvent, TabIndex},
+    hooks::{CreateDisabledSignal, CreateFocusSignal, CreateThemeSignal, ThemeToken},
+    materials::SliderRectMaterial,
+    pointer_capture::CapturePointer,
+    RoundedCorners,
+};
+
+const THUMB_WIDTH: f32 = 8.;
+
+fn style_range_slider(ss: &mut StyleBuilder) {
+    ss.min_width(64).height(20);
+}
+
+fn style_range_label(ss: &mut StyleBuilder) {
+    ss.position(ui::PositionType::Absolute)
+        .left(0)
+        .top(0)
+        .right(0)
+        .bottom(0)
+        .display(ui::Display::Flex)
+        .align_items(ui::AlignItems::Center)
+        .justify_content(ui::JustifyContent::Center)
+        .font("obsidian_ui://fonts/Open_Sans/static/OpenSans-Medium.ttf")
+        .font_size(16)
+        .pointer_events(false);
+}
+
+fn style_range_thumb(ss: &mut StyleBuilder) {
+    ss.position(ui::PositionType::Absolute)
+        .top(2)
+        .bottom(2)
+        .width(THUMB_WIDTH)
+        .margin_left(-THUMB_WIDTH * 0.5)
+        .border_radius(2.);
+}
+
+/// Identifies one of the two thumbs of a [`RangeSlider`].
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Thumb {
+    Lo,
+    Hi,
+}
+
+/// Limits which apply to both thumbs of a range slider.
+#[derive(Clone, Copy)]
+struct RangeLimits {
+    min: Signal<f32>,
+    max: Signal<f32>,
+    min_gap: f32,
+    precision: usize,
+}
+
+impl RangeLimits {
+    /// Move one thumb to `value`, keeping the range within bounds and at least `min_gap` wide.
+    fn constrain(&self, world: &World, thumb: Thumb, value: f32, range: (f32, f32)) -> (f32, f32) {
+        let min = self.min.get(world);
+        let max = self.max.get(world);
+        let rounding = f32::powi(10., self.precision as i32);
+        let value = (value * rounding).round() / rounding;
+        match thumb {
+            Thumb::Lo => (value.min(range.1 - self.min_gap).max(min), range.1),
+            Thumb::Hi => (range.0, value.max(range.0 + self.min_gap).min(max)),
+        }
+    }
+}
+
+/// Choose which thumb a drag towards `value` should move. When the thumbs are stacked, the
+/// one on top may be unable to move in the drag direction (for example, both thumbs at the
+/// maximum), so the drag is handed to the other thumb instead.
+fn drag_thumb(thumb: Thumb, value: f32, range: (f32, f32), min_gap: f32) -> Thumb {
+    let stacked = range.1 - range.0 <= min_gap;
+    match thumb {
+        Thumb::Hi if stacked && value < range.1 => Thumb::Lo,
+        Thumb::Lo if stacked && value > range.0 => Thumb::Hi,
+        _ => thumb,
+    }
+}
+
+/// Horizontal slider with two thumbs, for selecting a range of values. The selected range is
+/// highlighted.
+pub struct RangeSlider {
+    /// Current range, as `(low, high)`.
+    pub value: Signal<(f32, f32)>,
+
+    /// Minimum slider value.
+    pub min: Signal<f32>,
+
+    /// Maximum slider value.
+    pub max: Signal<f32>,
+
+    /// Minimum distance between the two thumbs.
+    pub min_gap: f32,
+
+    /// Number of decimal places to round to (0 = integer).
+    pub precision: usize,
+
+    /// Amount to move a thumb when using the arrow keys.
+    pub step: f32,
+
+    /// Whether the slider is disabled.
+    pub disabled: Signal<bool>,
+
+    /// Style handle for slider root element.
+    pub style: StyleHandle,
+
+    /// Callback called when the range changes.
+    pub on_change: Option<Callback<(f32, f32)>>,
+}
+
+impl RangeSlider {
+    /// Create a new range slider.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the current range.
+    pub fn value(mut self, value: impl IntoSignal<(f32, f32)>) -> Self {
+        self.value = value.into_signal();
+        self
+    }
+
+    /// Set the minimum slider value.
+    pub fn min(mut self, min: impl IntoSignal<f32>) -> Self {
+        self.min = min.into_signal();
+        self
+    }
+
+    /// Set the maximum slider value.
+    pub fn max(mut self, max: impl IntoSignal<f32>) -> Self {
+        self.max = max.into_signal();
+        self
+    }
+
+    /// Set the minimum distance between the two thumbs.
+    pub fn min_gap(mut self, min_gap: f32) -> Self {
+        self.min_gap = min_gap;
+        self
+    }
+
+    /// Set the number of decimal places to round to (0 = integer).
+    pub fn precision(mut self, precision: usize) -> Self {
+        self.precision = precision;
+        self
+    }
+
+    /// Set the amount to move a thumb when using the arrow keys.
+    pub fn step(mut self, step: f32) -> Self {
+        self.step = step;
+        self
+    }
+
+    /// Set whether the slider is disabled.
+    pub fn disabled(mut self, disabled: impl IntoSignal<bool>) -> Self {
+        self.disabled = disabled.into_signal();
+        self
+    }
+
+    /// Set the style handle for the slider root element.
+    pub fn style<S: StyleTuple + 'static>(mut self, style: S) -> Self {
+        self.style = style.into_handle();
+        self
+    }
+
+    /// Set the callback called when the range changes.
+    pub fn on_change(mut self, on_change: Callback<(f32, f32)>) -> Self {
+        self.on_change = Some(on_change);
+        self
+    }
+}
+
+impl Default for RangeSlider {
+    fn default() -> Self {
+        Self {
+            value: Signal::Constant((0., 1.)),
+            min: Signal::Constant(0.),
+            max: Signal::Constant(1.),
+            min_gap: 0.,
+            precision: 0,
+            step: 1.,
+            disabled: Signal::Constant(false),
+            style: StyleHandle::default(),
+            on_change: None,
+        }
+    }
+}
+
+impl ViewTemplate for RangeSlider {
+    fn create(&self, cx: &mut Cx) -> impl IntoView {
+        let slider_id = cx.create_entity();
+        let min = self.min;
+        let max = self.max;
+        let value = self.value;
+        let precision = self.precision;
+        let limits = RangeLimits {
+            min,
+            max,
+            min_gap: self.min_gap,
+            precision,
+        };
+
+        let mut ui_materials = cx
+            .world_mut()
+            .get_resource_mut::<Assets<SliderRectMaterial>>()
+            .unwrap();
+        let material = ui_materials.add(SliderRectMaterial {
+            color_lo: LinearRgba::from(colors::U1).to_vec4(),
+            color_hi: LinearRgba::from(colors::U3).to_vec4(),
+            value: 1.,
+            radius: RoundedCorners::All.to_vec(4.),
+            start: 0.,
+            vertical: 0,
+        });
+
+        let disabled = cx.create_disabled_signal(self.disabled);
+        let thumb = |thumb: Thumb| RangeThumb {
+            thumb,
+            slider_id,
+            value,
+            limits,
+            step: self.step,
+            disabled,
+            on_change: self.on_change,
+        };
+
+        Element::<MaterialNodeBundle<SliderRectMaterial>>::for_entity(slider_id)
+            .named("RangeSlider")
+            .style((style_range_slider, self.style.clone()))
+            .insert(material.clone())
+            .create_effect(move |cx, _ent| {
+                let min = min.get(cx);
+                let max = max.get(cx);
+                let (lo, hi) = value.get(cx);
+                let (start, end) = if max > min {
+                    ((lo - min) / (max - min), (hi - min) / (max - min))
+                } else {
+                    (0., 0.)
+                };
+
+                let mut ui_materials = cx
+                    .world_mut()
+                    .get_resource_mut::<Assets<SliderRectMaterial>>()
+                    .unwrap();
+                let material = ui_materials.get_mut(material.id()).unwrap();
+                material.start = start;
+                material.value = end;
+            })
+            .children((
+                Element::<NodeBundle>::new()
+                    .named("RangeSlider::Label")
+                    .style(style_range_label)
+                    .children(text_computed(move |cx| {
+                        let (lo, hi) = value.get(cx);
+                        format!("{:.*} – {:.*}", precision, lo, precision, hi)
+                    })),
+                thumb(Thumb::Lo),
+                thumb(Thumb::Hi),
+            ))
+    }
+}
+
+struct RangeThumb {
+    thumb: Thumb,
+    slider_id: Entity,
+    value: Signal<(f32, f32)>,
+    limits: RangeLimits,
+    step: f32,
+    disabled: Signal<bool>,
+    on_change: Option<Callback<(f32, f32)>>,
+}
+
+impl ViewTemplate for RangeThumb {
+    fn create(&self, cx: &mut Cx) -> impl IntoView {
+        let id = cx.create_entity();
+        let hovering = cx.create_hover_signal(id);
+        let focused = cx.create_focus_visible_signal(id);
+        let color_focus = cx.create_theme_color(ThemeToken::Focus);
+        // Thumb being moved by the current drag, and its value at the start of the drag.
+        let drag_offset = cx.create_mutable::<Option<(Thumb, f32)>>(None);
+
+        let thumb = self.thumb;
+        let slider_id = self.slider_id;
+        let value = self.value;
+        let limits = self.limits;
+        let step = self.step;
+        let disabled = self.disabled;
+        let on_change = self.on_change;
+
+        let thumb_value = move |range: (f32, f32)| match thumb {
+            Thumb::Lo => range.0,
+            Thumb::Hi => range.1,
+        };
+
+        Element::<NodeBundle>::for_entity(id)
+            .named(match thumb {
+                Thumb::Lo => "RangeSlider::ThumbLo",
+                Thumb::Hi => "RangeSlider::ThumbHi",
+            })
+            .style(style_range_thumb)
+            .insert((
+                CapturePointer,
+                TabIndex(0),
+                AccessibilityNode::from(NodeBuilder::new(Role::Slider)),
+                On::<Pointer<DragStart>>::run(move |world: &mut World| {
+                    let mut event = world
+                        .get_resource_mut::<ListenerInput<Pointer<DragStart>>>()
+                        .unwrap();
+                    event.stop_propagation();
+                    let mut focus = world.get_resource_mut::<Focus>().unwrap();
+                    focus.0 = Some(id);
+                    if !disabled.get(world) {
+                        let offset = thumb_value(value.get(world));
+                        drag_offset.set(world, Some((thumb, offset)));
+                    }
+                }),
+                On::<Pointer<DragEnd>>::run(move |world: &mut World| {
+                    drag_offset.set(world, None);
+                }),
+                On::<Pointer<Drag>>::run(move |world: &mut World| {
+                    let Some((dragged, offset)) = drag_offset.get(world) else {
+                        return;
+                    };
+                    let event = world
+                        .get_resource::<ListenerInput<Pointer<Drag>>>()
+                        .unwrap();
+                    let distance = event.distance.x;
+                    let ent = world.entity(slider_id);
+                    let node = ent.get::<Node>();
+                    let transform = ent.get::<GlobalTransform>();
+                    if let (Some(node), Some(transform)) = (node, transform) {
+                        let slider_width = node.logical_rect(transform).width();
+                        let range = limits.max.get(world) - limits.min.get(world);
+                        if range > 0. && slider_width > 0. {
+                            let new_value = offset + (distance * range) / slider_width;
+                            let current = value.get(world);
+                            let next = drag_thumb(dragged, new_value, current, limits.min_gap);
+                            if next != dragged {
+                                drag_offset.set(world, Some((next, offset)));
+                            }
+                            let new_range = limits.constrain(world, next, new_value, current);
+                            if let Some(on_change) = on_change {
+                                world.run_callback(on_change, new_range);
+                            }
+                        }
+                    }
+                }),
+                On::<KeyPressEvent>::run(move |world: &mut World| {
+                    if disabled.get(world) {
+                        return;
+                    }
+                    let range = value.get(world);
+                    let current = thumb_value(range);
+                    let key_code = world
+                        .get_resource::<ListenerInput<KeyPressEvent>>()
+                        .unwrap()
+                        .key_code;
+                    let new_value = match key_code {
+                        KeyCode::ArrowLeft | KeyCode::ArrowDown => current - step,
+                        KeyCode::ArrowRight | KeyCode::ArrowUp => current + step,
+                        KeyCode::Home => limits.min.get(world),
+                        KeyCode::End => limits.max.get(world),
+                        _ => return,
+                    };
+                    let mut event = world
+                        .get_resource_mut::<ListenerInput<KeyPressEvent>>()
+                        .unwrap();
+                    event.stop_propagation();
+                    let new_range = limits.constrain(world, thumb, new_value, range);
+                    if new_range != range {
+                        if let Some(on_change) = on_change {
+                            world.run_callback(on_change, new_range);
+                        }
+                    }
+                }),
+            ))
+            .create_effect(move |cx, ent| {
+                let min = limits.min.get(cx);
+                let max = limits.max.get(cx);
+                let current = thumb_value(value.get(cx));
+                let percent = if max > min {
+                    ((current - min) / (max - min)).clamp(0., 1.)
+                } else {
+                    0.
+                };
+                let mut style = cx.world_mut().get_mut::<Style>(ent).unwrap();
+                style.left = ui::Val::Percent(percent * 100.);
+            })
+            .create_effect(move |cx, ent| {
+                let color = match (drag_offset.get(cx).is_some(), hovering.get(cx)) {
+                    (true, _) => colors::U4.lighter(0.1),
+                    (false, true) => colors::U4.lighter(0.05),
+                    (false, false) => colors::U4,
+                };
+                let mut bg = cx.world_mut().get_mut::<BackgroundColor>(ent).unwrap();
+                bg.0 = color.into();
+            })
+            .create_effect(move |cx, ent| {
+                let is_focused = focused.get(cx);
+                let focus = color_focus.get(cx);
+                let mut entt = cx.world_mut().entity_mut(ent);
+                match is_focused {
+                    true => {
+                        entt.insert(Outline {
+                            color: focus.into(),
+                            offset: ui::Val::Px(2.0),
+                            width: ui::Val::Px(2.0),
+                        });
+                    }
+                    false => {
+                        entt.remove::<Outline>();
+                    }
+                };
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_drag_thumb() {
+        // Separated thumbs always move themselves.
+        assert_eq!(drag_thumb(Thumb::Hi, 2., (1., 5.), 0.), Thumb::Hi);
+        assert_eq!(drag_thumb(Thumb::Lo, 3., (1., 5.), 0.), Thumb::Lo);
+
+        // Both thumbs at the maximum: dragging the top thumb down moves the low thumb.
+        assert_eq!(drag_thumb(Thumb::Hi, 8., (10., 10.), 0.), Thumb::Lo);
+        assert_eq!(drag_thumb(Thumb::Hi, 12., (10., 10.), 0.), Thumb::Hi);
+
+        // Both thumbs at the minimum: dragging the low thumb up moves the high thumb.
+        assert_eq!(drag_thumb(Thumb::Lo, 1., (0., 0.), 0.), Thumb::Hi);
+        assert_eq!(drag_thumb(Thumb::Lo, -1., (0., 0.), 0.), Thumb::Lo);
+
+        // Thumbs held apart by the minimum gap count as stacked.
+        assert_eq!(drag_thumb(Thumb::Hi, 8., (9., 10.), 1.), Thumb::Lo);
+    }
+}
@@ -0,0 +1,318 @@
+use bevy::{color::LinearRgba, prelude::*, ui, window::PrimaryWindow};
+use bevy_mod_picking::prelude::*;
+use bevy_reactor::*;
+
+use crate::{
+    box_shadow::resolve_corner_radii, colors, materials::RoundedClipMaterial, RoundedCorners,
+};
+
+fn style_rounded_clip(ss: &mut StyleBuilder) {
+    ss.position(ui::PositionType::Absolute)
+        .left(0)
+        .top(0)
+        .right(0)
+        .bottom(0);
+}
+
+/// An overlay which makes the content of its parent appear clipped to rounded corners.
+///
+/// Bevy UI can only clip to rectangles, so this paints the backdrop color - the color of
+/// whatever surrounds the parent - over the parts of the parent which lie outside of the
+/// corner radius. This only works over a solid background; use [`RoundedMask`] to clip
+/// content over images or gradients. It should be the last child of the parent, so that it
+/// is drawn on top of the other children, and it ignores pointer events. To clip an
+/// [`Element`] to its own border radius, use [`WithClipCorners::clip_corners`] instead.
+#[derive(Clone)]
+pub struct RoundedClip {
+    /// Which corners are rounded.
+    pub corners: RoundedCorners,
+
+    /// Radius of the rounded corners, in pixels.
+    pub radius: f32,
+
+    /// Color of the area outside of the rounded corners.
+    pub backdrop: Signal<Color>,
+}
+
+impl RoundedClip {
+    /// Construct a new `RoundedClip` which rounds all corners with the given radius.
+    pub fn new(radius: f32) -> Self {
+        Self {
+            radius,
+            ..default()
+        }
+    }
+
+    /// Set which corners are rounded.
+    pub fn corners(mut self, corners: RoundedCorners) -> Self {
+        self.corners = corners;
+        self
+    }
+
+    /// Set the color of the area outside of the rounded corners.
+    pub fn backdrop(mut self, backdrop: impl IntoSignal<Color>) -> Self {
+        self.backdrop = backdrop.into_signal();
+        self
+    }
+}
+
+impl Default for RoundedClip {
+    fn default() -> Self {
+        Self {
+            corners: RoundedCorners::All,
+            radius: 4.,
+            backdrop: Signal::Constant(colors::BACKGROUND.into()),
+        }
+    }
+}
+
+impl ViewTemplate for RoundedClip {
+    fn create(&self, cx: &mut Cx) -> impl IntoView {
+        let backdrop = self.backdrop;
+        let material = cx
+            .world_mut()
+            .get_resource_mut::<Assets<RoundedClipMaterial>>()
+            .unwrap()
+            .add(RoundedClipMaterial {
+                color: Vec4::ZERO,
+                radius: self.corners.to_vec(self.radius),
+            });
+
+        Element::<MaterialNodeBundle<RoundedClipMaterial>>::new()
+            .named("RoundedClip")
+            .style(style_rounded_clip)
+            .insert((material.clone(), Pickable::IGNORE))
+            .create_effect(move |cx, _| {
+                let backdrop = backdrop.get(cx);
+                let mut ui_materials = cx
+                    .world_mut()
+                    .get_resource_mut::<Assets<RoundedClipMaterial>>()
+                    .unwrap();
+                let material = ui_materials.get_mut(material.id()).unwrap();
+                material.color = LinearRgba::from(backdrop).to_vec4();
+            })
+    }
+}
+
+/// Component which makes the content of a node appear clipped to the node's [`BorderRadius`],
+/// by painting `backdrop` over the corners in the same way as [`RoundedClip`]. It is usually
+/// added with [`WithClipCorners::clip_corners`].
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct ClipCorners {
+    /// Color of the area outside of the rounded corners.
+    pub backdrop: Color,
+}
+
+/// Component on a node with [`ClipCorners`], which holds the entity that paints the corners.
+#[derive(Component)]
+pub(crate) struct ClipCornersNode(Entity);
+
+/// Trait to clip the content of an element to its border radius.
+pub trait WithClipCorners {
+    /// Make the content of the element appear clipped to its border radius, as set with the
+    /// `border_radius` style, by painting `backdrop` over the corners. The argument may be a
+    /// signal, in which case the color is updated whenever it changes.
+    fn clip_corners(self, backdrop: impl IntoSignal<Color>) -> Self;
+}
+
+impl<B: Bundle + Default> WithClipCorners for Element<B> {
+    fn clip_corners(self, backdrop: impl IntoSignal<Color>) -> Self {
+        let backdrop = backdrop.into_signal();
+        self.create_effect(move |cx, target| {
+            let clip = ClipCorners {
+                backdrop: backdrop.get(cx),
+            };
+            let mut entt = cx.world_mut().entity_mut(target);
+            if entt.get::<ClipCorners>() != Some(&clip) {
+                entt.insert(clip);
+            }
+        })
+    }
+}
+
+/// Creates and updates the nodes which paint the corners of nodes with [`ClipCorners`]. The
+/// corner node is an [`Overlay`], so elements keep it after their other children, where it is
+/// drawn on top of them.
+#[allow(clippy::type_complexity)]
+pub(crate) fn update_clip_corners(
+    mut commands: Commands,
+    mut materials: ResMut<Assets<RoundedClipMaterial>>,
+    targets: Query<
+        (
+            Entity,
+            &ClipCorners,
+            Option<&Node>,
+            Option<&BorderRadius>,
+            Option<&Children>,
+            Option<&ClipCornersNode>,
+        ),
+        Or<(
+            Changed<ClipCorners>,
+            Changed<Node>,
+            Changed<BorderRadius>,
+            Changed<Children>,
+        )>,
+    >,
+    overlays: Query<&Handle<RoundedClipMaterial>>,
+    clip_nodes: Query<&ClipCornersNode>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut removed: RemovedComponents<ClipCorners>,
+) {
+    for entity in removed.read() {
+        if let Ok(ClipCornersNode(overlay)) = clip_nodes.get(entity) {
+            commands.entity(*overlay).despawn_recursive();
+            commands.entity(entity).remove::<ClipCornersNode>();
+        }
+    }
+
+    let viewport = windows
+        .get_single()
+        .map_or(Vec2::ZERO, |window| window.size());
+    for (entity, clip, node, radius, children, clip_node) in targets.iter() {
+        let material = RoundedClipMaterial {
+            color: LinearRgba::from(clip.backdrop).to_vec4(),
+            radius: resolve_corner_radii(
+                radius,
+                node.map_or(Vec2::ZERO, |node| node.size()),
+                viewport,
+            ),
+        };
+        let overlay = match clip_node {
+            Some(ClipCornersNode(overlay)) => {
+                if let Some(current) = overlays
+                    .get(*overlay)
+                    .ok()
+                    .and_then(|handle| materials.get_mut(handle))
+                {
+                    *current = material;
+                }
+                *overlay
+            }
+            None => {
+                let overlay = commands
+                    .spawn((
+                        MaterialNodeBundle {
+                            style: Style {
+                                position_type: ui::PositionType::Absolute,
+                                left: ui::Val::Px(0.),
+                                top: ui::Val::Px(0.),
+                                right: ui::Val::Px(0.),
+                                bottom: ui::Val::Px(0.),
+                                ..default()
+                            },
+                            material: materials.add(material),
+                            ..default()
+                        },
+                        Name::new("ClipCorners"),
+                        Overlay,
+                        Pickable::IGNORE,
+                    ))
+                    .id();
+                commands.entity(entity).insert(ClipCornersNode(overlay));
+                overlay
+            }
+        };
+
+        // Keep the overlay last, so it is drawn above the other children.
+        if children.and_then(|children| children.last()) != Some(&overlay) {
+            commands.entity(entity).push_children(&[overlay]);
+        }
+    }
+}
+
+/// Clips its children to rounded corners over any background, including images and
+/// gradients. The children are rendered into a texture by a [`Compositor`], which is then
+/// displayed with rounded corners.
+///
+/// This is more expensive than [`RoundedClip`], since each mask needs its own camera and
+/// texture. Pointer input only reaches the children if the [`CompositorPickingPlugin`] is
+/// installed.
+#[derive(Clone, Default)]
+pub struct RoundedMask {
+    /// Which corners are rounded.
+    pub corners: RoundedCorners,
+
+    /// Radius of the rounded corners, in pixels.
+    pub radius: f32,
+
+    /// The content to clip.
+    pub children: ChildArray,
+}
+
+impl RoundedMask {
+    /// Construct a new `RoundedMask` which rounds all corners with the given radius.
+    pub fn new(radius: f32) -> Self {
+        Self {
+            radius,
+            ..default()
+        }
+    }
+
+    /// Set which corners are rounded.
+    pub fn corners(mut self, corners: RoundedCorners) -> Self {
+        self.corners = corners;
+        self
+    }
+
+    /// Set the content to clip.
+    pub fn children<V: ChildViewTuple>(mut self, children: V) -> Self {
+        self.children = children.to_child_array();
+        self
+    }
+}
+
+impl ViewTemplate for RoundedMask {
+    fn create(&self, _cx: &mut Cx) -> impl IntoView {
+        let radius = self.corners.to_border_radius(self.radius);
+        Compositor::new(self.children.clone())
+            .clear_color(Srgba::NONE.into())
+            .style(move |ss: &mut StyleBuilder| {
+                ss.border_radius(radius);
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::RunSystemOnce;
+
+    use super::*;
+
+    #[test]
+    fn test_update_clip_corners() {
+        let mut world = World::new();
+        world.init_resource::<Assets<RoundedClipMaterial>>();
+        let target = world
+            .spawn((
+                NodeBundle::default(),
+                BorderRadius::all(ui::Val::Px(6.)),
+                ClipCorners {
+                    backdrop: Color::BLACK,
+                },
+            ))
+            .id();
+        let child = world.spawn(NodeBundle::default()).set_parent(target).id();
+
+        world.run_system_once(update_clip_corners);
+        let overlay = world.get::<ClipCornersNode>(target).unwrap().0;
+        assert!(world.get::<Overlay>(overlay).is_some());
+        assert_eq!(
+            world.get::<Children>(target).unwrap().to_vec(),
+            vec![child, overlay]
+        );
+
+        // The overlay is moved back to the end when children are added after it.
+        let late = world.spawn(NodeBundle::default()).set_parent(target).id();
+        world.run_system_once(update_clip_corners);
+        assert_eq!(
+            world.get::<Children>(target).unwrap().to_vec(),
+            vec![child, late, overlay]
+        );
+
+        // Removing the component despawns the overlay.
+        world.entity_mut(target).remove::<ClipCorners>();
+        world.run_system_once(update_clip_corners);
+        assert!(world.get_entity(overlay).is_none());
+        assert!(world.get::<ClipCornersNode>(target).is_none());
+    }
+}
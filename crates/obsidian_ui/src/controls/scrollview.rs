@@ -2,7 +2,16 @@ use bevy::{prelude::*, ui};
 use bevy_mod_picking::prelude::*;
 use bevy_reactor::*;
 
-use crate::scrolling::{ScrollArea, ScrollBar, ScrollBarThumb, ScrollContent, ScrollWheel};
+use super::RoundedClip;
+use crate::{
+    pointer_capture::CapturePointer,
+    scrolling::{
+        ScrollArea, ScrollBar, ScrollBarFade, ScrollBarThumb, ScrollContent, ScrollPosition,
+        ScrollPositionSync, ScrollWheel,
+    },
+    touch_target::{TouchSettings, TouchTarget},
+    RoundedCorners,
+};
 
 // Style definitions for scrollview widget.
 
@@ -20,6 +29,11 @@ fn style_scroll_view(ss: &mut StyleBuilder) {
         .gap(2);
 }
 
+/// With overlay scrollbars, the scrollbars are drawn over the content rather than beside it.
+fn style_scroll_view_overlay(ss: &mut StyleBuilder) {
+    ss.gap(0);
+}
+
 /// The scrolling region which defines the clipping bounds.
 fn style_scroll_region(ss: &mut StyleBuilder) {
     ss.grid_column(ui::GridPlacement::start_span(1, 1))
@@ -56,6 +70,22 @@ fn style_scrollbar_y(ss: &mut StyleBuilder) {
         .width(8);
 }
 
+fn style_scrollbar_x_overlay(ss: &mut StyleBuilder) {
+    ss.grid_row(ui::GridPlacement::start_span(1, 1))
+        .align_self(ui::AlignSelf::End)
+        .height(6)
+        .z_index(ZIndex::Local(1))
+        .margin((2, 1));
+}
+
+fn style_scrollbar_y_overlay(ss: &mut StyleBuilder) {
+    ss.grid_column(ui::GridPlacement::start_span(1, 1))
+        .justify_self(ui::JustifySelf::End)
+        .width(6)
+        .z_index(ZIndex::Local(1))
+        .margin((1, 2));
+}
+
 fn style_scrollbar_y_thumb(ss: &mut StyleBuilder) {
     ss.background_color("#334")
         .position(ui::PositionType::Absolute)
@@ -80,6 +110,10 @@ struct DragState {
 }
 
 /// The scroll view widget.
+///
+/// Styleable parts:
+/// * `"scrollbar"` - the scrollbar track (both horizontal and vertical).
+/// * `"thumb"` - the draggable scrollbar thumb.
 #[derive(Default)]
 pub struct ScrollView {
     /// Views for the scrolling content
@@ -92,6 +126,18 @@ pub struct ScrollView {
     pub scroll_enable_x: bool,
     /// Whether to enable vertical scrolling.
     pub scroll_enable_y: bool,
+    /// Style overrides for the internal parts of the scroll view.
+    pub part_styles: PartStyles,
+    /// Rounded corners to clip the scrolling content to.
+    pub clip: Option<RoundedClip>,
+    /// Entity to use for the scroll area, which holds the [`ScrollArea`] component.
+    pub scroll_area: Option<Entity>,
+    /// Whether to draw the scrollbars over the content, fading them out when idle.
+    pub overlay_scrollbars: bool,
+    /// Minimum length of the scrollbar thumbs, in pixels. Defaults to 10.
+    pub thumb_min_size: Option<f32>,
+    /// Signal which is kept in sync with the scroll position.
+    pub position: Option<ScrollPosition>,
 }
 
 impl ScrollView {
@@ -118,6 +164,12 @@ impl ScrollView {
         self
     }
 
+    /// Set additional styles for one of the scroll view's internal parts.
+    pub fn part_style<S: StyleTuple + 'static>(mut self, part: &'static str, style: S) -> Self {
+        self.part_styles.insert(part, style);
+        self
+    }
+
     /// Enable horizontal scrolling.
     pub fn scroll_enable_x(mut self, enable: bool) -> Self {
         self.scroll_enable_x = enable;
@@ -129,6 +181,46 @@ impl ScrollView {
         self.scroll_enable_y = enable;
         self
     }
+
+    /// Use the given entity for the scroll area. This allows other widgets to read and change
+    /// the scroll position via its [`ScrollArea`] component.
+    pub fn scroll_area_id(mut self, id: Entity) -> Self {
+        self.scroll_area = Some(id);
+        self
+    }
+
+    /// Draw the scrollbars over the content instead of beside it. Overlay scrollbars fade out
+    /// when the view is not being scrolled, and grow wider while hovered.
+    pub fn overlay_scrollbars(mut self, overlay: bool) -> Self {
+        self.overlay_scrollbars = overlay;
+        self
+    }
+
+    /// Set the minimum length of the scrollbar thumbs, in pixels.
+    pub fn thumb_min_size(mut self, size: f32) -> Self {
+        self.thumb_min_size = Some(size);
+        self
+    }
+
+    /// Keep `position` in sync with the scroll position. Setting the signal scrolls the view.
+    pub fn position(mut self, position: ScrollPosition) -> Self {
+        self.position = Some(position);
+        self
+    }
+
+    /// Clip the scrolling content to rounded corners. `backdrop` is the color surrounding the
+    /// scroll area, which is painted over the content outside of the corners. If the scroll
+    /// view sits on an image or gradient, wrap it in a [`RoundedMask`](super::RoundedMask)
+    /// instead.
+    pub fn clip_corners(
+        mut self,
+        corners: RoundedCorners,
+        radius: f32,
+        backdrop: impl IntoSignal<Color>,
+    ) -> Self {
+        self.clip = Some(RoundedClip::new(radius).corners(corners).backdrop(backdrop));
+        self
+    }
 }
 
 impl ViewTemplate for ScrollView {
@@ -136,16 +228,35 @@ impl ViewTemplate for ScrollView {
         // A widget which displays a scrolling view of its children.
         let enable_x = self.scroll_enable_x;
         let enable_y = self.scroll_enable_y;
-        let id_scroll_area = cx.create_entity();
+        let id_scroll_area = self.scroll_area.unwrap_or_else(|| cx.create_entity());
         let id_scrollbar_x = cx.create_entity();
         let id_scrollbar_y = cx.create_entity();
         let drag_state = cx.create_mutable::<DragState>(DragState::default());
+        let overlay = self.overlay_scrollbars;
+        let min_thumb_size = self.thumb_min_size.unwrap_or(10.);
+        let scroll_area = Element::<NodeBundle>::for_entity(id_scroll_area);
+        let scroll_area = match self.position {
+            Some(position) => scroll_area.insert(ScrollPositionSync {
+                position,
+                last: None,
+                pending: None,
+            }),
+            None => scroll_area,
+        };
         Element::<NodeBundle>::new()
             .named("ScrollView")
-            .style((style_scroll_view, self.style.clone()))
+            .style((
+                style_scroll_view,
+                move |ss: &mut StyleBuilder| {
+                    if overlay {
+                        style_scroll_view_overlay(ss);
+                    }
+                },
+                self.style.clone(),
+            ))
             .children((
                 // Scroll area
-                Element::<NodeBundle>::for_entity(id_scroll_area)
+                scroll_area
                     .named("ScrollView::ScrollArea")
                     .insert((
                         ScrollArea {
@@ -159,38 +270,71 @@ impl ViewTemplate for ScrollView {
                                 scrolling.scroll_by(-ev.delta.x, -ev.delta.y);
                             },
                         ),
+                        // Dragging the content with a touch scrolls it, once the touch has moved
+                        // far enough that it is no longer a tap.
+                        On::<Pointer<Drag>>::run(
+                            move |mut ev: ListenerMut<Pointer<Drag>>,
+                                  settings: Res<TouchSettings>,
+                                  mut scroll_areas: Query<&mut ScrollArea>| {
+                                if !ev.pointer_id.is_touch() {
+                                    return;
+                                }
+                                ev.stop_propagation();
+                                if ev.distance.length() <= settings.drag_threshold {
+                                    return;
+                                }
+                                if let Ok(mut scrolling) = scroll_areas.get_mut(id_scroll_area) {
+                                    let dx = if enable_x { -ev.delta.x } else { 0. };
+                                    let dy = if enable_y { -ev.delta.y } else { 0. };
+                                    scrolling.scroll_by(dx, dy);
+                                }
+                            },
+                        ),
                     ))
                     .style(style_scroll_region)
-                    .children(
+                    .children((
                         Element::<NodeBundle>::new()
                             .named("ScrollView::ScrollRegion")
                             .insert(ScrollContent)
                             .style((style_scroll_content, self.content_style.clone()))
                             .children(self.children.clone()),
-                    ),
+                        self.clip.clone(),
+                    )),
                 // Horizontal scroll bar
                 Cond::new(
                     move |_| enable_x,
-                    move || {
-                        Scrollbar::new(ScrollbarProps {
-                            id_scroll_area,
-                            id_scrollbar: id_scrollbar_x,
-                            drag_state,
-                            vertical: false,
-                        })
+                    {
+                        let part_styles = self.part_styles.clone();
+                        move || {
+                            Scrollbar::new(ScrollbarProps {
+                                id_scroll_area,
+                                id_scrollbar: id_scrollbar_x,
+                                drag_state,
+                                vertical: false,
+                                overlay,
+                                min_thumb_size,
+                                part_styles: part_styles.clone(),
+                            })
+                        }
                     },
                     || (),
                 ),
                 // Vertical scroll bar
                 Cond::new(
                     move |_| enable_y,
-                    move || {
-                        Scrollbar::new(ScrollbarProps {
-                            id_scroll_area,
-                            id_scrollbar: id_scrollbar_y,
-                            drag_state,
-                            vertical: true,
-                        })
+                    {
+                        let part_styles = self.part_styles.clone();
+                        move || {
+                            Scrollbar::new(ScrollbarProps {
+                                id_scroll_area,
+                                id_scrollbar: id_scrollbar_y,
+                                drag_state,
+                                vertical: true,
+                                overlay,
+                                min_thumb_size,
+                                part_styles: part_styles.clone(),
+                            })
+                        }
                     },
                     || (),
                 ),
@@ -205,6 +349,9 @@ pub struct ScrollbarProps {
     id_scrollbar: Entity,
     drag_state: Mutable<DragState>,
     vertical: bool,
+    overlay: bool,
+    min_thumb_size: f32,
+    part_styles: PartStyles,
 }
 
 /// Scrollbar widget.
@@ -222,15 +369,38 @@ impl ViewTemplate for Scrollbar {
         let vertical = self.0.vertical;
         let drag_state = self.0.drag_state;
         let id_scroll_area = self.0.id_scroll_area;
+        let id_scrollbar = self.0.id_scrollbar;
         let id_thumb = cx.create_entity();
-        Element::<NodeBundle>::for_entity(self.0.id_scrollbar)
+        let overlay = self.0.overlay;
+        let element = Element::<NodeBundle>::for_entity(id_scrollbar);
+        let element = if overlay {
+            // Overlay scrollbars stay visible and grow wider while hovered or dragged.
+            let hovering = cx.create_hover_signal(id_scrollbar);
+            element
+                .insert((ScrollBarFade::default(), Opacity::default()))
+                .create_effect(move |cx, ent| {
+                    let active = hovering.get(cx) || drag_state.get(cx).mode != DragMode::None;
+                    let thickness = ui::Val::Px(if active { 10. } else { 6. });
+                    let mut entt = cx.world_mut().entity_mut(ent);
+                    entt.get_mut::<ScrollBarFade>().unwrap().active = active;
+                    let mut style = entt.get_mut::<Style>().unwrap();
+                    if vertical {
+                        style.width = thickness;
+                    } else {
+                        style.height = thickness;
+                    }
+                })
+        } else {
+            element
+        };
+        element
             .named("Scrollbar")
             .insert(
                 (
                     ScrollBar {
                         id_scroll_area,
                         vertical,
-                        min_thumb_size: 10.,
+                        min_thumb_size: self.0.min_thumb_size,
                     },
                     // Click outside of thumb
                     On::<Pointer<DragStart>>::run(
@@ -258,21 +428,38 @@ impl ViewTemplate for Scrollbar {
                     ),
                 ),
             )
-            .style(if vertical {
-                style_scrollbar_y
-            } else {
-                style_scrollbar_x
-            })
+            .style((
+                if vertical {
+                    style_scrollbar_y
+                } else {
+                    style_scrollbar_x
+                },
+                move |ss: &mut StyleBuilder| match (overlay, vertical) {
+                    (true, true) => {
+                        style_scrollbar_y_overlay(ss);
+                    }
+                    (true, false) => {
+                        style_scrollbar_x_overlay(ss);
+                    }
+                    (false, _) => {}
+                },
+                self.0.part_styles.get("scrollbar"),
+            ))
             .children(
                 Element::<NodeBundle>::for_entity(id_thumb)
                     // .class_names(CLS_DRAG.if_true(cx.read_atom(drag_state).mode == mode))
-                    .style(if vertical {
-                        style_scrollbar_y_thumb
-                    } else {
-                        style_scrollbar_x_thumb
-                    })
+                    .style((
+                        if vertical {
+                            style_scrollbar_y_thumb
+                        } else {
+                            style_scrollbar_x_thumb
+                        },
+                        self.0.part_styles.get("thumb"),
+                    ))
                     .insert((
+                        CapturePointer,
                         ScrollBarThumb,
+                        TouchTarget::default(),
                         // Click/Drag on thumb
                         On::<Pointer<DragStart>>::run(move |world: &mut World| {
                             let mut event = world
@@ -283,7 +470,11 @@ impl ViewTemplate for Scrollbar {
                                 drag_state.set(
                                     world,
                                     DragState {
-                                        mode: DragMode::DragY,
+                                        mode: if vertical {
+                                            DragMode::DragY
+                                        } else {
+                                            DragMode::DragX
+                                        },
                                         offset: if vertical {
                                             scroll_area.scroll_top
                                         } else {
@@ -6,7 +6,14 @@ use bevy::{
 use bevy_mod_picking::prelude::*;
 use bevy_reactor::*;
 
-use crate::{colors, materials::SliderRectMaterial, RoundedCorners};
+use crate::{
+    colors,
+    hooks::{CreateDisabledSignal, CreateThemeSignal, ThemeToken},
+    materials::SliderRectMaterial,
+    pointer_capture::CapturePointer,
+    touch_target::TouchTarget,
+    RoundedCorners,
+};
 
 #[derive(Clone, PartialEq, Default, Copy)]
 enum DragType {
@@ -17,6 +24,34 @@ enum DragType {
     HoldIncrement,
 }
 
+/// The orientation of a slider.
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum SliderOrientation {
+    /// The slider increases from left to right.
+    #[default]
+    Horizontal,
+    /// The slider increases from bottom to top.
+    Vertical,
+}
+
+impl SliderOrientation {
+    /// The length of the slider along its axis.
+    pub(crate) fn length(&self, rect: Rect) -> f32 {
+        match self {
+            SliderOrientation::Horizontal => rect.width(),
+            SliderOrientation::Vertical => rect.height(),
+        }
+    }
+
+    /// The distance moved along the slider's axis, in the direction of increasing value.
+    pub(crate) fn drag_distance(&self, distance: Vec2) -> f32 {
+        match self {
+            SliderOrientation::Horizontal => distance.x,
+            SliderOrientation::Vertical => -distance.y,
+        }
+    }
+}
+
 #[derive(Clone, PartialEq, Default, Copy)]
 struct DragState {
     dragging: DragType,
@@ -27,6 +62,10 @@ fn style_slider(ss: &mut StyleBuilder) {
     ss.min_width(64).height(20);
 }
 
+fn style_slider_vertical(ss: &mut StyleBuilder) {
+    ss.min_height(64).width(32);
+}
+
 fn style_overlay(ss: &mut StyleBuilder) {
     ss.display(ui::Display::Flex)
         .flex_direction(ui::FlexDirection::Row)
@@ -74,11 +113,18 @@ fn style_label(ss: &mut StyleBuilder) {
         .padding((6, 0));
 }
 
+fn style_label_vertical(ss: &mut StyleBuilder) {
+    ss.padding(0).font_size(12);
+}
+
 fn style_label_spacer(ss: &mut StyleBuilder) {
     ss.flex_grow(1.);
 }
 
-/// Horizontal slider widget
+/// Slider widget, which can be horizontal or vertical.
+///
+/// Styleable parts:
+/// * `"label"` - the container for the label and formatted value text.
 pub struct Slider {
     /// Current slider value.
     pub value: Signal<f32>,
@@ -110,6 +156,13 @@ pub struct Slider {
 
     /// Callback called when value changes
     pub on_change: Option<Callback<f32>>,
+
+    /// Style overrides for the internal parts of the slider.
+    pub part_styles: PartStyles,
+
+    /// Whether the slider is horizontal or vertical. Vertical sliders omit the step buttons
+    /// and the label, displaying only the value.
+    pub orientation: SliderOrientation,
 }
 
 impl Slider {
@@ -173,11 +226,23 @@ impl Slider {
         self
     }
 
+    /// Set additional styles for one of the slider's internal parts.
+    pub fn part_style<S: StyleTuple + 'static>(mut self, part: &'static str, style: S) -> Self {
+        self.part_styles.insert(part, style);
+        self
+    }
+
     /// Set the callback called when value changes.
     pub fn on_change(mut self, on_change: Callback<f32>) -> Self {
         self.on_change = Some(on_change);
         self
     }
+
+    /// Set whether the slider is horizontal or vertical.
+    pub fn orientation(mut self, orientation: SliderOrientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
 }
 
 impl Default for Slider {
@@ -193,6 +258,8 @@ impl Default for Slider {
             style: StyleHandle::default(),
             label: None,
             on_change: None,
+            part_styles: PartStyles::default(),
+            orientation: SliderOrientation::default(),
         }
     }
 }
@@ -210,6 +277,9 @@ impl ViewTemplate for Slider {
         let precision = self.precision;
         let step = self.step;
         let on_change = self.on_change;
+        let orientation = self.orientation;
+        let vertical = orientation == SliderOrientation::Vertical;
+        let disabled = cx.create_disabled_signal(self.disabled);
 
         let mut ui_materials = cx
             .world_mut()
@@ -220,13 +290,26 @@ impl ViewTemplate for Slider {
             color_hi: LinearRgba::from(colors::U3).to_vec4(),
             value: 0.5,
             radius: RoundedCorners::All.to_vec(4.),
+            start: 0.,
+            vertical: vertical as u32,
         });
 
         Element::<MaterialNodeBundle<SliderRectMaterial>>::for_entity(slider_id)
-            .style((style_slider, self.style.clone()))
+            .style((
+                match orientation {
+                    SliderOrientation::Horizontal => style_slider,
+                    SliderOrientation::Vertical => style_slider_vertical,
+                },
+                self.style.clone(),
+            ))
             .insert((
+                CapturePointer,
+                TouchTarget::default(),
                 material.clone(),
                 On::<Pointer<DragStart>>::run(move |world: &mut World| {
+                    if disabled.get(world) {
+                        return;
+                    }
                     // Save initial value to use as drag offset.
                     let mut event = world
                         .get_resource_mut::<ListenerInput<Pointer<DragStart>>>()
@@ -255,20 +338,24 @@ impl ViewTemplate for Slider {
                 On::<Pointer<Drag>>::run(move |world: &mut World| {
                     let ds = drag_state.get(world);
                     if ds.dragging == DragType::Dragging {
-                        let event = world
-                            .get_resource::<ListenerInput<Pointer<Drag>>>()
+                        let mut event = world
+                            .get_resource_mut::<ListenerInput<Pointer<Drag>>>()
                             .unwrap();
+                        // Don't let touch drags on the slider scroll an enclosing view.
+                        event.stop_propagation();
+                        let distance = event.distance;
                         let ent = world.entity(slider_id);
                         let node = ent.get::<Node>();
                         let transform = ent.get::<GlobalTransform>();
                         if let (Some(node), Some(transform)) = (node, transform) {
                             // Measure node width and slider value.
-                            let slider_width = node.logical_rect(transform).width();
+                            let slider_length = orientation.length(node.logical_rect(transform));
                             let min = min.get(world);
                             let max = max.get(world);
                             let range = max - min;
                             let new_value = if range > 0. {
-                                ds.offset + (event.distance.x * range) / slider_width
+                                ds.offset
+                                    + (orientation.drag_distance(distance) * range) / slider_length
                             } else {
                                 min + range * 0.5
                             };
@@ -302,48 +389,68 @@ impl ViewTemplate for Slider {
                 .named("Slider")
                 .style(style_overlay)
                 .children((
-                    SliderButton {
-                        value,
-                        min,
-                        max,
-                        step: -step,
-                        hovering,
-                        on_change,
-                        drag_state,
-                    },
-                    Element::<NodeBundle>::new().style(style_label).children((
-                        Cond::new(
-                            {
-                                let label = self.label.clone();
-                                move |_cx| label.is_some()
-                            },
-                            {
-                                let label = self.label.clone();
-                                move || {
-                                    Fragment::new((
-                                        label.clone().unwrap(),
-                                        Element::<NodeBundle>::new().style(style_label_spacer),
-                                    ))
+                    Cond::new(
+                        move |_cx| !vertical,
+                        move || SliderButton {
+                            value,
+                            min,
+                            max,
+                            step: -step,
+                            hovering,
+                            disabled,
+                            on_change,
+                            drag_state,
+                        },
+                        || (),
+                    ),
+                    Element::<NodeBundle>::new()
+                        .style((
+                            style_label,
+                            move |ss: &mut StyleBuilder| {
+                                if vertical {
+                                    style_label_vertical(ss);
                                 }
                             },
-                            || (),
-                        ),
-                        text_computed({
-                            move |cx| {
-                                let value = value.get(cx);
-                                format!("{:.*}", precision, value)
-                            }
-                        }),
-                    )),
-                    SliderButton {
-                        value,
-                        min,
-                        max,
-                        step,
-                        hovering,
-                        on_change,
-                        drag_state,
-                    },
+                            self.part_styles.get("label"),
+                        ))
+                        .children((
+                            Cond::new(
+                                {
+                                    let label = self.label.clone();
+                                    move |_cx| label.is_some() && !vertical
+                                },
+                                {
+                                    let label = self.label.clone();
+                                    move || {
+                                        Fragment::new((
+                                            label.clone().unwrap(),
+                                            Element::<NodeBundle>::new().style(style_label_spacer),
+                                        ))
+                                    }
+                                },
+                                || (),
+                            ),
+                            text_computed({
+                                move |cx| {
+                                    let value = value.get(cx);
+                                    format!("{:.*}", precision, value)
+                                }
+                            }),
+                        )),
+                    Cond::new(
+                        move |_cx| !vertical,
+                        move || SliderButton {
+                            value,
+                            min,
+                            max,
+                            step,
+                            hovering,
+                            disabled,
+                            on_change,
+                            drag_state,
+                        },
+                        || (),
+                    ),
                 )),))
     }
 }
@@ -354,6 +461,7 @@ struct SliderButton {
     max: Signal<f32>,
     step: f32,
     hovering: Signal<bool>,
+    disabled: Signal<bool>,
     on_change: Option<Callback<f32>>,
     drag_state: Mutable<DragState>,
 }
@@ -362,12 +470,14 @@ impl ViewTemplate for SliderButton {
     fn create(&self, cx: &mut Cx) -> impl IntoView {
         let button_id = cx.create_entity();
         let button_hovering = cx.create_hover_signal(button_id);
+        let color_foreground = cx.create_theme_color(ThemeToken::Foreground);
         let hovering = self.hovering;
         let min = self.min;
         let max = self.max;
         let value = self.value;
         let step = self.step;
         let on_change = self.on_change;
+        let disabled = self.disabled;
         let drag_state = self.drag_state;
         let drag_type = if step > 0.0 {
             DragType::HoldIncrement
@@ -379,6 +489,9 @@ impl ViewTemplate for SliderButton {
             .style(style_button)
             .insert((
                 On::<Pointer<DragStart>>::run(move |world: &mut World| {
+                    if disabled.get(world) {
+                        return;
+                    }
                     let mut event = world
                         .get_resource_mut::<ListenerInput<Pointer<DragStart>>>()
                         .unwrap();
@@ -411,6 +524,9 @@ impl ViewTemplate for SliderButton {
                     );
                 }),
                 On::<Pointer<DragEnter>>::run(move |world: &mut World| {
+                    if disabled.get(world) {
+                        return;
+                    }
                     let ds = drag_state.get(world);
                     let mut event = world
                         .get_resource_mut::<ListenerInput<Pointer<DragEnter>>>()
@@ -457,9 +573,10 @@ impl ViewTemplate for SliderButton {
                         let ds = drag_state.get(cx);
                         let is_hovering = hovering.get(cx) && step != 0.0;
                         let is_hovering_inc = button_hovering.get(cx);
+                        let foreground = color_foreground.get(cx);
                         let color = match (ds.dragging, is_hovering, is_hovering_inc) {
-                            (DragType::HoldIncrement, _, _) if step > 0.0 => colors::FOREGROUND,
-                            (DragType::HoldDecrement, _, _) if step < 0.0 => colors::FOREGROUND,
+                            (DragType::HoldIncrement, _, _) if step > 0.0 => foreground,
+                            (DragType::HoldDecrement, _, _) if step < 0.0 => foreground,
                             (DragType::Dragging, _, _) => colors::TRANSPARENT,
                             (_, true, true) => colors::U4.lighter(0.1),
                             (_, true, false) => colors::U4,
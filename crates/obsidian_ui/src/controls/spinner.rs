@@ -0,0 +1,95 @@
+use bevy::{prelude::*, ui};
+use bevy_reactor::*;
+
+use crate::{
+    hooks::{CreateThemeSignal, ThemeToken},
+    materials::SpinnerMaterial,
+    size::Size,
+};
+
+fn style_spinner(ss: &mut StyleBuilder) {
+    ss.flex_shrink(0.).pointer_events(false);
+}
+
+/// An animated ring indicating that an operation of unknown duration is in progress.
+pub struct Spinner {
+    /// Spinner size. The spinner is somewhat smaller than a button of the same size.
+    pub size: Size,
+
+    /// Color of the spinner. Defaults to the theme's foreground color.
+    pub color: Option<Signal<Srgba>>,
+
+    /// Additional styles to be applied to the spinner.
+    pub style: StyleHandle,
+}
+
+impl Spinner {
+    /// Create a new spinner.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the spinner size.
+    pub fn size(mut self, size: Size) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Set the color of the spinner.
+    pub fn color(mut self, color: impl IntoSignal<Srgba>) -> Self {
+        self.color = Some(color.into_signal());
+        self
+    }
+
+    /// Set additional styles to be applied to the spinner.
+    pub fn style<S: StyleTuple + 'static>(mut self, style: S) -> Self {
+        self.style = style.into_handle();
+        self
+    }
+}
+
+impl Default for Spinner {
+    fn default() -> Self {
+        Self {
+            size: Size::default(),
+            color: None,
+            style: StyleHandle::default(),
+        }
+    }
+}
+
+impl ViewTemplate for Spinner {
+    fn create(&self, cx: &mut Cx) -> impl IntoView {
+        let diameter = (self.size.height() * 0.66).round();
+        let color = self
+            .color
+            .unwrap_or_else(|| cx.create_theme_color(ThemeToken::Foreground));
+        let initial = color.get(cx);
+
+        let mut ui_materials = cx
+            .world_mut()
+            .get_resource_mut::<Assets<SpinnerMaterial>>()
+            .unwrap();
+        let material = ui_materials.add(SpinnerMaterial {
+            color: LinearRgba::from(initial).to_vec4(),
+        });
+
+        Element::<MaterialNodeBundle<SpinnerMaterial>>::new()
+            .named("Spinner")
+            .style((
+                style_spinner,
+                move |ss: &mut StyleBuilder| {
+                    ss.width(ui::Val::Px(diameter))
+                        .height(ui::Val::Px(diameter));
+                },
+                self.style.clone(),
+            ))
+            .insert(material)
+            .material_param(
+                |material: &mut SpinnerMaterial, color: Srgba| {
+                    material.color = LinearRgba::from(color).to_vec4();
+                },
+                color,
+            )
+    }
+}
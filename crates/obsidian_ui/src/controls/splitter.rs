@@ -1,8 +1,17 @@
-use bevy::{color::Luminance, prelude::*, ui};
+use bevy::{color::Luminance, prelude::*, ui, window::CursorIcon};
 use bevy_mod_picking::{events::PointerCancel, prelude::*};
 use bevy_reactor::*;
 
-use crate::colors;
+use crate::{
+    colors,
+    hooks::{CreateThemeSignal, ThemeToken},
+    layout_state::{PaneLayout, UiLayoutState},
+    pointer_capture::CapturePointer,
+    touch_target::TouchTarget,
+};
+
+/// Maximum time between two clicks for them to count as a double-click, in seconds.
+const DOUBLE_CLICK_TIME: f32 = 0.3;
 
 /// The direction of the splitter. Represents the direction of the bar, not the items being split.
 #[derive(Clone, PartialEq, Default)]
@@ -19,6 +28,42 @@ pub enum SplitterDirection {
 struct DragState {
     dragging: bool,
     offset: f32,
+    /// Set when the bar has been dragged, so that the click ending the drag is ignored.
+    moved: bool,
+}
+
+/// Size constraints and persistence settings, shared by the splitter's event handlers.
+#[derive(Clone)]
+struct SplitterLimits {
+    min: f32,
+    max: f32,
+    collapse_threshold: Option<f32>,
+    persist_key: Option<String>,
+}
+
+impl SplitterLimits {
+    /// Apply constraints to a dragged value. Values below the collapse threshold snap to zero.
+    fn constrain(&self, value: f32) -> f32 {
+        match self.collapse_threshold {
+            Some(threshold) if value < threshold => 0.,
+            _ => value.clamp(self.min, self.max),
+        }
+    }
+
+    /// Record the new value in the [`UiLayoutState`], if this splitter is persisted.
+    fn persist(&self, world: &mut World, value: f32, restore_size: Option<f32>) {
+        if let Some(key) = &self.persist_key {
+            if let Some(mut state) = world.get_resource_mut::<UiLayoutState>() {
+                state.set_pane(
+                    key.clone(),
+                    PaneLayout {
+                        size: value,
+                        restore_size,
+                    },
+                );
+            }
+        }
+    }
 }
 
 fn style_vsplitter(ss: &mut StyleBuilder) {
@@ -28,6 +73,7 @@ fn style_vsplitter(ss: &mut StyleBuilder) {
         .flex_direction(ui::FlexDirection::Column)
         .gap(8)
         .width(9)
+        .cursor(CursorIcon::ColResize)
         .background_color(colors::U2);
 }
 
@@ -47,6 +93,7 @@ fn style_hsplitter(ss: &mut StyleBuilder) {
         .flex_direction(ui::FlexDirection::Column)
         .gap(8)
         .height(9)
+        .cursor(CursorIcon::RowResize)
         .background_color(colors::U2);
 }
 
@@ -68,6 +115,23 @@ pub struct Splitter {
 
     /// Callback involved with the new split value.
     pub on_change: Option<Callback<f32>>,
+
+    /// Minimum split value.
+    pub min: f32,
+
+    /// Maximum split value.
+    pub max: f32,
+
+    /// Split value restored when the splitter bar is double-clicked.
+    pub default_value: Option<f32>,
+
+    /// If set, dragging the split value below this threshold collapses the pane to zero.
+    /// Clicking the bar of a collapsed splitter restores the pane to its previous size.
+    pub collapse_threshold: Option<f32>,
+
+    /// Key under which the split value is saved in the [`UiLayoutState`] resource. When the
+    /// splitter is created, a saved value is restored via `on_change`.
+    pub persist_key: Option<String>,
 }
 
 impl Splitter {
@@ -93,6 +157,31 @@ impl Splitter {
         self.on_change = Some(on_change);
         self
     }
+
+    /// Set the minimum and maximum split values.
+    pub fn limits(mut self, min: f32, max: f32) -> Self {
+        self.min = min;
+        self.max = max;
+        self
+    }
+
+    /// Set the split value restored when the splitter bar is double-clicked.
+    pub fn default_value(mut self, default_value: f32) -> Self {
+        self.default_value = Some(default_value);
+        self
+    }
+
+    /// Collapse the pane when the split value is dragged below `threshold`.
+    pub fn collapse_threshold(mut self, threshold: f32) -> Self {
+        self.collapse_threshold = Some(threshold);
+        self
+    }
+
+    /// Save the split value in the [`UiLayoutState`] resource under the given key.
+    pub fn persist(mut self, key: impl Into<String>) -> Self {
+        self.persist_key = Some(key.into());
+        self
+    }
 }
 
 impl Default for Splitter {
@@ -101,6 +190,11 @@ impl Default for Splitter {
             value: Signal::Constant(0.),
             direction: SplitterDirection::Vertical,
             on_change: None,
+            min: 0.,
+            max: f32::MAX,
+            default_value: None,
+            collapse_threshold: None,
+            persist_key: None,
         }
     }
 }
@@ -109,9 +203,30 @@ impl ViewTemplate for Splitter {
     fn create(&self, cx: &mut Cx) -> impl IntoView {
         let id = cx.create_entity();
         let hovering = cx.create_hover_signal(id);
+        let color_accent = cx.create_theme_color(ThemeToken::Accent);
         let drag_state = cx.create_mutable::<DragState>(DragState::default());
+        let last_click = cx.create_mutable::<f32>(f32::NEG_INFINITY);
         let current_offset = self.value;
         let direction = self.direction.clone();
+        let on_change = self.on_change;
+        let default_value = self.default_value;
+        let limits = SplitterLimits {
+            min: self.min,
+            max: self.max,
+            collapse_threshold: self.collapse_threshold,
+            persist_key: self.persist_key.clone(),
+        };
+
+        // Restore the saved pane size, if any.
+        let saved = limits
+            .persist_key
+            .as_ref()
+            .and_then(|key| cx.world().get_resource::<UiLayoutState>()?.pane(key));
+        let restore_size = cx.create_mutable::<Option<f32>>(saved.and_then(|p| p.restore_size));
+        if let (Some(saved), Some(on_change)) = (saved, on_change) {
+            cx.run_callback(on_change, saved.size);
+        }
+
         let style_splitter = match self.direction {
             SplitterDirection::Horizontal => style_hsplitter,
             SplitterDirection::Vertical => style_vsplitter,
@@ -125,6 +240,8 @@ impl ViewTemplate for Splitter {
             // .class_names(CLS_DRAG.if_true(cx.read_atom(drag_state).dragging))
             .style(style_splitter)
             .insert((
+                CapturePointer,
+                TouchTarget::default(),
                 On::<Pointer<DragStart>>::run(move |world: &mut World| {
                     // Save initial value to use as drag offset.
                     drag_state.set(
@@ -132,40 +249,80 @@ impl ViewTemplate for Splitter {
                         DragState {
                             dragging: true,
                             offset: current_offset.get(world),
+                            moved: false,
                         },
                     );
                 }),
                 On::<Pointer<DragEnd>>::run(move |world: &mut World| {
+                    let moved = drag_state.get(world).moved;
                     drag_state.set(
                         world,
                         DragState {
                             dragging: false,
                             offset: current_offset.get(world),
+                            moved,
                         },
                     );
                 }),
                 On::<Pointer<Drag>>::run({
-                    let on_change = self.on_change;
+                    let limits = limits.clone();
                     move |world: &mut World| {
-                        let event = world
-                            .get_resource::<ListenerInput<Pointer<Drag>>>()
+                        let mut event = world
+                            .get_resource_mut::<ListenerInput<Pointer<Drag>>>()
                             .unwrap();
+                        // Don't let touch drags on the bar scroll an enclosing view.
+                        event.stop_propagation();
                         let ev = event.distance;
                         let ds = drag_state.get(world);
                         if let Some(on_change) = on_change {
                             if ds.dragging {
-                                match direction {
-                                    SplitterDirection::Horizontal => {
-                                        world.run_callback(on_change, ds.offset - ev.y);
-                                    },
-                                    SplitterDirection::Vertical => {
-                                        world.run_callback(on_change, ev.x + ds.offset);
-                                    },
-                                }
+                                let value = match direction {
+                                    SplitterDirection::Horizontal => ds.offset - ev.y,
+                                    SplitterDirection::Vertical => ev.x + ds.offset,
+                                };
+                                let value = limits.constrain(value);
+                                // Remember the size before collapsing, so it can be restored.
+                                let restore = match (value, ds.offset) {
+                                    (v, offset) if v == 0. && offset > 0. => Some(offset),
+                                    (v, _) if v == 0. => restore_size.get(world),
+                                    _ => None,
+                                };
+                                drag_state.set(world, DragState { moved: true, ..ds });
+                                restore_size.set(world, restore);
+                                limits.persist(world, value, restore);
+                                world.run_callback(on_change, value);
                             }
                         }
                     }
                 }),
+                On::<Pointer<Click>>::run(move |world: &mut World| {
+                    let Some(on_change) = on_change else {
+                        return;
+                    };
+                    let ds = drag_state.get(world);
+                    if ds.moved {
+                        drag_state.set(world, DragState { moved: false, ..ds });
+                        return;
+                    }
+                    let now = world.resource::<Time>().elapsed_seconds();
+                    let double_click = now - last_click.get(world) < DOUBLE_CLICK_TIME;
+                    last_click.set(world, now);
+
+                    let value = if double_click {
+                        default_value
+                    } else if current_offset.get(world) == 0. {
+                        // Clicking a collapsed splitter restores the pane.
+                        restore_size.get(world)
+                    } else {
+                        None
+                    };
+                    if let Some(value) = value {
+                        let value = value.clamp(limits.min, limits.max);
+                        restore_size.set(world, None);
+                        limits.persist(world, value, None);
+                        world.run_callback(on_change, value);
+                    }
+                }),
                 On::<Pointer<PointerCancel>>::run(move |world: &mut World| {
                     println!("Splitter Cancel");
                     drag_state.set(
@@ -173,6 +330,7 @@ impl ViewTemplate for Splitter {
                         DragState {
                             dragging: false,
                             offset: current_offset.get(world),
+                            moved: false,
                         },
                     );
                 }),
@@ -181,13 +339,18 @@ impl ViewTemplate for Splitter {
                 Element::<NodeBundle>::new()
                     .style(style_splitter_inner)
                     .create_effect(move |cx, ent| {
-                        // Color change on hover / drag
+                        // Color change on hover / drag, highlighted when collapsed so the
+                        // pane can be found and restored.
                         let ds = drag_state.get(cx);
                         let is_hovering = hovering.get(cx);
+                        let base_color = match restore_size.get(cx) {
+                            Some(_) => color_accent.get(cx),
+                            None => colors::U3,
+                        };
                         let color = match (ds.dragging, is_hovering) {
-                            (true, _) => colors::U3.lighter(0.05),
-                            (false, true) => colors::U3.lighter(0.02),
-                            (false, false) => colors::U3,
+                            (true, _) => base_color.lighter(0.05),
+                            (false, true) => base_color.lighter(0.02),
+                            (false, false) => base_color,
                         };
                         let mut bg = cx.world_mut().get_mut::<BackgroundColor>(ent).unwrap();
                         bg.0 = color.into();
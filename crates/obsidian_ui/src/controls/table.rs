@@ -0,0 +1,695 @@
+use std::{ops::Range, sync::Arc};
+
+use bevy::{prelude::*, ui, window::CursorIcon};
+use bevy_mod_picking::prelude::*;
+use bevy_reactor::*;
+
+use crate::{colors, pointer_capture::CapturePointer, scrolling::ScrollArea, typography};
+
+use super::ScrollView;
+
+fn style_table(ss: &mut StyleBuilder) {
+    ss.background_color(colors::U1).border_radius(5.0);
+}
+
+fn style_table_inner(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Column)
+        .align_items(ui::AlignItems::FlexStart);
+}
+
+fn style_table_row(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Row)
+        .align_items(ui::AlignItems::Stretch);
+}
+
+fn style_table_edge_row(ss: &mut StyleBuilder) {
+    ss.position(ui::PositionType::Relative)
+        .z_index(ZIndex::Local(2))
+        .background_color(colors::U2)
+        .color(colors::DIM);
+}
+
+fn style_table_body(ss: &mut StyleBuilder) {
+    ss.position(ui::PositionType::Relative);
+}
+
+fn style_table_body_row(ss: &mut StyleBuilder) {
+    ss.position(ui::PositionType::Absolute).left(0);
+}
+
+fn style_table_cell(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .position(ui::PositionType::Relative)
+        .flex_direction(ui::FlexDirection::Row)
+        .align_items(ui::AlignItems::Center)
+        .flex_shrink(0.)
+        .padding((6, 0))
+        .text_overflow(TextOverflow::Ellipsis);
+}
+
+fn style_table_pinned_cell(ss: &mut StyleBuilder) {
+    ss.z_index(ZIndex::Local(1));
+}
+
+fn style_table_resize_handle(ss: &mut StyleBuilder) {
+    ss.position(ui::PositionType::Absolute)
+        .top(0)
+        .bottom(0)
+        .right(-3)
+        .width(6)
+        .z_index(ZIndex::Local(1))
+        .cursor(CursorIcon::ColResize);
+}
+
+/// Which edge of a [`Table`] a column is pinned to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum ColumnPin {
+    /// The column scrolls with the rest of the table.
+    #[default]
+    None,
+
+    /// The column is displayed before the other columns, and stays at the left edge when the
+    /// table is scrolled horizontally.
+    Left,
+
+    /// The column is displayed after the other columns, and stays at the right edge when the
+    /// table is scrolled horizontally.
+    Right,
+}
+
+/// A column of a [`Table`].
+#[derive(Clone)]
+pub struct TableColumn {
+    /// The content of the column's header cell.
+    pub header: ChildArray,
+
+    /// The content of the column's footer cell. The footer row is only shown when at least
+    /// one column has footer content.
+    pub footer: ChildArray,
+
+    /// The initial width of the column, in pixels.
+    pub width: f32,
+
+    /// The minimum width the column can be resized to, in pixels.
+    pub min_width: f32,
+
+    /// Which edge of the table the column is pinned to, if any.
+    pub pin: ColumnPin,
+
+    /// Whether the column can be resized by dragging the right edge of its header.
+    pub resizable: bool,
+}
+
+impl Default for TableColumn {
+    fn default() -> Self {
+        Self {
+            header: ChildArray::default(),
+            footer: ChildArray::default(),
+            width: 100.,
+            min_width: 24.,
+            pin: ColumnPin::None,
+            resizable: true,
+        }
+    }
+}
+
+impl TableColumn {
+    /// Create a new column with the given header content.
+    pub fn new<V: ChildViewTuple>(header: V) -> Self {
+        Self {
+            header: header.to_child_array(),
+            ..default()
+        }
+    }
+
+    /// Set the content of the column's footer cell.
+    pub fn footer<V: ChildViewTuple>(mut self, footer: V) -> Self {
+        self.footer = footer.to_child_array();
+        self
+    }
+
+    /// Set the initial width of the column.
+    pub fn width(mut self, width: f32) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Set the minimum width the column can be resized to.
+    pub fn min_width(mut self, min_width: f32) -> Self {
+        self.min_width = min_width;
+        self
+    }
+
+    /// Pin the column to the left or right edge of the table.
+    pub fn pin(mut self, pin: ColumnPin) -> Self {
+        self.pin = pin;
+        self
+    }
+
+    /// Set whether the column can be resized (default true).
+    pub fn resizable(mut self, resizable: bool) -> Self {
+        self.resizable = resizable;
+        self
+    }
+}
+
+/// Which edge of the visible area a pinned table cell or row is held at.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum PinEdge {
+    Left,
+    Right,
+    Top,
+    Bottom,
+}
+
+/// Component which offsets a pinned cell or row of a table so that it stays at an edge of
+/// the scroll area while the rest of the table scrolls.
+#[derive(Component, Clone, Copy)]
+pub(crate) struct TablePin {
+    /// The scroll area which the table is displayed in.
+    scroll_area: Entity,
+
+    /// The edge the cell or row is held at.
+    edge: PinEdge,
+}
+
+/// A scrollable grid of cells, with a header row and optional footer row. Only the rows which
+/// are within view are built, so tables can display large data sets. Columns can be pinned to
+/// the left or right edge, and the header and footer rows to the top and bottom, so that they
+/// stay in view while the rest of the table scrolls.
+pub struct Table {
+    /// The columns of the table.
+    pub columns: Vec<TableColumn>,
+
+    /// The number of rows in the table.
+    pub row_count: Signal<usize>,
+
+    /// Function which creates the view for a cell, given the row index and the index of the
+    /// column in `columns`.
+    pub cell: Arc<dyn Fn(usize, usize) -> ViewRef + Send + Sync>,
+
+    /// The height of each row, in pixels. Rows have a fixed height so that the rows in view
+    /// can be found without building the others.
+    pub row_height: f32,
+
+    /// The number of rows to build above and below the ones in view, so that scrolling
+    /// doesn't reveal rows before they are built.
+    pub overscan: usize,
+
+    /// Whether the header row stays at the top when the table is scrolled (default true).
+    pub pin_header: bool,
+
+    /// Whether the footer row stays at the bottom when the table is scrolled (default true).
+    pub pin_footer: bool,
+
+    /// Callback called when the user has finished resizing a column, with the index of the
+    /// column and its new width.
+    pub on_resize: Option<Callback<(usize, f32)>>,
+
+    /// Additional styles to be applied to the table.
+    pub style: StyleHandle,
+}
+
+impl Table {
+    /// Create a new table.
+    ///
+    /// # Arguments
+    /// * `cell` - Function which creates the view for a cell, given the row index and the
+    ///   index of the column.
+    pub fn new<V: IntoView>(cell: impl Fn(usize, usize) -> V + Send + Sync + 'static) -> Self {
+        Self {
+            columns: Vec::new(),
+            row_count: Signal::Constant(0),
+            cell: Arc::new(move |row, column| cell(row, column).into_view()),
+            row_height: 24.,
+            overscan: 4,
+            pin_header: true,
+            pin_footer: true,
+            on_resize: None,
+            style: StyleHandle::default(),
+        }
+    }
+
+    /// Add a column to the table.
+    pub fn column(mut self, column: TableColumn) -> Self {
+        self.columns.push(column);
+        self
+    }
+
+    /// Set the number of rows in the table.
+    pub fn row_count(mut self, row_count: impl IntoSignal<usize>) -> Self {
+        self.row_count = row_count.into_signal();
+        self
+    }
+
+    /// Set the height of each row.
+    pub fn row_height(mut self, row_height: f32) -> Self {
+        self.row_height = row_height;
+        self
+    }
+
+    /// Set the number of rows to build beyond the ones in view.
+    pub fn overscan(mut self, overscan: usize) -> Self {
+        self.overscan = overscan;
+        self
+    }
+
+    /// Set whether the header row stays at the top when the table is scrolled.
+    pub fn pin_header(mut self, pin_header: bool) -> Self {
+        self.pin_header = pin_header;
+        self
+    }
+
+    /// Set whether the footer row stays at the bottom when the table is scrolled.
+    pub fn pin_footer(mut self, pin_footer: bool) -> Self {
+        self.pin_footer = pin_footer;
+        self
+    }
+
+    /// Set the callback called when a column has been resized.
+    pub fn on_resize(mut self, on_resize: Callback<(usize, f32)>) -> Self {
+        self.on_resize = Some(on_resize);
+        self
+    }
+
+    /// Set additional styles to be applied to the table.
+    pub fn style<S: StyleTuple + 'static>(mut self, style: S) -> Self {
+        self.style = style.into_handle();
+        self
+    }
+}
+
+impl ViewTemplate for Table {
+    fn create(&self, cx: &mut Cx) -> impl IntoView {
+        let id_scroll_area = cx.create_entity();
+        let id_header = cx.create_entity();
+        let widths = cx.create_mutable::<Vec<f32>>(self.columns.iter().map(|c| c.width).collect());
+        // Width of the column being resized, when the drag started.
+        let start_width = cx.create_mutable::<f32>(0.);
+        let order: Arc<[usize]> = display_order(&self.columns).into();
+        let pins: Arc<[ColumnPin]> = self.columns.iter().map(|c| c.pin).collect();
+        let row_count = self.row_count;
+        let row_height = self.row_height;
+        let overscan = self.overscan;
+
+        // The rows in view, measured below the header.
+        let visible = cx.create_memo(move |cx| {
+            let count = row_count.get(cx);
+            let header_height = cx
+                .use_component::<Node>(id_header)
+                .map_or(0., |node| node.size().y);
+            match cx.use_component::<ScrollArea>(id_scroll_area) {
+                Some(area) => visible_rows(
+                    area.scroll_top - header_height,
+                    area.visible_size.y,
+                    row_height,
+                    count,
+                    overscan,
+                ),
+                None => visible_rows(0., 0., row_height, count, overscan),
+            }
+        });
+
+        let cell = self.cell.clone();
+        let row = {
+            let order = order.clone();
+            let pins = pins.clone();
+            move |row: &usize| {
+                let row = *row;
+                Element::<NodeBundle>::new()
+                    .named("Table::Row")
+                    .style((
+                        style_table_row,
+                        style_table_body_row,
+                        move |ss: &mut StyleBuilder| {
+                            ss.top(row as f32 * row_height).height(row_height);
+                        },
+                    ))
+                    .children(
+                        order
+                            .iter()
+                            .map(|column| {
+                                table_cell(
+                                    id_scroll_area,
+                                    widths,
+                                    *column,
+                                    pins[*column],
+                                    colors::U1,
+                                    (cell)(row, *column),
+                                )
+                            })
+                            .collect::<Vec<_>>(),
+                    )
+            }
+        };
+
+        let header = Element::<NodeBundle>::for_entity(id_header)
+            .named("Table::Header")
+            .style((
+                typography::text_default,
+                style_table_row,
+                style_table_edge_row,
+                move |ss: &mut StyleBuilder| {
+                    ss.min_height(row_height);
+                },
+            ))
+            .insert_if(
+                self.pin_header,
+                TablePin {
+                    scroll_area: id_scroll_area,
+                    edge: PinEdge::Top,
+                },
+            )
+            .children(
+                order
+                    .iter()
+                    .map(|column| {
+                        let col = &self.columns[*column];
+                        let resize = col.resizable.then(|| {
+                            resize_handle(
+                                widths,
+                                start_width,
+                                *column,
+                                col.min_width,
+                                self.on_resize,
+                            )
+                        });
+                        table_cell(
+                            id_scroll_area,
+                            widths,
+                            *column,
+                            col.pin,
+                            colors::U2,
+                            (col.header.clone(), resize).to_child_array(),
+                        )
+                    })
+                    .collect::<Vec<_>>(),
+            );
+
+        let has_footer = self.columns.iter().any(|c| !c.footer.is_empty());
+        let footer = has_footer.then(|| {
+            Element::<NodeBundle>::new()
+                .named("Table::Footer")
+                .style((
+                    typography::text_default,
+                    style_table_row,
+                    style_table_edge_row,
+                    move |ss: &mut StyleBuilder| {
+                        ss.min_height(row_height);
+                    },
+                ))
+                .insert_if(
+                    self.pin_footer,
+                    TablePin {
+                        scroll_area: id_scroll_area,
+                        edge: PinEdge::Bottom,
+                    },
+                )
+                .children(
+                    order
+                        .iter()
+                        .map(|column| {
+                            table_cell(
+                                id_scroll_area,
+                                widths,
+                                *column,
+                                pins[*column],
+                                colors::U2,
+                                self.columns[*column].footer.clone(),
+                            )
+                        })
+                        .collect::<Vec<_>>(),
+                )
+        });
+
+        ScrollView::new()
+            .scroll_area_id(id_scroll_area)
+            .children(
+                Element::<NodeBundle>::new()
+                    .named("Table")
+                    .style((typography::text_default, style_table_inner))
+                    .children((
+                        header,
+                        Element::<NodeBundle>::new()
+                            .named("Table::Body")
+                            .style(style_table_body)
+                            .create_effect(move |cx, ent| {
+                                // Rows are positioned absolutely, so the body is sized to
+                                // hold all of them.
+                                let width: f32 = widths.get_clone(cx).iter().sum();
+                                let height = row_count.get(cx) as f32 * row_height;
+                                let mut style = cx.world_mut().get_mut::<Style>(ent).unwrap();
+                                style.width = ui::Val::Px(width);
+                                style.height = ui::Val::Px(height);
+                            })
+                            .children(For::each(move |cx| visible.get_clone(cx), row)),
+                        footer,
+                    )),
+            )
+            .style((style_table, self.style.clone()))
+            .scroll_enable_x(true)
+            .scroll_enable_y(true)
+    }
+}
+
+/// A cell of a table, sized to the width of its column and pinned if the column is.
+fn table_cell(
+    scroll_area: Entity,
+    widths: Mutable<Vec<f32>>,
+    column: usize,
+    pin: ColumnPin,
+    background: Srgba,
+    children: impl ChildViewTuple,
+) -> ViewRef {
+    let edge = match pin {
+        ColumnPin::None => None,
+        ColumnPin::Left => Some(PinEdge::Left),
+        ColumnPin::Right => Some(PinEdge::Right),
+    };
+    Element::<NodeBundle>::new()
+        .named("Table::Cell")
+        .style((style_table_cell, move |ss: &mut StyleBuilder| {
+            if edge.is_some() {
+                style_table_pinned_cell(ss);
+                ss.background_color(background);
+            }
+        }))
+        .insert_if(
+            edge.is_some(),
+            TablePin {
+                scroll_area,
+                edge: edge.unwrap_or(PinEdge::Left),
+            },
+        )
+        .create_effect(move |cx, ent| {
+            let width = widths.as_ref(cx)[column];
+            let mut style = cx.world_mut().get_mut::<Style>(ent).unwrap();
+            style.width = ui::Val::Px(width);
+        })
+        .children(children)
+        .into_view()
+}
+
+/// A handle at the right edge of a header cell, which resizes the column when dragged.
+fn resize_handle(
+    widths: Mutable<Vec<f32>>,
+    start_width: Mutable<f32>,
+    column: usize,
+    min_width: f32,
+    on_resize: Option<Callback<(usize, f32)>>,
+) -> impl IntoView {
+    Element::<NodeBundle>::new()
+        .named("Table::ResizeHandle")
+        .style(style_table_resize_handle)
+        .insert((
+            CapturePointer,
+            On::<Pointer<DragStart>>::run(move |world: &mut World| {
+                // Save the initial width to use as the drag offset.
+                let width = widths.get_clone(world)[column];
+                start_width.set(world, width);
+            }),
+            On::<Pointer<Drag>>::run(move |world: &mut World| {
+                let mut event = world
+                    .get_resource_mut::<ListenerInput<Pointer<Drag>>>()
+                    .unwrap();
+                // Don't let the drag scroll the table.
+                event.stop_propagation();
+                let distance = event.distance.x;
+                let width = (start_width.get(world) + distance).max(min_width);
+                widths.update(world, |mut widths| widths[column] = width);
+            }),
+            On::<Pointer<DragEnd>>::run(move |world: &mut World| {
+                if let Some(on_resize) = on_resize {
+                    let width = widths.get_clone(world)[column];
+                    world.run_callback(on_resize, (column, width));
+                }
+            }),
+        ))
+}
+
+/// The order in which columns are displayed: columns pinned to the left first, then the
+/// unpinned columns, then the columns pinned to the right, otherwise keeping their order.
+fn display_order(columns: &[TableColumn]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..columns.len()).collect();
+    order.sort_by_key(|index| match columns[*index].pin {
+        ColumnPin::Left => 0,
+        ColumnPin::None => 1,
+        ColumnPin::Right => 2,
+    });
+    order
+}
+
+/// The range of rows to build, given the top of the visible area relative to the first row,
+/// the height of the visible area and the number of extra rows to build on each side.
+fn visible_rows(
+    top: f32,
+    height: f32,
+    row_height: f32,
+    count: usize,
+    overscan: usize,
+) -> Range<usize> {
+    if row_height <= 0. {
+        return 0..count;
+    }
+    let first = (top / row_height).floor().max(0.) as usize;
+    let last = ((top + height) / row_height).ceil().max(0.) as usize;
+    first.saturating_sub(overscan).min(count)..(last + overscan).min(count)
+}
+
+/// The offset which holds a pinned cell or row at an edge of the visible area. Cells pinned to
+/// the left and the header are displayed at the start of the content, so they move with the
+/// scroll position; cells pinned to the right and the footer are displayed at the end, so
+/// they move back by the remaining scroll range.
+fn pin_offset(edge: PinEdge, area: &ScrollArea) -> f32 {
+    match edge {
+        PinEdge::Left => area.scroll_left,
+        PinEdge::Top => area.scroll_top,
+        PinEdge::Right => area.scroll_left - (area.content_size.x - area.visible_size.x).max(0.),
+        PinEdge::Bottom => area.scroll_top - (area.content_size.y - area.visible_size.y).max(0.),
+    }
+}
+
+/// Offset the pinned cells and rows of each table, so that they stay at the edges of the
+/// visible area while the rest of the table scrolls.
+pub(crate) fn update_table_pins(
+    mut query_pins: Query<(&TablePin, &mut Style)>,
+    query_scroll_area: Query<&ScrollArea>,
+) {
+    for (pin, mut style) in query_pins.iter_mut() {
+        let Ok(area) = query_scroll_area.get(pin.scroll_area) else {
+            continue;
+        };
+        let offset = ui::Val::Px(pin_offset(pin.edge, area));
+        match pin.edge {
+            PinEdge::Left | PinEdge::Right => {
+                if style.left != offset {
+                    style.left = offset;
+                }
+            }
+            PinEdge::Top | PinEdge::Bottom => {
+                if style.top != offset {
+                    style.top = offset;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::RunSystemOnce;
+
+    use super::*;
+
+    fn scroll_area(content: Vec2, visible: Vec2, scroll: Vec2) -> ScrollArea {
+        ScrollArea {
+            scroll_left: scroll.x,
+            scroll_top: scroll.y,
+            content_size: content,
+            visible_size: visible,
+            id_scrollbar_x: None,
+            id_scrollbar_y: None,
+        }
+    }
+
+    #[test]
+    fn test_display_order() {
+        let columns = [
+            TableColumn::new("a"),
+            TableColumn::new("b").pin(ColumnPin::Right),
+            TableColumn::new("c").pin(ColumnPin::Left),
+            TableColumn::new("d"),
+            TableColumn::new("e").pin(ColumnPin::Left),
+        ];
+        assert_eq!(display_order(&columns), [2, 4, 0, 3, 1]);
+    }
+
+    #[test]
+    fn test_visible_rows() {
+        // Before the layout has measured the table, only the overscan is built.
+        assert_eq!(visible_rows(0., 0., 20., 1000, 4), 0..4);
+        assert_eq!(visible_rows(0., 100., 20., 1000, 4), 0..9);
+        assert_eq!(visible_rows(410., 100., 20., 1000, 4), 16..30);
+        assert_eq!(visible_rows(19_950., 100., 20., 1000, 4), 993..1000);
+        // The header is above the first row.
+        assert_eq!(visible_rows(-24., 100., 20., 1000, 0), 0..4);
+        assert_eq!(visible_rows(0., 100., 20., 0, 4), 0..0);
+    }
+
+    #[test]
+    fn test_pin_offset() {
+        let area = scroll_area(
+            Vec2::new(800., 2000.),
+            Vec2::new(300., 200.),
+            Vec2::new(100., 500.),
+        );
+        assert_eq!(pin_offset(PinEdge::Left, &area), 100.);
+        assert_eq!(pin_offset(PinEdge::Right, &area), -400.);
+        assert_eq!(pin_offset(PinEdge::Top, &area), 500.);
+        assert_eq!(pin_offset(PinEdge::Bottom, &area), -1300.);
+
+        // Content which fits doesn't need to be moved.
+        let area = scroll_area(Vec2::new(200., 100.), Vec2::new(300., 200.), Vec2::ZERO);
+        assert_eq!(pin_offset(PinEdge::Right, &area), 0.);
+        assert_eq!(pin_offset(PinEdge::Bottom, &area), 0.);
+    }
+
+    #[test]
+    fn test_update_table_pins() {
+        let mut world = World::new();
+        let area = world
+            .spawn(scroll_area(
+                Vec2::new(800., 2000.),
+                Vec2::new(300., 200.),
+                Vec2::new(100., 500.),
+            ))
+            .id();
+        let left = world
+            .spawn((
+                TablePin {
+                    scroll_area: area,
+                    edge: PinEdge::Left,
+                },
+                Style::default(),
+            ))
+            .id();
+        let header = world
+            .spawn((
+                TablePin {
+                    scroll_area: area,
+                    edge: PinEdge::Top,
+                },
+                Style::default(),
+            ))
+            .id();
+        world.run_system_once(update_table_pins);
+        let left = world.get::<Style>(left).unwrap();
+        assert_eq!(left.left, ui::Val::Px(100.));
+        assert_eq!(left.top, ui::Val::Auto);
+        let header = world.get::<Style>(header).unwrap();
+        assert_eq!(header.top, ui::Val::Px(500.));
+        assert_eq!(header.left, ui::Val::Auto);
+    }
+}
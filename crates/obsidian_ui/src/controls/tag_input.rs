@@ -0,0 +1,185 @@
+use bevy::{prelude::*, ui};
+use bevy_reactor::*;
+
+use crate::{
+    colors,
+    controls::{Chip, TextInput, TextInputProps},
+    size::Size,
+};
+
+fn style_tag_input(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Row)
+        .flex_wrap(ui::FlexWrap::Wrap)
+        .align_items(ui::AlignItems::Center)
+        .gap(4)
+        .padding(3)
+        .border_radius(5.0)
+        .background_color(colors::U1);
+}
+
+fn style_tag_input_field(ss: &mut StyleBuilder) {
+    ss.flex_grow(1.).min_width(60);
+}
+
+/// Append the non-empty, trimmed entries in `entries` to `tags`, skipping duplicates.
+fn add_tags<'a>(tags: &mut Vec<String>, entries: impl Iterator<Item = &'a str>) -> bool {
+    let mut changed = false;
+    for entry in entries {
+        let entry = entry.trim();
+        if !entry.is_empty() && !tags.iter().any(|t| t == entry) {
+            tags.push(entry.to_string());
+            changed = true;
+        }
+    }
+    changed
+}
+
+/// A text input which converts entries into chips, for editing a list of tags or filter
+/// terms. Pressing Enter or typing a comma converts the current text into a chip; clicking
+/// the close button on a chip removes it.
+#[derive(Default)]
+pub struct TagInput {
+    /// The current list of tags.
+    pub value: Signal<Vec<String>>,
+
+    /// Widget size.
+    pub size: Size,
+
+    /// Additional styles to be applied to the widget.
+    pub style: StyleHandle,
+
+    /// Callback called when the list of tags changes.
+    pub on_change: Option<Callback<Vec<String>>>,
+}
+
+impl TagInput {
+    /// Create a new tag input.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the current list of tags.
+    pub fn value(mut self, value: impl IntoSignal<Vec<String>>) -> Self {
+        self.value = value.into_signal();
+        self
+    }
+
+    /// Set the widget size.
+    pub fn size(mut self, size: Size) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Set additional styles to be applied to the widget.
+    pub fn style<S: StyleTuple + 'static>(mut self, style: S) -> Self {
+        self.style = style.into_handle();
+        self
+    }
+
+    /// Set the callback called when the list of tags changes.
+    pub fn on_change(mut self, on_change: Callback<Vec<String>>) -> Self {
+        self.on_change = Some(on_change);
+        self
+    }
+}
+
+impl ViewTemplate for TagInput {
+    fn create(&self, cx: &mut Cx) -> impl IntoView {
+        let value = self.value.clone();
+        let on_change = self.on_change;
+        let size = self.size;
+        let draft = cx.create_mutable(String::new());
+
+        let on_draft_change = cx.create_callback({
+            let value = value.clone();
+            move |cx: &mut Cx, text: String| {
+                // Everything before the last comma becomes tags; the rest remains as the draft.
+                match text.rfind(',') {
+                    Some(index) => {
+                        let mut tags = value.get_clone(cx);
+                        if add_tags(&mut tags, text[..index].split(',')) {
+                            if let Some(on_change) = on_change {
+                                cx.run_callback(on_change, tags);
+                            }
+                        }
+                        draft.set_clone(cx, text[index + 1..].to_string());
+                    }
+                    None => draft.set_clone(cx, text),
+                }
+            }
+        });
+
+        let on_submit = cx.create_callback({
+            let value = value.clone();
+            move |cx: &mut Cx, text: String| {
+                let mut tags = value.get_clone(cx);
+                if add_tags(&mut tags, std::iter::once(text.as_str())) {
+                    if let Some(on_change) = on_change {
+                        cx.run_callback(on_change, tags);
+                    }
+                }
+                draft.set_clone(cx, String::new());
+            }
+        });
+
+        Element::<NodeBundle>::new()
+            .named("TagInput")
+            .style((style_tag_input, self.style.clone()))
+            .children((
+                For::each(
+                    {
+                        let value = value.clone();
+                        move |cx| value.get_clone(cx).into_iter()
+                    },
+                    move |tag| TagChip {
+                        tag: tag.clone(),
+                        tags: value.clone(),
+                        size,
+                        on_change,
+                    },
+                ),
+                TextInput::new(TextInputProps {
+                    size,
+                    value: draft.signal(),
+                    style: style_tag_input_field.into_handle(),
+                    on_change: Some(on_draft_change),
+                    on_submit: Some(on_submit),
+                    ..default()
+                }),
+            ))
+    }
+}
+
+struct TagChip {
+    tag: String,
+    tags: Signal<Vec<String>>,
+    size: Size,
+    on_change: Option<Callback<Vec<String>>>,
+}
+
+impl ViewTemplate for TagChip {
+    fn create(&self, cx: &mut Cx) -> impl IntoView {
+        let tag = self.tag.clone();
+        let tags = self.tags.clone();
+        let on_change = self.on_change;
+        let size = match self.size {
+            Size::Xl | Size::Lg | Size::Md => Size::Sm,
+            Size::Sm => Size::Xs,
+            _ => Size::Xxs,
+        };
+
+        let on_remove = cx.create_callback({
+            let tag = tag.clone();
+            move |cx: &mut Cx, _| {
+                let mut tags = tags.get_clone(cx);
+                tags.retain(|t| *t != tag);
+                if let Some(on_change) = on_change {
+                    cx.run_callback(on_change, tags);
+                }
+            }
+        });
+
+        Chip::new(tag).size(size).on_remove(on_remove)
+    }
+}
@@ -1,7 +1,10 @@
 use crate::{
-    focus::{AutoFocus, KeyCharEvent, KeyPressEvent, TabIndex},
-    hooks::CreateFocusSignal,
+    clipboard::{Clipboard, CopyEvent, PasteEvent},
+    focus::{AutoFocus, KeyCharEvent, KeyPressEvent, TabIndex, TextEntry},
+    hooks::{CreateDisabledSignal, CreateFocusSignal, CreateThemeSignal, ThemeToken},
 };
+use std::time::Duration;
+
 use bevy::{
     a11y::{
         accesskit::{NodeBuilder, Role},
@@ -11,11 +14,19 @@ use bevy::{
     prelude::*,
     text::{BreakLineOn, TextLayoutInfo},
     ui,
+    window::CursorIcon,
 };
 use bevy_mod_picking::{events::PointerCancel, prelude::*};
 use bevy_reactor::*;
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::{colors, materials::TextDecorationMaterial, size::Size};
+
+/// How long the caret stays visible (or hidden) during each half of the blink cycle.
+const CARET_BLINK_INTERVAL: Duration = Duration::from_millis(530);
 
-use crate::{colors, size::Size};
+/// Width of the caret, in pixels.
+const CARET_WIDTH: f32 = 2.;
 
 /// Text input properties
 #[derive(Default)]
@@ -35,6 +46,9 @@ pub struct TextInputProps {
     /// Callback called when the text changes.
     pub on_change: Option<Callback<String>>,
 
+    /// Callback called with the current text when the user presses Enter.
+    pub on_submit: Option<Callback<String>>,
+
     /// The tab index of the text input (default 0).
     pub tab_index: i32,
 
@@ -57,6 +71,7 @@ fn style_text_input(ss: &mut StyleBuilder) {
         .align_content(ui::AlignContent::Center)
         .padding((4, 0))
         .border(0)
+        .cursor(CursorIcon::Text)
         .color(colors::FOREGROUND);
 }
 
@@ -86,16 +101,14 @@ fn style_text_inner(ss: &mut StyleBuilder) {
     ss.position(ui::PositionType::Relative).left(0);
 }
 
-fn style_text_cursor(ss: &mut StyleBuilder) {
-    ss.position(ui::PositionType::Absolute)
-        .background_color(colors::TEXT_SELECT)
-        .width(2)
-        .height(16);
-}
-
-fn style_text_selection(ss: &mut StyleBuilder) {
+// Overlay covering the text, which draws the caret and selection. It extends past the end of
+// the text so that the caret is not clipped when it is after the last character.
+fn style_text_decoration(ss: &mut StyleBuilder) {
     ss.position(ui::PositionType::Absolute)
-        .background_color(colors::TEXT_SELECT);
+        .left(0)
+        .top(0)
+        .bottom(0)
+        .right(-CARET_WIDTH);
 }
 
 /// Selection state for a text input.
@@ -140,6 +153,90 @@ impl Selection {
     fn range(&self) -> std::ops::Range<usize> {
         self.start()..self.end()
     }
+
+    /// Clamp both ends of the selection to `text`, and move them back onto grapheme cluster
+    /// boundaries. Needed when the text is changed by something other than this widget.
+    fn snap(&self, text: &str) -> Self {
+        Self::new(
+            snap_grapheme(text, self.cursor),
+            snap_grapheme(text, self.anchor),
+        )
+    }
+}
+
+// Cursor positions are byte offsets into the text, and are always kept on grapheme cluster
+// boundaries so that editing never splits a multi-byte character.
+
+/// Returns the last grapheme cluster boundary at or before `pos`, which may be past the end of
+/// the text or in the middle of a character.
+fn snap_grapheme(text: &str, pos: usize) -> usize {
+    if pos >= text.len() {
+        return text.len();
+    }
+    text.grapheme_indices(true)
+        .map(|(index, _)| index)
+        .take_while(|index| *index <= pos)
+        .last()
+        .unwrap_or(0)
+}
+
+/// Returns the position of the grapheme cluster boundary preceding `pos`.
+fn prev_grapheme(text: &str, pos: usize) -> usize {
+    text[..pos]
+        .grapheme_indices(true)
+        .next_back()
+        .map_or(0, |(index, _)| index)
+}
+
+/// Returns the position of the grapheme cluster boundary following `pos`.
+fn next_grapheme(text: &str, pos: usize) -> usize {
+    text[pos..]
+        .graphemes(true)
+        .next()
+        .map_or(pos, |grapheme| pos + grapheme.len())
+}
+
+/// Whether a grapheme cluster is part of a word for the purpose of word-wise navigation.
+fn is_word_grapheme(grapheme: &str) -> bool {
+    grapheme
+        .chars()
+        .next()
+        .map_or(false, |ch| ch.is_alphanumeric() || ch == '_')
+}
+
+/// Returns the start of the word preceding `pos`, skipping any whitespace or punctuation
+/// in between.
+fn prev_word(text: &str, pos: usize) -> usize {
+    let mut in_word = false;
+    for (index, grapheme) in text[..pos].grapheme_indices(true).rev() {
+        if is_word_grapheme(grapheme) {
+            in_word = true;
+        } else if in_word {
+            return index + grapheme.len();
+        }
+    }
+    0
+}
+
+/// Returns the end of the word following `pos`, skipping any whitespace or punctuation
+/// in between.
+fn next_word(text: &str, pos: usize) -> usize {
+    let mut in_word = false;
+    for (index, grapheme) in text[pos..].grapheme_indices(true) {
+        if is_word_grapheme(grapheme) {
+            in_word = true;
+        } else if in_word {
+            return pos + index;
+        }
+    }
+    text.len()
+}
+
+/// Converts a byte offset into an index into the laid-out glyphs, which are one per `char`.
+fn glyph_index(text: &str, pos: usize) -> usize {
+    text.char_indices()
+        .take_while(|(index, _)| *index < pos)
+        .count()
 }
 
 /// Text input field.
@@ -161,7 +258,9 @@ impl ViewTemplate for TextInput {
         let focused = cx.create_focus_signal(id);
         let selection = cx.create_mutable::<Selection>(Selection::default());
 
-        let disabled = self.0.disabled;
+        let disabled = cx.create_disabled_signal(self.0.disabled);
+        let color_foreground = cx.create_theme_color(ThemeToken::Foreground);
+        let color_focus = cx.create_theme_color(ThemeToken::Focus);
 
         let size = self.0.size;
 
@@ -170,31 +269,89 @@ impl ViewTemplate for TextInput {
 
         let value = self.0.value.clone();
 
-        // Derived signal that computes the selection rectangles.
-        let selection_rects = cx.create_derived(move |cx| {
-            let selection = selection.get(cx);
-            let mut rects = Vec::<Rect>::new();
-            if !focused.get(cx) {
-                return rects;
+        // Snap the selection if the text is changed from outside, e.g. cleared by the owner.
+        cx.create_effect({
+            let value = value.clone();
+            move |cx| {
+                let sel = selection.get(cx);
+                let snapped = value.map(cx, |text| sel.snap(text));
+                if snapped != sel {
+                    selection.set(cx, snapped);
+                }
+            }
+        });
+
+        // Derived signal that computes the selection rectangle.
+        let selection_rect = cx.create_derived({
+            let value = value.clone();
+            move |cx| {
+                let selection = selection.get(cx);
+                if !focused.get(cx) || selection.is_empty() {
+                    return None;
+                }
+                let (start, end) = value.map(cx, |text| {
+                    (
+                        glyph_index(text, selection.start()),
+                        glyph_index(text, selection.end()),
+                    )
+                });
+                let text_layout = cx.use_component::<TextLayoutInfo>(text_id)?;
+                let start_glyph = text_layout.glyphs.get(start)?;
+                let end_glyph = text_layout.glyphs.get(end - 1)?;
+                Some(Rect::new(
+                    start_glyph.position.x * 0.5,
+                    (start_glyph.position.y - start_glyph.size.y)
+                        .min(end_glyph.position.y - end_glyph.size.y),
+                    end_glyph.position.x * 0.5 + end_glyph.size.x,
+                    start_glyph.position.y.max(end_glyph.position.y),
+                ))
             }
-            if let Some(text_layout) = cx.use_component::<TextLayoutInfo>(text_id) {
-                if !selection.is_empty() {
-                    let start = selection.start();
-                    let end = selection.end();
-                    let start_glyph = &text_layout.glyphs[start];
-                    let end_glyph = &text_layout.glyphs[end - 1];
-                    rects.push(Rect::new(
-                        start_glyph.position.x * 0.5,
-                        (start_glyph.position.y - start_glyph.size.y)
-                            .min(end_glyph.position.y - end_glyph.size.y),
-                        end_glyph.position.x * 0.5 + end_glyph.size.x,
-                        start_glyph.position.y.max(end_glyph.position.y),
-                    ));
+        });
+
+        // Derived signal that computes the caret rectangle from the glyph layout.
+        let caret_rect = cx.create_derived({
+            let value = value.clone();
+            move |cx| {
+                let cursor = selection.get(cx).cursor;
+                let index = value.map(cx, |text| glyph_index(text, cursor));
+                let mut pos: Vec2 = Vec2::default();
+                let height: f32;
+                let text_layout = cx.use_component::<TextLayoutInfo>(text_id);
+                let glyphs = text_layout.map_or(&[][..], |layout| &layout.glyphs[..]);
+                if let Some(glyph) = glyphs.get(index) {
+                    pos.x = glyph.position.x;
+                    pos.y = glyph.position.y;
+                    height = glyph.size.y;
+                } else if let Some(glyph) = glyphs.last() {
+                    pos.x = glyph.position.x + glyph.size.x;
+                    pos.y = glyph.position.y;
+                    height = glyph.size.y;
+                } else {
+                    // Empty text.
+                    height = 16.;
+                    pos.y = height;
                 }
+                Rect::new(
+                    pos.x * 0.5,
+                    pos.y - height,
+                    pos.x * 0.5 + CARET_WIDTH,
+                    pos.y,
+                )
             }
-            rects
         });
 
+        let blink = cx.create_interval(CARET_BLINK_INTERVAL);
+        let material = cx
+            .world_mut()
+            .get_resource_mut::<Assets<TextDecorationMaterial>>()
+            .unwrap()
+            .add(TextDecorationMaterial {
+                selection: Vec4::ZERO,
+                selection_color: LinearRgba::from(colors::TEXT_SELECT).to_vec4(),
+                caret: Vec4::ZERO,
+                caret_color: LinearRgba::from(colors::TEXT_SELECT).to_vec4(),
+            });
+
         Element::<NodeBundle>::for_entity(id)
             .named("text_input")
             .style((
@@ -206,6 +363,7 @@ impl ViewTemplate for TextInput {
             ))
             .insert((
                 TabIndex(self.0.tab_index),
+                TextEntry,
                 AccessibilityNode::from(NodeBuilder::new(Role::TextInput)),
                 {
                     // let on_click = self.0.on_click;
@@ -241,13 +399,13 @@ impl ViewTemplate for TextInput {
                     move |world: &mut World| {
                         if !disabled.get(world) {
                             let mut text_value = value.get_clone(world);
-                            let sel = selection.get(world);
+                            let sel = selection.get(world).snap(&text_value);
                             let mut event = world
                                 .get_resource_mut::<ListenerInput<KeyCharEvent>>()
                                 .unwrap();
                             if !event.key.is_control() {
                                 text_value.replace_range(sel.range(), &event.key.to_string());
-                                let new_cursor_pos = sel.start() + 1;
+                                let new_cursor_pos = sel.start() + event.key.len_utf8();
                                 event.stop_propagation();
                                 if let Some(on_change) = on_change {
                                     world.run_callback(on_change, text_value);
@@ -259,40 +417,50 @@ impl ViewTemplate for TextInput {
                 }),
                 On::<KeyPressEvent>::run({
                     let on_change = self.0.on_change;
+                    let on_submit = self.0.on_submit;
                     let value = value.clone();
                     move |world: &mut World| {
                         if !disabled.get(world) {
-                            let text_len = value.map(world, |v| v.len());
-                            let sel = selection.get(world);
+                            let text = value.get_clone(world);
+                            let sel = selection.get(world).snap(&text);
                             let event = world
-                                .get_resource_mut::<ListenerInput<KeyPressEvent>>()
+                                .get_resource::<ListenerInput<KeyPressEvent>>()
                                 .unwrap();
+                            let key_code = event.key_code;
+                            let shift = event.shift;
+                            let ctrl = event.ctrl;
+
+                            // Move the cursor, extending the selection if shift is held.
+                            let move_to = |pos: usize| {
+                                if shift {
+                                    Selection::new(pos, sel.anchor)
+                                } else {
+                                    Selection::single(pos)
+                                }
+                            };
+
                             let mut handled = false;
-                            match event.key_code {
+                            match key_code {
                                 KeyCode::ArrowLeft => {
                                     if sel.cursor > 0 {
-                                        if event.shift {
-                                            selection.set(
-                                                world,
-                                                Selection::new(sel.cursor - 1, sel.anchor),
-                                            );
+                                        let pos = if ctrl {
+                                            prev_word(&text, sel.cursor)
                                         } else {
-                                            selection.set(world, Selection::single(sel.cursor - 1));
-                                        }
+                                            prev_grapheme(&text, sel.cursor)
+                                        };
+                                        selection.set(world, move_to(pos));
                                         handled = true;
                                     }
                                 }
 
                                 KeyCode::ArrowRight => {
-                                    if sel.cursor < text_len {
-                                        if event.shift {
-                                            selection.set(
-                                                world,
-                                                Selection::new(sel.cursor + 1, sel.anchor),
-                                            );
+                                    if sel.cursor < text.len() {
+                                        let pos = if ctrl {
+                                            next_word(&text, sel.cursor)
                                         } else {
-                                            selection.set(world, Selection::single(sel.cursor + 1));
-                                        }
+                                            next_grapheme(&text, sel.cursor)
+                                        };
+                                        selection.set(world, move_to(pos));
                                         handled = true;
                                     }
                                 }
@@ -309,57 +477,52 @@ impl ViewTemplate for TextInput {
 
                                 KeyCode::Home => {
                                     if sel.cursor > 0 {
-                                        selection.set(world, Selection::single(0));
+                                        selection.set(world, move_to(0));
                                         handled = true;
                                     }
                                 }
 
                                 KeyCode::End => {
-                                    if sel.cursor < text_len {
-                                        selection.set(world, Selection::single(text_len));
+                                    if sel.cursor < text.len() {
+                                        selection.set(world, move_to(text.len()));
                                         handled = true;
                                     }
                                 }
 
-                                KeyCode::Backspace => {
-                                    let mut new_text = value.get_clone(world);
-                                    if sel.is_empty() {
-                                        if sel.cursor > 0 {
-                                            new_text.remove(sel.cursor - 1);
-                                            if let Some(on_change) = on_change {
-                                                world.run_callback(on_change, new_text);
-                                            }
-                                            selection.set(world, Selection::single(sel.cursor - 1));
-                                        }
+                                KeyCode::Backspace | KeyCode::Delete => {
+                                    let range = if !sel.is_empty() {
+                                        sel.range()
+                                    } else if key_code == KeyCode::Backspace {
+                                        let start = if ctrl {
+                                            prev_word(&text, sel.cursor)
+                                        } else {
+                                            prev_grapheme(&text, sel.cursor)
+                                        };
+                                        start..sel.cursor
                                     } else {
-                                        new_text.replace_range(sel.range(), "");
+                                        let end = if ctrl {
+                                            next_word(&text, sel.cursor)
+                                        } else {
+                                            next_grapheme(&text, sel.cursor)
+                                        };
+                                        sel.cursor..end
+                                    };
+                                    if !range.is_empty() {
+                                        let mut new_text = text;
+                                        new_text.replace_range(range.clone(), "");
                                         if let Some(on_change) = on_change {
                                             world.run_callback(on_change, new_text);
                                         }
-                                        selection.set(world, Selection::single(sel.start()));
+                                        selection.set(world, Selection::single(range.start));
                                     }
                                     handled = true;
                                 }
 
-                                KeyCode::Delete => {
-                                    let mut new_text = value.get_clone(world);
-                                    if sel.is_empty() {
-                                        if sel.cursor < new_text.len() {
-                                            new_text.remove(sel.cursor);
-                                            if let Some(on_change) = on_change {
-                                                world.run_callback(on_change, new_text);
-                                            }
-                                            selection.set(world, Selection::single(sel.cursor));
-                                        }
-                                    } else {
-                                        let mut new_text = value.get_clone(world);
-                                        new_text.replace_range(sel.range(), "");
-                                        if let Some(on_change) = on_change {
-                                            world.run_callback(on_change, new_text);
-                                        }
-                                        selection.set(world, Selection::single(sel.start()));
+                                KeyCode::Enter | KeyCode::NumpadEnter => {
+                                    if let Some(on_submit) = on_submit {
+                                        world.run_callback(on_submit, text);
+                                        handled = true;
                                     }
-                                    handled = true;
                                 }
                                 _ => {}
                             }
@@ -373,6 +536,47 @@ impl ViewTemplate for TextInput {
                         }
                     }
                 }),
+                On::<CopyEvent>::run({
+                    let value = value.clone();
+                    move |world: &mut World| {
+                        let sel = selection.get(world);
+                        let text =
+                            value.map(world, |text| text[sel.snap(text).range()].to_string());
+                        if !text.is_empty() {
+                            world.resource_mut::<Clipboard>().set_text(text);
+                        }
+                        // Consume the event even if nothing is selected, so that it doesn't
+                        // copy whatever the enclosing widgets have selected.
+                        world
+                            .resource_mut::<ListenerInput<CopyEvent>>()
+                            .stop_propagation();
+                    }
+                }),
+                On::<PasteEvent>::run({
+                    let on_change = self.0.on_change;
+                    let value = value.clone();
+                    move |world: &mut World| {
+                        world
+                            .resource_mut::<ListenerInput<PasteEvent>>()
+                            .stop_propagation();
+                        if disabled.get(world) {
+                            return;
+                        }
+                        let Some(pasted) = world.resource::<Clipboard>().text() else {
+                            return;
+                        };
+                        // The input is a single line, so line breaks and other control
+                        // characters are dropped.
+                        let pasted: String = pasted.chars().filter(|ch| !ch.is_control()).collect();
+                        let mut text_value = value.get_clone(world);
+                        let sel = selection.get(world).snap(&text_value);
+                        text_value.replace_range(sel.range(), &pasted);
+                        if let Some(on_change) = on_change {
+                            world.run_callback(on_change, text_value);
+                            selection.set(world, Selection::single(sel.start() + pasted.len()));
+                        }
+                    }
+                }),
             ))
             .insert_if(self.0.autofocus, AutoFocus)
             .children((
@@ -392,12 +596,13 @@ impl ViewTemplate for TextInput {
                     })
                     .create_effect(move |cx, entt| {
                         let is_focused = focused.get(cx);
+                        let focus = color_focus.get(cx);
                         let mut entt = cx.world_mut().entity_mut(entt);
                         // TODO: Don't do this as an outline, do it as an inset border.
                         match is_focused {
                             true => {
                                 entt.insert(Outline {
-                                    color: colors::FOCUS.into(),
+                                    color: focus.into(),
                                     offset: ui::Val::Px(2.0),
                                     width: ui::Val::Px(2.0),
                                 });
@@ -416,65 +621,74 @@ impl ViewTemplate for TextInput {
                         Element::<NodeBundle>::new()
                             .style(style_text_inner)
                             .children((
-                                // Selection rects
-                                For::index(
-                                    move |cx| selection_rects.get_clone(cx).into_iter(),
-                                    move |rect, _| {
-                                        Element::<NodeBundle>::new().style((
-                                            style_text_selection,
+                                // Caret and selection. These are drawn by a material rather
+                                // than as child nodes, so that editing doesn't change the
+                                // node hierarchy.
+                                Element::<MaterialNodeBundle<TextDecorationMaterial>>::new()
+                                    .named("text_input::decoration")
+                                    .style(style_text_decoration)
+                                    .insert(material.clone())
+                                    .create_effect({
+                                        let material = material.clone();
+                                        move |cx, _| {
+                                            let rect = selection_rect.get(cx);
+                                            let mut ui_materials = cx
+                                                .world_mut()
+                                                .get_resource_mut::<Assets<TextDecorationMaterial>>(
+                                                )
+                                                .unwrap();
+                                            let material =
+                                                ui_materials.get_mut(material.id()).unwrap();
+                                            material.selection = rect.map_or(Vec4::ZERO, |rect| {
+                                                Vec4::new(
+                                                    rect.min.x, rect.min.y, rect.max.x, rect.max.y,
+                                                )
+                                            });
+                                        }
+                                    })
+                                    .create_effect({
+                                        let material = material.clone();
+                                        // The selection, and blink count, when the caret was
+                                        // last moved or shown. Moving the caret restarts the
+                                        // blink cycle.
+                                        let mut blink_start: Option<(Selection, u32)> = None;
+                                        move |cx, _| {
+                                            let sel = selection.get(cx);
+                                            let mut caret = Vec4::ZERO;
+                                            if sel.is_empty()
+                                                && focused.get(cx)
+                                                && !disabled.get(cx)
                                             {
-                                                let rect = *rect;
-                                                move |ss: &mut StyleBuilder| {
-                                                    ss.left(ui::Val::Px(rect.min.x))
-                                                        .top(ui::Val::Px(rect.min.y))
-                                                        .width(ui::Val::Px(rect.width()))
-                                                        .height(ui::Val::Px(rect.height()));
-                                                }
-                                            },
-                                        ))
-                                    },
-                                ),
-                                // Caret
-                                Cond::new(
-                                    move |cx| {
-                                        selection.get(cx).is_empty()
-                                            && focused.get(cx)
-                                            && !disabled.get(cx)
-                                    },
-                                    // React to changes in glyph layout.
-                                    move || {
-                                        Element::<NodeBundle>::new()
-                                            .style(style_text_cursor)
-                                            .create_effect(move |cx, el| {
-                                                let index = selection.get(cx).cursor;
-                                                let text_layout = cx
-                                                    .use_component::<TextLayoutInfo>(text_id)
-                                                    .unwrap();
-                                                let mut pos: Vec2 = Vec2::default();
-                                                let height: f32;
-                                                if index >= text_layout.glyphs.len() {
-                                                    let glyph = text_layout.glyphs.last().unwrap();
-                                                    pos.x = glyph.position.x + glyph.size.x;
-                                                    pos.y = glyph.position.y;
-                                                    height = glyph.size.y;
-                                                } else {
-                                                    let glyph = &text_layout.glyphs[index];
-                                                    pos.x = glyph.position.x;
-                                                    pos.y = glyph.position.y;
-                                                    height = glyph.size.y;
+                                                let count = blink.get(cx);
+                                                let start = match blink_start {
+                                                    Some((prev, start)) if prev == sel => start,
+                                                    _ => count,
+                                                };
+                                                blink_start = Some((sel, start));
+                                                if count.wrapping_sub(start) % 2 == 0 {
+                                                    let rect = caret_rect.get(cx);
+                                                    caret = Vec4::new(
+                                                        rect.min.x, rect.min.y, rect.max.x,
+                                                        rect.max.y,
+                                                    );
                                                 }
-                                                let mut entt = cx.world_mut().entity_mut(el);
-                                                let mut style = entt.get_mut::<Style>().unwrap();
-                                                style.left = ui::Val::Px(pos.x * 0.5);
-                                                style.top = ui::Val::Px(pos.y - height);
-                                                style.height = ui::Val::Px(height);
-                                            })
-                                    },
-                                    || (),
-                                ),
+                                            } else {
+                                                blink_start = None;
+                                            }
+                                            let mut ui_materials = cx
+                                                .world_mut()
+                                                .get_resource_mut::<Assets<TextDecorationMaterial>>(
+                                                )
+                                                .unwrap();
+                                            let material =
+                                                ui_materials.get_mut(material.id()).unwrap();
+                                            material.caret = caret;
+                                        }
+                                    }),
                                 // Text
                                 Element::<TextBundle>::for_entity(text_id).create_effect(
                                     move |cx, elem| {
+                                        let foreground = color_foreground.get(cx);
                                         let sections = value.map(cx, |s| {
                                             let mut sections: Vec<TextSection> = Vec::new();
                                             sections.push(TextSection {
@@ -482,7 +696,7 @@ impl ViewTemplate for TextInput {
                                                 style: TextStyle {
                                                     font: font.clone(),
                                                     font_size: 16.0,
-                                                    color: colors::FOREGROUND.into(),
+                                                    color: foreground.into(),
                                                 },
                                             });
                                             sections
@@ -501,3 +715,29 @@ impl ViewTemplate for TextInput {
             ))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snap_selection() {
+        // "é" as 'e' + combining acute accent is one grapheme of three bytes.
+        let text = "ae\u{301}b";
+        assert_eq!(snap_grapheme(text, 0), 0);
+        assert_eq!(snap_grapheme(text, 1), 1);
+        assert_eq!(snap_grapheme(text, 2), 1);
+        assert_eq!(snap_grapheme(text, 3), 1);
+        assert_eq!(snap_grapheme(text, 4), 4);
+        assert_eq!(snap_grapheme(text, 5), 5);
+        assert_eq!(snap_grapheme(text, 99), 5);
+
+        // Off a char boundary: "ü" is two bytes.
+        assert_eq!(snap_grapheme("üx", 1), 0);
+
+        let sel = Selection::new(9, 2).snap(text);
+        assert_eq!(sel, Selection::new(5, 1));
+        assert_eq!(prev_grapheme(text, sel.cursor), 4);
+        assert_eq!(next_grapheme(text, sel.anchor), 4);
+    }
+}
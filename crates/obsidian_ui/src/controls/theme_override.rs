@@ -0,0 +1,48 @@
+use bevy::{prelude::*, utils::HashMap};
+use bevy_reactor::*;
+
+use crate::hooks::{CreateThemeSignal, ThemeToken};
+
+/// Overrides theme tokens for all of the views within it. Controls which resolve a token pick
+/// up the value from the nearest enclosing override, so for example a "danger zone" panel can
+/// give all of its buttons destructive colors without setting a variant on each one:
+///
+/// ```ignore
+/// ThemeOverride::new()
+///     .token(ThemeToken::Button, colors::DESTRUCTIVE)
+///     .children((Button::new().children("Delete"), Button::new().children("Reset")))
+/// ```
+#[derive(Clone, Default)]
+pub struct ThemeOverride {
+    /// The overridden tokens.
+    pub tokens: HashMap<ThemeToken, Signal<Srgba>>,
+
+    /// The views which the overrides apply to.
+    pub children: ChildArray,
+}
+
+impl ThemeOverride {
+    /// Create a new theme override, with no tokens overridden.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Override the color of a token. The color may be a signal.
+    pub fn token(mut self, token: ThemeToken, color: impl IntoSignal<Srgba>) -> Self {
+        self.tokens.insert(token, color.into_signal());
+        self
+    }
+
+    /// Set the views which the overrides apply to.
+    pub fn children<V: ChildViewTuple>(mut self, children: V) -> Self {
+        self.children = children.to_child_array();
+        self
+    }
+}
+
+impl ViewTemplate for ThemeOverride {
+    fn create(&self, cx: &mut Cx) -> impl IntoView {
+        cx.provide_theme(self.tokens.clone());
+        Fragment::new(self.children.clone())
+    }
+}
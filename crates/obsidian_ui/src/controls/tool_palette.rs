@@ -1,15 +1,23 @@
-use crate::{size::Size, RoundedCorners};
+use crate::{
+    colors,
+    floating::{FloatAlign, FloatPosition, FloatSide, Floating},
+    focus::TextEntry,
+    hooks::CreateDisabledSignal,
+    hotkeys::key_name,
+    size::Size,
+    typography, RoundedCorners,
+};
 use bevy::{
     a11y::{
         accesskit::{NodeBuilder, Role},
-        AccessibilityNode,
+        AccessibilityNode, Focus,
     },
     prelude::*,
     ui,
 };
 use bevy_reactor::*;
 
-use super::{Button, ButtonVariant};
+use super::{Button, ButtonVariant, MenuButton, MenuPopup};
 
 fn style_tool_palette(ss: &mut StyleBuilder) {
     ss.display(ui::Display::Grid)
@@ -20,9 +28,50 @@ fn style_tool_palette(ss: &mut StyleBuilder) {
         .grid_auto_rows(vec![ui::GridTrack::default()]);
 }
 
-#[derive(Clone, Debug, Default, Component)]
+fn style_tool_palette_overflow(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Column)
+        .gap(1);
+}
+
+fn style_tool_button(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Column)
+        .align_items(ui::AlignItems::Stretch);
+}
+
+fn style_tooltip(ss: &mut StyleBuilder) {
+    ss.position(ui::PositionType::Absolute)
+        .padding((6, 2))
+        .border_radius(3.0)
+        .font_size(13)
+        .background_color(colors::U1)
+        .color(colors::FOREGROUND)
+        .pointer_events(false);
+}
+
+/// The direction in which a [`ToolPalette`] arranges its tools.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum ToolPaletteOrientation {
+    /// Tools are placed in rows of `columns` tools, and the palette grows downward.
+    #[default]
+    Horizontal,
+    /// Tools are placed in columns of `columns` tools, and the palette grows to the right.
+    Vertical,
+}
+
+#[derive(Clone, Default, Component)]
 struct ToolPaletteContext {
     size: Size,
+    active: Option<Signal<String>>,
+    on_select: Option<Callback<String>>,
+}
+
+/// Component which activates a tool when its keyboard shortcut is pressed.
+#[derive(Component)]
+pub(crate) struct ToolShortcut {
+    key: KeyCode,
+    on_activate: Callback,
 }
 
 /// ToolPalette - a grid of tool buttons
@@ -39,6 +88,19 @@ pub struct ToolPalette {
 
     /// Number of button columns
     pub columns: u16,
+
+    /// Whether tools are arranged in rows or columns.
+    pub orientation: ToolPaletteOrientation,
+
+    /// If true, tools which don't fit within the palette's parent are moved into an overflow
+    /// menu.
+    pub overflow: bool,
+
+    /// Id of the active tool. Tool buttons with a matching id are displayed as selected.
+    pub active: Option<Signal<String>>,
+
+    /// Callback called with the id of a tool button when it is clicked.
+    pub on_select: Option<Callback<String>>,
 }
 
 impl ToolPalette {
@@ -70,25 +132,159 @@ impl ToolPalette {
         self.columns = columns;
         self
     }
+
+    /// Set whether tools are arranged in rows or columns.
+    pub fn orientation(mut self, orientation: ToolPaletteOrientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
+
+    /// Set whether tools which don't fit are moved into an overflow menu.
+    pub fn overflow(mut self, overflow: bool) -> Self {
+        self.overflow = overflow;
+        self
+    }
+
+    /// Set the id of the active tool.
+    pub fn active(mut self, active: impl IntoSignal<String>) -> Self {
+        self.active = Some(active.into_signal());
+        self
+    }
+
+    /// Set the callback called with the id of a tool button when it is clicked.
+    pub fn on_select(mut self, on_select: Callback<String>) -> Self {
+        self.on_select = Some(on_select);
+        self
+    }
 }
 
 impl ViewTemplate for ToolPalette {
     fn create(&self, cx: &mut Cx) -> impl IntoView {
+        let id = cx.create_entity();
         let columns = self.columns;
-
-        cx.insert(ToolPaletteContext { size: self.size });
-
-        Element::<NodeBundle>::new()
+        let orientation = self.orientation;
+        let size = self.size;
+        let count = self.children.len();
+
+        cx.insert(ToolPaletteContext {
+            size: self.size,
+            active: self.active.clone(),
+            on_select: self.on_select,
+        });
+
+        // The number of tools shown directly in the palette; the remainder go into the
+        // overflow menu.
+        let visible = match self.overflow {
+            true => cx.create_memo(move |cx| {
+                let Some(parent) = cx.use_component::<Parent>(id).map(|p| p.get()) else {
+                    return count;
+                };
+                let available =
+                    cx.use_component::<Node>(parent)
+                        .map_or(0., |node| match orientation {
+                            ToolPaletteOrientation::Horizontal => node.size().y,
+                            ToolPaletteOrientation::Vertical => node.size().x,
+                        });
+                // Horizontal palettes grow by rows of fixed-height buttons; vertical palettes
+                // grow by columns, which are as wide as the widest button.
+                let cell = match orientation {
+                    ToolPaletteOrientation::Horizontal => size.height(),
+                    ToolPaletteOrientation::Vertical => cx
+                        .use_component::<Children>(id)
+                        .map_or(0., |children| {
+                            children
+                                .iter()
+                                .filter_map(|child| cx.use_component::<Node>(*child))
+                                .fold(0., |width: f32, node| width.max(node.size().x))
+                        })
+                        .max(size.height()),
+                };
+                let lanes = ((available + 1.) / (cell + 1.)).floor().max(1.) as usize;
+                let capacity = lanes * columns.max(1) as usize;
+                if count > capacity {
+                    // One cell is taken by the overflow button.
+                    capacity.saturating_sub(1)
+                } else {
+                    count
+                }
+            }),
+            false => Signal::Constant(count),
+        };
+
+        let children = self.children.clone();
+        Element::<NodeBundle>::for_entity(id)
             .named("ToolPalette")
             .style((
                 style_tool_palette,
-                move |ss: &mut StyleBuilder| {
-                    ss.grid_template_columns(vec![ui::RepeatedGridTrack::auto(columns)]);
+                move |ss: &mut StyleBuilder| match orientation {
+                    ToolPaletteOrientation::Horizontal => {
+                        ss.grid_template_columns(vec![ui::RepeatedGridTrack::auto(columns)]);
+                    }
+                    ToolPaletteOrientation::Vertical => {
+                        ss.grid_auto_flow(ui::GridAutoFlow::Column)
+                            .grid_template_rows(vec![ui::RepeatedGridTrack::auto(columns)]);
+                    }
                 },
                 self.style.clone(),
             ))
             .insert(AccessibilityNode::from(NodeBuilder::new(Role::Group)))
-            .children(self.children.clone())
+            .children(DynamicKeyed::new(
+                move |cx| visible.get(cx),
+                move |visible| {
+                    let shown = children.iter().take(visible).cloned().collect::<Vec<_>>();
+                    if visible >= children.len() {
+                        return Fragment::new(shown).into_view();
+                    }
+                    let overflow = children.iter().skip(visible).cloned().collect::<Vec<_>>();
+                    Fragment::new((
+                        shown,
+                        MenuButton::new().size(size).minimal(true).popup(
+                            MenuPopup::new().children(
+                                Element::<NodeBundle>::new()
+                                    .named("ToolPalette::Overflow")
+                                    .style(style_tool_palette_overflow)
+                                    .children(overflow),
+                            ),
+                        ),
+                    ))
+                    .into_view()
+                },
+            ))
+    }
+}
+
+/// System which activates tools when their keyboard shortcut is pressed without modifiers.
+/// Shortcuts are ignored while a [`TextEntry`] widget, such as a text input, has focus.
+pub(crate) fn trigger_tool_shortcuts(
+    keys: Option<Res<ButtonInput<KeyCode>>>,
+    focus: Option<Res<Focus>>,
+    text_entries: Query<(), With<TextEntry>>,
+    shortcuts: Query<&ToolShortcut>,
+    mut commands: Commands,
+) {
+    let Some(keys) = keys else {
+        return;
+    };
+    if focus
+        .and_then(|focus| focus.0)
+        .is_some_and(|entity| text_entries.contains(entity))
+    {
+        return;
+    }
+    if keys.any_pressed([
+        KeyCode::ControlLeft,
+        KeyCode::ControlRight,
+        KeyCode::AltLeft,
+        KeyCode::AltRight,
+        KeyCode::SuperLeft,
+        KeyCode::SuperRight,
+    ]) {
+        return;
+    }
+    for shortcut in shortcuts.iter() {
+        if keys.just_pressed(shortcut.key) {
+            commands.run_callback(shortcut.on_activate, ());
+        }
     }
 }
 
@@ -114,6 +310,18 @@ pub struct ToolButton {
 
     /// If true, set focus to this button when it's added to the UI.
     pub(crate) autofocus: bool,
+
+    /// If true (the default), expand the hit area to the minimum touch target size.
+    pub(crate) touch_target: bool,
+
+    /// Id of the tool, compared against the palette's active tool.
+    pub(crate) id: Option<String>,
+
+    /// Text displayed when hovering over the button.
+    pub(crate) tooltip: Option<String>,
+
+    /// Keyboard shortcut which activates the tool.
+    pub(crate) shortcut: Option<KeyCode>,
 }
 
 impl ToolButton {
@@ -164,6 +372,31 @@ impl ToolButton {
         self.autofocus = autofocus;
         self
     }
+
+    /// Set whether to expand the hit area to the minimum touch target size (default true).
+    pub fn touch_target(mut self, touch_target: bool) -> Self {
+        self.touch_target = touch_target;
+        self
+    }
+
+    /// Set the id of the tool. When the palette's active tool has this id, the button is
+    /// displayed as selected, and clicking the button calls the palette's `on_select`.
+    pub fn id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Set the text displayed when hovering over the button.
+    pub fn tooltip(mut self, tooltip: impl Into<String>) -> Self {
+        self.tooltip = Some(tooltip.into());
+        self
+    }
+
+    /// Set the keyboard shortcut which activates the tool.
+    pub fn shortcut(mut self, key: KeyCode) -> Self {
+        self.shortcut = Some(key);
+        self
+    }
 }
 
 impl Default for ToolButton {
@@ -176,23 +409,125 @@ impl Default for ToolButton {
             tab_index: 0,
             corners: RoundedCorners::None,
             autofocus: false,
+            touch_target: true,
+            id: None,
+            tooltip: None,
+            shortcut: None,
         }
     }
 }
 
 impl ViewTemplate for ToolButton {
     fn create(&self, cx: &mut Cx) -> impl IntoView {
-        let context = cx.use_inherited_component::<ToolPaletteContext>().unwrap();
+        let context = cx
+            .use_inherited_component::<ToolPaletteContext>()
+            .unwrap()
+            .clone();
+        let id = cx.create_entity();
+        let hovering = cx.create_hover_signal(id);
+
+        // Tools with an id are selected when they match the palette's active tool.
+        let variant = match (context.active, self.id.clone()) {
+            (Some(active), Some(tool)) => {
+                let variant = self.variant;
+                cx.create_derived(move |cx| {
+                    if active.get_clone(cx) == tool {
+                        ButtonVariant::Selected
+                    } else {
+                        variant.get(cx)
+                    }
+                })
+            }
+            _ => self.variant,
+        };
+
+        let on_click = match (context.on_select, self.id.clone()) {
+            (Some(on_select), Some(tool)) => {
+                let on_click = self.on_click;
+                Some(cx.create_callback(move |cx: &mut Cx, _| {
+                    cx.run_callback(on_select, tool.clone());
+                    if let Some(on_click) = on_click {
+                        cx.run_callback(on_click, ());
+                    }
+                }))
+            }
+            _ => self.on_click,
+        };
+
         let mut btn = Button::new()
             .size(context.size)
-            .variant(self.variant)
+            .variant(variant)
             .disabled(self.disabled)
             .children(self.children.clone())
             // .on_click(self.on_click)
             .tab_index(self.tab_index)
             .autofocus(self.autofocus)
+            .touch_target(self.touch_target)
             .corners(self.corners);
-        btn.on_click = self.on_click;
-        btn
+        btn.on_click = on_click;
+
+        let tooltip = match (&self.tooltip, self.shortcut) {
+            (Some(tooltip), Some(key)) => Some(format!("{} ({})", tooltip, key_name(key))),
+            (Some(tooltip), None) => Some(tooltip.clone()),
+            (None, Some(key)) => Some(key_name(key)),
+            (None, None) => None,
+        };
+
+        let disabled = cx.create_disabled_signal(self.disabled);
+        let shortcut = match (self.shortcut, on_click) {
+            (Some(key), Some(on_click)) => Some(ToolShortcut {
+                key,
+                on_activate: cx.create_callback(move |cx: &mut Cx, _| {
+                    if !disabled.get(cx) {
+                        cx.run_callback(on_click, ());
+                    }
+                }),
+            }),
+            _ => None,
+        };
+
+        let element = Element::<NodeBundle>::for_entity(id)
+            .named("ToolButton")
+            .style(style_tool_button);
+        let element = match shortcut {
+            Some(shortcut) => element.insert(shortcut),
+            None => element,
+        };
+
+        element.children((
+            btn,
+            Cond::new(
+                move |cx| hovering.get(cx),
+                move || match tooltip.clone() {
+                    Some(tooltip) => Portal::new(
+                        Element::<NodeBundle>::new()
+                            .named("ToolButton::Tooltip")
+                            .style((typography::text_default, style_tooltip))
+                            .insert(Floating {
+                                anchor: id.into(),
+                                position: vec![
+                                    FloatPosition {
+                                        side: FloatSide::Bottom,
+                                        align: FloatAlign::Center,
+                                        stretch: false,
+                                        gap: 4.0,
+                                    },
+                                    FloatPosition {
+                                        side: FloatSide::Top,
+                                        align: FloatAlign::Center,
+                                        stretch: false,
+                                        gap: 4.0,
+                                    },
+                                ],
+                            })
+                            .children(tooltip),
+                    )
+                    .layer("tooltips")
+                    .into_view(),
+                    None => ().into_view(),
+                },
+                || (),
+            ),
+        ))
     }
 }
@@ -0,0 +1,17 @@
+use bevy::{color::Alpha, prelude::*};
+use bevy_reactor::{StyleBuilder, StyleBuilderBoxShadow};
+
+/// Elevation for surfaces which rest just above their container, such as cards.
+pub fn elevation_1(ss: &mut StyleBuilder) {
+    ss.box_shadow(Vec2::new(0., 1.), 2., Srgba::BLACK.with_alpha(0.3));
+}
+
+/// Elevation for transient popups, such as menus and tooltips.
+pub fn elevation_2(ss: &mut StyleBuilder) {
+    ss.box_shadow(Vec2::new(0., 3.), 6., Srgba::BLACK.with_alpha(0.4));
+}
+
+/// Elevation for dialogs and floating windows.
+pub fn elevation_3(ss: &mut StyleBuilder) {
+    ss.box_shadow(Vec2::new(0., 6.), 12., Srgba::BLACK.with_alpha(0.5));
+}
@@ -1,6 +1,12 @@
 use bevy::{
-    ecs::{component::Component, entity::Entity, query::Without, system::Query},
-    math::Rect,
+    ecs::{
+        component::Component,
+        entity::Entity,
+        query::Without,
+        system::{Commands, Query},
+    },
+    math::{Rect, Vec2, Vec3},
+    render::{camera::Camera, view::Visibility},
     transform::components::GlobalTransform,
     ui::{self, Node, Style},
     window::Window,
@@ -61,12 +67,58 @@ pub struct FloatPosition {
     pub gap: f32,
 }
 
+/// A location in the 3D world that a floating element can be anchored to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WorldTarget {
+    /// The origin of an entity's [`GlobalTransform`], which is tracked as the entity moves.
+    Entity(Entity),
+    /// A fixed point in world space.
+    Point(Vec3),
+}
+
+impl From<Entity> for WorldTarget {
+    fn from(entity: Entity) -> Self {
+        WorldTarget::Entity(entity)
+    }
+}
+
+impl From<Vec3> for WorldTarget {
+    fn from(point: Vec3) -> Self {
+        WorldTarget::Point(point)
+    }
+}
+
+/// What a floating element is anchored to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FloatAnchor {
+    /// A UI node.
+    Node(Entity),
+    /// A location in the 3D world, projected onto the window through a camera.
+    World {
+        /// The location being tracked.
+        target: WorldTarget,
+        /// The camera used to project the location.
+        camera: Entity,
+        /// If true, the anchor is kept within the window when the location is offscreen or
+        /// behind the camera. Otherwise, the floating element is hidden while the location
+        /// is behind the camera.
+        clamp: bool,
+    },
+}
+
+impl From<Entity> for FloatAnchor {
+    fn from(entity: Entity) -> Self {
+        FloatAnchor::Node(entity)
+    }
+}
+
 /// Component which is inserted into a floating element to make it float relative to
 /// an anchor element.
 #[derive(Component, PartialEq)]
 pub struct Floating {
-    /// The entity that this floating element is anchored to.
-    pub anchor: Entity,
+    /// The element or world location that this floating element is anchored to. To anchor to
+    /// a UI node, convert its entity with `into()`.
+    pub anchor: FloatAnchor,
 
     /// The position of the floating element relative to the anchor.
     pub position: Vec<FloatPosition>,
@@ -81,9 +133,91 @@ impl Clone for Floating {
     }
 }
 
+impl Floating {
+    /// Anchor the floating element to a location in the 3D world, such as an entity, as seen
+    /// through `camera`. This is useful for nameplates and labels on objects in a scene. By
+    /// default, the element is centered above the location, and clamped to the window.
+    pub fn anchor_world(target: impl Into<WorldTarget>, camera: Entity) -> Self {
+        Self {
+            anchor: FloatAnchor::World {
+                target: target.into(),
+                camera,
+                clamp: true,
+            },
+            position: vec![FloatPosition {
+                side: FloatSide::Top,
+                align: FloatAlign::Center,
+                stretch: false,
+                gap: 0.,
+            }],
+        }
+    }
+
+    /// Set the positions to try, in order of preference.
+    pub fn position(mut self, position: Vec<FloatPosition>) -> Self {
+        self.position = position;
+        self
+    }
+
+    /// Set whether a world anchor is kept within the window when it is offscreen. Has no
+    /// effect for elements anchored to a UI node.
+    pub fn clamp(mut self, clamp: bool) -> Self {
+        if let FloatAnchor::World { clamp: c, .. } = &mut self.anchor {
+            *c = clamp;
+        }
+        self
+    }
+}
+
+/// Component which is updated on floating elements anchored to a world location, so that they
+/// can show an indicator when the location is out of view.
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq)]
+pub struct WorldAnchorState {
+    /// True if the location is outside the window, or behind the camera.
+    pub offscreen: bool,
+    /// Unit vector pointing from the center of the window towards the location, in window
+    /// coordinates (+Y is down). Can be used to rotate an arrow pointing at the location.
+    pub direction: Vec2,
+}
+
+/// Project a world location onto the window, in logical pixels. Returns the projected point,
+/// and whether the location is behind the camera. For locations behind the camera, the point
+/// is in the direction of the location but otherwise meaningless.
+fn project_anchor(
+    camera: &Camera,
+    camera_transform: &GlobalTransform,
+    point: Vec3,
+) -> (Vec2, bool) {
+    let viewport = camera.logical_viewport_rect().unwrap_or_default();
+    let view = camera_transform
+        .compute_matrix()
+        .inverse()
+        .transform_point3(point);
+    if view.z < 0. {
+        if let Some(projected) = camera.world_to_viewport(camera_transform, point) {
+            return (projected + viewport.min, false);
+        }
+    }
+    // Behind the camera: push the point far out in the direction of the location.
+    let direction = Vec2::new(view.x, -view.y).normalize_or_zero();
+    (viewport.center() + direction * 1.0e6, true)
+}
+
+#[allow(clippy::type_complexity)]
 pub(crate) fn position_floating(
-    mut query: Query<(&mut Style, &Node, &Floating, &GlobalTransform)>,
+    mut commands: Commands,
+    mut query: Query<(
+        Entity,
+        &mut Style,
+        &Node,
+        &Floating,
+        &GlobalTransform,
+        Option<&mut WorldAnchorState>,
+        Option<&mut Visibility>,
+    )>,
     anchor_query: Query<(&Node, &GlobalTransform), Without<Floating>>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    world_targets: Query<&GlobalTransform>,
     windows: Query<&Window>,
 ) {
     let Ok(window) = windows.get_single() else {
@@ -95,126 +229,266 @@ pub(crate) fn position_floating(
 
     let window_rect = Rect::new(0., 0., ww / sf, wh / sf).inset(8.);
 
-    for (mut style, target_node, floating, floating_transform) in query.iter_mut() {
-        let Ok((anchor, anchor_transform)) = anchor_query.get(floating.anchor) else {
-            continue;
-        };
+    for (entity, mut style, target_node, floating, floating_transform, state, visibility) in
+        query.iter_mut()
+    {
+        let anchor_rect = match floating.anchor {
+            FloatAnchor::Node(anchor) => {
+                let Ok((anchor, anchor_transform)) = anchor_query.get(anchor) else {
+                    continue;
+                };
+                anchor.logical_rect(anchor_transform)
+            }
 
-        let anchor_rect = anchor.logical_rect(anchor_transform);
-        let mut best_occluded = f32::MAX;
-        let mut best_rect = Rect::default();
-        let mut best_position: FloatPosition = Default::default();
-
-        for position in &floating.position {
-            let floating_rect = target_node.logical_rect(floating_transform);
-            let mut rect = Rect::default();
-
-            // Taraget width and height depends on whether 'stretch' is true.
-            let target_width = if position.stretch && position.side == FloatSide::Top
-                || position.side == FloatSide::Bottom
-            {
-                floating_rect.width().max(anchor_rect.width())
-            } else {
-                floating_rect.width()
-            };
-
-            let target_height = if position.stretch && position.side == FloatSide::Left
-                || position.side == FloatSide::Right
-            {
-                floating_rect.height().max(anchor_rect.height())
-            } else {
-                floating_rect.height()
-            };
-
-            // Position along main axis.
-            match position.side {
-                FloatSide::Top => {
-                    rect.max.y = anchor_rect.min.y - position.gap;
-                    rect.min.y = rect.max.y - floating_rect.height();
+            FloatAnchor::World {
+                target,
+                camera,
+                clamp,
+            } => {
+                let Ok((camera, camera_transform)) = cameras.get(camera) else {
+                    continue;
+                };
+                let point = match target {
+                    WorldTarget::Entity(target) => match world_targets.get(target) {
+                        Ok(transform) => transform.translation(),
+                        Err(_) => continue,
+                    },
+                    WorldTarget::Point(point) => point,
+                };
+                let (projected, behind) = project_anchor(camera, camera_transform, point);
+                let clamped = projected.clamp(window_rect.min, window_rect.max);
+                let new_state = WorldAnchorState {
+                    offscreen: behind || clamped != projected,
+                    direction: (projected - window_rect.center()).normalize_or_zero(),
+                };
+                match state {
+                    Some(mut state) => {
+                        if *state != new_state {
+                            *state = new_state;
+                        }
+                    }
+                    None => {
+                        commands.entity(entity).insert(new_state);
+                    }
                 }
 
-                FloatSide::Bottom => {
-                    rect.min.y = anchor_rect.max.y + position.gap;
-                    rect.max.y = rect.min.y + floating_rect.height();
+                let hidden = behind && !clamp;
+                if let Some(mut visibility) = visibility {
+                    let desired = if hidden {
+                        Visibility::Hidden
+                    } else {
+                        Visibility::Inherited
+                    };
+                    if *visibility != desired {
+                        *visibility = desired;
+                    }
                 }
-
-                FloatSide::Left => {
-                    rect.max.x = anchor_rect.min.x - position.gap;
-                    rect.min.x = rect.max.x - floating_rect.width();
+                if hidden {
+                    continue;
                 }
 
-                FloatSide::Right => {
-                    rect.min.x = anchor_rect.max.x + position.gap;
-                    rect.max.x = rect.min.x + floating_rect.width();
-                }
+                let point = if clamp { clamped } else { projected };
+                Rect::from_corners(point, point)
             }
-
-            // Position along secondary axis.
-            match position.align {
-                FloatAlign::Start => match position.side {
+        };
+        let floating_size = target_node.logical_rect(floating_transform).size();
+        if let Some((best_rect, best_position)) =
+            place_floating(anchor_rect, floating_size, &floating.position, window_rect)
+        {
+            style.left = ui::Val::Px(best_rect.min.x);
+            style.top = ui::Val::Px(best_rect.min.y);
+            if best_position.stretch {
+                match best_position.side {
                     FloatSide::Top | FloatSide::Bottom => {
-                        rect.min.x = anchor_rect.min.x;
-                        rect.max.x = rect.min.x + target_width;
+                        style.width = ui::Val::Px(best_rect.width());
                     }
 
                     FloatSide::Left | FloatSide::Right => {
-                        rect.min.y = anchor_rect.min.y;
-                        rect.max.y = rect.min.y + target_height;
+                        style.height = ui::Val::Px(best_rect.height());
                     }
-                },
+                }
+            }
+        }
+    }
+}
 
-                FloatAlign::End => match position.side {
-                    FloatSide::Top | FloatSide::Bottom => {
-                        rect.max.x = anchor_rect.max.x;
-                        rect.min.x = rect.max.x - target_width;
-                    }
+/// Choose where to place a floating element of the given size next to `anchor_rect`. Each of
+/// `positions` is tried in turn, and the first one which leaves the least of the element
+/// outside of `window_rect` wins. Returns the element's rect and the chosen position, or
+/// `None` if there are no positions to try.
+fn place_floating(
+    anchor_rect: Rect,
+    floating_size: Vec2,
+    positions: &[FloatPosition],
+    window_rect: Rect,
+) -> Option<(Rect, FloatPosition)> {
+    let mut best_occluded = f32::MAX;
+    let mut best_rect = Rect::default();
+    let mut best_position: FloatPosition = Default::default();
 
-                    FloatSide::Left | FloatSide::Right => {
-                        rect.max.y = anchor_rect.max.y;
-                        rect.min.y = rect.max.y - target_height;
-                    }
-                },
+    for position in positions {
+        let mut rect = Rect::default();
 
-                FloatAlign::Center => match position.side {
-                    FloatSide::Top | FloatSide::Bottom => {
-                        rect.min.x = (anchor_rect.width() - target_width) * 0.5;
-                        rect.max.x = rect.min.x + target_width;
-                    }
+        // Target width and height depends on whether 'stretch' is true.
+        let target_width = if position.stretch
+            && (position.side == FloatSide::Top || position.side == FloatSide::Bottom)
+        {
+            floating_size.x.max(anchor_rect.width())
+        } else {
+            floating_size.x
+        };
 
-                    FloatSide::Left | FloatSide::Right => {
-                        rect.min.y = (anchor_rect.width() - target_height) * 0.5;
-                        rect.max.y = rect.min.y + target_height;
-                    }
-                },
+        let target_height = if position.stretch
+            && (position.side == FloatSide::Left || position.side == FloatSide::Right)
+        {
+            floating_size.y.max(anchor_rect.height())
+        } else {
+            floating_size.y
+        };
+
+        // Position along main axis.
+        match position.side {
+            FloatSide::Top => {
+                rect.max.y = anchor_rect.min.y - position.gap;
+                rect.min.y = rect.max.y - floating_size.y;
+            }
+
+            FloatSide::Bottom => {
+                rect.min.y = anchor_rect.max.y + position.gap;
+                rect.max.y = rect.min.y + floating_size.y;
             }
 
-            // Clip to window and see how much of the floating element is occluded.
-            let clipped_rect = floating_rect.intersect(window_rect);
-            let occlusion = floating_rect.width() * floating_rect.height()
-                - clipped_rect.width() * clipped_rect.height();
+            FloatSide::Left => {
+                rect.max.x = anchor_rect.min.x - position.gap;
+                rect.min.x = rect.max.x - floating_size.x;
+            }
 
-            // Find the position that has the least occlusion.
-            if occlusion < best_occluded {
-                best_occluded = occlusion;
-                best_rect = rect;
-                best_position = *position;
+            FloatSide::Right => {
+                rect.min.x = anchor_rect.max.x + position.gap;
+                rect.max.x = rect.min.x + floating_size.x;
             }
         }
 
-        if best_occluded < f32::MAX {
-            style.left = ui::Val::Px(best_rect.min.x);
-            style.top = ui::Val::Px(best_rect.min.y);
-            if best_position.stretch {
-                match best_position.side {
-                    FloatSide::Top | FloatSide::Bottom => {
-                        style.width = ui::Val::Px(best_rect.width());
-                    }
+        // Position along secondary axis.
+        match position.align {
+            FloatAlign::Start => match position.side {
+                FloatSide::Top | FloatSide::Bottom => {
+                    rect.min.x = anchor_rect.min.x;
+                    rect.max.x = rect.min.x + target_width;
+                }
 
-                    FloatSide::Left | FloatSide::Right => {
-                        style.height = ui::Val::Px(best_rect.height());
-                    }
+                FloatSide::Left | FloatSide::Right => {
+                    rect.min.y = anchor_rect.min.y;
+                    rect.max.y = rect.min.y + target_height;
                 }
-            }
+            },
+
+            FloatAlign::End => match position.side {
+                FloatSide::Top | FloatSide::Bottom => {
+                    rect.max.x = anchor_rect.max.x;
+                    rect.min.x = rect.max.x - target_width;
+                }
+
+                FloatSide::Left | FloatSide::Right => {
+                    rect.max.y = anchor_rect.max.y;
+                    rect.min.y = rect.max.y - target_height;
+                }
+            },
+
+            FloatAlign::Center => match position.side {
+                FloatSide::Top | FloatSide::Bottom => {
+                    rect.min.x = anchor_rect.center().x - target_width * 0.5;
+                    rect.max.x = rect.min.x + target_width;
+                }
+
+                FloatSide::Left | FloatSide::Right => {
+                    rect.min.y = anchor_rect.center().y - target_height * 0.5;
+                    rect.max.y = rect.min.y + target_height;
+                }
+            },
         }
+
+        // Clip to window and see how much of the floating element is occluded.
+        let clipped_rect = rect.intersect(window_rect);
+        let occlusion = rect.width() * rect.height() - clipped_rect.width() * clipped_rect.height();
+
+        // Find the position that has the least occlusion.
+        if occlusion < best_occluded {
+            best_occluded = occlusion;
+            best_rect = rect;
+            best_position = *position;
+        }
+    }
+
+    (best_occluded < f32::MAX).then_some((best_rect, best_position))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position(side: FloatSide, align: FloatAlign) -> FloatPosition {
+        FloatPosition {
+            side,
+            align,
+            stretch: false,
+            gap: 2.,
+        }
+    }
+
+    #[test]
+    fn test_place_centered() {
+        let window = Rect::new(0., 0., 400., 400.);
+        let anchor = Rect::new(100., 100., 140., 120.);
+        let size = Vec2::new(20., 10.);
+
+        let (rect, _) = place_floating(
+            anchor,
+            size,
+            &[position(FloatSide::Bottom, FloatAlign::Center)],
+            window,
+        )
+        .unwrap();
+        assert_eq!(rect, Rect::new(110., 122., 130., 132.));
+
+        let (rect, _) = place_floating(
+            anchor,
+            size,
+            &[position(FloatSide::Right, FloatAlign::Center)],
+            window,
+        )
+        .unwrap();
+        assert_eq!(rect, Rect::new(142., 105., 162., 115.));
+
+        // Stretching widens the element to the anchor.
+        let stretched = FloatPosition {
+            stretch: true,
+            ..position(FloatSide::Bottom, FloatAlign::Center)
+        };
+        let (rect, _) = place_floating(anchor, size, &[stretched], window).unwrap();
+        assert_eq!(rect, Rect::new(100., 122., 140., 132.));
+    }
+
+    #[test]
+    fn test_place_least_occluded() {
+        let window = Rect::new(0., 0., 200., 200.);
+        let anchor = Rect::new(50., 180., 90., 195.);
+        let size = Vec2::new(30., 20.);
+        let positions = [
+            position(FloatSide::Bottom, FloatAlign::Start),
+            position(FloatSide::Top, FloatAlign::Start),
+        ];
+
+        // There is no room below the anchor, so the element goes above it.
+        let (rect, chosen) = place_floating(anchor, size, &positions, window).unwrap();
+        assert_eq!(chosen.side, FloatSide::Top);
+        assert_eq!(rect, Rect::new(50., 158., 80., 178.));
+
+        // When both fit, the first position is preferred.
+        let anchor = Rect::new(50., 50., 90., 65.);
+        let (_, chosen) = place_floating(anchor, size, &positions, window).unwrap();
+        assert_eq!(chosen.side, FloatSide::Bottom);
+
+        assert!(place_floating(anchor, size, &[], window).is_none());
     }
 }
@@ -6,14 +6,18 @@ use bevy::{
         entity::Entity,
         event::{Event, EventReader, EventWriter},
         query::{Added, With, Without},
-        system::{Query, Res, ResMut, Resource, SystemParam},
+        removal_detection::RemovedComponents,
+        system::{Local, Query, Res, ResMut, Resource, SystemParam},
     },
     hierarchy::{Children, Parent},
     input::{
+        gamepad::{GamepadAxis, GamepadAxisType, GamepadButton, GamepadButtonType, Gamepads},
         keyboard::{KeyCode, KeyboardInput},
-        ButtonInput, ButtonState,
+        Axis, ButtonInput, ButtonState,
     },
     log::*,
+    math::Vec2,
+    transform::components::GlobalTransform,
     ui::Node,
 };
 use bevy_mod_picking::prelude::{EntityEvent, EventListenerPlugin};
@@ -46,6 +50,9 @@ pub struct KeyPressEvent {
 
     /// Whether the shift key is held down.
     pub shift: bool,
+
+    /// Whether the control key is held down.
+    pub ctrl: bool,
 }
 
 /// A component which indicates that an entity wants to participate in tab navigation.
@@ -67,6 +74,11 @@ pub struct TabIndex(pub i32);
 #[derive(Debug, Default, Component, Copy, Clone)]
 pub struct AutoFocus;
 
+/// Marks a focusable widget, such as a text input, which uses plain key presses for typing.
+/// Single-key shortcuts, such as those of tool palettes, are ignored while it has focus.
+#[derive(Debug, Default, Component, Copy, Clone)]
+pub struct TextEntry;
+
 /// Resource that controls whether the focus indicators are visible or not. Generally
 /// these are only visible after the Tab key has been pressed, and become hidden when
 /// the user interacts with the UI in with the pointing device.
@@ -86,6 +98,111 @@ pub struct TabGroup {
     pub modal: bool,
 }
 
+/// A component which marks an entity, such as a popup or dialog, as a focus scope. When a
+/// focus scope is added, the current focus entity is saved in the [`FocusHistory`]; when the
+/// scope is removed or despawned, the saved focus is restored.
+#[derive(Debug, Default, Component, Copy, Clone)]
+pub struct FocusScope;
+
+/// Resource which records the focus entity that was current when each [`FocusScope`] was
+/// opened, in order from oldest to newest.
+#[derive(Debug, Default, Resource)]
+pub struct FocusHistory {
+    entries: Vec<(Entity, Option<Entity>)>,
+}
+
+impl FocusHistory {
+    /// Save `focus` as the entity to restore when `scope` is closed. If the scope is already
+    /// in the history, this replaces the saved entity.
+    pub fn push(&mut self, scope: Entity, focus: Option<Entity>) {
+        match self.entries.iter_mut().find(|(s, _)| *s == scope) {
+            Some(entry) => entry.1 = focus,
+            None => self.entries.push((scope, focus)),
+        }
+    }
+
+    /// Remove `scope` from the history, returning the focus entity that was saved for it.
+    ///
+    /// If scopes were opened after `scope`, then nothing is returned; instead the next scope
+    /// inherits the saved focus, since its own saved focus was most likely inside `scope`.
+    pub fn remove(&mut self, scope: Entity) -> Option<Entity> {
+        let index = self.entries.iter().position(|(s, _)| *s == scope)?;
+        let (_, saved) = self.entries.remove(index);
+        if let Some(next) = self.entries.get_mut(index) {
+            next.1 = saved;
+            return None;
+        }
+        saved
+    }
+
+    /// The focus entity saved for `scope`, if any.
+    pub fn saved(&self, scope: Entity) -> Option<Entity> {
+        self.entries
+            .iter()
+            .find(|(s, _)| *s == scope)
+            .and_then(|(_, focus)| *focus)
+    }
+
+    /// The most recently opened focus scope.
+    pub fn top(&self) -> Option<Entity> {
+        self.entries.last().map(|(scope, _)| *scope)
+    }
+}
+
+/// Direction for spatial (directional) navigation.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum NavDirection {
+    /// Navigate upwards.
+    Up,
+    /// Navigate downwards.
+    Down,
+    /// Navigate to the left.
+    Left,
+    /// Navigate to the right.
+    Right,
+}
+
+impl NavDirection {
+    /// Unit vector pointing in this direction, in UI coordinates (where +y is down).
+    fn vector(self) -> Vec2 {
+        match self {
+            NavDirection::Up => Vec2::new(0., -1.),
+            NavDirection::Down => Vec2::new(0., 1.),
+            NavDirection::Left => Vec2::new(-1., 0.),
+            NavDirection::Right => Vec2::new(1., 0.),
+        }
+    }
+}
+
+/// Explicit neighbors for directional navigation, overriding the geometric search.
+///
+/// When placed on a focusable entity, this specifies which entity receives focus when
+/// navigating from that entity in a given direction. When placed on a [`TabGroup`], it applies
+/// to all entities within the group which don't have their own override.
+#[derive(Debug, Default, Component, Copy, Clone)]
+pub struct NavNeighbors {
+    /// Entity to focus when navigating up.
+    pub up: Option<Entity>,
+    /// Entity to focus when navigating down.
+    pub down: Option<Entity>,
+    /// Entity to focus when navigating left.
+    pub left: Option<Entity>,
+    /// Entity to focus when navigating right.
+    pub right: Option<Entity>,
+}
+
+impl NavNeighbors {
+    /// Return the neighbor in the given direction, if any.
+    pub fn get(&self, direction: NavDirection) -> Option<Entity> {
+        match direction {
+            NavDirection::Up => self.up,
+            NavDirection::Down => self.down,
+            NavDirection::Left => self.left,
+            NavDirection::Right => self.right,
+        }
+    }
+}
+
 /// An injectable object that provides tab navigation functionality.
 #[doc(hidden)]
 #[derive(SystemParam)]
@@ -102,6 +219,10 @@ pub struct TabNavigation<'w, 's> {
     >,
     // Query for parents.
     parent: Query<'w, 's, &'static Parent, With<Node>>,
+    // Query for layout rects, used for directional navigation.
+    rect: Query<'w, 's, (&'static Node, &'static GlobalTransform)>,
+    // Query for explicit directional neighbors.
+    neighbors: Query<'w, 's, &'static NavNeighbors>,
 }
 
 /// Navigation action for tabbing.
@@ -151,6 +272,76 @@ impl TabNavigation<'_, '_> {
         self.navigate_in_group(tabgroup, focus, action)
     }
 
+    /// Navigate to the focusable entity which is geometrically nearest to the current focus
+    /// entity in the given direction. Explicit [`NavNeighbors`] on the focus entity or its
+    /// tab group take precedence over the geometric search.
+    ///
+    /// If there is no current focus entity, then the first focusable entity is returned.
+    pub fn navigate_direction(
+        &self,
+        focus: Option<Entity>,
+        direction: NavDirection,
+    ) -> Option<Entity> {
+        let Some(focus) = focus else {
+            return self.navigate(None, NavAction::First);
+        };
+
+        // Look for explicit overrides, first on the focus entity and then on its tab group.
+        let mut tabgroup: Option<(Entity, &TabGroup)> = None;
+        let mut entity = Some(focus);
+        while let Some(ent) = entity {
+            if let Some(neighbor) = self
+                .neighbors
+                .get(ent)
+                .ok()
+                .and_then(|neighbors| neighbors.get(direction))
+            {
+                return Some(neighbor);
+            }
+            if let Ok((tg_entity, tg, _)) = self.tabgroup.get(ent) {
+                tabgroup = Some((tg_entity, tg));
+                break;
+            }
+            entity = self.parent.get(ent).ok().map(|parent| parent.get());
+        }
+
+        if tabgroup.is_none() {
+            warn!("No tab group found for focus entity");
+            return None;
+        }
+
+        let origin = self.center(focus)?;
+
+        let mut focusable: Vec<(Entity, TabIndex)> = Vec::new();
+        self.gather_group(&mut focusable, tabgroup);
+
+        // Score candidates by distance along the direction of travel, penalizing distance
+        // perpendicular to it so that aligned widgets are preferred.
+        let axis = direction.vector();
+        focusable
+            .iter()
+            .filter(|(entity, _)| *entity != focus)
+            .filter_map(|(entity, _)| {
+                let offset = self.center(*entity)? - origin;
+                let along = offset.dot(axis);
+                if along <= 0. {
+                    return None;
+                }
+                let across = (offset - axis * along).length();
+                Some((*entity, along + across * 2.))
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(entity, _)| entity)
+    }
+
+    /// Center of an entity's layout rect, in logical pixels.
+    fn center(&self, entity: Entity) -> Option<Vec2> {
+        self.rect
+            .get(entity)
+            .ok()
+            .map(|(node, transform)| node.logical_rect(transform).center())
+    }
+
     fn navigate_in_group(
         &self,
         tabgroup: Option<(Entity, &TabGroup)>,
@@ -159,13 +350,41 @@ impl TabNavigation<'_, '_> {
     ) -> Option<Entity> {
         // List of all focusable entities found.
         let mut focusable: Vec<(Entity, TabIndex)> = Vec::with_capacity(self.tabindex.iter().len());
+        self.gather_group(&mut focusable, tabgroup);
+
+        if focusable.is_empty() {
+            warn!("No focusable entities found");
+            return None;
+        }
+
+        // Stable sort by tabindex
+        focusable.sort_by(compare_tab_indices);
+
+        let index = focusable.iter().position(|e| Some(e.0) == focus);
+        let count = focusable.len();
+        let next = match (index, action) {
+            (Some(idx), NavAction::Next) => (idx + 1).rem_euclid(count),
+            (Some(idx), NavAction::Previous) => (idx + count - 1).rem_euclid(count),
+            (None, NavAction::Next) => 0,
+            (None, NavAction::Previous) => count - 1,
+            (_, NavAction::First) => 0,
+            (_, NavAction::Last) => count - 1,
+        };
+        focusable.get(next).map(|(e, _)| e).copied()
+    }
 
+    /// Gather the focusable entities which are reachable from within the given tab group.
+    fn gather_group(
+        &self,
+        focusable: &mut Vec<(Entity, TabIndex)>,
+        tabgroup: Option<(Entity, &TabGroup)>,
+    ) {
         match tabgroup {
             Some((tg_entity, tg)) if tg.modal => {
                 // We're in a modal tab group, then gather all tab indices in that group.
                 if let Ok((_, _, children)) = self.tabgroup.get(tg_entity) {
                     for child in children.iter() {
-                        self.gather_focusable(&mut focusable, *child);
+                        self.gather_focusable(focusable, *child);
                     }
                 }
             }
@@ -182,30 +401,10 @@ impl TabNavigation<'_, '_> {
 
                 // Search group descendants
                 tab_groups.iter().for_each(|(tg_entity, _)| {
-                    self.gather_focusable(&mut focusable, *tg_entity);
+                    self.gather_focusable(focusable, *tg_entity);
                 })
             }
         }
-
-        if focusable.is_empty() {
-            warn!("No focusable entities found");
-            return None;
-        }
-
-        // Stable sort by tabindex
-        focusable.sort_by(compare_tab_indices);
-
-        let index = focusable.iter().position(|e| Some(e.0) == focus);
-        let count = focusable.len();
-        let next = match (index, action) {
-            (Some(idx), NavAction::Next) => (idx + 1).rem_euclid(count),
-            (Some(idx), NavAction::Previous) => (idx + count - 1).rem_euclid(count),
-            (None, NavAction::Next) => 0,
-            (None, NavAction::Previous) => count - 1,
-            (_, NavAction::First) => 0,
-            (_, NavAction::Last) => count - 1,
-        };
-        focusable.get(next).map(|(e, _)| e).copied()
     }
 
     /// Gather all focusable entities in tree order.
@@ -252,6 +451,34 @@ fn handle_auto_focus(
     }
 }
 
+fn push_focus_scopes(
+    focus: Res<Focus>,
+    mut history: ResMut<FocusHistory>,
+    query: Query<Entity, Added<FocusScope>>,
+) {
+    for scope in query.iter() {
+        history.push(scope, focus.0);
+    }
+}
+
+fn restore_focus_scopes(
+    mut focus: ResMut<Focus>,
+    mut history: ResMut<FocusHistory>,
+    mut removed: RemovedComponents<FocusScope>,
+    nodes: Query<(), With<Node>>,
+) {
+    for scope in removed.read() {
+        let saved = history.remove(scope);
+        // Don't steal focus if it has already moved to another live entity.
+        let focus_lost = focus.0.map_or(true, |current| !nodes.contains(current));
+        if let Some(saved) = saved {
+            if focus_lost && nodes.contains(saved) {
+                focus.0 = Some(saved);
+            }
+        }
+    }
+}
+
 fn handle_tab(
     nav: TabNavigation,
     key: Res<ButtonInput<KeyCode>>,
@@ -274,6 +501,88 @@ fn handle_tab(
     }
 }
 
+/// Deflection of an analog stick required to trigger directional navigation.
+const STICK_THRESHOLD: f32 = 0.5;
+
+fn handle_gamepad_navigation(
+    nav: TabNavigation,
+    gamepads: Res<Gamepads>,
+    buttons: Res<ButtonInput<GamepadButton>>,
+    axes: Res<Axis<GamepadAxis>>,
+    mut focus: ResMut<Focus>,
+    mut visible: ResMut<FocusVisible>,
+    mut press_writer: EventWriter<KeyPressEvent>,
+    mut stick_direction: Local<Option<NavDirection>>,
+) {
+    let mut direction: Option<NavDirection> = None;
+    let mut stick: Option<NavDirection> = None;
+    for gamepad in gamepads.iter() {
+        let pressed = |button_type| buttons.just_pressed(GamepadButton::new(gamepad, button_type));
+        if pressed(GamepadButtonType::DPadUp) {
+            direction = Some(NavDirection::Up);
+        } else if pressed(GamepadButtonType::DPadDown) {
+            direction = Some(NavDirection::Down);
+        } else if pressed(GamepadButtonType::DPadLeft) {
+            direction = Some(NavDirection::Left);
+        } else if pressed(GamepadButtonType::DPadRight) {
+            direction = Some(NavDirection::Right);
+        }
+
+        // The stick only navigates once each time it is pushed past the threshold.
+        let x = axes
+            .get(GamepadAxis::new(gamepad, GamepadAxisType::LeftStickX))
+            .unwrap_or(0.);
+        let y = axes
+            .get(GamepadAxis::new(gamepad, GamepadAxisType::LeftStickY))
+            .unwrap_or(0.);
+        if x.abs().max(y.abs()) >= STICK_THRESHOLD {
+            stick = Some(if x.abs() > y.abs() {
+                if x > 0. {
+                    NavDirection::Right
+                } else {
+                    NavDirection::Left
+                }
+            } else if y > 0. {
+                // Stick Y axis points up.
+                NavDirection::Up
+            } else {
+                NavDirection::Down
+            });
+        }
+
+        // Activate and cancel are delivered to the focused widget as Enter and Escape.
+        if let Some(focus_elt) = focus.0 {
+            for (button_type, key_code) in [
+                (GamepadButtonType::South, KeyCode::Enter),
+                (GamepadButtonType::East, KeyCode::Escape),
+            ] {
+                if pressed(button_type) {
+                    press_writer.send(KeyPressEvent {
+                        target: focus_elt,
+                        key_code,
+                        repeat: false,
+                        shift: false,
+                        ctrl: false,
+                    });
+                }
+            }
+        }
+    }
+
+    if stick != *stick_direction {
+        *stick_direction = stick;
+        direction = direction.or(stick);
+    }
+
+    if let Some(direction) = direction {
+        let next = nav.navigate_direction(focus.0, direction);
+        if next.is_some() {
+            focus.0 = next;
+            visible.0 = true;
+        }
+    }
+}
+
 fn handle_text_input(
     mut key_events: EventReader<KeyboardInput>,
     key: Res<ButtonInput<KeyCode>>,
@@ -289,9 +598,15 @@ fn handle_text_input(
                     key_code: ev.key_code,
                     repeat: !key.just_pressed(ev.key_code),
                     shift: key.pressed(KeyCode::ShiftLeft) || key.pressed(KeyCode::ShiftRight),
+                    ctrl: key.pressed(KeyCode::ControlLeft) || key.pressed(KeyCode::ControlRight),
                 };
                 press_writer.send(press_event);
 
+                // Characters typed with the shortcut modifier held are commands, not text.
+                if crate::clipboard::shortcut_modifier(&key) {
+                    continue;
+                }
+
                 if let bevy::input::keyboard::Key::Character(ref ch) = ev.logical_key {
                     let ev = KeyCharEvent {
                         target: focus_elt,
@@ -304,7 +619,7 @@ fn handle_text_input(
     }
 }
 
-/// Plugin for handling keyboard input.
+/// Plugin for handling keyboard and gamepad input.
 pub struct KeyboardInputPlugin;
 
 impl Plugin for KeyboardInputPlugin {
@@ -314,8 +629,19 @@ impl Plugin for KeyboardInputPlugin {
             EventListenerPlugin::<KeyPressEvent>::default(),
         ))
         .init_resource::<FocusVisible>()
+        .init_resource::<FocusHistory>()
         .add_event::<KeyPressEvent>()
         .add_event::<KeyCharEvent>()
-        .add_systems(Update, (handle_auto_focus, handle_tab, handle_text_input));
+        .add_systems(
+            Update,
+            (
+                // Scopes have to record the focus before an autofocused entity inside them
+                // takes it.
+                (push_focus_scopes, restore_focus_scopes, handle_auto_focus).chain(),
+                handle_tab,
+                handle_gamepad_navigation,
+                handle_text_input,
+            ),
+        );
     }
 }
@@ -0,0 +1,487 @@
+use std::collections::BTreeMap;
+
+use bevy::prelude::*;
+#[cfg(feature = "serialize")]
+use serde::{Deserialize, Serialize};
+
+/// Identifies a node within a [`GraphModel`]. Ids are not reused within a model, so they remain
+/// valid in the undo history after the node has been removed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct GraphNodeId(pub u64);
+
+/// Identifies a port on a node: the index of one of its inputs or outputs, depending on
+/// context.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct GraphPortRef {
+    /// The node which owns the port.
+    pub node: GraphNodeId,
+    /// The index of the port within the node's inputs or outputs.
+    pub port: usize,
+}
+
+impl GraphPortRef {
+    /// Construct a new port reference.
+    pub fn new(node: GraphNodeId, port: usize) -> Self {
+        Self { node, port }
+    }
+}
+
+/// An input or output port of a node.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct GraphPort {
+    /// The label of the port.
+    pub name: String,
+    /// Name of the type of data carried by the port. Only ports with the same data type can be
+    /// connected.
+    pub data_type: String,
+}
+
+impl GraphPort {
+    /// Construct a new port.
+    pub fn new(name: impl Into<String>, data_type: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            data_type: data_type.into(),
+        }
+    }
+}
+
+/// A node within a [`GraphModel`].
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct GraphNode {
+    /// The title of the node.
+    pub title: String,
+    /// The coordinates of the node's upper-left corner.
+    pub position: Vec2,
+    /// Input ports, which accept at most one connection each.
+    pub inputs: Vec<GraphPort>,
+    /// Output ports, which can be connected to any number of inputs.
+    pub outputs: Vec<GraphPort>,
+}
+
+impl GraphNode {
+    /// Construct a new node with the given title and position, and no ports.
+    pub fn new(title: impl Into<String>, position: Vec2) -> Self {
+        Self {
+            title: title.into(),
+            position,
+            ..default()
+        }
+    }
+
+    /// Add an input port to the node.
+    pub fn with_input(mut self, port: GraphPort) -> Self {
+        self.inputs.push(port);
+        self
+    }
+
+    /// Add an output port to the node.
+    pub fn with_output(mut self, port: GraphPort) -> Self {
+        self.outputs.push(port);
+        self
+    }
+}
+
+/// A connection from an output port to an input port.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct GraphConnection {
+    /// The output port which the connection starts from.
+    pub src: GraphPortRef,
+    /// The input port which the connection ends at.
+    pub dst: GraphPortRef,
+}
+
+/// An edit to a [`GraphModel`]. Edits are applied with [`GraphModel::execute`], which records
+/// them in the model's undo history.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GraphCommand {
+    /// Add a node with the given id, which should be allocated with
+    /// [`GraphModel::next_node_id`].
+    AddNode(GraphNodeId, GraphNode),
+    /// Remove a node along with all of its connections.
+    RemoveNode(GraphNodeId),
+    /// Move a node to a new position.
+    MoveNode(GraphNodeId, Vec2),
+    /// Connect an output to an input, replacing any existing connection to the input.
+    Connect(GraphConnection),
+    /// Remove a connection.
+    Disconnect(GraphConnection),
+}
+
+/// Undo and redo stacks for a [`GraphModel`]. Each entry holds the commands which reverse
+/// one step.
+#[derive(Debug, Clone, PartialEq, Default)]
+struct GraphHistory {
+    undo: Vec<Vec<GraphCommand>>,
+    redo: Vec<Vec<GraphCommand>>,
+    /// Node whose moves are being merged into the last undo entry.
+    merging: Option<GraphNodeId>,
+}
+
+/// A serializable data model for a node graph: the nodes, their ports and positions, and the
+/// connections between them. The model is kept separate from the view, which is built
+/// reactively from it (see [`GraphModelDisplay`](crate::controls::GraphModelDisplay)), so it
+/// can be saved, loaded and edited without any UI.
+///
+/// The model is a component, so an app can have several graphs open at once: insert it on an
+/// entity and pass that entity to the display.
+///
+/// All edits go through [`execute`](GraphModel::execute), which makes them undoable. The
+/// undo history is not serialized.
+#[derive(Component, Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct GraphModel {
+    nodes: BTreeMap<GraphNodeId, GraphNode>,
+    connections: Vec<GraphConnection>,
+    next_id: u64,
+    #[cfg_attr(feature = "serialize", serde(skip))]
+    history: GraphHistory,
+}
+
+impl GraphModel {
+    /// Construct a new, empty graph.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allocate an id for a new node.
+    pub fn next_node_id(&mut self) -> GraphNodeId {
+        let id = GraphNodeId(self.next_id);
+        self.next_id += 1;
+        id
+    }
+
+    /// Return the node with the given id.
+    pub fn node(&self, id: GraphNodeId) -> Option<&GraphNode> {
+        self.nodes.get(&id)
+    }
+
+    /// Iterate over the nodes of the graph, in order of id.
+    pub fn nodes(&self) -> impl Iterator<Item = (GraphNodeId, &GraphNode)> {
+        self.nodes.iter().map(|(id, node)| (*id, node))
+    }
+
+    /// Return the ids of all nodes, in order.
+    pub fn node_ids(&self) -> Vec<GraphNodeId> {
+        self.nodes.keys().copied().collect()
+    }
+
+    /// Return the list of connections.
+    pub fn connections(&self) -> &[GraphConnection] {
+        &self.connections
+    }
+
+    /// Return the connection to the given input, if any.
+    pub fn input_connection(&self, input: GraphPortRef) -> Option<GraphConnection> {
+        self.connections.iter().find(|c| c.dst == input).copied()
+    }
+
+    /// Apply a command, recording it as a single step in the undo history. Returns false if
+    /// the command was invalid or had no effect, in which case nothing is recorded.
+    pub fn execute(&mut self, command: GraphCommand) -> bool {
+        self.history.merging = None;
+        self.execute_step(command)
+    }
+
+    /// Apply a command like [`execute`](GraphModel::execute), but if it moves the same node
+    /// as the previous merged command, combine the two into one step of the undo history.
+    /// This is used while dragging, so that the whole drag is undone at once. Call
+    /// [`end_merge`](GraphModel::end_merge) when the drag ends.
+    pub fn execute_merged(&mut self, command: GraphCommand) -> bool {
+        let node = match command {
+            GraphCommand::MoveNode(node, _) => node,
+            _ => return self.execute(command),
+        };
+        if self.history.merging != Some(node) {
+            let changed = self.execute_step(command);
+            if changed {
+                self.history.merging = Some(node);
+            }
+            return changed;
+        }
+        // Keep the inverse from the start of the merge.
+        self.apply(command).is_some()
+    }
+
+    /// Stop merging commands into the last step of the undo history.
+    pub fn end_merge(&mut self) {
+        self.history.merging = None;
+    }
+
+    /// Whether there is a step which can be undone.
+    pub fn can_undo(&self) -> bool {
+        !self.history.undo.is_empty()
+    }
+
+    /// Whether there is a step which can be redone.
+    pub fn can_redo(&self) -> bool {
+        !self.history.redo.is_empty()
+    }
+
+    /// Undo the most recent step. Returns false if there is nothing to undo.
+    pub fn undo(&mut self) -> bool {
+        self.history.merging = None;
+        let Some(step) = self.history.undo.pop() else {
+            return false;
+        };
+        let inverse = self.apply_all(step);
+        self.history.redo.push(inverse);
+        true
+    }
+
+    /// Redo the most recently undone step. Returns false if there is nothing to redo.
+    pub fn redo(&mut self) -> bool {
+        self.history.merging = None;
+        let Some(step) = self.history.redo.pop() else {
+            return false;
+        };
+        let inverse = self.apply_all(step);
+        self.history.undo.push(inverse);
+        true
+    }
+
+    /// Apply a command and push its inverse onto the undo stack.
+    fn execute_step(&mut self, command: GraphCommand) -> bool {
+        match self.apply(command) {
+            Some(inverse) => {
+                self.history.undo.push(inverse);
+                self.history.redo.clear();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Apply a list of commands, returning the commands which reverse all of them.
+    fn apply_all(&mut self, commands: Vec<GraphCommand>) -> Vec<GraphCommand> {
+        let mut inverse = Vec::new();
+        for command in commands {
+            if let Some(mut undo) = self.apply(command) {
+                undo.append(&mut inverse);
+                inverse = undo;
+            }
+        }
+        inverse
+    }
+
+    /// Apply a single command without recording it, returning the commands which reverse it,
+    /// or `None` if the command was invalid or had no effect.
+    fn apply(&mut self, command: GraphCommand) -> Option<Vec<GraphCommand>> {
+        match command {
+            GraphCommand::AddNode(id, node) => {
+                if self.nodes.contains_key(&id) {
+                    return None;
+                }
+                self.next_id = self.next_id.max(id.0 + 1);
+                self.nodes.insert(id, node);
+                Some(vec![GraphCommand::RemoveNode(id)])
+            }
+
+            GraphCommand::RemoveNode(id) => {
+                let node = self.nodes.remove(&id)?;
+                // Restore the node before its connections.
+                let mut inverse = vec![GraphCommand::AddNode(id, node)];
+                self.connections.retain(|c| {
+                    let attached = c.src.node == id || c.dst.node == id;
+                    if attached {
+                        inverse.push(GraphCommand::Connect(*c));
+                    }
+                    !attached
+                });
+                Some(inverse)
+            }
+
+            GraphCommand::MoveNode(id, position) => {
+                let node = self.nodes.get_mut(&id)?;
+                if node.position == position {
+                    return None;
+                }
+                let previous = std::mem::replace(&mut node.position, position);
+                Some(vec![GraphCommand::MoveNode(id, previous)])
+            }
+
+            GraphCommand::Connect(connection) => {
+                let src = self.nodes.get(&connection.src.node)?;
+                let dst = self.nodes.get(&connection.dst.node)?;
+                let output = src.outputs.get(connection.src.port)?;
+                let input = dst.inputs.get(connection.dst.port)?;
+                if output.data_type != input.data_type || connection.src.node == connection.dst.node
+                {
+                    return None;
+                }
+                let mut inverse = vec![GraphCommand::Disconnect(connection)];
+                match self.input_connection(connection.dst) {
+                    Some(existing) if existing == connection => return None,
+                    Some(existing) => {
+                        self.connections.retain(|c| *c != existing);
+                        inverse.push(GraphCommand::Connect(existing));
+                    }
+                    None => {}
+                }
+                self.connections.push(connection);
+                Some(inverse)
+            }
+
+            GraphCommand::Disconnect(connection) => {
+                let index = self.connections.iter().position(|c| *c == connection)?;
+                self.connections.remove(index);
+                Some(vec![GraphCommand::Connect(connection)])
+            }
+        }
+    }
+}
+
+#[cfg(feature = "serialize")]
+impl GraphModel {
+    /// Serialize the graph to a JSON string.
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Deserialize a graph from a JSON string. The undo history of the result is empty.
+    pub fn from_json(text: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(text)
+    }
+
+    /// Save the graph to a JSON file.
+    pub fn save(&self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+        std::fs::write(path, self.to_json()?)
+    }
+
+    /// Load a graph from a JSON file.
+    pub fn load(path: impl AsRef<std::path::Path>) -> std::io::Result<Self> {
+        let text = std::fs::read_to_string(path)?;
+        Ok(Self::from_json(&text)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A graph with two nodes, each with one input and one output of the same type.
+    fn two_nodes() -> (GraphModel, GraphNodeId, GraphNodeId) {
+        let mut model = GraphModel::new();
+        let node = || {
+            GraphNode::new("Node", Vec2::ZERO)
+                .with_input(GraphPort::new("in", "f32"))
+                .with_output(GraphPort::new("out", "f32"))
+        };
+        let a = model.next_node_id();
+        let b = model.next_node_id();
+        assert!(model.execute(GraphCommand::AddNode(a, node())));
+        assert!(model.execute(GraphCommand::AddNode(b, node())));
+        (model, a, b)
+    }
+
+    fn connection(src: GraphNodeId, dst: GraphNodeId) -> GraphConnection {
+        GraphConnection {
+            src: GraphPortRef::new(src, 0),
+            dst: GraphPortRef::new(dst, 0),
+        }
+    }
+
+    #[test]
+    fn test_execute_undo_redo() {
+        let (mut model, a, b) = two_nodes();
+        assert!(model.execute(GraphCommand::Connect(connection(a, b))));
+        assert!(model.execute(GraphCommand::MoveNode(a, Vec2::new(10., 20.))));
+        // Commands which have no effect are not recorded.
+        assert!(!model.execute(GraphCommand::MoveNode(a, Vec2::new(10., 20.))));
+        assert!(!model.execute(GraphCommand::Connect(connection(a, a))));
+
+        // Removing a node also removes its connections, and undoing restores both.
+        let before = model.clone();
+        assert!(model.execute(GraphCommand::RemoveNode(b)));
+        assert!(model.node(b).is_none());
+        assert!(model.connections().is_empty());
+        assert!(model.undo());
+        assert_eq!(model.node(b), before.node(b));
+        assert_eq!(model.connections(), &[connection(a, b)]);
+
+        assert!(model.redo());
+        assert!(model.node(b).is_none());
+        assert!(model.undo());
+
+        assert!(model.undo());
+        assert_eq!(model.node(a).unwrap().position, Vec2::ZERO);
+        assert!(model.undo());
+        assert!(model.connections().is_empty());
+        assert!(model.undo());
+        assert!(model.undo());
+        assert!(!model.can_undo());
+        assert!(!model.undo());
+        assert_eq!(model.nodes().count(), 0);
+
+        // A new edit discards the redo stack.
+        assert!(model.redo());
+        assert!(model.can_redo());
+        let c = model.next_node_id();
+        assert!(model.execute(GraphCommand::AddNode(c, GraphNode::default())));
+        assert!(!model.can_redo());
+        // Ids are not reused after an undo.
+        assert!(model.next_node_id() > c);
+    }
+
+    #[test]
+    fn test_execute_merged() {
+        let (mut model, a, b) = two_nodes();
+        for x in 1..=3 {
+            let position = Vec2::new(x as f32, 0.);
+            assert!(model.execute_merged(GraphCommand::MoveNode(a, position)));
+        }
+        model.end_merge();
+        assert!(model.execute_merged(GraphCommand::MoveNode(a, Vec2::new(5., 0.))));
+        // Moving another node starts a new step.
+        assert!(model.execute_merged(GraphCommand::MoveNode(b, Vec2::new(1., 1.))));
+
+        assert!(model.undo());
+        assert_eq!(model.node(b).unwrap().position, Vec2::ZERO);
+        assert!(model.undo());
+        assert_eq!(model.node(a).unwrap().position, Vec2::new(3., 0.));
+        // The whole drag is undone at once.
+        assert!(model.undo());
+        assert_eq!(model.node(a).unwrap().position, Vec2::ZERO);
+
+        assert!(model.redo());
+        assert_eq!(model.node(a).unwrap().position, Vec2::new(3., 0.));
+    }
+
+    #[test]
+    fn test_connect_replaces_input() {
+        let (mut model, a, b) = two_nodes();
+        let c = model.next_node_id();
+        let node = GraphNode::new("Source", Vec2::ZERO).with_output(GraphPort::new("out", "f32"));
+        assert!(model.execute(GraphCommand::AddNode(c, node)));
+        assert!(model.execute(GraphCommand::Connect(connection(a, b))));
+
+        // An input accepts only one connection, so connecting another output replaces it.
+        assert!(model.execute(GraphCommand::Connect(connection(c, b))));
+        assert_eq!(model.connections(), &[connection(c, b)]);
+        assert_eq!(
+            model.input_connection(GraphPortRef::new(b, 0)),
+            Some(connection(c, b))
+        );
+
+        // Undoing restores the replaced connection.
+        assert!(model.undo());
+        assert_eq!(model.connections(), &[connection(a, b)]);
+        assert!(model.redo());
+        assert_eq!(model.connections(), &[connection(c, b)]);
+
+        // Ports must exist and have matching types.
+        let d = model.next_node_id();
+        let node = GraphNode::new("Text", Vec2::ZERO).with_output(GraphPort::new("out", "str"));
+        assert!(model.execute(GraphCommand::AddNode(d, node)));
+        assert!(!model.execute(GraphCommand::Connect(connection(d, a))));
+        assert!(!model.execute(GraphCommand::Connect(GraphConnection {
+            src: GraphPortRef::new(c, 1),
+            dst: GraphPortRef::new(a, 0),
+        })));
+    }
+}
@@ -0,0 +1,37 @@
+use bevy::prelude::*;
+use bevy_reactor::{Cx, RunContextSetup, Signal};
+
+/// Context component inserted by containers such as [`Form`](crate::controls::Form) and
+/// [`Dialog`](crate::controls::Dialog), which disables all of the controls within them. The
+/// signal is already combined with the disabled state of any enclosing container.
+#[derive(Component, Clone, Copy)]
+pub struct DisabledContext(pub Signal<bool>);
+
+/// Methods to propagate a disabled state from a container to the controls within it.
+pub trait CreateDisabledSignal {
+    /// Disable all of the controls within the current view while `disabled` is true, in
+    /// addition to any which are disabled by an enclosing container.
+    fn provide_disabled(&mut self, disabled: Signal<bool>);
+
+    /// Signal that returns true when either `disabled` is true, or the control is within a
+    /// disabled container. Controls should use this instead of their own disabled signal, for
+    /// both their appearance and their pointer and keyboard handlers.
+    fn create_disabled_signal(&mut self, disabled: Signal<bool>) -> Signal<bool>;
+}
+
+impl<'p, 'w> CreateDisabledSignal for Cx<'p, 'w> {
+    fn provide_disabled(&mut self, disabled: Signal<bool>) {
+        let disabled = self.create_disabled_signal(disabled);
+        self.insert(DisabledContext(disabled));
+    }
+
+    fn create_disabled_signal(&mut self, disabled: Signal<bool>) -> Signal<bool> {
+        match self.use_inherited_component::<DisabledContext>() {
+            Some(DisabledContext(inherited)) => {
+                let inherited = *inherited;
+                self.create_derived(move |cx| disabled.get(cx) || inherited.get(cx))
+            }
+            None => disabled,
+        }
+    }
+}
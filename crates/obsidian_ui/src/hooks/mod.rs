@@ -1,7 +1,15 @@
 mod bistable_transition;
+mod disabled_signal;
 mod focus_signal;
+mod pointer_position;
+mod theme_signal;
+mod tweened_signal;
 
 pub use bistable_transition::{
     BistableTransitionPlugin, BistableTransitionState, CreateBistableTransition,
 };
+pub use disabled_signal::{CreateDisabledSignal, DisabledContext};
 pub use focus_signal::CreateFocusSignal;
+pub use pointer_position::{CreatePointerPositionSignal, PointerPositionPlugin};
+pub use theme_signal::{CreateThemeSignal, ThemeContext, ThemeToken};
+pub use tweened_signal::{CreateTweenedSignal, Tweenable, TweenedSignalPlugin};
@@ -0,0 +1,73 @@
+use bevy::prelude::*;
+use bevy_mod_picking::{
+    focus::HoverMap,
+    pointer::{PointerId, PointerLocation},
+};
+use bevy_reactor::{Cx, RunContextRead, RunContextSetup, Signal};
+
+use crate::pointer_capture::PointerCapture;
+
+/// Plugin that updates the pointer position signals.
+pub struct PointerPositionPlugin;
+
+impl Plugin for PointerPositionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, update_pointer_positions);
+    }
+}
+
+/// Component which tracks the position of the pointer relative to an entity's node.
+#[derive(Default, Component)]
+pub(crate) struct PointerPosition(Option<Vec2>);
+
+fn update_pointer_positions(
+    hover_map: Option<Res<HoverMap>>,
+    capture: Option<Res<PointerCapture>>,
+    pointers: Query<(&PointerId, &PointerLocation)>,
+    mut query: Query<(Entity, &Node, &GlobalTransform, &mut PointerPosition)>,
+    parent_query: Query<&Parent>,
+) {
+    let location = pointers
+        .iter()
+        .find(|(id, _)| **id == PointerId::Mouse)
+        .and_then(|(_, location)| location.location.as_ref());
+    let hover_set = hover_map
+        .as_ref()
+        .and_then(|map| map.get(&PointerId::Mouse));
+    let captor = capture.and_then(|capture| capture.captor(PointerId::Mouse));
+    for (entity, node, transform, mut position) in query.iter_mut() {
+        let tracking = captor == Some(entity)
+            || hover_set.map_or(false, |map| {
+                map.keys().any(|ha| {
+                    *ha == entity || parent_query.iter_ancestors(*ha).any(|e| e == entity)
+                })
+            });
+        let local = match (tracking, location) {
+            (true, Some(location)) => Some(location.position - node.logical_rect(transform).min),
+            _ => None,
+        };
+        if position.0 != local {
+            position.0 = local;
+        }
+    }
+}
+
+/// Method to create a signal that tracks the position of the pointer relative to an entity.
+pub trait CreatePointerPositionSignal {
+    /// Signal that returns the position of the mouse pointer relative to the top-left corner
+    /// of the target's node, in logical pixels. Returns `None` when the pointer is not over
+    /// the target or one of its descendants, unless the target has captured the pointer.
+    fn create_pointer_position_signal(&mut self, target: Entity) -> Signal<Option<Vec2>>;
+}
+
+impl<'p, 'w> CreatePointerPositionSignal for Cx<'p, 'w> {
+    fn create_pointer_position_signal(&mut self, target: Entity) -> Signal<Option<Vec2>> {
+        self.world_mut()
+            .entity_mut(target)
+            .insert(PointerPosition::default());
+        self.create_derived(move |cx| {
+            cx.use_component::<PointerPosition>(target)
+                .and_then(|p| p.0)
+        })
+    }
+}
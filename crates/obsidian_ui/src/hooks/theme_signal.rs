@@ -0,0 +1,175 @@
+use bevy::{prelude::*, utils::HashMap};
+use bevy_reactor::{AccessibilityPreferences, Cx, Rcx, RunContextRead, RunContextSetup, Signal};
+
+use crate::colors;
+
+/// A named color of the theme, which controls resolve instead of using a fixed color. Tokens
+/// can be overridden for part of the UI with
+/// [`ThemeOverride`](crate::controls::ThemeOverride).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ThemeToken {
+    /// Color of text in inputs, and of icons and spinners.
+    Foreground,
+    /// Accent color, used for checked and active states.
+    Accent,
+    /// Focus outline color.
+    Focus,
+    /// Background of default buttons.
+    Button,
+    /// Background of primary buttons.
+    ButtonPrimary,
+    /// Background of danger buttons.
+    ButtonDanger,
+    /// Background of selected buttons.
+    ButtonSelected,
+}
+
+impl ThemeToken {
+    /// The color of the token when it is not overridden.
+    pub fn default_color(self) -> Srgba {
+        match self {
+            ThemeToken::Foreground => colors::FOREGROUND,
+            ThemeToken::Accent => colors::ACCENT,
+            ThemeToken::Focus => colors::FOCUS,
+            ThemeToken::Button => colors::U3,
+            ThemeToken::ButtonPrimary => colors::PRIMARY,
+            ThemeToken::ButtonDanger => colors::DESTRUCTIVE,
+            ThemeToken::ButtonSelected => colors::U4,
+        }
+    }
+
+    /// The color of the token when it is not overridden and the user prefers high contrast.
+    /// Text and icons are pure white, outlines are opaque, and backgrounds are darkened so
+    /// that white text stands out against them.
+    pub fn high_contrast_color(self) -> Srgba {
+        match self {
+            ThemeToken::Foreground => colors::U5,
+            ThemeToken::Accent => Srgba::new(0.4, 0.85, 1.0, 1.0),
+            ThemeToken::Focus => Srgba::new(0.4, 0.85, 1.0, 1.0),
+            ThemeToken::Button => colors::U1,
+            ThemeToken::ButtonPrimary => Srgba::new(0.145, 0.235, 0.341, 1.0),
+            ThemeToken::ButtonDanger => Srgba::new(0.400, 0.098, 0.180, 1.0),
+            ThemeToken::ButtonSelected => Srgba::new(0.0, 0.341, 0.537, 1.0),
+        }
+    }
+}
+
+/// Context component inserted by [`ThemeOverride`](crate::controls::ThemeOverride), which holds
+/// the tokens overridden for the views within it. Tokens which are not in the map are looked
+/// up in the enclosing overrides.
+#[derive(Component, Clone, Default)]
+pub struct ThemeContext(pub HashMap<ThemeToken, Signal<Srgba>>);
+
+/// Methods to override theme tokens for a subtree, and to resolve them.
+pub trait CreateThemeSignal {
+    /// Override theme tokens for the views within the current view. Tokens which are not
+    /// listed keep the values of any enclosing override.
+    fn provide_theme(&mut self, overrides: HashMap<ThemeToken, Signal<Srgba>>);
+
+    /// Signal that returns the color of `token`, taken from the nearest enclosing override, or
+    /// the token's default color if there is none. When
+    /// [`AccessibilityPreferences::high_contrast`] is set, the token's high contrast color is
+    /// used instead of the default. The signal reacts to overrides being added or changed, to
+    /// changes in the overriding signals, and to changes in the preference.
+    fn create_theme_color(&mut self, token: ThemeToken) -> Signal<Srgba>;
+}
+
+impl<'p, 'w> CreateThemeSignal for Cx<'p, 'w> {
+    fn provide_theme(&mut self, overrides: HashMap<ThemeToken, Signal<Srgba>>) {
+        self.insert(ThemeContext(overrides));
+    }
+
+    fn create_theme_color(&mut self, token: ThemeToken) -> Signal<Srgba> {
+        let owner = self.owner();
+        self.create_derived(move |rcx| theme_color(rcx, owner, token))
+    }
+}
+
+/// Resolve `token` from the [`ThemeContext`] of `entity` or its nearest ancestor which
+/// overrides it, falling back to the default or high contrast color of the token.
+fn theme_color(rcx: &Rcx, entity: Entity, token: ThemeToken) -> Srgba {
+    let mut ancestor = Some(entity);
+    while let Some(e) = ancestor {
+        if let Some(color) = rcx
+            .use_component::<ThemeContext>(e)
+            .and_then(|theme| theme.0.get(&token).copied())
+        {
+            return color.get(rcx);
+        }
+        ancestor = rcx.world().get::<Parent>(e).map(Parent::get);
+    }
+    let high_contrast = rcx.world().contains_resource::<AccessibilityPreferences>()
+        && rcx.use_resource::<AccessibilityPreferences>().high_contrast;
+    if high_contrast {
+        token.high_contrast_color()
+    } else {
+        token.default_color()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::color::palettes::css;
+    use bevy_reactor::TrackingScope;
+
+    use super::*;
+
+    #[test]
+    fn test_theme_color() {
+        let mut world = World::default();
+        let outer = world.spawn_empty().id();
+        let inner = world.spawn_empty().set_parent(outer).id();
+        let view = world.spawn_empty().set_parent(inner).id();
+        let mut scope = TrackingScope::new(world.change_tick());
+        let mut cx = Cx::new(&mut world, view, &mut scope);
+        let accent = cx.create_theme_color(ThemeToken::Accent);
+        let focus = cx.create_theme_color(ThemeToken::Focus);
+        assert_eq!(accent.get(&world), colors::ACCENT);
+
+        // Overrides which are added after the signal was created are picked up, and the
+        // nearest override of each token wins.
+        world
+            .entity_mut(outer)
+            .insert(ThemeContext(HashMap::from_iter([
+                (ThemeToken::Accent, Signal::Constant(css::RED)),
+                (ThemeToken::Focus, Signal::Constant(css::BLUE)),
+            ])));
+        world
+            .entity_mut(inner)
+            .insert(ThemeContext(HashMap::from_iter([(
+                ThemeToken::Accent,
+                Signal::Constant(css::GREEN),
+            )])));
+        assert_eq!(accent.get(&world), css::GREEN);
+        assert_eq!(focus.get(&world), css::BLUE);
+    }
+
+    #[test]
+    fn test_theme_color_high_contrast() {
+        let mut world = World::default();
+        world.init_resource::<AccessibilityPreferences>();
+        let view = world.spawn_empty().id();
+        let mut scope = TrackingScope::new(world.change_tick());
+        let mut cx = Cx::new(&mut world, view, &mut scope);
+        let button = cx.create_theme_color(ThemeToken::Button);
+        assert_eq!(button.get(&world), ThemeToken::Button.default_color());
+
+        world
+            .resource_mut::<AccessibilityPreferences>()
+            .high_contrast = true;
+        assert_eq!(button.get(&world), ThemeToken::Button.high_contrast_color());
+        assert_ne!(
+            ThemeToken::Button.high_contrast_color(),
+            ThemeToken::Button.default_color()
+        );
+
+        // Explicit overrides still win.
+        world
+            .entity_mut(view)
+            .insert(ThemeContext(HashMap::from_iter([(
+                ThemeToken::Button,
+                Signal::Constant(css::RED),
+            )])));
+        assert_eq!(button.get(&world), css::RED);
+    }
+}
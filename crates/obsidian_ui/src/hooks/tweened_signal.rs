@@ -0,0 +1,153 @@
+use bevy::prelude::*;
+use bevy_reactor::{
+    AccessibilityPreferences, Cx, IntoSignal, RunContextRead, RunContextSetup, Signal,
+};
+
+/// Plugin that advances tweened signals.
+pub struct TweenedSignalPlugin;
+
+impl Plugin for TweenedSignalPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                advance_tweens::<f32>,
+                advance_tweens::<Vec2>,
+                advance_tweens::<Vec3>,
+            ),
+        );
+    }
+}
+
+/// A value which can be smoothly interpolated by a tweened signal. This is implemented for
+/// `f32`, `Vec2` and `Vec3`.
+pub trait Tweenable: Copy + PartialEq + Send + Sync + 'static {
+    /// Interpolate between `self` and `other`, where `t` ranges from 0 to 1.
+    fn tween(self, other: Self, t: f32) -> Self;
+}
+
+impl Tweenable for f32 {
+    fn tween(self, other: Self, t: f32) -> Self {
+        self + (other - self) * t
+    }
+}
+
+impl Tweenable for Vec2 {
+    fn tween(self, other: Self, t: f32) -> Self {
+        self.lerp(other, t)
+    }
+}
+
+impl Tweenable for Vec3 {
+    fn tween(self, other: Self, t: f32) -> Self {
+        self.lerp(other, t)
+    }
+}
+
+/// The state of a tweened signal.
+#[derive(Component)]
+struct Tween<T: Tweenable> {
+    origin: T,
+    target: T,
+    current: T,
+    duration: f32,
+    clock: f32,
+}
+
+/// Trait which adds `create_tweened_signal` to [`Cx`].
+pub trait CreateTweenedSignal {
+    /// Create a signal which follows `target`, but which moves smoothly to each new value
+    /// rather than jumping to it. This can be used to animate objects to positions which are
+    /// computed all at once, such as the result of an automatic layout.
+    ///
+    /// # Arguments
+    /// * `target` - The value which the signal moves towards. The signal starts out with the
+    ///   initial value of `target`.
+    /// * `duration` - The duration of each transition, in seconds. While this is zero, changes
+    ///   to `target` take effect immediately, which is useful for suppressing the animation
+    ///   while a value is being dragged.
+    fn create_tweened_signal<T: Tweenable>(
+        &mut self,
+        target: impl IntoSignal<T>,
+        duration: impl IntoSignal<f32>,
+    ) -> Signal<T>;
+}
+
+impl<'w, 'p> CreateTweenedSignal for Cx<'w, 'p> {
+    fn create_tweened_signal<T: Tweenable>(
+        &mut self,
+        target: impl IntoSignal<T>,
+        duration: impl IntoSignal<f32>,
+    ) -> Signal<T> {
+        let target = target.into_signal();
+        let duration = duration.into_signal();
+
+        // Create an entity to hold the tween.
+        let entity = self.create_owned_entity();
+
+        // Effect which restarts the tween when the target changes.
+        self.create_effect(move |ve| {
+            let target = target.get(ve);
+            let duration = duration.get(ve).max(0.);
+            let mut entt = ve.world_mut().entity_mut(entity);
+            match entt.get_mut::<Tween<T>>() {
+                Some(mut tween) => {
+                    if tween.target != target {
+                        tween.origin = tween.current;
+                        tween.target = target;
+                        tween.clock = 0.;
+                    }
+                    tween.duration = duration;
+                    if duration == 0. {
+                        tween.current = target;
+                    }
+                }
+                None => {
+                    entt.insert(Tween {
+                        origin: target,
+                        target,
+                        current: target,
+                        duration,
+                        clock: duration,
+                    });
+                }
+            };
+        });
+
+        // Derived signal which returns the current value.
+        self.create_derived(move |cc| {
+            cc.use_component::<Tween<T>>(entity)
+                .map_or_else(|| target.get(cc), |tween| tween.current)
+        })
+    }
+}
+
+fn advance_tweens<T: Tweenable>(
+    mut query: Query<&mut Tween<T>>,
+    time: Res<Time>,
+    preferences: Option<Res<AccessibilityPreferences>>,
+) {
+    let reduced_motion = preferences.map_or(false, |p| p.reduced_motion);
+    for mut tween in query.iter_mut() {
+        if tween.current == tween.target {
+            continue;
+        }
+        // With reduced motion, skip straight to the end of the tween.
+        let step = match reduced_motion {
+            true => tween.duration,
+            false => time.delta_seconds(),
+        };
+        tween.clock = (tween.clock + step).min(tween.duration);
+        let t = if tween.duration > 0.0001 {
+            tween.clock / tween.duration
+        } else {
+            1.0
+        };
+        tween.current = if t >= 1.0 {
+            tween.target
+        } else {
+            // Ease in and out.
+            tween.origin.tween(tween.target, t * t * (3.0 - 2.0 * t))
+        };
+    }
+}
@@ -0,0 +1,288 @@
+use std::{borrow::Cow, fmt};
+
+use bevy::{
+    a11y::Focus,
+    app::{App, Plugin, Update},
+    ecs::{
+        event::{Event, EventWriter},
+        query::With,
+        system::{Query, Res, Resource},
+    },
+    input::{keyboard::KeyCode, ButtonInput},
+    utils::HashMap,
+};
+
+use crate::focus::TextEntry;
+
+/// A key pressed together with a set of modifier keys, such as Ctrl+Shift+S.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyChord {
+    /// The key which triggers the chord.
+    pub key: KeyCode,
+
+    /// Whether the platform's shortcut modifier must be held: Ctrl, or Cmd on macOS.
+    pub command: bool,
+
+    /// Whether Shift must be held.
+    pub shift: bool,
+
+    /// Whether Alt (Option on macOS) must be held.
+    pub alt: bool,
+}
+
+impl KeyChord {
+    /// Create a chord for a key pressed without modifiers.
+    pub fn new(key: KeyCode) -> Self {
+        Self {
+            key,
+            command: false,
+            shift: false,
+            alt: false,
+        }
+    }
+
+    /// Require the platform's shortcut modifier: Ctrl, or Cmd on macOS.
+    pub fn command(mut self) -> Self {
+        self.command = true;
+        self
+    }
+
+    /// Require the Shift key.
+    pub fn shift(mut self) -> Self {
+        self.shift = true;
+        self
+    }
+
+    /// Require the Alt key.
+    pub fn alt(mut self) -> Self {
+        self.alt = true;
+        self
+    }
+
+    /// True if the key of the chord was just pressed, while exactly the modifiers of the
+    /// chord are held.
+    pub fn just_pressed(&self, keys: &ButtonInput<KeyCode>) -> bool {
+        keys.just_pressed(self.key)
+            && self.command
+                == keys.any_pressed([
+                    KeyCode::ControlLeft,
+                    KeyCode::ControlRight,
+                    KeyCode::SuperLeft,
+                    KeyCode::SuperRight,
+                ])
+            && self.shift == keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight])
+            && self.alt == keys.any_pressed([KeyCode::AltLeft, KeyCode::AltRight])
+    }
+}
+
+impl From<KeyCode> for KeyChord {
+    fn from(key: KeyCode) -> Self {
+        Self::new(key)
+    }
+}
+
+impl fmt::Display for KeyChord {
+    /// Formats the chord the way it is displayed in menus, such as "Ctrl+Shift+S".
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.command {
+            let name = if cfg!(target_os = "macos") {
+                "Cmd"
+            } else {
+                "Ctrl"
+            };
+            write!(f, "{}+", name)?;
+        }
+        if self.shift {
+            write!(f, "Shift+")?;
+        }
+        if self.alt {
+            let name = if cfg!(target_os = "macos") {
+                "Option"
+            } else {
+                "Alt"
+            };
+            write!(f, "{}+", name)?;
+        }
+        write!(f, "{}", key_name(self.key))
+    }
+}
+
+/// Return a short display name for a key, such as "B" for `KeyCode::KeyB`.
+pub fn key_name(key: KeyCode) -> String {
+    let name = format!("{:?}", key);
+    match name
+        .strip_prefix("Key")
+        .or_else(|| name.strip_prefix("Digit"))
+    {
+        Some(short) => short.to_string(),
+        None => name,
+    }
+}
+
+/// Registry of the application's keyboard shortcuts, which binds named actions, such as
+/// `"file.save"`, to key chords. When a bound chord is pressed, a [`HotkeyEvent`] is sent for
+/// its action. Menu items display the chords of the actions they represent.
+#[derive(Resource, Default)]
+pub struct Hotkeys {
+    bindings: HashMap<Cow<'static, str>, KeyChord>,
+}
+
+impl Hotkeys {
+    /// Bind an action to a key chord, replacing any previous binding of the action. A chord
+    /// bound to more than one action triggers all of them.
+    pub fn bind(&mut self, action: impl Into<Cow<'static, str>>, chord: impl Into<KeyChord>) {
+        self.bindings.insert(action.into(), chord.into());
+    }
+
+    /// Remove the binding of an action.
+    pub fn unbind(&mut self, action: &str) {
+        self.bindings.remove(action);
+    }
+
+    /// Returns the chord bound to an action, if any.
+    pub fn chord(&self, action: &str) -> Option<KeyChord> {
+        self.bindings.get(action).copied()
+    }
+
+    /// Returns the actions bound to a chord.
+    pub fn actions(&self, chord: KeyChord) -> impl Iterator<Item = &str> {
+        self.bindings
+            .iter()
+            .filter(move |(_, c)| **c == chord)
+            .map(|(action, _)| action.as_ref())
+    }
+}
+
+/// Event sent when the key chord bound to an action in [`Hotkeys`] is pressed.
+#[derive(Clone, Debug, Event)]
+pub struct HotkeyEvent {
+    /// The name of the action.
+    pub action: Cow<'static, str>,
+}
+
+/// System which sends a [`HotkeyEvent`] when a bound chord is pressed. Chords without the
+/// command or Alt modifier are ignored while a [`TextEntry`] widget has focus, since those
+/// keys are used for typing.
+fn trigger_hotkeys(
+    keys: Option<Res<ButtonInput<KeyCode>>>,
+    hotkeys: Res<Hotkeys>,
+    focus: Option<Res<Focus>>,
+    text_entries: Query<(), With<TextEntry>>,
+    mut writer: EventWriter<HotkeyEvent>,
+) {
+    let Some(keys) = keys else {
+        return;
+    };
+    let typing = focus
+        .and_then(|focus| focus.0)
+        .is_some_and(|entity| text_entries.contains(entity));
+    for (action, chord) in hotkeys.bindings.iter() {
+        if typing && !chord.command && !chord.alt {
+            continue;
+        }
+        if chord.just_pressed(&keys) {
+            writer.send(HotkeyEvent {
+                action: action.clone(),
+            });
+        }
+    }
+}
+
+/// Plugin which provides the [`Hotkeys`] registry and sends [`HotkeyEvent`]s.
+pub struct HotkeysPlugin;
+
+impl Plugin for HotkeysPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Hotkeys>()
+            .add_event::<HotkeyEvent>()
+            .add_systems(Update, trigger_hotkeys);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::{event::Events, system::RunSystemOnce, world::World};
+
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(KeyChord::new(KeyCode::KeyB).to_string(), "B");
+        assert_eq!(KeyChord::new(KeyCode::F2).to_string(), "F2");
+        let command = if cfg!(target_os = "macos") {
+            "Cmd"
+        } else {
+            "Ctrl"
+        };
+        assert_eq!(
+            KeyChord::new(KeyCode::Digit1).command().shift().to_string(),
+            format!("{}+Shift+1", command)
+        );
+    }
+
+    #[test]
+    fn test_bindings() {
+        let mut hotkeys = Hotkeys::default();
+        let save = KeyChord::new(KeyCode::KeyS).command();
+        hotkeys.bind("file.save", save);
+        hotkeys.bind("edit.delete", KeyCode::Delete);
+        assert_eq!(hotkeys.chord("file.save"), Some(save));
+        assert_eq!(hotkeys.actions(save).collect::<Vec<_>>(), ["file.save"]);
+
+        // Rebinding replaces the previous chord.
+        hotkeys.bind("file.save", KeyChord::new(KeyCode::KeyS).command().alt());
+        assert_eq!(hotkeys.actions(save).count(), 0);
+
+        hotkeys.unbind("edit.delete");
+        assert_eq!(hotkeys.chord("edit.delete"), None);
+    }
+
+    #[test]
+    fn test_trigger_hotkeys() {
+        let mut world = World::new();
+        world.init_resource::<Events<HotkeyEvent>>();
+        world.init_resource::<Focus>();
+        let mut hotkeys = Hotkeys::default();
+        hotkeys.bind("file.save", KeyChord::new(KeyCode::KeyS).command());
+        hotkeys.bind("edit.delete", KeyCode::Delete);
+        world.insert_resource(hotkeys);
+
+        let press = |world: &mut World, keys: &[KeyCode]| -> Vec<String> {
+            let mut input = ButtonInput::<KeyCode>::default();
+            for key in keys {
+                input.press(*key);
+            }
+            world.insert_resource(input);
+            world.run_system_once(trigger_hotkeys);
+            world
+                .resource_mut::<Events<HotkeyEvent>>()
+                .drain()
+                .map(|ev| ev.action.to_string())
+                .collect()
+        };
+
+        // Modifiers must match exactly.
+        assert_eq!(press(&mut world, &[KeyCode::KeyS]), Vec::<String>::new());
+        assert_eq!(
+            press(&mut world, &[KeyCode::ControlLeft, KeyCode::KeyS]),
+            ["file.save"]
+        );
+        assert_eq!(
+            press(
+                &mut world,
+                &[KeyCode::ControlLeft, KeyCode::ShiftLeft, KeyCode::KeyS]
+            ),
+            Vec::<String>::new()
+        );
+        assert_eq!(press(&mut world, &[KeyCode::Delete]), ["edit.delete"]);
+
+        // Plain keys are left to a focused text input.
+        let input = world.spawn(TextEntry).id();
+        world.resource_mut::<Focus>().0 = Some(input);
+        assert_eq!(press(&mut world, &[KeyCode::Delete]), Vec::<String>::new());
+        assert_eq!(
+            press(&mut world, &[KeyCode::ControlLeft, KeyCode::KeyS]),
+            ["file.save"]
+        );
+    }
+}
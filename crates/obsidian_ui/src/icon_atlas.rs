@@ -0,0 +1,104 @@
+use bevy::{asset::AssetPath, prelude::*, utils::HashMap};
+
+/// A set of named icons packed into a single texture. Icons in an atlas share one image
+/// handle, so toolbars with many icons don't need an asset per icon, and the icons can be
+/// drawn together. Register atlases with [`RegisterIconAtlas::register_icon_atlas`], and
+/// display their icons with [`Icon::named`](crate::controls::Icon::named).
+pub struct IconAtlas {
+    texture: AssetPath<'static>,
+    size: UVec2,
+    regions: Vec<(String, URect)>,
+}
+
+impl IconAtlas {
+    /// Construct a new, empty [`IconAtlas`] for the texture at `texture`, which is `size`
+    /// pixels in size.
+    pub fn new(texture: impl Into<AssetPath<'static>>, size: UVec2) -> Self {
+        Self {
+            texture: texture.into(),
+            size,
+            regions: Vec::new(),
+        }
+    }
+
+    /// Add an icon occupying the given rectangle of the texture, in pixels.
+    pub fn region(mut self, name: impl Into<String>, rect: URect) -> Self {
+        self.regions.push((name.into(), rect));
+        self
+    }
+
+    /// Add icons laid out in a grid of `tile_size` cells, `columns` cells wide, starting from
+    /// the top-left corner of the texture. Icons are named in row-major order; use an empty
+    /// name to skip a cell.
+    pub fn grid(mut self, tile_size: UVec2, columns: u32, names: &[&str]) -> Self {
+        for (index, name) in (0u32..).zip(names) {
+            if name.is_empty() {
+                continue;
+            }
+            let min = UVec2::new(index % columns, index / columns) * tile_size;
+            self.regions
+                .push((name.to_string(), URect::from_corners(min, min + tile_size)));
+        }
+        self
+    }
+}
+
+/// An icon in a registered [`IconAtlas`].
+#[derive(Clone, Debug)]
+pub struct AtlasIcon {
+    /// The atlas texture.
+    pub texture: Handle<Image>,
+
+    /// The layout of the atlas.
+    pub layout: Handle<TextureAtlasLayout>,
+
+    /// The index of the icon within the layout.
+    pub index: usize,
+}
+
+/// Resource which holds the icons of all registered [`IconAtlas`]es, by name.
+#[derive(Resource, Default)]
+pub struct IconAtlases(HashMap<String, AtlasIcon>);
+
+impl IconAtlases {
+    /// Look up an icon by name.
+    pub fn get(&self, name: &str) -> Option<&AtlasIcon> {
+        self.0.get(name)
+    }
+}
+
+/// Method to register [`IconAtlas`]es.
+pub trait RegisterIconAtlas {
+    /// Register the icons in an atlas. If an icon has the same name as one which is already
+    /// registered, it replaces it.
+    fn register_icon_atlas(&mut self, atlas: IconAtlas) -> &mut Self;
+}
+
+impl RegisterIconAtlas for App {
+    fn register_icon_atlas(&mut self, atlas: IconAtlas) -> &mut Self {
+        self.init_resource::<IconAtlases>();
+        let world = self.world_mut();
+        let texture = world.resource::<AssetServer>().load(atlas.texture);
+        let mut layout = TextureAtlasLayout::new_empty(atlas.size);
+        let indices: Vec<(String, usize)> = atlas
+            .regions
+            .into_iter()
+            .map(|(name, rect)| (name, layout.add_texture(rect)))
+            .collect();
+        let layout = world
+            .resource_mut::<Assets<TextureAtlasLayout>>()
+            .add(layout);
+        let mut icons = world.resource_mut::<IconAtlases>();
+        for (name, index) in indices {
+            icons.0.insert(
+                name,
+                AtlasIcon {
+                    texture: texture.clone(),
+                    layout: layout.clone(),
+                    index,
+                },
+            );
+        }
+        self
+    }
+}
@@ -0,0 +1,32 @@
+use bevy::{prelude::*, utils::HashMap};
+
+/// The saved size of a resizable pane.
+#[derive(Reflect, Debug, Default, Clone, Copy, PartialEq)]
+pub struct PaneLayout {
+    /// The current size of the pane, in pixels. A size of zero means the pane is collapsed.
+    pub size: f32,
+    /// The size the pane had before it was collapsed, used to restore it.
+    pub restore_size: Option<f32>,
+}
+
+/// Resource which stores the sizes of resizable panes, keyed by a persistence key chosen by
+/// the app. This resource is registered for reflection, so it can be serialized along with the
+/// rest of the app's settings and re-inserted at startup, allowing layouts to survive restarts.
+#[derive(Resource, Reflect, Debug, Default, Clone)]
+#[reflect(Resource, Default)]
+pub struct UiLayoutState {
+    /// Pane sizes, by persistence key.
+    pub panes: HashMap<String, PaneLayout>,
+}
+
+impl UiLayoutState {
+    /// Return the saved layout for the given key, if any.
+    pub fn pane(&self, key: &str) -> Option<PaneLayout> {
+        self.panes.get(key).copied()
+    }
+
+    /// Save the layout for the given key.
+    pub fn set_pane(&mut self, key: impl Into<String>, layout: PaneLayout) {
+        self.panes.insert(key.into(), layout);
+    }
+}
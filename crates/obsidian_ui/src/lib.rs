@@ -2,16 +2,32 @@
 
 #![warn(missing_docs)]
 
-use bevy::{app::*, ui::UiMaterialPlugin};
-use bevy_mod_picking::prelude::EventListenerPlugin;
+use bevy::{
+    app::*,
+    asset::AssetApp,
+    ecs::schedule::IntoSystemConfigs,
+    ui::{UiMaterialPlugin, UiSystem},
+};
+use bevy_mod_picking::{picking_core::PickSet, prelude::EventListenerPlugin};
 use controls::MenuCloseEvent;
 use materials::{
-    DotGridMaterial, DrawPathMaterial, GradientRectMaterial, SliderRectMaterial, SwatchRectMaterial,
+    BackdropBlurMaterial, BoxShadowMaterial, DotGridMaterial, DrawPathMaterial,
+    GradientRectMaterial, ProgressBarMaterial, RoundedClipMaterial, SliderRectMaterial,
+    SpinnerMaterial, SwatchRectMaterial, TextDecorationMaterial,
 };
 
 /// Utilities for animation.
 pub mod animation;
 
+/// Frosted-glass backdrops which blur the content behind dialogs and menus.
+pub mod backdrop;
+
+/// Rendering of drop shadows set with `box_shadow` styles.
+mod box_shadow;
+
+/// Access to the system clipboard, and copy and paste events.
+pub mod clipboard;
+
 /// Module containing standard color definitions.
 #[allow(missing_docs)]
 pub mod colors;
@@ -19,24 +35,58 @@ pub mod colors;
 /// Module containing interactive and layout control widgets.
 pub mod controls;
 
+/// Standard drop shadows for surfaces which float above the background. Higher levels of
+/// elevation cast larger and softer shadows.
+pub mod elevation;
+
+/// Atlases of named icons which share a single texture.
+pub mod icon_atlas;
+
 /// Utilities for tabbing between widgets.
 pub mod focus;
 
+/// Serializable data model for node graphs, with undoable edits.
+pub mod graph_model;
+
 /// Utilities for floating popups.
 pub mod floating;
 
 /// Module containing extensions to `Cx`.
 pub mod hooks;
 
+/// Registry of named keyboard shortcuts.
+pub mod hotkeys;
+
+/// Persistent sizes of resizable panes.
+pub mod layout_state;
+
 /// Module containing custom materials.
 pub mod materials;
 
+/// Reflected widget props and view templates which can be declared in scenes.
+pub mod scene;
+
 /// Utilities for managing scrolling views.
 pub mod scrolling;
 
+/// Utilities for stacking popups.
+pub mod popup_stack;
+
+/// Utilities for capturing the pointer during drag interactions.
+pub mod pointer_capture;
+
+/// Shared selection state for lists, trees and graphs.
+pub mod selection;
+
 /// Module containing standard sizes.
 pub mod size;
 
+/// Loading and rasterizing SVG icons.
+pub mod svg_icon;
+
+/// Utilities for enlarging the hit areas of small controls, and recognizing touch gestures.
+pub mod touch_target;
+
 /// Module of utilities for embedding a 3D viewport in the 2D UI.
 pub mod viewport;
 
@@ -59,22 +109,86 @@ impl Plugin for ObsidianUiPlugin {
             UiMaterialPlugin::<SwatchRectMaterial>::default(),
             UiMaterialPlugin::<DotGridMaterial>::default(),
             UiMaterialPlugin::<DrawPathMaterial>::default(),
+            UiMaterialPlugin::<SpinnerMaterial>::default(),
+            UiMaterialPlugin::<ProgressBarMaterial>::default(),
+            UiMaterialPlugin::<TextDecorationMaterial>::default(),
+            UiMaterialPlugin::<RoundedClipMaterial>::default(),
+            UiMaterialPlugin::<BoxShadowMaterial>::default(),
+            UiMaterialPlugin::<BackdropBlurMaterial>::default(),
+        ))
+        .add_plugins((
             hooks::BistableTransitionPlugin,
+            hooks::PointerPositionPlugin,
+            hooks::TweenedSignalPlugin,
             animation::AnimatedTransitionPlugin,
             focus::KeyboardInputPlugin,
+            clipboard::ClipboardPlugin,
+            hotkeys::HotkeysPlugin,
+            pointer_capture::PointerCapturePlugin,
         ))
         .add_plugins((
             EventListenerPlugin::<ScrollWheel>::default(),
             EventListenerPlugin::<MenuCloseEvent>::default(),
         ))
         .add_event::<ScrollWheel>()
+        .init_resource::<touch_target::MinimumTouchTarget>()
+        .init_resource::<touch_target::TouchSettings>()
+        .init_resource::<touch_target::TouchGestures>()
+        .init_resource::<popup_stack::PopupStack>()
+        .init_resource::<popup_stack::WindowStack>()
+        .init_resource::<layout_state::UiLayoutState>()
+        .register_type::<layout_state::UiLayoutState>()
+        .init_resource::<controls::DockLayout>()
+        .init_resource::<controls::DockDragState>()
+        .register_type::<controls::DockLayout>()
+        .init_asset::<svg_icon::Svg>()
+        .register_asset_loader(svg_icon::SvgLoader)
+        .init_resource::<svg_icon::DisplayScale>()
+        .init_resource::<svg_icon::SvgIconCache>()
+        .init_resource::<backdrop::BackdropImage>()
+        .add_systems(
+            PreUpdate,
+            (
+                touch_target::update_touch_gestures,
+                controls::release_pinch_touches,
+            )
+                .in_set(PickSet::PostFocus),
+        )
         .add_systems(
             Update,
             (
                 scrolling::handle_scroll_events,
+                scrolling::sync_scroll_positions.before(scrolling::update_scroll_positions),
                 scrolling::update_scroll_positions,
+                scrolling::fade_scrollbars.after(scrolling::update_scroll_positions),
+                controls::update_sticky_headers.after(scrolling::update_scroll_positions),
+                controls::update_table_pins.after(scrolling::update_scroll_positions),
+                touch_target::update_touch_targets,
+                popup_stack::update_popup_stack,
+                popup_stack::update_window_stack,
+                popup_stack::close_top_popup_on_escape,
+                controls::trigger_tool_shortcuts,
+                controls::toggle_menu_bar_focus,
+                controls::poll_pending_tasks,
+                controls::poll_list_pages,
+                controls::update_graph_minimaps,
+                backdrop::update_backdrop_materials,
+                (
+                    svg_icon::update_display_scale,
+                    svg_icon::rasterize_svg_icons,
+                )
+                    .chain(),
             ),
         )
-        .add_systems(PostUpdate, floating::position_floating);
+        .add_systems(
+            PostUpdate,
+            (
+                floating::position_floating,
+                box_shadow::update_box_shadows.before(UiSystem::Layout),
+                box_shadow::unclip_box_shadows.after(bevy::ui::update::update_clipping_system),
+                controls::align_menu_shortcuts.before(UiSystem::Layout),
+                controls::update_clip_corners.before(UiSystem::Layout),
+            ),
+        );
     }
 }
@@ -0,0 +1,30 @@
+use bevy::prelude::*;
+use bevy::reflect::TypePath;
+use bevy::render::render_resource::*;
+
+/// Draws a blurred and tinted copy of an image of the content behind the UI. The node is
+/// assumed to cover the whole window, so that its pixels line up with the image's.
+#[derive(AsBindGroup, Asset, TypePath, Debug, Clone)]
+pub struct BackdropBlurMaterial {
+    #[uniform(0)]
+    pub(crate) tint: Vec4,
+    /// Blur radius in pixels, at full intensity.
+    #[uniform(1)]
+    pub(crate) radius: f32,
+    /// Strength of the effect, from 0 to 1.
+    #[uniform(2)]
+    pub(crate) intensity: f32,
+    /// 1 if `backdrop` holds an image of the content behind the UI, otherwise only the tint is
+    /// drawn.
+    #[uniform(3)]
+    pub(crate) has_backdrop: u32,
+    #[texture(4)]
+    #[sampler(5)]
+    pub(crate) backdrop: Handle<Image>,
+}
+
+impl UiMaterial for BackdropBlurMaterial {
+    fn fragment_shader() -> ShaderRef {
+        "obsidian_ui://shaders/backdrop_blur.wgsl".into()
+    }
+}
@@ -0,0 +1,24 @@
+use bevy::prelude::*;
+use bevy::reflect::TypePath;
+use bevy::render::render_resource::*;
+
+/// Draws a blurred drop shadow. The node is larger than the shadowed node by `blur` on each
+/// side, and is centered on the shadow; the shadowed node itself, displaced by `-offset`, is
+/// left unpainted.
+#[derive(AsBindGroup, Asset, TypePath, Debug, Clone)]
+pub struct BoxShadowMaterial {
+    #[uniform(0)]
+    pub(crate) color: Vec4,
+    #[uniform(1)]
+    pub(crate) radius: Vec4,
+    #[uniform(2)]
+    pub(crate) offset: Vec2,
+    #[uniform(3)]
+    pub(crate) blur: f32,
+}
+
+impl UiMaterial for BoxShadowMaterial {
+    fn fragment_shader() -> ShaderRef {
+        "obsidian_ui://shaders/box_shadow.wgsl".into()
+    }
+}
@@ -8,6 +8,36 @@ pub struct DotGridMaterial {
     pub(crate) color_bg: Vec4,
     #[uniform(1)]
     pub(crate) color_fg: Vec4,
+    #[uniform(2)]
+    pub(crate) color_major: Vec4,
+    #[uniform(3)]
+    pub(crate) spacing: f32, // Distance between minor grid points, before zooming.
+    #[uniform(4)]
+    pub(crate) dot_size: f32, // Dot diameter or line width, in screen pixels.
+    #[uniform(5)]
+    pub(crate) major_every: u32, // Number of minor cells per major cell, or 0 for none.
+    #[uniform(6)]
+    pub(crate) zoom: f32,
+    #[uniform(7)]
+    pub(crate) offset: Vec2, // Grid position at the top-left corner of the node.
+    #[uniform(8)]
+    pub(crate) lines: u32, // Draw grid lines instead of dots.
+}
+
+impl Default for DotGridMaterial {
+    fn default() -> Self {
+        Self {
+            color_bg: Vec4::ZERO,
+            color_fg: Vec4::ONE,
+            color_major: Vec4::ONE,
+            spacing: 16.,
+            dot_size: 1.5,
+            major_every: 0,
+            zoom: 1.,
+            offset: Vec2::ZERO,
+            lines: 0,
+        }
+    }
 }
 
 impl UiMaterial for DotGridMaterial {
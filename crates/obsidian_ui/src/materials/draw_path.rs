@@ -2,6 +2,10 @@ use bevy::prelude::*;
 use bevy::reflect::TypePath;
 use bevy::render::render_resource::*;
 
+/// Number of line segments used to approximate a quadratic curve when measuring or hit-testing
+/// a path on the CPU.
+const QUADRATIC_STEPS: usize = 16;
+
 /// An element within a stroked path.
 #[derive(Debug, Copy, Clone)]
 pub enum DrawablePathSegment {
@@ -13,36 +17,168 @@ pub enum DrawablePathSegment {
     Quadratic((Vec2, Vec2)),
 }
 
+/// How the open ends of a stroked path are drawn.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum PathCap {
+    /// The stroke extends past the end points by half the stroke width, with a rounded end.
+    #[default]
+    Round,
+    /// The stroke stops exactly at the end points.
+    Butt,
+}
+
 /// Defines a stroked path
 #[derive(Debug, Clone)]
 pub struct DrawablePath {
+    /// Color used for segments added after the next call to `set_color`.
     color: Srgba,
     width: f32,
-    commands: Vec<DrawablePathSegment>,
+    dash: Option<(f32, f32)>,
+    cap: PathCap,
+    commands: Vec<(DrawablePathSegment, Srgba)>,
 }
 
 impl DrawablePath {
+    /// Construct a new, empty path with the given stroke color and stroke width. The width is
+    /// in screen pixels.
     pub fn new(color: Srgba, width: f32) -> Self {
         Self {
             color,
             width,
+            dash: None,
+            cap: PathCap::default(),
             commands: Vec::new(),
         }
     }
 
+    /// Set the stroke color of the segments which are added after this call.
+    pub fn set_color(&mut self, color: Srgba) {
+        self.color = color;
+    }
+
+    /// Set the stroke width, in screen pixels.
+    pub fn set_width(&mut self, width: f32) {
+        self.width = width;
+    }
+
+    /// Draw the path as dashes of length `dash`, separated by gaps of length `gap`, measured
+    /// in screen pixels along the path. Each sub-path starts with a dash.
+    pub fn set_dash(&mut self, dash: f32, gap: f32) {
+        self.dash = Some((dash, gap));
+    }
+
+    /// Set how the open ends of the path are drawn.
+    pub fn set_cap(&mut self, cap: PathCap) {
+        self.cap = cap;
+    }
+
+    /// Start a new sub-path at `point`.
     pub fn move_to(&mut self, point: Vec2) {
-        self.commands.push(DrawablePathSegment::Move(point));
+        self.push(DrawablePathSegment::Move(point));
     }
 
+    /// Draw a straight line from the current position to `point`.
     pub fn line_to(&mut self, point: Vec2) {
-        self.commands.push(DrawablePathSegment::Line(point));
+        self.push(DrawablePathSegment::Line(point));
     }
 
+    /// Draw a quadratic curve from the current position to `point`.
     pub fn quadratic_to(&mut self, control: Vec2, point: Vec2) {
-        self.commands
-            .push(DrawablePathSegment::Quadratic((control, point)));
+        self.push(DrawablePathSegment::Quadratic((control, point)));
+    }
+
+    /// Draw an open arrowhead at the current position, pointing in the direction of the last
+    /// segment. `size` is the length of the sides of the arrowhead. Does nothing if the path
+    /// doesn't end with a line or curve.
+    pub fn arrowhead(&mut self, size: f32) {
+        let Some((tip, direction)) = self.end_tangent() else {
+            return;
+        };
+        let back = -direction * size;
+        let left = tip + Vec2::from_angle(0.5).rotate(back);
+        let right = tip + Vec2::from_angle(-0.5).rotate(back);
+        self.move_to(left);
+        self.line_to(tip);
+        self.line_to(right);
+    }
+
+    fn push(&mut self, segment: DrawablePathSegment) {
+        self.commands.push((segment, self.color));
+    }
+
+    /// The end point of the path and the direction of travel there, if the last segment is a
+    /// line or curve of non-zero length.
+    fn end_tangent(&self) -> Option<(Vec2, Vec2)> {
+        let mut prev = Vec2::ZERO;
+        let mut tangent = None;
+        for (segment, _) in &self.commands {
+            match *segment {
+                DrawablePathSegment::Move(point) => {
+                    tangent = None;
+                    prev = point;
+                }
+                DrawablePathSegment::Line(point) => {
+                    tangent = Some((point, (point - prev).normalize_or_zero()));
+                    prev = point;
+                }
+                DrawablePathSegment::Quadratic((control, point)) => {
+                    let from = if control == point { prev } else { control };
+                    tangent = Some((point, (point - from).normalize_or_zero()));
+                    prev = point;
+                }
+            }
+        }
+        tangent.filter(|(_, direction)| *direction != Vec2::ZERO)
+    }
+
+    /// Calls `f` with the start and end points of each straight piece of the path, with
+    /// quadratic curves subdivided into short lines.
+    fn for_each_line(&self, mut f: impl FnMut(Vec2, Vec2)) {
+        let mut prev = Vec2::ZERO;
+        for (segment, _) in &self.commands {
+            match *segment {
+                DrawablePathSegment::Move(point) => {
+                    prev = point;
+                }
+                DrawablePathSegment::Line(point) => {
+                    f(prev, point);
+                    prev = point;
+                }
+                DrawablePathSegment::Quadratic((control, point)) => {
+                    let start = prev;
+                    for step in 1..=QUADRATIC_STEPS {
+                        let next = quadratic_point(
+                            start,
+                            control,
+                            point,
+                            step as f32 / QUADRATIC_STEPS as f32,
+                        );
+                        f(prev, next);
+                        prev = next;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns the distance from `point` to the centerline of the path, or infinity if the
+    /// path has no lines or curves. Curves are approximated, so the result may be off by a
+    /// small fraction of a pixel.
+    pub fn distance(&self, point: Vec2) -> f32 {
+        let mut dist_sq = f32::INFINITY;
+        self.for_each_line(|a, b| {
+            dist_sq = dist_sq.min(distance_sq_to_line(point, a, b));
+        });
+        dist_sq.sqrt()
     }
 
+    /// Returns true if `point` lies within the stroke of the path, expanded by `tolerance`
+    /// pixels. Use this to hover or pick paths which are too thin to hit precisely.
+    pub fn hit_test(&self, point: Vec2, tolerance: f32) -> bool {
+        self.distance(point) <= self.width * 0.5 + tolerance
+    }
+
+    /// Returns the bounding rectangle of the path, including the stroke width.
     pub fn bounds(&self) -> Rect {
         if self.commands.is_empty() {
             return Rect::default();
@@ -51,7 +187,7 @@ impl DrawablePath {
             min: Vec2::splat(f32::INFINITY),
             max: Vec2::splat(f32::NEG_INFINITY),
         };
-        for segment in &self.commands {
+        for (segment, _) in &self.commands {
             match segment {
                 DrawablePathSegment::Move(point) | DrawablePathSegment::Line(point) => {
                     bounds = bounds.union_point(*point);
@@ -66,6 +202,21 @@ impl DrawablePath {
     }
 }
 
+fn quadratic_point(start: Vec2, control: Vec2, end: Vec2, t: f32) -> Vec2 {
+    start.lerp(control, t).lerp(control.lerp(end, t), t)
+}
+
+fn distance_sq_to_line(point: Vec2, a: Vec2, b: Vec2) -> f32 {
+    let pa = point - a;
+    let ba = b - a;
+    let len_sq = ba.length_squared();
+    if len_sq == 0. {
+        return pa.length_squared();
+    }
+    let h = (pa.dot(ba) / len_sq).clamp(0., 1.);
+    (pa - ba * h).length_squared()
+}
+
 /// Type of drawing operation for each path segment.
 enum PathCommandType {
     Move = 0,
@@ -74,56 +225,122 @@ enum PathCommandType {
     Quad2 = 3,
 }
 
+/// Flag set on a command whose segment begins a sub-path.
+const FLAG_START: u32 = 0x100;
+/// Flag set on a command whose segment ends a sub-path.
+const FLAG_END: u32 = 0x200;
+
 #[derive(ShaderType, Debug, Clone)]
 pub struct PathCommand {
+    /// The [`PathCommandType`], combined with the `FLAG_` bits.
     op: u32,
     point: Vec2,
+    /// Distance along the sub-path at the end of this command, used for dashes.
+    distance: f32,
+    /// Stroke color of the segment.
+    color: Vec4,
 }
 
 #[derive(AsBindGroup, Asset, TypePath, Debug, Clone, Default)]
 pub struct DrawPathMaterial {
-    /// Stroke color
+    /// Stroke width
     #[uniform(0)]
-    pub(crate) color: Vec4,
+    pub(crate) width: f32,
 
-    /// Stroke width
+    /// Dash and gap length, or zero for a solid stroke.
     #[uniform(1)]
-    pub(crate) width: f32,
+    pub(crate) dash: Vec2,
+
+    /// End cap style: 0 for round, 1 for butt.
+    #[uniform(2)]
+    pub(crate) cap: u32,
 
-    // #[uniform(2)]
-    #[storage(2, read_only)]
+    #[storage(3, read_only)]
     pub(crate) commands: Vec<PathCommand>,
 }
 
 impl DrawPathMaterial {
     pub fn update(&mut self, path: &DrawablePath) {
         let bounds = path.bounds();
-        self.color = path.color.to_vec4();
         self.width = path.width;
+        self.dash = path
+            .dash
+            .map_or(Vec2::ZERO, |(dash, gap)| Vec2::new(dash, gap.max(0.)));
+        self.cap = match path.cap {
+            PathCap::Round => 0,
+            PathCap::Butt => 1,
+        };
         self.commands.clear();
-        for segment in &path.commands {
-            match segment {
+        let mut prev = Vec2::ZERO;
+        let mut distance = 0.;
+        let mut at_start = true;
+        for (index, (segment, color)) in path.commands.iter().enumerate() {
+            let color = color.to_vec4();
+            let mut flags = 0;
+            if !matches!(segment, DrawablePathSegment::Move(_)) {
+                if at_start {
+                    flags |= FLAG_START;
+                }
+                if !matches!(
+                    path.commands.get(index + 1),
+                    Some((
+                        DrawablePathSegment::Line(_) | DrawablePathSegment::Quadratic(_),
+                        _
+                    ))
+                ) {
+                    flags |= FLAG_END;
+                }
+            }
+            match *segment {
                 DrawablePathSegment::Move(point) => {
+                    distance = 0.;
+                    at_start = true;
                     self.commands.push(PathCommand {
                         op: PathCommandType::Move as u32,
-                        point: *point - bounds.min,
+                        point: point - bounds.min,
+                        distance,
+                        color,
                     });
+                    prev = point;
                 }
                 DrawablePathSegment::Line(point) => {
+                    distance += prev.distance(point);
+                    at_start = false;
                     self.commands.push(PathCommand {
-                        op: PathCommandType::Line as u32,
-                        point: *point - bounds.min,
+                        op: PathCommandType::Line as u32 | flags,
+                        point: point - bounds.min,
+                        distance,
+                        color,
                     });
+                    prev = point;
                 }
                 DrawablePathSegment::Quadratic((control, point)) => {
+                    let start = distance;
+                    let mut last = prev;
+                    for step in 1..=QUADRATIC_STEPS {
+                        let next = quadratic_point(
+                            prev,
+                            control,
+                            point,
+                            step as f32 / QUADRATIC_STEPS as f32,
+                        );
+                        distance += last.distance(next);
+                        last = next;
+                    }
+                    at_start = false;
                     self.commands.push(PathCommand {
-                        op: PathCommandType::Quad1 as u32,
-                        point: *control - bounds.min,
+                        op: PathCommandType::Quad1 as u32 | flags,
+                        point: control - bounds.min,
+                        distance: start,
+                        color,
                     });
                     self.commands.push(PathCommand {
                         op: PathCommandType::Quad2 as u32,
-                        point: *point - bounds.min,
+                        point: point - bounds.min,
+                        distance,
+                        color,
                     });
+                    prev = point;
                 }
             }
         }
@@ -135,3 +352,112 @@ impl UiMaterial for DrawPathMaterial {
         "obsidian_ui://shaders/draw_path.wgsl".into()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn polyline() -> DrawablePath {
+        let mut path = DrawablePath::new(Srgba::WHITE, 2.);
+        path.move_to(Vec2::new(10., 10.));
+        path.line_to(Vec2::new(20., 10.));
+        path.line_to(Vec2::new(20., 30.));
+        path
+    }
+
+    #[test]
+    fn test_distance_and_hit_test() {
+        let path = polyline();
+        assert_eq!(path.distance(Vec2::new(15., 13.)), 3.);
+        assert_eq!(path.distance(Vec2::new(24., 20.)), 4.);
+        assert!(path.hit_test(Vec2::new(15., 11.), 0.));
+        assert!(!path.hit_test(Vec2::new(15., 13.), 0.));
+        assert!(path.hit_test(Vec2::new(15., 13.), 2.));
+
+        let empty = DrawablePath::new(Srgba::WHITE, 2.);
+        assert_eq!(empty.distance(Vec2::ZERO), f32::INFINITY);
+
+        // The apex of a symmetric quadratic curve is halfway to its control point.
+        let mut curve = DrawablePath::new(Srgba::WHITE, 2.);
+        curve.move_to(Vec2::new(0., 0.));
+        curve.quadratic_to(Vec2::new(10., 20.), Vec2::new(20., 0.));
+        assert!(curve.distance(Vec2::new(10., 10.)) < 0.01);
+        assert!(curve.distance(Vec2::new(10., 20.)) > 9.);
+    }
+
+    #[test]
+    fn test_bounds() {
+        let path = polyline();
+        let bounds = path.bounds();
+        assert_eq!(bounds.min, Vec2::new(9., 9.));
+        assert_eq!(bounds.max, Vec2::new(21., 31.));
+        assert_eq!(
+            DrawablePath::new(Srgba::WHITE, 2.).bounds(),
+            Rect::default()
+        );
+    }
+
+    #[test]
+    fn test_arrowhead() {
+        let mut path = polyline();
+        path.arrowhead(5.);
+        let points: Vec<Vec2> = path
+            .commands
+            .iter()
+            .skip(3)
+            .map(|(segment, _)| match segment {
+                DrawablePathSegment::Move(p) | DrawablePathSegment::Line(p) => *p,
+                DrawablePathSegment::Quadratic(_) => panic!("unexpected curve"),
+            })
+            .collect();
+        assert_eq!(points.len(), 3);
+        let tip = Vec2::new(20., 30.);
+        assert_eq!(points[1], tip);
+        // The barbs trail behind the tip, on either side of the path.
+        for barb in [points[0], points[2]] {
+            assert!((barb.distance(tip) - 5.).abs() < 0.001);
+            assert!(barb.y < tip.y);
+        }
+        assert!(points[0].x != points[2].x);
+
+        // Nothing to point along.
+        let mut empty = DrawablePath::new(Srgba::WHITE, 2.);
+        empty.move_to(Vec2::ZERO);
+        empty.arrowhead(5.);
+        assert_eq!(empty.commands.len(), 1);
+    }
+
+    #[test]
+    fn test_update_material() {
+        let mut path = polyline();
+        path.set_dash(4., -1.);
+        path.set_cap(PathCap::Butt);
+        path.set_color(Srgba::BLACK);
+        path.move_to(Vec2::new(30., 10.));
+        path.line_to(Vec2::new(30., 20.));
+
+        let mut material = DrawPathMaterial::default();
+        material.update(&path);
+        assert_eq!(material.width, 2.);
+        assert_eq!(material.dash, Vec2::new(4., 0.));
+        assert_eq!(material.cap, 1);
+
+        let ops: Vec<u32> = material.commands.iter().map(|c| c.op).collect();
+        assert_eq!(
+            ops,
+            vec![
+                PathCommandType::Move as u32,
+                PathCommandType::Line as u32 | FLAG_START,
+                PathCommandType::Line as u32 | FLAG_END,
+                PathCommandType::Move as u32,
+                PathCommandType::Line as u32 | FLAG_START | FLAG_END,
+            ]
+        );
+        // Distances restart with each sub-path, and points are relative to the bounds.
+        let distances: Vec<f32> = material.commands.iter().map(|c| c.distance).collect();
+        assert_eq!(distances, vec![0., 10., 30., 0., 10.]);
+        assert_eq!(material.commands[0].point, Vec2::new(1., 1.));
+        assert_eq!(material.commands[4].color, Srgba::BLACK.to_vec4());
+        assert_eq!(material.commands[2].color, Srgba::WHITE.to_vec4());
+    }
+}
@@ -8,8 +8,39 @@ pub(crate) struct GradientRectMaterial {
     pub(crate) num_color_stops: i32,
     #[uniform(1)]
     pub(crate) color_stops: [Vec4; 8],
+    /// Positions of the color stops along the gradient, from 0 to 1, packed four to a vector.
+    #[uniform(2)]
+    pub(crate) stop_positions: [Vec4; 2],
     #[uniform(3)]
     pub(crate) cap_size: f32,
+    #[uniform(4)]
+    pub(crate) flags: u32,
+    #[uniform(5)]
+    pub(crate) exposure: f32,
+}
+
+impl GradientRectMaterial {
+    /// Color stops are linear values, and should be interpolated without conversion.
+    pub(crate) const LINEAR: u32 = 1;
+    /// Output colors in Display P3 primaries, clipping and checking them against that gamut
+    /// rather than sRGB.
+    pub(crate) const DISPLAY_P3: u32 = 2;
+    /// Draw a warning pattern over colors which fall outside of the display gamut.
+    pub(crate) const GAMUT_WARNING: u32 = 4;
+    /// The gradient runs from bottom to top rather than from left to right.
+    pub(crate) const VERTICAL: u32 = 8;
+    /// Interpolate between color stops in Oklab space. The color stops must be linear.
+    pub(crate) const OKLAB: u32 = 16;
+    /// Apply ordered dithering to the output, to hide banding in subtle gradients.
+    pub(crate) const DITHER: u32 = 32;
+
+    /// Pack color stop positions into the layout expected by the shader.
+    pub(crate) fn pack_positions(positions: &[f32; 8]) -> [Vec4; 2] {
+        [
+            Vec4::from_slice(&positions[0..4]),
+            Vec4::from_slice(&positions[4..8]),
+        ]
+    }
 }
 
 impl UiMaterial for GradientRectMaterial {
@@ -1,11 +1,24 @@
+mod backdrop_blur;
+mod box_shadow;
 mod dot_grid;
 mod draw_path;
 mod gradient_rect;
+mod progress_bar;
+mod rounded_clip;
 mod slider_rect;
+mod spinner;
 mod swatch_rect;
+mod text_decoration;
 
+pub(crate) use backdrop_blur::BackdropBlurMaterial;
+pub(crate) use box_shadow::BoxShadowMaterial;
 pub(crate) use dot_grid::DotGridMaterial;
-pub(crate) use draw_path::*;
+pub(crate) use draw_path::DrawPathMaterial;
+pub use draw_path::{DrawablePath, DrawablePathSegment, PathCap};
 pub(crate) use gradient_rect::*;
+pub(crate) use progress_bar::ProgressBarMaterial;
+pub(crate) use rounded_clip::RoundedClipMaterial;
 pub(crate) use slider_rect::SliderRectMaterial;
+pub(crate) use spinner::SpinnerMaterial;
 pub(crate) use swatch_rect::SwatchRectMaterial;
+pub(crate) use text_decoration::TextDecorationMaterial;
@@ -0,0 +1,41 @@
+use bevy::prelude::*;
+use bevy::reflect::TypePath;
+use bevy::render::render_resource::*;
+
+#[derive(AsBindGroup, Asset, TypePath, Debug, Clone)]
+pub(crate) struct ProgressBarMaterial {
+    #[uniform(0)]
+    pub(crate) color_track: Vec4,
+    #[uniform(1)]
+    pub(crate) color_fill: Vec4,
+    #[uniform(2)]
+    pub(crate) value: f32,
+    /// Fill fraction at the start of the current transition.
+    #[uniform(3)]
+    pub(crate) start_value: f32,
+    /// Global shader time at the start of the current transition.
+    #[uniform(4)]
+    pub(crate) start_time: f32,
+    #[uniform(5)]
+    pub(crate) radius: Vec4, // TopLeft, TopRight, BottomRight, BottomLeft
+}
+
+/// Duration of the transition when the progress value changes, in seconds. Must match the
+/// constant in `progress_bar.wgsl`.
+pub(crate) const PROGRESS_TRANSITION: f32 = 0.2;
+
+impl ProgressBarMaterial {
+    /// The fill fraction currently displayed at the given shader time, taking the transition
+    /// into account.
+    pub(crate) fn displayed_value(&self, time: f32) -> f32 {
+        let t = ((time - self.start_time) / PROGRESS_TRANSITION).clamp(0., 1.);
+        let t = t * t * (3. - 2. * t);
+        self.start_value + (self.value - self.start_value) * t
+    }
+}
+
+impl UiMaterial for ProgressBarMaterial {
+    fn fragment_shader() -> ShaderRef {
+        "obsidian_ui://shaders/progress_bar.wgsl".into()
+    }
+}
@@ -0,0 +1,19 @@
+use bevy::prelude::*;
+use bevy::reflect::TypePath;
+use bevy::render::render_resource::*;
+
+/// Paints the backdrop color over the parts of a node which lie outside of its rounded
+/// corners, so that content drawn beneath appears to be clipped to the corner radius.
+#[derive(AsBindGroup, Asset, TypePath, Debug, Clone)]
+pub struct RoundedClipMaterial {
+    #[uniform(0)]
+    pub(crate) color: Vec4,
+    #[uniform(1)]
+    pub(crate) radius: Vec4,
+}
+
+impl UiMaterial for RoundedClipMaterial {
+    fn fragment_shader() -> ShaderRef {
+        "obsidian_ui://shaders/rounded_clip.wgsl".into()
+    }
+}
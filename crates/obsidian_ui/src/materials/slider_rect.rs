@@ -12,6 +12,10 @@ pub struct SliderRectMaterial {
     pub(crate) value: f32,
     #[uniform(3)]
     pub(crate) radius: Vec4, // TopLeft, TopRight, BottomRight, BottomLeft
+    #[uniform(4)]
+    pub(crate) start: f32,
+    #[uniform(5)]
+    pub(crate) vertical: u32,
 }
 
 impl UiMaterial for SliderRectMaterial {
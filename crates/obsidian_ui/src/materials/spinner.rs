@@ -0,0 +1,15 @@
+use bevy::prelude::*;
+use bevy::reflect::TypePath;
+use bevy::render::render_resource::*;
+
+#[derive(AsBindGroup, Asset, TypePath, Debug, Clone)]
+pub(crate) struct SpinnerMaterial {
+    #[uniform(0)]
+    pub(crate) color: Vec4,
+}
+
+impl UiMaterial for SpinnerMaterial {
+    fn fragment_shader() -> ShaderRef {
+        "obsidian_ui://shaders/spinner.wgsl".into()
+    }
+}
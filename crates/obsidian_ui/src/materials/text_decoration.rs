@@ -0,0 +1,23 @@
+use bevy::prelude::*;
+use bevy::reflect::TypePath;
+use bevy::render::render_resource::*;
+
+/// Draws the caret and selection highlight of a text input. Rectangles are in pixels relative
+/// to the top-left of the node, as (min x, min y, max x, max y); an empty rectangle is not drawn.
+#[derive(AsBindGroup, Asset, TypePath, Debug, Clone)]
+pub struct TextDecorationMaterial {
+    #[uniform(0)]
+    pub(crate) selection: Vec4,
+    #[uniform(1)]
+    pub(crate) selection_color: Vec4,
+    #[uniform(2)]
+    pub(crate) caret: Vec4,
+    #[uniform(3)]
+    pub(crate) caret_color: Vec4,
+}
+
+impl UiMaterial for TextDecorationMaterial {
+    fn fragment_shader() -> ShaderRef {
+        "obsidian_ui://shaders/text_decoration.wgsl".into()
+    }
+}
@@ -0,0 +1,263 @@
+use bevy::{prelude::*, utils::HashMap};
+use bevy_mod_picking::{
+    backend::HitData,
+    events::{pointer_events, PointerCancel},
+    focus::{update_focus, HoverMap},
+    picking_core::PickSet,
+    pointer::{InputPress, PointerId, PressDirection},
+    prelude::*,
+};
+
+/// Component which captures the pointer when a drag starts on this entity or one of its
+/// descendants. While the pointer is captured, entities outside of the captor's hierarchy are
+/// not hovered, so the captor keeps receiving [`Move`], [`Up`] and drag events, and stays
+/// hovered, even when the pointer is no longer over it. The capture is released when the
+/// pointer button is released or the pointer is cancelled.
+///
+/// Add this alongside a widget's drag handlers so that the widget keeps tracking the pointer
+/// after it leaves the widget's bounds, and so that other widgets don't react to the drag.
+#[derive(Component, Default, Clone, Copy)]
+pub struct CapturePointer;
+
+#[derive(Clone, Copy, Debug)]
+struct Captor {
+    entity: Entity,
+    /// The camera used to report hit data when the pointer is outside the captor. Updated from
+    /// the pointer's hits while captured.
+    camera: Option<Entity>,
+}
+
+/// Resource which tracks which entity, if any, has captured each pointer.
+#[derive(Resource, Default)]
+pub struct PointerCapture {
+    captors: HashMap<PointerId, Captor>,
+}
+
+impl PointerCapture {
+    /// Route events for `pointer` to `entity` until the capture is released. `camera` is the
+    /// camera reported in the hit data of captured events, normally the camera from the event
+    /// which started the drag.
+    pub fn capture(&mut self, pointer: PointerId, entity: Entity, camera: Entity) {
+        self.captors.insert(
+            pointer,
+            Captor {
+                entity,
+                camera: Some(camera),
+            },
+        );
+    }
+
+    /// Release the capture for `pointer`, if any.
+    pub fn release(&mut self, pointer: PointerId) {
+        self.captors.remove(&pointer);
+    }
+
+    /// Returns the entity which has captured `pointer`, if any.
+    pub fn captor(&self, pointer: PointerId) -> Option<Entity> {
+        self.captors.get(&pointer).map(|captor| captor.entity)
+    }
+
+    /// True if `entity` has captured any pointer.
+    pub fn is_captured(&self, entity: Entity) -> bool {
+        self.captors.values().any(|captor| captor.entity == entity)
+    }
+}
+
+/// Methods for capturing the mouse pointer from event handlers, for widgets which want to
+/// start a capture on something other than a drag, such as a press.
+pub trait CaptureMouse {
+    /// Capture the mouse pointer for `entity`, until the button is released or
+    /// [`release_pointer`](CaptureMouse::release_pointer) is called.
+    fn capture_pointer(&mut self, entity: Entity);
+
+    /// Release the mouse pointer, if it is captured by `entity`.
+    fn release_pointer(&mut self, entity: Entity);
+}
+
+impl CaptureMouse for World {
+    fn capture_pointer(&mut self, entity: Entity) {
+        let camera = self.get_resource::<HoverMap>().and_then(|hover_map| {
+            hover_map
+                .get(&PointerId::Mouse)
+                .and_then(|hovered| hovered.values().next())
+                .map(|hit| hit.camera)
+        });
+        self.resource_mut::<PointerCapture>()
+            .captors
+            .insert(PointerId::Mouse, Captor { entity, camera });
+    }
+
+    fn release_pointer(&mut self, entity: Entity) {
+        let mut capture = self.resource_mut::<PointerCapture>();
+        if capture.captor(PointerId::Mouse) == Some(entity) {
+            capture.release(PointerId::Mouse);
+        }
+    }
+}
+
+/// Capture the pointer when a drag starts on an entity with a [`CapturePointer`] component,
+/// or one of its descendants, and release it when the pointer is cancelled.
+fn update_captures(
+    mut drag_starts: EventReader<Pointer<DragStart>>,
+    mut cancels: EventReader<Pointer<PointerCancel>>,
+    query: Query<(), With<CapturePointer>>,
+    parents: Query<&Parent>,
+    mut capture: ResMut<PointerCapture>,
+) {
+    for ev in drag_starts.read() {
+        let captor = std::iter::once(ev.target)
+            .chain(parents.iter_ancestors(ev.target))
+            .find(|entity| query.contains(*entity));
+        if let Some(captor) = captor {
+            capture.capture(ev.pointer_id, captor, ev.hit.camera);
+        }
+    }
+
+    for ev in cancels.read() {
+        capture.release(ev.pointer_id);
+    }
+}
+
+/// Restrict the hover set of each captured pointer to the captor and its descendants,
+/// substituting the captor when the pointer is outside of it. This runs between the picking
+/// focus update and event dispatch, so captured events are delivered in the same frame, and
+/// the drag state sees the captor as hovered. Captures are released when the button is
+/// released, after the release has been routed to the captor.
+fn retarget_captured_pointers(
+    mut presses: EventReader<InputPress>,
+    mut hover_map: ResMut<HoverMap>,
+    entities: Query<Entity>,
+    parents: Query<&Parent>,
+    mut capture: ResMut<PointerCapture>,
+) {
+    // Drop captures whose captor has been despawned.
+    capture
+        .captors
+        .retain(|_, captor| entities.contains(captor.entity));
+
+    for (pointer, captor) in capture.captors.iter_mut() {
+        let hovered = hover_map.entry(*pointer).or_default();
+        if let Some(hit) = hovered.values().next() {
+            captor.camera = Some(hit.camera);
+        }
+        let captor_entity = captor.entity;
+        hovered.retain(|entity, _| {
+            *entity == captor_entity || parents.iter_ancestors(*entity).any(|e| e == captor_entity)
+        });
+        if hovered.is_empty() {
+            if let Some(camera) = captor.camera {
+                hovered.insert(captor_entity, HitData::new(camera, 0., None, None));
+            }
+        }
+    }
+
+    for ev in presses.read() {
+        if ev.direction == PressDirection::Up {
+            capture.release(ev.pointer_id);
+        }
+    }
+}
+
+/// Plugin which routes pointer events to entities which have captured the pointer.
+pub struct PointerCapturePlugin;
+
+impl Plugin for PointerCapturePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PointerCapture>()
+            .add_systems(
+                PreUpdate,
+                retarget_captured_pointers
+                    .in_set(PickSet::Focus)
+                    .after(update_focus)
+                    .before(pointer_events),
+            )
+            .add_systems(PreUpdate, update_captures.in_set(PickSet::PostFocus));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::RunSystemOnce;
+    use bevy_mod_picking::pointer::PointerButton;
+
+    use super::*;
+
+    fn hovered(world: &World) -> Vec<Entity> {
+        world.resource::<HoverMap>()[&PointerId::Mouse]
+            .keys()
+            .copied()
+            .collect()
+    }
+
+    #[test]
+    fn test_retarget_captured_pointer() {
+        let mut world = World::new();
+        world.init_resource::<HoverMap>();
+        world.init_resource::<PointerCapture>();
+        world.init_resource::<Events<InputPress>>();
+        let camera = world.spawn_empty().id();
+        let child = world.spawn_empty().id();
+        let captor = world.spawn_empty().add_child(child).id();
+        let other = world.spawn_empty().id();
+        let hit = HitData::new(camera, 0., None, None);
+
+        // Not captured: the hover map is left alone.
+        world.resource_mut::<HoverMap>().insert(
+            PointerId::Mouse,
+            [(other, hit.clone())].into_iter().collect(),
+        );
+        world.run_system_once(retarget_captured_pointers);
+        assert_eq!(hovered(&world), vec![other]);
+
+        // Pointer outside the captor: the captor replaces whatever is under the pointer.
+        world
+            .resource_mut::<PointerCapture>()
+            .capture(PointerId::Mouse, captor, camera);
+        world.run_system_once(retarget_captured_pointers);
+        assert_eq!(hovered(&world), vec![captor]);
+
+        // Pointer over a part of the captor: that part stays hovered.
+        world.resource_mut::<HoverMap>().insert(
+            PointerId::Mouse,
+            [(child, hit.clone()), (other, hit.clone())]
+                .into_iter()
+                .collect(),
+        );
+        world.run_system_once(retarget_captured_pointers);
+        assert_eq!(hovered(&world), vec![child]);
+
+        // Releasing the button routes the release to the captor, then ends the capture.
+        world.resource_mut::<HoverMap>().insert(
+            PointerId::Mouse,
+            [(other, hit.clone())].into_iter().collect(),
+        );
+        world.send_event(InputPress {
+            pointer_id: PointerId::Mouse,
+            direction: PressDirection::Up,
+            button: PointerButton::Primary,
+        });
+        world.run_system_once(retarget_captured_pointers);
+        assert_eq!(hovered(&world), vec![captor]);
+        assert_eq!(
+            world.resource::<PointerCapture>().captor(PointerId::Mouse),
+            None
+        );
+    }
+
+    #[test]
+    fn test_capture_pointer() {
+        let mut world = World::new();
+        world.init_resource::<PointerCapture>();
+        let a = world.spawn_empty().id();
+        let b = world.spawn_empty().id();
+
+        world.capture_pointer(a);
+        assert!(world.resource::<PointerCapture>().is_captured(a));
+
+        // Only the captor can release the capture.
+        world.release_pointer(b);
+        assert!(world.resource::<PointerCapture>().is_captured(a));
+        world.release_pointer(a);
+        assert!(!world.resource::<PointerCapture>().is_captured(a));
+    }
+}
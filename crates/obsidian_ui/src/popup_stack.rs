@@ -0,0 +1,376 @@
+use std::borrow::Cow;
+
+use bevy::{a11y::Focus, prelude::*};
+use bevy_mod_picking::prelude::{ListenerInput, On};
+use bevy_reactor::*;
+
+use crate::focus::KeyPressEvent;
+
+/// Component which registers an entity with the [`PopupStack`]. This should be placed on the
+/// root display node of a popup, such as a menu, dialog or tooltip. The entity is removed from
+/// the stack when the component is removed or the entity is despawned.
+///
+/// The stack adds an `On<KeyPressEvent>` listener to the entity, which closes the popup when
+/// Escape bubbles up to it, so the entity should not have a key press listener of its own.
+#[derive(Component, Default, Clone)]
+pub struct Popup {
+    /// Callback which closes the popup. This is invoked when the user presses Escape while
+    /// this popup is topmost, or when the popup is closed via [`PopupStack::close_above`] or
+    /// [`PopupStack::close_all`].
+    pub on_close: Option<Callback>,
+}
+
+impl Popup {
+    /// Construct a new `Popup` component.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the callback used to close the popup.
+    pub fn on_close(mut self, on_close: Callback) -> Self {
+        self.on_close = Some(on_close);
+        self
+    }
+}
+
+/// Resource which tracks the currently open popups, in the order they were opened. Each
+/// popup is assigned a global z-index above the popups opened before it, so that nested
+/// combinations of menus, dialogs and tooltips stack predictably.
+#[derive(Resource)]
+pub struct PopupStack {
+    /// The portal layer which popups are stacked in. Popups are drawn just above the layer's
+    /// own z-index, and below the next layer up. Defaults to `"dialogs"`.
+    pub layer: Cow<'static, str>,
+    /// Global z-index of the bottom-most popup, updated from [`PortalLayers`].
+    base_z_index: i32,
+    entries: Vec<(Entity, Option<Callback>)>,
+}
+
+impl Default for PopupStack {
+    fn default() -> Self {
+        let layer = "dialogs";
+        Self {
+            layer: layer.into(),
+            base_z_index: PortalLayers::default().z_index(layer).unwrap_or(1000) + 1,
+            entries: Vec::new(),
+        }
+    }
+}
+
+impl PopupStack {
+    /// The topmost open popup, if any.
+    pub fn top(&self) -> Option<Entity> {
+        self.entries.last().map(|(entity, _)| *entity)
+    }
+
+    /// The number of open popups.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if there are no open popups.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns true if the given entity is an open popup.
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.entries.iter().any(|(e, _)| *e == entity)
+    }
+
+    /// The global z-index assigned to the given popup.
+    pub fn z_index(&self, entity: Entity) -> Option<i32> {
+        self.entries
+            .iter()
+            .position(|(e, _)| *e == entity)
+            .map(|index| self.base_z_index + index as i32)
+    }
+
+    /// Move the given popup to the top of the stack, so that it is drawn above the others and
+    /// is the first to be closed by Escape.
+    pub fn raise(&mut self, entity: Entity) {
+        if let Some(index) = self.entries.iter().position(|(e, _)| *e == entity) {
+            let entry = self.entries.remove(index);
+            self.entries.push(entry);
+        }
+    }
+
+    /// Close all popups which were opened after `entity`, starting with the topmost.
+    pub fn close_above(&self, commands: &mut Commands, entity: Entity) {
+        if let Some(index) = self.entries.iter().position(|(e, _)| *e == entity) {
+            Self::close_entries(&self.entries[index + 1..], commands);
+        }
+    }
+
+    /// Close all open popups, starting with the topmost.
+    pub fn close_all(&self, commands: &mut Commands) {
+        Self::close_entries(&self.entries, commands);
+    }
+
+    fn close_entries(entries: &[(Entity, Option<Callback>)], commands: &mut Commands) {
+        for (_, on_close) in entries.iter().rev() {
+            if let Some(on_close) = on_close {
+                commands.run_callback(*on_close, ());
+            }
+        }
+    }
+}
+
+/// Component which registers a non-modal window with the [`WindowStack`]. This should be placed
+/// on the root display node of the window.
+#[derive(Component, Default, Clone, Copy)]
+pub struct StackedWindow;
+
+/// Resource which tracks the stacking order of non-modal windows, such as floating tool
+/// windows. Windows are drawn in the `"windows"` portal layer, below dialogs and popups, and
+/// unlike popups they are not closed by Escape.
+#[derive(Resource, Default)]
+pub struct WindowStack {
+    entries: Vec<Entity>,
+}
+
+impl WindowStack {
+    /// The frontmost window, if any.
+    pub fn top(&self) -> Option<Entity> {
+        self.entries.last().copied()
+    }
+
+    /// Returns true if the given entity is an open window.
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.entries.contains(&entity)
+    }
+
+    /// Move the given window in front of the others.
+    pub fn raise(&mut self, entity: Entity) {
+        if let Some(index) = self.entries.iter().position(|e| *e == entity) {
+            let entry = self.entries.remove(index);
+            self.entries.push(entry);
+        }
+    }
+}
+
+/// System which adds and removes windows from the stack, and keeps their z-indices up to date.
+pub(crate) fn update_window_stack(
+    mut stack: ResMut<WindowStack>,
+    layers: Option<Res<PortalLayers>>,
+    added: Query<Entity, Added<StackedWindow>>,
+    mut removed: RemovedComponents<StackedWindow>,
+    mut z_indices: Query<&mut ZIndex>,
+) {
+    for entity in removed.read() {
+        stack.entries.retain(|e| *e != entity);
+    }
+    stack.entries.extend(added.iter());
+
+    let Some(base_z_index) = layers.and_then(|layers| layers.z_index("windows")) else {
+        return;
+    };
+    for (index, entity) in stack.entries.iter().enumerate() {
+        let z_index = base_z_index + index as i32;
+        if let Ok(mut current) = z_indices.get_mut(*entity) {
+            if !matches!(*current, ZIndex::Global(z) if z == z_index) {
+                *current = ZIndex::Global(z_index);
+            }
+        }
+    }
+}
+
+/// System which adds and removes popups from the stack, and keeps their z-indices up to date.
+pub(crate) fn update_popup_stack(
+    mut stack: ResMut<PopupStack>,
+    layers: Option<Res<PortalLayers>>,
+    added: Query<(Entity, &Popup), Added<Popup>>,
+    mut removed: RemovedComponents<Popup>,
+    mut z_indices: Query<&mut ZIndex>,
+    mut commands: Commands,
+) {
+    for entity in removed.read() {
+        stack.entries.retain(|(e, _)| *e != entity);
+    }
+    for (entity, popup) in added.iter() {
+        stack.entries.push((entity, popup.on_close));
+        commands
+            .entity(entity)
+            .insert(On::<KeyPressEvent>::run(close_popup_on_key_press));
+    }
+    // Follow the layer if the app has rearranged the portal layers.
+    if let Some(z_index) = layers.and_then(|layers| layers.z_index(&stack.layer)) {
+        if stack.base_z_index != z_index + 1 {
+            stack.base_z_index = z_index + 1;
+        }
+    }
+
+    // Other systems (such as portal layers) may also set the z-index, so re-apply it whenever
+    // it differs.
+    for (index, (entity, _)) in stack.entries.iter().enumerate() {
+        let z_index = ZIndex::Global(stack.base_z_index + index as i32);
+        if let Ok(mut current) = z_indices.get_mut(*entity) {
+            if !matches!((*current, z_index), (ZIndex::Global(a), ZIndex::Global(b)) if a == b) {
+                *current = z_index;
+            }
+        }
+    }
+}
+
+/// Listener which closes a popup when an Escape key press bubbles up to it from a focused
+/// widget inside it. The gamepad cancel button is delivered to the focused widget as Escape, so
+/// it takes the same route. Widgets which handle Escape themselves stop its propagation, so
+/// the press doesn't also close the popup.
+fn close_popup_on_key_press(world: &mut World) {
+    let event = world.resource::<ListenerInput<KeyPressEvent>>();
+    if event.key_code != KeyCode::Escape || event.repeat {
+        return;
+    }
+    let popup = event.listener();
+    let stack = world.resource::<PopupStack>();
+    if stack.top() != Some(popup) {
+        return;
+    }
+    let on_close = stack.entries.last().and_then(|(_, on_close)| *on_close);
+    world
+        .resource_mut::<ListenerInput<KeyPressEvent>>()
+        .stop_propagation();
+    if let Some(on_close) = on_close {
+        world.run_callback(on_close, ());
+    }
+}
+
+/// System which closes the topmost popup when Escape, or the gamepad cancel button, is pressed
+/// while the focus is outside of it. Presses made while the focus is inside the popup are
+/// delivered to the focused widget instead, and close the popup only if the widget doesn't
+/// handle them; see [`close_popup_on_key_press`].
+pub(crate) fn close_top_popup_on_escape(
+    keys: Option<Res<ButtonInput<KeyCode>>>,
+    gamepads: Option<Res<Gamepads>>,
+    buttons: Option<Res<ButtonInput<GamepadButton>>>,
+    focus: Option<Res<Focus>>,
+    parents: Query<&Parent>,
+    stack: Res<PopupStack>,
+    mut commands: Commands,
+) {
+    let escape = keys.map_or(false, |keys| keys.just_pressed(KeyCode::Escape));
+    let cancel = match (gamepads, buttons) {
+        (Some(gamepads), Some(buttons)) => gamepads.iter().any(|gamepad| {
+            buttons.just_pressed(GamepadButton::new(gamepad, GamepadButtonType::East))
+        }),
+        _ => false,
+    };
+    if !escape && !cancel {
+        return;
+    }
+    let Some((top, on_close)) = stack.entries.last() else {
+        return;
+    };
+    if let Some(focused) = focus.and_then(|focus| focus.0) {
+        if focused == *top || parents.iter_ancestors(focused).any(|e| e == *top) {
+            return;
+        }
+    }
+    if let Some(on_close) = on_close {
+        commands.run_callback(*on_close, ());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::RunSystemOnce;
+    use bevy_reactor::TrackingScope;
+
+    use super::*;
+
+    #[derive(Resource, Default)]
+    struct Closed(usize);
+
+    fn global_z(world: &World, entity: Entity) -> i32 {
+        match world.get::<ZIndex>(entity) {
+            Some(ZIndex::Global(z)) => *z,
+            _ => panic!("expected a global z-index"),
+        }
+    }
+
+    #[test]
+    fn test_popup_stack_follows_layers() {
+        let mut world = World::default();
+        world.init_resource::<PortalLayers>();
+        world.init_resource::<PopupStack>();
+
+        let a = world.spawn((Popup::new(), ZIndex::default())).id();
+        let b = world.spawn((Popup::new(), ZIndex::default())).id();
+        world.run_system_once(update_popup_stack);
+        let dialogs = PortalLayers::default().z_index("dialogs").unwrap();
+        assert_eq!(global_z(&world, a), dialogs + 1);
+        assert_eq!(global_z(&world, b), dialogs + 2);
+
+        // Adding a layer below "dialogs" moves it up, and the popups with it.
+        world
+            .resource_mut::<PortalLayers>()
+            .insert_below("panels", "dialogs");
+        world.run_system_once(update_popup_stack);
+        let dialogs = world.resource::<PortalLayers>().z_index("dialogs").unwrap();
+        assert_eq!(global_z(&world, a), dialogs + 1);
+        assert_eq!(global_z(&world, b), dialogs + 2);
+        assert_eq!(world.resource::<PopupStack>().z_index(b), Some(dialogs + 2));
+        assert!(dialogs + 2 < world.resource::<PortalLayers>().z_index("menus").unwrap());
+    }
+
+    #[test]
+    fn test_window_stack() {
+        let mut world = World::default();
+        world.init_resource::<PortalLayers>();
+        world.init_resource::<WindowStack>();
+        let base = PortalLayers::default().z_index("windows").unwrap();
+
+        let a = world.spawn((StackedWindow, ZIndex::default())).id();
+        let b = world.spawn((StackedWindow, ZIndex::default())).id();
+        world.run_system_once(update_window_stack);
+        assert_eq!(global_z(&world, a), base);
+        assert_eq!(global_z(&world, b), base + 1);
+        assert_eq!(world.resource::<WindowStack>().top(), Some(b));
+
+        world.resource_mut::<WindowStack>().raise(a);
+        world.run_system_once(update_window_stack);
+        assert_eq!(global_z(&world, b), base);
+        assert_eq!(global_z(&world, a), base + 1);
+
+        world.despawn(a);
+        world.run_system_once(update_window_stack);
+        assert!(!world.resource::<WindowStack>().contains(a));
+        assert_eq!(world.resource::<WindowStack>().top(), Some(b));
+    }
+
+    #[test]
+    fn test_escape_closes_top_popup_once() {
+        let mut world = World::default();
+        world.init_resource::<PopupStack>();
+        world.init_resource::<Focus>();
+        world.init_resource::<Closed>();
+        let owner = world.spawn_empty().id();
+        let mut scope = TrackingScope::new(world.change_tick());
+        let mut cx = Cx::new(&mut world, owner, &mut scope);
+        let on_close = cx.create_callback(|cx, ()| {
+            cx.world_mut().resource_mut::<Closed>().0 += 1;
+        });
+
+        let popup = world
+            .spawn((Popup::new().on_close(on_close), ZIndex::default()))
+            .id();
+        let button = world.spawn_empty().set_parent(popup).id();
+        world.run_system_once(update_popup_stack);
+        assert!(world.get::<On<KeyPressEvent>>(popup).is_some());
+
+        let press_escape = |world: &mut World| {
+            let mut keys = ButtonInput::<KeyCode>::default();
+            keys.press(KeyCode::Escape);
+            world.insert_resource(keys);
+            world.run_system_once(close_top_popup_on_escape);
+            world.resource::<Closed>().0
+        };
+
+        // With the focus outside, the stack closes the popup.
+        assert_eq!(press_escape(&mut world), 1);
+
+        // With the focus inside, the press is left to the key press listener.
+        world.resource_mut::<Focus>().0 = Some(button);
+        assert_eq!(press_escape(&mut world), 1);
+    }
+}
@@ -2,7 +2,8 @@ use bevy::prelude::*;
 
 /// Options for rendering rounded corners.
 #[allow(missing_docs)]
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, Reflect)]
+#[reflect(Default)]
 pub enum RoundedCorners {
     None,
     #[default]
@@ -0,0 +1,94 @@
+use bevy::prelude::*;
+use bevy_reactor::{text, RegisterUiTemplate, StyleRef};
+
+use crate::{
+    controls::{Button, ButtonVariant},
+    size::Size,
+    RoundedCorners,
+};
+
+/// Props for a [`Button`] declared in a scene, instantiated by a [`UiTemplate`] named
+/// `"obsidian::button"`. Props in scenes are constant, so they are converted to constant
+/// signals.
+///
+/// [`UiTemplate`]: bevy_reactor::UiTemplate
+#[derive(Component, Clone, Default, Debug, Reflect)]
+#[reflect(Component, Default)]
+pub struct ButtonProps {
+    /// Text displayed in the button.
+    pub label: String,
+
+    /// Color variant.
+    pub variant: ButtonVariant,
+
+    /// Button size.
+    pub size: Size,
+
+    /// Whether the button is disabled.
+    pub disabled: bool,
+
+    /// Which corners to render rounded.
+    pub corners: RoundedCorners,
+
+    /// If true, render the button in a 'minimal' style.
+    pub minimal: bool,
+
+    /// Additional styles, looked up in the `NamedStyles` registry.
+    pub style: StyleRef,
+}
+
+/// Plugin which registers the reflected prop types of Obsidian UI widgets, and the view
+/// templates which can be declared in scenes.
+pub struct ObsidianScenePlugin;
+
+impl Plugin for ObsidianScenePlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Size>()
+            .register_type::<RoundedCorners>()
+            .register_type::<ButtonVariant>()
+            .register_type::<StyleRef>()
+            .register_ui_template("obsidian::button", |props: &ButtonProps, world| {
+                Button::new()
+                    .variant(props.variant)
+                    .size(props.size)
+                    .disabled(props.disabled)
+                    .corners(props.corners)
+                    .minimal(props.minimal)
+                    .style(props.style.resolve(world))
+                    .children(text(&props.label))
+            });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::any::TypeId;
+
+    use bevy_reactor::UiTemplateRegistry;
+
+    use super::*;
+
+    #[test]
+    fn test_scene_plugin() {
+        let mut app = App::new();
+        app.add_plugins(ObsidianScenePlugin);
+        assert!(app
+            .world()
+            .resource::<UiTemplateRegistry>()
+            .contains("obsidian::button"));
+
+        // Button props can be declared in scenes.
+        let registry = app.world().resource::<AppTypeRegistry>().read();
+        let props = registry.get(TypeId::of::<ButtonProps>()).unwrap();
+        assert!(props.data::<ReflectComponent>().is_some());
+        assert!(props.data::<ReflectDefault>().is_some());
+        for field in [
+            TypeId::of::<ButtonVariant>(),
+            TypeId::of::<Size>(),
+            TypeId::of::<RoundedCorners>(),
+            TypeId::of::<StyleRef>(),
+        ] {
+            assert!(registry.contains(field));
+        }
+    }
+}
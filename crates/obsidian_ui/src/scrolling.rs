@@ -1,5 +1,15 @@
 use bevy::{input::mouse::MouseWheel, prelude::*, ui};
 use bevy_mod_picking::{focus::HoverMap, pointer::PointerId, prelude::EntityEvent};
+use bevy_reactor::{Cx, Mutable, Opacity, ReadMutable, RunContextSetup, Signal, WriteMutable};
+
+/// Number of pixels to scroll for each line of a mouse wheel which scrolls by lines.
+const SCROLL_LINE_HEIGHT: f32 = 20.;
+
+/// How long an auto-hiding scrollbar stays visible after scrolling stops, in seconds.
+const SCROLLBAR_IDLE_DELAY: f32 = 1.0;
+
+/// How long an auto-hiding scrollbar takes to fade out, in seconds.
+const SCROLLBAR_FADE_DURATION: f32 = 0.3;
 
 /// Mouse wheel entity event
 #[derive(Clone, Event, EntityEvent, Debug)]
@@ -72,6 +82,64 @@ pub struct ScrollBar {
     pub min_thumb_size: f32,
 }
 
+/// Component which fades out a scrollbar while its scroll area is idle, and hides it when
+/// there is nothing to scroll. Requires an [`Opacity`] component on the scrollbar. Once fully
+/// faded, the scrollbar is made invisible, so that it no longer intercepts the pointer.
+#[derive(Component, Default)]
+pub struct ScrollBarFade {
+    /// Whether the scrollbar is hovered or being dragged, which keeps it visible.
+    pub active: bool,
+
+    /// Seconds since the scroll position last changed.
+    idle: f32,
+
+    /// The scroll position when last checked.
+    last_position: Vec2,
+}
+
+/// A read/write signal holding the scroll position of a
+/// [`ScrollView`](crate::controls::ScrollView): the distance its content is scrolled along
+/// each axis. Reading it tracks scrolling by the user; setting it scrolls the view. This can
+/// be used to save and restore the scroll position, or to scroll programmatically.
+#[derive(Clone, Copy)]
+pub struct ScrollPosition(Mutable<Vec2>);
+
+impl ScrollPosition {
+    /// Create a new scroll position, initially scrolled to `position`.
+    pub fn new(cx: &mut Cx, position: Vec2) -> Self {
+        Self(cx.create_mutable(position))
+    }
+
+    /// Get the scroll position.
+    pub fn get<R: ReadMutable>(&self, cx: &R) -> Vec2 {
+        self.0.get(cx)
+    }
+
+    /// Scroll to the given position. The position is clamped to the scrollable range.
+    pub fn set<R: WriteMutable>(&self, cx: &mut R, position: Vec2) {
+        self.0.set(cx, position);
+    }
+
+    /// Return a signal for the scroll position.
+    pub fn signal(&self) -> Signal<Vec2> {
+        self.0.signal()
+    }
+}
+
+/// Component which keeps a [`ScrollPosition`] in sync with the [`ScrollArea`] it is attached
+/// to.
+#[derive(Component)]
+pub(crate) struct ScrollPositionSync {
+    pub(crate) position: ScrollPosition,
+
+    /// The position last written to the signal, used to detect changes made by the app.
+    pub(crate) last: Option<Vec2>,
+
+    /// A position requested by the app which could not be reached yet, because the content
+    /// was too small. It is retried as the content grows, until the user scrolls.
+    pub(crate) pending: Option<Vec2>,
+}
+
 /// Marker component indicating this entity is a scrollbar thumb.
 #[derive(Component)]
 pub struct ScrollBarThumb;
@@ -168,23 +236,210 @@ pub(crate) fn handle_scroll_events(
     mut scroll_evr: EventReader<MouseWheel>,
     mut writer: EventWriter<ScrollWheel>,
     hover_map: Res<HoverMap>,
+    keys: Res<ButtonInput<KeyCode>>,
 ) {
     if let Some(hover) = hover_map.get(&PointerId::Mouse) {
         use bevy::input::mouse::MouseScrollUnit;
+        let shift = keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
         for ev in scroll_evr.read() {
-            match ev.unit {
-                MouseScrollUnit::Line => {
-                    // Ignore for now.
-                }
-                MouseScrollUnit::Pixel => {
-                    for k in hover.keys() {
-                        writer.send(ScrollWheel {
-                            target: *k,
-                            delta: Vec2 { x: ev.x, y: ev.y },
-                        });
-                    }
-                }
+            let mut delta = match ev.unit {
+                MouseScrollUnit::Line => Vec2::new(ev.x, ev.y) * SCROLL_LINE_HEIGHT,
+                MouseScrollUnit::Pixel => Vec2::new(ev.x, ev.y),
+            };
+            // Holding shift turns a vertical wheel into a horizontal one.
+            if shift && delta.x == 0. {
+                delta = Vec2::new(delta.y, 0.);
+            }
+            for k in hover.keys() {
+                writer.send(ScrollWheel { target: *k, delta });
             }
         }
     }
 }
+
+/// Fade out auto-hiding scrollbars once their scroll area has been idle for a while.
+pub(crate) fn fade_scrollbars(
+    mut query: Query<(
+        &ScrollBar,
+        &mut ScrollBarFade,
+        &mut Opacity,
+        &mut Visibility,
+    )>,
+    query_scroll_area: Query<&ScrollArea>,
+    time: Res<Time>,
+) {
+    for (scrollbar, mut fade, mut opacity, mut visibility) in query.iter_mut() {
+        let Ok(scroll_area) = query_scroll_area.get(scrollbar.id_scroll_area) else {
+            continue;
+        };
+        let position = Vec2::new(scroll_area.scroll_left, scroll_area.scroll_top);
+        if fade.active || position != fade.last_position {
+            fade.idle = 0.;
+            fade.last_position = position;
+        } else {
+            fade.idle += time.delta_seconds();
+        }
+
+        let range = if scrollbar.vertical {
+            scroll_area.content_size.y - scroll_area.visible_size.y
+        } else {
+            scroll_area.content_size.x - scroll_area.visible_size.x
+        };
+        let alpha = if range > 0. {
+            1. - ((fade.idle - SCROLLBAR_IDLE_DELAY) / SCROLLBAR_FADE_DURATION).clamp(0., 1.)
+        } else {
+            0.
+        };
+        if opacity.0 != alpha {
+            opacity.0 = alpha;
+        }
+        let shown = match alpha > 0. {
+            true => Visibility::Inherited,
+            false => Visibility::Hidden,
+        };
+        if *visibility != shown {
+            *visibility = shown;
+        }
+    }
+}
+
+/// Apply changes to [`ScrollPosition`] signals to their scroll areas, and update the signals
+/// when the user scrolls.
+pub(crate) fn sync_scroll_positions(world: &mut World) {
+    let mut query = world.query::<(Entity, &ScrollPositionSync)>();
+    let syncs: Vec<(Entity, ScrollPosition, Option<Vec2>, Option<Vec2>)> = query
+        .iter(world)
+        .map(|(entity, sync)| (entity, sync.position, sync.last, sync.pending))
+        .collect();
+    for (entity, position, last, mut pending) in syncs {
+        let requested = position.get(world);
+        let Some(mut scroll_area) = world.get_mut::<ScrollArea>(entity) else {
+            continue;
+        };
+        let current = Vec2::new(scroll_area.scroll_left, scroll_area.scroll_top);
+        if last != Some(requested) {
+            pending = Some(requested);
+        } else if last != Some(current) {
+            // The user scrolled, which abandons any position the app is waiting for.
+            pending = None;
+        }
+        // Wait until the content has been measured, so that the position isn't clamped to
+        // zero.
+        if scroll_area.content_size == Vec2::ZERO {
+            world.get_mut::<ScrollPositionSync>(entity).unwrap().pending = pending;
+            continue;
+        }
+        if let Some(target) = pending {
+            scroll_area.scroll_to(target.x, target.y);
+        }
+        let actual = Vec2::new(scroll_area.scroll_left, scroll_area.scroll_top);
+        let mut sync = world.get_mut::<ScrollPositionSync>(entity).unwrap();
+        sync.last = Some(actual);
+        sync.pending = pending.filter(|target| *target != actual);
+        if actual != requested {
+            position.set(world, actual);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::ecs::system::RunSystemOnce;
+    use bevy_reactor::TrackingScope;
+
+    use super::*;
+
+    #[test]
+    fn test_scroll_position_waits_for_content() {
+        let mut world = World::default();
+        let owner = world.spawn_empty().id();
+        let mut scope = TrackingScope::new(world.change_tick());
+        let mut cx = Cx::new(&mut world, owner, &mut scope);
+        let position = ScrollPosition::new(&mut cx, Vec2::new(0., 500.));
+        let area = world
+            .spawn((
+                ScrollArea {
+                    visible_size: Vec2::new(100., 200.),
+                    ..default()
+                },
+                ScrollPositionSync {
+                    position,
+                    last: None,
+                    pending: None,
+                },
+            ))
+            .id();
+        let sync = |world: &mut World, content: f32| {
+            world.get_mut::<ScrollArea>(area).unwrap().content_size = Vec2::new(100., content);
+            sync_scroll_positions(world);
+            world.flush_commands();
+            world.get::<ScrollArea>(area).unwrap().scroll_top
+        };
+
+        // The first page of content is too short to reach the requested position, so the
+        // view scrolls as far as it can, and follows the content as it grows.
+        assert_eq!(sync(&mut world, 400.), 200.);
+        assert_eq!(position.get(&world).y, 200.);
+        assert_eq!(sync(&mut world, 600.), 400.);
+        assert_eq!(sync(&mut world, 1000.), 500.);
+        assert_eq!(position.get(&world).y, 500.);
+        assert_eq!(sync(&mut world, 1200.), 500.);
+
+        // Once the user scrolls, the requested position is abandoned.
+        position.set(&mut world, Vec2::new(0., 2000.));
+        world.flush_commands();
+        assert_eq!(sync(&mut world, 1200.), 1000.);
+        world.get_mut::<ScrollArea>(area).unwrap().scroll_top = 300.;
+        assert_eq!(sync(&mut world, 1200.), 300.);
+        assert_eq!(sync(&mut world, 3000.), 300.);
+        assert_eq!(position.get(&world).y, 300.);
+    }
+
+    #[test]
+    fn test_faded_scrollbar_is_hidden() {
+        let mut world = World::default();
+        world.init_resource::<Time>();
+        let area = world
+            .spawn(ScrollArea {
+                content_size: Vec2::new(100., 1000.),
+                visible_size: Vec2::new(100., 200.),
+                ..default()
+            })
+            .id();
+        let scrollbar = world
+            .spawn((
+                ScrollBar {
+                    vertical: true,
+                    id_scroll_area: area,
+                    min_thumb_size: 10.,
+                },
+                ScrollBarFade::default(),
+                Opacity::default(),
+                Visibility::default(),
+            ))
+            .id();
+        let fade = |world: &mut World, seconds: f32| {
+            world
+                .resource_mut::<Time>()
+                .advance_by(Duration::from_secs_f32(seconds));
+            world.run_system_once(fade_scrollbars);
+            let entt = world.entity(scrollbar);
+            (
+                entt.get::<Opacity>().unwrap().0,
+                *entt.get::<Visibility>().unwrap(),
+            )
+        };
+
+        assert_eq!(fade(&mut world, 0.), (1., Visibility::Inherited));
+        let (alpha, visibility) = fade(&mut world, SCROLLBAR_IDLE_DELAY + 0.1);
+        assert!(alpha > 0. && alpha < 1.);
+        assert_eq!(visibility, Visibility::Inherited);
+        assert_eq!(fade(&mut world, 1.), (0., Visibility::Hidden));
+
+        // Scrolling shows the scrollbar again.
+        world.get_mut::<ScrollArea>(area).unwrap().scroll_top = 100.;
+        assert_eq!(fade(&mut world, 0.), (1., Visibility::Inherited));
+    }
+}
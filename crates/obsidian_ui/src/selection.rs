@@ -0,0 +1,252 @@
+use bevy::prelude::*;
+use bevy_reactor::*;
+
+/// How a [`SelectionModel`] responds to clicks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SelectionMode {
+    /// At most one item can be selected.
+    #[default]
+    Single,
+    /// Any set of items can be selected. Ctrl-click toggles an item, shift-click selects the
+    /// range from the anchor item.
+    Multi,
+    /// A single contiguous range of items can be selected. Shift-click extends the range from
+    /// the anchor item.
+    Range,
+}
+
+/// Modifier keys which affect how a click changes the selection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SelectionModifiers {
+    /// Toggle the clicked item (Ctrl, or Cmd on macOS).
+    pub toggle: bool,
+    /// Extend the selection from the anchor to the clicked item (Shift).
+    pub extend: bool,
+}
+
+impl SelectionModifiers {
+    /// Read the current modifier key state from the world.
+    pub fn from_world(world: &World) -> Self {
+        match world.get_resource::<ButtonInput<KeyCode>>() {
+            Some(keys) => Self {
+                toggle: keys.any_pressed([
+                    KeyCode::ControlLeft,
+                    KeyCode::ControlRight,
+                    KeyCode::SuperLeft,
+                    KeyCode::SuperRight,
+                ]),
+                extend: keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]),
+            },
+            None => Self::default(),
+        }
+    }
+}
+
+/// The set of selected items in a list, tree or graph, along with the primary (most recently
+/// clicked) item and the anchor used for range selection.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SelectionModel<T: Clone + PartialEq> {
+    mode: SelectionMode,
+    selected: Vec<T>,
+    primary: Option<T>,
+    anchor: Option<T>,
+}
+
+impl<T: Clone + PartialEq> SelectionModel<T> {
+    /// Construct a new, empty selection model.
+    pub fn new(mode: SelectionMode) -> Self {
+        Self {
+            mode,
+            selected: Vec::new(),
+            primary: None,
+            anchor: None,
+        }
+    }
+
+    /// The selection mode.
+    pub fn mode(&self) -> SelectionMode {
+        self.mode
+    }
+
+    /// The selected items, in the order they were selected.
+    pub fn selected(&self) -> &[T] {
+        &self.selected
+    }
+
+    /// The primary selected item, if any.
+    pub fn primary(&self) -> Option<&T> {
+        self.primary.as_ref()
+    }
+
+    /// The anchor item used for range selection, if any.
+    pub fn anchor(&self) -> Option<&T> {
+        self.anchor.as_ref()
+    }
+
+    /// Returns true if the given item is selected.
+    pub fn is_selected(&self, item: &T) -> bool {
+        self.selected.contains(item)
+    }
+
+    /// Returns true if no items are selected.
+    pub fn is_empty(&self) -> bool {
+        self.selected.is_empty()
+    }
+
+    /// Deselect all items.
+    pub fn clear(&mut self) {
+        self.selected.clear();
+        self.primary = None;
+        self.anchor = None;
+    }
+
+    /// Replace the selection with a single item, which also becomes the anchor.
+    pub fn select(&mut self, item: T) {
+        self.selected.clear();
+        self.selected.push(item.clone());
+        self.primary = Some(item.clone());
+        self.anchor = Some(item);
+    }
+
+    /// Add or remove an item from the selection. In single and range modes, this is the
+    /// same as [`SelectionModel::select`], except that a selected item is deselected.
+    pub fn toggle(&mut self, item: T) {
+        if self.mode != SelectionMode::Multi {
+            if self.is_selected(&item) {
+                self.clear();
+            } else {
+                self.select(item);
+            }
+            return;
+        }
+
+        if let Some(index) = self.selected.iter().position(|s| *s == item) {
+            self.selected.remove(index);
+            if self.primary.as_ref() == Some(&item) {
+                self.primary = self.selected.last().cloned();
+            }
+        } else {
+            self.selected.push(item.clone());
+            self.primary = Some(item.clone());
+        }
+        self.anchor = Some(item);
+    }
+
+    /// Select the items between the anchor and `item` inclusive, given the display order of
+    /// all items. If `additive` is true (multi mode only), the range is added to the current
+    /// selection instead of replacing it. The anchor is left unchanged.
+    pub fn select_range(&mut self, item: T, items: &[T], additive: bool) {
+        let from = self
+            .anchor
+            .as_ref()
+            .and_then(|anchor| items.iter().position(|i| i == anchor));
+        let to = items.iter().position(|i| *i == item);
+        let (Some(from), Some(to)) = (from, to) else {
+            self.select(item);
+            return;
+        };
+
+        if !(additive && self.mode == SelectionMode::Multi) {
+            self.selected.clear();
+        }
+        for i in &items[from.min(to)..=from.max(to)] {
+            if !self.selected.contains(i) {
+                self.selected.push(i.clone());
+            }
+        }
+        self.primary = Some(item);
+    }
+
+    /// Update the selection in response to a click on `item`, using the standard
+    /// ctrl-click and shift-click conventions for the current mode. `items` is the display
+    /// order of all items, used for range selection; it may be empty if ranges are not
+    /// meaningful (e.g. in a node graph), in which case shift-click behaves like ctrl-click.
+    pub fn click(&mut self, item: T, items: &[T], modifiers: SelectionModifiers) {
+        match self.mode {
+            SelectionMode::Single => self.select(item),
+            SelectionMode::Multi | SelectionMode::Range => {
+                if modifiers.extend && !items.is_empty() {
+                    self.select_range(item, items, modifiers.toggle);
+                } else if modifiers.extend || modifiers.toggle {
+                    self.toggle(item);
+                } else {
+                    self.select(item);
+                }
+            }
+        }
+    }
+}
+
+/// A reactive handle to a [`SelectionModel`], which can be shared between views so that
+/// several panels display and edit the same selection.
+pub struct Selection<T: Clone + PartialEq + Send + Sync + 'static>(Mutable<SelectionModel<T>>);
+
+impl<T: Clone + PartialEq + Send + Sync + 'static> Copy for Selection<T> {}
+impl<T: Clone + PartialEq + Send + Sync + 'static> Clone for Selection<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: Clone + PartialEq + Send + Sync + 'static> Selection<T> {
+    /// Returns true if the given item is selected.
+    pub fn is_selected<R: ReadMutable + ReadDerived>(&self, rc: &R, item: &T) -> bool {
+        self.0.signal().map(rc, |model| model.is_selected(item))
+    }
+
+    /// The currently selected items.
+    pub fn selected<R: ReadMutable + ReadDerived>(&self, rc: &R) -> Vec<T> {
+        self.0.signal().map(rc, |model| model.selected().to_vec())
+    }
+
+    /// The primary selected item, if any.
+    pub fn primary<R: ReadMutable + ReadDerived>(&self, rc: &R) -> Option<T> {
+        self.0.signal().map(rc, |model| model.primary().cloned())
+    }
+
+    /// Modify the selection model.
+    pub fn update<R: RunContextWrite, F: FnOnce(&mut SelectionModel<T>)>(
+        &self,
+        cx: &mut R,
+        updater: F,
+    ) {
+        self.0.update(cx, |mut model| updater(&mut model));
+    }
+
+    /// Update the selection in response to a click on `item`, reading the modifier keys
+    /// from the world. See [`SelectionModel::click`].
+    pub fn click(&self, world: &mut World, item: T, items: &[T]) {
+        let modifiers = SelectionModifiers::from_world(world);
+        self.update(world, |model| model.click(item, items, modifiers));
+    }
+
+    /// Create a signal which returns the selected items.
+    pub fn selected_signal(&self, cx: &mut Cx) -> Signal<Vec<T>> {
+        let selection = *self;
+        cx.create_derived(move |rcx| selection.selected(rcx))
+    }
+
+    /// Create a signal which returns the primary selected item.
+    pub fn primary_signal(&self, cx: &mut Cx) -> Signal<Option<T>> {
+        let selection = *self;
+        cx.create_derived(move |rcx| selection.primary(rcx))
+    }
+}
+
+/// Trait which adds `create_selection` to [`Cx`].
+pub trait CreateSelection {
+    /// Create a new, empty selection with the given mode.
+    fn create_selection<T: Clone + PartialEq + Send + Sync + 'static>(
+        &mut self,
+        mode: SelectionMode,
+    ) -> Selection<T>;
+}
+
+impl<'p, 'w> CreateSelection for Cx<'p, 'w> {
+    fn create_selection<T: Clone + PartialEq + Send + Sync + 'static>(
+        &mut self,
+        mode: SelectionMode,
+    ) -> Selection<T> {
+        Selection(self.create_mutable(SelectionModel::new(mode)))
+    }
+}
@@ -0,0 +1,319 @@
+use std::{
+    io,
+    sync::{Arc, OnceLock},
+};
+
+use bevy::{
+    asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext},
+    prelude::*,
+    render::{
+        render_asset::RenderAssetUsages,
+        render_resource::{Extent3d, TextureDimension, TextureFormat},
+    },
+    tasks::{block_on, futures_lite::future, AsyncComputeTaskPool, Task},
+    utils::{HashMap, HashSet},
+    window::PrimaryWindow,
+};
+use resvg::{tiny_skia, usvg};
+
+/// An SVG document loaded from a `.svg` file. Documents are rasterized on demand, once for
+/// each pixel size they are displayed at.
+#[derive(Asset, TypePath, Debug)]
+pub struct Svg {
+    data: Arc<[u8]>,
+}
+
+/// Fonts used to render text in SVG documents. The system fonts are loaded the first time a
+/// document is rasterized, which normally happens on a background thread.
+fn svg_fonts() -> Arc<usvg::fontdb::Database> {
+    static FONTS: OnceLock<Arc<usvg::fontdb::Database>> = OnceLock::new();
+    FONTS
+        .get_or_init(|| {
+            let mut fonts = usvg::fontdb::Database::new();
+            fonts.load_system_fonts();
+            Arc::new(fonts)
+        })
+        .clone()
+}
+
+impl Svg {
+    /// Rasterize the document into an image of the given size in pixels, stretching it to fit.
+    /// Returns `None` if the document could not be parsed.
+    pub fn rasterize(&self, size: UVec2) -> Option<Image> {
+        rasterize(&self.data, size)
+    }
+}
+
+/// Rasterize an SVG document, see [`Svg::rasterize`].
+fn rasterize(data: &[u8], size: UVec2) -> Option<Image> {
+    let options = usvg::Options {
+        fontdb: svg_fonts(),
+        ..default()
+    };
+    let tree = match usvg::Tree::from_data(data, &options) {
+        Ok(tree) => tree,
+        Err(err) => {
+            warn!("Failed to parse SVG: {}", err);
+            return None;
+        }
+    };
+    let mut pixmap = tiny_skia::Pixmap::new(size.x, size.y)?;
+    let tree_size = tree.size();
+    resvg::render(
+        &tree,
+        tiny_skia::Transform::from_scale(
+            size.x as f32 / tree_size.width(),
+            size.y as f32 / tree_size.height(),
+        ),
+        &mut pixmap.as_mut(),
+    );
+
+    // tiny-skia produces premultiplied alpha, but Bevy expects straight alpha.
+    let data = pixmap
+        .pixels()
+        .iter()
+        .flat_map(|pixel| {
+            let color = pixel.demultiply();
+            [color.red(), color.green(), color.blue(), color.alpha()]
+        })
+        .collect();
+    Some(Image::new(
+        Extent3d {
+            width: size.x,
+            height: size.y,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::RENDER_WORLD,
+    ))
+}
+
+/// Asset loader for `.svg` files.
+#[derive(Default)]
+pub(crate) struct SvgLoader;
+
+impl AssetLoader for SvgLoader {
+    type Asset = Svg;
+    type Settings = ();
+    type Error = io::Error;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a Self::Settings,
+        _load_context: &'a mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data).await?;
+        Ok(Svg { data: data.into() })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["svg"]
+    }
+}
+
+/// Resource holding the number of physical pixels per logical UI pixel, which combines the
+/// scale factor of the primary window with the [`UiScale`]. Vector images are rasterized at
+/// this resolution so that they stay sharp on high-DPI displays.
+#[derive(Resource, Clone, Copy, Debug, PartialEq)]
+pub struct DisplayScale(pub f32);
+
+impl Default for DisplayScale {
+    fn default() -> Self {
+        Self(1.)
+    }
+}
+
+pub(crate) fn update_display_scale(
+    mut scale: ResMut<DisplayScale>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    ui_scale: Res<UiScale>,
+) {
+    let Ok(window) = windows.get_single() else {
+        return;
+    };
+    scale.set_if_neq(DisplayScale(window.resolution.scale_factor() * ui_scale.0));
+}
+
+/// Component for a UI image node which displays an [`Svg`]. The document is rasterized at
+/// `size` times the [`DisplayScale`], and re-rasterized when the scale changes. The node's
+/// [`UiImage`] color tints the image as usual.
+#[derive(Component)]
+pub struct SvgIcon {
+    /// The SVG document to display.
+    pub svg: Handle<Svg>,
+
+    /// The size of the image in logical pixels.
+    pub size: Vec2,
+
+    /// The pixel size the image was last rasterized at.
+    rasterized: Option<UVec2>,
+}
+
+impl SvgIcon {
+    /// Construct a new [`SvgIcon`] which displays `svg` at the given logical size.
+    pub fn new(svg: Handle<Svg>, size: Vec2) -> Self {
+        Self {
+            svg,
+            size,
+            rasterized: None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+struct SvgIconKey {
+    svg: AssetId<Svg>,
+    /// The rasterized size in physical pixels, i.e. the logical size times the display scale.
+    size: UVec2,
+}
+
+/// Resource which caches rasterized SVG images by document and pixel size, so that an icon
+/// which appears many times is only rasterized once for each size and display scale.
+///
+/// Documents are rasterized on a background thread. The cache only holds weak references, so
+/// an image is dropped once no node displays it, for example after the display scale changes.
+#[derive(Resource, Default)]
+pub struct SvgIconCache {
+    images: HashMap<SvgIconKey, AssetId<Image>>,
+    pending: HashMap<SvgIconKey, Task<Option<Image>>>,
+    /// Documents which could not be parsed, so that they aren't retried every frame.
+    failed: HashSet<SvgIconKey>,
+}
+
+impl SvgIconCache {
+    /// Remove all cached images. Images which are still displayed are kept alive by their
+    /// nodes, but will be rasterized again if another node needs them.
+    pub fn clear(&mut self) {
+        self.images.clear();
+        self.failed.clear();
+    }
+}
+
+pub(crate) fn rasterize_svg_icons(
+    scale: Res<DisplayScale>,
+    svgs: Res<Assets<Svg>>,
+    mut images: ResMut<Assets<Image>>,
+    mut image_events: EventReader<AssetEvent<Image>>,
+    mut cache: ResMut<SvgIconCache>,
+    mut icons: Query<(&mut SvgIcon, &mut UiImage)>,
+) {
+    // Forget images which are no longer displayed anywhere.
+    for event in image_events.read() {
+        if let AssetEvent::Removed { id } = event {
+            cache.images.retain(|_, image| image != id);
+        }
+    }
+
+    // Collect finished rasterizations. The handles are held until the end of the frame, so
+    // that waiting icons can pick them up.
+    let mut finished = HashMap::<SvgIconKey, Handle<Image>>::default();
+    let cache = &mut *cache;
+    cache.pending.retain(|key, task| {
+        let Some(result) = block_on(future::poll_once(task)) else {
+            return true;
+        };
+        match result {
+            Some(image) => {
+                let texture = images.add(image);
+                cache.images.insert(*key, texture.id());
+                finished.insert(*key, texture);
+            }
+            None => {
+                cache.failed.insert(*key);
+            }
+        }
+        false
+    });
+
+    for (mut icon, mut image) in icons.iter_mut() {
+        let size = (icon.size * scale.0).round().as_uvec2().max(UVec2::ONE);
+        if icon.rasterized == Some(size) {
+            continue;
+        }
+
+        let key = SvgIconKey {
+            svg: icon.svg.id(),
+            size,
+        };
+        let texture = finished.get(&key).cloned().or_else(|| {
+            cache
+                .images
+                .get(&key)
+                .and_then(|id| images.get_strong_handle(*id))
+        });
+        match texture {
+            Some(texture) => {
+                image.texture = texture;
+                icon.rasterized = Some(size);
+            }
+            None if cache.failed.contains(&key) => {
+                icon.rasterized = Some(size);
+            }
+            None if cache.pending.contains_key(&key) => {}
+            None => {
+                // Wait for the document to load.
+                let Some(svg) = svgs.get(&icon.svg) else {
+                    continue;
+                };
+                let data = svg.data.clone();
+                let task = AsyncComputeTaskPool::get().spawn(async move { rasterize(&data, size) });
+                cache.pending.insert(key, task);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{ecs::system::RunSystemOnce, tasks::TaskPool};
+
+    use super::*;
+
+    const SQUARE: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" width="4" height="4">
+        <rect width="4" height="4" fill="red"/>
+    </svg>"#;
+
+    #[test]
+    fn test_rasterize_icons() {
+        AsyncComputeTaskPool::get_or_init(TaskPool::default);
+        let mut world = World::default();
+        world.init_resource::<Assets<Image>>();
+        world.init_resource::<Assets<Svg>>();
+        world.init_resource::<Events<AssetEvent<Image>>>();
+        world.init_resource::<DisplayScale>();
+        world.init_resource::<SvgIconCache>();
+        let svg = world.resource_mut::<Assets<Svg>>().add(Svg {
+            data: SQUARE.as_bytes().into(),
+        });
+        let size = Vec2::splat(8.);
+        let a = world
+            .spawn((SvgIcon::new(svg.clone(), size), UiImage::default()))
+            .id();
+        let b = world
+            .spawn((SvgIcon::new(svg, size), UiImage::default()))
+            .id();
+
+        // Rasterization happens in the background, so keep running until both icons are done.
+        for _ in 0..500 {
+            world.run_system_once(rasterize_svg_icons);
+            if world.get::<SvgIcon>(a).unwrap().rasterized.is_some()
+                && world.get::<SvgIcon>(b).unwrap().rasterized.is_some()
+            {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+
+        let texture = world.get::<UiImage>(a).unwrap().texture.clone();
+        assert_eq!(world.get::<UiImage>(b).unwrap().texture, texture);
+        let image = world.resource::<Assets<Image>>().get(&texture).unwrap();
+        assert_eq!(image.size(), UVec2::splat(8));
+        let cache = world.resource::<SvgIconCache>();
+        assert_eq!(cache.images.len(), 1);
+        assert!(cache.pending.is_empty());
+    }
+}
@@ -0,0 +1,177 @@
+use bevy::{prelude::*, ui, utils::HashSet};
+use bevy_mod_picking::{events::Pointer, pointer::PointerId, prelude::*};
+
+/// Resource which configures the minimum size of interactive targets. When set, small
+/// controls which are marked with [`TouchTarget`] have their hit areas (but not their
+/// visuals) expanded to at least this size. This is disabled by default; apps running on
+/// touch platforms will typically set it to something like 44x44 logical pixels.
+#[derive(Resource, Default, Clone, Copy, Debug)]
+pub struct MinimumTouchTarget(pub Option<Vec2>);
+
+/// Marker component for interactive elements whose hit area should be expanded to the
+/// [`MinimumTouchTarget`] size.
+#[derive(Component, Default)]
+pub struct TouchTarget {
+    /// The invisible child node which extends the hit area, if any.
+    hit_zone: Option<Entity>,
+}
+
+/// Marker component for the invisible node which extends the hit area of a [`TouchTarget`].
+/// Pointer events on this node bubble up to the target.
+#[derive(Component)]
+pub struct TouchHitZone;
+
+pub(crate) fn update_touch_targets(
+    mut commands: Commands,
+    settings: Res<MinimumTouchTarget>,
+    mut targets: Query<(Entity, &Node, &mut TouchTarget, Option<&Children>)>,
+    mut zones: Query<&mut Style, With<TouchHitZone>>,
+) {
+    for (entity, node, mut target, children) in targets.iter_mut() {
+        let deficit = match settings.0 {
+            Some(min_size) => (min_size - node.size()).max(Vec2::ZERO),
+            None => Vec2::ZERO,
+        };
+
+        // Remove the hit zone if it's no longer needed.
+        if deficit == Vec2::ZERO {
+            if let Some(zone) = target.hit_zone.take() {
+                commands.entity(zone).despawn_recursive();
+            }
+            continue;
+        }
+
+        let inset_x = ui::Val::Px(-deficit.x * 0.5);
+        let inset_y = ui::Val::Px(-deficit.y * 0.5);
+        match target.hit_zone.map(|zone| (zone, zones.get_mut(zone))) {
+            Some((zone, Ok(mut style))) => {
+                if style.left != inset_x || style.top != inset_y {
+                    style.left = inset_x;
+                    style.right = inset_x;
+                    style.top = inset_y;
+                    style.bottom = inset_y;
+                }
+                // The zone may have been detached when the element's children were replaced.
+                if !children.is_some_and(|c| c.contains(&zone)) {
+                    commands.entity(entity).add_child(zone);
+                }
+            }
+            _ => {
+                let zone = commands
+                    .spawn((
+                        NodeBundle {
+                            style: ui::Style {
+                                position_type: ui::PositionType::Absolute,
+                                left: inset_x,
+                                right: inset_x,
+                                top: inset_y,
+                                bottom: inset_y,
+                                ..default()
+                            },
+                            ..default()
+                        },
+                        TouchHitZone,
+                        Name::new("TouchHitZone"),
+                    ))
+                    .id();
+                commands.entity(entity).add_child(zone);
+                target.hit_zone = Some(zone);
+            }
+        }
+    }
+}
+
+/// Resource which configures how touch gestures are recognized.
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct TouchSettings {
+    /// How far, in logical pixels, a touch has to move before it is treated as a drag rather
+    /// than a tap. Touches which exceed this distance don't activate buttons when released,
+    /// and don't scroll until they have exceeded it.
+    pub drag_threshold: f32,
+}
+
+impl Default for TouchSettings {
+    fn default() -> Self {
+        Self { drag_threshold: 8. }
+    }
+}
+
+/// Resource which tracks which touch pointers have moved far enough to be considered drags.
+#[derive(Resource, Default)]
+pub struct TouchGestures {
+    dragging: HashSet<PointerId>,
+}
+
+impl TouchGestures {
+    /// Returns true if the given pointer is a touch which has moved beyond the drag
+    /// threshold since it was pressed. Always false for non-touch pointers.
+    pub fn is_dragging(&self, pointer_id: PointerId) -> bool {
+        self.dragging.contains(&pointer_id)
+    }
+
+    /// Forget any drag made by a pointer when it is pressed again.
+    fn press(&mut self, pointer_id: PointerId) {
+        self.dragging.remove(&pointer_id);
+    }
+
+    /// Record that a pointer has been dragged by `distance` since it was pressed.
+    fn drag(&mut self, pointer_id: PointerId, distance: Vec2, threshold: f32) {
+        if pointer_id.is_touch() && distance.length() > threshold {
+            self.dragging.insert(pointer_id);
+        }
+    }
+}
+
+/// Returns true if the `Click` event currently being handled ended a touch drag, such as
+/// a scroll, rather than a tap. Click handlers should ignore such clicks.
+pub(crate) fn click_was_drag(world: &World) -> bool {
+    let pointer_id = world.resource::<ListenerInput<Pointer<Click>>>().pointer_id;
+    world.resource::<TouchGestures>().is_dragging(pointer_id)
+}
+
+/// Classifies touches as taps or drags. This runs before the picking event listeners, so that
+/// a `Click` handler sees the state of the touch which produced it.
+pub(crate) fn update_touch_gestures(
+    settings: Res<TouchSettings>,
+    mut gestures: ResMut<TouchGestures>,
+    mut downs: EventReader<Pointer<Down>>,
+    mut drags: EventReader<Pointer<Drag>>,
+) {
+    for down in downs.read() {
+        gestures.press(down.pointer_id);
+    }
+    for drag in drags.read() {
+        gestures.drag(drag.pointer_id, drag.distance, settings.drag_threshold);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::utils::Uuid;
+
+    use super::*;
+
+    #[test]
+    fn test_touch_gestures() {
+        let touch = PointerId::Touch(0);
+        let mut gestures = TouchGestures::default();
+
+        // Small movements are still taps.
+        gestures.drag(touch, Vec2::new(3., 4.), 8.);
+        assert!(!gestures.is_dragging(touch));
+
+        gestures.drag(touch, Vec2::new(6., 8.), 8.);
+        assert!(gestures.is_dragging(touch));
+
+        // The next press starts a new gesture.
+        gestures.press(touch);
+        assert!(!gestures.is_dragging(touch));
+
+        // Only touches are classified; mouse drags are never treated as scrolling.
+        gestures.drag(PointerId::Mouse, Vec2::new(100., 0.), 8.);
+        assert!(!gestures.is_dragging(PointerId::Mouse));
+        let custom = PointerId::Custom(Uuid::from_u128(1));
+        gestures.drag(custom, Vec2::new(100., 0.), 8.);
+        assert!(!gestures.is_dragging(custom));
+    }
+}
@@ -0,0 +1,28 @@
+use bevy::reflect::Reflect;
+
+/// Reflection attribute which places a struct field in a named, collapsible section of the
+/// inspector. Fields without a category are shown above all of the sections, and sections
+/// appear in the order of their first field.
+///
+/// ```ignore
+/// #[derive(Reflect)]
+/// struct Material {
+///     #[reflect(@InspectorCategory("Surface"))]
+///     roughness: f32,
+/// }
+/// ```
+#[derive(Reflect, Debug, Clone, Copy, PartialEq)]
+pub struct InspectorCategory(pub &'static str);
+
+/// Reflection attribute which supplies the tooltip shown when hovering over the label of a
+/// struct field in the inspector.
+///
+/// ```ignore
+/// #[derive(Reflect)]
+/// struct Material {
+///     #[reflect(@InspectorTooltip("How rough the surface is, from 0 (mirror) to 1"))]
+///     roughness: f32,
+/// }
+/// ```
+#[derive(Reflect, Debug, Clone, Copy, PartialEq)]
+pub struct InspectorTooltip(pub &'static str);
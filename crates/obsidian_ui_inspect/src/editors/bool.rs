@@ -1,6 +1,6 @@
 use bevy::prelude::*;
 use bevy_reactor::*;
-use obsidian_ui::controls::Checkbox;
+use obsidian_ui::controls::{CheckState, Checkbox};
 
 use crate::{templates::field_label::FieldLabel, InspectableField};
 
@@ -18,6 +18,12 @@ impl ViewTemplate for FieldEditBool {
             false
         });
 
+        let field = self.0.clone();
+        let state = cx.create_memo(move |cx| match field.is_mixed(cx) {
+            true => CheckState::Indeterminate,
+            false => is_checked.get(cx).into(),
+        });
+
         let field = self.0.clone();
         Fragment::new((
             FieldLabel {
@@ -25,6 +31,7 @@ impl ViewTemplate for FieldEditBool {
             },
             Checkbox {
                 checked: is_checked,
+                state: Some(state),
                 on_change: Some(cx.create_callback(move |cx: &mut Cx, value: bool| {
                     field.set_value(cx, value.as_reflect());
                 })),
@@ -45,6 +45,9 @@ impl ViewTemplate for FieldEditSrgba {
             Srgba::NONE
         });
 
+        let field = self.field.clone();
+        let mixed = cx.create_memo(move |cx| field.is_mixed(cx));
+
         let state = cx.create_mutable(ColorEditState {
             mode: ColorMode::Rgb,
             rgb: Srgba::default(),
@@ -68,9 +71,9 @@ impl ViewTemplate for FieldEditSrgba {
             },
             Element::<NodeBundle>::new().style(style_field).children((
                 Swatch::new(value).style(style_swatch),
-                text_computed(move |cx| {
-                    let value = value.get(cx);
-                    value.to_hex()
+                text_computed(move |cx| match mixed.get(cx) {
+                    true => "Mixed".to_string(),
+                    false => value.get(cx).to_hex(),
                 }),
                 Spacer,
                 MenuButton::new()
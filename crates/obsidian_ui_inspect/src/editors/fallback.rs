@@ -22,12 +22,16 @@ impl ViewTemplate for FieldEditFallback {
         //     false
         // });
 
-        // let field = self.field.clone();
+        let type_path = reflect.reflect_type_path().to_string();
+        let mixed = cx.create_memo(move |cx| field.is_mixed(cx));
         Fragment::new((
             FieldLabel {
                 field: self.0.clone(),
             },
-            FieldReadonlyValue::new().children(format!("TODO: {}", reflect.reflect_type_path())),
+            FieldReadonlyValue::new().children(text_computed(move |cx| match mixed.get(cx) {
+                true => "Mixed".to_string(),
+                false => format!("TODO: {}", type_path),
+            })),
         ))
         .into_view()
     }
@@ -31,6 +31,9 @@ impl ViewTemplate for FieldEditList {
 
         let pop_disabled = cx.create_derived(move |cx| length.get(cx) == 0);
 
+        let field = self.0.clone();
+        let mixed = cx.create_memo(move |cx| field.is_mixed(cx));
+
         let field = self.0.clone();
         let push = cx.create_callback(move |cx, _| {
             if let Some(list) = field.reflect(cx) {
@@ -106,29 +109,43 @@ impl ViewTemplate for FieldEditList {
             },
             Element::<NodeBundle>::new()
                 .style(style_list_items)
-                .children((For::index(
-                    move |cx| 0..length.get(cx),
-                    move |_, index| {
-                        let mut path = field.path.clone();
-                        path.0.push(OffsetAccess {
-                            access: bevy::reflect::Access::ListIndex(index),
-                            offset: None,
-                        });
-                        let access = Arc::new(InspectableField {
-                            root: field.root.clone(),
-                            name: format!("{}", index),
-                            path,
-                            container_path: field.path.clone(),
-                            can_remove: false,
-                        });
-                        ListItemInspector { field: access }.into_view()
+                .children(Cond::new(
+                    move |cx| mixed.get(cx),
+                    // The lists differ between the inspected items, so their elements can't
+                    // be edited together; they can still be pushed and popped.
+                    || {
+                        Element::<NodeBundle>::new()
+                            .style(style_empty_list)
+                            .children("(mixed)")
+                    },
+                    move || {
+                        let field = field.clone();
+                        For::index(
+                            move |cx| 0..length.get(cx),
+                            move |_, index| {
+                                let mut path = field.path.clone();
+                                path.0.push(OffsetAccess {
+                                    access: bevy::reflect::Access::ListIndex(index),
+                                    offset: None,
+                                });
+                                let access = Arc::new(InspectableField {
+                                    root: field.root.clone(),
+                                    name: format!("{}", index),
+                                    path,
+                                    container_path: field.path.clone(),
+                                    can_remove: false,
+                                    tooltip: None,
+                                });
+                                ListItemInspector { field: access }.into_view()
+                            },
+                        )
+                        .with_fallback(
+                            Element::<NodeBundle>::new()
+                                .style(style_empty_list)
+                                .children("(empty list)"),
+                        )
                     },
-                )
-                .with_fallback(
-                    Element::<NodeBundle>::new()
-                        .style(style_empty_list)
-                        .children("(empty list)"),
-                ),)),
+                )),
         ))
     }
 }
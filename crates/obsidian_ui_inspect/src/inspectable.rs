@@ -1,5 +1,5 @@
 use core::panic;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 use bevy::{
     prelude::*,
@@ -24,6 +24,19 @@ pub trait Inspectable: Send + Sync {
 
     /// Apply a closure to a field within the item
     fn update_field(&self, cx: &mut Cx, path: &ParsedPath, f: &dyn Fn(&mut dyn Reflect));
+
+    /// Whether a field has different values in the items being inspected.
+    fn is_field_mixed(&self, cx: &Cx, path: &ParsedPath) -> bool {
+        false
+    }
+
+    /// Whether a field has been edited, and can be reverted to its original value.
+    fn can_revert_field(&self, cx: &Cx, path: &ParsedPath) -> bool {
+        false
+    }
+
+    /// Restore a field to the value it had before it was edited.
+    fn revert_field(&self, cx: &mut Cx, path: &ParsedPath) {}
 }
 
 /// A resource that can be inspected
@@ -69,6 +82,181 @@ impl<T: Resource + Reflect> Inspectable for InspectableResource<T> {
     }
 }
 
+/// A component of an entity that can be inspected
+pub struct InspectableComponent<T: Component + Reflect> {
+    entity: Entity,
+    marker: std::marker::PhantomData<T>,
+}
+
+impl<T: Component + Reflect> InspectableComponent<T> {
+    /// Inspect the component `T` of the given entity.
+    pub fn new(entity: Entity) -> Self {
+        Self {
+            entity,
+            marker: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<T: Component + Reflect> Inspectable for InspectableComponent<T> {
+    fn name(&self, cx: &Cx) -> String {
+        let cmp = cx.use_component::<T>(self.entity).unwrap();
+        cmp.reflect_short_type_path().to_string()
+    }
+
+    fn reflect<'a>(&self, cx: &'a Cx) -> &'a dyn Reflect {
+        cx.use_component::<T>(self.entity).unwrap().as_reflect()
+    }
+
+    fn reflect_field<'a>(&self, cx: &'a Cx, path: &ParsedPath) -> Option<&'a dyn Reflect> {
+        let cmp = cx.use_component::<T>(self.entity)?;
+        match cmp.reflect_path(path) {
+            Ok(result) => Some(result),
+            Err(ReflectPathError::InvalidAccess(_)) => None,
+            Err(err) => panic!("{:?}", err),
+        }
+    }
+
+    fn set_field(&self, cx: &mut Cx, path: &ParsedPath, value: &dyn Reflect) {
+        let mut cmp = cx.world_mut().get_mut::<T>(self.entity).unwrap();
+        cmp.reflect_path_mut(path).unwrap().apply(value);
+    }
+
+    fn update_field(&self, cx: &mut Cx, path: &ParsedPath, f: &dyn Fn(&mut dyn Reflect)) {
+        let mut cmp = cx.world_mut().get_mut::<T>(self.entity).unwrap();
+        f(cmp.reflect_path_mut(path).unwrap());
+    }
+}
+
+/// A set of items of the same type which are inspected together, such as the selected
+/// entities in an editor. Fields display the value of the first item, and edits are applied
+/// to every item.
+///
+/// The original values of each edited field are recorded, so that the field can be reverted.
+///
+/// A selection may be empty, in which case it has no fields; [`Inspector`](crate::Inspector)
+/// displays nothing for an empty selection.
+pub struct InspectableSelection {
+    targets: Vec<Arc<dyn Inspectable>>,
+    /// Values of each edited field, for each target, from before the first edit.
+    originals: Mutex<Vec<(ParsedPath, Vec<Option<Box<dyn Reflect>>>)>>,
+}
+
+impl InspectableSelection {
+    /// Construct a new selection from a list of items.
+    pub fn new(targets: Vec<Arc<dyn Inspectable>>) -> Self {
+        Self {
+            targets,
+            originals: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// True if nothing is selected.
+    pub fn is_empty(&self) -> bool {
+        self.targets.is_empty()
+    }
+
+    /// Record the current values of a field, if this is the first time it has been edited.
+    fn record_original(&self, cx: &Cx, path: &ParsedPath) {
+        let mut originals = self.originals.lock().unwrap();
+        if originals.iter().any(|(p, _)| p == path) {
+            return;
+        }
+        let values = self
+            .targets
+            .iter()
+            .map(|target| target.reflect_field(cx, path).map(|f| f.clone_value()))
+            .collect();
+        originals.push((path.clone(), values));
+    }
+}
+
+impl Inspectable for InspectableSelection {
+    fn name(&self, cx: &Cx) -> String {
+        match self.targets.as_slice() {
+            [] => String::new(),
+            [target] => target.name(cx),
+            [target, ..] => format!("{} ({} selected)", target.name(cx), self.targets.len()),
+        }
+    }
+
+    fn reflect<'a>(&self, cx: &'a Cx) -> &'a dyn Reflect {
+        self.targets
+            .first()
+            .expect("An empty selection has no value")
+            .reflect(cx)
+    }
+
+    fn reflect_field<'a>(&self, cx: &'a Cx, path: &ParsedPath) -> Option<&'a dyn Reflect> {
+        self.targets.first()?.reflect_field(cx, path)
+    }
+
+    fn set_field(&self, cx: &mut Cx, path: &ParsedPath, value: &dyn Reflect) {
+        self.record_original(cx, path);
+        for target in self.targets.iter() {
+            if target.reflect_field(cx, path).is_some() {
+                target.set_field(cx, path, value);
+            }
+        }
+    }
+
+    fn update_field(&self, cx: &mut Cx, path: &ParsedPath, f: &dyn Fn(&mut dyn Reflect)) {
+        self.record_original(cx, path);
+        for target in self.targets.iter() {
+            if target.reflect_field(cx, path).is_some() {
+                target.update_field(cx, path, f);
+            }
+        }
+    }
+
+    fn is_field_mixed(&self, cx: &Cx, path: &ParsedPath) -> bool {
+        let Some(first) = self.reflect_field(cx, path) else {
+            return false;
+        };
+        self.targets[1..].iter().any(|target| {
+            target
+                .reflect_field(cx, path)
+                .map_or(false, |value| value.reflect_partial_eq(first) != Some(true))
+        })
+    }
+
+    fn can_revert_field(&self, cx: &Cx, path: &ParsedPath) -> bool {
+        let originals = self.originals.lock().unwrap();
+        let Some((_, values)) = originals.iter().find(|(p, _)| p == path) else {
+            return false;
+        };
+        self.targets
+            .iter()
+            .zip(values.iter())
+            .any(
+                |(target, original)| match (target.reflect_field(cx, path), original) {
+                    (Some(current), Some(original)) => {
+                        current.reflect_partial_eq(original.as_ref()) != Some(true)
+                    }
+                    _ => false,
+                },
+            )
+    }
+
+    fn revert_field(&self, cx: &mut Cx, path: &ParsedPath) {
+        let original = {
+            let mut originals = self.originals.lock().unwrap();
+            let index = originals.iter().position(|(p, _)| p == path);
+            index.map(|index| originals.remove(index).1)
+        };
+        let Some(values) = original else {
+            return;
+        };
+        for (target, value) in self.targets.iter().zip(values) {
+            if let Some(value) = value {
+                if target.reflect_field(cx, path).is_some() {
+                    target.set_field(cx, path, value.as_ref());
+                }
+            }
+        }
+    }
+}
+
 /// A reference to a field within an `Inspectable`. This contains information needed to
 /// get and set the field as well as query it's type.
 #[derive(Clone)]
@@ -78,6 +266,7 @@ pub struct InspectableField {
     pub(crate) path: ParsedPath,
     pub(crate) container_path: ParsedPath,
     pub(crate) can_remove: bool,
+    pub(crate) tooltip: Option<&'static str>,
 }
 
 impl InspectableField {
@@ -86,6 +275,13 @@ impl InspectableField {
         &self.name
     }
 
+    /// Return the tooltip for this field, from its [`InspectorTooltip`] attribute.
+    ///
+    /// [`InspectorTooltip`]: crate::InspectorTooltip
+    pub fn tooltip(&self) -> Option<&'static str> {
+        self.tooltip
+    }
+
     /// Get the reflected value of the field.
     pub fn reflect<'a>(&self, cx: &'a Cx) -> Option<&'a dyn Reflect> {
         self.root.reflect_field(cx, &self.path)
@@ -96,6 +292,21 @@ impl InspectableField {
         self.root.set_field(cx, &self.path, value);
     }
 
+    /// Whether the field has different values in the items being inspected.
+    pub fn is_mixed(&self, cx: &Cx) -> bool {
+        self.root.is_field_mixed(cx, &self.path)
+    }
+
+    /// Whether the field has been edited, and can be reverted to its original value.
+    pub fn can_revert(&self, cx: &Cx) -> bool {
+        self.root.can_revert_field(cx, &self.path)
+    }
+
+    /// Restore the field to the value it had before it was edited.
+    pub fn revert(&self, cx: &mut Cx) {
+        self.root.revert_field(cx, &self.path);
+    }
+
     /// Whether the item can be removed (in other words, is it optional or an array element)
     pub fn can_remove(&self) -> bool {
         self.can_remove
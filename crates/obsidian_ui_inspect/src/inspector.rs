@@ -3,42 +3,78 @@ use std::sync::Arc;
 use bevy::{
     color::Color,
     ecs::reflect::AppTypeRegistry,
+    math::Vec2,
+    prelude::{default, NodeBundle},
     reflect::{
         std_traits::ReflectDefault, DynamicEnum, DynamicTuple, OffsetAccess, ParsedPath,
         ReflectKind, ReflectRef, TypeInfo, VariantInfo,
     },
+    ui,
 };
 use bevy_reactor::*;
 use obsidian_ui::{
     colors,
-    controls::{Icon, MenuButton, MenuItem, MenuPopup, Spacer},
+    controls::{Button, Icon, MenuButton, MenuItem, MenuPopup, Spacer, TextInput, TextInputProps},
     floating::FloatAlign,
     size::Size,
+    typography,
 };
 
 use crate::{
     templates::inspector_panel::InspectorPanel, Inspectable, InspectableField,
-    InspectorFactoryRegistry,
+    InspectableSelection, InspectorCategory, InspectorFactoryRegistry, InspectorState,
+    InspectorTooltip,
 };
 
 pub struct Inspector {
-    // Reference to the entity being inspected
-    target: Arc<dyn Inspectable>,
+    // Reference to the entity being inspected, or None for an empty selection
+    target: Option<Arc<dyn Inspectable>>,
+    // Whether to show a search box which filters the fields
+    search: bool,
 }
 
 impl Inspector {
     pub fn new(target: Arc<dyn Inspectable>) -> Self {
-        Self { target }
+        Self {
+            target: Some(target),
+            search: false,
+        }
     }
 
-    fn create_fields(&self, cx: &mut Cx, target: Arc<dyn Inspectable>) -> ViewRef {
-        let field_type = cx.create_memo(move |cx| target.reflect(cx).reflect_kind());
-        let target = self.target.clone();
+    /// Inspect several items of the same type at once. Fields whose values differ between the
+    /// items are shown as mixed, edits are applied to all of them, and edited fields can be
+    /// reverted. Nothing is displayed if the selection is empty.
+    pub fn selection(targets: Vec<Arc<dyn Inspectable>>) -> Self {
+        let selection = InspectableSelection::new(targets);
+        Self {
+            target: (!selection.is_empty()).then(|| Arc::new(selection) as Arc<dyn Inspectable>),
+            search: false,
+        }
+    }
+
+    /// Show a search box above the fields. Only fields whose names contain the search text
+    /// are shown, and sections with no matching fields are hidden.
+    pub fn search(mut self, search: bool) -> Self {
+        self.search = search;
+        self
+    }
+
+    fn create_fields(
+        &self,
+        cx: &mut Cx,
+        target: Arc<dyn Inspectable>,
+        filter: Signal<String>,
+    ) -> ViewRef {
+        let field_type = cx.create_memo({
+            let target = target.clone();
+            move |cx| target.reflect(cx).reflect_kind()
+        });
         DynamicKeyed::new(
             move |cx| field_type.get(cx),
             move |ftype| match ftype {
                 ReflectKind::Struct => StructInspector {
                     target: target.clone(),
+                    filter,
                 },
                 _ => todo!(),
             },
@@ -49,21 +85,53 @@ impl Inspector {
 
 impl ViewTemplate for Inspector {
     fn create(&self, cx: &mut Cx) -> impl IntoView {
+        let Some(target) = self.target.clone() else {
+            return ().into_view();
+        };
+        let filter = cx.create_mutable(String::new());
+        let search = self.search.then(|| {
+            TextInput::new(TextInputProps {
+                value: filter.signal(),
+                on_change: Some(cx.create_callback(move |cx, value: String| {
+                    filter.set_clone(cx, value);
+                })),
+                size: Size::Sm,
+                style: style_search.into_handle(),
+                ..default()
+            })
+            .into_view()
+        });
         InspectorPanel::new()
             .title((
-                self.target.name(cx),
+                target.name(cx),
                 Spacer,
                 AddFieldsButton {
-                    target: self.target.clone(),
+                    target: target.clone(),
                 },
             ))
-            .body(self.create_fields(cx, self.target.clone()))
+            .body((search, self.create_fields(cx, target, filter.signal())))
             .expanded(true)
+            .into_view()
     }
 }
 
+/// A field of an inspected struct, along with the reflection attributes which affect how it
+/// is displayed.
+#[derive(Clone, PartialEq)]
+struct StructFieldEntry {
+    name: String,
+    category: Option<&'static str>,
+    tooltip: Option<&'static str>,
+}
+
+/// Whether a field name matches the search filter, ignoring case.
+fn matches_filter(name: &str, filter: &str) -> bool {
+    filter.is_empty() || name.to_lowercase().contains(&filter.trim().to_lowercase())
+}
+
 struct StructInspector {
     target: Arc<dyn Inspectable>,
+    filter: Signal<String>,
 }
 
 impl ViewTemplate for StructInspector {
@@ -72,15 +140,20 @@ impl ViewTemplate for StructInspector {
         // Get the memoized field names of the struct, minus missing optionals. This should
         // isolate the field editors from each other so that they don't constantly update.
         // We will still need to memoize the individual field values.
-        let field_names = cx.create_memo(move |cx| {
-            let ReflectRef::Struct(st) = target.reflect(cx).reflect_ref() else {
+        let fields = cx.create_memo(move |cx| {
+            let reflect = target.reflect(cx);
+            let ReflectRef::Struct(st) = reflect.reflect_ref() else {
                 panic!("Expected ReflectRef::Struct")
             };
+            let struct_info = match reflect.get_represented_type_info() {
+                Some(TypeInfo::Struct(info)) => Some(info),
+                _ => None,
+            };
             let num_fields = st.field_len();
-            let mut names = Vec::with_capacity(num_fields);
-            // Filter out field names for fields with a value of `None`.
+            let mut fields = Vec::with_capacity(num_fields);
             for findex in 0..num_fields {
                 let field = st.field_at(findex).unwrap();
+                // Filter out fields with a value of `None`.
                 if field.reflect_kind() == ReflectKind::Enum
                     && field
                         .reflect_type_path()
@@ -89,33 +162,151 @@ impl ViewTemplate for StructInspector {
                     let ReflectRef::Enum(enum_ref) = field.reflect_ref() else {
                         panic!("Expected ReflectRef::Enum");
                     };
-                    if enum_ref.variant_name() != "None" {
-                        names.push(st.name_at(findex).unwrap().to_string());
+                    if enum_ref.variant_name() == "None" {
+                        continue;
                     }
-                } else {
-                    names.push(st.name_at(findex).unwrap().to_string());
+                }
+                let field_info = struct_info.and_then(|info| info.field_at(findex));
+                fields.push(StructFieldEntry {
+                    name: st.name_at(findex).unwrap().to_string(),
+                    category: field_info
+                        .and_then(|f| f.get_attribute::<InspectorCategory>())
+                        .map(|c| c.0),
+                    tooltip: field_info
+                        .and_then(|f| f.get_attribute::<InspectorTooltip>())
+                        .map(|t| t.0),
+                });
+            }
+            fields
+        });
+
+        // The list of sections, in order of their first field. Uncategorized fields come first.
+        let categories = cx.create_memo(move |cx| {
+            let mut categories: Vec<Option<&'static str>> = Vec::new();
+            for field in fields.get_clone(cx) {
+                if !categories.contains(&field.category) {
+                    categories.push(field.category);
                 }
             }
-            names
+            categories.sort_by_key(|category| category.is_some());
+            categories
         });
+
         let target = self.target.clone();
+        let filter = self.filter;
         For::each(
-            move |cx| field_names.get_clone(cx).into_iter(),
-            move |name| {
-                let path = ParsedPath::parse(name).unwrap();
-                let field = Arc::new(InspectableField {
-                    root: target.clone(),
-                    name: name.to_string(),
-                    path: path.clone(),
-                    container_path: path,
-                    can_remove: false,
-                });
-                FieldInspector { field }.into_view()
+            move |cx| categories.get_clone(cx).into_iter(),
+            move |category| StructSection {
+                target: target.clone(),
+                fields,
+                category: *category,
+                filter,
             },
         )
     }
 }
 
+/// The fields of a struct which belong to a single category. Categorized fields are shown
+/// under a collapsible header, whose expansion state is kept in [`InspectorState`].
+struct StructSection {
+    target: Arc<dyn Inspectable>,
+    fields: Signal<Vec<StructFieldEntry>>,
+    category: Option<&'static str>,
+    filter: Signal<String>,
+}
+
+impl ViewTemplate for StructSection {
+    fn create(&self, cx: &mut Cx) -> impl IntoView {
+        let fields = self.fields;
+        let category = self.category;
+        let filter = self.filter;
+        let visible = cx.create_memo(move |cx| {
+            let filter = filter.get_clone(cx);
+            fields
+                .get_clone(cx)
+                .into_iter()
+                .filter(|field| field.category == category && matches_filter(&field.name, &filter))
+                .collect::<Vec<_>>()
+        });
+
+        let target = self.target.clone();
+        let field_list = move || {
+            let target = target.clone();
+            For::each(
+                move |cx| visible.get_clone(cx).into_iter(),
+                move |entry| {
+                    let path = ParsedPath::parse(&entry.name).unwrap();
+                    let field = Arc::new(InspectableField {
+                        root: target.clone(),
+                        name: entry.name.clone(),
+                        path: path.clone(),
+                        container_path: path,
+                        can_remove: false,
+                        tooltip: entry.tooltip,
+                    });
+                    FieldInspector { field }.into_view()
+                },
+            )
+        };
+
+        let Some(title) = category else {
+            return field_list().into_view();
+        };
+
+        let key = format!("{}::{}", self.target.reflect(cx).reflect_type_path(), title);
+        let expanded = cx.create_derived({
+            let key = key.clone();
+            move |cx| cx.use_resource::<InspectorState>().is_expanded(&key)
+        });
+        let toggle = cx.create_callback(move |cx, _| {
+            let mut state = cx.world_mut().resource_mut::<InspectorState>();
+            let expanded = state.is_expanded(&key);
+            state.set_expanded(key.clone(), !expanded);
+        });
+        let has_fields = cx.create_memo(move |cx| !visible.get_clone(cx).is_empty());
+        let filtering = cx.create_memo(move |cx| !filter.get_clone(cx).trim().is_empty());
+        Cond::new(
+            move |cx| has_fields.get(cx),
+            move || {
+                let field_list = field_list.clone();
+                Fragment::new((
+                    Element::<NodeBundle>::new()
+                        .named("Inspector::Section")
+                        .style((typography::text_default, style_section_header))
+                        .children((
+                            Button::new()
+                                .children(Cond::new(
+                                    move |cx| expanded.get(cx),
+                                    || {
+                                        Icon::new("obsidian_ui://icons/chevron_down.png")
+                                            .size(Vec2::splat(12.))
+                                            .color(Color::from(colors::DIM))
+                                    },
+                                    || {
+                                        Icon::new("obsidian_ui://icons/chevron_right.png")
+                                            .size(Vec2::splat(12.))
+                                            .color(Color::from(colors::DIM))
+                                    },
+                                ))
+                                .size(Size::Xxs)
+                                .minimal(true)
+                                .on_click(toggle),
+                            title,
+                        )),
+                    // Matching fields are shown in collapsed sections while searching.
+                    Cond::new(
+                        move |cx| expanded.get(cx) || filtering.get(cx),
+                        field_list,
+                        || (),
+                    ),
+                ))
+            },
+            || (),
+        )
+        .into_view()
+    }
+}
+
 struct FieldInspector {
     field: Arc<InspectableField>,
 }
@@ -151,6 +342,7 @@ impl ViewTemplate for FieldInspector {
                     path,
                     container_path: field.path.clone(),
                     can_remove: true,
+                    tooltip: field.tooltip,
                 });
                 for factory in factories.0.iter().rev() {
                     if let Some(view_ref) = factory.create_inspector(cx, &access) {
@@ -220,6 +412,7 @@ impl ViewTemplate for AddFieldsButton {
                                             path: ParsedPath::parse(name).unwrap(),
                                             container_path: ParsedPath::parse(name).unwrap(),
                                             can_remove: false,
+                                            tooltip: None,
                                         }),
                                         // path: ParsedPath::parse(name).unwrap(),
                                         // name: name.to_string(),
@@ -324,3 +517,20 @@ impl ViewTemplate for AddStructFieldItem {
 fn style_menu_icon(ss: &mut StyleBuilder) {
     ss.margin((4, 0));
 }
+
+fn style_search(ss: &mut StyleBuilder) {
+    ss.grid_column_span(2).margin_bottom(2);
+}
+
+fn style_section_header(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Row)
+        .align_items(ui::AlignItems::Center)
+        .grid_column_span(2)
+        .gap(2)
+        .margin_top(4)
+        .font_size(14)
+        .color(colors::FOREGROUND)
+        .border_bottom(1)
+        .border_color(colors::U3);
+}
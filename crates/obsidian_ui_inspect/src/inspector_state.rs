@@ -0,0 +1,24 @@
+use bevy::{prelude::*, utils::HashMap};
+
+/// Resource which stores which sections of the inspector are expanded, keyed by the type
+/// path of the inspected struct and the name of the section. This resource is registered for
+/// reflection, so it can be saved along with the rest of the app's settings and re-inserted at
+/// startup.
+#[derive(Resource, Reflect, Debug, Default, Clone)]
+#[reflect(Resource, Default)]
+pub struct InspectorState {
+    /// Expansion state of sections, by key. Sections which are not listed are expanded.
+    pub expanded: HashMap<String, bool>,
+}
+
+impl InspectorState {
+    /// Whether the section with the given key is expanded.
+    pub fn is_expanded(&self, key: &str) -> bool {
+        self.expanded.get(key).copied().unwrap_or(true)
+    }
+
+    /// Set whether the section with the given key is expanded.
+    pub fn set_expanded(&mut self, key: impl Into<String>, expanded: bool) {
+        self.expanded.insert(key.into(), expanded);
+    }
+}
@@ -1,16 +1,20 @@
+mod attributes;
 mod default_factory;
 mod editors;
 mod inspectable;
 mod inspector;
 mod inspector_factory;
+mod inspector_state;
 mod templates;
 
 use bevy::app::{App, Plugin};
 use default_factory::DefaultInspectorFactory;
 
+pub use attributes::*;
 pub use inspectable::*;
 pub use inspector::*;
 pub use inspector_factory::*;
+pub use inspector_state::*;
 use templates::color_edit::RecentColors;
 
 pub struct InspectorPlugin;
@@ -18,6 +22,8 @@ pub struct InspectorPlugin;
 impl Plugin for InspectorPlugin {
     fn build(&self, app: &mut App) {
         app.register_inspector::<DefaultInspectorFactory>()
-            .init_resource::<RecentColors>();
+            .init_resource::<RecentColors>()
+            .init_resource::<InspectorState>()
+            .register_type::<InspectorState>();
     }
 }
@@ -3,6 +3,7 @@ use bevy_reactor::*;
 use obsidian_ui::{
     colors,
     controls::{Button, Icon, Spacer},
+    floating::{FloatAlign, FloatPosition, FloatSide, Floating},
     typography,
 };
 
@@ -31,10 +32,22 @@ impl ViewTemplate for FieldLabel {
         let remove = cx.create_callback(move |cx, _| {
             field.remove(cx);
         });
-        Element::<NodeBundle>::new()
+        let field = self.field.clone();
+        let revert = cx.create_callback(move |cx, _| {
+            field.revert(cx);
+        });
+        let id = cx.create_entity();
+        Element::<NodeBundle>::for_entity(id)
             .style((typography::text_default, style_field_label))
             .children((
                 self.field.name(),
+                self.field
+                    .tooltip()
+                    .map(|text| FieldTooltip { anchor: id, text }.into_view()),
+                RevertButton {
+                    field: self.field.clone(),
+                    on_revert: revert,
+                },
                 Cond::new(
                     move |_cx| can_remove,
                     move || {
@@ -83,12 +96,24 @@ impl ViewTemplate for FieldLabelWide {
         let remove = cx.create_callback(move |cx, _| {
             field.remove(cx);
         });
-        Element::<NodeBundle>::new()
+        let field = self.field.clone();
+        let revert = cx.create_callback(move |cx, _| {
+            field.revert(cx);
+        });
+        let id = cx.create_entity();
+        Element::<NodeBundle>::for_entity(id)
             .style((typography::text_default, style_field_label_wide))
             .children((
                 self.field.name(),
+                self.field
+                    .tooltip()
+                    .map(|text| FieldTooltip { anchor: id, text }.into_view()),
                 Spacer,
                 self.buttons.clone(),
+                RevertButton {
+                    field: self.field.clone(),
+                    on_revert: revert,
+                },
                 Cond::new(
                     move |_cx| can_remove,
                     move || {
@@ -109,3 +134,89 @@ impl ViewTemplate for FieldLabelWide {
             ))
     }
 }
+
+fn style_tooltip(ss: &mut StyleBuilder) {
+    ss.position(ui::PositionType::Absolute)
+        .padding((6, 2))
+        .border_radius(3.0)
+        .font_size(13)
+        .max_width(240)
+        .background_color(colors::U1)
+        .color(colors::FOREGROUND)
+        .pointer_events(false);
+}
+
+/// Tooltip which is shown while the pointer is over a field label.
+struct FieldTooltip {
+    anchor: Entity,
+    text: &'static str,
+}
+
+impl ViewTemplate for FieldTooltip {
+    fn create(&self, cx: &mut Cx) -> impl IntoView {
+        let anchor = self.anchor;
+        let text = self.text;
+        let hovering = cx.create_hover_signal(anchor);
+        Cond::new(
+            move |cx| hovering.get(cx),
+            move || {
+                Portal::new(
+                    Element::<NodeBundle>::new()
+                        .named("FieldLabel::Tooltip")
+                        .style((typography::text_default, style_tooltip))
+                        .insert(Floating {
+                            anchor: anchor.into(),
+                            position: vec![
+                                FloatPosition {
+                                    side: FloatSide::Bottom,
+                                    align: FloatAlign::Start,
+                                    stretch: false,
+                                    gap: 4.0,
+                                },
+                                FloatPosition {
+                                    side: FloatSide::Top,
+                                    align: FloatAlign::Start,
+                                    stretch: false,
+                                    gap: 4.0,
+                                },
+                            ],
+                        })
+                        .children(text),
+                )
+                .layer("tooltips")
+            },
+            || (),
+        )
+    }
+}
+
+/// Button which is shown when a field has been edited, and reverts it when clicked.
+struct RevertButton {
+    field: InspectableField,
+    on_revert: Callback,
+}
+
+impl ViewTemplate for RevertButton {
+    fn create(&self, cx: &mut Cx) -> impl IntoView {
+        let field = self.field.clone();
+        let can_revert = cx.create_memo(move |cx| field.can_revert(cx));
+        let on_revert = self.on_revert;
+        Cond::new(
+            move |cx| can_revert.get(cx),
+            move || {
+                Button::new()
+                    .children(
+                        Icon::new("obsidian_ui://icons/disc.png")
+                            .size(Vec2::splat(8.))
+                            .color(Color::from(colors::ACCENT))
+                            .style(|ss: &mut StyleBuilder| {
+                                ss.margin((4, 0));
+                            }),
+                    )
+                    .minimal(true)
+                    .on_click(on_revert)
+            },
+            || (),
+        )
+    }
+}
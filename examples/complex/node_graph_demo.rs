@@ -3,8 +3,8 @@ use bevy_reactor::*;
 use obsidian_ui::{
     colors,
     controls::{
-        EdgeDisplay, GraphDisplay, InputTerminalDisplay, NodeDisplay, OutputTerminalDisplay,
-        Slider, Swatch,
+        Button, EdgeDisplay, GraphDisplay, InputTerminalDisplay, LayeredGraphLayout, NodeDisplay,
+        OutputTerminalDisplay, Slider, Swatch,
     },
 };
 
@@ -241,6 +241,22 @@ impl FromWorld for DemoGraphRoot {
 
 pub struct NodeGraphDemo {}
 
+/// Approximate size of a node, used for automatic layout.
+const NODE_SIZE: Vec2 = Vec2::new(180., 120.);
+
+fn style_node_graph_demo(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Column)
+        .flex_grow(1.);
+}
+
+fn style_node_graph_toolbar(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Row)
+        .gap(4)
+        .padding(4);
+}
+
 fn style_node_graph(ss: &mut StyleBuilder) {
     ss.flex_grow(1.).border_left(1).border_color(Color::BLACK);
 }
@@ -258,25 +274,61 @@ fn style_input_label(ss: &mut StyleBuilder) {
 }
 
 impl ViewTemplate for NodeGraphDemo {
-    fn create(&self, _cx: &mut Cx) -> impl IntoView {
-        GraphDisplay::new()
+    fn create(&self, cx: &mut Cx) -> impl IntoView {
+        // Arrange the nodes according to their connections.
+        let auto_layout = cx.create_callback(|cx: &mut Cx, _| {
+            let world = cx.world_mut();
+            let graph = world.resource::<DemoGraphRoot>();
+            let nodes = graph
+                .nodes
+                .iter()
+                .map(|node| (*node, NODE_SIZE))
+                .collect::<Vec<_>>();
+            let edges = graph
+                .edges
+                .iter()
+                .filter_map(|edge| {
+                    let edge = world.get::<Edge>(*edge)?;
+                    let src = world.get::<Parent>(edge.src)?.get();
+                    let dst = world.get::<Parent>(edge.dst)?.get();
+                    Some((src, dst))
+                })
+                .collect::<Vec<_>>();
+            let positions = LayeredGraphLayout::new().compute(&nodes, &edges);
+            for (node, position) in positions {
+                if let Some(mut pos) = world.get_mut::<NodePosition>(node) {
+                    pos.0 = position;
+                }
+            }
+        });
+
+        Element::<NodeBundle>::new()
+            .named("NodeGraphDemo")
+            .style(style_node_graph_demo)
             .children((
-                For::each(
-                    |cx| {
-                        let graph = cx.use_resource::<DemoGraphRoot>();
-                        graph.edges.clone().into_iter()
-                    },
-                    |id| EdgeTemplate { id: *id },
-                ),
-                For::each(
-                    |cx| {
-                        let graph = cx.use_resource::<DemoGraphRoot>();
-                        graph.nodes.clone().into_iter()
-                    },
-                    |id| NodeTemplate { id: *id },
-                ),
+                Element::<NodeBundle>::new()
+                    .style(style_node_graph_toolbar)
+                    .children(Button::new().children("Auto Layout").on_click(auto_layout)),
+                GraphDisplay::new()
+                    .minimap(true)
+                    .children((
+                        For::each(
+                            |cx| {
+                                let graph = cx.use_resource::<DemoGraphRoot>();
+                                graph.edges.clone().into_iter()
+                            },
+                            |id| EdgeTemplate { id: *id },
+                        ),
+                        For::each(
+                            |cx| {
+                                let graph = cx.use_resource::<DemoGraphRoot>();
+                                graph.nodes.clone().into_iter()
+                            },
+                            |id| NodeTemplate { id: *id },
+                        ),
+                    ))
+                    .style(style_node_graph),
             ))
-            .style(style_node_graph)
     }
 }
 
@@ -293,6 +345,7 @@ impl ViewTemplate for NodeTemplate {
         NodeDisplay::new()
             .position(position)
             .title(title)
+            .animate(0.3)
             .children((
                 For::each(
                     move |cx| {
@@ -2,17 +2,25 @@ use std::sync::Arc;
 
 use bevy::prelude::*;
 use bevy_reactor::*;
-use obsidian_ui_inspect::{InspectableResource, Inspector};
+use obsidian_ui_inspect::{InspectableResource, Inspector, InspectorCategory, InspectorTooltip};
 
 #[derive(Resource, Debug, Reflect, Clone, Default)]
 pub struct TestStruct {
+    #[reflect(@InspectorTooltip("Whether the object is selected"))]
     pub selected: bool,
+    #[reflect(@InspectorCategory("Transform"))]
     pub scale: f32,
+    #[reflect(@InspectorCategory("Material"))]
     pub color: Srgba,
+    #[reflect(@InspectorCategory("Transform"))]
     pub position: Vec3,
 
+    #[reflect(@InspectorCategory("Material"))]
+    #[reflect(@InspectorTooltip("Disable lighting for this material"))]
     pub unlit: Option<bool>,
+    #[reflect(@InspectorCategory("Material"))]
     pub roughness: Option<f32>,
+    #[reflect(@InspectorCategory("Material"))]
     pub metalness: Option<f32>,
     pub factors: Vec<f32>,
 }
@@ -31,6 +39,6 @@ impl<T: Resource> ResourcePropertyInspector<T> {
 
 impl<T: Resource + Reflect> ViewTemplate for ResourcePropertyInspector<T> {
     fn create(&self, _cx: &mut Cx) -> impl IntoView {
-        Inspector::new(Arc::<InspectableResource<T>>::default())
+        Inspector::new(Arc::<InspectableResource<T>>::default()).search(true)
     }
 }
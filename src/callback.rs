@@ -1,108 +1,114 @@
-use bevy::{ecs::system::SystemParam, prelude::*};
+use std::marker::PhantomData;
 
-use crate::Cx;
+use bevy::{
+    ecs::{system::SystemParam, world::Command},
+    prelude::*,
+};
 
-pub(crate) trait CallbackFnRef<P> {
-    fn call(&self, cx: &mut Cx, props: P);
+use crate::{origin::HandleOrigin, Cx, RunContextWrite};
+
+pub(crate) trait CallbackFnRef<P, R> {
+    fn call(&self, cx: &mut Cx, props: P) -> R;
 }
 
-impl<P, F: Fn(&mut Cx, P)> CallbackFnRef<P> for F {
-    fn call(&self, cx: &mut Cx, props: P) {
-        self(cx, props);
+impl<P, R, F: Fn(&mut Cx, P) -> R> CallbackFnRef<P, R> for F {
+    fn call(&self, cx: &mut Cx, props: P) -> R {
+        self(cx, props)
     }
 }
 
-pub(crate) trait CallbackFnMutRef<P> {
-    fn call(&mut self, cx: &mut Cx, props: P);
+pub(crate) trait CallbackFnMutRef<P, R> {
+    fn call(&mut self, cx: &mut Cx, props: P) -> R;
 }
 
-impl<P, F: FnMut(&mut Cx, P)> CallbackFnMutRef<P> for F {
-    fn call(&mut self, cx: &mut Cx, props: P) {
-        self(cx, props);
+impl<P, R, F: FnMut(&mut Cx, P) -> R> CallbackFnMutRef<P, R> for F {
+    fn call(&mut self, cx: &mut Cx, props: P) -> R {
+        self(cx, props)
     }
 }
 
-/// Contains a boxed, type-erased callback.
+/// Contains a boxed, type-erased callback. The component type is parameterized by both
+/// the input and output types, so the ECS acts as a typed registry of callbacks.
 #[derive(Component)]
-pub(crate) struct CallbackFnCell<P> {
-    pub(crate) inner: Option<Box<dyn CallbackFnRef<P> + Send + Sync>>,
+pub(crate) struct CallbackFnCell<P, R = ()> {
+    pub(crate) inner: Option<Box<dyn CallbackFnRef<P, R> + Send + Sync>>,
 }
 
 #[derive(Component)]
-pub(crate) struct CallbackFnMutCell<P> {
-    pub(crate) inner: Option<Box<dyn CallbackFnMutRef<P> + Send + Sync>>,
+pub(crate) struct CallbackFnMutCell<P, R = ()> {
+    pub(crate) inner: Option<Box<dyn CallbackFnMutRef<P, R> + Send + Sync>>,
 }
 
-/// Contains a reference to a callback. `P` is the type of the props.
+/// Contains a reference to a callback. `P` is the type of the props, and `R` is the
+/// type of the value returned by the callback.
 #[derive(PartialEq)]
-pub struct Callback<P = ()> {
+pub struct Callback<P = (), R = ()> {
     pub(crate) id: Entity,
-    pub(crate) marker: std::marker::PhantomData<P>,
+    pub(crate) origin: HandleOrigin,
+    pub(crate) marker: std::marker::PhantomData<fn(P) -> R>,
+}
+
+impl<P, R> Callback<P, R> {
+    /// The entity that holds the callback function.
+    pub fn id(&self) -> Entity {
+        self.id
+    }
 }
 
-impl<P> Copy for Callback<P> {}
-impl<P> Clone for Callback<P> {
+impl<P, R> Copy for Callback<P, R> {}
+impl<P, R> Clone for Callback<P, R> {
     fn clone(&self) -> Self {
         *self
     }
 }
 
-/// An event which will call a callback with the given props.
-#[derive(Event)]
-#[allow(dead_code)] // For now
-pub(crate) struct DeferredCall<P> {
-    pub(crate) receiver: Entity,
+/// A command which invokes a callback with the given props.
+pub(crate) struct RunCallbackCommand<P, R> {
+    pub(crate) callback: Callback<P, R>,
     pub(crate) props: P,
 }
 
+impl<P: Send + 'static, R: Default + 'static> Command for RunCallbackCommand<P, R> {
+    fn apply(self, world: &mut World) {
+        world.run_callback(self.callback, self.props);
+    }
+}
+
 /// Type that allows us to call a callback without having a reference to a `World`.
-/// These calls are deferred until the next frame.
+/// These calls are deferred until the next time commands are applied. Because the call
+/// is deferred, the callback's return value is discarded.
 #[derive(SystemParam)]
-pub struct CallDeferred<'w, P: Send + Sync + 'static = ()> {
-    pub(crate) writer: EventWriter<'w, DeferredCall<P>>,
+pub struct CallDeferred<'w, 's, P: Send + Sync + 'static = (), R: Default + 'static = ()> {
+    pub(crate) commands: Commands<'w, 's>,
+    pub(crate) marker: PhantomData<fn(P) -> R>,
 }
 
-impl<'w, P: Send + Sync + 'static> CallDeferred<'w, P> {
-    /// Call the callback with the given props. This will be processed on the next frame.
-    pub fn call(&mut self, callback: Callback<P>, props: P) {
-        self.writer.send(DeferredCall {
-            receiver: callback.id,
-            props,
-        });
+impl<'w, 's, P: Send + Sync + 'static, R: Default + 'static> CallDeferred<'w, 's, P, R> {
+    /// Call the callback with the given props. This will be processed the next time
+    /// commands are applied.
+    pub fn call(&mut self, callback: Callback<P, R>, props: P) {
+        self.commands.add(RunCallbackCommand { callback, props });
     }
 }
 
-// #[allow(dead_code)] // For now
-// /// System that runs callbacks from an event listener.
-// pub fn run_deferred_callbacks<P: 'static + Send + Sync>(world: &mut World) {
-//     let tick = world.change_tick();
-//     let mut events = world.get_resource_mut::<Events<DeferredCall<P>>>().unwrap();
-//     let events = events.drain().collect::<Vec<_>>();
-//     let mut tracking = TrackingScope::new(tick);
-//     for event in events {
-//         let mut callback_entity = world.entity_mut(event.receiver);
-//         if let Some(mut callback_cmp) = callback_entity.get_mut::<CallbackFnCell<P>>() {
-//             let mut callback_fn = callback_cmp.inner.take();
-//             let callback_box = callback_fn.as_ref().expect("Callback is not present");
-//             let mut cx = Cx::new(event.props, world, &mut tracking);
-//             callback_box.call(&mut cx);
-//             let mut callback_entity = world.entity_mut(event.receiver);
-//             callback_entity
-//                 .get_mut::<CallbackFnCell<P>>()
-//                 .unwrap()
-//                 .inner = callback_fn.take();
-//         } else if let Some(mut callback_cmp) = callback_entity.get_mut::<CallbackFnMutCell<P>>() {
-//             let mut callback_fn = callback_cmp.inner.take();
-//             let callback_box = callback_fn.as_mut().expect("Callback is not present");
-//             let mut cx = Cx::new(event.props, world, &mut tracking);
-//             callback_box.call(&mut cx);
-//             let mut callback_entity = world.entity_mut(event.receiver);
-//             callback_entity
-//                 .get_mut::<CallbackFnMutCell<P>>()
-//                 .unwrap()
-//                 .inner = callback_fn.take();
-//         } else {
-//             warn!("No callback found for {:?}", event.receiver);
-//         }
-//     }
-// }
+/// Extension trait for [`Commands`] which allows callbacks to be invoked from ordinary
+/// systems.
+pub trait CommandsRunCallback {
+    /// Queue a command to invoke `callback` with the given props. The callback's return
+    /// value is discarded.
+    fn run_callback<P: Send + 'static, R: Default + 'static>(
+        &mut self,
+        callback: Callback<P, R>,
+        props: P,
+    );
+}
+
+impl<'w, 's> CommandsRunCallback for Commands<'w, 's> {
+    fn run_callback<P: Send + 'static, R: Default + 'static>(
+        &mut self,
+        callback: Callback<P, R>,
+        props: P,
+    ) {
+        self.add(RunCallbackCommand { callback, props });
+    }
+}
@@ -1,4 +1,5 @@
 use bevy::{
+    asset::AssetId,
     prelude::*,
     render::{
         camera::RenderTarget,
@@ -6,6 +7,8 @@ use bevy::{
             Extent3d, TextureDescriptor, TextureDimension, TextureFormat, TextureUsages,
         },
     },
+    utils::Uuid,
+    window::PrimaryWindow,
 };
 
 use crate::{
@@ -18,15 +21,50 @@ use crate::{
 #[derive(Component)]
 pub(crate) struct CompositorCamera;
 
+/// Determines the size of the texture which a [`Compositor`] renders into.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq)]
+pub enum CompositorSize {
+    /// The texture is resized to fit the children of the compositor.
+    #[default]
+    FitContent,
+    /// The texture has a fixed size, in pixels. Children are clipped to it.
+    Fixed(UVec2),
+    /// The texture is resized to match the logical size of the primary window.
+    FillWindow,
+}
+
+/// Component on the display node of a [`Compositor`] which holds the texture that the
+/// compositor renders into. Systems can query for this to use the texture in their own
+/// materials or post-processing, together with a marker inserted on the compositor.
+#[derive(Component, Clone, Debug)]
+pub struct CompositorTexture(pub Handle<Image>);
+
 /// A `Compositor` is a UI element which renders its children to an offscreen buffer, then
 /// displays that buffer as a single element. This can be used for things like animating
 /// opacity.
 pub struct Compositor {
     children: Vec<ChildView>,
     camera: Option<Entity>,
-    image: Option<Handle<Image>>,
     image_entity: Option<Entity>,
 
+    /// Texture the compositor renders into, unless `target_image` is set. The id is allocated
+    /// when the compositor is constructed, so that it can be used before the compositor is
+    /// built; the image itself is added when the compositor is built, and removed when it is
+    /// razed.
+    image: Handle<Image>,
+
+    /// Image supplied by the caller to render into, instead of a new one.
+    target_image: Option<Handle<Image>>,
+
+    /// How the size of the image is determined.
+    size: CompositorSize,
+
+    /// Whether the image is displayed in the UI, or only rendered into.
+    offscreen: bool,
+
+    /// Color the image is cleared to before the children are rendered.
+    clear_color: Color,
+
     /// List of effects to be added to the image entity.
     effects: Vec<Box<dyn EntityEffect>>,
 }
@@ -44,12 +82,92 @@ impl Compositor {
                 })
                 .collect(),
             camera: None,
-            image: None,
             image_entity: None,
+            image: Handle::Weak(AssetId::Uuid {
+                uuid: Uuid::new_v4(),
+            }),
+            target_image: None,
+            size: CompositorSize::default(),
+            offscreen: false,
+            clear_color: Color::default(),
             effects: Vec::new(),
         }
     }
 
+    /// Render the children into the given image, rather than a new one. This allows other
+    /// elements to sample what the compositor draws, for example to blur the content behind a
+    /// dialog. The image is resized to fit the children.
+    pub fn with_image(mut self, image: Handle<Image>) -> Self {
+        self.target_image = Some(image);
+        self
+    }
+
+    /// Set how the size of the texture is determined. The default is
+    /// [`CompositorSize::FitContent`].
+    pub fn size(mut self, size: CompositorSize) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Render the children into the texture without displaying it in the UI. This is useful
+    /// when the texture is consumed elsewhere, such as in a material on a 3D mesh.
+    pub fn offscreen(mut self) -> Self {
+        self.offscreen = true;
+        self
+    }
+
+    /// Set the color that the texture is cleared to before the children are rendered. Use a
+    /// transparent color when the texture is blended over other content.
+    pub fn clear_color(mut self, color: Color) -> Self {
+        self.clear_color = color;
+        self
+    }
+
+    /// Create a new texture which a compositor can render into, to be passed to
+    /// [`with_image`](Self::with_image). The texture starts small, and is resized to fit.
+    pub fn new_texture(images: &mut Assets<Image>) -> Handle<Image> {
+        images.add(Self::texture_image())
+    }
+
+    /// Create a small image which can be rendered into.
+    fn texture_image() -> Image {
+        let size = Extent3d {
+            width: 16,
+            height: 16,
+            ..Extent3d::default()
+        };
+
+        let mut image = Image {
+            texture_descriptor: TextureDescriptor {
+                label: None,
+                size,
+                dimension: TextureDimension::D2,
+                format: TextureFormat::Bgra8UnormSrgb,
+                mip_level_count: 1,
+                sample_count: 1,
+                usage: TextureUsages::TEXTURE_BINDING
+                    | TextureUsages::COPY_DST
+                    | TextureUsages::RENDER_ATTACHMENT,
+                view_formats: &[],
+            },
+            ..Image::default()
+        };
+        image.resize(size);
+        image
+    }
+
+    /// Returns the texture that the compositor renders into: the image passed to
+    /// [`with_image`](Self::with_image), if any, or else a texture owned by the compositor.
+    /// The handle can be used as soon as the compositor is constructed, for example in a
+    /// material which is built alongside it, although the texture only holds an image while
+    /// the compositor is built. The same handle is available from the [`CompositorTexture`]
+    /// component on the display node.
+    pub fn texture_handle(&self) -> Handle<Image> {
+        self.target_image
+            .clone()
+            .unwrap_or_else(|| self.image.clone())
+    }
+
     fn attach_children(&self, world: &mut World) {
         let mut count: usize = 0;
         for child in self.children.iter() {
@@ -71,7 +189,11 @@ impl Compositor {
 
 impl View for Compositor {
     fn nodes(&self) -> NodeSpan {
-        NodeSpan::Node(self.image_entity.unwrap())
+        if self.offscreen {
+            NodeSpan::Empty
+        } else {
+            NodeSpan::Node(self.image_entity.unwrap())
+        }
     }
 
     fn build(&mut self, view_entity: Entity, world: &mut World) {
@@ -83,45 +205,32 @@ impl View for Compositor {
         }
 
         // Create offscreen buffer. Start with a default size, will resize later.
-        let size = Extent3d {
-            width: 16,
-            height: 16,
-            ..Extent3d::default()
-        };
-
-        let mut image = Image {
-            texture_descriptor: TextureDescriptor {
-                label: None,
-                size,
-                dimension: TextureDimension::D2,
-                format: TextureFormat::Bgra8UnormSrgb,
-                mip_level_count: 1,
-                sample_count: 1,
-                usage: TextureUsages::TEXTURE_BINDING
-                    | TextureUsages::COPY_DST
-                    | TextureUsages::RENDER_ATTACHMENT,
-                view_formats: &[],
-            },
-            ..Image::default()
-        };
-        image.resize(size);
-
-        let image_handle = world
-            .get_resource_mut::<Assets<Image>>()
-            .unwrap()
-            .add(image);
+        let image_handle = self.texture_handle();
+        if self.target_image.is_none() {
+            world
+                .resource_mut::<Assets<Image>>()
+                .insert(self.image.id(), Self::texture_image());
+        }
 
         // Create the entity that will display the image on the main UI camera.
         let image_entity = world
-            .spawn((ImageBundle {
-                image: UiImage::new(image_handle.clone()),
-                style: Style {
-                    width: Val::Auto,
-                    height: Val::Auto,
+            .spawn((
+                ImageBundle {
+                    image: UiImage::new(image_handle.clone()),
+                    style: Style {
+                        width: Val::Auto,
+                        height: Val::Auto,
+                        display: if self.offscreen {
+                            Display::None
+                        } else {
+                            Display::Flex
+                        },
+                        ..default()
+                    },
                     ..default()
                 },
-                ..default()
-            },))
+                CompositorTexture(image_handle.clone()),
+            ))
             .id();
 
         // Create a 2d camera which will render the children to the offscreen buffer.
@@ -131,7 +240,7 @@ impl View for Compositor {
                     Camera2dBundle {
                         camera: Camera {
                             order: -1,
-                            clear_color: ClearColorConfig::Custom(Color::default()),
+                            clear_color: ClearColorConfig::Custom(self.clear_color),
                             target: RenderTarget::Image(image_handle.clone()),
                             ..Camera::default()
                         },
@@ -139,10 +248,10 @@ impl View for Compositor {
                         ..default()
                     },
                     CompositorCamera,
+                    self.size,
                 ))
                 .id(),
         );
-        self.image = Some(image_handle);
         self.image_entity = Some(image_entity);
 
         // Insert components from effects.
@@ -168,7 +277,11 @@ impl View for Compositor {
         world.despawn_owned_recursive(view_entity);
         world.despawn(self.image_entity.unwrap());
         world.despawn(self.camera.unwrap());
-        self.image = None;
+        if self.target_image.is_none() {
+            world
+                .resource_mut::<Assets<Image>>()
+                .remove(self.image.id());
+        }
     }
 
     fn children_changed(&mut self, _view_entity: Entity, world: &mut World) -> bool {
@@ -196,12 +309,14 @@ impl WithStyles for Compositor {
     }
 }
 
+/// Resizes the texture of each compositor according to its [`CompositorSize`].
 pub(crate) fn update_compositor_size(
-    query_camera: Query<(Entity, &Camera), With<CompositorCamera>>,
+    query_camera: Query<(Entity, &Camera, &CompositorSize), With<CompositorCamera>>,
     query_children: Query<(&Node, &GlobalTransform, &TargetCamera)>,
+    windows: Query<&Window, With<PrimaryWindow>>,
     mut images: ResMut<Assets<Image>>,
 ) {
-    for (camera_entity, camera) in query_camera.iter() {
+    for (camera_entity, camera, policy) in query_camera.iter() {
         let image = images.get_mut(camera.target.as_image().unwrap()).unwrap();
         let mut size = Extent3d {
             width: 16,
@@ -209,12 +324,26 @@ pub(crate) fn update_compositor_size(
             ..Extent3d::default()
         };
 
-        for (node, transform, target) in query_children.iter() {
-            let target = target.0;
-            if target == camera_entity {
-                let rect = node.logical_rect(transform);
-                size.width = size.width.max(rect.max.x.ceil() as u32);
-                size.height = size.height.max(rect.max.y.ceil() as u32);
+        match policy {
+            CompositorSize::FitContent => {
+                for (node, transform, target) in query_children.iter() {
+                    let target = target.0;
+                    if target == camera_entity {
+                        let rect = node.logical_rect(transform);
+                        size.width = size.width.max(rect.max.x.ceil() as u32);
+                        size.height = size.height.max(rect.max.y.ceil() as u32);
+                    }
+                }
+            }
+            CompositorSize::Fixed(fixed) => {
+                size.width = fixed.x.max(1);
+                size.height = fixed.y.max(1);
+            }
+            CompositorSize::FillWindow => {
+                if let Ok(window) = windows.get_single() {
+                    size.width = (window.width().ceil() as u32).max(1);
+                    size.height = (window.height().ceil() as u32).max(1);
+                }
             }
         }
 
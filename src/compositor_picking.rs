@@ -0,0 +1,419 @@
+use bevy::{
+    ecs::event::ManualEventReader,
+    prelude::*,
+    render::camera::NormalizedRenderTarget,
+    ui::UiStack,
+    utils::{HashMap, HashSet, Uuid},
+    window::PrimaryWindow,
+};
+use bevy_mod_picking::{
+    picking_core::{PickSet, Pickable, PointerBundle},
+    pointer::{InputMove, InputPress, Location, PointerId, PressDirection},
+};
+
+use crate::compositor::CompositorTexture;
+
+/// Displays the texture of a [`Compositor`](crate::Compositor) on a flat quad in the world.
+/// Adding this to the entity which shows the texture lets the [`CompositorPickingPlugin`]
+/// map pointer rays which hit the quad back into the compositor's UI. The quad is centered
+/// on the entity's transform and faces along its local +Z axis.
+#[derive(Component, Clone, Debug)]
+pub struct CompositorQuad {
+    /// The texture the compositor renders into.
+    pub image: Handle<Image>,
+
+    /// The size of the quad, in local units.
+    pub size: Vec2,
+}
+
+/// Plugin which delivers pointer input to the UI inside a [`Compositor`](crate::Compositor)
+/// whose texture is displayed in the UI, or on a [`CompositorQuad`] in the world. The mouse
+/// position on the presentation is mapped into the compositor's texture and forwarded to a
+/// virtual pointer which targets the texture, so that the regular UI picking backend and
+/// existing `On` handlers work inside the compositor.
+///
+/// A texture displayed in the UI only receives input where it is not covered by other UI
+/// nodes, unless they are made transparent to the pointer with [`Pickable::IGNORE`]. The
+/// virtual pointer of a texture is removed along with the last node or quad which shows it.
+pub struct CompositorPickingPlugin;
+
+impl Plugin for CompositorPickingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CompositorPointers>().add_systems(
+            PreUpdate,
+            forward_compositor_pointers
+                .after(PickSet::Input)
+                .before(PickSet::PostInput),
+        );
+    }
+}
+
+/// The virtual pointers used to deliver input to compositors.
+#[derive(Resource, Default)]
+struct CompositorPointers {
+    /// The virtual pointer for each compositor texture, and the entity which represents it.
+    pointers: HashMap<AssetId<Image>, (PointerId, Entity)>,
+
+    /// The pointer for the texture which the mouse is currently over, if any.
+    current: Option<(PointerId, Handle<Image>)>,
+}
+
+/// A UI node which may be picked, in the order they are stacked from top to bottom.
+struct StackedNode<'a> {
+    /// The area covered by the node, in logical pixels.
+    rect: Rect,
+
+    /// The compositor texture the node displays, if any.
+    texture: Option<&'a Handle<Image>>,
+
+    /// Whether the node hides the nodes beneath it from the pointer.
+    blocks: bool,
+}
+
+/// The topmost UI node under the pointer which matters for compositor picking.
+#[derive(Debug, PartialEq)]
+enum PanelHit<'a> {
+    /// A node which displays a compositor texture, and the position on it as a fraction of
+    /// its size.
+    Texture(&'a Handle<Image>, Vec2),
+
+    /// A node which hides everything beneath it from the pointer.
+    Blocked,
+}
+
+/// Find the topmost node under `position` which either displays a compositor texture, or
+/// blocks the pointer. Returns `None` if the pointer is not over any such node.
+fn find_panel<'a>(
+    position: Vec2,
+    nodes: impl IntoIterator<Item = StackedNode<'a>>,
+) -> Option<PanelHit<'a>> {
+    nodes.into_iter().find_map(|node| {
+        if node.rect.is_empty() || !node.rect.contains(position) {
+            None
+        } else if let Some(texture) = node.texture {
+            let uv = (position - node.rect.min) / node.rect.size();
+            Some(PanelHit::Texture(texture, uv))
+        } else {
+            node.blocks.then_some(PanelHit::Blocked)
+        }
+    })
+}
+
+/// Find the nearest quad hit by `ray`, its distance along the ray, and the position on it as
+/// a fraction of its size.
+fn find_quad<'a>(
+    ray: Ray3d,
+    quads: impl IntoIterator<Item = (&'a CompositorQuad, &'a GlobalTransform)>,
+) -> Option<(f32, &'a Handle<Image>, Vec2)> {
+    let mut nearest: Option<(f32, &Handle<Image>, Vec2)> = None;
+    for (quad, transform) in quads {
+        let Some(distance) = ray.intersect_plane(
+            transform.translation(),
+            InfinitePlane3d::new(transform.back()),
+        ) else {
+            continue;
+        };
+        if nearest.as_ref().map_or(false, |(d, _, _)| *d <= distance) {
+            continue;
+        }
+        let local = transform
+            .affine()
+            .inverse()
+            .transform_point3(ray.get_point(distance));
+        let uv = Vec2::new(local.x / quad.size.x + 0.5, 0.5 - local.y / quad.size.y);
+        if uv.cmplt(Vec2::ZERO).any() || uv.cmpgt(Vec2::ONE).any() {
+            continue;
+        }
+        nearest = Some((distance, &quad.image, uv));
+    }
+    nearest
+}
+
+/// The UI nodes which may cover or display a compositor texture.
+type UiNodeQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static Node,
+        &'static GlobalTransform,
+        &'static ViewVisibility,
+        Option<&'static CompositorTexture>,
+        Option<&'static Pickable>,
+        Option<&'static TargetCamera>,
+    ),
+>;
+
+/// Find the compositor texture under `location`, and the position on it in pixels.
+fn find_texture(
+    location: &Location,
+    stack: &UiStack,
+    nodes: &UiNodeQuery,
+    quads: &Query<(&CompositorQuad, &GlobalTransform)>,
+    cameras: &Query<(&Camera, &GlobalTransform)>,
+    primary_window: Option<Entity>,
+    images: &Assets<Image>,
+) -> Option<(Handle<Image>, Vec2)> {
+    let in_pixels = |image: &Handle<Image>, uv: Vec2| {
+        let size = images.get(image)?.size_f32();
+        Some((image.clone(), uv * size))
+    };
+
+    // Textures displayed in the UI, which covers the world beneath it. Only nodes rendered
+    // to the same window count; this excludes the UI inside compositors.
+    if let NormalizedRenderTarget::Window(window) = &location.target {
+        let on_window = |camera: Option<&TargetCamera>| match camera {
+            Some(camera) => cameras.get(camera.entity()).is_ok_and(|(camera, _)| {
+                camera.target.normalize(primary_window).as_ref() == Some(&location.target)
+            }),
+            None => primary_window == Some(window.entity()),
+        };
+        let stacked = stack.uinodes.iter().rev().filter_map(|entity| {
+            let (node, transform, visibility, texture, pickable, camera) =
+                nodes.get(*entity).ok()?;
+            (visibility.get() && on_window(camera)).then(|| StackedNode {
+                rect: node.logical_rect(transform),
+                texture: texture.map(|texture| &texture.0),
+                blocks: pickable.map_or(true, |pickable| pickable.should_block_lower),
+            })
+        });
+        match find_panel(location.position, stacked) {
+            Some(PanelHit::Texture(image, uv)) => return in_pixels(image, uv),
+            Some(PanelHit::Blocked) => return None,
+            None => {}
+        }
+    }
+
+    // Textures displayed on quads in the world; the nearest one along the ray wins.
+    let mut nearest: Option<(f32, &Handle<Image>, Vec2)> = None;
+    for (camera, camera_transform) in cameras.iter() {
+        if camera.target.normalize(primary_window).as_ref() != Some(&location.target) {
+            continue;
+        }
+        let Some(ray) = camera.viewport_to_world(camera_transform, location.position) else {
+            continue;
+        };
+        let loaded = quads
+            .iter()
+            .filter(|(quad, _)| images.contains(&quad.image));
+        if let Some(hit) = find_quad(ray, loaded) {
+            if nearest.as_ref().map_or(true, |(d, _, _)| hit.0 < *d) {
+                nearest = Some(hit);
+            }
+        }
+    }
+    nearest.and_then(|(_, image, uv)| in_pixels(image, uv))
+}
+
+/// Forward mouse input to the virtual pointer of the compositor texture under the mouse.
+#[allow(clippy::too_many_arguments)]
+fn forward_compositor_pointers(
+    mut commands: Commands,
+    mut state: ResMut<CompositorPointers>,
+    mut moves: ResMut<Events<InputMove>>,
+    mut move_reader: Local<ManualEventReader<InputMove>>,
+    mut presses: ResMut<Events<InputPress>>,
+    mut press_reader: Local<ManualEventReader<InputPress>>,
+    stack: Res<UiStack>,
+    nodes: UiNodeQuery,
+    textures: Query<&CompositorTexture>,
+    quads: Query<(&CompositorQuad, &GlobalTransform)>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    primary_window: Query<Entity, With<PrimaryWindow>>,
+    images: Res<Assets<Image>>,
+) {
+    // Remove the pointers of textures which are no longer displayed.
+    let displayed: HashSet<AssetId<Image>> = textures
+        .iter()
+        .map(|texture| texture.0.id())
+        .chain(quads.iter().map(|(quad, _)| quad.image.id()))
+        .collect();
+    state.pointers.retain(|image, (_, entity)| {
+        let keep = displayed.contains(image);
+        if !keep {
+            commands.entity(*entity).despawn();
+        }
+        keep
+    });
+    if state
+        .current
+        .as_ref()
+        .is_some_and(|(_, image)| !displayed.contains(&image.id()))
+    {
+        state.current = None;
+    }
+
+    let primary_window = primary_window.get_single().ok();
+    let mouse_moves: Vec<(Location, Vec2)> = move_reader
+        .read(&moves)
+        .filter(|ev| ev.pointer_id == PointerId::Mouse)
+        .map(|ev| (ev.location.clone(), ev.delta))
+        .collect();
+
+    for (location, delta) in mouse_moves {
+        let hit = find_texture(
+            &location,
+            &stack,
+            &nodes,
+            &quads,
+            &cameras,
+            primary_window,
+            &images,
+        );
+
+        // Move the pointer of the previous texture off of it, so that its hover state ends.
+        if let Some((pointer_id, image)) = state.current.take() {
+            if hit
+                .as_ref()
+                .map_or(true, |(hit_image, _)| *hit_image != image)
+            {
+                moves.send(InputMove {
+                    pointer_id,
+                    location: Location {
+                        target: NormalizedRenderTarget::Image(image),
+                        position: Vec2::splat(-1.),
+                    },
+                    delta: Vec2::ZERO,
+                });
+            }
+        }
+
+        let Some((image, position)) = hit else {
+            continue;
+        };
+        let (pointer_id, _) = *state.pointers.entry(image.id()).or_insert_with(|| {
+            let pointer_id = PointerId::Custom(Uuid::new_v4());
+            let entity = commands
+                .spawn((
+                    PointerBundle::new(pointer_id),
+                    Name::new("CompositorPointer"),
+                ))
+                .id();
+            (pointer_id, entity)
+        });
+        moves.send(InputMove {
+            pointer_id,
+            location: Location {
+                target: NormalizedRenderTarget::Image(image.clone()),
+                position,
+            },
+            delta,
+        });
+        state.current = Some((pointer_id, image));
+    }
+
+    // Presses go to the texture under the mouse, while releases go to every texture, so that
+    // a drag which leaves the texture still ends.
+    let mouse_presses: Vec<InputPress> = press_reader
+        .read(&presses)
+        .filter(|ev| ev.pointer_id == PointerId::Mouse)
+        .map(|ev| InputPress {
+            pointer_id: ev.pointer_id,
+            direction: ev.direction,
+            button: ev.button,
+        })
+        .collect();
+    for press in mouse_presses {
+        match press.direction {
+            PressDirection::Down => {
+                if let Some((pointer_id, _)) = state.current {
+                    presses.send(InputPress {
+                        pointer_id,
+                        ..press
+                    });
+                }
+            }
+            PressDirection::Up => {
+                for (pointer_id, _) in state.pointers.values() {
+                    presses.send(InputPress {
+                        pointer_id: *pointer_id,
+                        ..press
+                    });
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(min: Vec2, max: Vec2, texture: Option<&Handle<Image>>, blocks: bool) -> StackedNode {
+        StackedNode {
+            rect: Rect::from_corners(min, max),
+            texture,
+            blocks,
+        }
+    }
+
+    #[test]
+    fn test_find_panel() {
+        let a = Handle::<Image>::weak_from_u128(1);
+        let b = Handle::<Image>::weak_from_u128(2);
+        let stack = |overlay_blocks: bool| {
+            [
+                // A popup above both panels.
+                node(Vec2::new(0., 0.), Vec2::new(20., 20.), None, overlay_blocks),
+                node(Vec2::new(50., 0.), Vec2::new(150., 100.), Some(&b), true),
+                node(Vec2::new(0., 0.), Vec2::new(100., 100.), Some(&a), true),
+                // The window background.
+                node(Vec2::new(0., 0.), Vec2::new(400., 400.), None, true),
+            ]
+        };
+
+        // The topmost panel wins where panels overlap.
+        assert_eq!(
+            find_panel(Vec2::new(25., 50.), stack(true)),
+            Some(PanelHit::Texture(&a, Vec2::new(0.25, 0.5)))
+        );
+        assert_eq!(
+            find_panel(Vec2::new(75., 50.), stack(true)),
+            Some(PanelHit::Texture(&b, Vec2::new(0.25, 0.5)))
+        );
+
+        // Nodes above a panel hide it, unless they let the pointer through.
+        assert_eq!(
+            find_panel(Vec2::new(10., 10.), stack(true)),
+            Some(PanelHit::Blocked)
+        );
+        assert_eq!(
+            find_panel(Vec2::new(10., 10.), stack(false)),
+            Some(PanelHit::Texture(&a, Vec2::new(0.1, 0.1)))
+        );
+
+        assert_eq!(
+            find_panel(Vec2::new(300., 300.), stack(true)),
+            Some(PanelHit::Blocked)
+        );
+        assert_eq!(find_panel(Vec2::new(500., 500.), stack(true)), None);
+    }
+
+    #[test]
+    fn test_find_quad() {
+        let near = CompositorQuad {
+            image: Handle::weak_from_u128(1),
+            size: Vec2::new(2., 1.),
+        };
+        let far = CompositorQuad {
+            image: Handle::weak_from_u128(2),
+            size: Vec2::new(10., 10.),
+        };
+        let near_transform = GlobalTransform::IDENTITY;
+        let far_transform = GlobalTransform::from_xyz(0., 0., -5.);
+        let quads = [(&far, &far_transform), (&near, &near_transform)];
+        let ray = |x: f32, y: f32| Ray3d::new(Vec3::new(x, y, 10.), Vec3::NEG_Z);
+
+        // The nearest quad along the ray wins, with the top left corner at the origin.
+        assert_eq!(
+            find_quad(ray(0.5, 0.25), quads),
+            Some((10., &near.image, Vec2::new(0.75, 0.25)))
+        );
+
+        // Rays which miss the near quad hit the one behind it.
+        assert_eq!(
+            find_quad(ray(2.5, 0.), quads),
+            Some((15., &far.image, Vec2::new(0.75, 0.5)))
+        );
+        assert_eq!(find_quad(ray(20., 0.), quads), None);
+    }
+}
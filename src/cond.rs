@@ -17,6 +17,8 @@ pub struct Cond<Test: 'static, Pos: IntoView, PosFn: Fn() -> Pos, Neg: IntoView,
     pos: PosFn,
     neg: NegFn,
     state: CondState,
+    /// Placeholder node marking the position of the active branch while it is empty.
+    anchor: Option<Entity>,
 }
 
 impl<
@@ -34,6 +36,16 @@ impl<
             pos,
             neg,
             state: CondState::Unset,
+            anchor: None,
+        }
+    }
+
+    /// The display nodes of the active branch, not including the anchor.
+    fn branch_nodes(&self) -> NodeSpan {
+        match self.state {
+            CondState::Unset => NodeSpan::Empty,
+            CondState::True(ref true_state) => true_state.0.nodes(),
+            CondState::False(ref false_state) => false_state.0.nodes(),
         }
     }
 
@@ -62,11 +74,7 @@ impl<
     > View for Cond<Test, Pos, PosFn, Neg, NegFn>
 {
     fn nodes(&self) -> NodeSpan {
-        match self.state {
-            CondState::Unset => NodeSpan::Empty,
-            CondState::True(ref true_state) => true_state.0.nodes(),
-            CondState::False(ref false_state) => false_state.0.nodes(),
-        }
+        NodeSpan::anchored(self.anchor, self.branch_nodes())
     }
 
     fn build(&mut self, view_entity: Entity, world: &mut World) {
@@ -83,6 +91,8 @@ impl<
     fn react(&mut self, view_entity: Entity, world: &mut World, tracking: &mut TrackingScope) {
         let re = Rcx::new(world, view_entity, tracking);
         let cond = (self.test)(&re);
+        let old_nodes = self.nodes();
+        let position = old_nodes.position(world);
         // possibly raze previous state
         match self.state {
             CondState::True(_) if cond => {
@@ -106,6 +116,8 @@ impl<
             CondState::False(self.build_branch_state(&self.neg, view_entity, world))
         };
 
+        NodeSpan::update_anchor(&mut self.anchor, &self.branch_nodes(), world);
+        NodeSpan::splice(position, &old_nodes, &self.nodes(), world);
         world.entity_mut(view_entity).insert(DisplayNodeChanged);
     }
 
@@ -116,6 +128,9 @@ impl<
             CondState::Unset => {}
         }
         self.state = CondState::Unset;
+        if let Some(anchor) = self.anchor.take() {
+            NodeSpan::despawn_anchor(anchor, world);
+        }
         world.despawn_owned_recursive(view_entity);
     }
 }
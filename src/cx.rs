@@ -1,15 +1,26 @@
 use std::{
     cell::RefCell,
+    hash::Hash,
     marker::PhantomData,
     sync::{Arc, Mutex},
 };
 
-use bevy::prelude::*;
+use bevy::{
+    ecs::query::{QueryFilter, ReadOnlyQueryData},
+    prelude::*,
+    utils::HashMap,
+};
 
 use crate::{
     callback::{Callback, CallbackFnCell, CallbackFnMutCell},
     derived::{Derived, DerivedCell, ReadDerived, ReadDerivedInternal},
-    mutable::{MutableCell, ReadMutable, UpdateMutableCell, WriteMutable},
+    derived_family::{DerivedFamily, DerivedFamilyCell},
+    mutable::{
+        ModifyMutableCell, MutableCell, MutableEquality, MutableKey, OwnedMutable, ReadMutable,
+        ReleasedMutables, UpdateMutableCell, WriteMutable,
+    },
+    mutable_store::{MutableStorage, MutableStore},
+    origin::HandleOrigin,
     tracking_scope::TrackingScope,
     Mutable, Reaction, ReactionCell, Signal,
 };
@@ -23,6 +34,18 @@ pub trait RunContextRead {
     /// Return a reference to the Component `C` on the given entity. Calling this function
     /// adds the component as a dependency of the current tracking scope.
     fn use_component<C: Component>(&self, entity: Entity) -> Option<&C>;
+
+    /// Return a reference to the asset referred to by `handle`, or `None` if the asset is not
+    /// loaded yet. Calling this function adds the asset as a dependency of the current tracking
+    /// scope, so the caller will re-run when the asset finishes loading or is modified (e.g. via
+    /// hot-reloading). Changes to other assets of the same type don't re-run the caller.
+    fn use_asset<A: Asset>(&self, handle: &Handle<A>) -> Option<&A>;
+
+    /// Return the entities matching the query `Q` with filter `F`. Calling this function adds
+    /// the query as a dependency of the current tracking scope, so the caller will re-run when
+    /// an entity starts or stops matching the query, or when any component read by the query
+    /// changes on a matching entity.
+    fn use_query<Q: ReadOnlyQueryData + 'static, F: QueryFilter + 'static>(&self) -> Vec<Entity>;
 }
 
 /// A mutable reactive context. This allows write access to reactive data sources.
@@ -30,41 +53,91 @@ pub trait RunContextWrite: RunContextRead {
     /// The current Bevy [`World`].
     fn world_mut(&mut self) -> &mut World;
 
-    /// Invoke a callback with the given props.
+    /// Invoke a callback with the given props, and return the callback's result. If the
+    /// callback no longer exists, a warning is logged and the default value is returned.
+    ///
+    /// Callbacks don't have a persistent tracking scope, so any cleanup functions registered
+    /// by the callback via [`Cx::on_cleanup`] are run as soon as the callback returns.
     ///
     /// Arguments:
     /// * `callback` - The callback to invoke.
     /// * `props` - The props to pass to the callback.
-    fn run_callback<P: 'static>(&mut self, callback: Callback<P>, props: P) {
+    fn run_callback<P: 'static, R: Default + 'static>(
+        &mut self,
+        callback: Callback<P, R>,
+        props: P,
+    ) -> R {
         let world = self.world_mut();
+        callback.origin.check(world, "Callback");
         let tick = world.change_tick();
         let mut tracking = TrackingScope::new(tick);
         let mut cx = Cx::new(world, callback.id, &mut tracking);
-        let mut callback_entity = cx.world.entity_mut(callback.id);
-        if let Some(mut cell) = callback_entity.get_mut::<CallbackFnCell<P>>() {
+        let Some(mut callback_entity) = cx.world.get_entity_mut(callback.id) else {
+            warn!("No callback found for {:?}", callback.id);
+            return R::default();
+        };
+        if let Some(mut cell) = callback_entity.get_mut::<CallbackFnCell<P, R>>() {
             let mut callback_fn = cell.inner.take();
             let callback_box = callback_fn.as_ref().expect("Callback is not present");
-            callback_box.call(&mut cx, props);
+            let result = callback_box.call(&mut cx, props);
             let mut callback_entity = cx.world.entity_mut(callback.id);
             callback_entity
-                .get_mut::<CallbackFnCell<P>>()
+                .get_mut::<CallbackFnCell<P, R>>()
                 .unwrap()
                 .inner = callback_fn.take();
-        } else if let Some(mut cell) = callback_entity.get_mut::<CallbackFnMutCell<P>>() {
+            run_cleanups(&mut cx);
+            result
+        } else if let Some(mut cell) = callback_entity.get_mut::<CallbackFnMutCell<P, R>>() {
             let mut callback_fn = cell.inner.take();
             let callback_box = callback_fn.as_mut().expect("Callback is not present");
-            callback_box.call(&mut cx, props);
+            let result = callback_box.call(&mut cx, props);
             let mut callback_entity = cx.world.entity_mut(callback.id);
             callback_entity
-                .get_mut::<CallbackFnMutCell<P>>()
+                .get_mut::<CallbackFnMutCell<P, R>>()
                 .unwrap()
                 .inner = callback_fn.take();
+            run_cleanups(&mut cx);
+            result
         } else {
             warn!("No callback found for {:?}", callback.id);
+            R::default()
         }
     }
 }
 
+/// Run the cleanup functions registered on a context's temporary tracking scope.
+fn run_cleanups(cx: &mut Cx) {
+    let cleanups = std::mem::take(&mut cx.tracking.borrow_mut().cleanups);
+    for cleanup_fn in cleanups {
+        cleanup_fn(cx.world);
+    }
+}
+
+/// Create a [`Mutable`] which is held in a component on its own entity, regardless of the
+/// [`MutableStorage`] mode. This is used for mutables which other components are attached to.
+#[track_caller]
+pub(crate) fn create_entity_mutable<'p, C: RunContextSetup<'p> + ?Sized, T>(
+    cx: &mut C,
+    init: T,
+) -> Mutable<T>
+where
+    T: Send + Sync + 'static,
+{
+    let owner = cx.owner();
+    let cell = cx
+        .world_mut()
+        .spawn(MutableCell::<T>(init))
+        .set_parent(owner)
+        .id();
+    let component = cx.world_mut().init_component::<MutableCell<T>>();
+    cx.add_owned(cell);
+    Mutable {
+        key: MutableKey::Entity(cell, component),
+        origin: HandleOrigin::new(cx.world_mut()),
+        marker: PhantomData,
+    }
+}
+
 /// A "setup context" is similar to a reactive context, but can also be used to create
 /// reactive data sources such as mutables and effects.
 pub trait RunContextSetup<'p> {
@@ -85,39 +158,92 @@ pub trait RunContextSetup<'p> {
             .insert(Name::new(name.to_string()));
     }
 
-    /// Create a new [`Mutable`] in this context.
+    /// Create a new [`Mutable`] in this context. Where the value is held depends on the
+    /// [`MutableStorage`] resource.
+    #[track_caller]
     fn create_mutable<T>(&mut self, init: T) -> Mutable<T>
     where
         T: Send + Sync + 'static,
     {
         let owner = self.owner();
-        let cell = self
-            .world_mut()
-            .spawn(MutableCell::<T>(init))
-            .set_parent(owner)
-            .id();
-        let component = self.world_mut().init_component::<MutableCell<T>>();
-        self.add_owned(cell);
-        Mutable {
-            cell,
-            component,
-            marker: PhantomData,
+        let world = self.world_mut();
+        let storage = world
+            .get_resource::<MutableStorage>()
+            .copied()
+            .unwrap_or_default();
+        match storage {
+            MutableStorage::Store => {
+                let tick = world.change_tick();
+                let slot = world
+                    .get_resource_or_insert_with(MutableStore::default)
+                    .insert(owner, init, tick);
+                Mutable {
+                    key: MutableKey::Slot(slot),
+                    origin: HandleOrigin::new(world),
+                    marker: PhantomData,
+                }
+            }
+            MutableStorage::Entities => create_entity_mutable(self, init),
         }
     }
 
+    /// Create a new [`Mutable`] in this context, returned as an [`OwnedMutable`] guard which
+    /// knows the entity that owns it. The mutable is despawned along with the tracking scope
+    /// of this context, or when the guard is dropped, unless it is promoted with
+    /// [`OwnedMutable::promote_to_world`].
+    #[track_caller]
+    fn create_owned_mutable<T>(&mut self, init: T) -> OwnedMutable<T>
+    where
+        T: Send + Sync + 'static,
+    {
+        let mutable = self.create_mutable(init);
+        OwnedMutable {
+            mutable,
+            owner: self.owner(),
+            release: Some(ReleasedMutables::sender(self.world_mut())),
+        }
+    }
+
+    /// Create a new [`Mutable`] which uses `eq` instead of `PartialEq` to decide whether a
+    /// write changes the value. This is useful for values which are expensive to compare, or
+    /// whose `PartialEq` doesn't reflect whether they have changed. Mutables with a custom
+    /// equality function are always held in an entity.
+    #[track_caller]
+    fn create_mutable_with_equality<T>(&mut self, init: T, eq: fn(&T, &T) -> bool) -> Mutable<T>
+    where
+        T: Send + Sync + 'static,
+    {
+        let mutable = create_entity_mutable(self, init);
+        self.world_mut()
+            .entity_mut(mutable.id())
+            .insert(MutableEquality(eq));
+        mutable
+    }
+
+    /// Create a new [`Mutable`] which notifies its dependents on every write, even when the
+    /// new value is equal to the old one.
+    #[track_caller]
+    fn create_mutable_always_notify<T>(&mut self, init: T) -> Mutable<T>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.create_mutable_with_equality(init, |_, _| false)
+    }
+
     /// Create a new [`Callback`] in this context. This holds a `Fn` within an entity.
     ///
     /// Arguments:
-    /// * `callback` - The callback function to invoke. This will be called with a single
-    ///    parameter, which is a [`Cx`] object. The context may or may not have props.
-    fn create_callback<P: 'static, F: Send + Sync + 'static + Fn(&mut Cx, P)>(
+    /// * `callback` - The callback function to invoke. This will be called with a
+    ///    [`Cx`] object and the props, and returns a value of type `R`.
+    #[track_caller]
+    fn create_callback<P: 'static, R: 'static, F: Send + Sync + 'static + Fn(&mut Cx, P) -> R>(
         &mut self,
         callback: F,
-    ) -> Callback<P> {
+    ) -> Callback<P, R> {
         let owner = self.owner();
         let callback = self
             .world_mut()
-            .spawn(CallbackFnCell::<P> {
+            .spawn(CallbackFnCell::<P, R> {
                 inner: Some(Box::new(callback)),
             })
             .set_parent(owner)
@@ -125,6 +251,7 @@ pub trait RunContextSetup<'p> {
         self.add_owned(callback);
         Callback {
             id: callback,
+            origin: HandleOrigin::new(self.world_mut()),
             marker: PhantomData,
         }
     }
@@ -132,16 +259,20 @@ pub trait RunContextSetup<'p> {
     /// Create a new [`CallbackFnMut`] in this context. This holds a `FnMut` within an entity.
     ///
     /// Arguments:
-    /// * `callback` - The callback function to invoke. This will be called with a single
-    ///    parameter, which is a [`Cx`] object. The context may or may not have props.
-    fn create_callback_mut<P: 'static, F: FnMut(&mut Cx, P)>(&mut self, callback: F) -> Callback<P>
+    /// * `callback` - The callback function to invoke. This will be called with a
+    ///    [`Cx`] object and the props, and returns a value of type `R`.
+    #[track_caller]
+    fn create_callback_mut<P: 'static, R: 'static, F: FnMut(&mut Cx, P) -> R>(
+        &mut self,
+        callback: F,
+    ) -> Callback<P, R>
     where
         F: Send + Sync + 'static,
     {
         let owner = self.owner();
         let callback = self
             .world_mut()
-            .spawn(CallbackFnMutCell {
+            .spawn(CallbackFnMutCell::<P, R> {
                 inner: Some(Box::new(callback)),
             })
             .set_parent(owner)
@@ -149,10 +280,41 @@ pub trait RunContextSetup<'p> {
         self.add_owned(callback);
         Callback {
             id: callback,
+            origin: HandleOrigin::new(self.world_mut()),
             marker: PhantomData,
         }
     }
 
+    /// Create a new [`Callback`] from a Bevy system. The system is registered as a one-shot
+    /// system, which means that it can use ordinary system params such as `Query`, `Res`
+    /// and `Commands`, and receives the callback props via `In<P>`. The system is
+    /// unregistered when the owner of this context is despawned.
+    ///
+    /// Arguments:
+    /// * `system` - The system to run when the callback is invoked.
+    #[track_caller]
+    fn create_callback_system<
+        P: Send + 'static,
+        R: Default + Send + 'static,
+        M,
+        S: IntoSystem<P, R, M> + 'static,
+    >(
+        &mut self,
+        system: S,
+    ) -> Callback<P, R> {
+        let system_id = self.world_mut().register_system(system);
+        self.add_owned(system_id.entity());
+        self.create_callback(move |cx: &mut Cx, props: P| {
+            match cx.world_mut().run_system_with_input(system_id, props) {
+                Ok(result) => result,
+                Err(err) => {
+                    warn!("Failed to run callback system: {:?}", err);
+                    R::default()
+                }
+            }
+        })
+    }
+
     /// Create a new [`Derived`] in this context. This represents a readable signal which
     /// is computed from other signals. The result is not memoized, but is recomputed whenever
     /// the dependencies change.
@@ -160,6 +322,7 @@ pub trait RunContextSetup<'p> {
     /// Arguments:
     /// * `compute` - The function that computes the output. This will be called with a single
     ///    parameter, which is an [`Rcx`] object.
+    #[track_caller]
     fn create_derived<R: 'static, F: Send + Sync + 'static + Fn(&mut Rcx) -> R>(
         &mut self,
         compute: F,
@@ -173,6 +336,7 @@ pub trait RunContextSetup<'p> {
         self.add_owned(derived);
         Signal::Derived(Derived {
             id: derived,
+            origin: HandleOrigin::new(self.world_mut()),
             marker: PhantomData,
         })
     }
@@ -184,27 +348,49 @@ pub trait RunContextSetup<'p> {
     /// Arguments:
     /// * `compute` - The function that computes the output. This will be called with a single
     ///    parameter, which is a [`Cx`] object.
+    #[track_caller]
     fn create_memo<
         R: 'static + PartialEq + Send + Sync + Clone,
         F: Send + Sync + 'static + Fn(&mut Cx) -> R,
     >(
         &mut self,
         compute: F,
+    ) -> Signal<R> {
+        self.create_memo_with_equality(compute, R::eq)
+    }
+
+    /// Create a new [`Memo`] which uses `eq` instead of `PartialEq` to decide whether the
+    /// output has changed, so the output type doesn't need to implement `PartialEq`. Passing
+    /// a function which always returns false creates a memo which notifies its dependents
+    /// every time it is recomputed.
+    ///
+    /// Arguments:
+    /// * `compute` - The function that computes the output. This will be called with a single
+    ///    parameter, which is a [`Cx`] object.
+    /// * `eq` - Returns true if two outputs are equal.
+    #[track_caller]
+    fn create_memo_with_equality<
+        R: 'static + Send + Sync + Clone,
+        F: Send + Sync + 'static + Fn(&mut Cx) -> R,
+    >(
+        &mut self,
+        compute: F,
+        eq: fn(&R, &R) -> bool,
     ) -> Signal<R> {
         let owner = self.owner();
         let ticks = self.world_mut().change_tick();
         let mut scope = TrackingScope::new(ticks);
         let init = compute(&mut Cx::new(self.world_mut(), owner, &mut scope));
-        let mutable = self.create_mutable(init);
+        // Memos need an entity to hold their reaction, so they always use an entity cell.
+        let mutable = create_entity_mutable(self, init);
         let signal = mutable.signal();
+        // The cell's `MutableEquality` compares the new output with the previous one.
         let reaction = Arc::new(Mutex::new(move |cx: &mut Cx| {
-            let prev_value = mutable.get_clone(cx);
             let value = compute(cx);
-            if value != prev_value {
-                mutable.set_clone(cx, value);
-            }
+            mutable.replace(cx, value);
         }));
-        self.world_mut().entity_mut(mutable.cell).insert((
+        self.world_mut().entity_mut(mutable.id()).insert((
+            MutableEquality(eq),
             ReactionCell(reaction),
             scope,
             Name::new(format!("Memo::<{}>", std::any::type_name::<R>())),
@@ -213,6 +399,44 @@ pub trait RunContextSetup<'p> {
         signal
     }
 
+    /// Create a new [`DerivedFamily`] in this context. This is a set of memoized signals
+    /// which share a compute function, one for each key. Members are created on demand by
+    /// [`DerivedFamily::get`], and are despawned along with this context's tracking scope.
+    ///
+    /// Arguments:
+    /// * `compute` - The function that computes the output for a key. This will be called
+    ///    with a [`Cx`] object and the key.
+    #[track_caller]
+    fn create_derived_family<
+        K: Hash + Eq + Clone + Send + Sync + 'static,
+        R: 'static + PartialEq + Send + Sync + Clone,
+        F: Send + Sync + 'static + Fn(&mut Cx, K) -> R,
+    >(
+        &mut self,
+        compute: F,
+    ) -> DerivedFamily<K, R> {
+        let owner = self.owner();
+        let ticks = self.world_mut().change_tick();
+        let family = self
+            .world_mut()
+            .spawn((
+                DerivedFamilyCell::<K, R> {
+                    compute: Arc::new(compute),
+                    members: HashMap::default(),
+                },
+                TrackingScope::new(ticks),
+                Name::new(format!("DerivedFamily::<{}>", std::any::type_name::<R>())),
+            ))
+            .set_parent(owner)
+            .id();
+        self.add_owned(family);
+        DerivedFamily {
+            id: family,
+            origin: HandleOrigin::new(self.world_mut()),
+            marker: PhantomData,
+        }
+    }
+
     /// Create an effect. This is a function that is associated with an entity, and which
     /// re-runs whenever any of it's dependencies change.
     ///
@@ -220,17 +444,59 @@ pub trait RunContextSetup<'p> {
     /// * `effect` - The function that computes the output. This will be called with a single
     ///    parameter, which is a [`Cx`] object.
     fn create_effect<F: Send + Sync + 'static + FnMut(&mut Cx)>(&mut self, effect: F) {
-        let owner = self.owner();
-        let ticks = self.world_mut().change_tick();
-        let action = Arc::new(Mutex::new(effect));
-        let mut scope = TrackingScope::new(ticks);
-        let entity = self.world_mut().spawn_empty().set_parent(owner).id();
-        self.add_owned(entity);
-        action.lock().unwrap()(&mut Cx::new(self.world_mut(), entity, &mut scope));
-        self.world_mut()
-            .entity_mut(entity)
-            .insert((scope, ReactionCell(action)));
+        spawn_effect(self, effect);
     }
+
+    /// Observe changes to a signal. Whenever the value of `signal` changes, `callback` is called
+    /// with the previous value, the new value and the world. The callback is not called for
+    /// the initial value, and signals read through the world are not tracked. The observer is
+    /// removed along with this context's tracking scope.
+    ///
+    /// Arguments:
+    /// * `signal` - The signal to observe.
+    /// * `callback` - Called with the previous value, the new value and the world.
+    fn watch<
+        T: PartialEq + Clone + Send + Sync + 'static,
+        F: Send + Sync + 'static + FnMut(&T, &T, &mut World),
+    >(
+        &mut self,
+        signal: Signal<T>,
+        mut callback: F,
+    ) {
+        let mut prev: Option<T> = None;
+        spawn_effect(self, move |cx: &mut Cx| {
+            let value = signal.get_clone(cx);
+            if let Some(old) = prev.as_ref() {
+                if *old != value {
+                    callback(old, &value, cx.world_mut());
+                }
+            }
+            prev = Some(value);
+        });
+    }
+}
+
+/// Spawn an entity for an effect, owned by the context's tracking scope, and run the effect
+/// once. Returns the effect entity.
+pub(crate) fn spawn_effect<
+    'p,
+    C: RunContextSetup<'p> + ?Sized,
+    F: Send + Sync + 'static + FnMut(&mut Cx),
+>(
+    cx: &mut C,
+    effect: F,
+) -> Entity {
+    let owner = cx.owner();
+    let ticks = cx.world_mut().change_tick();
+    let action = Arc::new(Mutex::new(effect));
+    let mut scope = TrackingScope::new(ticks);
+    let entity = cx.world_mut().spawn_empty().set_parent(owner).id();
+    cx.add_owned(entity);
+    action.lock().unwrap()(&mut Cx::new(cx.world_mut(), entity, &mut scope));
+    cx.world_mut()
+        .entity_mut(entity)
+        .insert((scope, ReactionCell(action)));
+    entity
 }
 
 impl<F: Send + Sync + 'static + FnMut(&mut Cx)> Reaction for F {
@@ -255,11 +521,9 @@ pub struct Cx<'p, 'w> {
 }
 
 impl<'p, 'w> Cx<'p, 'w> {
-    pub(crate) fn new(
-        world: &'w mut World,
-        owner: Entity,
-        tracking: &'p mut TrackingScope,
-    ) -> Self {
+    /// Create a new setup context. This is mainly useful for tests and benchmarks which drive
+    /// a [`World`] directly.
+    pub fn new(world: &'w mut World, owner: Entity, tracking: &'p mut TrackingScope) -> Self {
         Self {
             world,
             owner,
@@ -354,9 +618,7 @@ impl<'p, 'w> ReadMutable for Cx<'p, 'w> {
     where
         T: Send + Sync + Copy + 'static,
     {
-        self.tracking
-            .borrow_mut()
-            .track_component_id(mutable.cell, mutable.component);
+        self.tracking.borrow_mut().track_mutable(mutable.key);
         self.world.read_mutable(mutable)
     }
 
@@ -364,9 +626,7 @@ impl<'p, 'w> ReadMutable for Cx<'p, 'w> {
     where
         T: Send + Sync + Clone + 'static,
     {
-        self.tracking
-            .borrow_mut()
-            .track_component_id(mutable.cell, mutable.component);
+        self.tracking.borrow_mut().track_mutable(mutable.key);
         self.world.read_mutable_clone(mutable)
     }
 
@@ -374,9 +634,7 @@ impl<'p, 'w> ReadMutable for Cx<'p, 'w> {
     where
         T: Send + Sync + 'static,
     {
-        self.tracking
-            .borrow_mut()
-            .track_component_id(mutable.cell, mutable.component);
+        self.tracking.borrow_mut().track_mutable(mutable.key);
         self.world.read_mutable_as_ref(mutable)
     }
 
@@ -384,9 +642,7 @@ impl<'p, 'w> ReadMutable for Cx<'p, 'w> {
     where
         T: Send + Sync + 'static,
     {
-        self.tracking
-            .borrow_mut()
-            .track_component_id(mutable.cell, mutable.component);
+        self.tracking.borrow_mut().track_mutable(mutable.key);
         self.world.read_mutable_map(mutable, f)
     }
 }
@@ -405,6 +661,21 @@ impl<'p, 'w> WriteMutable for Cx<'p, 'w> {
     {
         self.world.write_mutable_clone(mutable, value);
     }
+
+    fn set_mutable<T>(&mut self, mutable: &Mutable<T>, value: T)
+    where
+        T: Send + Sync + PartialEq + 'static,
+    {
+        self.world.set_mutable(mutable, value);
+    }
+
+    fn modify_mutable<T, F>(&mut self, mutable: &Mutable<T>, modifier: F)
+    where
+        T: Send + Sync + 'static,
+        F: FnOnce(&mut T) + Send + 'static,
+    {
+        self.world.modify_mutable(mutable, modifier);
+    }
 }
 
 impl<'p, 'w> ReadDerived for Cx<'p, 'w> {
@@ -412,6 +683,8 @@ impl<'p, 'w> ReadDerived for Cx<'p, 'w> {
     where
         R: Send + Sync + Copy + 'static,
     {
+        derived.origin.check(self.world, "Derived");
+        derived.check_alive(self.world);
         self.world
             .read_derived_with_scope(derived.id, &mut self.tracking.borrow_mut())
     }
@@ -420,6 +693,8 @@ impl<'p, 'w> ReadDerived for Cx<'p, 'w> {
     where
         R: Send + Sync + Clone + 'static,
     {
+        derived.origin.check(self.world, "Derived");
+        derived.check_alive(self.world);
         self.world
             .read_derived_clone_with_scope(derived.id, &mut self.tracking.borrow_mut())
     }
@@ -428,6 +703,8 @@ impl<'p, 'w> ReadDerived for Cx<'p, 'w> {
     where
         R: Send + Sync + 'static,
     {
+        derived.origin.check(self.world, "Derived");
+        derived.check_alive(self.world);
         self.world
             .read_derived_map_with_scope(derived.id, &mut self.tracking.borrow_mut(), f)
     }
@@ -445,6 +722,14 @@ impl<'p, 'w> RunContextRead for Cx<'p, 'w> {
             .track_component::<C>(entity, self.world);
         self.world.entity(entity).get::<C>()
     }
+    fn use_asset<A: Asset>(&self, handle: &Handle<A>) -> Option<&A> {
+        self.tracking.borrow_mut().track_asset(handle.id());
+        self.world.resource::<Assets<A>>().get(handle)
+    }
+
+    fn use_query<Q: ReadOnlyQueryData + 'static, F: QueryFilter + 'static>(&self) -> Vec<Entity> {
+        self.tracking.borrow_mut().track_query::<Q, F>(self.world)
+    }
 }
 
 impl<'p, 'w> RunContextWrite for Cx<'p, 'w> {
@@ -525,9 +810,7 @@ impl<'p, 'w> ReadMutable for Rcx<'p, 'w> {
     where
         T: Send + Sync + Copy + 'static,
     {
-        self.tracking
-            .borrow_mut()
-            .track_component_id(mutable.cell, mutable.component);
+        self.tracking.borrow_mut().track_mutable(mutable.key);
         self.world.read_mutable(mutable)
     }
 
@@ -535,9 +818,7 @@ impl<'p, 'w> ReadMutable for Rcx<'p, 'w> {
     where
         T: Send + Sync + Clone + 'static,
     {
-        self.tracking
-            .borrow_mut()
-            .track_component_id(mutable.cell, mutable.component);
+        self.tracking.borrow_mut().track_mutable(mutable.key);
         self.world.read_mutable_clone(mutable)
     }
 
@@ -545,9 +826,7 @@ impl<'p, 'w> ReadMutable for Rcx<'p, 'w> {
     where
         T: Send + Sync + 'static,
     {
-        self.tracking
-            .borrow_mut()
-            .track_component_id(mutable.cell, mutable.component);
+        self.tracking.borrow_mut().track_mutable(mutable.key);
         self.world.read_mutable_as_ref(mutable)
     }
 
@@ -555,9 +834,7 @@ impl<'p, 'w> ReadMutable for Rcx<'p, 'w> {
     where
         T: Send + Sync + 'static,
     {
-        self.tracking
-            .borrow_mut()
-            .track_component_id(mutable.cell, mutable.component);
+        self.tracking.borrow_mut().track_mutable(mutable.key);
         self.world.read_mutable_map(mutable, f)
     }
 }
@@ -567,6 +844,8 @@ impl<'p, 'w> ReadDerived for Rcx<'p, 'w> {
     where
         R: Send + Sync + Copy + 'static,
     {
+        derived.origin.check(self.world, "Derived");
+        derived.check_alive(self.world);
         self.world
             .read_derived_with_scope(derived.id, &mut self.tracking.borrow_mut())
     }
@@ -575,6 +854,8 @@ impl<'p, 'w> ReadDerived for Rcx<'p, 'w> {
     where
         R: Send + Sync + Clone + 'static,
     {
+        derived.origin.check(self.world, "Derived");
+        derived.check_alive(self.world);
         self.world
             .read_derived_clone_with_scope(derived.id, &mut self.tracking.borrow_mut())
     }
@@ -583,6 +864,8 @@ impl<'p, 'w> ReadDerived for Rcx<'p, 'w> {
     where
         R: Send + Sync + 'static,
     {
+        derived.origin.check(self.world, "Derived");
+        derived.check_alive(self.world);
         self.world
             .read_derived_map_with_scope(derived.id, &mut self.tracking.borrow_mut(), f)
     }
@@ -600,6 +883,14 @@ impl<'p, 'w> RunContextRead for Rcx<'p, 'w> {
             .track_component::<C>(entity, self.world);
         self.world.entity(entity).get::<C>()
     }
+    fn use_asset<A: Asset>(&self, handle: &Handle<A>) -> Option<&A> {
+        self.tracking.borrow_mut().track_asset(handle.id());
+        self.world.resource::<Assets<A>>().get(handle)
+    }
+
+    fn use_query<Q: ReadOnlyQueryData + 'static, F: QueryFilter + 'static>(&self) -> Vec<Entity> {
+        self.tracking.borrow_mut().track_query::<Q, F>(self.world)
+    }
 }
 
 impl ReadMutable for World {
@@ -607,32 +898,41 @@ impl ReadMutable for World {
     where
         T: Send + Sync + Copy + 'static,
     {
-        let mutable_entity = self.entity(mutable.cell);
-        mutable_entity.get::<MutableCell<T>>().unwrap().0
+        *self.read_mutable_as_ref(mutable)
     }
 
     fn read_mutable_clone<T>(&self, mutable: &Mutable<T>) -> T
     where
         T: Send + Sync + Clone + 'static,
     {
-        let mutable_entity = self.entity(mutable.cell);
-        mutable_entity.get::<MutableCell<T>>().unwrap().0.clone()
+        self.read_mutable_as_ref(mutable).clone()
     }
 
     fn read_mutable_as_ref<T>(&self, mutable: &Mutable<T>) -> &T
     where
         T: Send + Sync + 'static,
     {
-        let mutable_entity = self.entity(mutable.cell);
-        &mutable_entity.get::<MutableCell<T>>().unwrap().0
+        mutable.origin.check(self, "Mutable");
+        match mutable.key {
+            MutableKey::Entity(cell, _) => match self.get::<MutableCell<T>>(cell) {
+                Some(cell) => &cell.0,
+                None => mutable.origin.missing("Mutable"),
+            },
+            MutableKey::Slot(slot) => match self
+                .get_resource::<MutableStore>()
+                .and_then(|store| store.get::<T>(slot))
+            {
+                Some(value) => value,
+                None => mutable.origin.missing("Mutable"),
+            },
+        }
     }
 
     fn read_mutable_map<T, U, F: Fn(&T) -> U>(&self, mutable: &Mutable<T>, f: F) -> U
     where
         T: Send + Sync + 'static,
     {
-        let mutable_entity = self.entity(mutable.cell);
-        f(&mutable_entity.get::<MutableCell<T>>().unwrap().0)
+        f(self.read_mutable_as_ref(mutable))
     }
 }
 
@@ -643,7 +943,8 @@ impl WriteMutable for World {
     where
         T: Send + Sync + PartialEq + 'static,
     {
-        self.commands().add(UpdateMutableCell { mutable, value });
+        let mutable = entity_mutable::<T>(self, mutable);
+        self.set_mutable(&mutable, value);
     }
 
     /// Write the value of a mutable variable using Clone semantics. Does nothing if the
@@ -652,7 +953,40 @@ impl WriteMutable for World {
     where
         T: Send + Sync + Clone + PartialEq + 'static,
     {
-        self.commands().add(UpdateMutableCell { mutable, value });
+        let mutable = entity_mutable::<T>(self, mutable);
+        self.set_mutable(&mutable, value);
+    }
+
+    fn set_mutable<T>(&mut self, mutable: &Mutable<T>, value: T)
+    where
+        T: Send + Sync + PartialEq + 'static,
+    {
+        self.commands().add(UpdateMutableCell {
+            mutable: *mutable,
+            value,
+            eq: Some(T::eq),
+        });
+    }
+
+    fn modify_mutable<T, F>(&mut self, mutable: &Mutable<T>, modifier: F)
+    where
+        T: Send + Sync + 'static,
+        F: FnOnce(&mut T) + Send + 'static,
+    {
+        self.commands().add(ModifyMutableCell {
+            mutable: *mutable,
+            modifier,
+        });
+    }
+}
+
+/// A handle for the mutable held in the entity `cell`.
+fn entity_mutable<T: Send + Sync + 'static>(world: &mut World, cell: Entity) -> Mutable<T> {
+    let component = world.init_component::<MutableCell<T>>();
+    Mutable {
+        key: MutableKey::Entity(cell, component),
+        origin: HandleOrigin::new(world),
+        marker: PhantomData,
     }
 }
 
@@ -663,6 +997,8 @@ impl ReadDerived for World {
     {
         let ticks = self.read_change_tick();
         let mut scope = TrackingScope::new(ticks);
+        derived.origin.check(self, "Derived");
+        derived.check_alive(self);
         self.read_derived_with_scope(derived.id, &mut scope)
     }
 
@@ -672,6 +1008,8 @@ impl ReadDerived for World {
     {
         let ticks = self.read_change_tick();
         let mut scope = TrackingScope::new(ticks);
+        derived.origin.check(self, "Derived");
+        derived.check_alive(self);
         self.read_derived_clone_with_scope(derived.id, &mut scope)
     }
 
@@ -681,6 +1019,8 @@ impl ReadDerived for World {
     {
         let ticks = self.read_change_tick();
         let mut scope = TrackingScope::new(ticks);
+        derived.origin.check(self, "Derived");
+        derived.check_alive(self);
         self.read_derived_map_with_scope(derived.id, &mut scope, f)
     }
 }
@@ -745,6 +1085,16 @@ impl RunContextRead for World {
     fn use_component<C: Component>(&self, entity: Entity) -> Option<&C> {
         self.entity(entity).get::<C>()
     }
+    fn use_asset<A: Asset>(&self, handle: &Handle<A>) -> Option<&A> {
+        self.resource::<Assets<A>>().get(handle)
+    }
+
+    fn use_query<Q: ReadOnlyQueryData + 'static, F: QueryFilter + 'static>(&self) -> Vec<Entity> {
+        match QueryState::<(Entity, Q), F>::try_new(self) {
+            Some(mut state) => state.iter_manual(self).map(|(entity, _)| entity).collect(),
+            None => Vec::new(),
+        }
+    }
 }
 
 impl RunContextWrite for World {
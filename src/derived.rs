@@ -2,7 +2,7 @@ use std::sync::Arc;
 
 use bevy::prelude::*;
 
-use crate::{Rcx, TrackingScope};
+use crate::{origin::HandleOrigin, Rcx, TrackingScope};
 
 pub(crate) trait DerivedFnRef<R> {
     fn call(&self, cx: &mut Rcx) -> R;
@@ -22,9 +22,19 @@ pub(crate) struct DerivedCell<R>(pub(crate) Arc<dyn DerivedFnRef<R> + Send + Syn
 #[derive(PartialEq)]
 pub struct Derived<R> {
     pub(crate) id: Entity,
+    pub(crate) origin: HandleOrigin,
     pub(crate) marker: std::marker::PhantomData<R>,
 }
 
+impl<R> Derived<R> {
+    /// Panic with a diagnostic if the derived signal has been despawned.
+    pub(crate) fn check_alive(&self, world: &World) {
+        if world.get_entity(self.id).is_none() {
+            self.origin.missing("Derived");
+        }
+    }
+}
+
 impl<T> Copy for Derived<T> {}
 impl<T> Clone for Derived<T> {
     fn clone(&self) -> Self {
@@ -0,0 +1,204 @@
+use std::{hash::Hash, marker::PhantomData, sync::Arc};
+
+use bevy::{prelude::*, utils::HashMap};
+
+use crate::{origin::HandleOrigin, Cx, DespawnScopes, RunContextSetup, Signal, TrackingScope};
+
+/// Holds the compute function of a [`DerivedFamily`], and the signals created for it so far.
+#[derive(Component)]
+pub(crate) struct DerivedFamilyCell<K, R> {
+    pub(crate) compute: Arc<dyn Fn(&mut Cx, K) -> R + Send + Sync>,
+    pub(crate) members: HashMap<K, FamilyMember<R>>,
+}
+
+/// A member of a [`DerivedFamily`].
+pub(crate) struct FamilyMember<R> {
+    signal: Signal<R>,
+    /// The entity which holds the member's memo.
+    memo: Entity,
+    /// The number of requests for the member by tracking scopes which are still alive.
+    refs: usize,
+}
+
+/// A family of memoized signals which are computed by the same function, one for each key.
+/// This is useful when many views need a variation of the same signal, such as each row of a
+/// list asking whether it is selected: rather than constructing a memo per row, the rows
+/// share a family and look up their member by key.
+///
+/// Members are created the first time their key is requested, and are shared by every
+/// tracking scope which requests the same key. A member is despawned once all of the scopes
+/// which requested it have been despawned or have re-run, or along with the tracking scope
+/// which created the family.
+pub struct DerivedFamily<K, R> {
+    pub(crate) id: Entity,
+    pub(crate) origin: HandleOrigin,
+    pub(crate) marker: PhantomData<fn(K) -> R>,
+}
+
+impl<K, R> Copy for DerivedFamily<K, R> {}
+impl<K, R> Clone for DerivedFamily<K, R> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K, R> DerivedFamily<K, R>
+where
+    K: Hash + Eq + Clone + Send + Sync + 'static,
+    R: PartialEq + Clone + Send + Sync + 'static,
+{
+    /// Return the signal for `key`, creating it the first time the key is requested. The
+    /// member is kept alive until the tracking scope of `cx` is despawned or re-runs.
+    ///
+    /// Arguments:
+    /// * `cx`: The reactive context.
+    /// * `key`: The key of the member.
+    #[track_caller]
+    pub fn get(&self, cx: &mut Cx, key: K) -> Signal<R> {
+        let family = *self;
+        let released_key = key.clone();
+        cx.on_cleanup(move |world| family.release(world, released_key));
+
+        let world = cx.world_mut();
+        self.origin.check(world, "DerivedFamily");
+        let Some(mut cell) = world.get_mut::<DerivedFamilyCell<K, R>>(self.id) else {
+            self.origin.missing("DerivedFamily");
+        };
+        if let Some(member) = cell.members.get_mut(&key) {
+            member.refs += 1;
+            return member.signal.clone();
+        }
+
+        // Members are owned by the family, so that they are despawned along with it.
+        let compute = cell.compute.clone();
+        let mut scope = world
+            .entity_mut(self.id)
+            .take::<TrackingScope>()
+            .unwrap_or_else(|| TrackingScope::new(world.change_tick()));
+        let member_key = key.clone();
+        let signal = Cx::new(world, self.id, &mut scope)
+            .create_memo(move |cx| compute(cx, member_key.clone()));
+        world.entity_mut(self.id).insert(scope);
+        let Signal::Mutable(memo) = &signal else {
+            unreachable!("memos are held in a mutable");
+        };
+        let member = FamilyMember {
+            signal: signal.clone(),
+            memo: memo.id(),
+            refs: 1,
+        };
+        world
+            .get_mut::<DerivedFamilyCell<K, R>>(self.id)
+            .unwrap()
+            .members
+            .insert(key, member);
+        signal
+    }
+
+    /// Release one request for `key`. The member is evicted once it has no requests left
+    /// when commands are flushed, so that a reaction which requests the same key every time
+    /// it runs doesn't recreate the member.
+    fn release(self, world: &mut World, key: K) {
+        let Some(mut cell) = world.get_mut::<DerivedFamilyCell<K, R>>(self.id) else {
+            return;
+        };
+        let Some(member) = cell.members.get_mut(&key) else {
+            return;
+        };
+        member.refs -= 1;
+        if member.refs == 0 {
+            world
+                .commands()
+                .add(move |world: &mut World| self.evict(world, &key));
+        }
+    }
+
+    /// Despawn the member for `key` if it is no longer requested.
+    fn evict(self, world: &mut World, key: &K) {
+        let Some(mut cell) = world.get_mut::<DerivedFamilyCell<K, R>>(self.id) else {
+            return;
+        };
+        if cell.members.get(key).map_or(true, |member| member.refs > 0) {
+            return;
+        }
+        let member = cell.members.remove(key).unwrap();
+        if let Some(mut scope) = world.get_mut::<TrackingScope>(self.id) {
+            scope.owned.retain(|owned| *owned != member.memo);
+        }
+        world.despawn_owned_recursive(member.memo);
+    }
+
+    /// The number of members which have been created.
+    pub fn len(&self, world: &World) -> usize {
+        world
+            .get::<DerivedFamilyCell<K, R>>(self.id)
+            .map_or(0, |cell| cell.members.len())
+    }
+
+    /// Returns true if no members have been created.
+    pub fn is_empty(&self, world: &World) -> bool {
+        self.len(world) == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{tracking_scope::run_reactions, DespawnScopes};
+
+    use super::*;
+
+    #[test]
+    fn test_derived_family() {
+        let mut world = World::default();
+        let mut scope = TrackingScope::new(world.change_tick());
+        let owner = world.spawn_empty().id();
+        let mut cx = Cx::new(&mut world, owner, &mut scope);
+
+        let selected = cx.create_mutable::<usize>(1);
+        let is_selected = cx.create_derived_family(move |cx, row: usize| selected.get(cx) == row);
+
+        let row0 = is_selected.get(&mut cx, 0);
+        let row1 = is_selected.get(&mut cx, 1);
+        let Signal::Mutable(row0_memo) = row0 else {
+            unreachable!();
+        };
+        assert!(!row0.get(&cx));
+        assert!(row1.get(&cx));
+
+        // Requesting the same key again returns the cached member.
+        let _ = is_selected.get(&mut cx, 0);
+        assert_eq!(is_selected.len(&world), 2);
+
+        world.increment_change_tick();
+        selected.set(&mut world, 0);
+        world.flush_commands();
+        run_reactions(&mut world);
+        world.flush_commands();
+        assert!(row0.get(&world));
+        assert!(!row1.get(&world));
+
+        // A member which is requested by another scope is shared, and is evicted once none
+        // of the scopes which requested it are alive.
+        let mut reader_scope = TrackingScope::new(world.change_tick());
+        let reader = world.spawn_empty().id();
+        let mut cx = Cx::new(&mut world, reader, &mut reader_scope);
+        let row2 = is_selected.get(&mut cx, 2);
+        let _ = is_selected.get(&mut cx, 0);
+        let Signal::Mutable(row2_memo) = row2 else {
+            unreachable!();
+        };
+        assert_eq!(is_selected.len(&world), 3);
+        world.entity_mut(reader).insert(reader_scope);
+        world.despawn_owned_recursive(reader);
+        world.flush_commands();
+        assert_eq!(is_selected.len(&world), 2);
+        assert!(world.get_entity(row2_memo.id()).is_none());
+        assert!(world.get_entity(row0_memo.id()).is_some());
+
+        // Members are despawned along with the scope which created the family.
+        world.entity_mut(owner).insert(scope);
+        world.despawn_owned_recursive(owner);
+        assert!(is_selected.is_empty(&world));
+        assert!(world.get_entity(row0_memo.id()).is_none());
+    }
+}
@@ -0,0 +1,311 @@
+//! Remote debugging server, which allows a separate devtools process to inspect the UI of a
+//! running app. The protocol is JSON over WebSocket: each text message from the client is a
+//! request object with a `"cmd"` field, and the server replies with exactly one JSON object.
+//!
+//! Supported commands:
+//! * `{"cmd": "hierarchy"}` - the tree of UI nodes, starting from the root nodes.
+//! * `{"cmd": "inspect", "entity": <bits>}` - component names and style of an entity.
+//! * `{"cmd": "select", "entity": <bits>}` - set the [`DevtoolsSelection`].
+//! * `{"cmd": "watch", "entity": <bits>, "enabled": <bool>}` - watch a signal or view entity.
+//! * `{"cmd": "events"}` - watched entities which have reacted since the last poll.
+//! * `{"cmd": "perf"}` - reaction counters.
+//! * `{"cmd": "click", "entity": <bits>}` - simulate a pointer click on an entity.
+
+use std::{
+    net::{TcpListener, ToSocketAddrs},
+    sync::{
+        mpsc::{channel, Receiver, Sender},
+        Mutex,
+    },
+    time::Duration,
+};
+
+use bevy::{
+    prelude::*,
+    render::camera::NormalizedRenderTarget,
+    utils::HashSet,
+    window::{PrimaryWindow, WindowRef},
+};
+use bevy_mod_picking::{
+    backend::HitData,
+    pointer::{Location, PointerButton, PointerId},
+    prelude::{Click, Pointer},
+};
+use serde_json::{json, Value};
+
+use crate::{TrackingScope, TrackingScopeTracing};
+
+/// A request received from a devtools client, along with a channel for the reply.
+struct DevtoolsRequest {
+    request: Value,
+    reply: Sender<Value>,
+}
+
+/// Resource which holds the receiving end of the request queue.
+#[derive(Resource)]
+struct DevtoolsChannel(Mutex<Receiver<DevtoolsRequest>>);
+
+/// The entity most recently selected by a devtools client.
+#[derive(Resource, Default)]
+pub struct DevtoolsSelection(pub Option<Entity>);
+
+/// Internal state for watched entities and performance counters.
+#[derive(Resource, Default)]
+struct DevtoolsState {
+    watched: HashSet<Entity>,
+    pending: Vec<Entity>,
+    frames: u64,
+    reactions: u64,
+}
+
+/// Plugin which starts a remote debugging server on the given address.
+pub struct DevtoolsPlugin {
+    /// Address to listen on, e.g. `"127.0.0.1:9222"`.
+    pub address: String,
+}
+
+impl Default for DevtoolsPlugin {
+    fn default() -> Self {
+        Self {
+            address: "127.0.0.1:9222".to_string(),
+        }
+    }
+}
+
+impl Plugin for DevtoolsPlugin {
+    fn build(&self, app: &mut App) {
+        let (sender, receiver) = channel::<DevtoolsRequest>();
+        match start_server(&self.address, sender) {
+            Ok(()) => info!("Devtools server listening on {}", self.address),
+            Err(err) => {
+                warn!(
+                    "Failed to start devtools server on {}: {}",
+                    self.address, err
+                );
+                return;
+            }
+        }
+        app.insert_resource(DevtoolsChannel(Mutex::new(receiver)))
+            .init_resource::<DevtoolsSelection>()
+            .init_resource::<DevtoolsState>()
+            .init_resource::<TrackingScopeTracing>()
+            .add_systems(Last, handle_devtools_requests);
+    }
+}
+
+fn start_server(
+    address: impl ToSocketAddrs,
+    sender: Sender<DevtoolsRequest>,
+) -> std::io::Result<()> {
+    let listener = TcpListener::bind(address)?;
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let Ok(stream) = stream else { continue };
+            let sender = sender.clone();
+            std::thread::spawn(move || {
+                let Ok(mut socket) = tungstenite::accept(stream) else {
+                    return;
+                };
+                while let Ok(message) = socket.read() {
+                    let request = match message {
+                        tungstenite::Message::Text(text) => {
+                            match serde_json::from_str::<Value>(&text) {
+                                Ok(request) => request,
+                                Err(err) => {
+                                    let reply = json!({ "error": err.to_string() });
+                                    let _ = socket.send(reply.to_string().into());
+                                    continue;
+                                }
+                            }
+                        }
+                        tungstenite::Message::Close(_) => return,
+                        _ => continue,
+                    };
+                    let (reply_sender, reply_receiver) = channel();
+                    if sender
+                        .send(DevtoolsRequest {
+                            request,
+                            reply: reply_sender,
+                        })
+                        .is_err()
+                    {
+                        return;
+                    }
+                    let Ok(reply) = reply_receiver.recv() else {
+                        return;
+                    };
+                    if socket.send(reply.to_string().into()).is_err() {
+                        return;
+                    }
+                }
+            });
+        }
+    });
+    Ok(())
+}
+
+fn handle_devtools_requests(world: &mut World) {
+    // Update counters and collect reactions of watched entities.
+    let reacted = world
+        .get_resource::<TrackingScopeTracing>()
+        .map(|tracing| tracing.0.clone())
+        .unwrap_or_default();
+    {
+        let mut state = world.resource_mut::<DevtoolsState>();
+        state.frames += 1;
+        state.reactions += reacted.len() as u64;
+        for entity in reacted {
+            if state.watched.contains(&entity) {
+                state.pending.push(entity);
+            }
+        }
+    }
+
+    let requests: Vec<DevtoolsRequest> = {
+        let channel = world.resource::<DevtoolsChannel>();
+        let receiver = channel.0.lock().unwrap();
+        receiver.try_iter().collect()
+    };
+
+    for DevtoolsRequest { request, reply } in requests {
+        let response = match request["cmd"].as_str() {
+            Some("hierarchy") => hierarchy(world),
+            Some("inspect") => with_entity(world, &request, inspect),
+            Some("select") => with_entity(world, &request, |world, entity| {
+                world.resource_mut::<DevtoolsSelection>().0 = Some(entity);
+                json!({ "ok": true })
+            }),
+            Some("watch") => with_entity(world, &request, |world, entity| {
+                let enabled = request["enabled"].as_bool().unwrap_or(true);
+                let mut state = world.resource_mut::<DevtoolsState>();
+                if enabled {
+                    state.watched.insert(entity);
+                } else {
+                    state.watched.remove(&entity);
+                }
+                json!({ "ok": true })
+            }),
+            Some("events") => {
+                let mut state = world.resource_mut::<DevtoolsState>();
+                let reacted: Vec<u64> = state.pending.drain(..).map(|e| e.to_bits()).collect();
+                json!({ "reacted": reacted })
+            }
+            Some("perf") => {
+                let scopes = world.query::<&TrackingScope>().iter(world).count();
+                let state = world.resource::<DevtoolsState>();
+                json!({
+                    "frames": state.frames,
+                    "reactions": state.reactions,
+                    "scopes": scopes,
+                })
+            }
+            Some("click") => with_entity(world, &request, click),
+            _ => json!({ "error": "unknown command" }),
+        };
+        let _ = reply.send(response);
+    }
+}
+
+/// Parse the `"entity"` field of a request, and invoke `f` if the entity exists.
+fn with_entity(
+    world: &mut World,
+    request: &Value,
+    f: impl FnOnce(&mut World, Entity) -> Value,
+) -> Value {
+    let entity = request["entity"]
+        .as_u64()
+        .and_then(|bits| Entity::try_from_bits(bits).ok());
+    match entity {
+        Some(entity) if world.get_entity(entity).is_some() => f(world, entity),
+        _ => json!({ "error": "invalid entity" }),
+    }
+}
+
+fn hierarchy(world: &mut World) -> Value {
+    let roots: Vec<Entity> = world
+        .query_filtered::<(Entity, Option<&Parent>), With<Node>>()
+        .iter(world)
+        .filter(|(_, parent)| parent.map_or(true, |p| world.get::<Node>(p.get()).is_none()))
+        .map(|(entity, _)| entity)
+        .collect();
+    let nodes: Vec<Value> = roots.iter().map(|e| hierarchy_node(world, *e)).collect();
+    json!({ "roots": nodes })
+}
+
+fn hierarchy_node(world: &World, entity: Entity) -> Value {
+    let name = world.get::<Name>(entity).map(|n| n.as_str().to_string());
+    let children: Vec<Value> = world
+        .get::<Children>(entity)
+        .map(|children| {
+            children
+                .iter()
+                .map(|child| hierarchy_node(world, *child))
+                .collect()
+        })
+        .unwrap_or_default();
+    json!({
+        "entity": entity.to_bits(),
+        "name": name,
+        "children": children,
+    })
+}
+
+fn inspect(world: &mut World, entity: Entity) -> Value {
+    let components: Vec<String> = world
+        .inspect_entity(entity)
+        .iter()
+        .map(|info| info.name().to_string())
+        .collect();
+    let style = world.get::<Style>(entity).map(|s| format!("{:?}", s));
+    let rect = match (
+        world.get::<Node>(entity),
+        world.get::<GlobalTransform>(entity),
+    ) {
+        (Some(node), Some(transform)) => {
+            let rect = node.logical_rect(transform);
+            Some([rect.min.x, rect.min.y, rect.max.x, rect.max.y])
+        }
+        _ => None,
+    };
+    json!({
+        "entity": entity.to_bits(),
+        "name": world.get::<Name>(entity).map(|n| n.as_str().to_string()),
+        "components": components,
+        "style": style,
+        "rect": rect,
+    })
+}
+
+fn click(world: &mut World, entity: Entity) -> Value {
+    let Some(window) = world
+        .query_filtered::<Entity, With<PrimaryWindow>>()
+        .iter(world)
+        .next()
+    else {
+        return json!({ "error": "no primary window" });
+    };
+    let Some(target) = WindowRef::Entity(window).normalize(None) else {
+        return json!({ "error": "no primary window" });
+    };
+    let position = match (
+        world.get::<Node>(entity),
+        world.get::<GlobalTransform>(entity),
+    ) {
+        (Some(node), Some(transform)) => node.logical_rect(transform).center(),
+        _ => Vec2::ZERO,
+    };
+    world.send_event(Pointer::new(
+        PointerId::Mouse,
+        Location {
+            target: NormalizedRenderTarget::Window(target),
+            position,
+        },
+        entity,
+        Click {
+            button: PointerButton::Primary,
+            hit: HitData::new(Entity::PLACEHOLDER, 0.0, None, None),
+            duration: Duration::ZERO,
+        },
+    ));
+    json!({ "ok": true })
+}
@@ -12,6 +12,8 @@ use crate::node_span::NodeSpan;
 pub struct Dynamic<V: IntoView, F: Fn(&mut Cx) -> V + Send> {
     state: Option<(ViewRef, Entity)>,
     factory: F,
+    /// Placeholder node marking the position of the inner view while it is empty.
+    anchor: Option<Entity>,
 }
 
 impl<V: IntoView, F: Fn(&mut Cx) -> V + Send> Dynamic<V, F> {
@@ -20,17 +22,23 @@ impl<V: IntoView, F: Fn(&mut Cx) -> V + Send> Dynamic<V, F> {
         Self {
             state: None,
             factory,
+            anchor: None,
         }
     }
-}
 
-impl<V: IntoView, F: Fn(&mut Cx) -> V + Send> View for Dynamic<V, F> {
-    fn nodes(&self) -> NodeSpan {
+    /// The display nodes of the inner view, not including the anchor.
+    fn inner_nodes(&self) -> NodeSpan {
         match self.state {
             None => NodeSpan::Empty,
             Some((ref view, _)) => view.nodes(),
         }
     }
+}
+
+impl<V: IntoView, F: Fn(&mut Cx) -> V + Send> View for Dynamic<V, F> {
+    fn nodes(&self) -> NodeSpan {
+        NodeSpan::anchored(self.anchor, self.inner_nodes())
+    }
 
     fn build(&mut self, view_entity: bevy::prelude::Entity, world: &mut World) {
         let mut tracking = TrackingScope::new(world.change_tick());
@@ -41,6 +49,8 @@ impl<V: IntoView, F: Fn(&mut Cx) -> V + Send> View for Dynamic<V, F> {
     }
 
     fn react(&mut self, view_entity: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        let old_nodes = self.nodes();
+        let position = old_nodes.position(world);
         if let Some((view, entity)) = self.state.take() {
             view.raze(entity, world);
         }
@@ -48,6 +58,8 @@ impl<V: IntoView, F: Fn(&mut Cx) -> V + Send> View for Dynamic<V, F> {
         let view = (self.factory)(&mut Cx::new(world, view_entity, tracking)).into_view();
         let entity = ViewRef::spawn(&view, view_entity, world);
         self.state = Some((view, entity));
+        NodeSpan::update_anchor(&mut self.anchor, &self.inner_nodes(), world);
+        NodeSpan::splice(position, &old_nodes, &self.nodes(), world);
         world.entity_mut(view_entity).insert(DisplayNodeChanged);
     }
 
@@ -56,6 +68,9 @@ impl<V: IntoView, F: Fn(&mut Cx) -> V + Send> View for Dynamic<V, F> {
             view.raze(entity, world)
         }
         self.state = None;
+        if let Some(anchor) = self.anchor.take() {
+            NodeSpan::despawn_anchor(anchor, world);
+        }
         world.despawn_owned_recursive(view_entity);
     }
 }
@@ -15,6 +15,8 @@ pub struct DynamicKeyed<Key, KeyFn: Fn(&mut Cx) -> Key, V: IntoView, F: Fn(Key)
     state: Option<(ViewRef, Entity)>,
     key: KeyFn,
     factory: F,
+    /// Placeholder node marking the position of the inner view while it is empty.
+    anchor: Option<Entity>,
 }
 
 impl<Key, KeyFn: Fn(&mut Cx) -> Key, V: IntoView, F: Fn(Key) -> V + Send>
@@ -31,6 +33,15 @@ impl<Key, KeyFn: Fn(&mut Cx) -> Key, V: IntoView, F: Fn(Key) -> V + Send>
             state: None,
             key,
             factory,
+            anchor: None,
+        }
+    }
+
+    /// The display nodes of the inner view, not including the anchor.
+    fn inner_nodes(&self) -> NodeSpan {
+        match self.state {
+            None => NodeSpan::Empty,
+            Some((ref view, _)) => view.nodes(),
         }
     }
 }
@@ -39,10 +50,7 @@ impl<Key, KeyFn: Fn(&mut Cx) -> Key, V: IntoView, F: Fn(Key) -> V + Send> View
     for DynamicKeyed<Key, KeyFn, V, F>
 {
     fn nodes(&self) -> NodeSpan {
-        match self.state {
-            None => NodeSpan::Empty,
-            Some((ref view, _)) => view.nodes(),
-        }
+        NodeSpan::anchored(self.anchor, self.inner_nodes())
     }
 
     fn build(&mut self, view_entity: bevy::prelude::Entity, world: &mut World) {
@@ -54,6 +62,8 @@ impl<Key, KeyFn: Fn(&mut Cx) -> Key, V: IntoView, F: Fn(Key) -> V + Send> View
     }
 
     fn react(&mut self, view_entity: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        let old_nodes = self.nodes();
+        let position = old_nodes.position(world);
         if let Some((view, entity)) = self.state.take() {
             view.raze(entity, world);
         }
@@ -61,8 +71,10 @@ impl<Key, KeyFn: Fn(&mut Cx) -> Key, V: IntoView, F: Fn(Key) -> V + Send> View
         let key = (self.key)(&mut Cx::new(world, view_entity, tracking));
         let view = (self.factory)(key).into_view();
         let entity = ViewRef::spawn(&view, view_entity, world);
-        world.entity_mut(view_entity).insert(DisplayNodeChanged);
         self.state = Some((view, entity));
+        NodeSpan::update_anchor(&mut self.anchor, &self.inner_nodes(), world);
+        NodeSpan::splice(position, &old_nodes, &self.nodes(), world);
+        world.entity_mut(view_entity).insert(DisplayNodeChanged);
     }
 
     fn raze(&mut self, view_entity: Entity, world: &mut World) {
@@ -70,6 +82,9 @@ impl<Key, KeyFn: Fn(&mut Cx) -> Key, V: IntoView, F: Fn(Key) -> V + Send> View
             view.raze(entity, world)
         }
         self.state = None;
+        if let Some(anchor) = self.anchor.take() {
+            NodeSpan::despawn_anchor(anchor, world);
+        }
         world.despawn_owned_recursive(view_entity);
     }
 }
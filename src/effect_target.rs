@@ -6,7 +6,10 @@ use bevy::{
     hierarchy::BuildWorldChildren,
 };
 
-use crate::{tracking_scope::TrackingScope, Cx, Rcx, Reaction, ReactionCell, ReactionTarget};
+use crate::{
+    signal::IntoSignal, tracking_scope::TrackingScope, Cx, DespawnScopes, Rcx, Reaction,
+    ReactionCell, ReactionTarget, Signal,
+};
 
 /// A reactive effect that modifies a target entity.
 pub trait EntityEffect: Sync + Send {
@@ -88,6 +91,26 @@ where
         self
     }
 
+    /// Create an effect which only runs when a key changes. `deps` is run reactively to
+    /// compute the key, and `effect` is then run non-reactively, but only if the key is
+    /// different from the previous one. This allows precise control over which dependencies
+    /// the effect reacts to, similar to [`DynamicKeyed`](crate::DynamicKeyed).
+    ///
+    /// Any cleanup functions or owned entities created by the effect are kept until the
+    /// next time it runs.
+    fn create_keyed_effect<
+        K: PartialEq + Send + Sync + 'static,
+        D: Send + Sync + 'static + FnMut(&mut Rcx) -> K,
+        F: Send + Sync + 'static + FnMut(&mut Cx, Entity, &K),
+    >(
+        mut self,
+        deps: D,
+        effect: F,
+    ) -> Self {
+        self.add_reaction(KeyedUpdateReaction::new(deps, effect));
+        self
+    }
+
     /// Add a static bundle to the element.
     fn insert<T: Bundle>(mut self, bundle: T) -> Self {
         self.add_effect(Box::new(InsertBundleEffect {
@@ -106,6 +129,31 @@ where
         self
     }
 
+    /// Insert a bundle while a condition is true, and remove it when the condition becomes
+    /// false. The bundle is constructed by `factory` each time it is inserted, so this works
+    /// with bundles which can't be cloned, such as `On::<E>` event listeners. This lets an
+    /// element drop its handlers while it is disabled, or switch between sets of handlers
+    /// (by using two opposite conditions) without rebuilding the element.
+    ///
+    /// While it is inserted, the bundle replaces any components of the same type which were
+    /// already on the element, such as a static handler added with `insert`; those are put
+    /// back when the condition becomes false.
+    ///
+    /// Arguments:
+    /// * `condition` - Whether the bundle should be present; may be a signal.
+    /// * `factory` - Function which constructs the bundle.
+    fn insert_listener_if<B: Bundle, F: Send + Sync + 'static + Fn() -> B>(
+        mut self,
+        condition: impl IntoSignal<bool>,
+        factory: F,
+    ) -> Self {
+        self.add_reaction(ConditionalBundleReaction::new(
+            condition.into_signal(),
+            factory,
+        ));
+        self
+    }
+
     /// Add a computed bundle to the element.
     fn insert_computed<T: Bundle, F: Send + Sync + 'static + FnMut(&mut Rcx) -> T>(
         mut self,
@@ -215,6 +263,52 @@ impl<B: Bundle, F: Sync + Send + FnMut(&mut Rcx) -> B> Reaction for ComputedBund
     }
 }
 
+/// Inserts or removes a bundle depending on a condition.
+pub struct ConditionalBundleReaction<B: Bundle, F: Fn() -> B> {
+    condition: Signal<bool>,
+    factory: F,
+    inserted: bool,
+    /// Components of the same type which were on the target before the bundle was inserted.
+    displaced: Option<B>,
+}
+
+impl<B: Bundle, F: Fn() -> B> ConditionalBundleReaction<B, F> {
+    pub(crate) fn new(condition: Signal<bool>, factory: F) -> Self {
+        Self {
+            condition,
+            factory,
+            inserted: false,
+            displaced: None,
+        }
+    }
+}
+
+impl<B: Bundle, F: Fn() -> B> Reaction for ConditionalBundleReaction<B, F> {
+    fn react(&mut self, owner: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        let target = world.entity(owner).get::<ReactionTarget>().unwrap().0;
+        let condition = self.condition.get(&Rcx::new(world, owner, tracking));
+        if condition == self.inserted {
+            return;
+        }
+        let Some(mut entt) = world.get_entity_mut(target) else {
+            return;
+        };
+        match condition {
+            true => {
+                self.displaced = entt.take::<B>();
+                entt.insert((self.factory)());
+            }
+            false => {
+                entt.remove::<B>();
+                if let Some(displaced) = self.displaced.take() {
+                    entt.insert(displaced);
+                }
+            }
+        }
+        self.inserted = condition;
+    }
+}
+
 /// Produces a bundle reactively, returns the bundle as a result.
 // pub struct BundleComputedRefReaction<C: Component, F: FnMut(&mut Rcx, &mut C)> {
 //     target: Option<Entity>,
@@ -295,3 +389,208 @@ impl<F: FnMut(&mut Cx, Entity)> Reaction for UpdateReaction<F> {
         (self.effect)(&mut cx, target);
     }
 }
+
+/// Runs an effect whenever a reactively-computed key changes.
+pub struct KeyedUpdateReaction<K, D: FnMut(&mut Rcx) -> K, F: FnMut(&mut Cx, Entity, &K)> {
+    deps: D,
+    effect: F,
+    key: Option<K>,
+    /// Entity holding the tracking scope of the most recent run of the effect, so that its
+    /// cleanups and owned entities survive until the key changes.
+    holder: Option<Entity>,
+}
+
+impl<K, D: FnMut(&mut Rcx) -> K, F: FnMut(&mut Cx, Entity, &K)> KeyedUpdateReaction<K, D, F> {
+    pub(crate) fn new(deps: D, effect: F) -> Self {
+        Self {
+            deps,
+            effect,
+            key: None,
+            holder: None,
+        }
+    }
+}
+
+impl<K: PartialEq, D: FnMut(&mut Rcx) -> K, F: FnMut(&mut Cx, Entity, &K)> Reaction
+    for KeyedUpdateReaction<K, D, F>
+{
+    fn react(&mut self, owner: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        let target = world.entity(owner).get::<ReactionTarget>().unwrap().0;
+        let key = (self.deps)(&mut Rcx::new(world, owner, tracking));
+        if self.key.as_ref() == Some(&key) {
+            return;
+        }
+
+        // The holder is created during the first reaction, so that it is owned by the
+        // reaction's permanent scope and despawned along with it.
+        let holder = *self.holder.get_or_insert_with(|| {
+            let holder = world
+                .spawn((
+                    TrackingScope::new(world.change_tick()),
+                    Name::new("KeyedUpdateReaction"),
+                ))
+                .set_parent(owner)
+                .id();
+            tracking.add_owned(holder);
+            holder
+        });
+
+        // Release whatever the previous run of the effect created.
+        let (cleanups, owned) = match world.get_mut::<TrackingScope>(holder) {
+            Some(mut scope) => (
+                std::mem::take(&mut scope.cleanups),
+                std::mem::take(&mut scope.owned),
+            ),
+            None => return,
+        };
+        for cleanup_fn in cleanups {
+            cleanup_fn(world);
+        }
+        for entity in owned {
+            world.despawn_owned_recursive(entity);
+        }
+
+        // Run the effect with a scope of its own, so that whatever it reads does not become a
+        // dependency of the reaction.
+        let mut effect_scope = TrackingScope::new(world.change_tick());
+        (self.effect)(&mut Cx::new(world, holder, &mut effect_scope), target, &key);
+        if let Some(mut scope) = world.get_mut::<TrackingScope>(holder) {
+            scope.cleanups = std::mem::take(&mut effect_scope.cleanups);
+            scope.owned = std::mem::take(&mut effect_scope.owned);
+        }
+        self.key = Some(key);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{ecs::component::Component, ui::node_bundles::NodeBundle};
+
+    use crate::{
+        node_span::NodeSpan, tracking_scope::run_reactions, Element, IntoView, Mutable,
+        RunContextSetup, ViewRef,
+    };
+
+    use super::*;
+
+    #[derive(Component, Debug, PartialEq)]
+    struct Handler(&'static str);
+
+    fn spawn_conditional(
+        world: &mut World,
+        static_handler: bool,
+        initial: bool,
+    ) -> (Mutable<bool>, Entity) {
+        let owner = world.spawn_empty().id();
+        let mut scope = TrackingScope::new(world.change_tick());
+        let mut cx = Cx::new(world, owner, &mut scope);
+        let enabled: Mutable<bool> = cx.create_mutable(initial);
+        world.entity_mut(owner).insert(scope);
+
+        let view = Element::<NodeBundle>::new()
+            .insert_if(static_handler, Handler("static"))
+            .insert_listener_if(enabled, || Handler("conditional"))
+            .into_view();
+        ViewRef::spawn(&view, owner, world);
+        let NodeSpan::Node(display) = view.nodes() else {
+            panic!("element should have a display node");
+        };
+        (enabled, display)
+    }
+
+    fn set_enabled(world: &mut World, enabled: Mutable<bool>, value: bool) {
+        enabled.set(world, value);
+        world.flush_commands();
+        run_reactions(world);
+    }
+
+    #[test]
+    fn test_insert_listener_if() {
+        let mut world = World::default();
+        let (enabled, display) = spawn_conditional(&mut world, false, false);
+        assert_eq!(world.get::<Handler>(display), None);
+
+        set_enabled(&mut world, enabled, true);
+        assert_eq!(world.get::<Handler>(display), Some(&Handler("conditional")));
+
+        set_enabled(&mut world, enabled, false);
+        assert_eq!(world.get::<Handler>(display), None);
+    }
+
+    #[test]
+    fn test_insert_listener_if_restores_static() {
+        let mut world = World::default();
+        let (enabled, display) = spawn_conditional(&mut world, true, true);
+        assert_eq!(world.get::<Handler>(display), Some(&Handler("conditional")));
+
+        // Removing the conditional handler puts back the one it replaced.
+        set_enabled(&mut world, enabled, false);
+        assert_eq!(world.get::<Handler>(display), Some(&Handler("static")));
+
+        set_enabled(&mut world, enabled, true);
+        assert_eq!(world.get::<Handler>(display), Some(&Handler("conditional")));
+        set_enabled(&mut world, enabled, false);
+        assert_eq!(world.get::<Handler>(display), Some(&Handler("static")));
+    }
+
+    #[test]
+    fn test_keyed_effect() {
+        let mut world = World::default();
+        let owner = world.spawn_empty().id();
+        let mut scope = TrackingScope::new(world.change_tick());
+        let mut cx = Cx::new(&mut world, owner, &mut scope);
+        let value: Mutable<i32> = cx.create_mutable(1);
+        let untracked: Mutable<i32> = cx.create_mutable(0);
+        world.entity_mut(owner).insert(scope);
+
+        let log = Arc::new(Mutex::new(Vec::<String>::new()));
+        let effect_log = log.clone();
+        let view = Element::<NodeBundle>::new()
+            .create_keyed_effect(
+                move |rcx| value.get(rcx) / 10,
+                move |cx, _target, key| {
+                    let seen = untracked.get(cx);
+                    effect_log
+                        .lock()
+                        .unwrap()
+                        .push(format!("run {} {}", key, seen));
+                    let cleanup_log = effect_log.clone();
+                    let key = *key;
+                    cx.on_cleanup(move |_world| {
+                        cleanup_log.lock().unwrap().push(format!("cleanup {}", key));
+                    });
+                },
+            )
+            .into_view();
+        let view_entity = ViewRef::spawn(&view, owner, &mut world);
+        assert_eq!(*log.lock().unwrap(), vec!["run 0 0"]);
+
+        // A change to the value which leaves the key alone does not run the effect.
+        value.set(&mut world, 5);
+        world.flush_commands();
+        run_reactions(&mut world);
+        assert_eq!(*log.lock().unwrap(), vec!["run 0 0"]);
+
+        // Signals read by the effect itself are not dependencies.
+        untracked.set(&mut world, 1);
+        world.flush_commands();
+        run_reactions(&mut world);
+        assert_eq!(*log.lock().unwrap(), vec!["run 0 0"]);
+
+        // A new key cleans up after the previous run before running again.
+        value.set(&mut world, 12);
+        world.flush_commands();
+        run_reactions(&mut world);
+        assert_eq!(
+            *log.lock().unwrap(),
+            vec!["run 0 0", "cleanup 0", "run 1 1"]
+        );
+
+        // The cleanups of the last run are kept until the view is razed.
+        view.raze(view_entity, &mut world);
+        assert_eq!(
+            *log.lock().unwrap(),
+            vec!["run 0 0", "cleanup 0", "run 1 1", "cleanup 1"]
+        );
+    }
+}
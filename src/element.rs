@@ -1,13 +1,14 @@
-use std::marker::PhantomData;
+use std::{borrow::Cow, marker::PhantomData};
 
-use bevy::prelude::*;
+use bevy::{prelude::*, utils::HashSet};
 
 use crate::{
     effect_target::{EffectTarget, EntityEffect},
+    lcs::common_subsequence,
     node_span::NodeSpan,
     parent_view::{ChildView, ParentView},
     view::View,
-    DespawnScopes, IntoView, TrackingScope, ViewRef,
+    DespawnScopes, IntoView, TestId, TrackingScope, ViewRef,
 };
 
 /// A basic UI element
@@ -31,6 +32,24 @@ where
     marker: PhantomData<B>,
 }
 
+/// Component on the view entity of an [`Element`] which holds its display node, so that views
+/// which are not attached to the node hierarchy, such as portals, can find the node which
+/// encloses them.
+#[derive(Component, Clone, Copy)]
+pub(crate) struct ElementDisplay(pub(crate) Entity);
+
+/// Marks a node which is added to an element's display node by something other than the
+/// element's child views, such as the node which draws a drop shadow. When the element updates
+/// its children, underlays are kept at the start of the list, below the element's own children,
+/// rather than being detached.
+#[derive(Component, Clone, Copy, Default)]
+pub struct Underlay;
+
+/// Like [`Underlay`], but for nodes which are drawn on top of the element's own children, such
+/// as a mask which rounds their corners. Overlays are kept at the end of the list.
+#[derive(Component, Clone, Copy, Default)]
+pub struct Overlay;
+
 impl<B: Bundle + Default> Element<B> {
     /// Construct a new `Element`.
     pub fn new() -> Self {
@@ -60,6 +79,12 @@ impl<B: Bundle + Default> Element<B> {
         self
     }
 
+    /// Assign a stable [`TestId`] to this element, so that it can be found via the
+    /// [`TestIdRegistry`].
+    pub fn test_id(self, id: impl Into<Cow<'static, str>>) -> Self {
+        self.insert(TestId::new(id))
+    }
+
     // pub fn insert_computed_ref<
     //     T: Component,
     //     F1: Send + Sync + 'static + FnMut() -> T,
@@ -81,11 +106,79 @@ impl<B: Bundle + Default> Element<B> {
 
     /// Attach the children to the node. Note that each child view may produce multiple nodes,
     /// or none.
+    ///
+    /// Rather than replacing the whole list, this computes a diff against the current children
+    /// and only removes, inserts or moves the nodes which changed, so that unaffected nodes keep
+    /// their state. If the children are already in place, for example because a dynamic child
+    /// view spliced its new nodes in place, the hierarchy is left untouched.
+    /// [`Underlay`] nodes are kept in front of the child views' nodes, and [`Overlay`] nodes
+    /// after them.
     fn attach_children(&self, world: &mut World) {
-        let flat = self.child_entities();
-        world
-            .entity_mut(self.display.unwrap())
-            .replace_children(&flat);
+        let display_id = self.display.unwrap();
+        let prev: Vec<Entity> = world
+            .get::<Children>(display_id)
+            .map_or(Vec::new(), |children| children.to_vec());
+        let mut next: Vec<Entity> = prev
+            .iter()
+            .copied()
+            .filter(|child| world.get::<Underlay>(*child).is_some())
+            .collect();
+        next.extend(self.child_entities());
+        next.extend(
+            prev.iter()
+                .copied()
+                .filter(|child| world.get::<Overlay>(*child).is_some()),
+        );
+        let mut display = world.entity_mut(display_id);
+        if prev == next {
+            return;
+        }
+
+        // Nodes which are kept in the same relative order stay where they are; the rest are
+        // detached and re-inserted at their new positions below.
+        let next_set: HashSet<Entity> = next.iter().copied().collect();
+        let kept: Vec<Entity> = prev
+            .iter()
+            .copied()
+            .filter(|child| next_set.contains(child))
+            .collect();
+        let stable = common_subsequence(&kept, &next);
+        let stable_set: HashSet<Entity> = next
+            .iter()
+            .zip(stable.iter())
+            .filter_map(|(child, stable)| stable.then_some(*child))
+            .collect();
+        let detached: Vec<Entity> = prev
+            .iter()
+            .copied()
+            .filter(|child| !stable_set.contains(child))
+            .collect();
+        if !detached.is_empty() {
+            display.remove_children(&detached);
+        }
+
+        // The remaining children are now a subsequence of `next`, so inserting each run of
+        // unstable nodes at its final index, in order, produces the full list.
+        let mut index = 0;
+        while index < next.len() {
+            if stable[index] {
+                index += 1;
+                continue;
+            }
+            let run = stable[index..]
+                .iter()
+                .position(|stable| *stable)
+                .unwrap_or(next.len() - index);
+            display.insert_children(index, &next[index..index + run]);
+            index += run;
+        }
+    }
+}
+
+impl<B: Bundle + Default> Element<B> {
+    /// Add an effect which is started before all of the element's other effects.
+    pub(crate) fn add_effect_first(&mut self, effect: Box<dyn EntityEffect>) {
+        self.effects.insert(0, effect);
     }
 }
 
@@ -139,6 +232,9 @@ impl<B: Bundle + Default> View for Element<B> {
                 entity
             }
         };
+        world
+            .entity_mut(view_entity)
+            .insert(ElementDisplay(display));
 
         // Insert components from effects.
         if !self.effects.is_empty() {
@@ -174,6 +270,10 @@ impl<B: Bundle + Default> View for Element<B> {
         self.attach_children(world);
         true
     }
+
+    fn child_views(&mut self) -> Option<&mut Vec<ChildView>> {
+        Some(&mut self.children)
+    }
 }
 
 impl<B: Bundle + Default> IntoView for Element<B> {
@@ -181,3 +281,45 @@ impl<B: Bundle + Default> IntoView for Element<B> {
         ViewRef::new(self)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_attach_children_keeps_underlays() {
+        let mut world = World::default();
+        let a = world.spawn_empty().id();
+        let b = world.spawn_empty().id();
+        let mut element = Element::<NodeBundle>::new().children((
+            Element::<NodeBundle>::for_entity(a),
+            Element::<NodeBundle>::for_entity(b),
+        ));
+        let view_entity = world.spawn_empty().id();
+        element.build(view_entity, &mut world);
+        let display = element.display.unwrap();
+        let children = |world: &World| {
+            world
+                .get::<Children>(display)
+                .map_or(Vec::new(), |c| c.to_vec())
+        };
+        assert_eq!(children(&world), vec![a, b]);
+
+        // Nodes added by something other than the views are detached, unless they are
+        // underlays, which are moved in front of the views' nodes, or overlays, which are
+        // moved after them.
+        let underlay = world.spawn(Underlay).id();
+        let overlay = world.spawn(Overlay).id();
+        let stray = world.spawn_empty().id();
+        world
+            .entity_mut(display)
+            .insert_children(0, &[overlay])
+            .push_children(&[stray, underlay]);
+        element.children_changed(view_entity, &mut world);
+        assert_eq!(children(&world), vec![underlay, a, b, overlay]);
+
+        // Once in place, the underlay and overlay are left alone.
+        element.children_changed(view_entity, &mut world);
+        assert_eq!(children(&world), vec![underlay, a, b, overlay]);
+    }
+}
@@ -0,0 +1,27 @@
+use bevy::prelude::*;
+
+use crate::{signal::Signal, Cx, RunContextSetup};
+
+/// Method to create a signal that tracks the layout rectangle of a UI node.
+pub trait UseElementRect {
+    /// Signal that returns the rectangle occupied by the target's [`Node`], in logical
+    /// window coordinates. The signal updates when the node's computed layout or its
+    /// [`GlobalTransform`] changes, and is empty if the target has no layout yet.
+    fn use_element_rect(&mut self, target: Entity) -> Signal<Rect>;
+}
+
+impl<'p, 'w> UseElementRect for Cx<'p, 'w> {
+    fn use_element_rect(&mut self, target: Entity) -> Signal<Rect> {
+        // Memoized so that dependents only react when the rectangle actually changes, not
+        // whenever layout touches the node.
+        self.create_memo(move |cx| {
+            match (
+                cx.use_component::<Node>(target),
+                cx.use_component::<GlobalTransform>(target),
+            ) {
+                (Some(node), Some(transform)) => node.logical_rect(transform),
+                _ => Rect::default(),
+            }
+        })
+    }
+}
@@ -0,0 +1,246 @@
+use std::{
+    any::Any,
+    marker::PhantomData,
+    panic::{catch_unwind, AssertUnwindSafe},
+    sync::PoisonError,
+};
+
+use bevy::prelude::*;
+
+use crate::{
+    callback::CallbackFnCell, node_span::NodeSpan, origin::HandleOrigin, Callback, Cx,
+    DespawnScopes, DisplayNodeChanged, IntoView, TrackingScope, View, ViewHandle, ViewRef,
+};
+
+/// Component which records the error caught by an [`ErrorBoundary`], if any.
+#[derive(Component, Default)]
+pub(crate) struct ErrorBoundaryState {
+    pub(crate) error: Option<String>,
+}
+
+/// Extract a human-readable message from a panic payload.
+pub(crate) fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(msg) = payload.downcast_ref::<&str>() {
+        msg.to_string()
+    } else if let Some(msg) = payload.downcast_ref::<String>() {
+        msg.clone()
+    } else {
+        "Unknown error".to_string()
+    }
+}
+
+/// Report a panic that occurred while running the reaction for `entity`. If the entity has
+/// an [`ErrorBoundary`] ancestor, the error is recorded there and `true` is returned.
+pub(crate) fn report_to_error_boundary(world: &mut World, entity: Entity, message: &str) -> bool {
+    let mut ancestor = Some(entity);
+    while let Some(e) = ancestor {
+        if let Some(mut state) = world.get_mut::<ErrorBoundaryState>(e) {
+            if state.error.is_none() {
+                state.error = Some(message.to_string());
+            }
+            return true;
+        }
+        ancestor = world.get::<Parent>(e).map(|p| p.get());
+    }
+    false
+}
+
+enum BoundaryState {
+    Unset,
+    Children(Entity),
+    Fallback(ViewRef, Entity),
+}
+
+/// A view which catches panics that occur while building or updating its children. When a
+/// panic is caught, the children are razed and replaced with a fallback view, which is given
+/// the error message and a callback that can be used to retry building the children.
+pub struct ErrorBoundary<FV: IntoView, F: Fn(String, Callback) -> FV> {
+    children: ViewRef,
+    fallback: F,
+    state: BoundaryState,
+    retry: Option<Callback>,
+    marker: PhantomData<FV>,
+}
+
+impl<FV: IntoView, F: Fn(String, Callback) -> FV> ErrorBoundary<FV, F> {
+    /// Construct a new `ErrorBoundary`.
+    ///
+    /// Arguments:
+    /// * `children` - The views to display when there is no error.
+    /// * `fallback` - Function which constructs the view to display when an error has been
+    ///   caught. It is passed the error message, and a callback which clears the error and
+    ///   rebuilds the children.
+    pub fn new(children: impl IntoView, fallback: F) -> Self {
+        Self {
+            children: children.into_view(),
+            fallback,
+            state: BoundaryState::Unset,
+            retry: None,
+            marker: PhantomData,
+        }
+    }
+
+    /// Raze whichever view is currently displayed.
+    fn raze_current(&mut self, world: &mut World) {
+        match std::mem::replace(&mut self.state, BoundaryState::Unset) {
+            BoundaryState::Children(entity) => {
+                let children = self.children.clone();
+                let razed = catch_unwind(AssertUnwindSafe(|| children.raze(entity, world)));
+                if razed.is_err() {
+                    // The subtree is broken; make sure nothing is left behind.
+                    if world.get_entity(entity).is_some() {
+                        world.despawn_owned_recursive(entity);
+                    }
+                    if let Some(entt) = world.get_entity_mut(entity) {
+                        entt.despawn_recursive();
+                    }
+                }
+            }
+            BoundaryState::Fallback(view, entity) => view.raze(entity, world),
+            BoundaryState::Unset => {}
+        }
+    }
+
+    /// Attempt to build the children, returning the error message on failure.
+    fn build_children(&mut self, view_entity: Entity, world: &mut World) -> Result<(), String> {
+        let child = world
+            .spawn(ViewHandle(self.children.0.clone()))
+            .set_parent(view_entity)
+            .id();
+        let children = self.children.clone();
+        match catch_unwind(AssertUnwindSafe(|| {
+            children
+                .0
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .build(child, world)
+        })) {
+            Ok(()) => {
+                self.state = BoundaryState::Children(child);
+                Ok(())
+            }
+            Err(payload) => {
+                self.state = BoundaryState::Children(child);
+                self.raze_current(world);
+                Err(panic_message(payload.as_ref()))
+            }
+        }
+    }
+
+    fn build_fallback(&mut self, message: String, view_entity: Entity, world: &mut World) {
+        let view = (self.fallback)(message, self.retry.unwrap()).into_view();
+        let entity = ViewRef::spawn(&view, view_entity, world);
+        self.state = BoundaryState::Fallback(view, entity);
+    }
+}
+
+impl<FV: IntoView, F: Fn(String, Callback) -> FV> View for ErrorBoundary<FV, F> {
+    fn nodes(&self) -> NodeSpan {
+        match self.state {
+            BoundaryState::Unset => NodeSpan::Empty,
+            BoundaryState::Children(_) => self.children.nodes(),
+            BoundaryState::Fallback(ref view, _) => view.nodes(),
+        }
+    }
+
+    fn build(&mut self, view_entity: Entity, world: &mut World) {
+        world
+            .entity_mut(view_entity)
+            .insert((Name::new("ErrorBoundary"), ErrorBoundaryState::default()));
+        let mut tracking = TrackingScope::new(world.change_tick());
+
+        // Callback which clears the error, causing the children to be rebuilt.
+        let retry = world
+            .spawn(CallbackFnCell::<()> {
+                inner: Some(Box::new(move |cx: &mut Cx, _: ()| {
+                    if let Some(mut state) =
+                        cx.world_mut().get_mut::<ErrorBoundaryState>(view_entity)
+                    {
+                        state.error = None;
+                    }
+                })),
+            })
+            .set_parent(view_entity)
+            .id();
+        tracking.add_owned(retry);
+        self.retry = Some(Callback {
+            id: retry,
+            origin: HandleOrigin::new(world),
+            marker: PhantomData,
+        });
+
+        self.react(view_entity, world, &mut tracking);
+        world.entity_mut(view_entity).insert(tracking);
+    }
+
+    fn react(&mut self, view_entity: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        tracking.track_component::<ErrorBoundaryState>(view_entity, world);
+        let error = world
+            .get::<ErrorBoundaryState>(view_entity)
+            .and_then(|state| state.error.clone());
+        match (&self.state, error) {
+            (BoundaryState::Fallback(..), Some(_)) | (BoundaryState::Children(_), None) => {
+                return;
+            }
+            (_, Some(message)) => {
+                self.raze_current(world);
+                self.build_fallback(message, view_entity, world);
+            }
+            (_, None) => {
+                self.raze_current(world);
+                if let Err(message) = self.build_children(view_entity, world) {
+                    // Record the error without triggering another reaction.
+                    if let Some(mut state) = world.get_mut::<ErrorBoundaryState>(view_entity) {
+                        state.bypass_change_detection().error = Some(message.clone());
+                    }
+                    self.build_fallback(message, view_entity, world);
+                }
+            }
+        }
+        world.entity_mut(view_entity).insert(DisplayNodeChanged);
+    }
+
+    fn raze(&mut self, view_entity: Entity, world: &mut World) {
+        self.raze_current(world);
+        world.despawn_owned_recursive(view_entity);
+    }
+}
+
+impl<FV: IntoView + 'static, F: Fn(String, Callback) -> FV + Send + Sync + 'static> IntoView
+    for ErrorBoundary<FV, F>
+{
+    fn into_view(self) -> ViewRef {
+        ViewRef::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{tracking_scope::run_reactions, RunContextSetup};
+
+    use super::*;
+
+    #[test]
+    fn test_effect_panic_is_contained() {
+        let mut world = World::default();
+        let boundary = world.spawn(ErrorBoundaryState::default()).id();
+        let owner = world.spawn_empty().set_parent(boundary).id();
+        let mut scope = TrackingScope::new(world.change_tick());
+        let mut cx = Cx::new(&mut world, owner, &mut scope);
+        let fail = cx.create_mutable::<bool>(false);
+        cx.create_effect(move |cx| {
+            if fail.get(cx) {
+                panic!("effect failed");
+            }
+        });
+        world.entity_mut(owner).insert(scope);
+
+        // The panic is recorded on the boundary rather than unwinding out of the reactions.
+        world.increment_change_tick();
+        fail.set(&mut world, true);
+        world.flush_commands();
+        run_reactions(&mut world);
+        let state = world.get::<ErrorBoundaryState>(boundary).unwrap();
+        assert_eq!(state.error.as_deref(), Some("effect failed"));
+    }
+}
@@ -0,0 +1,98 @@
+use bevy::{ecs::event::ManualEventReader, prelude::*};
+
+use crate::{cx::create_entity_mutable, mutable::MutableCell, signal::Signal, Cx};
+
+/// Type-erased function which polls an event queue and updates the mutable cell that
+/// lives on the same entity.
+type EventPollFn = Box<dyn FnMut(&mut World, Entity) + Send + Sync>;
+
+/// Component which reads events of a given type and copies them into a mutable cell.
+#[derive(Component)]
+pub(crate) struct EventSignalCell(Option<EventPollFn>);
+
+// Event signals can't be implemented as a Reaction, because the `Events` resource is
+// mutated every frame when the event buffers are swapped. Instead, each event signal owns
+// its own reader, which is polled once per frame.
+pub(crate) fn update_event_signals(world: &mut World) {
+    let mut query = world.query_filtered::<Entity, With<EventSignalCell>>();
+    let entities: Vec<Entity> = query.iter(world).collect();
+    for entity in entities {
+        let Some(mut poll) = world
+            .get_mut::<EventSignalCell>(entity)
+            .and_then(|mut cell| cell.0.take())
+        else {
+            continue;
+        };
+        (poll)(world, entity);
+        if let Some(mut cell) = world.get_mut::<EventSignalCell>(entity) {
+            cell.0 = Some(poll);
+        }
+    }
+}
+
+/// Create a new event reader which skips over any events already in the queue.
+fn current_reader<E: Event>(world: &World) -> ManualEventReader<E> {
+    match world.get_resource::<Events<E>>() {
+        Some(events) => events.get_reader_current(),
+        None => ManualEventReader::default(),
+    }
+}
+
+/// Methods to create signals which are driven by Bevy events.
+pub trait CreateEventSignal {
+    /// Create a signal which contains the most recent event of type `E`, or `None` if no
+    /// such event has been received since the signal was created. Reactions which depend
+    /// on this signal will run on the frame in which the event arrives.
+    fn create_event_signal<E: Event + Clone>(&mut self) -> Signal<Option<E>>;
+
+    /// Create a signal which contains all of the events of type `E` that were received
+    /// since the previous frame. The buffer is emptied on the next frame in which no events
+    /// arrive.
+    fn create_event_buffer_signal<E: Event + Clone>(&mut self) -> Signal<Vec<E>>;
+}
+
+impl<'p, 'w> CreateEventSignal for Cx<'p, 'w> {
+    fn create_event_signal<E: Event + Clone>(&mut self) -> Signal<Option<E>> {
+        let mut reader = current_reader::<E>(self.world());
+        let mutable = create_entity_mutable::<_, Option<E>>(self, None);
+        self.world_mut()
+            .entity_mut(mutable.id())
+            .insert(EventSignalCell(Some(Box::new(
+                move |world: &mut World, entity: Entity| {
+                    let Some(events) = world.get_resource::<Events<E>>() else {
+                        return;
+                    };
+                    let latest = reader.read(events).last().cloned();
+                    if let Some(event) = latest {
+                        if let Some(mut cell) = world.get_mut::<MutableCell<Option<E>>>(entity) {
+                            cell.0 = Some(event);
+                        }
+                    }
+                },
+            ))));
+        Signal::Mutable(mutable)
+    }
+
+    fn create_event_buffer_signal<E: Event + Clone>(&mut self) -> Signal<Vec<E>> {
+        let mut reader = current_reader::<E>(self.world());
+        let mutable = create_entity_mutable::<_, Vec<E>>(self, Vec::new());
+        self.world_mut()
+            .entity_mut(mutable.id())
+            .insert(EventSignalCell(Some(Box::new(
+                move |world: &mut World, entity: Entity| {
+                    let Some(events) = world.get_resource::<Events<E>>() else {
+                        return;
+                    };
+                    let received: Vec<E> = reader.read(events).cloned().collect();
+                    let Some(mut cell) = world.get_mut::<MutableCell<Vec<E>>>(entity) else {
+                        return;
+                    };
+                    // Avoid triggering change detection when the buffer stays empty.
+                    if !received.is_empty() || !cell.0.is_empty() {
+                        cell.0 = received;
+                    }
+                },
+            ))));
+        Signal::Mutable(mutable)
+    }
+}
@@ -1,18 +1,25 @@
+use std::sync::Arc;
+
 use bevy::prelude::*;
 
 use crate::{
     node_span::NodeSpan, parent_view::ChildViewTuple, view::View, ChildView, DespawnScopes,
-    IntoView, ViewRef,
+    DisplayNodeChanged, IntoView, Rcx, Signal, TrackingScope, ViewRef,
 };
 
 /// A `Fragment` represents a group of one or more child views which can be inserted inline
 /// within a parent view. A parent view which contains a `Fragment` will render the child views of
 /// the `Fragment` in place of the `Fragment` itself. This is useful in cases where a function's
 /// return type only allows a single view to be returned, but you want to return multiple views.
+///
+/// The list of children can also be reactive: see [`Fragment::children_signal`].
 #[derive(Default)]
 pub struct Fragment {
     /// Children of this fragment.
     children: Vec<ChildView>,
+
+    /// Reactive list of children, if any.
+    children_signal: Option<Signal<Vec<ViewRef>>>,
 }
 
 impl Fragment {
@@ -27,6 +34,7 @@ impl Fragment {
                     entity: None,
                 })
                 .collect(),
+            children_signal: None,
         }
     }
 
@@ -40,6 +48,17 @@ impl Fragment {
                     entity: None,
                 })
                 .collect(),
+            children_signal: None,
+        }
+    }
+
+    /// Construct a new `Fragment` whose children are given by a signal. When the list changes,
+    /// children are matched to the previous list by identity, so that only added children are
+    /// built and only removed children are razed; unchanged siblings are left alone.
+    pub fn children_signal(children: Signal<Vec<ViewRef>>) -> Self {
+        Self {
+            children: Vec::new(),
+            children_signal: Some(children),
         }
     }
 }
@@ -52,17 +71,73 @@ impl View for Fragment {
     }
 
     fn build(&mut self, view_entity: Entity, world: &mut World) {
+        if self.children_signal.is_some() {
+            let mut tracking = TrackingScope::new(world.change_tick());
+            self.react(view_entity, world, &mut tracking);
+            world.entity_mut(view_entity).insert(tracking);
+            return;
+        }
+
         // Build child nodes.
         for child in self.children.iter_mut() {
             child.entity = Some(ViewRef::spawn(&child.view, view_entity, world));
         }
     }
 
+    fn react(&mut self, view_entity: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        let Some(children_signal) = self.children_signal.clone() else {
+            return;
+        };
+        let next_views = {
+            let re = Rcx::new(world, view_entity, tracking);
+            children_signal.get_clone(&re)
+        };
+
+        // Match new children with previous ones by identity.
+        let mut prev_children = std::mem::take(&mut self.children);
+        let mut next_children: Vec<ChildView> = Vec::with_capacity(next_views.len());
+        let mut changed = prev_children.len() != next_views.len();
+        for view in next_views {
+            match prev_children
+                .iter()
+                .position(|child| Arc::ptr_eq(&child.view.0, &view.0))
+            {
+                Some(prev_index) => {
+                    // If the order is unchanged, the match is always the first remaining child.
+                    changed |= prev_index != 0;
+                    next_children.push(prev_children.remove(prev_index));
+                }
+                None => {
+                    changed = true;
+                    let entity = ViewRef::spawn(&view, view_entity, world);
+                    next_children.push(ChildView {
+                        view,
+                        entity: Some(entity),
+                    });
+                }
+            }
+        }
+
+        // Raze children which are no longer present.
+        for child in prev_children {
+            changed = true;
+            if let Some(entity) = child.entity {
+                child.view.raze(entity, world);
+            }
+        }
+
+        self.children = next_children;
+        if changed {
+            world.entity_mut(view_entity).insert(DisplayNodeChanged);
+        }
+    }
+
     fn raze(&mut self, view_entity: Entity, world: &mut World) {
         // Raze all child views
         for child in self.children.iter_mut() {
-            child.view.raze(child.entity.unwrap(), world);
-            child.entity = None;
+            if let Some(entity) = child.entity.take() {
+                child.view.raze(entity, world);
+            }
             // Child raze() will despawn itself.
         }
 
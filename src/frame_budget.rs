@@ -0,0 +1,146 @@
+use std::time::Duration;
+
+use bevy::{prelude::*, utils::Instant};
+
+use crate::{cx::spawn_effect, signal::Signal, Cx, RunContextRead, RunContextSetup};
+
+/// Resource which limits how much deferrable work the reactor does in a single frame.
+///
+/// By default there is no limit, and all pending work is done in the frame in which it is
+/// detected. When a budget is set, newly-added view roots and reactions marked [`LowPriority`]
+/// are only started while the frame's time budget has not been used up; the rest are queued
+/// and resumed in the following frames. This spreads large rebuilds over several frames
+/// instead of causing a single long hitch. Ordinary reactions are never deferred.
+///
+/// At least one queued item is processed each frame, so progress is made even if the budget
+/// is smaller than the time taken by a single item.
+#[derive(Resource, Default)]
+pub struct FrameBudget {
+    /// Maximum time to spend per frame, or `None` for no limit.
+    pub budget: Option<Duration>,
+
+    /// Time at which the reactor started work in the current frame.
+    started: Option<Instant>,
+
+    /// Low-priority reaction scopes which have changed but not yet run.
+    pending_reactions: Vec<Entity>,
+
+    /// View roots which have been added but not yet built.
+    pending_roots: Vec<Entity>,
+}
+
+impl FrameBudget {
+    /// Construct a new [`FrameBudget`] with the given time limit per frame.
+    pub fn new(budget: Duration) -> Self {
+        Self {
+            budget: Some(budget),
+            ..default()
+        }
+    }
+
+    /// True if there is deferred work which has not yet been done.
+    pub fn is_building(&self) -> bool {
+        !self.pending_reactions.is_empty() || !self.pending_roots.is_empty()
+    }
+
+    /// True if work splitting is enabled.
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.budget.is_some()
+    }
+
+    /// True if the time budget for the current frame has been used up.
+    pub(crate) fn is_exhausted(&self) -> bool {
+        match (self.budget, self.started) {
+            (Some(budget), Some(started)) => started.elapsed() >= budget,
+            _ => false,
+        }
+    }
+
+    /// Take the queue of deferred reactions, leaving it empty.
+    pub(crate) fn take_pending_reactions(world: &mut World) -> Vec<Entity> {
+        world
+            .get_resource_mut::<FrameBudget>()
+            .map(|mut budget| {
+                std::mem::take(&mut budget.bypass_change_detection().pending_reactions)
+            })
+            .unwrap_or_default()
+    }
+
+    /// Replace the queue of deferred reactions.
+    pub(crate) fn set_pending_reactions(world: &mut World, pending: Vec<Entity>) {
+        update_pending(world, |budget| budget.pending_reactions = pending);
+    }
+
+    /// Take the queue of deferred view roots, leaving it empty.
+    #[cfg(feature = "ui")]
+    pub(crate) fn take_pending_roots(world: &mut World) -> Vec<Entity> {
+        world
+            .get_resource_mut::<FrameBudget>()
+            .map(|mut budget| std::mem::take(&mut budget.bypass_change_detection().pending_roots))
+            .unwrap_or_default()
+    }
+
+    /// Replace the queue of deferred view roots.
+    #[cfg(feature = "ui")]
+    pub(crate) fn set_pending_roots(world: &mut World, pending: Vec<Entity>) {
+        update_pending(world, |budget| budget.pending_roots = pending);
+    }
+}
+
+/// Modify the queues. The resource is only marked as changed when the building state changes,
+/// so that the building signal doesn't cause reactions every frame.
+fn update_pending(world: &mut World, f: impl FnOnce(&mut FrameBudget)) {
+    if let Some(mut budget) = world.get_resource_mut::<FrameBudget>() {
+        let was_building = budget.is_building();
+        f(budget.bypass_change_detection());
+        if budget.is_building() != was_building {
+            budget.set_changed();
+        }
+    }
+}
+
+/// Returns true if the frame budget is enabled and has been used up.
+pub(crate) fn budget_exhausted(world: &World) -> bool {
+    world
+        .get_resource::<FrameBudget>()
+        .map_or(false, FrameBudget::is_exhausted)
+}
+
+/// Returns true if the frame budget resource is present and has a limit.
+pub(crate) fn budget_enabled(world: &World) -> bool {
+    world
+        .get_resource::<FrameBudget>()
+        .map_or(false, FrameBudget::is_enabled)
+}
+
+/// System which records the time at which the reactor starts work in each frame.
+pub(crate) fn start_frame_budget(mut budget: ResMut<FrameBudget>) {
+    budget.bypass_change_detection().started = Some(Instant::now());
+}
+
+/// Marker component for tracking scopes whose reactions may be deferred to a later frame when
+/// the [`FrameBudget`] has been used up.
+#[derive(Component, Default, Clone, Copy)]
+pub struct LowPriority;
+
+/// Methods for working with the [`FrameBudget`] from a reactive context.
+pub trait CreateFrameBudgetSignals {
+    /// Signal that returns true while there is deferred work waiting to be done, for example
+    /// to show a spinner.
+    fn create_building_signal(&mut self) -> Signal<bool>;
+
+    /// Create an effect which may be deferred to a later frame when the frame budget has been
+    /// used up. The first run happens immediately.
+    fn create_low_priority_effect<F: Send + Sync + 'static + FnMut(&mut Cx)>(&mut self, effect: F);
+}
+
+impl<'p, 'w> CreateFrameBudgetSignals for Cx<'p, 'w> {
+    fn create_building_signal(&mut self) -> Signal<bool> {
+        self.create_derived(|cx| cx.use_resource::<FrameBudget>().is_building())
+    }
+
+    fn create_low_priority_effect<F: Send + Sync + 'static + FnMut(&mut Cx)>(&mut self, effect: F) {
+        let entity = spawn_effect(self, effect);
+        self.world_mut().entity_mut(entity).insert(LowPriority);
+    }
+}
@@ -3,7 +3,9 @@ use bevy_mod_picking::{focus::HoverMap, pointer::PointerId};
 
 use crate::{signal::Signal, Cx, RunContextRead, RunContextSetup};
 
-/// Component which tracks whether the pointer is hovering over an entity.
+/// Component which tracks whether the pointer is hovering over an entity. This is true when
+/// the entity itself is under the pointer, as well as when one of its descendants is. An entity
+/// with no pickable descendants, such as an overlay shape, is only hovered directly.
 #[derive(Default, Component)]
 pub(crate) struct Hovering(pub bool);
 
@@ -18,9 +20,9 @@ pub(crate) fn update_hover_states(
     let hover_set = hover_map.get(&PointerId::Mouse);
     for (entity, mut hoverable) in hovers.iter_mut() {
         let is_hovering = match hover_set {
-            Some(map) => map
-                .iter()
-                .any(|(ha, _)| parent_query.iter_ancestors(*ha).any(|e| e == entity)),
+            Some(map) => map.iter().any(|(ha, _)| {
+                *ha == entity || parent_query.iter_ancestors(*ha).any(|e| e == entity)
+            }),
             None => false,
         };
         if hoverable.0 != is_hovering {
@@ -31,7 +33,8 @@ pub(crate) fn update_hover_states(
 
 /// Method to create a signal that tracks whether the mouse is hovering over the given entity.
 pub trait CreateHoverSignal {
-    /// Signal that returns true when the mouse is hovering over the given entity or a descendant.
+    /// Signal that returns true when the mouse is hovering over the given entity itself or any
+    /// of its descendants.
     fn create_hover_signal(&mut self, target: Entity) -> Signal<bool>;
 }
 
@@ -46,3 +49,51 @@ impl<'p, 'w> CreateHoverSignal for Cx<'p, 'w> {
         hovering
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy::{ecs::system::RunSystemOnce, utils::HashMap};
+    use bevy_mod_picking::backend::HitData;
+
+    use super::*;
+
+    /// Hover the given entities with the mouse, and return the hover states of `entities`.
+    fn hover<const N: usize>(
+        world: &mut World,
+        hovered: &[Entity],
+        entities: [Entity; N],
+    ) -> [bool; N] {
+        let map: HashMap<Entity, HitData> = hovered
+            .iter()
+            .map(|entity| (*entity, HitData::new(Entity::PLACEHOLDER, 0., None, None)))
+            .collect();
+        world
+            .resource_mut::<HoverMap>()
+            .insert(PointerId::Mouse, map);
+        world.run_system_once(update_hover_states);
+        entities.map(|entity| world.get::<Hovering>(entity).unwrap().0)
+    }
+
+    #[test]
+    fn test_hover_states() {
+        let mut world = World::default();
+        world.init_resource::<HoverMap>();
+        let child = world.spawn(Hovering(false)).id();
+        let parent = world.spawn(Hovering(false)).add_child(child).id();
+        let other = world.spawn(Hovering(false)).id();
+        let all = [parent, child, other];
+
+        // Hovering an entity directly counts, as does hovering a descendant.
+        assert_eq!(hover(&mut world, &[parent], all), [true, false, false]);
+        assert_eq!(hover(&mut world, &[child], all), [true, true, false]);
+        assert_eq!(hover(&mut world, &[other], all), [false, false, true]);
+        assert_eq!(hover(&mut world, &[], all), [false, false, false]);
+
+        // Without a mouse entry in the hover map, nothing is hovered.
+        hover(&mut world, &[child], all);
+        world.resource_mut::<HoverMap>().remove(&PointerId::Mouse);
+        world.run_system_once(update_hover_states);
+        assert!(!world.get::<Hovering>(parent).unwrap().0);
+        assert!(!world.get::<Hovering>(child).unwrap().0);
+    }
+}
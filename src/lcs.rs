@@ -24,9 +24,53 @@ where
     (arr1_offset, arr2_offset, longest)
 }
 
+/// Find a common subsequence of `prev` and `next`, built by repeatedly matching the longest
+/// common substring. Returns a flag for each entry of `next` which is true if the entry is
+/// part of the subsequence, meaning that it can stay where it is when transforming `prev`
+/// into `next`. Entries are assumed to be unique within each slice.
+pub fn common_subsequence<T: PartialEq>(prev: &[T], next: &[T]) -> Vec<bool> {
+    let mut stable = vec![false; next.len()];
+    mark_common(prev, next, &mut stable);
+    stable
+}
+
+fn mark_common<T: PartialEq>(prev: &[T], next: &[T], stable: &mut [bool]) {
+    // Trim the common prefix and suffix first, which is the common case and avoids building
+    // the comparison table for long lists.
+    let prefix = prev.iter().zip(next).take_while(|(a, b)| a == b).count();
+    let suffix = prev[prefix..]
+        .iter()
+        .rev()
+        .zip(next[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    stable[..prefix].fill(true);
+    stable[next.len() - suffix..].fill(true);
+
+    let prev = &prev[prefix..prev.len() - suffix];
+    let next_end = next.len() - suffix;
+    let (prev_start, next_start, length) = lcs(prev, &next[prefix..next_end], |a, b| a == b);
+    if length == 0 {
+        return;
+    }
+
+    let next_start = prefix + next_start;
+    stable[next_start..next_start + length].fill(true);
+    mark_common(
+        &prev[..prev_start],
+        &next[prefix..next_start],
+        &mut stable[prefix..next_start],
+    );
+    mark_common(
+        &prev[prev_start + length..],
+        &next[next_start + length..next_end],
+        &mut stable[next_start + length..next_end],
+    );
+}
+
 #[cfg(test)]
 mod tests {
-    use super::lcs;
+    use super::{common_subsequence, lcs};
 
     #[test]
     fn test_empty() {
@@ -115,4 +159,107 @@ mod tests {
         assert_eq!(start_b, 11);
         assert_eq!(length, 2);
     }
+
+    #[test]
+    fn test_common_subsequence_insert() {
+        let a = vec![1, 2, 3];
+        let b = vec![1, 4, 2, 3];
+        assert_eq!(common_subsequence(&a, &b), vec![true, false, true, true]);
+    }
+
+    #[test]
+    fn test_common_subsequence_remove() {
+        let a = vec![1, 2, 3, 4];
+        let b = vec![1, 3, 4];
+        assert_eq!(common_subsequence(&a, &b), vec![true, true, true]);
+    }
+
+    #[test]
+    fn test_common_subsequence_move() {
+        let a = vec![1, 2, 3, 4, 5];
+        let b = vec![2, 3, 4, 5, 1];
+        assert_eq!(
+            common_subsequence(&a, &b),
+            vec![true, true, true, true, false]
+        );
+    }
+
+    #[test]
+    fn test_common_subsequence_split() {
+        let a = vec![1, 2, 3, 4, 5, 6];
+        let b = vec![7, 1, 2, 8, 4, 5, 6, 3];
+        assert_eq!(
+            common_subsequence(&a, &b),
+            vec![false, true, true, false, true, true, true, false]
+        );
+    }
+
+    #[test]
+    fn test_common_subsequence_empty() {
+        let empty: Vec<i32> = vec![];
+        assert_eq!(common_subsequence(&empty, &empty), Vec::<bool>::new());
+        assert_eq!(common_subsequence(&[1, 2], &empty), Vec::<bool>::new());
+        assert_eq!(common_subsequence(&empty, &[1, 2]), vec![false, false]);
+    }
+
+    #[test]
+    fn test_common_subsequence_reverse() {
+        let a = vec![1, 2, 3];
+        let b = vec![3, 2, 1];
+        assert_eq!(common_subsequence(&a, &b).iter().filter(|s| **s).count(), 1);
+    }
+
+    /// Applies the flags the way `Element::attach_children` does: entries which are not
+    /// stable are removed from `prev`, then inserted at their index in `next`.
+    fn splice(prev: &[i32], next: &[i32]) -> Vec<i32> {
+        let stable = common_subsequence(prev, next);
+        let stable_entries: Vec<i32> = next
+            .iter()
+            .zip(&stable)
+            .filter_map(|(entry, stable)| stable.then_some(*entry))
+            .collect();
+        let mut result: Vec<i32> = prev
+            .iter()
+            .copied()
+            .filter(|entry| stable_entries.contains(entry))
+            .collect();
+        // The stable entries must keep their relative order from `prev`.
+        assert_eq!(result, stable_entries);
+        for (index, entry) in next.iter().enumerate() {
+            if !stable[index] {
+                result.insert(index, *entry);
+            }
+        }
+        result
+    }
+
+    #[test]
+    fn test_common_subsequence_splice() {
+        // Every reordering of four entries, plus removals and insertions.
+        let prev = [1, 2, 3, 4];
+        let mut permutations = vec![vec![]];
+        for _ in 0..prev.len() {
+            permutations = permutations
+                .into_iter()
+                .flat_map(|perm: Vec<i32>| {
+                    prev.iter()
+                        .filter(|entry| !perm.contains(entry))
+                        .map(|entry| {
+                            let mut next = perm.clone();
+                            next.push(*entry);
+                            next
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .collect();
+        }
+        assert_eq!(permutations.len(), 24);
+        for next in permutations {
+            assert_eq!(splice(&prev, &next), next);
+            assert_eq!(splice(&prev, &next[1..]), &next[1..]);
+            let mut inserted = next.clone();
+            inserted.insert(2, 5);
+            assert_eq!(splice(&prev, &inserted), inserted);
+        }
+    }
 }
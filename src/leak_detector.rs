@@ -0,0 +1,334 @@
+use std::{collections::VecDeque, fmt};
+
+use bevy::{prelude::*, utils::HashMap};
+
+#[cfg(feature = "ui")]
+use bevy::utils::HashSet;
+
+#[cfg(feature = "ui")]
+use crate::{mutable::PromotedMutable, tracking_scope::TrackingScope, View};
+
+/// Plugin which checks that razing a view despawns every entity the view created, and that
+/// nothing is despawned twice. Problems are logged as warnings and collected in the
+/// [`LeakDetector`] resource.
+///
+/// This adds work to every raze, so it is meant for tests and development builds. To change
+/// how many despawned entities are remembered, insert a [`LeakDetector`] created with
+/// [`LeakDetector::with_capacity`] before adding the plugin.
+pub struct ReactorLeakDetector;
+
+impl Plugin for ReactorLeakDetector {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LeakDetector>();
+    }
+}
+
+/// A problem found by the [`LeakDetector`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum LeakReport {
+    /// Entities created by a view or tracking scope which still exist after it was razed.
+    Leaked {
+        /// Name of the view or scope which was razed.
+        owner: String,
+        /// The entities which were not despawned.
+        entities: Vec<Entity>,
+    },
+
+    /// An entity which was razed or despawned more than once.
+    DoubleDespawn {
+        /// Name of the view or scope which first despawned the entity.
+        owner: String,
+        /// The entity which was despawned again.
+        entity: Entity,
+    },
+}
+
+impl fmt::Display for LeakReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LeakReport::Leaked { owner, entities } => {
+                write!(
+                    f,
+                    "{} leaked {} entities: {:?}",
+                    owner,
+                    entities.len(),
+                    entities
+                )
+            }
+            LeakReport::DoubleDespawn { owner, entity } => {
+                write!(f, "{:?} owned by {} was despawned twice", entity, owner)
+            }
+        }
+    }
+}
+
+/// Resource which records the problems found by [`ReactorLeakDetector`]. It is only present
+/// when the plugin has been added.
+#[derive(Resource)]
+pub struct LeakDetector {
+    /// Entities which have already been despawned, with the name of their owner. Entity ids
+    /// include a generation, so recycled ids are not mistaken for these.
+    despawned: HashMap<Entity, String>,
+
+    /// Entities in `despawned`, oldest first.
+    order: VecDeque<Entity>,
+
+    /// The number of despawned entities to remember. Once there are more, the oldest are
+    /// forgotten, and despawning them again is no longer reported.
+    capacity: usize,
+
+    /// Problems found so far.
+    reports: Vec<LeakReport>,
+}
+
+impl Default for LeakDetector {
+    fn default() -> Self {
+        Self::with_capacity(Self::DEFAULT_CAPACITY)
+    }
+}
+
+impl LeakDetector {
+    /// The number of despawned entities remembered by default.
+    pub const DEFAULT_CAPACITY: usize = 16384;
+
+    /// Construct a leak detector which remembers up to `capacity` despawned entities.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            despawned: HashMap::default(),
+            order: VecDeque::new(),
+            capacity,
+            reports: Vec::new(),
+        }
+    }
+
+    /// The problems found so far.
+    pub fn reports(&self) -> &[LeakReport] {
+        &self.reports
+    }
+
+    /// Take the problems found so far, leaving the list empty.
+    pub fn take_reports(&mut self) -> Vec<LeakReport> {
+        std::mem::take(&mut self.reports)
+    }
+
+    fn report(&mut self, report: LeakReport) {
+        warn!("{}", report);
+        self.reports.push(report);
+    }
+
+    /// Remember that an entity has been despawned, forgetting the oldest entities if there
+    /// are too many.
+    fn remember(&mut self, entity: Entity, owner: String) {
+        if self.despawned.contains_key(&entity) {
+            return;
+        }
+        self.despawned.insert(entity, owner);
+        self.order.push_back(entity);
+        while self.order.len() > self.capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.despawned.remove(&oldest);
+            }
+        }
+    }
+}
+
+/// Returns the name used to identify an entity in reports.
+fn owner_name(world: &World, entity: Entity) -> String {
+    match world.get::<Name>(entity) {
+        Some(name) => format!("{} ({:?})", name, entity),
+        None => format!("{:?}", entity),
+    }
+}
+
+/// Record that a tracking scope is about to be despawned. Returns false if the scope has
+/// already been despawned, in which case a report is made and the caller should do nothing.
+/// Always returns true when the leak detector is not enabled.
+pub(crate) fn record_despawn(world: &mut World, entity: Entity) -> bool {
+    if !world.contains_resource::<LeakDetector>() {
+        return true;
+    }
+    let name = owner_name(world, entity);
+    let mut detector = world.resource_mut::<LeakDetector>();
+    if let Some(owner) = detector.despawned.get(&entity).cloned() {
+        detector.report(LeakReport::DoubleDespawn { owner, entity });
+        return false;
+    }
+    detector.remember(entity, name);
+    true
+}
+
+/// Raze a view, checking that everything it created has been despawned afterwards.
+#[cfg(feature = "ui")]
+pub(crate) fn raze_view<V: View + ?Sized>(view: &mut V, view_entity: Entity, world: &mut World) {
+    if !world.contains_resource::<LeakDetector>() {
+        view.raze(view_entity, world);
+        return;
+    }
+
+    if let Some(owner) = world
+        .resource::<LeakDetector>()
+        .despawned
+        .get(&view_entity)
+        .cloned()
+    {
+        world
+            .resource_mut::<LeakDetector>()
+            .report(LeakReport::DoubleDespawn {
+                owner,
+                entity: view_entity,
+            });
+        return;
+    }
+
+    let name = owner_name(world, view_entity);
+    let mut created = HashSet::new();
+    collect_created(world, view_entity, &mut created);
+    let mut nodes = Vec::new();
+    view.nodes().flatten(&mut nodes);
+    for node in nodes {
+        collect_created(world, node, &mut created);
+    }
+
+    view.raze(view_entity, world);
+
+    let mut leaked: Vec<Entity> = created
+        .iter()
+        .copied()
+        .filter(|entity| world.get_entity(*entity).is_some())
+        .collect();
+    let mut detector = world.resource_mut::<LeakDetector>();
+    for entity in created {
+        detector.remember(entity, name.clone());
+    }
+    if !leaked.is_empty() {
+        leaked.sort();
+        detector.report(LeakReport::Leaked {
+            owner: name,
+            entities: leaked,
+        });
+    }
+}
+
+/// Collect an entity, its descendants and the entities owned by its tracking scope.
+#[cfg(feature = "ui")]
+fn collect_created(world: &World, entity: Entity, out: &mut HashSet<Entity>) {
+    let Some(entt) = world.get_entity(entity) else {
+        return;
+    };
+    // Promoted mutables are meant to outlive their owner.
+    if entt.contains::<PromotedMutable>() || !out.insert(entity) {
+        return;
+    }
+    if let Some(children) = entt.get::<Children>() {
+        for child in children.iter() {
+            collect_created(world, *child, out);
+        }
+    }
+    if let Some(scope) = entt.get::<TrackingScope>() {
+        for owned in scope.owned.iter() {
+            collect_created(world, *owned, out);
+        }
+    }
+}
+
+#[cfg(all(test, feature = "ui"))]
+mod tests {
+    use super::*;
+    use crate::{node_span::NodeSpan, DespawnScopes};
+
+    /// A view which spawns a display node, and optionally forgets to despawn it.
+    struct NodeView {
+        node: Option<Entity>,
+        leak: bool,
+    }
+
+    impl View for NodeView {
+        fn nodes(&self) -> NodeSpan {
+            match self.node {
+                Some(node) => NodeSpan::Node(node),
+                None => NodeSpan::Empty,
+            }
+        }
+
+        fn build(&mut self, view_entity: Entity, world: &mut World) {
+            world
+                .entity_mut(view_entity)
+                .insert(TrackingScope::new(world.change_tick()));
+            self.node = Some(world.spawn(Name::new("Node")).id());
+        }
+
+        fn raze(&mut self, view_entity: Entity, world: &mut World) {
+            if let Some(node) = self.node.take() {
+                if !self.leak {
+                    world.entity_mut(node).despawn();
+                }
+            }
+            world.despawn_owned_recursive(view_entity);
+        }
+    }
+
+    fn build_view(world: &mut World, leak: bool) -> (NodeView, Entity) {
+        let mut view = NodeView { node: None, leak };
+        let view_entity = world.spawn(Name::new("NodeView")).id();
+        view.build(view_entity, world);
+        (view, view_entity)
+    }
+
+    #[test]
+    fn test_no_leaks() {
+        let mut world = World::default();
+        world.init_resource::<LeakDetector>();
+        let (mut view, view_entity) = build_view(&mut world, false);
+        raze_view(&mut view, view_entity, &mut world);
+        assert!(world.resource::<LeakDetector>().reports().is_empty());
+    }
+
+    #[test]
+    fn test_leaked_node() {
+        let mut world = World::default();
+        world.init_resource::<LeakDetector>();
+        let (mut view, view_entity) = build_view(&mut world, true);
+        let node = view.node.unwrap();
+        raze_view(&mut view, view_entity, &mut world);
+        assert_eq!(
+            world.resource_mut::<LeakDetector>().take_reports(),
+            vec![LeakReport::Leaked {
+                owner: format!("NodeView ({:?})", view_entity),
+                entities: vec![node],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_double_despawn() {
+        let mut world = World::default();
+        world.init_resource::<LeakDetector>();
+        let (mut view, view_entity) = build_view(&mut world, false);
+        raze_view(&mut view, view_entity, &mut world);
+        raze_view(&mut view, view_entity, &mut world);
+        world.despawn_owned_recursive(view_entity);
+        let reports = world.resource_mut::<LeakDetector>().take_reports();
+        assert_eq!(reports.len(), 2);
+        assert!(reports
+            .iter()
+            .all(|report| matches!(report, LeakReport::DoubleDespawn { entity, .. } if *entity == view_entity)));
+    }
+
+    #[test]
+    fn test_capacity() {
+        let mut world = World::default();
+        world.insert_resource(LeakDetector::with_capacity(2));
+        let scopes: Vec<Entity> = (0..3)
+            .map(|_| world.spawn(TrackingScope::new(world.change_tick())).id())
+            .collect();
+        for scope in scopes.iter() {
+            world.despawn_owned_recursive(*scope);
+        }
+        assert_eq!(world.resource::<LeakDetector>().despawned.len(), 2);
+
+        // The oldest entity has been forgotten, the newer ones have not.
+        assert!(record_despawn(&mut world, scopes[0]));
+        assert!(!record_despawn(&mut world, scopes[2]));
+        assert_eq!(world.resource::<LeakDetector>().reports().len(), 1);
+    }
+}
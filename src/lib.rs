@@ -3,36 +3,97 @@
 #![warn(missing_docs)]
 
 mod callback;
-mod compositor;
-mod cond;
 mod cx;
 mod derived;
+mod derived_family;
+#[cfg(feature = "remote_devtools")]
+pub mod devtools;
+mod event_signal;
+mod frame_budget;
+mod leak_detector;
+mod mutable;
+mod mutable_store;
+mod origin;
+#[cfg(feature = "persistence")]
+pub mod persistence;
+mod plugin;
+mod preferences;
+mod reaction;
+mod signal;
+mod test_id;
+mod timer;
+mod tracking_scope;
+mod versioned;
+mod world_signals;
+
+// Views, styles and UI widgets. These are not needed for headless builds which only use the
+// reactive core.
+#[cfg(feature = "ui")]
+mod compositor;
+#[cfg(feature = "ui")]
+mod compositor_picking;
+#[cfg(feature = "ui")]
+mod cond;
+#[cfg(feature = "ui")]
 mod dynamic;
+#[cfg(feature = "ui")]
 mod dynamic_keyed;
+#[cfg(feature = "ui")]
 mod effect_target;
+#[cfg(feature = "ui")]
 mod element;
+#[cfg(feature = "ui")]
+mod element_rect;
+#[cfg(feature = "ui")]
+mod error_boundary;
+#[cfg(feature = "ui")]
 mod r#for;
+#[cfg(feature = "ui")]
 mod for_each;
+#[cfg(feature = "ui")]
 mod for_index;
+#[cfg(feature = "ui")]
 mod fragment;
+#[cfg(feature = "ui")]
 mod hover;
+#[cfg(feature = "ui")]
 mod lcs;
-mod mutable;
+#[cfg(feature = "ui")]
+mod material_param;
+#[cfg(feature = "ui")]
+mod measure;
+#[cfg(feature = "ui")]
+mod mirror;
+#[cfg(feature = "ui")]
 mod node_span;
+#[cfg(feature = "ui")]
+mod opacity;
+#[cfg(feature = "ui")]
 mod parent_view;
-mod plugin;
+#[cfg(feature = "ui")]
 mod portal;
-mod reaction;
-mod signal;
+#[cfg(feature = "ui")]
+mod router;
+#[cfg(feature = "ui")]
 mod style;
+#[cfg(feature = "ui")]
+mod suspense;
+#[cfg(feature = "ui")]
+mod switch;
+#[cfg(feature = "ui")]
 mod text;
-mod tracking_scope;
+#[cfg(feature = "ui")]
+mod ui_template;
+#[cfg(feature = "ui")]
 mod view;
+#[cfg(feature = "ui")]
+mod window_size;
+#[cfg(feature = "ui")]
+mod zoom;
 
 pub use callback::CallDeferred;
 pub use callback::Callback;
-pub use compositor::Compositor;
-pub use cond::Cond;
+pub use callback::CommandsRunCallback;
 pub use cx::Cx;
 pub use cx::Rcx;
 pub use cx::RunContextRead;
@@ -40,44 +101,78 @@ pub use cx::RunContextSetup;
 pub use cx::RunContextWrite;
 pub use derived::Derived;
 pub use derived::ReadDerived;
-pub use dynamic::Dynamic;
-pub use dynamic_keyed::DynamicKeyed;
-pub use effect_target::EffectTarget;
-pub use effect_target::EntityEffect;
-pub use element::Element;
-pub use for_each::ForEach;
-pub use for_index::ForIndex;
-pub use fragment::Fragment;
-pub use hover::CreateHoverSignal;
+pub use derived_family::DerivedFamily;
+pub use event_signal::CreateEventSignal;
+pub use frame_budget::CreateFrameBudgetSignals;
+pub use frame_budget::FrameBudget;
+pub use frame_budget::LowPriority;
+pub use leak_detector::LeakDetector;
+pub use leak_detector::LeakReport;
+pub use leak_detector::ReactorLeakDetector;
 pub use mutable::Mutable;
+pub use mutable::OwnedMutable;
 pub use mutable::ReadMutable;
 pub use mutable::WriteMutable;
-pub use node_span::NodeSpan;
-pub use parent_view::ChildArray;
-pub use parent_view::ChildView;
-pub use parent_view::ChildViewTuple;
-pub use parent_view::ParentView;
+pub use mutable_store::MutableStorage;
+pub use mutable_store::MutableStore;
+pub use plugin::update_reactor;
 pub use plugin::ReactorPlugin;
-pub use portal::Portal;
-pub use r#for::For;
+pub use preferences::AccessibilityPreferences;
+pub use preferences::CreatePreferenceSignals;
 pub use reaction::*;
 pub use signal::IntoSignal;
 pub use signal::Signal;
-pub use style::StyleBuilder;
-pub use style::StyleBuilderBackground;
-pub use style::StyleBuilderBorderColor;
-pub use style::StyleBuilderBorderRadius;
-pub use style::StyleBuilderFont;
-pub use style::StyleBuilderLayout;
-pub use style::StyleBuilderOutline;
-pub use style::StyleBuilderPointerEvents;
-pub use style::StyleBuilderZIndex;
-pub use style::StyleHandle;
-pub use style::StyleTuple;
-pub use style::WithStyles;
-pub use text::*;
+pub use test_id::TestId;
+pub use test_id::TestIdEvent;
+pub use test_id::TestIdPlugin;
+pub use test_id::TestIdRegistry;
+pub use timer::CreateTimerSignal;
 pub use tracking_scope::DespawnScopes;
 pub use tracking_scope::TrackingScope;
 pub use tracking_scope::TrackingScopeTracing;
-pub use view::*;
+pub use versioned::Versioned;
+pub use world_signals::ReactiveWorld;
+
+#[cfg(feature = "ui")]
+pub use {
+    bevy_reactor_macros::view,
+    compositor::{Compositor, CompositorSize, CompositorTexture},
+    compositor_picking::{CompositorPickingPlugin, CompositorQuad},
+    cond::Cond,
+    dynamic::Dynamic,
+    dynamic_keyed::DynamicKeyed,
+    effect_target::{EffectTarget, EntityEffect},
+    element::{Element, Overlay, Underlay},
+    element_rect::UseElementRect,
+    error_boundary::ErrorBoundary,
+    for_each::ForEach,
+    for_index::ForIndex,
+    fragment::Fragment,
+    hover::CreateHoverSignal,
+    material_param::WithMaterialParams,
+    measure::Measure,
+    mirror::{CreateMirror, MirrorPlugin, Mirrored},
+    node_span::{NodeAnchor, NodeSpan},
+    opacity::{Opacity, WithOpacity},
+    parent_view::{ChildArray, ChildView, ChildViewTuple, ParentView},
+    portal::{Portal, PortalLayers},
+    r#for::For,
+    router::{CreateRouter, Router, Routes},
+    style::{
+        BoxShadow, Breakpoint, Breakpoints, CreateBreakpointSignal, CreateTruncatedSignal, Easing,
+        HitShape, HoverCursor, NamedStyles, PartStyles, StyleBuilder, StyleBuilderBackground,
+        StyleBuilderBorderColor, StyleBuilderBorderRadius, StyleBuilderBoxShadow,
+        StyleBuilderCursor, StyleBuilderFont, StyleBuilderLayout, StyleBuilderOutline,
+        StyleBuilderPointerEvents, StyleBuilderTextOverflow, StyleBuilderTransition,
+        StyleBuilderZIndex, StyleHandle, StyleRef, StyleTransitions, StyleTuple, TextOverflow,
+        TransitionProperty, WithBreakpointStyles, WithSlicedImage, WithStyles,
+    },
+    suspense::Suspense,
+    switch::Switch,
+    text::*,
+    ui_template::{RegisterUiTemplate, UiTemplate, UiTemplateRegistry},
+    view::*,
+    window_size::{CreateWindowSizeSignal, WindowSize},
+    zoom::{WithZoom, Zoom},
+};
 // pub use style::StyleBuilderTextureAtlas;
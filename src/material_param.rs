@@ -0,0 +1,89 @@
+use std::sync::Arc;
+
+use bevy::{prelude::*, ui::UiMaterial};
+
+use crate::{signal::IntoSignal, EffectTarget, Element};
+
+/// A function which writes to the material of an entity, given the elapsed time.
+type FrameUpdate = Arc<dyn Fn(&mut World, Entity, f32) + Send + Sync>;
+
+/// Component holding the material updates which run every frame for an element.
+#[derive(Component, Default)]
+pub(crate) struct MaterialFrameUpdates(Vec<FrameUpdate>);
+
+/// System which runs the per-frame material updates.
+pub(crate) fn update_material_frames(world: &mut World) {
+    let time = world.resource::<Time>().elapsed_seconds_wrapped();
+    let mut query = world.query::<(Entity, &MaterialFrameUpdates)>();
+    let updates: Vec<(Entity, FrameUpdate)> = query
+        .iter(world)
+        .flat_map(|(entity, updates)| updates.0.iter().map(move |update| (entity, update.clone())))
+        .collect();
+    for (entity, update) in updates {
+        update(world, entity, time);
+    }
+}
+
+/// Apply `update` to the material of `entity`, if it has one.
+fn update_material<M: UiMaterial>(world: &mut World, entity: Entity, update: impl FnOnce(&mut M)) {
+    let Some(handle) = world.get::<Handle<M>>(entity).cloned() else {
+        return;
+    };
+    if let Some(material) = world.resource_mut::<Assets<M>>().get_mut(handle.id()) {
+        update(material);
+    }
+}
+
+/// Trait to drive the uniforms of an element's [`UiMaterial`] from signals, so that animated
+/// materials don't each need their own system.
+pub trait WithMaterialParams<M: UiMaterial> {
+    /// Write a value into the element's material whenever it changes.
+    ///
+    /// # Arguments
+    /// * `field` - Function which stores the value in the material, usually by assigning a
+    ///   uniform.
+    /// * `value` - The value to write, which may be a signal.
+    fn material_param<T: Clone + Send + Sync + 'static>(
+        self,
+        field: impl Fn(&mut M, T) + Send + Sync + 'static,
+        value: impl IntoSignal<T>,
+    ) -> Self;
+
+    /// Update the element's material every frame, for time-based shaders. The function is
+    /// passed the elapsed time in seconds, wrapped in the same way as the shader's global time.
+    fn material_param_per_frame(self, field: impl Fn(&mut M, f32) + Send + Sync + 'static) -> Self;
+}
+
+impl<M: UiMaterial> WithMaterialParams<M> for Element<MaterialNodeBundle<M>> {
+    fn material_param<T: Clone + Send + Sync + 'static>(
+        self,
+        field: impl Fn(&mut M, T) + Send + Sync + 'static,
+        value: impl IntoSignal<T>,
+    ) -> Self {
+        let value = value.into_signal();
+        self.create_effect(move |cx, target| {
+            let value = value.get_clone(cx);
+            update_material(cx.world_mut(), target, |material: &mut M| {
+                field(material, value)
+            });
+        })
+    }
+
+    fn material_param_per_frame(self, field: impl Fn(&mut M, f32) + Send + Sync + 'static) -> Self {
+        let field = Arc::new(field);
+        // This effect has no dependencies, so it only runs once.
+        self.create_effect(move |cx, target| {
+            let field = field.clone();
+            let update: FrameUpdate = Arc::new(move |world, entity, time| {
+                update_material(world, entity, |material: &mut M| field(material, time));
+            });
+            let mut entt = cx.world_mut().entity_mut(target);
+            match entt.get_mut::<MaterialFrameUpdates>() {
+                Some(mut updates) => updates.0.push(update),
+                None => {
+                    entt.insert(MaterialFrameUpdates(vec![update]));
+                }
+            }
+        })
+    }
+}
@@ -0,0 +1,274 @@
+use bevy::prelude::*;
+
+use crate::{
+    node_span::NodeSpan, view::View, Callback, DisplayNodeChanged, IntoView, RunContextWrite,
+    ViewRef,
+};
+
+/// Hidden node under which the content of a [`Measure`] is laid out before it is placed.
+#[derive(Component)]
+pub(crate) struct MeasuringRoot {
+    view_entity: Entity,
+    on_measure: Option<Callback<Vec2>>,
+}
+
+/// Component inserted on the view entity of a [`Measure`] once its content has been laid out.
+#[derive(Component, Clone, Copy, Debug)]
+pub(crate) struct Measured(Vec2);
+
+/// A `Measure` lays out a view offscreen to find its intrinsic size before it is displayed.
+/// This is useful for popups and docked panels, which need to know the size of their content
+/// in order to choose where to place it.
+///
+/// The content is built immediately, but its display nodes are mounted under a hidden,
+/// absolutely-positioned measuring node rather than in the parent. Once layout has run, the
+/// logical size of the content is passed to the [`on_measure`](Measure::on_measure) callback,
+/// and the content is then either moved into the parent (see [`adopt`](Measure::adopt)) or
+/// discarded. Measurement happens once; the size is not updated if the content changes later.
+pub struct Measure {
+    view: ViewRef,
+    on_measure: Option<Callback<Vec2>>,
+    adopt: bool,
+    entity: Option<Entity>,
+    root: Option<Entity>,
+    adopted: bool,
+}
+
+impl Measure {
+    /// Construct a new `Measure` for the given content.
+    pub fn new(view: impl IntoView) -> Self {
+        Self {
+            view: view.into_view(),
+            on_measure: None,
+            adopt: false,
+            entity: None,
+            root: None,
+            adopted: false,
+        }
+    }
+
+    /// Set the callback which receives the size of the content, in logical pixels. The
+    /// callback is invoked once layout has run, in the same frame that the content is built.
+    pub fn on_measure(mut self, callback: Callback<Vec2>) -> Self {
+        self.on_measure = Some(callback);
+        self
+    }
+
+    /// If true, the content is moved into the parent once it has been measured, instead of
+    /// being discarded. The content is not rebuilt, so any state within it is kept.
+    pub fn adopt(mut self, adopt: bool) -> Self {
+        self.adopt = adopt;
+        self
+    }
+
+    /// Attach the display nodes of the content to the measuring node.
+    fn attach(&self, world: &mut World) {
+        let Some(root) = self.root else {
+            return;
+        };
+        let mut nodes = Vec::new();
+        self.view.nodes().flatten(&mut nodes);
+        world.entity_mut(root).replace_children(&nodes);
+    }
+
+    /// Despawn the measuring node, leaving its children in place.
+    fn despawn_root(&mut self, world: &mut World) {
+        if let Some(root) = self.root.take() {
+            if let Some(mut root) = world.get_entity_mut(root) {
+                root.clear_children();
+                root.despawn();
+            }
+        }
+    }
+}
+
+impl View for Measure {
+    fn nodes(&self) -> NodeSpan {
+        if self.adopted {
+            self.view.nodes()
+        } else {
+            NodeSpan::Empty
+        }
+    }
+
+    fn build(&mut self, view_entity: Entity, world: &mut World) {
+        assert!(self.entity.is_none());
+        world.entity_mut(view_entity).insert(Name::new("Measure"));
+        self.entity = Some(ViewRef::spawn(&self.view, view_entity, world));
+        self.root = Some(
+            world
+                .spawn((
+                    NodeBundle {
+                        style: Style {
+                            position_type: PositionType::Absolute,
+                            left: Val::Px(0.),
+                            top: Val::Px(0.),
+                            ..default()
+                        },
+                        visibility: Visibility::Hidden,
+                        ..default()
+                    },
+                    MeasuringRoot {
+                        view_entity,
+                        on_measure: self.on_measure,
+                    },
+                    Name::new("MeasuringRoot"),
+                ))
+                .id(),
+        );
+        self.attach(world);
+    }
+
+    fn raze(&mut self, view_entity: Entity, world: &mut World) {
+        if let Some(entity) = self.entity.take() {
+            self.view.raze(entity, world);
+        }
+        self.despawn_root(world);
+        world.despawn_owned_recursive(view_entity);
+    }
+
+    fn children_changed(&mut self, view_entity: Entity, world: &mut World) -> bool {
+        if self.adopted {
+            return false;
+        }
+        if !world.entity(view_entity).contains::<Measured>() {
+            self.attach(world);
+            return true;
+        }
+
+        // The content has been measured, so it can leave the measuring node.
+        if self.adopt {
+            self.despawn_root(world);
+            self.adopted = true;
+            false
+        } else {
+            if let Some(entity) = self.entity.take() {
+                self.view.raze(entity, world);
+            }
+            self.despawn_root(world);
+            true
+        }
+    }
+}
+
+impl IntoView for Measure {
+    fn into_view(self) -> ViewRef {
+        ViewRef::new(self)
+    }
+}
+
+/// Records the size of each measuring node after layout, and notifies its [`Measure`] view
+/// so that the content can be adopted or discarded.
+pub(crate) fn update_measurements(world: &mut World) {
+    let mut query = world.query::<(&MeasuringRoot, &Node)>();
+    let measured = query
+        .iter(world)
+        .filter(|(root, _)| {
+            world
+                .get_entity(root.view_entity)
+                .is_some_and(|e| !e.contains::<Measured>())
+        })
+        .map(|(root, node)| (root.view_entity, root.on_measure, node.size()))
+        .collect::<Vec<_>>();
+    for (view_entity, on_measure, size) in measured {
+        let Some(mut entt) = world.get_entity_mut(view_entity) else {
+            continue;
+        };
+        entt.insert((Measured(size), DisplayNodeChanged));
+        if let Some(callback) = on_measure {
+            world.run_callback(callback, size);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        view::attach_child_views, Cx, Element, Mutable, ParentView, RunContextSetup, TrackingScope,
+    };
+
+    use super::*;
+
+    struct Fixture {
+        display: Entity,
+        content: Entity,
+        size: Mutable<Option<Vec2>>,
+    }
+
+    fn build(world: &mut World, adopt: bool) -> Fixture {
+        let owner = world.spawn_empty().id();
+        let mut scope = TrackingScope::new(world.change_tick());
+        let mut cx = Cx::new(world, owner, &mut scope);
+        let size = cx.create_mutable::<Option<Vec2>>(None);
+        let on_measure = cx.create_callback(move |cx: &mut Cx, measured: Vec2| {
+            size.set(cx, Some(measured));
+        });
+        world.entity_mut(owner).insert(scope);
+
+        let content = world.spawn_empty().id();
+        let view = Element::<NodeBundle>::new()
+            .children(
+                Measure::new(Element::<NodeBundle>::for_entity(content))
+                    .on_measure(on_measure)
+                    .adopt(adopt),
+            )
+            .into_view();
+        ViewRef::spawn(&view, owner, world);
+        let NodeSpan::Node(display) = view.nodes() else {
+            panic!("element should have a display node");
+        };
+        Fixture {
+            display,
+            content,
+            size,
+        }
+    }
+
+    #[test]
+    fn test_measure_adopt() {
+        let mut world = World::default();
+        let fixture = build(&mut world, true);
+
+        // Before layout, the content is under the measuring node rather than the parent.
+        let root = world.get::<Parent>(fixture.content).unwrap().get();
+        assert!(world.get::<MeasuringRoot>(root).is_some());
+        assert!(world.get::<Children>(fixture.display).is_none());
+
+        update_measurements(&mut world);
+        world.flush_commands();
+        assert_eq!(fixture.size.get(&world), Some(Vec2::ZERO));
+
+        // The measured content moves into the parent, and the measuring node goes away.
+        attach_child_views(&mut world);
+        assert_eq!(
+            world.get::<Children>(fixture.display).unwrap().to_vec(),
+            vec![fixture.content]
+        );
+        assert!(world.get_entity(root).is_none());
+
+        // Measurement only happens once.
+        fixture.size.set(&mut world, None);
+        update_measurements(&mut world);
+        world.flush_commands();
+        assert_eq!(fixture.size.get(&world), None);
+    }
+
+    #[test]
+    fn test_measure_discard() {
+        let mut world = World::default();
+        let fixture = build(&mut world, false);
+        let root = world.get::<Parent>(fixture.content).unwrap().get();
+
+        update_measurements(&mut world);
+        world.flush_commands();
+        assert_eq!(fixture.size.get(&world), Some(Vec2::ZERO));
+
+        // Without adoption, the content is razed once it has been measured.
+        attach_child_views(&mut world);
+        assert!(world.get_entity(fixture.content).is_none());
+        assert!(world.get_entity(root).is_none());
+        assert!(world
+            .get::<Children>(fixture.display)
+            .map_or(true, |children| children.is_empty()));
+    }
+}
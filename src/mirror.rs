@@ -0,0 +1,98 @@
+use std::marker::PhantomData;
+
+use bevy::{
+    app::{AppLabel, InternedAppLabel},
+    prelude::*,
+    render::{Extract, ExtractSchedule, RenderApp},
+};
+
+use crate::{signal::Signal, Cx, RunContextSetup};
+
+/// Resource which holds the current value of a mirrored signal. In the main world, this is
+/// written whenever the mirrored signal changes; in the render world (or other sub-app) it is
+/// a copy which is updated by [`MirrorPlugin`] during extraction.
+#[derive(Resource, Clone, Debug, Default)]
+pub struct Mirrored<T>(pub T);
+
+/// Plugin which copies the [`Mirrored<T>`] resource from the main world into a sub-app
+/// (by default, the render world) during extraction. The value is only copied on frames
+/// where it has changed.
+pub struct MirrorPlugin<T> {
+    app_label: InternedAppLabel,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> MirrorPlugin<T> {
+    /// Construct a plugin which mirrors values into the render world.
+    pub fn new() -> Self {
+        Self {
+            app_label: RenderApp.intern(),
+            marker: PhantomData,
+        }
+    }
+
+    /// Mirror values into the given sub-app instead of the render world. The sub-app must run
+    /// [`ExtractSchedule`] during its extract step.
+    pub fn sub_app(mut self, label: impl AppLabel) -> Self {
+        self.app_label = label.intern();
+        self
+    }
+}
+
+impl<T> Default for MirrorPlugin<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone + Send + Sync + 'static> Plugin for MirrorPlugin<T> {
+    fn build(&self, app: &mut App) {
+        match app.get_sub_app_mut(self.app_label) {
+            Some(sub_app) => {
+                sub_app.add_systems(ExtractSchedule, extract_mirrored::<T>);
+            }
+            None => warn!("MirrorPlugin: sub-app {:?} not found", self.app_label),
+        }
+    }
+}
+
+fn extract_mirrored<T: Clone + Send + Sync + 'static>(
+    mut commands: Commands,
+    source: Extract<Option<Res<Mirrored<T>>>>,
+    target: Option<Res<Mirrored<T>>>,
+) {
+    match source.as_ref() {
+        Some(source) if source.is_changed() || target.is_none() => {
+            commands.insert_resource(Mirrored(source.0.clone()));
+        }
+        None if target.is_some() => commands.remove_resource::<Mirrored<T>>(),
+        _ => {}
+    }
+}
+
+/// Method to mirror a signal into a resource, so that it can be read by non-reactive code
+/// such as render systems.
+pub trait CreateMirror {
+    /// Create an effect which writes the value of `signal` to the [`Mirrored<T>`] resource
+    /// whenever it changes. Add a [`MirrorPlugin<T>`] to make the value available in the
+    /// render world.
+    fn create_mirror<T: PartialEq + Clone + Send + Sync + 'static>(&mut self, signal: Signal<T>);
+}
+
+impl<'p, 'w> CreateMirror for Cx<'p, 'w> {
+    fn create_mirror<T: PartialEq + Clone + Send + Sync + 'static>(&mut self, signal: Signal<T>) {
+        self.create_effect(move |cx| {
+            let value = signal.get_clone(cx);
+            let world = cx.world_mut();
+            match world.get_resource_mut::<Mirrored<T>>() {
+                Some(mut mirrored) => {
+                    // Avoid triggering change detection if the value is the same.
+                    if mirrored.0 != value {
+                        mirrored.0 = value;
+                    }
+                }
+                None => world.insert_resource(Mirrored(value)),
+            }
+        });
+    }
+}
@@ -1,17 +1,50 @@
-use crate::{signal::Signal, RunContextWrite};
+use std::sync::{
+    mpsc::{channel, Receiver, Sender},
+    Mutex,
+};
+
+use crate::{
+    mutable_store::{MutableStorage, MutableStore, SlotId},
+    origin::HandleOrigin,
+    signal::Signal,
+    tracking_scope::TrackingScope,
+    world_signals::world_signal_owner,
+    DespawnScopes, RunContextSetup, RunContextWrite,
+};
 use bevy::{ecs::component::ComponentId, ecs::world::Command, prelude::*};
 
 /// Contains a mutable reactive value.
 #[derive(Component)]
 pub(crate) struct MutableCell<T>(pub(crate) T);
 
+/// Marks a mutable cell which has been promoted to the world, so that it is skipped when its
+/// original owner is despawned.
+#[derive(Component)]
+pub(crate) struct PromotedMutable;
+
+/// Equality function for a [`Mutable`] which replaces `PartialEq` when deciding whether a
+/// write changes the value. Only mutables held in an entity can have one.
+#[derive(Component)]
+pub(crate) struct MutableEquality<T>(pub(crate) fn(&T, &T) -> bool);
+
+/// Identifies where the value of a [`Mutable`] is held.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub(crate) enum MutableKey {
+    /// A [`MutableCell`] component on an entity, along with the component id of the cell.
+    Entity(Entity, ComponentId),
+
+    /// A slot in the [`MutableStore`].
+    Slot(SlotId),
+}
+
 /// Contains a reference to a reactive mutable variable.
 #[derive(PartialEq, Debug)]
 pub struct Mutable<T> {
-    /// The entity that holds the mutable value.
-    pub(crate) cell: Entity,
-    /// The component id for the mutable cell.
-    pub(crate) component: ComponentId,
+    /// Where the mutable value is held.
+    pub(crate) key: MutableKey,
+
+    /// The world and location where this mutable was created.
+    pub(crate) origin: HandleOrigin,
 
     /// Marker
     pub(crate) marker: std::marker::PhantomData<T>,
@@ -19,8 +52,188 @@ pub struct Mutable<T> {
 
 impl<T> Mutable<T> {
     /// The entity that holds the mutable value.
+    ///
+    /// # Panics
+    /// Panics if the mutable was created with [`MutableStorage::Store`], in which case it is
+    /// not held in an entity. Use [`storage`](Self::storage) to tell the two apart.
     pub fn id(&self) -> Entity {
-        self.cell
+        match self.key {
+            MutableKey::Entity(cell, _) => cell,
+            MutableKey::Slot(_) => panic!("Mutable is held in the MutableStore, not in an entity"),
+        }
+    }
+
+    /// Where the value of this mutable is held: in its own entity, or in the [`MutableStore`].
+    pub fn storage(&self) -> MutableStorage {
+        match self.key {
+            MutableKey::Entity(..) => MutableStorage::Entities,
+            MutableKey::Slot(_) => MutableStorage::Store,
+        }
+    }
+}
+
+impl<T> Mutable<T>
+where
+    T: Send + Sync + 'static,
+{
+    /// Returns true if the value of this [`Mutable`] still exists. Mutables are despawned
+    /// along with the tracking scope which created them, unless they have been promoted
+    /// with [`promote_to_world`](Self::promote_to_world).
+    pub fn is_alive(&self, world: &World) -> bool {
+        match self.key {
+            MutableKey::Entity(cell, _) => world.get::<MutableCell<T>>(cell).is_some(),
+            MutableKey::Slot(slot) => world
+                .get_resource::<MutableStore>()
+                .and_then(|store| store.get::<T>(slot))
+                .is_some(),
+        }
+    }
+
+    /// Detach this [`Mutable`] from the tracking scope which created it, so that it lives
+    /// until the world is dropped. Use this for mutables which are stored in resources or
+    /// otherwise outlive the view which created them.
+    pub fn promote_to_world(&self, world: &mut World) {
+        self.origin.check(world, "Mutable");
+        if !self.is_alive(world) {
+            self.origin.dead("Mutable");
+            return;
+        }
+        match self.key {
+            MutableKey::Entity(cell, _) => {
+                let owner = world_signal_owner(world);
+                world
+                    .entity_mut(cell)
+                    .insert(PromotedMutable)
+                    .set_parent(owner);
+            }
+            MutableKey::Slot(slot) => {
+                world.resource_mut::<MutableStore>().detach(slot);
+            }
+        }
+    }
+}
+
+/// A [`Mutable`] along with the entity whose tracking scope owns it, returned by
+/// [`create_owned_mutable`]. This is a guard: the mutable is despawned when its owner is, or
+/// when the guard is dropped, whichever comes first, unless it is detached with
+/// [`promote_to_world`](Self::promote_to_world). Mutables released by dropping the guard are
+/// despawned the next time reactions are run. Dereferences to the [`Mutable`].
+///
+/// [`create_owned_mutable`]: crate::RunContextSetup::create_owned_mutable
+#[derive(Debug)]
+pub struct OwnedMutable<T> {
+    pub(crate) mutable: Mutable<T>,
+    pub(crate) owner: Entity,
+    pub(crate) release: Option<Sender<(MutableKey, Entity)>>,
+}
+
+impl<T> OwnedMutable<T> {
+    /// The entity whose tracking scope owns the mutable.
+    pub fn owner(&self) -> Entity {
+        self.owner
+    }
+
+    /// The mutable itself. The returned handle doesn't keep the mutable alive.
+    pub fn mutable(&self) -> Mutable<T> {
+        self.mutable
+    }
+}
+
+impl<T> OwnedMutable<T>
+where
+    T: Send + Sync + 'static,
+{
+    /// Returns true if the owner and the value of the mutable still exist.
+    pub fn is_alive(&self, world: &World) -> bool {
+        world.get_entity(self.owner).is_some() && self.mutable.is_alive(world)
+    }
+
+    /// Detach the mutable from its owner, so that it lives until the world is dropped, and
+    /// return it.
+    pub fn promote_to_world(mut self, world: &mut World) -> Mutable<T> {
+        self.release = None;
+        self.mutable.promote_to_world(world);
+        self.mutable
+    }
+}
+
+impl<T> PartialEq for OwnedMutable<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.mutable == other.mutable && self.owner == other.owner
+    }
+}
+
+impl<T> Drop for OwnedMutable<T> {
+    fn drop(&mut self) {
+        if let Some(release) = self.release.take() {
+            // The receiver lives as long as the world, so this only fails during teardown.
+            let _ = release.send((self.mutable.key, self.owner));
+        }
+    }
+}
+
+impl<T> std::ops::Deref for OwnedMutable<T> {
+    type Target = Mutable<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.mutable
+    }
+}
+
+/// Resource which receives the mutables released by dropped [`OwnedMutable`] guards.
+#[derive(Resource)]
+pub(crate) struct ReleasedMutables {
+    sender: Sender<(MutableKey, Entity)>,
+    receiver: Mutex<Receiver<(MutableKey, Entity)>>,
+}
+
+impl Default for ReleasedMutables {
+    fn default() -> Self {
+        let (sender, receiver) = channel();
+        Self {
+            sender,
+            receiver: Mutex::new(receiver),
+        }
+    }
+}
+
+impl ReleasedMutables {
+    /// A sender for a new [`OwnedMutable`] guard.
+    pub(crate) fn sender(world: &mut World) -> Sender<(MutableKey, Entity)> {
+        world
+            .get_resource_or_insert_with(ReleasedMutables::default)
+            .sender
+            .clone()
+    }
+}
+
+/// Despawn the mutables whose [`OwnedMutable`] guards have been dropped.
+pub(crate) fn despawn_released_mutables(world: &mut World) {
+    let Some(released) = world.get_resource::<ReleasedMutables>() else {
+        return;
+    };
+    let released: Vec<_> = released.receiver.lock().unwrap().try_iter().collect();
+    for (key, owner) in released {
+        match key {
+            MutableKey::Entity(cell, _) => {
+                if world.get_entity(cell).is_none() || world.get::<PromotedMutable>(cell).is_some()
+                {
+                    continue;
+                }
+                // The cell has to be removed from its owner's scope, so that it isn't
+                // despawned again along with the owner.
+                let Some(mut scope) = world.get_mut::<TrackingScope>(owner) else {
+                    continue;
+                };
+                scope.owned.retain(|owned| *owned != cell);
+                world.despawn_owned_recursive(cell);
+            }
+            MutableKey::Slot(slot) => {
+                if let Some(mut store) = world.get_resource_mut::<MutableStore>() {
+                    store.remove(slot);
+                }
+            }
+        }
     }
 }
 
@@ -33,21 +246,79 @@ impl<T> Clone for Mutable<T> {
 
 impl<T> Mutable<T>
 where
-    T: PartialEq + Send + Sync + 'static,
+    T: Send + Sync + 'static,
 {
-    /// Update a mutable value in place using a callback. The callback is passed a
-    /// `Mut<T>` which can be used to modify the value.
+    /// Modify the value of this [`Mutable`] in place, without replacing or cloning it. The
+    /// callback is passed a `Mut<T>`; the value is only marked as changed, and dependents
+    /// notified, if the callback dereferences it mutably. Unlike [`set`](Self::set), the
+    /// modification is applied immediately rather than when commands are flushed. To defer
+    /// it in the same way as writes, use [`update_in_place`](Self::update_in_place).
+    ///
+    /// Arguments:
+    /// * `cx`: The reactive context.
+    /// * `updater`: Function which modifies the value.
     pub fn update<R: RunContextWrite, F: FnOnce(Mut<T>)>(&self, cx: &mut R, updater: F) {
-        let value = cx.world_mut().get_mut::<MutableCell<T>>(self.cell).unwrap();
-        let inner = value.map_unchanged(|v| &mut v.0);
-        (updater)(inner);
+        let world = cx.world_mut();
+        self.origin.check(world, "Mutable");
+        if !self.is_alive(world) {
+            self.origin.dead("Mutable");
+            return;
+        }
+        match self.key {
+            MutableKey::Entity(cell, _) => {
+                let value = world.get_mut::<MutableCell<T>>(cell).unwrap();
+                let inner = value.map_unchanged(|v| &mut v.0);
+                (updater)(inner);
+            }
+            MutableKey::Slot(slot) => {
+                let tick = world.change_tick();
+                world
+                    .resource_mut::<MutableStore>()
+                    .update(slot, tick, updater);
+            }
+        }
     }
 }
 
 impl<T> Mutable<T>
 where
-    T: PartialEq + Send + Sync + 'static,
+    T: Send + Sync + 'static,
 {
+    /// Modify the value of this [`Mutable`] in place, without replacing or cloning it. Like
+    /// [`set`](Self::set), the modification is deferred until commands are flushed, so the
+    /// new value is not visible until then. The value is always marked as changed.
+    ///
+    /// Arguments:
+    /// * `cx`: The reactive context.
+    /// * `modifier`: Function which modifies the value.
+    pub fn update_in_place<R: WriteMutable, F: FnOnce(&mut T) + Send + 'static>(
+        &self,
+        cx: &mut R,
+        modifier: F,
+    ) {
+        cx.modify_mutable(self, modifier);
+    }
+
+    /// Replace the value of this [`Mutable`]. Unlike [`set`](Self::set), this doesn't
+    /// require `PartialEq`: the mutable's custom equality function, if it was created with
+    /// [`create_mutable_with_equality`], decides whether the new value is a change, and
+    /// otherwise every write is a change. The write is applied when commands are flushed.
+    ///
+    /// Arguments:
+    /// * `cx`: The reactive context.
+    /// * `value`: The new value.
+    ///
+    /// [`create_mutable_with_equality`]: crate::RunContextSetup::create_mutable_with_equality
+    pub fn replace<R: RunContextWrite>(&self, cx: &mut R, value: T) {
+        let world = cx.world_mut();
+        self.origin.check(world, "Mutable");
+        world.commands().add(UpdateMutableCell {
+            mutable: *self,
+            value,
+            eq: None,
+        });
+    }
+
     /// Returns a signal for this [`Mutable`] with Copy semantics.
     pub fn signal(&self) -> Signal<T> {
         Signal::Mutable(*self)
@@ -80,7 +351,7 @@ where
     /// * `cx`: The reactive context.
     /// * `value`: The new value.
     pub fn set<R: WriteMutable>(&self, cx: &mut R, value: T) {
-        cx.write_mutable(self.cell, value);
+        cx.set_mutable(self, value);
     }
 }
 
@@ -102,11 +373,24 @@ where
     /// * `cx`: The reactive context.
     /// * `value`: The new value.
     pub fn set_clone<R: WriteMutable>(&self, cx: &mut R, value: T) {
-        cx.write_mutable_clone(self.cell, value);
+        cx.set_mutable(self, value);
+    }
+
+    /// Call `callback` with the previous and new values whenever this [`Mutable`] changes.
+    /// The observer is removed along with the tracking scope of `cx`.
+    ///
+    /// Arguments:
+    /// * `cx`: The setup context which owns the observer.
+    /// * `callback`: Called with the previous value, the new value and the world.
+    pub fn on_change<'p, C: RunContextSetup<'p>, F>(&self, cx: &mut C, callback: F)
+    where
+        F: Send + Sync + 'static + FnMut(&T, &T, &mut World),
+    {
+        cx.watch(self.signal(), callback);
     }
 }
 
-/// Trait for low-level read-access to mutables given an entity id.
+/// Trait for low-level read-access to mutables.
 pub trait ReadMutable {
     /// Read the value of a mutable variable using Copy semantics. Calling this function adds the
     /// mutable to the current tracking scope.
@@ -144,28 +428,83 @@ pub trait WriteMutable {
     fn write_mutable_clone<T>(&mut self, mutable: Entity, value: T)
     where
         T: Send + Sync + Clone + PartialEq + 'static;
+
+    /// Write the value of a mutable variable, wherever it is held. Unlike
+    /// [`write_mutable`](Self::write_mutable), this also works for mutables held in the
+    /// [`MutableStore`]. Does nothing if the value being set matches the existing value.
+    fn set_mutable<T>(&mut self, mutable: &Mutable<T>, value: T)
+    where
+        T: Send + Sync + PartialEq + 'static;
+
+    /// Modify the value of a mutable variable in place. The modification is deferred in the
+    /// same way as writes, and always marks the value as changed. Use [`Mutable::update`] to
+    /// modify the value immediately.
+    fn modify_mutable<T, F>(&mut self, mutable: &Mutable<T>, modifier: F)
+    where
+        T: Send + Sync + 'static,
+        F: FnOnce(&mut T) + Send + 'static;
 }
 
 /// Custom command which updates the state of a mutable cell.
-/// (Not used yet, waiting on changes in Bevy 0.14)
 pub(crate) struct UpdateMutableCell<T> {
-    pub(crate) mutable: Entity,
+    pub(crate) mutable: Mutable<T>,
     pub(crate) value: T,
+    /// Equality used when the cell has no [`MutableEquality`]. When `None`, every write
+    /// is a change.
+    pub(crate) eq: Option<fn(&T, &T) -> bool>,
 }
 
-impl<T: Send + Sync + 'static + PartialEq> Command for UpdateMutableCell<T> {
+impl<T: Send + Sync + 'static> Command for UpdateMutableCell<T> {
     fn apply(self, world: &mut World) {
-        let mut mutable_ent = world.entity_mut(self.mutable);
-        let mut mutable = mutable_ent.get_mut::<MutableCell<T>>().unwrap();
-        if mutable.0 != self.value {
-            mutable.0 = self.value;
+        self.mutable.origin.check(world, "Mutable");
+        if !self.mutable.is_alive(world) {
+            self.mutable.origin.dead("Mutable");
+            return;
         }
+        match self.mutable.key {
+            MutableKey::Entity(cell, _) => {
+                let mut mutable_ent = world.entity_mut(cell);
+                let eq = mutable_ent
+                    .get::<MutableEquality<T>>()
+                    .map(|eq| eq.0)
+                    .or(self.eq);
+                let mut mutable = mutable_ent.get_mut::<MutableCell<T>>().unwrap();
+                if !eq.is_some_and(|eq| eq(&mutable.0, &self.value)) {
+                    mutable.0 = self.value;
+                }
+            }
+            MutableKey::Slot(slot) => {
+                let tick = world.change_tick();
+                world
+                    .resource_mut::<MutableStore>()
+                    .set(slot, self.value, tick, self.eq);
+            }
+        }
+    }
+}
+
+/// Command which modifies the state of a mutable cell in place.
+pub(crate) struct ModifyMutableCell<T, F: FnOnce(&mut T)> {
+    pub(crate) mutable: Mutable<T>,
+    pub(crate) modifier: F,
+}
+
+impl<T: Send + Sync + 'static, F: FnOnce(&mut T) + Send + 'static> Command
+    for ModifyMutableCell<T, F>
+{
+    fn apply(self, world: &mut World) {
+        let modifier = self.modifier;
+        self.mutable
+            .update(world, |mut value: Mut<T>| modifier(&mut value));
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{cx::Cx, RunContextSetup, TrackingScope};
+    use crate::{
+        cx::Cx, tracking_scope::run_reactions, DespawnScopes, MutableStorage, RunContextSetup,
+        TrackingScope,
+    };
 
     use super::*;
 
@@ -198,6 +537,12 @@ mod tests {
         let cx = Cx::new(&mut world, owner, &mut scope);
         assert_eq!(reader.get(&cx), 1);
         assert_eq!(reader2.get(&cx), 0);
+
+        // Entity-backed mutables can also be written given the entity which holds them.
+        assert_eq!(mutable.storage(), MutableStorage::Entities);
+        world.write_mutable(mutable.id(), 2);
+        world.flush_commands();
+        assert_eq!(mutable.get(&world), 2);
     }
 
     #[test]
@@ -230,4 +575,368 @@ mod tests {
         assert_eq!(reader.get_clone(&cx), "Goodbye".to_string());
         assert_eq!(reader2.get(&cx), 0);
     }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "used with a different world")]
+    fn test_mutable_wrong_world() {
+        let mut world = World::default();
+        let mut scope = TrackingScope::new(world.change_tick());
+        let owner = world.spawn_empty().id();
+        let mut cx = Cx::new(&mut world, owner, &mut scope);
+        let mutable = cx.create_mutable::<i32>(0);
+
+        // Reading the mutable from another world should produce a diagnostic.
+        let other_world = World::default();
+        mutable.get(&other_world);
+    }
+
+    #[test]
+    fn test_mutable_store() {
+        let mut world = World::default();
+        world.insert_resource(MutableStorage::Store);
+        let mut scope = TrackingScope::new(world.change_tick());
+        let owner = world.spawn_empty().id();
+        let mut cx = Cx::new(&mut world, owner, &mut scope);
+
+        let mutable = cx.create_mutable::<i32>(0);
+        let reader = mutable.signal();
+        let text = cx.create_mutable("Hello".to_string());
+        assert_eq!(mutable.storage(), MutableStorage::Store);
+        assert_eq!(reader.get(&cx), 0);
+        assert_eq!(text.get_clone(&mut cx), "Hello".to_string());
+
+        mutable.set(&mut cx, 1);
+        text.update(&mut cx, |mut value| value.push('!'));
+        world.flush_commands();
+
+        let mut cx = Cx::new(&mut world, owner, &mut scope);
+        assert_eq!(reader.get(&cx), 1);
+        assert_eq!(text.get_clone(&mut cx), "Hello!".to_string());
+
+        // Values are removed along with the scope that owns them.
+        world.entity_mut(owner).insert(scope);
+        world.despawn_owned_recursive(owner);
+        assert!(world.resource::<MutableStore>().is_empty());
+    }
+
+    #[derive(Resource, Default)]
+    struct RunCount(usize);
+
+    #[test]
+    fn test_mutable_store_reaction() {
+        let mut world = World::default();
+        world.insert_resource(MutableStorage::Store);
+        world.init_resource::<RunCount>();
+        let mut scope = TrackingScope::new(world.change_tick());
+        let owner = world.spawn_empty().id();
+        let mut cx = Cx::new(&mut world, owner, &mut scope);
+
+        let mutable = cx.create_mutable::<i32>(0);
+        let other = cx.create_mutable::<i32>(0);
+        cx.create_effect(move |cx| {
+            let _ = mutable.get(cx);
+            cx.world_mut().resource_mut::<RunCount>().0 += 1;
+        });
+        assert_eq!(world.resource::<RunCount>().0, 1);
+
+        // Writing an unrelated value doesn't run the effect.
+        world.increment_change_tick();
+        other.set(&mut world, 1);
+        world.flush_commands();
+        run_reactions(&mut world);
+        assert_eq!(world.resource::<RunCount>().0, 1);
+
+        world.increment_change_tick();
+        mutable.set(&mut world, 1);
+        world.flush_commands();
+        run_reactions(&mut world);
+        assert_eq!(world.resource::<RunCount>().0, 2);
+    }
+
+    #[test]
+    fn test_mutable_always_notify() {
+        let mut world = World::default();
+        world.init_resource::<RunCount>();
+        let mut scope = TrackingScope::new(world.change_tick());
+        let owner = world.spawn_empty().id();
+        let mut cx = Cx::new(&mut world, owner, &mut scope);
+
+        let mutable = cx.create_mutable_always_notify::<i32>(0);
+        cx.create_effect(move |cx| {
+            let _ = mutable.get(cx);
+            cx.world_mut().resource_mut::<RunCount>().0 += 1;
+        });
+        assert_eq!(world.resource::<RunCount>().0, 1);
+
+        // Writing the same value still runs the effect.
+        world.increment_change_tick();
+        mutable.set(&mut world, 0);
+        world.flush_commands();
+        run_reactions(&mut world);
+        assert_eq!(world.resource::<RunCount>().0, 2);
+    }
+
+    #[test]
+    fn test_mutable_with_equality() {
+        let mut world = World::default();
+        world.init_resource::<RunCount>();
+        let mut scope = TrackingScope::new(world.change_tick());
+        let owner = world.spawn_empty().id();
+        let mut cx = Cx::new(&mut world, owner, &mut scope);
+
+        // Only the length of the string is compared.
+        let mutable = cx.create_mutable_with_equality("abc".to_string(), |a, b| a.len() == b.len());
+        cx.create_effect(move |cx| {
+            let _ = mutable.get_clone(cx);
+            cx.world_mut().resource_mut::<RunCount>().0 += 1;
+        });
+        assert_eq!(world.resource::<RunCount>().0, 1);
+
+        world.increment_change_tick();
+        mutable.set_clone(&mut world, "xyz".to_string());
+        world.flush_commands();
+        run_reactions(&mut world);
+        assert_eq!(world.resource::<RunCount>().0, 1);
+        assert_eq!(mutable.get_clone(&mut world), "abc".to_string());
+
+        world.increment_change_tick();
+        mutable.set_clone(&mut world, "abcd".to_string());
+        world.flush_commands();
+        run_reactions(&mut world);
+        assert_eq!(world.resource::<RunCount>().0, 2);
+    }
+
+    /// A value which can only be compared with a custom equality function.
+    #[derive(Clone)]
+    struct Opaque(i32);
+
+    #[test]
+    fn test_replace_without_partial_eq() {
+        let mut world = World::default();
+        world.init_resource::<RunCount>();
+        let mut scope = TrackingScope::new(world.change_tick());
+        let owner = world.spawn_empty().id();
+        let mut cx = Cx::new(&mut world, owner, &mut scope);
+
+        let mutable = cx.create_mutable_with_equality(Opaque(1), |a, b| a.0 == b.0);
+        let memo = cx.create_memo_with_equality(
+            move |cx| Opaque(mutable.as_ref(cx).0 / 2),
+            |a, b| a.0 == b.0,
+        );
+        cx.create_effect(move |cx| {
+            let _ = memo.get_clone(cx);
+            cx.world_mut().resource_mut::<RunCount>().0 += 1;
+        });
+        assert_eq!(world.resource::<RunCount>().0, 1);
+
+        // An equal value is not a change.
+        world.increment_change_tick();
+        mutable.replace(&mut world, Opaque(1));
+        world.flush_commands();
+        run_reactions(&mut world);
+        assert_eq!(world.resource::<RunCount>().0, 1);
+
+        // The mutable changes, but the memo's output doesn't.
+        world.increment_change_tick();
+        mutable.replace(&mut world, Opaque(0));
+        world.flush_commands();
+        run_reactions(&mut world);
+        world.flush_commands();
+        run_reactions(&mut world);
+        assert_eq!(world.resource::<RunCount>().0, 1);
+        assert_eq!(mutable.as_ref(&mut world).0, 0);
+
+        world.increment_change_tick();
+        mutable.replace(&mut world, Opaque(4));
+        world.flush_commands();
+        run_reactions(&mut world);
+        world.flush_commands();
+        run_reactions(&mut world);
+        assert_eq!(world.resource::<RunCount>().0, 2);
+        assert_eq!(memo.get_clone(&world).0, 2);
+    }
+
+    #[test]
+    fn test_modify_mutable() {
+        let mut world = World::default();
+        world.init_resource::<RunCount>();
+        let mut scope = TrackingScope::new(world.change_tick());
+        let owner = world.spawn_empty().id();
+        let mut cx = Cx::new(&mut world, owner, &mut scope);
+
+        let items = cx.create_mutable(vec![1, 2, 3]);
+        cx.create_effect(move |cx| {
+            let _ = items.as_ref(cx).len();
+            cx.world_mut().resource_mut::<RunCount>().0 += 1;
+        });
+        assert_eq!(world.resource::<RunCount>().0, 1);
+
+        world.increment_change_tick();
+        world.modify_mutable(&items, |items| items.push(4));
+
+        // The change is not visible until commands are flushed.
+        assert_eq!(items.as_ref(&mut world).len(), 3);
+        world.flush_commands();
+        assert_eq!(*items.as_ref(&mut world), vec![1, 2, 3, 4]);
+        run_reactions(&mut world);
+        assert_eq!(world.resource::<RunCount>().0, 2);
+    }
+
+    #[test]
+    fn test_update_in_place() {
+        for storage in [MutableStorage::Entities, MutableStorage::Store] {
+            let mut world = World::default();
+            world.insert_resource(storage);
+            let mut scope = TrackingScope::new(world.change_tick());
+            let owner = world.spawn_empty().id();
+            let mut cx = Cx::new(&mut world, owner, &mut scope);
+
+            let items = cx.create_mutable(vec![1, 2, 3]);
+            items.update_in_place(&mut cx, |items| items.push(4));
+
+            // The new value is not visible until commands are flushed.
+            assert_eq!(items.get_clone(&mut cx), vec![1, 2, 3]);
+            world.flush_commands();
+            assert_eq!(items.get_clone(&mut world), vec![1, 2, 3, 4]);
+        }
+    }
+
+    #[test]
+    fn test_modify_mutable_store() {
+        let mut world = World::default();
+        world.insert_resource(MutableStorage::Store);
+        let mut scope = TrackingScope::new(world.change_tick());
+        let owner = world.spawn_empty().id();
+        let mut cx = Cx::new(&mut world, owner, &mut scope);
+
+        let text = cx.create_mutable("Hello".to_string());
+        cx.modify_mutable(&text, |text| text.push('!'));
+        assert_eq!(text.get_clone(&mut cx), "Hello".to_string());
+        world.flush_commands();
+        assert_eq!(text.get_clone(&mut world), "Hello!".to_string());
+    }
+
+    #[test]
+    fn test_promote_to_world() {
+        let mut world = World::default();
+        let mut scope = TrackingScope::new(world.change_tick());
+        let owner = world.spawn_empty().id();
+        let mut cx = Cx::new(&mut world, owner, &mut scope);
+
+        let owned = cx.create_owned_mutable::<i32>(1);
+        let kept = cx.create_owned_mutable::<i32>(2);
+        assert_eq!(owned.owner(), owner);
+        let kept = kept.promote_to_world(&mut world);
+
+        world.entity_mut(owner).insert(scope);
+        world.despawn_owned_recursive(owner);
+        assert!(!owned.is_alive(&world));
+        assert!(kept.is_alive(&world));
+        assert_eq!(kept.get(&world), 2);
+    }
+
+    #[test]
+    fn test_owned_mutable_guard() {
+        for storage in [MutableStorage::Entities, MutableStorage::Store] {
+            let mut world = World::default();
+            world.insert_resource(storage);
+            let mut scope = TrackingScope::new(world.change_tick());
+            let owner = world.spawn_empty().id();
+            let mut cx = Cx::new(&mut world, owner, &mut scope);
+
+            let dropped = cx.create_owned_mutable::<i32>(1);
+            let held = cx.create_owned_mutable::<i32>(2);
+            let mutable = dropped.mutable();
+            world.entity_mut(owner).insert(scope);
+
+            // Dropping the guard releases the mutable the next time reactions are run.
+            drop(dropped);
+            assert!(mutable.is_alive(&world));
+            run_reactions(&mut world);
+            assert!(!mutable.is_alive(&world));
+            assert!(held.is_alive(&world));
+
+            // The owner no longer refers to the released mutable.
+            world.despawn_owned_recursive(owner);
+            assert!(!held.is_alive(&world));
+        }
+    }
+
+    #[test]
+    fn test_mutable_dead_write() {
+        let mut world = World::default();
+        let mut scope = TrackingScope::new(world.change_tick());
+        let owner = world.spawn_empty().id();
+        let mut cx = Cx::new(&mut world, owner, &mut scope);
+        let mutable = cx.create_mutable::<i32>(0);
+
+        // Writes to a dead mutable are reported, but don't panic.
+        world.entity_mut(owner).insert(scope);
+        world.despawn_owned_recursive(owner);
+        mutable.set(&mut world, 1);
+        mutable.update(&mut world, |mut value| *value = 2);
+        world.flush_commands();
+        assert!(!mutable.is_alive(&world));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "was used after the tracking scope which owns it was despawned")]
+    fn test_mutable_dead() {
+        let mut world = World::default();
+        let mut scope = TrackingScope::new(world.change_tick());
+        let owner = world.spawn_empty().id();
+        let mut cx = Cx::new(&mut world, owner, &mut scope);
+        let mutable = cx.create_mutable::<i32>(0);
+
+        world.entity_mut(owner).insert(scope);
+        world.despawn_owned_recursive(owner);
+        mutable.get(&world);
+    }
+
+    #[derive(Resource, Default)]
+    struct Changes(Vec<(i32, i32)>);
+
+    #[test]
+    fn test_on_change() {
+        let mut world = World::default();
+        world.init_resource::<Changes>();
+        let mut scope = TrackingScope::new(world.change_tick());
+        let owner = world.spawn_empty().id();
+        let mut cx = Cx::new(&mut world, owner, &mut scope);
+        let mutable = cx.create_mutable::<i32>(0);
+
+        // The observer has its own owner, so that it can be removed separately.
+        let mut watcher_scope = TrackingScope::new(world.change_tick());
+        let watcher = world.spawn_empty().id();
+        let mut cx = Cx::new(&mut world, watcher, &mut watcher_scope);
+        mutable.on_change(&mut cx, |old, new, world| {
+            world.resource_mut::<Changes>().0.push((*old, *new));
+        });
+
+        // The initial value is not a change.
+        assert!(world.resource::<Changes>().0.is_empty());
+
+        world.increment_change_tick();
+        mutable.set(&mut world, 1);
+        world.flush_commands();
+        run_reactions(&mut world);
+        assert_eq!(world.resource::<Changes>().0, vec![(0, 1)]);
+
+        world.increment_change_tick();
+        mutable.set(&mut world, 2);
+        world.flush_commands();
+        run_reactions(&mut world);
+        assert_eq!(world.resource::<Changes>().0, vec![(0, 1), (1, 2)]);
+
+        // The observer is removed along with its owner.
+        world.entity_mut(watcher).insert(watcher_scope);
+        world.despawn_owned_recursive(watcher);
+        world.increment_change_tick();
+        mutable.set(&mut world, 3);
+        world.flush_commands();
+        run_reactions(&mut world);
+        assert_eq!(world.resource::<Changes>().0.len(), 2);
+    }
 }
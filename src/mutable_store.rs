@@ -0,0 +1,375 @@
+use std::any::{Any, TypeId};
+
+use bevy::{
+    ecs::component::Tick,
+    prelude::*,
+    utils::{HashMap, HashSet},
+};
+
+/// Selects where [`create_mutable`] stores the values of new [`Mutable`]s. Insert this as a
+/// resource to change the storage mode; mutables which already exist are not affected.
+///
+/// [`create_mutable`]: crate::RunContextSetup::create_mutable
+/// [`Mutable`]: crate::Mutable
+#[derive(Resource, Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MutableStorage {
+    /// Each mutable is held in a component on its own entity. This is the default.
+    #[default]
+    Entities,
+
+    /// Mutables are held in typed slabs in the [`MutableStore`] resource. This avoids spawning
+    /// an entity per mutable, which keeps archetype tables small and values of the same type
+    /// close together in memory. Recommended for UIs with very large numbers of signals.
+    Store,
+}
+
+/// Generational id of a value in the [`MutableStore`]. Ids of removed values are never
+/// reused, so a stale id simply finds nothing.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub(crate) struct SlotId {
+    slab: TypeId,
+    index: u32,
+    generation: u32,
+}
+
+struct SlabEntry<T> {
+    generation: u32,
+    value: Option<T>,
+    added: Tick,
+    changed: Tick,
+}
+
+/// Storage for all mutable values of a single type.
+struct Slab<T> {
+    entries: Vec<SlabEntry<T>>,
+    free: Vec<u32>,
+    len: usize,
+}
+
+impl<T> Default for Slab<T> {
+    fn default() -> Self {
+        Self {
+            entries: Vec::new(),
+            free: Vec::new(),
+            len: 0,
+        }
+    }
+}
+
+impl<T> Slab<T> {
+    fn entry(&self, slot: SlotId) -> Option<&SlabEntry<T>> {
+        self.entries
+            .get(slot.index as usize)
+            .filter(|entry| entry.generation == slot.generation && entry.value.is_some())
+    }
+
+    fn entry_mut(&mut self, slot: SlotId) -> Option<&mut SlabEntry<T>> {
+        self.entries
+            .get_mut(slot.index as usize)
+            .filter(|entry| entry.generation == slot.generation && entry.value.is_some())
+    }
+}
+
+/// Type-erased interface to a [`Slab`].
+trait AnySlab: Send + Sync {
+    fn changed(&self, slot: SlotId) -> Option<Tick>;
+    fn remove(&mut self, slot: SlotId);
+    fn len(&self) -> usize;
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<T: Send + Sync + 'static> AnySlab for Slab<T> {
+    fn changed(&self, slot: SlotId) -> Option<Tick> {
+        self.entry(slot).map(|entry| entry.changed)
+    }
+
+    fn remove(&mut self, slot: SlotId) {
+        if let Some(entry) = self.entry_mut(slot) {
+            entry.value = None;
+            entry.generation = entry.generation.wrapping_add(1);
+            self.free.push(slot.index);
+            self.len -= 1;
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// Resource which holds the values of mutables created with [`MutableStorage::Store`].
+///
+/// Values are removed when the tracking scope which created them is despawned. Writes are
+/// recorded so that reactions can be dispatched to the subscribers of just the values which
+/// changed.
+#[derive(Resource, Default)]
+pub struct MutableStore {
+    slabs: HashMap<TypeId, Box<dyn AnySlab>>,
+
+    /// Values owned by each tracking scope.
+    owned: HashMap<Entity, Vec<SlotId>>,
+
+    /// The tracking scope which owns each value, if it hasn't been detached.
+    owners: HashMap<SlotId, Entity>,
+
+    /// Values written since the last time reactions were run.
+    dirty: HashSet<SlotId>,
+}
+
+impl MutableStore {
+    /// The number of values in the store.
+    pub fn len(&self) -> usize {
+        self.slabs.values().map(|slab| slab.len()).sum()
+    }
+
+    /// Returns true if the store holds no values.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn slab<T: Send + Sync + 'static>(&self) -> Option<&Slab<T>> {
+        self.slabs
+            .get(&TypeId::of::<T>())
+            .and_then(|slab| slab.as_any().downcast_ref::<Slab<T>>())
+    }
+
+    fn slab_mut<T: Send + Sync + 'static>(&mut self) -> Option<&mut Slab<T>> {
+        self.slabs
+            .get_mut(&TypeId::of::<T>())
+            .and_then(|slab| slab.as_any_mut().downcast_mut::<Slab<T>>())
+    }
+
+    /// Add a value owned by the tracking scope on `owner`.
+    pub(crate) fn insert<T: Send + Sync + 'static>(
+        &mut self,
+        owner: Entity,
+        value: T,
+        tick: Tick,
+    ) -> SlotId {
+        let slab = self
+            .slabs
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::new(Slab::<T>::default()))
+            .as_any_mut()
+            .downcast_mut::<Slab<T>>()
+            .unwrap();
+        let entry = SlabEntry {
+            generation: 0,
+            value: Some(value),
+            added: tick,
+            changed: tick,
+        };
+        let (index, generation) = match slab.free.pop() {
+            Some(index) => {
+                let slot = &mut slab.entries[index as usize];
+                let generation = slot.generation;
+                *slot = SlabEntry {
+                    generation,
+                    ..entry
+                };
+                (index, generation)
+            }
+            None => {
+                slab.entries.push(entry);
+                ((slab.entries.len() - 1) as u32, 0)
+            }
+        };
+        slab.len += 1;
+        let slot = SlotId {
+            slab: TypeId::of::<T>(),
+            index,
+            generation,
+        };
+        self.owned.entry(owner).or_default().push(slot);
+        self.owners.insert(slot, owner);
+        slot
+    }
+
+    /// Returns a reference to a value, or `None` if it has been removed.
+    pub(crate) fn get<T: Send + Sync + 'static>(&self, slot: SlotId) -> Option<&T> {
+        self.slab::<T>()?.entry(slot)?.value.as_ref()
+    }
+
+    /// Replace a value. Does nothing if `eq` says that the new value is equal to the existing
+    /// one; without an `eq` function every write is a change.
+    pub(crate) fn set<T: Send + Sync + 'static>(
+        &mut self,
+        slot: SlotId,
+        value: T,
+        tick: Tick,
+        eq: Option<fn(&T, &T) -> bool>,
+    ) {
+        let Some(entry) = self.slab_mut::<T>().and_then(|slab| slab.entry_mut(slot)) else {
+            return;
+        };
+        let unchanged = match (eq, entry.value.as_ref()) {
+            (Some(eq), Some(current)) => eq(current, &value),
+            _ => false,
+        };
+        if !unchanged {
+            entry.value = Some(value);
+            entry.changed = tick;
+            self.dirty.insert(slot);
+        }
+    }
+
+    /// Update a value in place. The value is only marked as changed if the updater
+    /// dereferences it mutably.
+    pub(crate) fn update<T: Send + Sync + 'static, F: FnOnce(Mut<T>)>(
+        &mut self,
+        slot: SlotId,
+        tick: Tick,
+        updater: F,
+    ) {
+        let Some(entry) = self.slab_mut::<T>().and_then(|slab| slab.entry_mut(slot)) else {
+            return;
+        };
+        let last_changed = entry.changed;
+        let value = entry.value.as_mut().unwrap();
+        updater(Mut::new(
+            value,
+            &mut entry.added,
+            &mut entry.changed,
+            last_changed,
+            tick,
+        ));
+        if entry.changed == tick {
+            self.dirty.insert(slot);
+        }
+    }
+
+    /// The tick at which a value was last changed, or `None` if it has been removed.
+    pub(crate) fn changed_tick(&self, slot: SlotId) -> Option<Tick> {
+        self.slabs.get(&slot.slab)?.changed(slot)
+    }
+
+    /// Remove all values owned by the tracking scope on `owner`.
+    pub(crate) fn remove_owned(&mut self, owner: Entity) {
+        let Some(slots) = self.owned.remove(&owner) else {
+            return;
+        };
+        for slot in slots {
+            self.owners.remove(&slot);
+            self.remove_value(slot);
+        }
+    }
+
+    /// Remove a single value, before its owner is despawned.
+    pub(crate) fn remove(&mut self, slot: SlotId) {
+        self.detach(slot);
+        self.remove_value(slot);
+    }
+
+    fn remove_value(&mut self, slot: SlotId) {
+        if let Some(slab) = self.slabs.get_mut(&slot.slab) {
+            slab.remove(slot);
+        }
+        self.dirty.remove(&slot);
+    }
+
+    /// Detach a value from the tracking scope which owns it, so that it is never removed.
+    pub(crate) fn detach(&mut self, slot: SlotId) {
+        let Some(owner) = self.owners.remove(&slot) else {
+            return;
+        };
+        if let Some(slots) = self.owned.get_mut(&owner) {
+            slots.retain(|owned| *owned != slot);
+        }
+    }
+
+    /// Take the set of values written since the previous call.
+    pub(crate) fn take_dirty(&mut self) -> HashSet<SlotId> {
+        std::mem::take(&mut self.dirty)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_get_remove() {
+        let mut store = MutableStore::default();
+        let owner = Entity::from_raw(1);
+        let a = store.insert(owner, 1i32, Tick::new(1));
+        let b = store.insert(owner, "b".to_string(), Tick::new(1));
+        assert_eq!(store.len(), 2);
+        assert_eq!(store.get::<i32>(a), Some(&1));
+        assert_eq!(store.get::<String>(b).map(String::as_str), Some("b"));
+
+        store.remove_owned(owner);
+        assert!(store.is_empty());
+        assert_eq!(store.get::<i32>(a), None);
+
+        // Slots are reused, but stale ids don't see the new value.
+        let c = store.insert(owner, 2i32, Tick::new(2));
+        assert_ne!(a, c);
+        assert_eq!(store.get::<i32>(a), None);
+        assert_eq!(store.get::<i32>(c), Some(&2));
+    }
+
+    #[test]
+    fn test_detach_and_remove() {
+        let mut store = MutableStore::default();
+        let owner = Entity::from_raw(1);
+        let a = store.insert(owner, 1i32, Tick::new(1));
+        let b = store.insert(owner, 2i32, Tick::new(1));
+        let c = store.insert(owner, 3i32, Tick::new(1));
+
+        // Detached values outlive their owner, and removed values don't wait for it.
+        store.detach(a);
+        store.remove(b);
+        assert_eq!(store.get::<i32>(b), None);
+        assert_eq!(store.len(), 2);
+
+        store.remove_owned(owner);
+        assert_eq!(store.get::<i32>(a), Some(&1));
+        assert_eq!(store.get::<i32>(c), None);
+        assert!(store.owners.is_empty());
+        assert!(store.owned.is_empty());
+    }
+
+    #[test]
+    fn test_set_marks_dirty() {
+        let mut store = MutableStore::default();
+        let a = store.insert(Entity::from_raw(1), 1i32, Tick::new(1));
+
+        // Setting an equal value is not a change.
+        store.set(a, 1i32, Tick::new(2), Some(i32::eq));
+        assert!(store.take_dirty().is_empty());
+        assert_eq!(store.changed_tick(a), Some(Tick::new(1)));
+
+        store.set(a, 2i32, Tick::new(3), Some(i32::eq));
+        assert!(store.take_dirty().contains(&a));
+        assert_eq!(store.changed_tick(a), Some(Tick::new(3)));
+        assert!(store.take_dirty().is_empty());
+
+        // Without an equality function, every write is a change.
+        store.set(a, 2i32, Tick::new(4), None);
+        assert!(store.take_dirty().contains(&a));
+        assert_eq!(store.changed_tick(a), Some(Tick::new(4)));
+    }
+
+    #[test]
+    fn test_update() {
+        let mut store = MutableStore::default();
+        let a = store.insert(Entity::from_raw(1), vec![1i32], Tick::new(1));
+
+        // Reading without mutating is not a change.
+        store.update::<Vec<i32>, _>(a, Tick::new(2), |v| assert_eq!(v.len(), 1));
+        assert!(store.take_dirty().is_empty());
+
+        store.update::<Vec<i32>, _>(a, Tick::new(3), |mut v| v.push(2));
+        assert!(store.take_dirty().contains(&a));
+        assert_eq!(store.get::<Vec<i32>>(a), Some(&vec![1, 2]));
+    }
+}
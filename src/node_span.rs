@@ -1,5 +1,12 @@
 use bevy::prelude::*;
 
+/// Marker component for the placeholder nodes which dynamic views such as `Cond` and
+/// `Dynamic` insert while they have no display nodes of their own. The anchor marks the
+/// position of the view among its siblings, so that the view can swap its display nodes in
+/// place when it changes, rather than having the parent re-attach all of its children.
+#[derive(Component, Default)]
+pub struct NodeAnchor;
+
 /// Hierarchical data structure representing an ordered sequence of entities. This is the
 /// rendered output of a `View`. This gets flattened before attaching to the parent UiNode.
 #[derive(Debug, Clone)]
@@ -25,6 +32,104 @@ impl NodeSpan {
         }
     }
 
+    /// Prefix `span` with an anchor node, if there is one.
+    pub(crate) fn anchored(anchor: Option<Entity>, span: NodeSpan) -> NodeSpan {
+        match anchor {
+            Some(anchor) => Self::Fragment(Box::new([Self::Node(anchor), span])),
+            None => span,
+        }
+    }
+
+    /// Spawn a hidden placeholder node which marks the position of a dynamic region within
+    /// its parent's list of children. The placeholder does not participate in layout.
+    pub(crate) fn spawn_anchor(world: &mut World) -> Entity {
+        world
+            .spawn((
+                NodeBundle {
+                    style: Style {
+                        display: Display::None,
+                        ..default()
+                    },
+                    ..default()
+                },
+                NodeAnchor,
+                Name::new("Anchor"),
+            ))
+            .id()
+    }
+
+    /// Despawn an anchor created by [`NodeSpan::spawn_anchor`].
+    pub(crate) fn despawn_anchor(anchor: Entity, world: &mut World) {
+        let mut entt = world.entity_mut(anchor);
+        entt.remove_parent();
+        entt.despawn();
+    }
+
+    /// Spawn or despawn the anchor of a dynamic region to match its `content`. An anchor is
+    /// only needed while the region is empty, since otherwise its first node marks its
+    /// position.
+    pub(crate) fn update_anchor(
+        anchor: &mut Option<Entity>,
+        content: &NodeSpan,
+        world: &mut World,
+    ) {
+        match (content.count(), *anchor) {
+            (0, None) => *anchor = Some(Self::spawn_anchor(world)),
+            (0, Some(_)) | (_, None) => {}
+            (_, Some(entity)) => {
+                Self::despawn_anchor(entity, world);
+                *anchor = None;
+            }
+        }
+    }
+
+    /// Returns the parent of the first node of this span, and the index of that node among
+    /// the parent's children, if the span has been attached to a parent.
+    pub(crate) fn position(&self, world: &World) -> Option<(Entity, usize)> {
+        let mut nodes: Vec<Entity> = Vec::with_capacity(self.count());
+        self.flatten(&mut nodes);
+        let first = *nodes.first()?;
+        let parent = world.get::<Parent>(first)?.get();
+        let index = world
+            .get::<Children>(parent)?
+            .iter()
+            .position(|child| *child == first)?;
+        Some((parent, index))
+    }
+
+    /// Replace the display nodes of a dynamic region, `old`, with `new`, at the `position`
+    /// which the old nodes had before they were razed (see [`NodeSpan::position`]). Sibling
+    /// nodes are left untouched. Does nothing if the region has not been attached to a parent
+    /// yet, in which case the parent will pick up the new nodes when it attaches its children.
+    pub(crate) fn splice(
+        position: Option<(Entity, usize)>,
+        old: &NodeSpan,
+        new: &NodeSpan,
+        world: &mut World,
+    ) {
+        let Some((parent, index)) = position else {
+            return;
+        };
+
+        // Old nodes which were despawned when the region was razed have already been removed
+        // from the parent.
+        let mut old_nodes: Vec<Entity> = Vec::with_capacity(old.count());
+        old.flatten(&mut old_nodes);
+        old_nodes.retain(|node| world.get_entity(*node).is_some());
+
+        let mut new_nodes: Vec<Entity> = Vec::with_capacity(new.count());
+        new.flatten(&mut new_nodes);
+
+        let Some(mut parent) = world.get_entity_mut(parent) else {
+            return;
+        };
+        parent.remove_children(&old_nodes);
+        let len = parent
+            .get::<Children>()
+            .map_or(0, |children| children.len());
+        parent.insert_children(index.min(len), &new_nodes);
+    }
+
     /// Flattens the list of entities into a vector.
     pub fn flatten(&self, out: &mut Vec<Entity>) {
         match self {
@@ -66,3 +171,56 @@ impl Default for NodeSpan {
         Self::Empty
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_anchor() {
+        let mut world = World::default();
+        let node = world.spawn_empty().id();
+        let mut anchor = None;
+
+        // An empty region needs an anchor, which is kept while it stays empty.
+        NodeSpan::update_anchor(&mut anchor, &NodeSpan::Empty, &mut world);
+        let first = anchor.unwrap();
+        assert!(world.get::<NodeAnchor>(first).is_some());
+        NodeSpan::update_anchor(&mut anchor, &NodeSpan::Empty, &mut world);
+        assert_eq!(anchor, Some(first));
+
+        // A region with nodes of its own doesn't.
+        NodeSpan::update_anchor(&mut anchor, &NodeSpan::Node(node), &mut world);
+        assert_eq!(anchor, None);
+        assert!(world.get_entity(first).is_none());
+    }
+
+    #[test]
+    fn test_splice() {
+        let mut world = World::default();
+        let [a, b, c, d, e, f] = [(); 6].map(|_| world.spawn_empty().id());
+        let parent = world.spawn_empty().push_children(&[a, b, c]).id();
+        let children = |world: &World| world.get::<Children>(parent).unwrap().to_vec();
+
+        // Replace the middle node, as a region would after razing its old content.
+        let old = NodeSpan::Node(b);
+        let position = old.position(&world);
+        assert_eq!(position, Some((parent, 1)));
+        world.entity_mut(b).remove_parent();
+        world.despawn(b);
+        let new = NodeSpan::Fragment(Box::new([NodeSpan::Node(d), NodeSpan::Node(e)]));
+        NodeSpan::splice(position, &old, &new, &mut world);
+        assert_eq!(children(&world), vec![a, d, e, c]);
+
+        // Old nodes which survived the raze are detached.
+        let position = new.position(&world);
+        NodeSpan::splice(position, &new, &NodeSpan::Node(f), &mut world);
+        assert_eq!(children(&world), vec![a, f, c]);
+        assert!(world.get::<Parent>(d).is_none());
+
+        // A region which isn't attached is left for its parent to attach.
+        assert_eq!(NodeSpan::Node(d).position(&world), None);
+        NodeSpan::splice(None, &NodeSpan::Node(d), &NodeSpan::Node(e), &mut world);
+        assert_eq!(children(&world), vec![a, f, c]);
+    }
+}
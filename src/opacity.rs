@@ -0,0 +1,272 @@
+use bevy::{
+    color::Alpha,
+    ecs::query::QueryData,
+    prelude::*,
+    ui::UiImage,
+    utils::{HashMap, HashSet},
+};
+
+use crate::{portal::PortalParent, signal::IntoSignal, EffectTarget, Element};
+
+/// Component which fades a UI node and all of its descendants. The opacity of nested nodes
+/// is multiplied together, and the accumulated value is applied to the alpha channel of the
+/// `BackgroundColor`, `BorderColor`, `Text` and `UiImage` colors of every node in the subtree.
+/// Colors rendered by materials are not affected.
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct Opacity(pub f32);
+
+impl Default for Opacity {
+    fn default() -> Self {
+        Self(1.)
+    }
+}
+
+/// Trait to set the opacity of an element and its descendants, for fade-in and fade-out
+/// effects.
+pub trait WithOpacity {
+    /// Set the opacity of the element, from 0 (transparent) to 1 (opaque). The argument may be
+    /// a signal, in which case the opacity is updated whenever it changes.
+    fn opacity(self, opacity: impl IntoSignal<f32>) -> Self;
+}
+
+impl<B: Bundle + Default> WithOpacity for Element<B> {
+    fn opacity(self, opacity: impl IntoSignal<f32>) -> Self {
+        let opacity = opacity.into_signal();
+        self.create_effect(move |cx, target| {
+            let opacity = Opacity(opacity.get(cx).clamp(0., 1.));
+            let mut entt = cx.world_mut().entity_mut(target);
+            match entt.get_mut::<Opacity>() {
+                Some(mut current) => {
+                    if *current != opacity {
+                        *current = opacity;
+                    }
+                }
+                None => {
+                    entt.insert(opacity);
+                }
+            }
+        })
+    }
+}
+
+/// A color which has been faded, along with its original value.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct FadedColor {
+    original: Color,
+    faded: Color,
+}
+
+/// Fade `color` to `alpha` times its original opacity. If the color was changed by something
+/// other than this function since it was last faded, the new color is taken as the original.
+fn fade_color(color: Color, faded: &mut Option<FadedColor>, alpha: f32) -> Color {
+    let original = match faded {
+        Some(faded) if faded.faded == color => faded.original,
+        _ => color,
+    };
+    let result = original.with_alpha(original.alpha() * alpha);
+    *faded = Some(FadedColor {
+        original,
+        faded: result,
+    });
+    result
+}
+
+/// The original colors of a node which is being faded by an ancestor's [`Opacity`].
+#[derive(Component, Default)]
+pub(crate) struct FadedColors {
+    background: Option<FadedColor>,
+    border: Option<FadedColor>,
+    image: Option<FadedColor>,
+    text: Vec<Option<FadedColor>>,
+}
+
+/// The colors of a node which can be faded.
+#[derive(QueryData)]
+#[query_data(mutable)]
+struct FadeTarget {
+    entity: Entity,
+    background: Option<&'static mut BackgroundColor>,
+    border: Option<&'static mut BorderColor>,
+    image: Option<&'static mut UiImage>,
+    text: Option<&'static mut Text>,
+    faded: Option<&'static mut FadedColors>,
+}
+
+/// Fade the colors of a single node, recording their original values in `faded`.
+fn fade_node(item: &mut FadeTargetItem, faded: &mut FadedColors, alpha: f32) {
+    if let Some(background) = item.background.as_mut() {
+        let color = fade_color(background.0, &mut faded.background, alpha);
+        if background.0 != color {
+            background.0 = color;
+        }
+    }
+    if let Some(border) = item.border.as_mut() {
+        let color = fade_color(border.0, &mut faded.border, alpha);
+        if border.0 != color {
+            border.0 = color;
+        }
+    }
+    if let Some(image) = item.image.as_mut() {
+        let color = fade_color(image.color, &mut faded.image, alpha);
+        if image.color != color {
+            image.color = color;
+        }
+    }
+    if let Some(text) = item.text.as_mut() {
+        faded.text.resize(text.sections.len(), None);
+        for (index, faded_section) in faded.text.iter_mut().enumerate() {
+            let color = fade_color(text.sections[index].style.color, faded_section, alpha);
+            if text.sections[index].style.color != color {
+                text.sections[index].style.color = color;
+            }
+        }
+    }
+}
+
+/// Applies the accumulated [`Opacity`] of each node's ancestors to its colors. Nodes which
+/// are no longer inside a faded subtree have their original colors restored. The contents of
+/// a [`Portal`](crate::Portal) are treated as children of the element which encloses it.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn update_opacity(
+    mut commands: Commands,
+    roots: Query<(Entity, &Opacity)>,
+    opacities: Query<&Opacity>,
+    parents: Query<&Parent>,
+    portal_parents: Query<(Entity, &PortalParent)>,
+    children: Query<&Children>,
+    mut targets: ParamSet<(Query<FadeTarget>, Query<FadeTarget, With<FadedColors>>)>,
+    mut visited: Local<HashSet<Entity>>,
+) {
+    let mut portals = HashMap::<Entity, Vec<Entity>>::default();
+    for (node, parent) in portal_parents.iter() {
+        portals.entry(parent.0).or_default().push(node);
+    }
+    let parent_of = |entity: Entity| {
+        parents
+            .get(entity)
+            .map(Parent::get)
+            .or_else(|_| portal_parents.get(entity).map(|(_, parent)| parent.0))
+            .ok()
+    };
+
+    visited.clear();
+    let mut stack: Vec<(Entity, f32)> = Vec::new();
+    for (root, opacity) in roots.iter() {
+        // Nested opacities are accumulated when visiting the outermost one.
+        let mut ancestor = parent_of(root);
+        let mut nested = false;
+        while let Some(entity) = ancestor {
+            if opacities.contains(entity) {
+                nested = true;
+                break;
+            }
+            ancestor = parent_of(entity);
+        }
+        if nested {
+            continue;
+        }
+
+        stack.push((root, opacity.0));
+        while let Some((entity, alpha)) = stack.pop() {
+            let child_nodes = children.get(entity).into_iter().flat_map(|c| c.iter());
+            let portal_nodes = portals.get(&entity).into_iter().flatten();
+            for child in child_nodes.chain(portal_nodes) {
+                let child_alpha = opacities.get(*child).map_or(1., |opacity| opacity.0);
+                stack.push((*child, alpha * child_alpha));
+            }
+
+            // Fully opaque nodes are left for the pass below to restore.
+            if alpha >= 1. {
+                continue;
+            }
+            let mut nodes = targets.p0();
+            let Ok(mut item) = nodes.get_mut(entity) else {
+                continue;
+            };
+            visited.insert(entity);
+            match item.faded.take() {
+                Some(mut faded) => fade_node(&mut item, &mut faded, alpha),
+                None => {
+                    let mut faded = FadedColors::default();
+                    fade_node(&mut item, &mut faded, alpha);
+                    commands.entity(entity).insert(faded);
+                }
+            }
+        }
+    }
+
+    // Restore the colors of nodes which are no longer faded.
+    for mut item in targets.p1().iter_mut() {
+        if visited.contains(&item.entity) {
+            continue;
+        }
+        if let Some(mut faded) = item.faded.take() {
+            fade_node(&mut item, &mut faded, 1.);
+            commands.entity(item.entity).remove::<FadedColors>();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::RunSystemOnce;
+
+    use super::*;
+
+    #[test]
+    fn test_fade_color() {
+        let mut faded = None;
+        let red = Color::srgba(1., 0., 0., 0.5);
+        let color = fade_color(red, &mut faded, 0.5);
+        assert_eq!(color, Color::srgba(1., 0., 0., 0.25));
+
+        // Fading again is relative to the original color, not the faded one.
+        let color = fade_color(color, &mut faded, 0.2);
+        assert_eq!(color, Color::srgba(1., 0., 0., 0.1));
+
+        // A color changed by someone else becomes the new original.
+        let blue = Color::srgb(0., 0., 1.);
+        let color = fade_color(blue, &mut faded, 0.5);
+        assert_eq!(color, Color::srgba(0., 0., 1., 0.5));
+        assert_eq!(fade_color(color, &mut faded, 1.), blue);
+    }
+
+    #[test]
+    fn test_update_opacity() {
+        let mut world = World::new();
+        let red = Color::srgb(1., 0., 0.);
+        let root = world.spawn(BackgroundColor(red)).id();
+        let child = world.spawn(BackgroundColor(red)).id();
+        let grandchild = world.spawn(BackgroundColor(red)).id();
+        let portal = world
+            .spawn((BackgroundColor(red), PortalParent(child)))
+            .id();
+        world.entity_mut(root).insert(Opacity(0.5)).add_child(child);
+        world
+            .entity_mut(child)
+            .insert(Opacity(0.5))
+            .add_child(grandchild);
+
+        let alpha =
+            |world: &World, entity: Entity| world.get::<BackgroundColor>(entity).unwrap().0.alpha();
+        world.run_system_once(update_opacity);
+        assert_eq!(alpha(&world, root), 0.5);
+        assert_eq!(alpha(&world, child), 0.25);
+        assert_eq!(alpha(&world, grandchild), 0.25);
+        // The contents of a portal are faded along with the element which encloses it.
+        assert_eq!(alpha(&world, portal), 0.25);
+
+        // Fading again is relative to the original colors.
+        world.run_system_once(update_opacity);
+        assert_eq!(alpha(&world, grandchild), 0.25);
+
+        // Nodes which are no longer faded by an opacity are restored.
+        world.entity_mut(root).remove::<Opacity>();
+        world.run_system_once(update_opacity);
+        assert_eq!(alpha(&world, root), 1.);
+        assert!(world.get::<FadedColors>(root).is_none());
+        assert_eq!(alpha(&world, child), 0.5);
+        assert_eq!(alpha(&world, grandchild), 0.5);
+        assert_eq!(alpha(&world, portal), 0.5);
+    }
+}
@@ -0,0 +1,78 @@
+#[cfg(debug_assertions)]
+use bevy::ecs::world::WorldId;
+use bevy::prelude::*;
+
+/// Records the world and source location where a reactive handle (such as a [`Mutable`],
+/// [`Derived`] or [`Callback`]) was created. Handles are plain entity ids, so using one
+/// with the wrong world would otherwise read unrelated data or panic with an unhelpful
+/// message. In release builds this is a zero-sized type and the checks are compiled out.
+///
+/// [`Mutable`]: crate::Mutable
+/// [`Derived`]: crate::Derived
+/// [`Callback`]: crate::Callback
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) struct HandleOrigin {
+    #[cfg(debug_assertions)]
+    world: WorldId,
+    #[cfg(debug_assertions)]
+    location: &'static std::panic::Location<'static>,
+}
+
+impl HandleOrigin {
+    /// Record the origin of a handle created in `world` by the caller.
+    #[track_caller]
+    #[allow(unused_variables)]
+    pub(crate) fn new(world: &World) -> Self {
+        Self {
+            #[cfg(debug_assertions)]
+            world: world.id(),
+            #[cfg(debug_assertions)]
+            location: std::panic::Location::caller(),
+        }
+    }
+
+    /// Panic with a diagnostic if the handle is being used with a world other than the
+    /// one that created it.
+    #[inline]
+    #[allow(unused_variables)]
+    pub(crate) fn check(&self, world: &World, kind: &str) {
+        #[cfg(debug_assertions)]
+        if self.world != world.id() {
+            panic!(
+                "{} created at {} in {:?} was used with a different world ({:?})",
+                kind,
+                self.location,
+                self.world,
+                world.id()
+            );
+        }
+    }
+
+    /// Report a handle which was used after its value was despawned, in an operation which
+    /// can safely do nothing instead, such as a write. In debug builds this logs a warning
+    /// with the location where the handle was created; in release builds it is silent.
+    #[cold]
+    #[allow(unused_variables)]
+    pub(crate) fn dead(&self, kind: &str) {
+        #[cfg(debug_assertions)]
+        warn!(
+            "{} created at {} was used after the tracking scope which owns it was despawned. \
+             Use `promote_to_world` to keep it alive independently of its owner.",
+            kind, self.location
+        );
+    }
+
+    /// Panic with a diagnostic for a handle whose value has been despawned, in an operation
+    /// which can't continue without it, such as a read.
+    #[cold]
+    pub(crate) fn missing(&self, kind: &str) -> ! {
+        #[cfg(debug_assertions)]
+        panic!(
+            "{} created at {} was used after the tracking scope which owns it was despawned. \
+             Use `promote_to_world` to keep it alive independently of its owner.",
+            kind, self.location
+        );
+        #[cfg(not(debug_assertions))]
+        panic!("{} was used after its owner was despawned", kind);
+    }
+}
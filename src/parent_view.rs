@@ -7,6 +7,23 @@ use smallvec::SmallVec;
 #[derive(Default, Clone)]
 pub struct ChildArray(pub(crate) SmallVec<[ViewRef; 4]>);
 
+impl ChildArray {
+    /// Iterate over the child views in this array.
+    pub fn iter(&self) -> impl Iterator<Item = &ViewRef> {
+        self.0.iter()
+    }
+
+    /// The number of child views in this array.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns true if this array contains no child views.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
 /// A view which can contain child views. This will generate child entities when spawned.
 pub trait ParentView: Sized {
     /// Get the child views for this element.
@@ -0,0 +1,374 @@
+//! Persistent state: mutables whose values are saved to a pluggable store and restored when
+//! the app restarts. This is intended for things like window layouts, recent files and user
+//! preferences.
+//!
+//! Add a [`PersistencePlugin`] with a [`PersistenceBackend`], such as a [`JsonFileBackend`] or
+//! [`RonFileBackend`], and then create mutables with [`CreatePersistentMutable::create_persistent_mutable`].
+//! Values are serialized with `serde`. Writes are debounced, so a value which changes on every
+//! frame (for example while dragging a splitter) is only saved once it settles, and any
+//! unsaved changes are written when the app exits.
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::Duration,
+};
+
+use bevy::{app::AppExit, prelude::*, utils::Instant};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{Map, Value};
+
+use crate::{Cx, Mutable, RunContextSetup};
+
+/// Storage for persistent values. Values are held as JSON values keyed by name, which a
+/// backend may store in whatever format it likes, such as JSON or RON.
+pub trait PersistenceBackend: Send + Sync + 'static {
+    /// Load all saved values. Returns an empty map if nothing has been saved yet.
+    fn load(&mut self) -> io::Result<Map<String, Value>>;
+
+    /// Save all values, replacing the previous contents.
+    fn save(&mut self, values: &Map<String, Value>) -> io::Result<()>;
+}
+
+/// Backend which stores values in a single JSON file.
+pub struct JsonFileBackend {
+    path: PathBuf,
+}
+
+impl JsonFileBackend {
+    /// Construct a new [`JsonFileBackend`] which reads and writes the file at `path`.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl PersistenceBackend for JsonFileBackend {
+    fn load(&mut self) -> io::Result<Map<String, Value>> {
+        let Some(text) = read_file(&self.path)? else {
+            return Ok(Map::new());
+        };
+        serde_json::from_str(&text).map_err(io::Error::from)
+    }
+
+    fn save(&mut self, values: &Map<String, Value>) -> io::Result<()> {
+        write_file(&self.path, &serde_json::to_string_pretty(values)?)
+    }
+}
+
+/// Backend which stores values in a single RON file.
+pub struct RonFileBackend {
+    path: PathBuf,
+}
+
+impl RonFileBackend {
+    /// Construct a new [`RonFileBackend`] which reads and writes the file at `path`.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl PersistenceBackend for RonFileBackend {
+    fn load(&mut self) -> io::Result<Map<String, Value>> {
+        let Some(text) = read_file(&self.path)? else {
+            return Ok(Map::new());
+        };
+        ron::from_str(&text).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    fn save(&mut self, values: &Map<String, Value>) -> io::Result<()> {
+        let text = ron::ser::to_string_pretty(values, ron::ser::PrettyConfig::default())
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        write_file(&self.path, &text)
+    }
+}
+
+/// Read the contents of a file, or `None` if it doesn't exist.
+fn read_file(path: &Path) -> io::Result<Option<String>> {
+    match fs::read_to_string(path) {
+        Ok(text) => Ok(Some(text)),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+/// Replace the contents of a file, creating its directory if needed.
+fn write_file(path: &Path, text: &str) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    // Write to a temporary file first, so that a crash doesn't leave a truncated file.
+    let temp = path.with_extension("tmp");
+    fs::write(&temp, text)?;
+    fs::rename(&temp, path)
+}
+
+/// Resource holding the current persistent values, and the backend they are saved to.
+#[derive(Resource)]
+pub struct PersistentStore {
+    backend: Box<dyn PersistenceBackend>,
+    values: Map<String, Value>,
+
+    /// How long to wait after the last change before saving.
+    debounce: Duration,
+
+    /// Time of the first change which has not been saved yet.
+    dirty_since: Option<Instant>,
+
+    /// Time of the most recent change which has not been saved yet.
+    changed_at: Option<Instant>,
+}
+
+impl PersistentStore {
+    /// Construct a new [`PersistentStore`], loading the saved values from `backend`.
+    pub fn new(mut backend: impl PersistenceBackend, debounce: Duration) -> Self {
+        let values = backend.load().unwrap_or_else(|err| {
+            warn!("Failed to load persistent state: {}", err);
+            Map::new()
+        });
+        Self {
+            backend: Box::new(backend),
+            values,
+            debounce,
+            dirty_since: None,
+            changed_at: None,
+        }
+    }
+
+    /// Returns the saved value for `key`, or `None` if there isn't one or it can't be
+    /// deserialized as a `T`.
+    pub fn get<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        let value = self.values.get(key)?;
+        match T::deserialize(value) {
+            Ok(value) => Some(value),
+            Err(err) => {
+                warn!("Ignoring persistent value for \"{}\": {}", key, err);
+                None
+            }
+        }
+    }
+
+    /// Set the value for `key`. The value is saved once no further changes have been made
+    /// for the debounce period.
+    pub fn set<T: Serialize>(&mut self, key: &str, value: &T) {
+        let value = match serde_json::to_value(value) {
+            Ok(value) => value,
+            Err(err) => {
+                warn!("Failed to serialize persistent value \"{}\": {}", key, err);
+                return;
+            }
+        };
+        if self.values.get(key) == Some(&value) {
+            return;
+        }
+        self.values.insert(key.to_string(), value);
+        let now = Instant::now();
+        self.dirty_since.get_or_insert(now);
+        self.changed_at = Some(now);
+    }
+
+    /// True if there are changes which have not been saved yet.
+    pub fn is_dirty(&self) -> bool {
+        self.dirty_since.is_some()
+    }
+
+    /// Save any unsaved changes now.
+    pub fn flush(&mut self) {
+        if self.dirty_since.take().is_none() {
+            return;
+        }
+        self.changed_at = None;
+        if let Err(err) = self.backend.save(&self.values) {
+            warn!("Failed to save persistent state: {}", err);
+        }
+    }
+
+    /// True if a save is due. Values are saved when they have settled for the debounce
+    /// period, or at the latest after ten debounce periods of continuous changes.
+    fn save_due(&self) -> bool {
+        match (self.dirty_since, self.changed_at) {
+            (Some(dirty_since), Some(changed_at)) => {
+                changed_at.elapsed() >= self.debounce || dirty_since.elapsed() >= self.debounce * 10
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Plugin which loads persistent state at startup, and saves it when it changes.
+pub struct PersistencePlugin {
+    create_store: Mutex<Option<Box<dyn FnOnce() -> PersistentStore + Send + Sync>>>,
+}
+
+impl PersistencePlugin {
+    /// Construct a new [`PersistencePlugin`] which saves to `backend`, waiting for values to
+    /// settle for `debounce` before writing.
+    pub fn new(backend: impl PersistenceBackend, debounce: Duration) -> Self {
+        Self {
+            create_store: Mutex::new(Some(Box::new(move || {
+                PersistentStore::new(backend, debounce)
+            }))),
+        }
+    }
+
+    /// Construct a new [`PersistencePlugin`] which saves to a JSON file at `path`, with a
+    /// debounce period of one second.
+    pub fn json_file(path: impl Into<PathBuf>) -> Self {
+        Self::new(JsonFileBackend::new(path), Duration::from_secs(1))
+    }
+
+    /// Construct a new [`PersistencePlugin`] which saves to a RON file at `path`, with a
+    /// debounce period of one second.
+    pub fn ron_file(path: impl Into<PathBuf>) -> Self {
+        Self::new(RonFileBackend::new(path), Duration::from_secs(1))
+    }
+}
+
+impl Plugin for PersistencePlugin {
+    fn build(&self, app: &mut App) {
+        let Some(create_store) = self.create_store.lock().unwrap().take() else {
+            warn!("PersistencePlugin can only be added once");
+            return;
+        };
+        app.insert_resource(create_store())
+            .add_systems(Last, save_persistent_state);
+    }
+}
+
+fn save_persistent_state(mut store: ResMut<PersistentStore>, mut exit: EventReader<AppExit>) {
+    if exit.read().next().is_some() || store.save_due() {
+        store.flush();
+    }
+}
+
+/// Method to create mutables whose values persist between runs of the app.
+pub trait CreatePersistentMutable {
+    /// Create a [`Mutable`] whose value is loaded from the [`PersistentStore`] under `key`,
+    /// or `default` if there is no saved value. Changes to the value are saved to the store.
+    ///
+    /// Keys are global, so two mutables created with the same key share their saved value.
+    fn create_persistent_mutable<T>(&mut self, key: &str, default: T) -> Mutable<T>
+    where
+        T: Serialize + DeserializeOwned + PartialEq + Clone + Send + Sync + 'static;
+}
+
+impl<'p, 'w> CreatePersistentMutable for Cx<'p, 'w> {
+    fn create_persistent_mutable<T>(&mut self, key: &str, default: T) -> Mutable<T>
+    where
+        T: Serialize + DeserializeOwned + PartialEq + Clone + Send + Sync + 'static,
+    {
+        let Some(store) = self.world().get_resource::<PersistentStore>() else {
+            warn!(
+                "PersistencePlugin is not installed, \"{}\" will not be saved",
+                key
+            );
+            return self.create_mutable(default);
+        };
+        let init = store.get(key).unwrap_or(default);
+        let mutable = self.create_mutable(init);
+        let key = key.to_string();
+        mutable.on_change(self, move |_, value, world| {
+            world.resource_mut::<PersistentStore>().set(&key, value);
+        });
+        mutable
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::{tracking_scope::run_reactions, TrackingScope};
+
+    /// Backend which keeps the saved values in memory, shared with the test.
+    #[derive(Clone, Default)]
+    struct MemoryBackend(Arc<Mutex<(Map<String, Value>, usize)>>);
+
+    impl PersistenceBackend for MemoryBackend {
+        fn load(&mut self) -> io::Result<Map<String, Value>> {
+            Ok(self.0.lock().unwrap().0.clone())
+        }
+
+        fn save(&mut self, values: &Map<String, Value>) -> io::Result<()> {
+            let mut saved = self.0.lock().unwrap();
+            saved.0 = values.clone();
+            saved.1 += 1;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_persistent_mutable() {
+        let backend = MemoryBackend::default();
+        backend
+            .0
+            .lock()
+            .unwrap()
+            .0
+            .insert("width".to_string(), Value::from(200));
+
+        let mut world = World::default();
+        world.insert_resource(PersistentStore::new(
+            backend.clone(),
+            Duration::from_secs(60),
+        ));
+        let mut scope = TrackingScope::new(world.change_tick());
+        let owner = world.spawn_empty().id();
+        let mut cx = Cx::new(&mut world, owner, &mut scope);
+
+        // Saved values are loaded, and missing ones use the default.
+        let width = cx.create_persistent_mutable("width", 100i32);
+        let name = cx.create_persistent_mutable("name", "untitled".to_string());
+        assert_eq!(width.get(&cx), 200);
+        assert_eq!(name.get_clone(&mut cx), "untitled");
+
+        // Changes are held until the debounce period has passed.
+        world.increment_change_tick();
+        width.set(&mut world, 300);
+        world.flush_commands();
+        run_reactions(&mut world);
+        assert!(world.resource::<PersistentStore>().is_dirty());
+        assert_eq!(backend.0.lock().unwrap().1, 0);
+
+        world.resource_mut::<PersistentStore>().flush();
+        let saved = backend.0.lock().unwrap();
+        assert_eq!(saved.1, 1);
+        assert_eq!(saved.0.get("width"), Some(&Value::from(300)));
+        assert_eq!(saved.0.get("name"), None);
+    }
+
+    #[test]
+    fn test_file_backends() {
+        let dir =
+            std::env::temp_dir().join(format!("bevy_reactor_persistence_{}", std::process::id()));
+        let mut values = Map::new();
+        values.insert("width".to_string(), Value::from(300));
+        values.insert("name".to_string(), Value::from("untitled"));
+        values.insert("recent".to_string(), Value::from(vec!["a.txt", "b.txt"]));
+        values.insert(
+            "layout".to_string(),
+            serde_json::json!({ "split": 0.25, "open": true }),
+        );
+
+        let backends: [(&str, Box<dyn PersistenceBackend>); 2] = [
+            (
+                "state.json",
+                Box::new(JsonFileBackend::new(dir.join("state.json"))),
+            ),
+            (
+                "state.ron",
+                Box::new(RonFileBackend::new(dir.join("state.ron"))),
+            ),
+        ];
+        for (name, mut backend) in backends {
+            // A missing file loads as empty.
+            assert_eq!(backend.load().unwrap(), Map::new(), "{}", name);
+            backend.save(&values).unwrap();
+            assert_eq!(backend.load().unwrap(), values, "{}", name);
+            assert!(!dir.join(name).with_extension("tmp").exists());
+        }
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,30 +1,122 @@
 use bevy::prelude::*;
+#[cfg(feature = "ui")]
+use bevy::ui::UiSystem;
+#[cfg(feature = "ui")]
+use bevy_mod_picking::picking_core::PickSet;
 
+#[cfg(feature = "ui")]
 use crate::{
-    attach_child_views, build_added_view_roots, compositor::update_compositor_size,
-    hover::update_hover_states, tracking_scope::run_reactions, update_text_styles,
+    attach_child_views, build_added_view_roots,
+    compositor::update_compositor_size,
+    hover::update_hover_states,
+    material_param::update_material_frames,
+    measure::update_measurements,
+    opacity::update_opacity,
+    style::{
+        animate_style_transitions, update_cursor_icon, update_hit_shapes, update_text_overflow,
+    },
+    ui_template::instantiate_ui_templates,
+    update_text_styles,
+    window_size::update_window_size,
+    zoom::update_zoom,
+    Breakpoints, NamedStyles, PortalLayers, Router, UiTemplate, UiTemplateRegistry, WindowSize,
+};
+use crate::{
+    event_signal::update_event_signals,
+    frame_budget::{start_frame_budget, FrameBudget},
+    preferences::AccessibilityPreferences,
+    timer::update_timer_signals,
+    tracking_scope::run_reactions,
 };
 
 /// Plugin that adds the reactive UI system to the app.
+///
+/// Without the `ui` feature, only the reactive core (mutables, derived signals, callbacks and
+/// effects) is installed, which is suitable for headless builds.
 pub struct ReactorPlugin;
 
 impl Plugin for ReactorPlugin {
+    #[cfg(feature = "ui")]
     fn build(&self, app: &mut App) {
-        app
+        app.init_resource::<AccessibilityPreferences>()
+            .init_resource::<FrameBudget>()
+            .init_resource::<PortalLayers>()
+            .init_resource::<Router>()
+            .init_resource::<WindowSize>()
+            .init_resource::<Breakpoints>()
+            .init_resource::<NamedStyles>()
+            .init_resource::<UiTemplateRegistry>()
+            .register_type::<UiTemplate>()
             //.register_asset_loader(TextureAtlasLoader)
             .add_systems(
                 Update,
                 (
                     (
+                        start_frame_budget,
+                        instantiate_ui_templates,
                         build_added_view_roots,
+                        update_window_size,
+                        update_event_signals,
+                        update_timer_signals,
                         run_reactions,
                         attach_child_views,
+                        update_zoom,
                         update_text_styles,
+                        animate_style_transitions,
+                        update_text_overflow,
                     )
                         .chain(),
                     update_hover_states,
+                    update_cursor_icon,
                     update_compositor_size,
+                    update_material_frames,
                 ),
+            )
+            .add_systems(
+                PreUpdate,
+                update_hit_shapes
+                    .after(PickSet::ProcessInput)
+                    .before(PickSet::Backend),
+            )
+            .add_systems(
+                PostUpdate,
+                (update_measurements.after(UiSystem::Layout), update_opacity),
+            );
+    }
+
+    #[cfg(not(feature = "ui"))]
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AccessibilityPreferences>()
+            .init_resource::<FrameBudget>()
+            .add_systems(
+                Update,
+                (
+                    start_frame_budget,
+                    update_event_signals,
+                    update_timer_signals,
+                    run_reactions,
+                )
+                    .chain(),
             );
     }
 }
+
+/// Run a single update of the reactor directly on a [`World`], without an [`App`] or a
+/// schedule: pending commands are applied, newly-added view roots are built, reactions are
+/// run, and changed display nodes are re-attached. Timers, event signals and animations are
+/// not updated, so the outcome depends only on the contents of the world. This is mainly
+/// useful for tests and benchmarks.
+pub fn update_reactor(world: &mut World) {
+    world.flush_commands();
+    world.increment_change_tick();
+    #[cfg(feature = "ui")]
+    {
+        instantiate_ui_templates(world);
+        build_added_view_roots(world);
+    }
+    run_reactions(world);
+    #[cfg(feature = "ui")]
+    attach_child_views(world);
+    world.flush_commands();
+    world.clear_trackers();
+}
@@ -1,12 +1,89 @@
+use std::borrow::Cow;
+
 use bevy::prelude::*;
 
-use crate::{node_span::NodeSpan, view::View, DespawnScopes, IntoView, ViewRef};
+use crate::{
+    element::ElementDisplay, node_span::NodeSpan, view::View, DespawnScopes, IntoView, ViewRef,
+};
+
+/// Resource which defines the stacking order of named portal layers. Layers later in the
+/// list are drawn above layers earlier in the list.
+///
+/// The default layers, from bottom to top, are `"overlays"`, `"windows"`, `"dialogs"`,
+/// `"menus"`, `"tooltips"` and `"toasts"`.
+#[derive(Resource)]
+pub struct PortalLayers {
+    layers: Vec<Cow<'static, str>>,
+}
+
+impl PortalLayers {
+    /// Global z-index of the lowest layer.
+    const BASE_Z_INDEX: i32 = 1000;
+
+    /// Distance between the global z-index of adjacent layers.
+    const LAYER_SPACING: i32 = 100;
+
+    /// Add a new layer above all existing layers.
+    pub fn push(&mut self, name: impl Into<Cow<'static, str>>) -> &mut Self {
+        let name = name.into();
+        self.layers.retain(|layer| *layer != name);
+        self.layers.push(name);
+        self
+    }
+
+    /// Add a new layer directly below the layer named `above`. If there is no such layer,
+    /// the new layer is added at the top.
+    pub fn insert_below(&mut self, name: impl Into<Cow<'static, str>>, above: &str) -> &mut Self {
+        let name = name.into();
+        self.layers.retain(|layer| *layer != name);
+        match self.layers.iter().position(|layer| layer == above) {
+            Some(index) => self.layers.insert(index, name),
+            None => self.layers.push(name),
+        }
+        self
+    }
+
+    /// Return the global z-index for the named layer, if it exists.
+    pub fn z_index(&self, name: &str) -> Option<i32> {
+        self.layers
+            .iter()
+            .position(|layer| layer == name)
+            .map(|index| Self::BASE_Z_INDEX + index as i32 * Self::LAYER_SPACING)
+    }
+}
+
+impl Default for PortalLayers {
+    fn default() -> Self {
+        Self {
+            layers: vec![
+                "overlays".into(),
+                "windows".into(),
+                "dialogs".into(),
+                "menus".into(),
+                "tooltips".into(),
+                "toasts".into(),
+            ],
+        }
+    }
+}
+
+/// Component on the root display nodes of a [`Portal`] which is not mounted under a target,
+/// holding the display node of the nearest element which encloses the portal. Effects which
+/// apply to a subtree, such as [`Opacity`](crate::Opacity), follow this link so that they
+/// also apply to the contents of portals.
+#[derive(Component, Clone, Copy)]
+pub(crate) struct PortalParent(pub(crate) Entity);
 
 /// A `Portal` represents a view that is displayed with no parent, causing it's location to
 /// be relative to the window rather than any parent view.
+///
+/// Optionally, the portal can mount its display nodes under a specific target entity, and
+/// can place them in a named layer (see [`PortalLayers`]) which determines stacking order.
 pub struct Portal {
     view: ViewRef,
     entity: Option<Entity>,
+    target: Option<Entity>,
+    layer: Option<Cow<'static, str>>,
 }
 
 impl Portal {
@@ -15,6 +92,69 @@ impl Portal {
         Self {
             view: view.into_view(),
             entity: None,
+            target: None,
+            layer: None,
+        }
+    }
+
+    /// Mount the display nodes of the portal as children of the `target` entity instead of
+    /// at the root. The target's children should not otherwise be managed by a view, since
+    /// views replace the children of their display nodes when they change.
+    pub fn target(mut self, target: Entity) -> Self {
+        self.target = Some(target);
+        self
+    }
+
+    /// Place the display nodes of the portal in the named layer.
+    pub fn layer(mut self, layer: impl Into<Cow<'static, str>>) -> Self {
+        self.layer = Some(layer.into());
+        self
+    }
+
+    /// Attach the display nodes to the target entity and apply the layer's z-index.
+    fn attach(&self, view_entity: Entity, world: &mut World) {
+        let mut nodes = Vec::new();
+        self.view.nodes().flatten(&mut nodes);
+
+        if self.target.is_none() {
+            let mut ancestor = world.get::<Parent>(view_entity).map(Parent::get);
+            let enclosing = loop {
+                let Some(entity) = ancestor else {
+                    break None;
+                };
+                if let Some(display) = world.get::<ElementDisplay>(entity) {
+                    break Some(display.0);
+                }
+                ancestor = world.get::<Parent>(entity).map(Parent::get);
+            };
+            if let Some(enclosing) = enclosing {
+                for node in nodes.iter() {
+                    world.entity_mut(*node).insert(PortalParent(enclosing));
+                }
+            }
+        }
+
+        if let Some(layer) = &self.layer {
+            let z_index = world
+                .get_resource::<PortalLayers>()
+                .and_then(|layers| layers.z_index(layer));
+            match z_index {
+                Some(z_index) => {
+                    for node in nodes.iter() {
+                        world.entity_mut(*node).insert(ZIndex::Global(z_index));
+                    }
+                }
+                None => warn!("Unknown portal layer: {}", layer),
+            }
+        }
+
+        if let Some(target) = self.target {
+            match world.get_entity_mut(target) {
+                Some(mut target) => {
+                    target.push_children(&nodes);
+                }
+                None => warn!("Portal target {:?} does not exist", target),
+            }
         }
     }
 }
@@ -28,6 +168,7 @@ impl View for Portal {
         assert!(self.entity.is_none());
         world.entity_mut(view_entity).insert(Name::new("Portal"));
         self.entity = Some(ViewRef::spawn(&self.view, view_entity, world));
+        self.attach(view_entity, world);
     }
 
     fn raze(&mut self, view_entity: Entity, world: &mut World) {
@@ -36,7 +177,8 @@ impl View for Portal {
         world.despawn_owned_recursive(view_entity);
     }
 
-    fn children_changed(&mut self, _view_entity: Entity, _world: &mut World) -> bool {
+    fn children_changed(&mut self, view_entity: Entity, world: &mut World) -> bool {
+        self.attach(view_entity, world);
         true
     }
 }
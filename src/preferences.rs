@@ -0,0 +1,36 @@
+use bevy::prelude::*;
+
+use crate::{signal::Signal, Cx, RunContextRead, RunContextSetup};
+
+/// Resource holding the user's accessibility preferences. Bevy does not currently expose
+/// these settings from the operating system, so applications are expected to fill this in,
+/// for example from their own settings UI.
+#[derive(Resource, Default, Clone, Copy, PartialEq, Debug)]
+pub struct AccessibilityPreferences {
+    /// The user prefers minimal motion. Style transitions and animations are skipped, jumping
+    /// directly to their final values.
+    pub reduced_motion: bool,
+
+    /// The user prefers increased contrast between foreground and background colors. Theme
+    /// colors which are not overridden switch to their high contrast variants.
+    pub high_contrast: bool,
+}
+
+/// Methods to create signals which track the user's accessibility preferences.
+pub trait CreatePreferenceSignals {
+    /// Signal that returns true when the user prefers reduced motion.
+    fn prefers_reduced_motion(&mut self) -> Signal<bool>;
+
+    /// Signal that returns true when the user prefers high contrast colors.
+    fn prefers_high_contrast(&mut self) -> Signal<bool>;
+}
+
+impl<'p, 'w> CreatePreferenceSignals for Cx<'p, 'w> {
+    fn prefers_reduced_motion(&mut self) -> Signal<bool> {
+        self.create_derived(|cx| cx.use_resource::<AccessibilityPreferences>().reduced_motion)
+    }
+
+    fn prefers_high_contrast(&mut self) -> Signal<bool> {
+        self.create_derived(|cx| cx.use_resource::<AccessibilityPreferences>().high_contrast)
+    }
+}
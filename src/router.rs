@@ -0,0 +1,258 @@
+use std::borrow::Cow;
+
+use bevy::prelude::*;
+
+use crate::node_span::NodeSpan;
+use crate::{
+    Callback, Cx, DespawnScopes, DisplayNodeChanged, IntoView, Rcx, RunContextRead,
+    RunContextSetup, Signal, TrackingScope, View, ViewRef,
+};
+
+/// Resource which holds the current route, along with the navigation history. Routes are
+/// paths made of segments separated by `/`, such as `"settings/display"`.
+#[derive(Resource, Debug, Clone)]
+pub struct Router {
+    history: Vec<String>,
+    index: usize,
+}
+
+impl Default for Router {
+    fn default() -> Self {
+        Self {
+            history: vec![String::new()],
+            index: 0,
+        }
+    }
+}
+
+impl Router {
+    /// The current route path.
+    pub fn current(&self) -> &str {
+        &self.history[self.index]
+    }
+
+    /// The segments of the current route.
+    pub fn segments(&self) -> impl Iterator<Item = &str> {
+        self.current().split('/').filter(|s| !s.is_empty())
+    }
+
+    /// Return the segment of the current route at the given depth, if any.
+    pub fn segment(&self, depth: usize) -> Option<&str> {
+        self.segments().nth(depth)
+    }
+
+    /// Breadcrumbs for the current route: a list of (segment, path) pairs, where each path
+    /// is the route up to and including that segment.
+    pub fn breadcrumbs(&self) -> Vec<(String, String)> {
+        let mut path = String::new();
+        self.segments()
+            .map(|segment| {
+                if !path.is_empty() {
+                    path.push('/');
+                }
+                path.push_str(segment);
+                (segment.to_string(), path.clone())
+            })
+            .collect()
+    }
+
+    /// Navigate to a new route. This discards any forward history.
+    pub fn navigate(&mut self, path: impl Into<String>) {
+        let path = path.into();
+        if path == self.current() {
+            return;
+        }
+        self.history.truncate(self.index + 1);
+        self.history.push(path);
+        self.index += 1;
+    }
+
+    /// Replace the current route without adding a history entry.
+    pub fn replace(&mut self, path: impl Into<String>) {
+        self.history[self.index] = path.into();
+    }
+
+    /// Whether there is a previous route to go back to.
+    pub fn can_go_back(&self) -> bool {
+        self.index > 0
+    }
+
+    /// Whether there is a route to go forward to.
+    pub fn can_go_forward(&self) -> bool {
+        self.index + 1 < self.history.len()
+    }
+
+    /// Go back to the previous route, if any.
+    pub fn back(&mut self) {
+        if self.can_go_back() {
+            self.index -= 1;
+        }
+    }
+
+    /// Go forward to the next route, if any.
+    pub fn forward(&mut self) {
+        if self.can_go_forward() {
+            self.index += 1;
+        }
+    }
+}
+
+/// Methods for accessing the [`Router`] from a view template.
+pub trait CreateRouter {
+    /// Create a signal which returns the current route path.
+    fn create_route_signal(&mut self) -> Signal<String>;
+
+    /// Create a callback which navigates to the given route path.
+    fn create_navigate_callback(&mut self) -> Callback<String>;
+
+    /// Create a callback which goes back to the previous route.
+    fn create_back_callback(&mut self) -> Callback;
+
+    /// Create a callback which goes forward to the next route.
+    fn create_forward_callback(&mut self) -> Callback;
+}
+
+impl<'p, 'w> CreateRouter for Cx<'p, 'w> {
+    fn create_route_signal(&mut self) -> Signal<String> {
+        self.create_derived(|rcx| rcx.use_resource::<Router>().current().to_string())
+    }
+
+    fn create_navigate_callback(&mut self) -> Callback<String> {
+        self.create_callback(|cx: &mut Cx, path: String| {
+            cx.world_mut().resource_mut::<Router>().navigate(path);
+        })
+    }
+
+    fn create_back_callback(&mut self) -> Callback {
+        self.create_callback(|cx: &mut Cx, _| {
+            cx.world_mut().resource_mut::<Router>().back();
+        })
+    }
+
+    fn create_forward_callback(&mut self) -> Callback {
+        self.create_callback(|cx: &mut Cx, _| {
+            cx.world_mut().resource_mut::<Router>().forward();
+        })
+    }
+}
+
+/// Component placed on [`Routes`] views, recording which route segment they match.
+#[derive(Component)]
+pub(crate) struct RouteDepth(usize);
+
+type RouteFactory = Box<dyn Fn() -> ViewRef + Send + Sync>;
+
+/// A view which displays the child whose route matches the current [`Router`] path. Each
+/// `Routes` matches a single path segment; a `Routes` nested inside a matched child matches
+/// the following segment. Children are built lazily when their route becomes active, and
+/// razed when it becomes inactive.
+#[derive(Default)]
+pub struct Routes {
+    routes: Vec<(Cow<'static, str>, RouteFactory)>,
+    fallback: Option<RouteFactory>,
+    depth: usize,
+    /// Index of the active route (`routes.len()` for the fallback), with its view.
+    state: Option<(usize, ViewRef, Entity)>,
+}
+
+impl Routes {
+    /// Construct a new, empty `Routes` view.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a route. The `segment` is matched against the route segment at this depth; an
+    /// empty segment matches when there are no more segments.
+    pub fn route<V: IntoView, F: Fn() -> V + Send + Sync + 'static>(
+        mut self,
+        segment: impl Into<Cow<'static, str>>,
+        factory: F,
+    ) -> Self {
+        self.routes
+            .push((segment.into(), Box::new(move || factory().into_view())));
+        self
+    }
+
+    /// Set the view to display when no route matches.
+    pub fn fallback<V: IntoView, F: Fn() -> V + Send + Sync + 'static>(
+        mut self,
+        factory: F,
+    ) -> Self {
+        self.fallback = Some(Box::new(move || factory().into_view()));
+        self
+    }
+}
+
+impl View for Routes {
+    fn nodes(&self) -> NodeSpan {
+        match self.state {
+            Some((_, ref view, _)) => view.nodes(),
+            None => NodeSpan::Empty,
+        }
+    }
+
+    fn build(&mut self, view_entity: Entity, world: &mut World) {
+        // Nested routes match the segment after their nearest ancestor.
+        let mut ancestor = world.get::<Parent>(view_entity).map(|p| p.get());
+        while let Some(entity) = ancestor {
+            if let Some(depth) = world.get::<RouteDepth>(entity) {
+                self.depth = depth.0 + 1;
+                break;
+            }
+            ancestor = world.get::<Parent>(entity).map(|p| p.get());
+        }
+        world
+            .entity_mut(view_entity)
+            .insert((Name::new("Routes"), RouteDepth(self.depth)));
+
+        let mut tracking = TrackingScope::new(world.change_tick());
+        self.react(view_entity, world, &mut tracking);
+        world.entity_mut(view_entity).insert(tracking);
+    }
+
+    fn react(&mut self, view_entity: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        let index = {
+            let re = Rcx::new(world, view_entity, tracking);
+            let segment = re
+                .use_resource::<Router>()
+                .segment(self.depth)
+                .unwrap_or("");
+            self.routes
+                .iter()
+                .position(|(route, _)| route == segment)
+                .unwrap_or(self.routes.len())
+        };
+
+        // Don't rebuild if the same route is still active.
+        if matches!(self.state, Some((active, _, _)) if active == index) {
+            return;
+        }
+        if let Some((_, view, entity)) = self.state.take() {
+            view.raze(entity, world);
+        }
+
+        let factory = match self.routes.get(index) {
+            Some((_, factory)) => Some(factory),
+            None => self.fallback.as_ref(),
+        };
+        if let Some(factory) = factory {
+            let view = factory();
+            let entity = ViewRef::spawn(&view, view_entity, world);
+            self.state = Some((index, view, entity));
+        }
+        world.entity_mut(view_entity).insert(DisplayNodeChanged);
+    }
+
+    fn raze(&mut self, view_entity: Entity, world: &mut World) {
+        if let Some((_, view, entity)) = self.state.take() {
+            view.raze(entity, world);
+        }
+        world.despawn_owned_recursive(view_entity);
+    }
+}
+
+impl IntoView for Routes {
+    fn into_view(self) -> ViewRef {
+        ViewRef::new(self)
+    }
+}
@@ -0,0 +1,146 @@
+use bevy::prelude::*;
+
+use crate::{
+    effect_target::EffectTarget, signal::Signal, window_size::WindowSize, Cx, Element,
+    RunContextRead, RunContextSetup,
+};
+
+use super::{apply_styles, StyleTuple};
+
+/// A named range of window widths, used to adapt layouts to the size of the window.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Breakpoint {
+    /// Narrower than the `sm` breakpoint.
+    #[default]
+    Xs,
+    /// At least as wide as the `sm` breakpoint.
+    Sm,
+    /// At least as wide as the `md` breakpoint.
+    Md,
+    /// At least as wide as the `lg` breakpoint.
+    Lg,
+    /// At least as wide as the `xl` breakpoint.
+    Xl,
+}
+
+/// Resource which defines the minimum window width, in logical pixels, of each [`Breakpoint`].
+/// Insert this resource with different values to use custom breakpoints.
+#[derive(Resource, Clone, Copy, Debug, PartialEq)]
+pub struct Breakpoints {
+    /// Minimum width of [`Breakpoint::Sm`].
+    pub sm: f32,
+    /// Minimum width of [`Breakpoint::Md`].
+    pub md: f32,
+    /// Minimum width of [`Breakpoint::Lg`].
+    pub lg: f32,
+    /// Minimum width of [`Breakpoint::Xl`].
+    pub xl: f32,
+}
+
+impl Default for Breakpoints {
+    fn default() -> Self {
+        Self {
+            sm: 640.,
+            md: 768.,
+            lg: 1024.,
+            xl: 1280.,
+        }
+    }
+}
+
+impl Breakpoints {
+    /// Return the breakpoint for a given window width.
+    pub fn classify(&self, width: f32) -> Breakpoint {
+        if width >= self.xl {
+            Breakpoint::Xl
+        } else if width >= self.lg {
+            Breakpoint::Lg
+        } else if width >= self.md {
+            Breakpoint::Md
+        } else if width >= self.sm {
+            Breakpoint::Sm
+        } else {
+            Breakpoint::Xs
+        }
+    }
+}
+
+/// Look up the breakpoint for the current window size.
+fn current_breakpoint<R: RunContextRead>(cx: &R) -> Breakpoint {
+    let width = cx.use_resource::<WindowSize>().0.x;
+    cx.use_resource::<Breakpoints>().classify(width)
+}
+
+/// Method to create a signal that tracks the current [`Breakpoint`].
+pub trait CreateBreakpointSignal {
+    /// Signal that returns the breakpoint for the current width of the primary window.
+    fn create_breakpoint_signal(&mut self) -> Signal<Breakpoint>;
+}
+
+impl<'p, 'w> CreateBreakpointSignal for Cx<'p, 'w> {
+    fn create_breakpoint_signal(&mut self) -> Signal<Breakpoint> {
+        self.create_memo(|cx| current_breakpoint(cx))
+    }
+}
+
+/// Trait to add styles which depend on the current [`Breakpoint`].
+pub trait WithBreakpointStyles {
+    /// Apply styles which depend on the current breakpoint. `select` is called with the
+    /// current breakpoint, and again whenever the breakpoint changes. Styles are applied on
+    /// top of the existing ones, so each breakpoint should set the same set of properties.
+    fn style_for_breakpoint<
+        S: StyleTuple + 'static,
+        F: Fn(Breakpoint) -> S + Send + Sync + 'static,
+    >(
+        self,
+        select: F,
+    ) -> Self;
+}
+
+impl<B: Bundle + Default> WithBreakpointStyles for Element<B> {
+    fn style_for_breakpoint<
+        S: StyleTuple + 'static,
+        F: Fn(Breakpoint) -> S + Send + Sync + 'static,
+    >(
+        self,
+        select: F,
+    ) -> Self {
+        let mut current: Option<Breakpoint> = None;
+        self.create_effect(move |cx, target| {
+            let breakpoint = current_breakpoint(cx);
+            if current != Some(breakpoint) {
+                current = Some(breakpoint);
+                let owner = cx.owner();
+                apply_styles(cx.world_mut(), owner, target, &select(breakpoint));
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify() {
+        let breakpoints = Breakpoints::default();
+        assert_eq!(breakpoints.classify(0.), Breakpoint::Xs);
+        assert_eq!(breakpoints.classify(639.), Breakpoint::Xs);
+        assert_eq!(breakpoints.classify(640.), Breakpoint::Sm);
+        assert_eq!(breakpoints.classify(800.), Breakpoint::Md);
+        assert_eq!(breakpoints.classify(1024.), Breakpoint::Lg);
+        assert_eq!(breakpoints.classify(4000.), Breakpoint::Xl);
+    }
+
+    #[test]
+    fn test_classify_custom() {
+        let breakpoints = Breakpoints {
+            sm: 100.,
+            md: 200.,
+            lg: 300.,
+            xl: 400.,
+        };
+        assert_eq!(breakpoints.classify(150.), Breakpoint::Sm);
+        assert_eq!(breakpoints.classify(399.), Breakpoint::Lg);
+    }
+}
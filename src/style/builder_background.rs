@@ -1,6 +1,7 @@
 use bevy::{
     prelude::*,
     render::texture::Image,
+    sprite::{ImageScaleMode, TextureSlicer},
     ui::{self, UiImage},
 };
 
@@ -20,6 +21,20 @@ pub trait StyleBuilderBackground {
         flip_y: bool,
     ) -> &mut Self;
 
+    /// Set a nine-slice background image. The corners of the image are drawn at their
+    /// original size, while the edges and center are stretched or tiled to fill the node as
+    /// configured by `slicer`.
+    fn background_image_sliced<'p>(
+        &mut self,
+        path: impl AssetPathParam<'p>,
+        slicer: TextureSlicer,
+    ) -> &mut Self;
+
+    /// Set how the background image is scaled to fill the node, or `None` to stretch the whole
+    /// image.
+    fn background_image_scale_mode(&mut self, mode: impl Into<Option<ImageScaleMode>>)
+        -> &mut Self;
+
     /// Set the background color, or `None` for transparent.
     fn background_color(&mut self, color: impl ColorParam) -> &mut Self;
 
@@ -77,6 +92,32 @@ impl<'a, 'w> StyleBuilderBackground for StyleBuilder<'a, 'w> {
         self
     }
 
+    fn background_image_sliced<'p>(
+        &mut self,
+        path: impl AssetPathParam<'p>,
+        slicer: TextureSlicer,
+    ) -> &mut Self {
+        let path = path.to_path();
+        let mode = path.is_some().then_some(ImageScaleMode::Sliced(slicer));
+        self.background_image(path)
+            .background_image_scale_mode(mode)
+    }
+
+    fn background_image_scale_mode(
+        &mut self,
+        mode: impl Into<Option<ImageScaleMode>>,
+    ) -> &mut Self {
+        match mode.into() {
+            Some(mode) => {
+                self.target.insert(mode);
+            }
+            None => {
+                self.target.remove::<ImageScaleMode>();
+            }
+        }
+        self
+    }
+
     fn background_color(&mut self, color: impl ColorParam) -> &mut Self {
         if let Some(color) = color.to_val() {
             self.target.insert(ui::BackgroundColor(color));
@@ -0,0 +1,48 @@
+use super::builder::{ColorParam, StyleBuilder};
+use bevy::prelude::*;
+
+/// A drop shadow drawn behind a UI node, following the node's rounded corners. This component
+/// only describes the shadow; it is drawn by a renderer such as the one in `obsidian_ui`.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct BoxShadow {
+    /// Offset of the shadow from the node, in pixels.
+    pub offset: Vec2,
+
+    /// Blur radius of the shadow, in pixels.
+    pub blur: f32,
+
+    /// Color of the shadow.
+    pub color: Color,
+}
+
+#[allow(missing_docs)]
+pub trait StyleBuilderBoxShadow {
+    /// Set the drop shadow of the node. Passing `None` for the color removes the shadow.
+    fn box_shadow(&mut self, offset: Vec2, blur: f32, color: impl ColorParam) -> &mut Self;
+}
+
+impl<'a, 'w> StyleBuilderBoxShadow for StyleBuilder<'a, 'w> {
+    fn box_shadow(&mut self, offset: Vec2, blur: f32, color: impl ColorParam) -> &mut Self {
+        match color.to_val() {
+            Some(color) => {
+                let shadow = BoxShadow {
+                    offset,
+                    blur: blur.max(0.),
+                    color,
+                };
+                match self.target.get_mut::<BoxShadow>() {
+                    Some(mut existing) => {
+                        existing.set_if_neq(shadow);
+                    }
+                    None => {
+                        self.target.insert(shadow);
+                    }
+                }
+            }
+            None => {
+                self.target.remove::<BoxShadow>();
+            }
+        };
+        self
+    }
+}
@@ -0,0 +1,115 @@
+use bevy::{
+    hierarchy::HierarchyQueryExt,
+    prelude::*,
+    window::{CursorIcon, PrimaryWindow},
+};
+use bevy_mod_picking::{focus::HoverMap, pointer::PointerId};
+
+use super::builder::StyleBuilder;
+
+/// Component which requests a mouse cursor icon while the pointer is over the entity or any of
+/// its descendants. When several hovered entities request a cursor, the topmost one wins; a
+/// descendant's request overrides that of its ancestors.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HoverCursor(pub CursorIcon);
+
+#[allow(missing_docs)]
+pub trait StyleBuilderCursor {
+    /// Set the cursor icon which is shown while the pointer is over the node.
+    fn cursor(&mut self, icon: CursorIcon) -> &mut Self;
+}
+
+impl<'a, 'w> StyleBuilderCursor for StyleBuilder<'a, 'w> {
+    fn cursor(&mut self, icon: CursorIcon) -> &mut Self {
+        match self.target.get::<HoverCursor>() {
+            Some(current) if current.0 == icon => {}
+            _ => {
+                self.target.insert(HoverCursor(icon));
+            }
+        }
+        self
+    }
+}
+
+/// The cursor requested by `entity` or its nearest ancestor, if any.
+fn requested_cursor(
+    entity: Entity,
+    parents: &Query<&Parent>,
+    cursors: &Query<&HoverCursor>,
+) -> Option<CursorIcon> {
+    std::iter::once(entity)
+        .chain(parents.iter_ancestors(entity))
+        .find_map(|e| cursors.get(e).ok().map(|cursor| cursor.0))
+}
+
+/// Sets the cursor of the primary window from the [`HoverCursor`] of the topmost entity under
+/// the mouse, or the default cursor when nothing requests one. While a mouse button is held,
+/// the cursor is left unchanged, so that it doesn't flicker when a drag leaves the widget which
+/// started it. The window is only written when the resolved cursor changes, so apps can still
+/// set the cursor themselves outside of hovered widgets.
+pub(crate) fn update_cursor_icon(
+    hover_map: Option<Res<HoverMap>>,
+    buttons: Res<ButtonInput<MouseButton>>,
+    parents: Query<&Parent>,
+    cursors: Query<&HoverCursor>,
+    mut windows: Query<&mut Window, With<PrimaryWindow>>,
+    mut applied: Local<Option<CursorIcon>>,
+) {
+    if buttons.get_pressed().next().is_some() {
+        return;
+    }
+
+    let mut hovered: Vec<_> = hover_map
+        .as_ref()
+        .and_then(|hover_map| hover_map.get(&PointerId::Mouse))
+        .map(|hits| hits.iter().map(|(e, hit)| (*e, hit.depth)).collect())
+        .unwrap_or_default();
+    hovered.sort_by(|a, b| a.1.total_cmp(&b.1));
+    let requested = hovered
+        .iter()
+        .find_map(|(entity, _)| requested_cursor(*entity, &parents, &cursors));
+
+    let icon = requested.unwrap_or_default();
+    if *applied == Some(icon) {
+        return;
+    }
+    if let Ok(mut window) = windows.get_single_mut() {
+        window.cursor.icon = icon;
+        *applied = Some(icon);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::SystemState;
+
+    use super::*;
+
+    #[test]
+    fn test_requested_cursor() {
+        let mut world = World::new();
+        let grandchild = world.spawn(HoverCursor(CursorIcon::Text)).id();
+        let child = world.spawn_empty().add_child(grandchild).id();
+        let root = world
+            .spawn(HoverCursor(CursorIcon::Grab))
+            .add_child(child)
+            .id();
+        let orphan = world.spawn_empty().id();
+
+        let mut state = SystemState::<(Query<&Parent>, Query<&HoverCursor>)>::new(&mut world);
+        let (parents, cursors) = state.get(&world);
+        assert_eq!(
+            requested_cursor(root, &parents, &cursors),
+            Some(CursorIcon::Grab)
+        );
+        assert_eq!(
+            requested_cursor(child, &parents, &cursors),
+            Some(CursorIcon::Grab)
+        );
+        assert_eq!(
+            requested_cursor(grandchild, &parents, &cursors),
+            Some(CursorIcon::Text)
+        );
+        assert_eq!(requested_cursor(orphan, &parents, &cursors), None);
+    }
+}
@@ -1,21 +1,309 @@
-use bevy_mod_picking::picking_core::Pickable;
+use std::sync::Arc;
+
+use bevy::prelude::*;
+use bevy_mod_picking::{picking_core::Pickable, pointer::PointerLocation};
 
 use super::builder::StyleBuilder;
 
+/// The area of a node which responds to the pointer. Outside of the shape, the pointer passes
+/// through to whatever is below the node.
+#[derive(Component, Clone, Default)]
+pub enum HitShape {
+    /// The whole rectangle of the node.
+    #[default]
+    Rect,
+    /// The largest circle which fits within the node, centered on it.
+    Circle,
+    /// The rectangle of the node, with corners rounded according to its `BorderRadius`.
+    RoundedRect,
+    /// A signed distance function, which is given the position of the pointer relative to the
+    /// center of the node and the half-size of the node, both in logical pixels. Points where
+    /// the distance is zero or less are inside the shape.
+    Sdf(Arc<dyn Fn(Vec2, Vec2) -> f32 + Send + Sync>),
+}
+
+impl HitShape {
+    /// Construct a hit shape from a signed distance function.
+    pub fn sdf(f: impl Fn(Vec2, Vec2) -> f32 + Send + Sync + 'static) -> Self {
+        Self::Sdf(Arc::new(f))
+    }
+
+    /// Returns true if `point`, relative to the center of a node whose half-size is
+    /// `half_size`, is within the shape. `radius` holds the corner radii, in the order
+    /// top-left, top-right, bottom-right, bottom-left.
+    fn contains(&self, point: Vec2, half_size: Vec2, radius: [f32; 4]) -> bool {
+        match self {
+            HitShape::Rect => point.x.abs() <= half_size.x && point.y.abs() <= half_size.y,
+            HitShape::Circle => point.length() <= half_size.min_element(),
+            HitShape::RoundedRect => {
+                let r = match (point.x < 0., point.y < 0.) {
+                    (true, true) => radius[0],
+                    (false, true) => radius[1],
+                    (false, false) => radius[2],
+                    (true, false) => radius[3],
+                };
+                let q = point.abs() - half_size + r;
+                q.max(Vec2::ZERO).length() + q.max_element().min(0.) - r <= 0.
+            }
+            HitShape::Sdf(sdf) => sdf(point, half_size) <= 0.,
+        }
+    }
+}
+
+/// `Pickable` for nodes which don't respond to the pointer at all.
+const IGNORE: Pickable = Pickable {
+    should_block_lower: false,
+    is_hoverable: false,
+};
+
+/// How a node responds to the pointer, as set by [`StyleBuilderPointerEvents`].
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct PointerMode {
+    enabled: bool,
+    pass_through: bool,
+}
+
+impl Default for PointerMode {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            pass_through: false,
+        }
+    }
+}
+
+impl PointerMode {
+    /// The `Pickable` for a node in this mode, or `None` for the default behavior.
+    fn pickable(&self) -> Option<Pickable> {
+        match (self.enabled, self.pass_through) {
+            (false, _) => Some(IGNORE),
+            (true, true) => Some(Pickable {
+                should_block_lower: false,
+                is_hoverable: true,
+            }),
+            (true, false) => None,
+        }
+    }
+}
+
 #[allow(missing_docs)]
 pub trait StyleBuilderPointerEvents {
     fn pointer_events(&mut self, enabled: bool) -> &mut Self;
+
+    /// When true, the node is hovered and receives pointer events, but doesn't prevent the
+    /// nodes below it from receiving them too. This is useful for overlays such as drop
+    /// targets, which need to react to a drag without interrupting it.
+    fn pointer_pass_through(&mut self, pass_through: bool) -> &mut Self;
+
+    /// Restrict the area of the node which responds to the pointer to the given shape.
+    fn hit_shape(&mut self, shape: HitShape) -> &mut Self;
+}
+
+impl<'a, 'w> StyleBuilder<'a, 'w> {
+    fn update_pointer_mode(&mut self, f: impl FnOnce(&mut PointerMode)) {
+        let mut mode = self
+            .target
+            .get::<PointerMode>()
+            .copied()
+            .unwrap_or_default();
+        f(&mut mode);
+        self.target.insert(mode);
+        // Nodes with a hit shape have their pickability updated by `update_hit_shapes`.
+        if self.target.contains::<HitShape>() {
+            return;
+        }
+        match mode.pickable() {
+            Some(pickable) => self.target.insert(pickable),
+            None => self.target.remove::<Pickable>(),
+        };
+    }
 }
 
 impl<'a, 'w> StyleBuilderPointerEvents for StyleBuilder<'a, 'w> {
     fn pointer_events(&mut self, enabled: bool) -> &mut Self {
-        match enabled {
-            true => self.target.remove::<Pickable>(),
-            false => self.target.insert(Pickable {
-                should_block_lower: false,
-                is_hoverable: false,
-            }),
-        };
+        self.update_pointer_mode(|mode| mode.enabled = enabled);
+        self
+    }
+
+    fn pointer_pass_through(&mut self, pass_through: bool) -> &mut Self {
+        self.update_pointer_mode(|mode| mode.pass_through = pass_through);
+        self
+    }
+
+    fn hit_shape(&mut self, shape: HitShape) -> &mut Self {
+        self.target.insert(shape);
         self
     }
 }
+
+/// Resolve a corner radius to logical pixels. Percentages are relative to the smaller side
+/// of the node.
+fn corner_radius(val: Val, half_size: Vec2) -> f32 {
+    let min_half = half_size.min_element();
+    let radius = match val {
+        Val::Px(px) => px,
+        Val::Percent(percent) => percent * 0.02 * min_half,
+        _ => 0.,
+    };
+    radius.clamp(0., min_half)
+}
+
+/// Whether the node with the given `shape`, `rect` and corner `radius` should be pickable by
+/// pointers at `positions`: some pointer must be within the shape, and no pointer over the
+/// rectangle may be outside of it.
+fn hit_test(shape: &HitShape, rect: Rect, radius: [f32; 4], positions: &[Vec2]) -> bool {
+    let (mut inside, mut outside) = (false, false);
+    for position in positions
+        .iter()
+        .filter(|position| rect.contains(**position))
+    {
+        match shape.contains(*position - rect.center(), rect.half_size(), radius) {
+            true => inside = true,
+            false => outside = true,
+        }
+    }
+    inside && !outside
+}
+
+/// Makes nodes with a [`HitShape`] pickable only while a pointer is within the shape. This
+/// runs after the pointer locations are updated and before the picking backends, so that the
+/// pointer falls through to the nodes below when it is outside of the shape.
+///
+/// Pickability is shared by all pointers, so pointers which aren't over the node's rectangle
+/// are ignored, and if one pointer is within the shape while another is over the rectangle
+/// but outside the shape, the node is not pickable, so that it never receives a hit from
+/// outside its shape.
+///
+/// When the hit shape is removed, the node's pickability is restored to that of its pointer
+/// mode.
+pub(crate) fn update_hit_shapes(
+    mut commands: Commands,
+    pointers: Query<&PointerLocation>,
+    mut removed: RemovedComponents<HitShape>,
+    unshaped: Query<Option<&PointerMode>, (With<Node>, Without<HitShape>)>,
+    mut query: Query<(
+        Entity,
+        &HitShape,
+        &Node,
+        &GlobalTransform,
+        Option<&BorderRadius>,
+        Option<&PointerMode>,
+        Option<&mut Pickable>,
+    )>,
+) {
+    for entity in removed.read() {
+        if let Ok(mode) = unshaped.get(entity) {
+            match mode.copied().unwrap_or_default().pickable() {
+                Some(pickable) => commands.entity(entity).insert(pickable),
+                None => commands.entity(entity).remove::<Pickable>(),
+            };
+        }
+    }
+
+    let positions: Vec<Vec2> = pointers
+        .iter()
+        .filter_map(|pointer| pointer.location.as_ref())
+        .map(|location| location.position)
+        .collect();
+    for (entity, shape, node, transform, border_radius, mode, pickable) in query.iter_mut() {
+        let rect = node.logical_rect(transform);
+        let half_size = rect.half_size();
+        let radius = border_radius.map_or([0.; 4], |r| {
+            [r.top_left, r.top_right, r.bottom_right, r.bottom_left]
+                .map(|val| corner_radius(val, half_size))
+        });
+        let inside = hit_test(shape, rect, radius, &positions);
+
+        let desired = match inside {
+            true => mode.copied().unwrap_or_default().pickable(),
+            false => Some(IGNORE),
+        };
+        match (pickable, desired) {
+            (Some(mut pickable), Some(desired)) => {
+                if pickable.should_block_lower != desired.should_block_lower
+                    || pickable.is_hoverable != desired.is_hoverable
+                {
+                    *pickable = desired;
+                }
+            }
+            (None, Some(desired)) => {
+                commands.entity(entity).insert(desired);
+            }
+            (Some(_), None) => {
+                commands.entity(entity).remove::<Pickable>();
+            }
+            (None, None) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hit_shape_contains() {
+        let half = Vec2::new(20., 10.);
+        assert!(HitShape::Rect.contains(Vec2::new(19., 9.), half, [0.; 4]));
+        assert!(!HitShape::Circle.contains(Vec2::new(15., 0.), half, [0.; 4]));
+        assert!(HitShape::Circle.contains(Vec2::new(0., 9.), half, [0.; 4]));
+
+        // Only the top-left corner is rounded.
+        let radius = [8., 0., 0., 0.];
+        assert!(!HitShape::RoundedRect.contains(Vec2::new(-19., -9.), half, radius));
+        assert!(HitShape::RoundedRect.contains(Vec2::new(19., -9.), half, radius));
+        assert!(HitShape::RoundedRect.contains(Vec2::new(-12., -2.), half, radius));
+
+        let diamond = HitShape::sdf(|p, half| p.x.abs() / half.x + p.y.abs() / half.y - 1.);
+        assert!(diamond.contains(Vec2::new(5., 2.), half, [0.; 4]));
+        assert!(!diamond.contains(Vec2::new(15., 5.), half, [0.; 4]));
+    }
+
+    #[test]
+    fn test_hit_test_pointers() {
+        let rect = Rect::new(0., 0., 40., 40.);
+        let hit = |positions: &[Vec2]| hit_test(&HitShape::Circle, rect, [0.; 4], positions);
+        let center = Vec2::new(20., 20.);
+        let corner = Vec2::new(2., 2.);
+        let elsewhere = Vec2::new(100., 100.);
+        assert!(hit(&[center]));
+        assert!(!hit(&[corner]));
+        assert!(!hit(&[]));
+
+        // Pointers which aren't over the node don't matter.
+        assert!(hit(&[center, elsewhere]));
+
+        // A pointer over the node but outside the shape must not hit it.
+        assert!(!hit(&[center, corner]));
+    }
+
+    #[test]
+    fn test_remove_hit_shape() {
+        use bevy::ecs::system::RunSystemOnce;
+
+        let mut world = World::new();
+        let node = world
+            .spawn((NodeBundle::default(), HitShape::Circle, IGNORE))
+            .id();
+        let disabled = world
+            .spawn((
+                NodeBundle::default(),
+                HitShape::Circle,
+                IGNORE,
+                PointerMode {
+                    enabled: false,
+                    pass_through: false,
+                },
+            ))
+            .id();
+        world.run_system_once(update_hit_shapes);
+
+        // Without the shape, the nodes go back to the pickability of their pointer mode.
+        world.entity_mut(node).remove::<HitShape>();
+        world.entity_mut(disabled).remove::<HitShape>();
+        world.run_system_once(update_hit_shapes);
+        assert!(world.get::<Pickable>(node).is_none());
+        let pickable = world.get::<Pickable>(disabled).unwrap();
+        assert!(!pickable.is_hoverable);
+    }
+}
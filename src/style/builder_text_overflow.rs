@@ -0,0 +1,336 @@
+use super::builder::StyleBuilder;
+use bevy::{
+    color::Alpha,
+    prelude::*,
+    text::{BreakLineOn, TextLayoutInfo},
+    ui::{self, UiScale},
+    window::PrimaryWindow,
+};
+
+use crate::{signal::Signal, Cx, RunContextRead, RunContextSetup};
+
+/// Width of the faded region at the end of text which overflows with [`TextOverflow::Fade`],
+/// in pixels.
+const FADE_WIDTH: f32 = 24.;
+
+/// The character appended to text which overflows with [`TextOverflow::Ellipsis`].
+const ELLIPSIS: &str = "\u{2026}";
+
+/// How single-line text which is too wide for its node is displayed. This applies to the
+/// `Text` of the node itself and of its direct children.
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TextOverflow {
+    /// The text is clipped at the edge of the node.
+    #[default]
+    Clip,
+    /// The text is shortened to fit, and ends with an ellipsis.
+    Ellipsis,
+    /// The end of the text fades out towards the edge of the node.
+    Fade,
+}
+
+/// Component which records whether the text within a node with [`TextOverflow`] is currently
+/// truncated.
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct TextTruncated(pub(crate) bool);
+
+#[allow(missing_docs)]
+pub trait StyleBuilderTextOverflow {
+    /// Keep the text within the node on a single line, and display it according to `overflow`
+    /// when it is too wide. Also clips the overflow of the node horizontally.
+    fn text_overflow(&mut self, overflow: TextOverflow) -> &mut Self;
+}
+
+impl<'a, 'w> StyleBuilderTextOverflow for StyleBuilder<'a, 'w> {
+    fn text_overflow(&mut self, overflow: TextOverflow) -> &mut Self {
+        self.style.overflow.x = ui::OverflowAxis::Clip;
+        self.style_changed = true;
+        match self.target.get_mut::<TextOverflow>() {
+            Some(mut existing) => {
+                existing.set_if_neq(overflow);
+            }
+            None => {
+                self.target.insert((overflow, TextTruncated::default()));
+            }
+        }
+        self
+    }
+}
+
+/// Method to create a signal that tracks whether the text in a node is truncated.
+pub trait CreateTruncatedSignal {
+    /// Signal that returns true when the text within the target, which should have a
+    /// [`TextOverflow`] style, is too wide to be displayed in full. This can be used to show
+    /// the full text in a tooltip only when it is needed.
+    fn create_truncated_signal(&mut self, target: Entity) -> Signal<bool>;
+}
+
+impl<'p, 'w> CreateTruncatedSignal for Cx<'p, 'w> {
+    fn create_truncated_signal(&mut self, target: Entity) -> Signal<bool> {
+        let mut entt = self.world_mut().entity_mut(target);
+        if !entt.contains::<TextTruncated>() {
+            entt.insert(TextTruncated::default());
+        }
+        self.create_derived(move |cx| {
+            cx.use_component::<TextTruncated>(target)
+                .map(|t| t.0)
+                .unwrap_or(false)
+        })
+    }
+}
+
+/// Horizontal extent of a glyph within the text, in logical pixels.
+#[derive(Debug, Clone, Copy)]
+struct GlyphExtent {
+    section: usize,
+    byte: usize,
+    left: f32,
+    right: f32,
+}
+
+/// The original content of a text whose overflow is being displayed, along with the
+/// measurements of its glyphs.
+#[derive(Component)]
+pub(crate) struct TextOverflowState {
+    original: Vec<TextSection>,
+    applied: Option<Vec<TextSection>>,
+    glyphs: Vec<GlyphExtent>,
+    width: f32,
+}
+
+/// True if the sections have the same text and font sizes, so that they have the same layout.
+fn same_values(a: &[TextSection], b: &[TextSection]) -> bool {
+    a.len() == b.len()
+        && a.iter()
+            .zip(b)
+            .all(|(a, b)| a.value == b.value && a.style.font_size == b.style.font_size)
+}
+
+fn same_sections(a: &[TextSection], b: &[TextSection]) -> bool {
+    same_values(a, b)
+        && a.iter().zip(b).all(|(a, b)| {
+            a.style.color == b.style.color
+                && a.style.font_size == b.style.font_size
+                && a.style.font == b.style.font
+        })
+}
+
+/// Shorten the text so that it ends with an ellipsis before `limit`.
+fn ellipsize(original: &[TextSection], glyphs: &[GlyphExtent], limit: f32) -> Vec<TextSection> {
+    let Some(cut) = glyphs.iter().find(|g| g.right > limit) else {
+        return original.to_vec();
+    };
+    let mut result = original[..=cut.section].to_vec();
+    let last = result.last_mut().unwrap();
+    last.value.truncate(cut.byte);
+    last.value.truncate(last.value.trim_end().len());
+    last.value.push_str(ELLIPSIS);
+    result
+}
+
+/// Split the end of the text into one section per glyph, with the alpha of each one
+/// decreasing towards `limit`. Glyphs past `limit` are removed.
+fn fade(original: &[TextSection], glyphs: &[GlyphExtent], limit: f32) -> Vec<TextSection> {
+    let start = limit - FADE_WIDTH;
+    let mut result = Vec::new();
+    for (index, section) in original.iter().enumerate() {
+        let section_glyphs: Vec<&GlyphExtent> =
+            glyphs.iter().filter(|g| g.section == index).collect();
+        // Glyphs before the faded region are kept together in a single section.
+        let Some(first) = section_glyphs.iter().position(|g| g.right > start) else {
+            result.push(section.clone());
+            continue;
+        };
+        let first_byte = section_glyphs[first].byte;
+        if first_byte > 0 {
+            result.push(TextSection::new(
+                &section.value[..first_byte],
+                section.style.clone(),
+            ));
+        }
+        for (i, glyph) in section_glyphs.iter().enumerate().skip(first) {
+            if glyph.left >= limit {
+                return result;
+            }
+            let next = section_glyphs
+                .get(i + 1)
+                .map_or(section.value.len(), |g| g.byte);
+            let center = (glyph.left + glyph.right) * 0.5;
+            let alpha = ((limit - center) / FADE_WIDTH).clamp(0., 1.);
+            let mut style = section.style.clone();
+            style.color.set_alpha(style.color.alpha() * alpha);
+            result.push(TextSection::new(&section.value[glyph.byte..next], style));
+        }
+    }
+    result
+}
+
+/// Measure the glyphs of a text, converting from physical to logical pixels.
+fn measure(layout: &TextLayoutInfo, scale: f32) -> (Vec<GlyphExtent>, f32) {
+    let glyphs = layout
+        .glyphs
+        .iter()
+        .map(|glyph| GlyphExtent {
+            section: glyph.section_index,
+            byte: glyph.byte_index,
+            left: (glyph.position.x - glyph.size.x * 0.5) / scale,
+            right: (glyph.position.x + glyph.size.x * 0.5) / scale,
+        })
+        .collect();
+    (glyphs, layout.logical_size.x / scale)
+}
+
+fn px(val: ui::Val) -> f32 {
+    match val {
+        ui::Val::Px(px) => px,
+        _ => 0.,
+    }
+}
+
+/// Shortens or fades the text within nodes with a [`TextOverflow`] style when it doesn't fit,
+/// and records whether it was truncated. Text is measured using the layout from the previous
+/// frame, so changes take effect one frame later.
+#[allow(clippy::type_complexity)]
+pub(crate) fn update_text_overflow(
+    mut commands: Commands,
+    mut containers: Query<(
+        Entity,
+        &TextOverflow,
+        &Node,
+        &GlobalTransform,
+        &Style,
+        Option<&Children>,
+        Option<&mut TextTruncated>,
+    )>,
+    mut texts: Query<(
+        &mut Text,
+        &TextLayoutInfo,
+        &Node,
+        &GlobalTransform,
+        Option<&mut TextOverflowState>,
+    )>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    ui_scale: Option<Res<UiScale>>,
+) {
+    let scale =
+        windows.get_single().map_or(1., |w| w.scale_factor()) * ui_scale.map_or(1., |s| s.0);
+    for (entity, overflow, node, transform, style, children, truncated) in containers.iter_mut() {
+        let limit_x = node.logical_rect(transform).max.x - px(style.padding.right);
+        let mut is_truncated = false;
+        let targets =
+            std::iter::once(entity).chain(children.into_iter().flat_map(|c| c.iter().copied()));
+        for target in targets {
+            let Ok((mut text, layout, text_node, text_transform, state)) = texts.get_mut(target)
+            else {
+                continue;
+            };
+            if text.linebreak_behavior != BreakLineOn::NoWrap {
+                text.linebreak_behavior = BreakLineOn::NoWrap;
+                continue;
+            }
+
+            // If the text was changed by something else, then wait for it to be laid out
+            // before measuring it. Changes to the color alone are ignored.
+            let mut state = match state {
+                Some(state) if text.is_changed() => {
+                    let ours = state
+                        .applied
+                        .as_ref()
+                        .map_or(&state.original, |applied| applied);
+                    if !same_values(ours, &text.sections) {
+                        commands.entity(target).remove::<TextOverflowState>();
+                        continue;
+                    }
+                    state
+                }
+                Some(state) => state,
+                None => {
+                    if !text.is_changed() {
+                        let (glyphs, width) = measure(layout, scale);
+                        commands.entity(target).insert(TextOverflowState {
+                            original: text.sections.clone(),
+                            applied: None,
+                            glyphs,
+                            width,
+                        });
+                    }
+                    continue;
+                }
+            };
+
+            let limit = limit_x - text_node.logical_rect(text_transform).min.x;
+            let sections = if state.width <= limit + 0.5 {
+                None
+            } else {
+                is_truncated = true;
+                let font_size = state.original.first().map_or(12., |s| s.style.font_size);
+                match overflow {
+                    TextOverflow::Clip => None,
+                    TextOverflow::Ellipsis => {
+                        Some(ellipsize(&state.original, &state.glyphs, limit - font_size))
+                    }
+                    TextOverflow::Fade => Some(fade(&state.original, &state.glyphs, limit)),
+                }
+            };
+
+            let unchanged = match (&sections, &state.applied) {
+                (None, None) => true,
+                (Some(a), Some(b)) => same_sections(a, b),
+                _ => false,
+            };
+            if !unchanged {
+                text.sections = sections.clone().unwrap_or_else(|| state.original.clone());
+                state.applied = sections;
+            }
+        }
+
+        match truncated {
+            Some(mut truncated) => {
+                truncated.set_if_neq(TextTruncated(is_truncated));
+            }
+            None => {
+                commands.entity(entity).insert(TextTruncated(is_truncated));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn glyphs(text: &str, advance: f32) -> Vec<GlyphExtent> {
+        text.char_indices()
+            .map(|(byte, _)| GlyphExtent {
+                section: 0,
+                byte,
+                left: byte as f32 * advance,
+                right: (byte + 1) as f32 * advance,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_ellipsize() {
+        let original = vec![TextSection::new("Hello world", TextStyle::default())];
+        let glyphs = glyphs("Hello world", 10.);
+        let result = ellipsize(&original, &glyphs, 65.);
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].value, "Hello\u{2026}");
+
+        let result = ellipsize(&original, &glyphs, 200.);
+        assert_eq!(result[0].value, "Hello world");
+    }
+
+    #[test]
+    fn test_fade() {
+        let original = vec![TextSection::new("abcdefghij", TextStyle::default())];
+        let glyphs = glyphs("abcdefghij", 10.);
+        let result = fade(&original, &glyphs, 54.);
+        let text: String = result.iter().map(|s| s.value.as_str()).collect();
+        assert_eq!(text, "abcdef");
+        assert_eq!(result[0].value, "abc");
+        assert_eq!(result[0].style.color.alpha(), 1.);
+        assert!(result[1].style.color.alpha() > result[3].style.color.alpha());
+    }
+}
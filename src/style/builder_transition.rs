@@ -0,0 +1,243 @@
+use bevy::{color::Mix, prelude::*, ui};
+
+use super::builder::StyleBuilder;
+use crate::AccessibilityPreferences;
+
+/// A style property which can be animated via [`StyleBuilderTransition::transition`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TransitionProperty {
+    /// The `BackgroundColor` component.
+    BackgroundColor,
+    /// The `BorderColor` component.
+    BorderColor,
+    /// `Style::width`, when specified in pixels.
+    Width,
+    /// `Style::height`, when specified in pixels.
+    Height,
+    /// `Style::left`, when specified in pixels.
+    Left,
+    /// `Style::top`, when specified in pixels.
+    Top,
+}
+
+/// Timing function for style transitions.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Easing {
+    /// Constant speed.
+    Linear,
+    /// Start slow, then accelerate.
+    EaseIn,
+    /// Start fast, then decelerate.
+    EaseOut,
+    /// Start and end slow.
+    #[default]
+    EaseInOut,
+}
+
+impl Easing {
+    /// Map a linear progress value in the range `[0, 1]` to an eased value.
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0., 1.);
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t,
+            Easing::EaseOut => t * (2. - t),
+            Easing::EaseInOut => t * t * (3. - 2. * t),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum TransitionValue {
+    Color(LinearRgba),
+    Length(f32),
+}
+
+impl TransitionValue {
+    fn lerp(self, other: Self, t: f32) -> Self {
+        match (self, other) {
+            (Self::Color(a), Self::Color(b)) => Self::Color(a.mix(&b, t)),
+            (Self::Length(a), Self::Length(b)) => Self::Length(a + (b - a) * t),
+            (_, b) => b,
+        }
+    }
+}
+
+struct TransitionEntry {
+    property: TransitionProperty,
+    duration: f32,
+    easing: Easing,
+    /// The value most recently displayed.
+    displayed: Option<TransitionValue>,
+    /// The transition currently in progress: start value, end value and elapsed time.
+    active: Option<(TransitionValue, TransitionValue, f32)>,
+}
+
+/// Component which holds the transition declarations for an entity. Whenever one of the
+/// listed properties is changed - whether by a style builder or a reactive effect - the
+/// new value is animated rather than applied immediately.
+#[derive(Component, Default)]
+pub struct StyleTransitions(Vec<TransitionEntry>);
+
+#[allow(missing_docs)]
+pub trait StyleBuilderTransition {
+    /// Animate future changes to the given properties over `duration` seconds.
+    fn transition(
+        &mut self,
+        properties: &[TransitionProperty],
+        duration: f32,
+        easing: Easing,
+    ) -> &mut Self;
+}
+
+impl<'a, 'w> StyleBuilderTransition for StyleBuilder<'a, 'w> {
+    fn transition(
+        &mut self,
+        properties: &[TransitionProperty],
+        duration: f32,
+        easing: Easing,
+    ) -> &mut Self {
+        if !self.target.contains::<StyleTransitions>() {
+            self.target.insert(StyleTransitions::default());
+        }
+        let mut transitions = self.target.get_mut::<StyleTransitions>().unwrap();
+        for property in properties {
+            match transitions.0.iter_mut().find(|e| e.property == *property) {
+                Some(entry) => {
+                    entry.duration = duration;
+                    entry.easing = easing;
+                }
+                None => transitions.0.push(TransitionEntry {
+                    property: *property,
+                    duration,
+                    easing,
+                    displayed: None,
+                    active: None,
+                }),
+            }
+        }
+        self
+    }
+}
+
+fn length_of(val: ui::Val) -> Option<TransitionValue> {
+    match val {
+        ui::Val::Px(px) => Some(TransitionValue::Length(px)),
+        _ => None,
+    }
+}
+
+fn read_property(
+    property: TransitionProperty,
+    bg: Option<&BackgroundColor>,
+    border: Option<&BorderColor>,
+    style: Option<&Style>,
+) -> Option<TransitionValue> {
+    match property {
+        TransitionProperty::BackgroundColor => bg.map(|c| TransitionValue::Color(c.0.into())),
+        TransitionProperty::BorderColor => border.map(|c| TransitionValue::Color(c.0.into())),
+        TransitionProperty::Width => style.and_then(|s| length_of(s.width)),
+        TransitionProperty::Height => style.and_then(|s| length_of(s.height)),
+        TransitionProperty::Left => style.and_then(|s| length_of(s.left)),
+        TransitionProperty::Top => style.and_then(|s| length_of(s.top)),
+    }
+}
+
+fn write_property(
+    property: TransitionProperty,
+    value: TransitionValue,
+    bg: Option<&mut Mut<BackgroundColor>>,
+    border: Option<&mut Mut<BorderColor>>,
+    style: Option<&mut Mut<Style>>,
+) {
+    match (property, value) {
+        (TransitionProperty::BackgroundColor, TransitionValue::Color(c)) => {
+            if let Some(bg) = bg {
+                bg.0 = c.into();
+            }
+        }
+        (TransitionProperty::BorderColor, TransitionValue::Color(c)) => {
+            if let Some(border) = border {
+                border.0 = c.into();
+            }
+        }
+        (_, TransitionValue::Length(px)) => {
+            if let Some(style) = style {
+                let val = ui::Val::Px(px);
+                match property {
+                    TransitionProperty::Width => style.width = val,
+                    TransitionProperty::Height => style.height = val,
+                    TransitionProperty::Left => style.left = val,
+                    TransitionProperty::Top => style.top = val,
+                    _ => {}
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// System which detects changes to transitioned properties and animates them. When the user
+/// prefers reduced motion, new values are applied immediately.
+pub(crate) fn animate_style_transitions(
+    time: Option<Res<Time>>,
+    preferences: Option<Res<AccessibilityPreferences>>,
+    mut query: Query<(
+        &mut StyleTransitions,
+        Option<&mut BackgroundColor>,
+        Option<&mut BorderColor>,
+        Option<&mut Style>,
+    )>,
+) {
+    let delta = time.map(|t| t.delta_seconds()).unwrap_or(0.);
+    let reduced_motion = preferences.map_or(false, |p| p.reduced_motion);
+    for (mut transitions, mut bg, mut border, mut style) in query.iter_mut() {
+        for entry in transitions.0.iter_mut() {
+            let Some(actual) = read_property(
+                entry.property,
+                bg.as_deref(),
+                border.as_deref(),
+                style.as_deref(),
+            ) else {
+                entry.displayed = None;
+                entry.active = None;
+                continue;
+            };
+
+            let Some(displayed) = entry.displayed else {
+                // First time we've seen this property: adopt the current value.
+                entry.displayed = Some(actual);
+                continue;
+            };
+
+            // A value other than the one we last displayed means a new target was set.
+            if actual != displayed && entry.active.map_or(true, |(_, to, _)| to != actual) {
+                if entry.duration <= 0. || reduced_motion {
+                    entry.displayed = Some(actual);
+                    entry.active = None;
+                    continue;
+                }
+                entry.active = Some((displayed, actual, 0.));
+            }
+
+            if let Some((from, to, elapsed)) = entry.active {
+                let elapsed = elapsed + delta;
+                let value = if elapsed >= entry.duration {
+                    entry.active = None;
+                    to
+                } else {
+                    entry.active = Some((from, to, elapsed));
+                    from.lerp(to, entry.easing.apply(elapsed / entry.duration))
+                };
+                write_property(
+                    entry.property,
+                    value,
+                    bg.as_mut(),
+                    border.as_mut(),
+                    style.as_mut(),
+                );
+                entry.displayed = Some(value);
+            }
+        }
+    }
+}
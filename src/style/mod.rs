@@ -1,33 +1,57 @@
 // mod atlas_loader;
+mod breakpoint;
 mod builder;
 mod builder_background;
 mod builder_border_color;
 mod builder_border_radius;
+mod builder_box_shadow;
+mod builder_cursor;
 mod builder_font;
 mod builder_layout;
 mod builder_outline;
 mod builder_pointer_events;
+mod builder_text_overflow;
 // mod builder_texture_atlas;
+mod builder_transition;
 mod builder_z_index;
+mod named_styles;
+mod sliced_image;
 
 use std::sync::Arc;
 
-use crate::{effect_target::EffectTarget, Element, EntityEffect, TrackingScope};
+use crate::{
+    effect_target::EffectTarget,
+    zoom::{view_zoom, ZoomedValues},
+    Element, EntityEffect, TrackingScope,
+};
 // pub use atlas_loader::TextureAtlasLoader;
 use bevy::{prelude::*, ui};
+pub use breakpoint::{Breakpoint, Breakpoints, CreateBreakpointSignal, WithBreakpointStyles};
 pub use builder::StyleBuilder;
 pub use builder_background::StyleBuilderBackground;
 pub use builder_border_color::StyleBuilderBorderColor;
 pub use builder_border_radius::StyleBuilderBorderRadius;
+pub use builder_box_shadow::{BoxShadow, StyleBuilderBoxShadow};
+pub use builder_cursor::{HoverCursor, StyleBuilderCursor};
 pub use builder_font::StyleBuilderFont;
 pub use builder_layout::StyleBuilderLayout;
 pub use builder_outline::StyleBuilderOutline;
-pub use builder_pointer_events::StyleBuilderPointerEvents;
+pub use builder_pointer_events::{HitShape, StyleBuilderPointerEvents};
+pub use builder_text_overflow::{CreateTruncatedSignal, StyleBuilderTextOverflow, TextOverflow};
 // pub use builder_texture_atlas::StyleBuilderTextureAtlas;
+pub use builder_transition::{
+    Easing, StyleBuilderTransition, StyleTransitions, TransitionProperty,
+};
 pub use builder_z_index::StyleBuilderZIndex;
 use impl_trait_for_tuples::*;
+pub use named_styles::{NamedStyles, StyleRef};
+pub use sliced_image::WithSlicedImage;
 
+pub(crate) use builder_cursor::update_cursor_icon;
 pub(crate) use builder_font::{InheritableFontStyles, TextStyleChanged};
+pub(crate) use builder_pointer_events::update_hit_shapes;
+pub(crate) use builder_text_overflow::update_text_overflow;
+pub(crate) use builder_transition::animate_style_transitions;
 
 /// `StyleTuple` - a variable-length tuple of [`StyleHandle`]s.
 pub trait StyleTuple: Sync + Send {
@@ -91,25 +115,52 @@ impl<S: StyleTuple> EntityEffect for ApplyStylesEffect<S> {
     // For a style builder, run the builder over the target entity.
     fn start(
         &mut self,
-        _owner: Entity,
+        owner: Entity,
         target: Entity,
         world: &mut World,
         _tracking: &mut TrackingScope,
     ) {
-        let mut target = world.entity_mut(target);
-        let mut style = ui::Style::default();
-        if let Some(s) = target.get::<ui::Style>() {
-            style.clone_from(s);
-        }
-        let mut ctx = StyleBuilder {
-            target: &mut target,
-            style,
-            style_changed: false,
-        };
-        self.styles.apply(&mut ctx);
-        if ctx.style_changed {
-            ctx.target.insert(ctx.style);
+        apply_styles(world, owner, target, &self.styles);
+    }
+}
+
+/// Run a set of style builders over a target entity. `owner` is the view entity (or one of
+/// its reactions) which the styles belong to; pixel lengths are scaled by its [`Zoom`].
+///
+/// [`Zoom`]: crate::Zoom
+pub(crate) fn apply_styles<S: StyleTuple + ?Sized>(
+    world: &mut World,
+    owner: Entity,
+    target: Entity,
+    styles: &S,
+) {
+    let zoom = view_zoom(world, owner);
+    let mut target = world.entity_mut(target);
+    let mut style = target.get::<ui::Style>().cloned().unwrap_or_default();
+    let mut zoomed = match (target.take::<ZoomedValues>(), zoom) {
+        (Some(zoomed), _) => Some(zoomed),
+        (None, Some(_)) => Some(ZoomedValues::new(owner)),
+        (None, None) => None,
+    };
+    // Apply on top of the unscaled lengths, so that unchanged lengths are not scaled twice.
+    if let Some(zoomed) = zoomed.as_mut() {
+        zoomed.unscale(&mut style);
+    }
+    let before = style.clone();
+    let mut ctx = StyleBuilder {
+        target: &mut target,
+        style,
+        style_changed: false,
+    };
+    styles.apply(&mut ctx);
+    if ctx.style_changed {
+        if let Some(zoomed) = zoomed.as_mut() {
+            zoomed.scale(&before, &mut ctx.style, zoom.unwrap_or(1.));
         }
+        ctx.target.insert(ctx.style);
+    }
+    if let Some(zoomed) = zoomed {
+        target.insert(zoomed);
     }
 }
 
@@ -146,3 +197,35 @@ impl StyleHandle {
         Self { style: None }
     }
 }
+
+/// A set of style overrides for the named internal elements ("parts") of a widget. Widgets
+/// that support part styles document the names of their parts; callers can then style
+/// those elements without having to fork the widget.
+#[derive(Default, Clone)]
+pub struct PartStyles(Vec<(&'static str, StyleHandle)>);
+
+impl PartStyles {
+    /// Construct an empty set of part styles.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a style override for the named part. If the part already has an override,
+    /// the new styles are applied after the existing ones.
+    pub fn insert<S: StyleTuple + 'static>(&mut self, part: &'static str, style: S) {
+        let handle = style.into_handle();
+        match self.0.iter_mut().find(|(name, _)| *name == part) {
+            Some((_, existing)) => *existing = (existing.clone(), handle).into_handle(),
+            None => self.0.push((part, handle)),
+        }
+    }
+
+    /// Return the style override for the named part, or an empty handle if there is none.
+    pub fn get(&self, part: &str) -> StyleHandle {
+        self.0
+            .iter()
+            .find(|(name, _)| *name == part)
+            .map(|(_, style)| style.clone())
+            .unwrap_or_default()
+    }
+}
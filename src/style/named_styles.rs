@@ -0,0 +1,83 @@
+use bevy::{prelude::*, utils::HashMap};
+
+use super::{StyleHandle, StyleTuple};
+
+/// Resource which maps names to styles, so that styles can be referred to from data such as
+/// scenes, where closures can't be stored.
+#[derive(Resource, Default)]
+pub struct NamedStyles(HashMap<String, StyleHandle>);
+
+impl NamedStyles {
+    /// Register a style under `name`, replacing any previous style with that name.
+    pub fn insert<S: StyleTuple + 'static>(&mut self, name: impl Into<String>, style: S) {
+        self.0.insert(name.into(), style.into_handle());
+    }
+
+    /// Returns the style registered under `name`, if any.
+    pub fn get(&self, name: &str) -> Option<&StyleHandle> {
+        self.0.get(name)
+    }
+}
+
+/// A reflectable reference to a style in the [`NamedStyles`] registry. An empty name refers
+/// to no style.
+#[derive(Clone, Default, Debug, PartialEq, Eq, Reflect)]
+#[reflect(Default)]
+pub struct StyleRef(pub String);
+
+impl StyleRef {
+    /// Look up the referenced style. Returns an empty style handle if the name is empty or
+    /// has not been registered.
+    pub fn resolve(&self, world: &World) -> StyleHandle {
+        if self.0.is_empty() {
+            return StyleHandle::none();
+        }
+        match world
+            .get_resource::<NamedStyles>()
+            .and_then(|styles| styles.get(&self.0))
+        {
+            Some(style) => style.clone(),
+            None => {
+                warn!("Unknown style name: {}", self.0);
+                StyleHandle::none()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::{StyleBuilder, StyleBuilderLayout};
+
+    #[test]
+    fn test_resolve_style_ref() {
+        let mut world = World::new();
+        let panel = StyleRef("panel".to_string());
+        assert!(panel.resolve(&world).style.is_none());
+
+        let mut styles = NamedStyles::default();
+        styles.insert("panel", |ss: &mut StyleBuilder| {
+            ss.width(100);
+        });
+        world.insert_resource(styles);
+        let registered = world
+            .resource::<NamedStyles>()
+            .get("panel")
+            .and_then(|handle| handle.style.clone())
+            .unwrap();
+        assert!(Arc::ptr_eq(
+            &panel.resolve(&world).style.unwrap(),
+            &registered
+        ));
+
+        // Empty and unknown names resolve to no style.
+        assert!(StyleRef::default().resolve(&world).style.is_none());
+        assert!(StyleRef("missing".to_string())
+            .resolve(&world)
+            .style
+            .is_none());
+    }
+}
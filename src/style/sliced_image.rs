@@ -0,0 +1,47 @@
+use bevy::{
+    prelude::*,
+    sprite::{ImageScaleMode, TextureSlicer},
+    ui::UiImage,
+};
+
+use crate::{signal::IntoSignal, EffectTarget, Element};
+
+/// Trait to set a nine-slice background image which updates reactively, for skinned widgets
+/// whose artwork or slice borders can change at runtime.
+pub trait WithSlicedImage {
+    /// Set the background image of the element, sliced according to `slicer`. Either argument
+    /// may be a signal, in which case the image is updated whenever it changes.
+    fn sliced_image(
+        self,
+        image: impl IntoSignal<Handle<Image>>,
+        slicer: impl IntoSignal<TextureSlicer>,
+    ) -> Self;
+}
+
+impl<B: Bundle + Default> WithSlicedImage for Element<B> {
+    fn sliced_image(
+        self,
+        image: impl IntoSignal<Handle<Image>>,
+        slicer: impl IntoSignal<TextureSlicer>,
+    ) -> Self {
+        let image = image.into_signal();
+        let slicer = slicer.into_signal();
+        self.create_effect(move |cx, target| {
+            let texture = image.get_clone(cx);
+            let slicer = slicer.get_clone(cx);
+            let mut entt = cx.world_mut().entity_mut(target);
+            match entt.get_mut::<UiImage>() {
+                Some(mut uii) => {
+                    uii.texture = texture;
+                }
+                None => {
+                    entt.insert(UiImage {
+                        texture,
+                        ..default()
+                    });
+                }
+            }
+            entt.insert(ImageScaleMode::Sliced(slicer));
+        })
+    }
+}
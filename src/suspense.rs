@@ -0,0 +1,254 @@
+use bevy::prelude::*;
+
+use crate::node_span::NodeSpan;
+use crate::{
+    DespawnScopes, DisplayNodeChanged, IntoView, Rcx, RunContextRead, TrackingScope, View, ViewRef,
+};
+
+/// Component which records whether a [`Suspense`] view is ready to display its children.
+#[derive(Component, Default)]
+pub(crate) struct SuspenseState {
+    ready: bool,
+}
+
+/// Component placed on aggregating [`Suspense`] views, listing the descendant suspense views
+/// that must be ready before the aggregating view reveals its children.
+#[derive(Component, Default)]
+pub(crate) struct SuspenseGroup(Vec<Entity>);
+
+/// A view which displays a fallback view until a condition becomes true, and then replaces
+/// the fallback with its children. This is useful for deferring the display of content until
+/// some asset or asynchronous task is ready. Once the children have been revealed, they
+/// remain visible even if the condition later becomes false.
+///
+/// In aggregating mode, the children are built immediately and attached alongside the
+/// fallback, but with `Display::None` so that they take up no space. The view also waits for
+/// any descendant `Suspense` views to become ready. This allows a parent to show a single
+/// fallback while waiting on all nested loading states.
+pub struct Suspense<Test: Fn(&Rcx) -> bool> {
+    test: Test,
+    children: ViewRef,
+    fallback: ViewRef,
+    aggregate: bool,
+    children_entity: Option<Entity>,
+    fallback_entity: Option<Entity>,
+    revealed: bool,
+    /// Children display nodes hidden in aggregating mode, along with their original display.
+    hidden: Vec<(Entity, Display)>,
+}
+
+impl<Test: Fn(&Rcx) -> bool> Suspense<Test> {
+    /// Construct a new `Suspense` view which waits for `test` to return true.
+    pub fn new(test: Test) -> Self {
+        Self {
+            test,
+            children: ViewRef::default(),
+            fallback: ViewRef::default(),
+            aggregate: false,
+            children_entity: None,
+            fallback_entity: None,
+            revealed: false,
+            hidden: Vec::new(),
+        }
+    }
+
+    /// Set the view to display once the condition is true.
+    pub fn children(mut self, children: impl IntoView) -> Self {
+        self.children = children.into_view();
+        self
+    }
+
+    /// Set the view to display while waiting for the condition.
+    pub fn fallback(mut self, fallback: impl IntoView) -> Self {
+        self.fallback = fallback.into_view();
+        self
+    }
+
+    /// If true, build the children immediately and also wait for any descendant `Suspense`
+    /// views to become ready before revealing them.
+    pub fn aggregate(mut self, aggregate: bool) -> Self {
+        self.aggregate = aggregate;
+        self
+    }
+
+    /// Take the display nodes of the children out of the layout while they are waiting.
+    fn hide_children(&mut self, world: &mut World) {
+        let mut nodes = Vec::new();
+        self.children.nodes().flatten(&mut nodes);
+        for node in nodes {
+            if let Some(mut style) = world.get_mut::<Style>(node) {
+                self.hidden.push((node, style.display));
+                style.display = Display::None;
+            }
+        }
+    }
+
+    /// Restore the display of the children hidden by `hide_children`.
+    fn show_children(&mut self, world: &mut World) {
+        for (node, display) in self.hidden.drain(..) {
+            if let Some(mut style) = world.get_mut::<Style>(node) {
+                style.display = display;
+            }
+        }
+    }
+}
+
+impl<Test: Fn(&Rcx) -> bool> View for Suspense<Test> {
+    fn nodes(&self) -> NodeSpan {
+        let fallback = match self.fallback_entity {
+            Some(_) => self.fallback.nodes(),
+            None => NodeSpan::Empty,
+        };
+        match (self.revealed, self.children_entity) {
+            (true, _) => self.children.nodes(),
+            // Hidden children stay attached, so that they are laid out as soon as they are shown.
+            (false, Some(_)) => NodeSpan::Fragment(Box::new([fallback, self.children.nodes()])),
+            (false, None) => fallback,
+        }
+    }
+
+    fn build(&mut self, view_entity: Entity, world: &mut World) {
+        world
+            .entity_mut(view_entity)
+            .insert((Name::new("Suspense"), SuspenseState::default()));
+
+        // Register with the nearest aggregating ancestor, if any.
+        let mut ancestor = world.get::<Parent>(view_entity).map(|p| p.get());
+        while let Some(entity) = ancestor {
+            if let Some(mut group) = world.get_mut::<SuspenseGroup>(entity) {
+                group.0.push(view_entity);
+                break;
+            }
+            ancestor = world.get::<Parent>(entity).map(|p| p.get());
+        }
+
+        if self.aggregate {
+            world
+                .entity_mut(view_entity)
+                .insert(SuspenseGroup::default());
+            self.children_entity = Some(ViewRef::spawn(&self.children, view_entity, world));
+            self.hide_children(world);
+        }
+
+        let mut tracking = TrackingScope::new(world.change_tick());
+        self.react(view_entity, world, &mut tracking);
+        world.entity_mut(view_entity).insert(tracking);
+    }
+
+    fn react(&mut self, view_entity: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        if self.revealed {
+            return;
+        }
+
+        let ready = {
+            let members = world
+                .get::<SuspenseGroup>(view_entity)
+                .map(|group| group.0.clone())
+                .unwrap_or_default();
+            let re = Rcx::new(world, view_entity, tracking);
+            // Evaluate all members so that each one is tracked as a dependency.
+            let members_ready = members.iter().fold(true, |all, member| {
+                let ready = re
+                    .use_component::<SuspenseState>(*member)
+                    .map_or(true, |state| state.ready);
+                all && ready
+            });
+            (self.test)(&re) && members_ready
+        };
+
+        if ready {
+            if let Some(mut state) = world.get_mut::<SuspenseState>(view_entity) {
+                state.ready = true;
+            }
+            if let Some(fallback_entity) = self.fallback_entity.take() {
+                self.fallback.raze(fallback_entity, world);
+            }
+            match self.children_entity {
+                Some(_) => self.show_children(world),
+                None => {
+                    self.children_entity = Some(ViewRef::spawn(&self.children, view_entity, world));
+                }
+            }
+            self.revealed = true;
+            world.entity_mut(view_entity).insert(DisplayNodeChanged);
+        } else if self.fallback_entity.is_none() {
+            self.fallback_entity = Some(ViewRef::spawn(&self.fallback, view_entity, world));
+            world.entity_mut(view_entity).insert(DisplayNodeChanged);
+        }
+    }
+
+    fn raze(&mut self, view_entity: Entity, world: &mut World) {
+        if let Some(fallback_entity) = self.fallback_entity.take() {
+            self.fallback.raze(fallback_entity, world);
+        }
+        if let Some(children_entity) = self.children_entity.take() {
+            self.children.raze(children_entity, world);
+        }
+        self.revealed = false;
+        self.hidden.clear();
+        world.despawn_owned_recursive(view_entity);
+    }
+}
+
+impl<Test: Send + Sync + 'static + Fn(&Rcx) -> bool> IntoView for Suspense<Test> {
+    fn into_view(self) -> ViewRef {
+        ViewRef::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        tracking_scope::run_reactions, view::attach_child_views, Cx, EffectTarget, Element,
+        Mutable, RunContextSetup,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_aggregate_keeps_children_attached() {
+        let mut world = World::default();
+        let owner = world.spawn_empty().id();
+        let mut scope = TrackingScope::new(world.change_tick());
+        let mut cx = Cx::new(&mut world, owner, &mut scope);
+        let ready: Mutable<bool> = cx.create_mutable(false);
+        world.entity_mut(owner).insert(scope);
+
+        let fallback = world.spawn_empty().id();
+        let content = world.spawn_empty().id();
+        let view = Element::<NodeBundle>::new()
+            .children(
+                Suspense::new(move |rcx| ready.get(rcx))
+                    .aggregate(true)
+                    .fallback(Element::<NodeBundle>::for_entity(fallback))
+                    .children(Element::<NodeBundle>::for_entity(content).insert(Style {
+                        display: Display::Grid,
+                        ..default()
+                    })),
+            )
+            .into_view();
+        ViewRef::spawn(&view, owner, &mut world);
+        let NodeSpan::Node(display) = view.nodes() else {
+            panic!("element should have a display node");
+        };
+
+        // While waiting, the children are attached next to the fallback, but not displayed.
+        attach_child_views(&mut world);
+        assert_eq!(
+            world.get::<Children>(display).unwrap().to_vec(),
+            vec![fallback, content]
+        );
+        assert_eq!(world.get::<Style>(content).unwrap().display, Display::None);
+
+        ready.set(&mut world, true);
+        world.flush_commands();
+        run_reactions(&mut world);
+        attach_child_views(&mut world);
+        assert_eq!(
+            world.get::<Children>(display).unwrap().to_vec(),
+            vec![content]
+        );
+        assert_eq!(world.get::<Style>(content).unwrap().display, Display::Grid);
+        assert!(world.get_entity(fallback).is_none());
+    }
+}
@@ -0,0 +1,129 @@
+use bevy::prelude::*;
+
+use crate::node_span::NodeSpan;
+use crate::{
+    DespawnScopes, DisplayNodeChanged, IntoView, Rcx, Signal, TrackingScope, View, ViewRef,
+};
+
+type CaseFactory = Box<dyn Fn() -> ViewRef + Send + Sync>;
+
+/// A conditional view which renders one of several branches depending on the value of a
+/// signal. When the value changes, the previous branch is razed and the matching branch is
+/// built. If the new value selects the same branch as before, nothing is rebuilt.
+pub struct Switch<T: PartialEq + Clone + Send + Sync + 'static> {
+    value: Signal<T>,
+    cases: Vec<(T, CaseFactory)>,
+    fallback: Option<CaseFactory>,
+    /// Index of the active case (`cases.len()` for the fallback), with its view.
+    state: Option<(usize, ViewRef, Entity)>,
+    /// Placeholder node marking the position of the active branch while it is empty.
+    anchor: Option<Entity>,
+}
+
+impl<T: PartialEq + Clone + Send + Sync + 'static> Switch<T> {
+    /// Construct a new `Switch` view which selects a branch based on `value`.
+    pub fn new(value: Signal<T>) -> Self {
+        Self {
+            value,
+            cases: Vec::new(),
+            fallback: None,
+            state: None,
+            anchor: None,
+        }
+    }
+
+    /// The display nodes of the active branch, not including the anchor.
+    fn branch_nodes(&self) -> NodeSpan {
+        match self.state {
+            Some((_, ref view, _)) => view.nodes(),
+            None => NodeSpan::Empty,
+        }
+    }
+
+    /// Add a branch which is displayed when the signal is equal to `value`.
+    pub fn case<V: IntoView, F: Fn() -> V + Send + Sync + 'static>(
+        mut self,
+        value: T,
+        factory: F,
+    ) -> Self {
+        self.cases
+            .push((value, Box::new(move || factory().into_view())));
+        self
+    }
+
+    /// Set the branch to display when no case matches.
+    pub fn fallback<V: IntoView, F: Fn() -> V + Send + Sync + 'static>(
+        mut self,
+        factory: F,
+    ) -> Self {
+        self.fallback = Some(Box::new(move || factory().into_view()));
+        self
+    }
+}
+
+impl<T: PartialEq + Clone + Send + Sync + 'static> View for Switch<T> {
+    fn nodes(&self) -> NodeSpan {
+        NodeSpan::anchored(self.anchor, self.branch_nodes())
+    }
+
+    fn build(&mut self, view_entity: Entity, world: &mut World) {
+        world.entity_mut(view_entity).insert(Name::new("Switch"));
+        let mut tracking = TrackingScope::new(world.change_tick());
+        self.react(view_entity, world, &mut tracking);
+        world.entity_mut(view_entity).insert(tracking);
+    }
+
+    fn react(&mut self, view_entity: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        let index = {
+            let re = Rcx::new(world, view_entity, tracking);
+            self.value.map(&re, |value| {
+                self.cases
+                    .iter()
+                    .position(|(case, _)| case == value)
+                    .unwrap_or(self.cases.len())
+            })
+        };
+
+        // Don't rebuild if the same branch is still selected.
+        if matches!(self.state, Some((active, _, _)) if active == index) {
+            return;
+        }
+        let had_branch = self.state.is_some();
+        let old_nodes = self.nodes();
+        let position = old_nodes.position(world);
+        if let Some((_, view, entity)) = self.state.take() {
+            view.raze(entity, world);
+        }
+
+        let factory = match self.cases.get(index) {
+            Some((_, factory)) => Some(factory),
+            None => self.fallback.as_ref(),
+        };
+        if let Some(factory) = factory {
+            let view = factory();
+            let entity = ViewRef::spawn(&view, view_entity, world);
+            self.state = Some((index, view, entity));
+        }
+        NodeSpan::update_anchor(&mut self.anchor, &self.branch_nodes(), world);
+        if had_branch || self.state.is_some() {
+            NodeSpan::splice(position, &old_nodes, &self.nodes(), world);
+            world.entity_mut(view_entity).insert(DisplayNodeChanged);
+        }
+    }
+
+    fn raze(&mut self, view_entity: Entity, world: &mut World) {
+        if let Some((_, view, entity)) = self.state.take() {
+            view.raze(entity, world);
+        }
+        if let Some(anchor) = self.anchor.take() {
+            NodeSpan::despawn_anchor(anchor, world);
+        }
+        world.despawn_owned_recursive(view_entity);
+    }
+}
+
+impl<T: PartialEq + Clone + Send + Sync + 'static> IntoView for Switch<T> {
+    fn into_view(self) -> ViewRef {
+        ViewRef::new(self)
+    }
+}
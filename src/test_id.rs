@@ -0,0 +1,102 @@
+use std::borrow::Cow;
+
+use bevy::{prelude::*, utils::HashMap};
+
+/// Component which assigns a stable string id to an entity, so that it can be located by
+/// test automation, guided tours or remote control surfaces regardless of where it lives
+/// in the entity hierarchy.
+#[derive(Component, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TestId(pub Cow<'static, str>);
+
+impl TestId {
+    /// Construct a new `TestId`.
+    pub fn new(id: impl Into<Cow<'static, str>>) -> Self {
+        Self(id.into())
+    }
+}
+
+/// Event which is sent whenever an id is added to or removed from the [`TestIdRegistry`].
+#[derive(Event, Clone, Debug)]
+pub enum TestIdEvent {
+    /// An entity with the given id was registered.
+    Added(Cow<'static, str>, Entity),
+    /// The entity with the given id was despawned, or its id was removed.
+    Removed(Cow<'static, str>, Entity),
+}
+
+/// Resource which maps [`TestId`]s to the entities which currently have them.
+#[derive(Resource, Default)]
+pub struct TestIdRegistry {
+    entities: HashMap<Cow<'static, str>, Entity>,
+    ids: HashMap<Entity, Cow<'static, str>>,
+}
+
+impl TestIdRegistry {
+    /// Look up the entity with the given id.
+    pub fn get(&self, id: &str) -> Option<Entity> {
+        self.entities.get(id).copied()
+    }
+
+    /// Look up the id of the given entity.
+    pub fn id_of(&self, entity: Entity) -> Option<&str> {
+        self.ids.get(&entity).map(|id| id.as_ref())
+    }
+
+    /// Iterate over all registered ids and their entities.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, Entity)> {
+        self.entities
+            .iter()
+            .map(|(id, entity)| (id.as_ref(), *entity))
+    }
+
+    fn remove(&mut self, entity: Entity) -> Option<Cow<'static, str>> {
+        let id = self.ids.remove(&entity)?;
+        if self.entities.get(&id) == Some(&entity) {
+            self.entities.remove(&id);
+        }
+        Some(id)
+    }
+}
+
+pub(crate) fn update_test_id_registry(
+    mut registry: ResMut<TestIdRegistry>,
+    query: Query<(Entity, &TestId), Changed<TestId>>,
+    mut removed: RemovedComponents<TestId>,
+    mut events: EventWriter<TestIdEvent>,
+) {
+    for entity in removed.read() {
+        if let Some(id) = registry.remove(entity) {
+            events.send(TestIdEvent::Removed(id, entity));
+        }
+    }
+
+    for (entity, test_id) in query.iter() {
+        if registry.id_of(entity) == Some(test_id.0.as_ref()) {
+            continue;
+        }
+        if let Some(id) = registry.remove(entity) {
+            events.send(TestIdEvent::Removed(id, entity));
+        }
+        if let Some(prev) = registry.get(&test_id.0) {
+            warn!(
+                "Duplicate test id {:?} for {:?}, already assigned to {:?}",
+                test_id.0, entity, prev
+            );
+        }
+        registry.entities.insert(test_id.0.clone(), entity);
+        registry.ids.insert(entity, test_id.0.clone());
+        events.send(TestIdEvent::Added(test_id.0.clone(), entity));
+    }
+}
+
+/// Plugin which maintains the [`TestIdRegistry`]. This is not added by default; apps which
+/// want to locate widgets by id should add it explicitly.
+pub struct TestIdPlugin;
+
+impl Plugin for TestIdPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TestIdRegistry>()
+            .add_event::<TestIdEvent>()
+            .add_systems(PostUpdate, update_test_id_registry);
+    }
+}
@@ -5,6 +5,7 @@ use crate::{
     style::{InheritableFontStyles, TextStyleChanged},
     tracking_scope::TrackingScope,
     view::View,
+    zoom::{ZoomedText, ZoomedValues},
     DespawnScopes, IntoView, Rcx, ViewRef,
 };
 
@@ -146,12 +147,19 @@ pub(crate) fn update_text_styles(
     mut commands: Commands,
     mut query: Query<(Entity, &mut Text), With<TextStyleChanged>>,
     inherited: Query<&InheritableFontStyles>,
+    zoomed: Query<&ZoomedValues>,
     parents: Query<&Parent>,
     server: Res<AssetServer>,
 ) {
     for (entity, mut text) in query.iter_mut() {
         let mut styles = InheritableFontStyles::default();
 
+        // The font size is scaled by the zoom of the nearest styled node in a zoomed view.
+        let zoom = parents
+            .iter_ancestors(entity)
+            .find_map(|ancestor| zoomed.get(ancestor).ok())
+            .map(|values| values.zoom);
+
         // Search parents for inherited styles.
         let mut ancestor = entity;
         loop {
@@ -183,13 +191,19 @@ pub(crate) fn update_text_styles(
 
         let style = TextStyle {
             font: styles.font.unwrap_or_default(),
-            font_size: styles.font_size.unwrap_or(12.),
+            font_size: styles.font_size.unwrap_or(12.) * zoom.unwrap_or(1.),
             color: styles.color.unwrap_or(Color::WHITE),
         };
 
         for section in text.sections.iter_mut() {
             section.style = style.clone();
         }
-        commands.entity(entity).remove::<TextStyleChanged>();
+        match zoom {
+            Some(_) => commands
+                .entity(entity)
+                .remove::<TextStyleChanged>()
+                .insert(ZoomedText),
+            None => commands.entity(entity).remove::<TextStyleChanged>(),
+        };
     }
 }
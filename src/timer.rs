@@ -0,0 +1,63 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::{cx::create_entity_mutable, mutable::MutableCell, signal::Signal, Cx};
+
+/// Component which drives a one-shot timer signal.
+#[derive(Component)]
+pub(crate) struct TimerSignal(Timer);
+
+/// Component which drives an interval signal.
+#[derive(Component)]
+pub(crate) struct IntervalSignal(Timer);
+
+pub(crate) fn update_timer_signals(
+    time: Option<Res<Time>>,
+    mut timers: Query<(&mut TimerSignal, &mut MutableCell<bool>)>,
+    mut intervals: Query<(&mut IntervalSignal, &mut MutableCell<u32>)>,
+) {
+    let Some(time) = time else { return };
+    let delta = time.delta();
+    for (mut timer, mut cell) in timers.iter_mut() {
+        if !cell.0 && timer.0.tick(delta).finished() {
+            cell.0 = true;
+        }
+    }
+    for (mut interval, mut cell) in intervals.iter_mut() {
+        let ticks = interval.0.tick(delta).times_finished_this_tick();
+        if ticks > 0 {
+            cell.0 = cell.0.wrapping_add(ticks);
+        }
+    }
+}
+
+/// Methods to create signals which are driven by the passage of time.
+pub trait CreateTimerSignal {
+    /// Create a signal which becomes `true` once the given duration has elapsed. The timer
+    /// is removed when the current tracking scope is despawned.
+    fn create_timer(&mut self, duration: Duration) -> Signal<bool>;
+
+    /// Create a signal which counts the number of times the given interval has elapsed.
+    /// Reactions which depend on this signal will run once per interval. The interval is
+    /// removed when the current tracking scope is despawned.
+    fn create_interval(&mut self, interval: Duration) -> Signal<u32>;
+}
+
+impl<'p, 'w> CreateTimerSignal for Cx<'p, 'w> {
+    fn create_timer(&mut self, duration: Duration) -> Signal<bool> {
+        let mutable = create_entity_mutable::<_, bool>(self, duration.is_zero());
+        self.world_mut()
+            .entity_mut(mutable.id())
+            .insert(TimerSignal(Timer::new(duration, TimerMode::Once)));
+        mutable.signal()
+    }
+
+    fn create_interval(&mut self, interval: Duration) -> Signal<u32> {
+        let mutable = create_entity_mutable::<_, u32>(self, 0);
+        self.world_mut()
+            .entity_mut(mutable.id())
+            .insert(IntervalSignal(Timer::new(interval, TimerMode::Repeating)));
+        mutable.signal()
+    }
+}
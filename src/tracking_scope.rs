@@ -1,10 +1,31 @@
+#[cfg(feature = "ui")]
+use std::panic::{catch_unwind, resume_unwind, AssertUnwindSafe};
+
+use std::{any::TypeId, sync::PoisonError};
+
 use bevy::{
-    ecs::component::{ComponentId, Tick},
+    asset::UntypedAssetId,
+    ecs::{
+        component::{ComponentId, Tick},
+        event::ManualEventReader,
+        query::{QueryFilter, QueryState, ReadOnlyQueryData},
+    },
     prelude::*,
-    utils::HashSet,
+    utils::{HashMap, HashSet},
 };
 
-use crate::{reaction::ReactionCell, ViewHandle};
+#[cfg(feature = "ui")]
+use crate::{
+    error_boundary::{panic_message, report_to_error_boundary},
+    ViewHandle,
+};
+use crate::{
+    frame_budget::{budget_enabled, budget_exhausted, FrameBudget, LowPriority},
+    leak_detector::record_despawn,
+    mutable::{despawn_released_mutables, MutableKey, PromotedMutable},
+    mutable_store::{MutableStore, SlotId},
+    reaction::ReactionCell,
+};
 
 /// A component that tracks the dependencies of a reactive task.
 #[derive(Component)]
@@ -18,6 +39,15 @@ pub struct TrackingScope {
     /// Set of resources that we are currently subscribed to.
     resource_deps: HashSet<ComponentId>,
 
+    /// Set of values in the [`MutableStore`] that we are currently subscribed to.
+    slot_deps: HashSet<SlotId>,
+
+    /// List of queries whose set of matching entities we are subscribed to.
+    query_deps: Vec<QueryDep>,
+
+    /// List of assets that we are currently subscribed to.
+    asset_deps: Vec<AssetDep>,
+
     /// Engine tick used for determining if components have changed. This represents the
     /// time of the previous reaction.
     tick: Tick,
@@ -44,6 +74,9 @@ impl TrackingScope {
             owned: Vec::new(),
             component_deps: HashSet::default(),
             resource_deps: HashSet::default(),
+            slot_deps: HashSet::default(),
+            query_deps: Vec::new(),
+            asset_deps: Vec::new(),
             tick,
             cleanups: Vec::new(),
         }
@@ -53,8 +86,14 @@ impl TrackingScope {
         self.owned.push(owned);
     }
 
-    /// Add a cleanup function which will be run once before the next reaction.
-    pub(crate) fn add_cleanup(&mut self, cleanup: impl FnOnce(&mut World) + 'static + Sync + Send) {
+    /// Add a cleanup function which will be run once before the next reaction, or when the
+    /// entity that owns this scope is despawned. This is mainly useful for custom [`Reaction`]
+    /// and [`View`] implementations; view templates should use [`Cx::on_cleanup`] instead.
+    ///
+    /// [`Reaction`]: crate::Reaction
+    /// [`View`]: crate::View
+    /// [`Cx::on_cleanup`]: crate::Cx::on_cleanup
+    pub fn add_cleanup(&mut self, cleanup: impl FnOnce(&mut World) + 'static + Sync + Send) {
         self.cleanups.push(Box::new(cleanup));
     }
 
@@ -84,12 +123,71 @@ impl TrackingScope {
         self.component_deps.insert((entity, component));
     }
 
+    /// Add a dependency on the value of a mutable.
+    pub(crate) fn track_mutable(&mut self, mutable: MutableKey) {
+        match mutable {
+            MutableKey::Entity(entity, component) => self.track_component_id(entity, component),
+            MutableKey::Slot(slot) => {
+                self.slot_deps.insert(slot);
+            }
+        }
+    }
+
+    /// Add a dependency on the result set of a query, returning the matching entities. The
+    /// scope will react when an entity starts or stops matching the query, or when any
+    /// component accessed by the query changes on a matching entity.
+    pub(crate) fn track_query<Q: ReadOnlyQueryData + 'static, F: QueryFilter + 'static>(
+        &mut self,
+        world: &World,
+    ) -> Vec<Entity> {
+        let entities = match QueryState::<(Entity, Q), F>::try_new(world).as_mut() {
+            Some(state) => {
+                let entities = query_entities(state, world);
+                let components: Vec<ComponentId> = state
+                    .component_access()
+                    .access()
+                    .reads_and_writes()
+                    .collect();
+                for entity in entities.iter() {
+                    for component in components.iter() {
+                        self.track_component_id(*entity, *component);
+                    }
+                }
+                entities
+            }
+            None => Vec::new(),
+        };
+        self.query_deps.push(QueryDep {
+            id: TypeId::of::<QueryState<(Entity, Q), F>>(),
+            create: create_query_results::<Q, F>,
+            entities: entities.clone(),
+        });
+        entities
+    }
+
+    /// Add a dependency on a single asset. The scope will react when the asset is added,
+    /// modified, loaded or removed, but not when other assets of the same type change.
+    pub(crate) fn track_asset<A: Asset>(&mut self, id: AssetId<A>) {
+        let id = id.untyped();
+        if !self.asset_deps.iter().any(|dep| dep.id == id) {
+            self.asset_deps.push(AssetDep {
+                id,
+                create: create_asset_changes::<A>,
+            });
+        }
+    }
+
     /// Returns true if any of the dependencies of this scope have been updated since
-    /// the previous reaction.
+    /// the previous reaction. [`run_reactions`] finds changed scopes through the
+    /// [`DependencyIndex`] instead; this checks a single scope in full.
+    #[cfg(test)]
     fn dependencies_changed(&self, world: &World, tick: Tick) -> bool {
-        self.components_changed(world, tick) || self.resources_changed(world, tick)
+        self.components_changed(world, tick)
+            || self.resources_changed(world, tick)
+            || self.slots_changed(world, tick)
     }
 
+    #[cfg(test)]
     fn components_changed(&self, world: &World, tick: Tick) -> bool {
         self.component_deps.iter().any(|(e, c)| {
             world.get_entity(*e).map_or(false, |e| {
@@ -100,6 +198,7 @@ impl TrackingScope {
         })
     }
 
+    #[cfg(test)]
     fn resources_changed(&self, world: &World, tick: Tick) -> bool {
         self.resource_deps.iter().any(|c| {
             world
@@ -109,15 +208,398 @@ impl TrackingScope {
         })
     }
 
+    #[cfg(test)]
+    fn slots_changed(&self, world: &World, tick: Tick) -> bool {
+        let Some(store) = world.get_resource::<MutableStore>() else {
+            return false;
+        };
+        self.slot_deps.iter().any(|slot| {
+            store
+                .changed_tick(*slot)
+                .map_or(false, |ct| ct.is_newer_than(self.tick, tick))
+        })
+    }
+
     /// Take the dependencies from another scope. Typically the other scope is a temporary
     /// scope that is used to compute the next set of dependencies.
     pub(crate) fn take_deps(&mut self, other: &mut Self) {
         self.component_deps = std::mem::take(&mut other.component_deps);
         self.resource_deps = std::mem::take(&mut other.resource_deps);
+        self.slot_deps = std::mem::take(&mut other.slot_deps);
+        self.query_deps = std::mem::take(&mut other.query_deps);
+        self.asset_deps = std::mem::take(&mut other.asset_deps);
         self.cleanups = std::mem::take(&mut other.cleanups);
     }
 }
 
+/// Reverse index from dependencies to the tracking scopes which subscribe to them. Rather than
+/// asking every scope whether any of its dependencies changed, [`run_reactions`] checks each
+/// distinct dependency once and only visits the scopes subscribed to the ones that changed.
+///
+/// Scopes are (re)indexed whenever their [`TrackingScope`] component is inserted or changed,
+/// which includes every time a scope reacts and records a new set of dependencies. Scopes
+/// despawned via [`DespawnScopes`] are removed from the index immediately; any others are
+/// pruned the next time one of their dependencies is checked.
+#[derive(Resource, Default)]
+pub(crate) struct DependencyIndex {
+    /// Tick at which `run_reactions` last ran.
+    last_run: Tick,
+
+    /// Subscribers for each component dependency.
+    components: HashMap<(Entity, ComponentId), HashSet<Entity>>,
+
+    /// Subscribers for each resource dependency.
+    resources: HashMap<ComponentId, HashSet<Entity>>,
+
+    /// Subscribers for each value in the [`MutableStore`]. The store records which values were
+    /// written, so these don't need to be checked individually.
+    slots: HashMap<SlotId, HashSet<Entity>>,
+
+    /// Subscribers for each query dependency. Query results can't be indexed by entity, so each
+    /// distinct query is evaluated on every run, once for all of its subscribers.
+    queries: HashMap<TypeId, IndexedQuery>,
+
+    /// Subscribers for each asset dependency, grouped by asset type so that the asset events
+    /// of each type are read once per run.
+    assets: HashMap<TypeId, IndexedAssets>,
+
+    /// The dependencies each scope was indexed with, so that they can be unsubscribed later.
+    subscriptions: HashMap<Entity, Subscription>,
+}
+
+struct Subscription {
+    components: Vec<(Entity, ComponentId)>,
+    resources: Vec<ComponentId>,
+    slots: Vec<SlotId>,
+    queries: Vec<TypeId>,
+    assets: Vec<UntypedAssetId>,
+}
+
+/// A query which one or more scopes depend on.
+struct IndexedQuery {
+    /// Query state, or `None` if the query's component types were not yet registered.
+    state: Option<Box<dyn QueryResults>>,
+    create: fn(&World) -> Option<Box<dyn QueryResults>>,
+    subscribers: HashSet<Entity>,
+}
+
+/// The assets of a single type which one or more scopes depend on.
+struct IndexedAssets {
+    /// Reader for the asset events of this type.
+    changes: Box<dyn AssetChanges>,
+    subscribers: HashMap<UntypedAssetId, HashSet<Entity>>,
+}
+
+impl DependencyIndex {
+    /// Changes made during or after the previous run are newer than this tick. The previous
+    /// run itself is included since reactions write to components at that tick.
+    fn since(&self) -> Tick {
+        Tick::new(self.last_run.get().wrapping_sub(1))
+    }
+
+    /// Index the current dependencies of a scope, replacing any previous subscriptions.
+    fn subscribe(&mut self, scope_entity: Entity, scope: &TrackingScope, world: &World) {
+        self.unsubscribe(scope_entity);
+        for key in scope.component_deps.iter() {
+            self.components
+                .entry(*key)
+                .or_default()
+                .insert(scope_entity);
+        }
+        for key in scope.resource_deps.iter() {
+            self.resources.entry(*key).or_default().insert(scope_entity);
+        }
+        for key in scope.slot_deps.iter() {
+            self.slots.entry(*key).or_default().insert(scope_entity);
+        }
+        let mut queries = Vec::<TypeId>::new();
+        for dep in scope.query_deps.iter() {
+            self.queries
+                .entry(dep.id)
+                .or_insert_with(|| IndexedQuery {
+                    state: None,
+                    create: dep.create,
+                    subscribers: HashSet::default(),
+                })
+                .subscribers
+                .insert(scope_entity);
+            if !queries.contains(&dep.id) {
+                queries.push(dep.id);
+            }
+        }
+        for dep in scope.asset_deps.iter() {
+            self.assets
+                .entry(dep.id.type_id())
+                .or_insert_with(|| IndexedAssets {
+                    changes: (dep.create)(world),
+                    subscribers: HashMap::default(),
+                })
+                .subscribers
+                .entry(dep.id)
+                .or_default()
+                .insert(scope_entity);
+        }
+        self.subscriptions.insert(
+            scope_entity,
+            Subscription {
+                components: scope.component_deps.iter().copied().collect(),
+                resources: scope.resource_deps.iter().copied().collect(),
+                slots: scope.slot_deps.iter().copied().collect(),
+                queries,
+                assets: scope.asset_deps.iter().map(|dep| dep.id).collect(),
+            },
+        );
+    }
+
+    /// Remove a scope from the index.
+    fn unsubscribe(&mut self, scope_entity: Entity) {
+        let Some(subscription) = self.subscriptions.remove(&scope_entity) else {
+            return;
+        };
+        for key in subscription.components.iter() {
+            if let Some(subscribers) = self.components.get_mut(key) {
+                subscribers.remove(&scope_entity);
+                if subscribers.is_empty() {
+                    self.components.remove(key);
+                }
+            }
+        }
+        for key in subscription.resources.iter() {
+            if let Some(subscribers) = self.resources.get_mut(key) {
+                subscribers.remove(&scope_entity);
+                if subscribers.is_empty() {
+                    self.resources.remove(key);
+                }
+            }
+        }
+        for key in subscription.slots.iter() {
+            if let Some(subscribers) = self.slots.get_mut(key) {
+                subscribers.remove(&scope_entity);
+                if subscribers.is_empty() {
+                    self.slots.remove(key);
+                }
+            }
+        }
+        for key in subscription.queries.iter() {
+            if let Some(query) = self.queries.get_mut(key) {
+                query.subscribers.remove(&scope_entity);
+                if query.subscribers.is_empty() {
+                    self.queries.remove(key);
+                }
+            }
+        }
+        for key in subscription.assets.iter() {
+            if let Some(assets) = self.assets.get_mut(&key.type_id()) {
+                if let Some(subscribers) = assets.subscribers.get_mut(key) {
+                    subscribers.remove(&scope_entity);
+                    if subscribers.is_empty() {
+                        assets.subscribers.remove(key);
+                    }
+                }
+                if assets.subscribers.is_empty() {
+                    self.assets.remove(&key.type_id());
+                }
+            }
+        }
+    }
+
+    /// Re-index all scopes which were added or changed since the previous run.
+    fn update(&mut self, world: &mut World, tick: Tick) {
+        let since = self.since();
+        let mut scopes = world.query::<(Entity, Ref<TrackingScope>)>();
+        for (entity, scope) in scopes.iter(world) {
+            if scope.last_changed().is_newer_than(since, tick) {
+                self.subscribe(entity, &scope, world);
+            }
+        }
+    }
+
+    /// Returns the scopes whose dependencies have changed since they last reacted, along with
+    /// the kind of dependency which triggered each one. `dirty` is the set of values written to
+    /// the [`MutableStore`] since the previous run.
+    fn changed_scopes(
+        &mut self,
+        world: &World,
+        tick: Tick,
+        dirty: &HashSet<SlotId>,
+    ) -> HashMap<Entity, Trigger> {
+        let since = self.since();
+        let mut changed = HashMap::<Entity, Trigger>::default();
+        let mut dead_scopes = Vec::<Entity>::new();
+        let mut dead_keys = Vec::<(Entity, ComponentId)>::new();
+
+        // Each subscriber compares against the tick of its own previous reaction, since scopes
+        // created after the previous run must not see changes which happened before them.
+        let mut notify = |subscribers: &HashSet<Entity>, last_changed: Tick, trigger: Trigger| {
+            for scope_entity in subscribers.iter() {
+                match world.get::<TrackingScope>(*scope_entity) {
+                    Some(scope) if last_changed.is_newer_than(scope.tick, tick) => {
+                        changed.entry(*scope_entity).or_insert(trigger);
+                    }
+                    Some(_) => {}
+                    None => dead_scopes.push(*scope_entity),
+                }
+            }
+        };
+
+        for (key, subscribers) in self.components.iter() {
+            let Some(entity) = world.get_entity(key.0) else {
+                dead_keys.push(*key);
+                continue;
+            };
+            match entity.get_change_ticks_by_id(key.1) {
+                Some(ticks) if ticks.is_changed(since, tick) => {
+                    notify(subscribers, ticks.last_changed_tick(), Trigger::Component)
+                }
+                _ => {}
+            }
+        }
+
+        for (key, subscribers) in self.resources.iter() {
+            match world.get_resource_change_ticks_by_id(*key) {
+                Some(ticks) if ticks.is_changed(since, tick) => {
+                    notify(subscribers, ticks.last_changed_tick(), Trigger::Resource)
+                }
+                _ => {}
+            }
+        }
+
+        if let Some(store) = world.get_resource::<MutableStore>() {
+            for slot in dirty.iter() {
+                if let (Some(subscribers), Some(last_changed)) =
+                    (self.slots.get(slot), store.changed_tick(*slot))
+                {
+                    notify(subscribers, last_changed, Trigger::Mutable);
+                }
+            }
+        }
+
+        // Asset events don't record when they were sent, so they count as changes made now.
+        for assets in self.assets.values_mut() {
+            for id in assets.changes.changed(world) {
+                if let Some(subscribers) = assets.subscribers.get(&id) {
+                    notify(subscribers, tick, Trigger::Asset);
+                }
+            }
+        }
+
+        // Evaluate each query once, and compare the result with the entities each subscriber
+        // saw when it last reacted.
+        for (id, query) in self.queries.iter_mut() {
+            if query.state.is_none() {
+                query.state = (query.create)(world);
+            }
+            let Some(state) = query.state.as_mut() else {
+                continue;
+            };
+            let entities = state.entities(world);
+            for scope_entity in query.subscribers.iter() {
+                match world.get::<TrackingScope>(*scope_entity) {
+                    Some(scope)
+                        if scope
+                            .query_deps
+                            .iter()
+                            .any(|dep| dep.id == *id && dep.entities != entities) =>
+                    {
+                        changed.entry(*scope_entity).or_insert(Trigger::Query);
+                    }
+                    Some(_) => {}
+                    None => dead_scopes.push(*scope_entity),
+                }
+            }
+        }
+
+        // Prune entries for despawned entities.
+        for key in dead_keys {
+            self.components.remove(&key);
+        }
+        for scope_entity in dead_scopes {
+            self.unsubscribe(scope_entity);
+        }
+
+        changed
+    }
+}
+
+/// A dependency on the set of entities matched by a query.
+struct QueryDep {
+    /// Identifies the query, so that scopes which depend on the same query share its state.
+    id: TypeId,
+    /// Creates the query state when the query is first indexed.
+    create: fn(&World) -> Option<Box<dyn QueryResults>>,
+    /// The entities which matched the query when the dependency was recorded.
+    entities: Vec<Entity>,
+}
+
+/// A dependency on a single asset.
+struct AssetDep {
+    /// The id of the asset, which includes its type.
+    id: UntypedAssetId,
+    /// Creates the reader for asset events of this type when the type is first indexed.
+    create: fn(&World) -> Box<dyn AssetChanges>,
+}
+
+/// A type-erased reader for the asset events of a single asset type.
+trait AssetChanges: Send + Sync {
+    /// Returns the assets which were added, modified, loaded or removed since the previous
+    /// call.
+    fn changed(&mut self, world: &World) -> Vec<UntypedAssetId>;
+}
+
+impl<A: Asset> AssetChanges for ManualEventReader<AssetEvent<A>> {
+    fn changed(&mut self, world: &World) -> Vec<UntypedAssetId> {
+        let Some(events) = world.get_resource::<Events<AssetEvent<A>>>() else {
+            return Vec::new();
+        };
+        self.read(events)
+            .filter_map(|event| match event {
+                AssetEvent::Added { id }
+                | AssetEvent::Modified { id }
+                | AssetEvent::Removed { id }
+                | AssetEvent::LoadedWithDependencies { id } => Some(id.untyped()),
+                AssetEvent::Unused { .. } => None,
+            })
+            .collect()
+    }
+}
+
+/// Create a reader which skips over any asset events already in the queue.
+fn create_asset_changes<A: Asset>(world: &World) -> Box<dyn AssetChanges> {
+    Box::new(match world.get_resource::<Events<AssetEvent<A>>>() {
+        Some(events) => events.get_reader_current(),
+        None => ManualEventReader::<AssetEvent<A>>::default(),
+    })
+}
+
+/// A type-erased query state which returns the matching entities.
+trait QueryResults: Send + Sync {
+    /// Returns the entities which currently match the query.
+    fn entities(&mut self, world: &World) -> Vec<Entity>;
+}
+
+impl<Q: ReadOnlyQueryData + 'static, F: QueryFilter + 'static> QueryResults
+    for QueryState<(Entity, Q), F>
+{
+    fn entities(&mut self, world: &World) -> Vec<Entity> {
+        query_entities(self, world)
+    }
+}
+
+fn create_query_results<Q: ReadOnlyQueryData + 'static, F: QueryFilter + 'static>(
+    world: &World,
+) -> Option<Box<dyn QueryResults>> {
+    QueryState::<(Entity, Q), F>::try_new(world)
+        .map(|state| Box::new(state) as Box<dyn QueryResults>)
+}
+
+fn query_entities<Q: ReadOnlyQueryData, F: QueryFilter>(
+    state: &mut QueryState<(Entity, Q), F>,
+    world: &World,
+) -> Vec<Entity> {
+    state.update_archetypes(world);
+    state.iter_manual(world).map(|(entity, _)| entity).collect()
+}
+
 /// Trait which allows despawning of any owned objects or reactions in the tracking scope
 /// associated with an entity. This operation is recursive in that an owned object may itself
 /// own other objects.
@@ -128,8 +610,14 @@ pub trait DespawnScopes {
 
 impl DespawnScopes for World {
     fn despawn_owned_recursive(&mut self, scope_entity: Entity) {
+        if !record_despawn(self, scope_entity) {
+            return;
+        }
         let mut entt = self.entity_mut(scope_entity);
         let Some(mut scope) = entt.get_mut::<TrackingScope>() else {
+            // Owned entities without a scope, such as mutable cells and callbacks, have
+            // nothing else to clean up.
+            entt.despawn();
             return;
         };
         // Run any cleanups
@@ -137,72 +625,244 @@ impl DespawnScopes for World {
         // Recursively despawn owned objects
         let owned_list = std::mem::take(&mut scope.owned);
         entt.despawn();
+        if let Some(mut index) = self.get_resource_mut::<DependencyIndex>() {
+            index.unsubscribe(scope_entity);
+        }
+        if let Some(mut store) = self.get_resource_mut::<MutableStore>() {
+            store.remove_owned(scope_entity);
+        }
         for cleanup_fn in cleanups.drain(..) {
             cleanup_fn(self);
         }
         for owned in owned_list {
-            self.despawn_owned_recursive(owned);
+            if self.get::<PromotedMutable>(owned).is_none() {
+                self.despawn_owned_recursive(owned);
+            }
+        }
+    }
+}
+
+/// The kind of dependency change which caused a reaction to run.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Trigger {
+    Component,
+    Resource,
+    Mutable,
+    Query,
+    Asset,
+    /// A low-priority reaction which was postponed to a later frame by the [`FrameBudget`].
+    Deferred,
+}
+
+impl Trigger {
+    #[cfg(feature = "trace")]
+    fn as_str(&self) -> &'static str {
+        match self {
+            Trigger::Component => "component",
+            Trigger::Resource => "resource",
+            Trigger::Mutable => "mutable",
+            Trigger::Query => "query",
+            Trigger::Asset => "asset",
+            Trigger::Deferred => "deferred",
         }
     }
 }
 
+/// Tracing span covering a single reaction. The span is named `reaction`, and has fields for
+/// the name of the owning view, the trigger, and the duration in microseconds, which is
+/// recorded when the span is dropped.
+#[cfg(feature = "trace")]
+struct ReactionSpan {
+    span: bevy::utils::tracing::span::EnteredSpan,
+    start: bevy::utils::Instant,
+}
+
+#[cfg(feature = "trace")]
+impl ReactionSpan {
+    fn enter(world: &World, scope_entity: Entity, trigger: Trigger) -> Self {
+        use bevy::utils::tracing::{field::Empty, info_span};
+        let span = info_span!(
+            "reaction",
+            view = Empty,
+            trigger = trigger.as_str(),
+            duration_us = Empty
+        );
+        if !span.is_disabled() {
+            // Effects and memos don't have names of their own, so use the nearest named
+            // ancestor, which is normally the view which created them.
+            let named = std::iter::successors(Some(scope_entity), |entity| {
+                world.get::<Parent>(*entity).map(Parent::get)
+            })
+            .find_map(|entity| world.get::<Name>(entity));
+            match named {
+                Some(name) => span.record("view", name.as_str()),
+                None => span.record("view", format!("{:?}", scope_entity).as_str()),
+            };
+        }
+        Self {
+            span: span.entered(),
+            start: bevy::utils::Instant::now(),
+        }
+    }
+}
+
+#[cfg(feature = "trace")]
+impl Drop for ReactionSpan {
+    fn drop(&mut self) {
+        self.span
+            .record("duration_us", self.start.elapsed().as_micros() as u64);
+    }
+}
+
 /// Run reactions whose dependencies have changed.
 pub fn run_reactions(world: &mut World) {
-    let mut scopes = world.query::<(Entity, &mut TrackingScope)>();
-    let mut changed = HashSet::<Entity>::default();
+    despawn_released_mutables(world);
     let tick = world.change_tick();
-    for (entity, scope) in scopes.iter(world) {
-        if scope.dependencies_changed(world, tick) {
-            changed.insert(entity);
-        }
+    if !world.contains_resource::<DependencyIndex>() {
+        world.init_resource::<DependencyIndex>();
     }
+    let changed = world.resource_scope(|world, mut index: Mut<DependencyIndex>| {
+        index.update(world, tick);
+        let dirty = world
+            .get_resource_mut::<MutableStore>()
+            .map(|mut store| store.take_dirty())
+            .unwrap_or_default();
+        let changed = index.changed_scopes(world, tick, &dirty);
+        index.last_run = tick;
+        changed
+    });
+    let mut scopes = world.query::<&mut TrackingScope>();
 
     // Record the changed entities for debugging purposes.
     if let Some(mut tracing) = world.get_resource_mut::<TrackingScopeTracing>() {
-        tracing.0 = changed.iter().copied().collect();
+        tracing.0 = changed.keys().copied().collect();
     }
 
-    for scope_entity in changed.iter() {
-        // Call registered cleanup functions
-        let mut cleanups = match scopes.get_mut(world, *scope_entity) {
-            Ok((_, mut scope)) => std::mem::take(&mut scope.cleanups),
-            Err(_) => Vec::new(),
-        };
-        for cleanup_fn in cleanups.drain(..) {
-            cleanup_fn(world);
+    // When a frame budget is set, low-priority reactions are run after all others, and only
+    // while there is time left in the frame. Ones which don't fit are queued for the next frame.
+    let mut deferrable = FrameBudget::take_pending_reactions(world);
+    let splitting = budget_enabled(world);
+    for (scope_entity, trigger) in changed.iter() {
+        if splitting && world.get::<LowPriority>(*scope_entity).is_some() {
+            if !deferrable.contains(scope_entity) {
+                deferrable.push(*scope_entity);
+            }
+        } else {
+            run_reaction(world, &mut scopes, *scope_entity, *trigger, tick);
         }
+    }
 
-        // Run the reaction
-        let mut next_scope = TrackingScope::new(tick);
-        if let Some(mut entt) = world.get_entity_mut(*scope_entity) {
-            if let Some(view_handle) = entt.get_mut::<ViewHandle>() {
-                let inner = view_handle.0.clone();
-                inner
-                    .lock()
-                    .unwrap()
-                    .react(*scope_entity, world, &mut next_scope);
-            } else if let Some(reaction) = entt.get_mut::<ReactionCell>() {
-                let inner = reaction.0.clone();
-                inner
-                    .lock()
-                    .unwrap()
-                    .react(*scope_entity, world, &mut next_scope);
-            }
+    let mut deferred = deferrable.into_iter();
+    for scope_entity in deferred.by_ref() {
+        // Reactions carried over from an earlier frame no longer know what triggered them.
+        let trigger = changed
+            .get(&scope_entity)
+            .copied()
+            .unwrap_or(Trigger::Deferred);
+        run_reaction(world, &mut scopes, scope_entity, trigger, tick);
+        if budget_exhausted(world) {
+            break;
+        }
+    }
+    FrameBudget::set_pending_reactions(world, deferred.collect());
+}
+
+/// Run the reaction for a single tracking scope, replacing its dependencies.
+fn run_reaction(
+    world: &mut World,
+    scopes: &mut QueryState<&mut TrackingScope>,
+    scope_entity: Entity,
+    #[cfg_attr(not(feature = "trace"), allow(unused_variables))] trigger: Trigger,
+    tick: Tick,
+) {
+    #[cfg(feature = "trace")]
+    let _span = ReactionSpan::enter(world, scope_entity, trigger);
+
+    // Call registered cleanup functions
+    let mut cleanups = match scopes.get_mut(world, scope_entity) {
+        Ok(mut scope) => std::mem::take(&mut scope.cleanups),
+        Err(_) => Vec::new(),
+    };
+    for cleanup_fn in cleanups.drain(..) {
+        cleanup_fn(world);
+    }
+
+    // Run the reaction
+    let mut next_scope = TrackingScope::new(tick);
+    if !react_view(world, scope_entity, &mut next_scope) {
+        if let Some(reaction) = world.get::<ReactionCell>(scope_entity) {
+            let inner = reaction.0.clone();
+            contain_panics(world, scope_entity, |world| {
+                inner.lock().unwrap_or_else(PoisonError::into_inner).react(
+                    scope_entity,
+                    world,
+                    &mut next_scope,
+                )
+            });
         }
+    }
+
+    // Replace deps and cleanups in the current scope with the next scope.
+    if let Ok(mut scope) = scopes.get_mut(world, scope_entity) {
+        // Swap the scopes so that the next scope becomes the current scope.
+        // The old scopes will be dropped at the end of the function.
+        scope.take_deps(&mut next_scope);
+        scope.tick = tick;
+    }
+}
+
+/// Run the reaction for a view entity. Returns false if the entity is not a view.
+#[cfg(feature = "ui")]
+fn react_view(world: &mut World, entity: Entity, tracking: &mut TrackingScope) -> bool {
+    let Some(view_handle) = world.get::<ViewHandle>(entity) else {
+        return false;
+    };
+    let inner = view_handle.0.clone();
+    contain_panics(world, entity, |world| {
+        inner
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .react(entity, world, tracking)
+    });
+    true
+}
 
-        // Replace deps and cleanups in the current scope with the next scope.
-        if let Ok((_, mut scope)) = scopes.get_mut(world, *scope_entity) {
-            // Swap the scopes so that the next scope becomes the current scope.
-            // The old scopes will be dropped at the end of the loop block.
-            scope.take_deps(&mut next_scope);
-            scope.tick = tick;
+/// Headless builds have no views.
+#[cfg(not(feature = "ui"))]
+fn react_view(_world: &mut World, _entity: Entity, _tracking: &mut TrackingScope) -> bool {
+    false
+}
+
+/// Run a reaction. Panics in views, effects and memos are caught so that an enclosing
+/// `ErrorBoundary` can replace the broken subtree with a fallback; panics outside of any
+/// boundary are resumed.
+#[cfg(feature = "ui")]
+fn contain_panics(world: &mut World, entity: Entity, reaction: impl FnOnce(&mut World)) {
+    if let Err(payload) = catch_unwind(AssertUnwindSafe(|| reaction(world))) {
+        let message = panic_message(payload.as_ref());
+        if !report_to_error_boundary(world, entity, &message) {
+            resume_unwind(payload);
         }
     }
 }
 
+/// Headless builds have no error boundaries.
+#[cfg(not(feature = "ui"))]
+fn contain_panics(world: &mut World, _entity: Entity, reaction: impl FnOnce(&mut World)) {
+    reaction(world);
+}
+
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
+    use bevy::ecs::system::RunSystemOnce;
+
     use super::*;
+    use crate::{
+        frame_budget::start_frame_budget, CreateFrameBudgetSignals, Cx, RunContextRead,
+        RunContextSetup,
+    };
 
     #[derive(Resource, Default)]
     struct TestResource(bool);
@@ -235,4 +895,254 @@ mod tests {
         let tick = world.change_tick();
         assert!(scope.dependencies_changed(&world, tick));
     }
+
+    #[derive(Resource, Default)]
+    struct CleanupCount(usize);
+
+    #[test]
+    fn test_cleanup_on_despawn() {
+        let mut world = World::default();
+        world.init_resource::<CleanupCount>();
+        let mut scope = TrackingScope::new(world.change_tick());
+        scope.add_cleanup(|world| world.resource_mut::<CleanupCount>().0 += 1);
+        let owner = world.spawn(scope).id();
+        assert_eq!(world.resource::<CleanupCount>().0, 0);
+
+        world.despawn_owned_recursive(owner);
+        assert_eq!(world.resource::<CleanupCount>().0, 1);
+        assert!(world.get_entity(owner).is_none());
+    }
+
+    #[test]
+    fn test_cleanup_before_reaction() {
+        let mut world = World::default();
+        world.init_resource::<CleanupCount>();
+        world.insert_resource(TestResource(false));
+        let owner = world.spawn_empty().id();
+        let mut scope = TrackingScope::new(world.change_tick());
+        let mut cx = Cx::new(&mut world, owner, &mut scope);
+        cx.create_effect(|cx| {
+            let _ = cx.use_resource::<TestResource>();
+            cx.on_cleanup(|world| world.resource_mut::<CleanupCount>().0 += 1);
+        });
+
+        // Nothing changed, so the effect should not re-run.
+        world.increment_change_tick();
+        run_reactions(&mut world);
+        assert_eq!(world.resource::<CleanupCount>().0, 0);
+
+        // Changing the dependency runs the previous cleanup before the reaction.
+        world.increment_change_tick();
+        world.resource_mut::<TestResource>().0 = true;
+        run_reactions(&mut world);
+        assert_eq!(world.resource::<CleanupCount>().0, 1);
+
+        // Despawning the owner runs the remaining cleanup.
+        world.entity_mut(owner).insert(scope);
+        world.despawn_owned_recursive(owner);
+        assert_eq!(world.resource::<CleanupCount>().0, 2);
+    }
+
+    #[derive(Resource, Default)]
+    struct OtherResource(bool);
+
+    #[derive(Resource, Default)]
+    struct RunCount(usize, usize);
+
+    #[test]
+    fn test_only_changed_scopes_react() {
+        let mut world = World::default();
+        world.init_resource::<RunCount>();
+        world.insert_resource(TestResource(false));
+        world.insert_resource(OtherResource(false));
+        let owner = world.spawn_empty().id();
+        let mut scope = TrackingScope::new(world.change_tick());
+        let mut cx = Cx::new(&mut world, owner, &mut scope);
+        cx.create_effect(|cx| {
+            let _ = cx.use_resource::<TestResource>();
+            cx.world_mut().resource_mut::<RunCount>().0 += 1;
+        });
+        cx.create_effect(|cx| {
+            let _ = cx.use_resource::<OtherResource>();
+            cx.world_mut().resource_mut::<RunCount>().1 += 1;
+        });
+        assert_eq!(world.resource::<RunCount>().0, 1);
+        assert_eq!(world.resource::<RunCount>().1, 1);
+
+        // Only the effect which depends on the changed resource should react.
+        world.increment_change_tick();
+        world.resource_mut::<TestResource>().0 = true;
+        run_reactions(&mut world);
+        assert_eq!(world.resource::<RunCount>().0, 2);
+        assert_eq!(world.resource::<RunCount>().1, 1);
+
+        // Nothing changed.
+        world.increment_change_tick();
+        run_reactions(&mut world);
+        assert_eq!(world.resource::<RunCount>().0, 2);
+        assert_eq!(world.resource::<RunCount>().1, 1);
+
+        world.increment_change_tick();
+        world.resource_mut::<OtherResource>().0 = true;
+        run_reactions(&mut world);
+        assert_eq!(world.resource::<RunCount>().0, 2);
+        assert_eq!(world.resource::<RunCount>().1, 2);
+    }
+
+    #[derive(Component)]
+    struct Marker;
+
+    #[test]
+    fn test_query_deps_are_shared() {
+        let mut world = World::default();
+        world.init_resource::<RunCount>();
+        world.spawn(Marker);
+        let owner = world.spawn_empty().id();
+        let mut scope = TrackingScope::new(world.change_tick());
+        let mut cx = Cx::new(&mut world, owner, &mut scope);
+        cx.create_effect(|cx| {
+            let _ = cx.use_query::<&Marker, ()>();
+            cx.world_mut().resource_mut::<RunCount>().0 += 1;
+        });
+        cx.create_effect(|cx| {
+            let _ = cx.use_query::<&Marker, ()>();
+            cx.world_mut().resource_mut::<RunCount>().1 += 1;
+        });
+
+        // Both effects subscribe to a single indexed query.
+        world.increment_change_tick();
+        run_reactions(&mut world);
+        assert_eq!(world.resource::<DependencyIndex>().queries.len(), 1);
+        assert_eq!(world.resource::<RunCount>().0, 1);
+        assert_eq!(world.resource::<RunCount>().1, 1);
+
+        // An entity starts matching the query.
+        world.increment_change_tick();
+        world.spawn(Marker);
+        run_reactions(&mut world);
+        assert_eq!(world.resource::<RunCount>().0, 2);
+        assert_eq!(world.resource::<RunCount>().1, 2);
+
+        // Nothing changed.
+        world.increment_change_tick();
+        run_reactions(&mut world);
+        assert_eq!(world.resource::<RunCount>().0, 2);
+        assert_eq!(world.resource::<RunCount>().1, 2);
+    }
+
+    #[derive(Asset, TypePath)]
+    struct TestAsset(#[allow(dead_code)] i32);
+
+    #[test]
+    fn test_asset_deps_are_per_asset() {
+        let mut world = World::default();
+        world.init_resource::<RunCount>();
+        world.init_resource::<Assets<TestAsset>>();
+        world.init_resource::<Events<AssetEvent<TestAsset>>>();
+        let (used, other) = {
+            let mut assets = world.resource_mut::<Assets<TestAsset>>();
+            (assets.add(TestAsset(0)), assets.add(TestAsset(1)))
+        };
+        let owner = world.spawn_empty().id();
+        let mut scope = TrackingScope::new(world.change_tick());
+        let mut cx = Cx::new(&mut world, owner, &mut scope);
+        let handle = used.clone();
+        cx.create_effect(move |cx| {
+            let _ = cx.use_asset(&handle);
+            cx.world_mut().resource_mut::<RunCount>().0 += 1;
+        });
+
+        // Index the effect.
+        world.increment_change_tick();
+        run_reactions(&mut world);
+        assert_eq!(world.resource::<RunCount>().0, 1);
+
+        // Changing another asset of the same type doesn't re-run the effect, even though the
+        // asset collection changed.
+        world.increment_change_tick();
+        world
+            .resource_mut::<Assets<TestAsset>>()
+            .get_mut(&other)
+            .unwrap()
+            .0 = 2;
+        world.send_event(AssetEvent::Modified { id: other.id() });
+        run_reactions(&mut world);
+        assert_eq!(world.resource::<RunCount>().0, 1);
+
+        // Changing the asset which was used does.
+        world.increment_change_tick();
+        world.send_event(AssetEvent::Modified { id: used.id() });
+        run_reactions(&mut world);
+        assert_eq!(world.resource::<RunCount>().0, 2);
+
+        // Events are only seen once.
+        world.increment_change_tick();
+        run_reactions(&mut world);
+        assert_eq!(world.resource::<RunCount>().0, 2);
+    }
+
+    #[test]
+    fn test_despawned_scope_is_unsubscribed() {
+        let mut world = World::default();
+        world.init_resource::<RunCount>();
+        world.insert_resource(TestResource(false));
+        let owner = world.spawn_empty().id();
+        let mut scope = TrackingScope::new(world.change_tick());
+        let mut cx = Cx::new(&mut world, owner, &mut scope);
+        cx.create_effect(|cx| {
+            let _ = cx.use_resource::<TestResource>();
+            cx.world_mut().resource_mut::<RunCount>().0 += 1;
+        });
+        world.entity_mut(owner).insert(scope);
+
+        // Index the owner and the effect.
+        world.increment_change_tick();
+        run_reactions(&mut world);
+        assert_eq!(world.resource::<DependencyIndex>().subscriptions.len(), 2);
+
+        world.despawn_owned_recursive(owner);
+        assert!(world.resource::<DependencyIndex>().subscriptions.is_empty());
+        assert!(world.resource::<DependencyIndex>().resources.is_empty());
+
+        world.increment_change_tick();
+        world.resource_mut::<TestResource>().0 = true;
+        run_reactions(&mut world);
+        assert_eq!(world.resource::<RunCount>().0, 1);
+    }
+
+    #[test]
+    fn test_low_priority_reactions_are_deferred() {
+        let mut world = World::default();
+        world.init_resource::<RunCount>();
+        world.insert_resource(TestResource(false));
+        // A zero budget is used up by the first deferrable reaction in each frame.
+        world.insert_resource(FrameBudget::new(Duration::ZERO));
+        let owner = world.spawn_empty().id();
+        let mut scope = TrackingScope::new(world.change_tick());
+        let mut cx = Cx::new(&mut world, owner, &mut scope);
+        cx.create_low_priority_effect(|cx| {
+            let _ = cx.use_resource::<TestResource>();
+            cx.world_mut().resource_mut::<RunCount>().0 += 1;
+        });
+        cx.create_low_priority_effect(|cx| {
+            let _ = cx.use_resource::<TestResource>();
+            cx.world_mut().resource_mut::<RunCount>().1 += 1;
+        });
+
+        world.increment_change_tick();
+        world.resource_mut::<TestResource>().0 = true;
+        world.run_system_once(start_frame_budget);
+        run_reactions(&mut world);
+        let count = world.resource::<RunCount>();
+        assert_eq!(count.0 + count.1, 3);
+        assert!(world.resource::<FrameBudget>().is_building());
+
+        // The deferred reaction runs in the next frame, even though nothing else changed.
+        world.increment_change_tick();
+        world.run_system_once(start_frame_budget);
+        run_reactions(&mut world);
+        assert_eq!(world.resource::<RunCount>().0, 2);
+        assert_eq!(world.resource::<RunCount>().1, 2);
+        assert!(!world.resource::<FrameBudget>().is_building());
+    }
 }
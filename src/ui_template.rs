@@ -0,0 +1,161 @@
+use bevy::{prelude::*, reflect::GetTypeRegistration, utils::HashMap};
+
+use crate::{IntoView, ViewRef, ViewRoot};
+
+/// Component which instantiates a view template registered with
+/// [`RegisterUiTemplate::register_ui_template`]. The template's props are read from a
+/// component on the same entity, so both can be declared in a scene. When the component is
+/// added, the entity becomes the root of the new view.
+#[derive(Component, Clone, Default, Debug, Reflect)]
+#[reflect(Component, Default)]
+pub struct UiTemplate {
+    /// The name the template was registered under.
+    pub name: String,
+}
+
+impl UiTemplate {
+    /// Construct a new [`UiTemplate`] for the template registered under `name`.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self { name: name.into() }
+    }
+}
+
+type TemplateFactory = Box<dyn Fn(&World, Entity) -> Option<ViewRef> + Send + Sync>;
+
+/// Resource which holds the view templates which can be instantiated by name.
+#[derive(Resource, Default)]
+pub struct UiTemplateRegistry(HashMap<String, TemplateFactory>);
+
+impl UiTemplateRegistry {
+    /// True if a template has been registered under `name`.
+    pub fn contains(&self, name: &str) -> bool {
+        self.0.contains_key(name)
+    }
+}
+
+/// Method to register view templates which can be instantiated with [`UiTemplate`].
+pub trait RegisterUiTemplate {
+    /// Register a view template under `name`. `P` is the type of the template's props, which
+    /// is also registered for reflection so that it can be loaded from scenes. `factory`
+    /// constructs the view from the props; it also has access to the world, for example to
+    /// resolve [`StyleRef`]s.
+    ///
+    /// [`StyleRef`]: crate::StyleRef
+    fn register_ui_template<P, V, F>(&mut self, name: impl Into<String>, factory: F) -> &mut Self
+    where
+        P: Component + Reflect + TypePath + GetTypeRegistration,
+        V: IntoView,
+        F: Fn(&P, &World) -> V + Send + Sync + 'static;
+}
+
+impl RegisterUiTemplate for App {
+    fn register_ui_template<P, V, F>(&mut self, name: impl Into<String>, factory: F) -> &mut Self
+    where
+        P: Component + Reflect + TypePath + GetTypeRegistration,
+        V: IntoView,
+        F: Fn(&P, &World) -> V + Send + Sync + 'static,
+    {
+        self.register_type::<P>()
+            .init_resource::<UiTemplateRegistry>();
+        let name = name.into();
+        let template_name = name.clone();
+        self.world_mut()
+            .resource_mut::<UiTemplateRegistry>()
+            .0
+            .insert(
+                name,
+                Box::new(move |world, entity| match world.get::<P>(entity) {
+                    Some(props) => Some(factory(props, world).into_view()),
+                    None => {
+                        warn!(
+                            "UiTemplate \"{}\" on {:?} has no {} props",
+                            template_name,
+                            entity,
+                            P::type_path()
+                        );
+                        None
+                    }
+                }),
+            );
+        self
+    }
+}
+
+/// System which turns newly-added [`UiTemplate`] entities into view roots.
+pub(crate) fn instantiate_ui_templates(world: &mut World) {
+    let mut added = world.query_filtered::<(Entity, &UiTemplate), Added<UiTemplate>>();
+    let templates: Vec<(Entity, String)> = added
+        .iter(world)
+        .map(|(entity, template)| (entity, template.name.clone()))
+        .collect();
+    if templates.is_empty() {
+        return;
+    }
+    let Some(registry) = world.get_resource::<UiTemplateRegistry>() else {
+        warn!("No UI templates have been registered");
+        return;
+    };
+    let views: Vec<(Entity, ViewRef)> = templates
+        .into_iter()
+        .filter_map(|(entity, name)| match registry.0.get(&name) {
+            Some(factory) => factory(world, entity).map(|view| (entity, view)),
+            None => {
+                warn!("Unknown UI template: {}", name);
+                None
+            }
+        })
+        .collect();
+    for (entity, view) in views {
+        world.entity_mut(entity).insert(ViewRoot(view.0));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Component, Clone, Default, Reflect)]
+    #[reflect(Component, Default)]
+    struct LabelProps {
+        label: String,
+    }
+
+    #[test]
+    fn test_instantiate_ui_templates() {
+        let mut app = App::new();
+        app.register_ui_template("label", |props: &LabelProps, _world| props.label.clone());
+        assert!(app
+            .world()
+            .resource::<UiTemplateRegistry>()
+            .contains("label"));
+        assert!(app
+            .world()
+            .resource::<AppTypeRegistry>()
+            .read()
+            .contains(std::any::TypeId::of::<LabelProps>()));
+
+        let world = app.world_mut();
+        let label = world
+            .spawn((
+                UiTemplate::new("label"),
+                LabelProps {
+                    label: "Hello".to_string(),
+                },
+            ))
+            .id();
+        let no_props = world.spawn(UiTemplate::new("label")).id();
+        let unknown = world
+            .spawn((UiTemplate::new("unknown"), LabelProps::default()))
+            .id();
+        instantiate_ui_templates(world);
+        assert!(world.get::<ViewRoot>(label).is_some());
+        assert!(world.get::<ViewRoot>(no_props).is_none());
+        assert!(world.get::<ViewRoot>(unknown).is_none());
+
+        // Templates are only instantiated when the component is added.
+        world.entity_mut(label).remove::<ViewRoot>();
+        world.clear_trackers();
+        instantiate_ui_templates(world);
+        assert!(world.get::<ViewRoot>(label).is_none());
+    }
+}
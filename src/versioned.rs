@@ -0,0 +1,159 @@
+use std::{
+    ops::Deref,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use crate::{Mutable, RunContextWrite};
+
+/// Source of the stamps which identify each write of a [`Versioned`] value.
+static NEXT_STAMP: AtomicU64 = AtomicU64::new(1);
+
+fn next_stamp() -> u64 {
+    NEXT_STAMP.fetch_add(1, Ordering::Relaxed)
+}
+
+/// A value paired with a version number, which is incremented on every write. Writes are
+/// compared instead of values, so a [`Mutable`] holding a `Versioned` value notifies its
+/// dependents whenever it is written, without the value itself needing to be compared. This
+/// is useful for values which are expensive to compare, or which use interior mutability
+/// so that `PartialEq` can't tell whether they have changed.
+///
+/// The value can be read via `Deref`, and written with [`Mutable::set_versioned`] or
+/// [`Mutable::modify`]. Each call to [`Versioned::new`] and each write is given a unique
+/// stamp, and two `Versioned` values are only equal if one is a clone of the other, so
+/// replacing the whole value with `set_clone(Versioned::new(..))` is also a change.
+#[derive(Clone, Debug)]
+pub struct Versioned<T> {
+    value: T,
+    version: u64,
+    stamp: u64,
+}
+
+impl<T> Versioned<T> {
+    /// Create a new versioned value, with a version of zero.
+    pub fn new(value: T) -> Self {
+        Self {
+            value,
+            version: 0,
+            stamp: next_stamp(),
+        }
+    }
+
+    /// The number of times the value has been written.
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    /// Return the value, discarding the version.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T: Default> Default for Versioned<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T> Deref for Versioned<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl<T> PartialEq for Versioned<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.stamp == other.stamp
+    }
+}
+
+impl<T> Mutable<Versioned<T>>
+where
+    T: Send + Sync + 'static,
+{
+    /// Replace the value of a versioned [`Mutable`], incrementing the version.
+    ///
+    /// Arguments:
+    /// * `cx`: The reactive context.
+    /// * `value`: The new value.
+    pub fn set_versioned<R: RunContextWrite>(&self, cx: &mut R, value: T) {
+        self.update(cx, |mut versioned| {
+            versioned.value = value;
+            versioned.version += 1;
+            versioned.stamp = next_stamp();
+        });
+    }
+
+    /// Modify the value of a versioned [`Mutable`] in place, incrementing the version.
+    ///
+    /// Arguments:
+    /// * `cx`: The reactive context.
+    /// * `modifier`: Function which modifies the value.
+    pub fn modify<R: RunContextWrite, F: FnOnce(&mut T)>(&self, cx: &mut R, modifier: F) {
+        self.update(cx, |mut versioned| {
+            modifier(&mut versioned.value);
+            versioned.version += 1;
+            versioned.stamp = next_stamp();
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use crate::{tracking_scope::run_reactions, Cx, RunContextSetup, TrackingScope};
+
+    use super::*;
+
+    #[derive(Resource, Default)]
+    struct RunCount(usize);
+
+    #[test]
+    fn test_versioned() {
+        let mut world = World::default();
+        world.init_resource::<RunCount>();
+        let mut scope = TrackingScope::new(world.change_tick());
+        let owner = world.spawn_empty().id();
+        let mut cx = Cx::new(&mut world, owner, &mut scope);
+
+        let buffer = cx.create_mutable(Versioned::new(vec![1, 2, 3]));
+        cx.create_effect(move |cx| {
+            let _ = buffer.as_ref(cx).version();
+            cx.world_mut().resource_mut::<RunCount>().0 += 1;
+        });
+        assert_eq!(world.resource::<RunCount>().0, 1);
+
+        // Writing an equal value is still a change.
+        world.increment_change_tick();
+        buffer.set_versioned(&mut world, vec![1, 2, 3]);
+        run_reactions(&mut world);
+        assert_eq!(world.resource::<RunCount>().0, 2);
+
+        world.increment_change_tick();
+        buffer.modify(&mut world, |items| items.push(4));
+        run_reactions(&mut world);
+        assert_eq!(world.resource::<RunCount>().0, 3);
+
+        let versioned = buffer.as_ref(&mut world);
+        assert_eq!(versioned.version(), 2);
+        assert_eq!(**versioned, vec![1, 2, 3, 4]);
+
+        // Replacing the whole value is a change, but writing back a clone is not.
+        let current = buffer.get_clone(&mut world);
+        world.increment_change_tick();
+        buffer.set_clone(&mut world, current);
+        world.flush_commands();
+        run_reactions(&mut world);
+        assert_eq!(world.resource::<RunCount>().0, 3);
+
+        world.increment_change_tick();
+        buffer.set_clone(&mut world, Versioned::new(vec![1, 2, 3, 4]));
+        world.flush_commands();
+        run_reactions(&mut world);
+        assert_eq!(world.resource::<RunCount>().0, 4);
+    }
+}
@@ -1,4 +1,4 @@
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, PoisonError};
 
 use bevy::{
     core::Name,
@@ -13,8 +13,13 @@ use bevy::{
 };
 
 use crate::{
-    node_span::NodeSpan, text::TextStatic, tracking_scope::TrackingScope, Cx, DespawnScopes,
-    Signal, TextComputed,
+    frame_budget::{budget_exhausted, FrameBudget},
+    leak_detector::raze_view,
+    node_span::NodeSpan,
+    parent_view::ChildView,
+    text::TextStatic,
+    tracking_scope::TrackingScope,
+    Cx, DespawnScopes, Signal, TextComputed,
 };
 
 /// Trait that defines a view, which is a template that constructs a hierarchy of
@@ -53,6 +58,13 @@ pub trait View {
     fn children_changed(&mut self, view_entity: Entity, world: &mut World) -> bool {
         false
     }
+
+    /// The list of child views owned by this view, for views which keep their children in a
+    /// [`ChildView`] list (like [`Element`]). Views which return `None` can't give up or adopt
+    /// children with [`ViewRef::reparent`].
+    fn child_views(&mut self) -> Option<&mut Vec<ChildView>> {
+        None
+    }
 }
 
 #[derive(Component)]
@@ -67,7 +79,7 @@ impl ViewRoot {
 
     /// Despawn the view, including the display nodes, and all descendant views.
     pub fn despawn(&mut self, root: Entity, world: &mut World) {
-        self.0.lock().unwrap().raze(root, world);
+        raze_view(&mut *self.0.lock().unwrap(), root, world);
         world.entity_mut(root).despawn();
     }
 }
@@ -90,7 +102,7 @@ impl Command for DespawnViewRoot {
         };
         let handle = root.0.clone();
         let mut view = handle.lock().unwrap();
-        view.raze(self.0, world);
+        raze_view(&mut *view, self.0, world);
         // let entt = world.entity_mut(self.0);
         // entt.despawn();
     }
@@ -122,15 +134,75 @@ impl ViewRef {
 
     /// Returns the display nodes produced by this `View`.
     pub fn nodes(&self) -> NodeSpan {
-        self.0.lock().unwrap().nodes()
+        self.0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .nodes()
     }
 
     /// Destroy the view, including the display nodes, and all descendant views.
     pub fn raze(&self, view_entity: Entity, world: &mut World) {
-        self.0.lock().unwrap().raze(view_entity, world);
+        let mut view = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        raze_view(&mut *view, view_entity, world);
+    }
+
+    /// Move an already-built view to a new parent view, keeping its state and display nodes.
+    /// `view_entity` is the entity returned by [`ViewRef::spawn`], and `new_parent` is the
+    /// entity of the view which will own it from now on: the view is razed along with its
+    /// new parent, and inherited context is looked up from there.
+    ///
+    /// The view is removed from the child list of its current parent and appended to that of
+    /// the new one, and both parents re-attach their display nodes. Both parents must keep
+    /// their children in a child list (see [`View::child_views`]); returns `false`, leaving
+    /// the view where it was, if either doesn't, or if the view isn't one of its parent's
+    /// children.
+    pub fn reparent(&self, view_entity: Entity, new_parent: Entity, world: &mut World) -> bool {
+        let Some(old_parent) = world.get::<Parent>(view_entity).map(Parent::get) else {
+            return false;
+        };
+        if old_parent == new_parent {
+            return true;
+        }
+        let (Some(old_view), Some(new_view)) =
+            (view_of(world, old_parent), view_of(world, new_parent))
+        else {
+            return false;
+        };
+        let mut old_view = old_view.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut new_view = new_view.lock().unwrap_or_else(PoisonError::into_inner);
+        let (Some(old_children), Some(new_children)) =
+            (old_view.child_views(), new_view.child_views())
+        else {
+            return false;
+        };
+        let Some(index) = old_children
+            .iter()
+            .position(|child| child.entity == Some(view_entity))
+        else {
+            return false;
+        };
+        new_children.push(old_children.remove(index));
+        world.entity_mut(view_entity).set_parent(new_parent);
+
+        // Detach the nodes from the old parent first, then splice them into the new one.
+        for (parent, view) in [(old_parent, &mut old_view), (new_parent, &mut new_view)] {
+            if !view.children_changed(parent, world) {
+                world.entity_mut(parent).insert(DisplayNodeChanged);
+            }
+        }
+        true
     }
 }
 
+/// The view held by a view entity, which is either a [`ViewHandle`] or a [`ViewRoot`].
+fn view_of(world: &World, entity: Entity) -> Option<Arc<Mutex<dyn View + Sync + Send + 'static>>> {
+    let entity = world.get_entity(entity)?;
+    entity
+        .get::<ViewHandle>()
+        .map(|handle| handle.0.clone())
+        .or_else(|| entity.get::<ViewRoot>().map(|root| root.0.clone()))
+}
+
 impl Clone for ViewRef {
     fn clone(&self) -> Self {
         Self(self.0.clone())
@@ -276,7 +348,8 @@ impl<W: ViewTemplate> View for ViewTemplateState<W> {
         let mut entt = world.entity_mut(self.output_entity.unwrap());
         if let Some(handle) = entt.get_mut::<ViewHandle>() {
             // Despawn the inner view.
-            handle.0.clone().lock().unwrap().raze(entt.id(), world);
+            let inner = entt.id();
+            raze_view(&mut *handle.0.clone().lock().unwrap(), inner, world);
         };
         self.output_entity = None;
         world.despawn_owned_recursive(view_entity);
@@ -294,18 +367,30 @@ impl<W: ViewTemplate> View for ViewTemplateState<W> {
     }
 }
 
-/// System that initializes any views that have been added.
+/// System that initializes any views that have been added. When a [`FrameBudget`] is set,
+/// roots which don't fit in the current frame are built in later frames.
+///
+/// [`FrameBudget`]: crate::FrameBudget
 pub(crate) fn build_added_view_roots(world: &mut World) {
     // Need to copy query result to avoid double-borrow of world.
-    let mut roots = world.query_filtered::<(Entity, &mut ViewRoot), Added<ViewRoot>>();
-    let roots_copy: Vec<Entity> = roots.iter(world).map(|(e, _)| e).collect();
-    for root_entity in roots_copy.iter() {
-        let Ok((_, root)) = roots.get(world, *root_entity) else {
+    let mut added = world.query_filtered::<Entity, Added<ViewRoot>>();
+    let mut roots_copy = FrameBudget::take_pending_roots(world);
+    roots_copy.extend(added.iter(world));
+    let mut roots = roots_copy.into_iter();
+    for root_entity in roots.by_ref() {
+        let Some(root) = world
+            .get_entity(root_entity)
+            .and_then(|e| e.get::<ViewRoot>())
+        else {
             continue;
         };
         let inner = root.0.clone();
-        inner.lock().unwrap().build(*root_entity, world);
+        inner.lock().unwrap().build(root_entity, world);
+        if budget_exhausted(world) {
+            break;
+        }
     }
+    FrameBudget::set_pending_roots(world, roots.collect());
 }
 
 /// System that looks for changed child views and replaces the parent's child nodes.
@@ -360,3 +445,88 @@ pub(crate) fn attach_child_views(world: &mut World) {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy::{hierarchy::Children, ui::node_bundles::NodeBundle};
+
+    use crate::{Element, Mutable, ParentView, RunContextSetup};
+
+    use super::*;
+
+    /// A template which holds some state, and shares it with the test.
+    struct Stateful(Arc<Mutex<Option<Mutable<i32>>>>);
+
+    impl ViewTemplate for Stateful {
+        fn create(&self, cx: &mut Cx) -> impl IntoView {
+            *self.0.lock().unwrap() = Some(cx.create_mutable(7));
+            Element::<NodeBundle>::new().children(Element::<NodeBundle>::new())
+        }
+    }
+
+    fn display(view: &ViewRef) -> Entity {
+        let NodeSpan::Node(node) = view.nodes() else {
+            panic!("view should have a single display node");
+        };
+        node
+    }
+
+    #[test]
+    fn test_reparent() {
+        let mut world = World::default();
+        let root = world.spawn_empty().id();
+        let state = Arc::new(Mutex::new(None));
+        let child = Stateful(state.clone()).into_view();
+        let a = Element::<NodeBundle>::new()
+            .children(child.clone())
+            .into_view();
+        let b = Element::<NodeBundle>::new().into_view();
+        let entity_a = ViewRef::spawn(&a, root, &mut world);
+        let entity_b = ViewRef::spawn(&b, root, &mut world);
+        let entity_child = world.get::<Children>(entity_a).unwrap()[0];
+        let node = display(&child);
+        let grandchild = world.get::<Children>(node).unwrap()[0];
+        let state = state.lock().unwrap().unwrap();
+        assert_eq!(world.get::<Parent>(node).unwrap().get(), display(&a));
+
+        // The view keeps its entity, its display nodes and its state.
+        assert!(child.reparent(entity_child, entity_b, &mut world));
+        assert_eq!(world.get::<Parent>(entity_child).unwrap().get(), entity_b);
+        assert_eq!(world.get::<Parent>(node).unwrap().get(), display(&b));
+        assert!(world
+            .get::<Children>(display(&a))
+            .map_or(true, |children| children.is_empty()));
+        assert_eq!(world.get::<Parent>(grandchild).unwrap().get(), node);
+        assert_eq!(state.get(&world), 7);
+
+        // The view now belongs to the new parent, so razing the old one leaves it alone.
+        a.raze(entity_a, &mut world);
+        assert!(world.get_entity(node).is_some());
+        assert_eq!(state.get(&world), 7);
+        b.raze(entity_b, &mut world);
+        assert!(world.get_entity(node).is_none());
+        assert!(world.get_entity(grandchild).is_none());
+    }
+
+    #[test]
+    fn test_reparent_requires_child_list() {
+        let mut world = World::default();
+        let root = world.spawn_empty().id();
+        let child = Element::<NodeBundle>::new().into_view();
+        let a = Element::<NodeBundle>::new()
+            .children(child.clone())
+            .into_view();
+        let empty = ViewRef::new(EmptyView);
+        let entity_a = ViewRef::spawn(&a, root, &mut world);
+        let entity_empty = ViewRef::spawn(&empty, root, &mut world);
+        let entity_child = world.get::<Children>(entity_a).unwrap()[0];
+
+        // An empty view can't adopt children, so the view stays where it was.
+        assert!(!child.reparent(entity_child, entity_empty, &mut world));
+        assert_eq!(world.get::<Parent>(entity_child).unwrap().get(), entity_a);
+        assert_eq!(
+            world.get::<Parent>(display(&child)).unwrap().get(),
+            display(&a)
+        );
+    }
+}
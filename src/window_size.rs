@@ -0,0 +1,33 @@
+use bevy::{prelude::*, window::PrimaryWindow};
+
+use crate::{signal::Signal, Cx, RunContextRead, RunContextSetup};
+
+/// Resource containing the logical size of the primary window. This is only modified when the
+/// size actually changes, so reactions which depend on it don't run every frame.
+#[derive(Resource, Default, Clone, Copy, PartialEq, Debug)]
+pub struct WindowSize(pub Vec2);
+
+pub(crate) fn update_window_size(
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut size: ResMut<WindowSize>,
+) {
+    let Ok(window) = windows.get_single() else {
+        return;
+    };
+    let new_size = Vec2::new(window.width(), window.height());
+    if size.0 != new_size {
+        size.0 = new_size;
+    }
+}
+
+/// Method to create a signal that tracks the size of the primary window.
+pub trait CreateWindowSizeSignal {
+    /// Signal that returns the logical size of the primary window.
+    fn create_window_size_signal(&mut self) -> Signal<Vec2>;
+}
+
+impl<'p, 'w> CreateWindowSizeSignal for Cx<'p, 'w> {
+    fn create_window_size_signal(&mut self) -> Signal<Vec2> {
+        self.create_derived(|cx| cx.use_resource::<WindowSize>().0)
+    }
+}
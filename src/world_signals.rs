@@ -0,0 +1,119 @@
+use std::sync::{Arc, Mutex};
+
+use bevy::prelude::*;
+
+use crate::{Cx, Mutable, ReactionCell, RunContextSetup, Signal, TrackingScope};
+
+/// Resource holding the entity which owns mutables created via [`ReactiveWorld`].
+#[derive(Resource)]
+struct WorldSignalOwner(Entity);
+
+/// Returns the entity which owns world-level mutables, spawning it if needed.
+pub(crate) fn world_signal_owner(world: &mut World) -> Entity {
+    match world.get_resource::<WorldSignalOwner>() {
+        Some(owner) => owner.0,
+        None => {
+            let owner = world
+                .spawn((
+                    TrackingScope::new(world.change_tick()),
+                    Name::new("WorldSignalOwner"),
+                ))
+                .id();
+            world.insert_resource(WorldSignalOwner(owner));
+            owner
+        }
+    }
+}
+
+/// Methods which allow systems outside of views, such as game logic, to create and read
+/// signals and to run reactions, given only the [`World`]. This lets non-UI code publish
+/// state which the UI consumes without needing a [`Cx`].
+pub trait ReactiveWorld {
+    /// Create a new [`Mutable`] which is not owned by any view. It lives until the world is
+    /// dropped.
+    fn create_mutable<T: Send + Sync + 'static>(&mut self, init: T) -> Mutable<T>;
+
+    /// Read the value of a signal using Clone semantics. Reading a signal from the world
+    /// does not add it to any tracking scope.
+    fn read_signal<T: Clone + Send + Sync + 'static>(&self, signal: Signal<T>) -> T;
+
+    /// Create a reaction which is not attached to any view. The reaction is run once
+    /// immediately, and again whenever any of its dependencies change. Returns the entity
+    /// which holds the reaction, which can be passed to
+    /// [`despawn_owned_recursive`](crate::DespawnScopes::despawn_owned_recursive) to stop it.
+    fn create_reaction<F: Send + Sync + 'static + FnMut(&mut Cx)>(&mut self, reaction: F)
+        -> Entity;
+}
+
+impl ReactiveWorld for World {
+    #[track_caller]
+    fn create_mutable<T: Send + Sync + 'static>(&mut self, init: T) -> Mutable<T> {
+        let owner = world_signal_owner(self);
+
+        // Temporarily take the owner's scope, so that the new mutable can be added to it.
+        let mut scope = self
+            .entity_mut(owner)
+            .take::<TrackingScope>()
+            .unwrap_or_else(|| TrackingScope::new(self.change_tick()));
+        let mutable = Cx::new(self, owner, &mut scope).create_mutable(init);
+        self.entity_mut(owner).insert(scope);
+        mutable
+    }
+
+    fn read_signal<T: Clone + Send + Sync + 'static>(&self, signal: Signal<T>) -> T {
+        signal.get_clone(self)
+    }
+
+    fn create_reaction<F: Send + Sync + 'static + FnMut(&mut Cx)>(
+        &mut self,
+        reaction: F,
+    ) -> Entity {
+        let action = Arc::new(Mutex::new(reaction));
+        let mut scope = TrackingScope::new(self.change_tick());
+        let entity = self.spawn(Name::new("WorldReaction")).id();
+        action.lock().unwrap()(&mut Cx::new(self, entity, &mut scope));
+        self.entity_mut(entity)
+            .insert((scope, ReactionCell(action)));
+        entity
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{tracking_scope::run_reactions, DespawnScopes};
+
+    use super::*;
+
+    #[derive(Resource, Default)]
+    struct Score(i32);
+
+    #[test]
+    fn test_world_signals() {
+        let mut world = World::default();
+        world.init_resource::<Score>();
+
+        let health = world.create_mutable::<i32>(10);
+        assert_eq!(world.read_signal(health.signal()), 10);
+
+        let reaction = world.create_reaction(move |cx| {
+            let value = health.get(cx);
+            cx.world_mut().resource_mut::<Score>().0 = value;
+        });
+        assert_eq!(world.resource::<Score>().0, 10);
+
+        world.increment_change_tick();
+        health.set(&mut world, 5);
+        world.flush_commands();
+        run_reactions(&mut world);
+        assert_eq!(world.read_signal(health.signal()), 5);
+        assert_eq!(world.resource::<Score>().0, 5);
+
+        // Once despawned, the reaction no longer runs.
+        world.despawn_owned_recursive(reaction);
+        world.increment_change_tick();
+        health.set(&mut world, 1);
+        world.flush_commands();
+        run_reactions(&mut world);
+        assert_eq!(world.resource::<Score>().0, 5);
+    }
+}
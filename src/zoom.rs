@@ -0,0 +1,294 @@
+use bevy::{ecs::world::Command, prelude::*};
+
+use crate::{
+    effect_target::EntityEffect, signal::IntoSignal, style::TextStyleChanged, Cx, Element,
+    RunContextSetup, TrackingScope,
+};
+
+/// Component which scales the UI built by a view, so that an editor UI can be zoomed in and
+/// out (e.g. with Ctrl+= and Ctrl+-) without changing its styles. Insert it on a [`ViewRoot`]
+/// entity when the root is spawned, and change it to zoom the whole root; the zoom is
+/// combined with the window scale factor and `UiScale`.
+///
+/// Pixel lengths produced by [`StyleBuilder`], and the inherited font sizes of text, are
+/// multiplied by the zoom. Percentages and other relative lengths are left unchanged, as are
+/// lengths written to a `Style` by other systems, such as scroll offsets, which are already
+/// in layout space.
+///
+/// Zoom can also be set on an individual element with [`WithZoom`], for canvases which zoom
+/// independently of the rest of the UI. Nested zooms are multiplied together. Because the
+/// zoom is applied when styles are built, it must be present before the view is built.
+///
+/// [`ViewRoot`]: crate::ViewRoot
+/// [`StyleBuilder`]: crate::StyleBuilder
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct Zoom(pub f32);
+
+impl Default for Zoom {
+    fn default() -> Self {
+        Self(1.)
+    }
+}
+
+/// Trait to set the zoom of an element and its descendants.
+pub trait WithZoom {
+    /// Set the zoom factor of the element, where 1 is the original size. The argument may be
+    /// a signal, in which case the zoom is updated whenever it changes.
+    fn zoom(self, zoom: impl IntoSignal<f32>) -> Self;
+}
+
+impl<B: Bundle + Default> WithZoom for Element<B> {
+    fn zoom(mut self, zoom: impl IntoSignal<f32>) -> Self {
+        // Styles look up the zoom when they are applied, so it has to be set before any of
+        // the element's other effects start.
+        self.add_effect_first(Box::new(ZoomEffect {
+            zoom: zoom.into_signal(),
+        }));
+        self
+    }
+}
+
+/// Effect which keeps the [`Zoom`] of an element's view entity in sync with a signal.
+struct ZoomEffect {
+    zoom: crate::Signal<f32>,
+}
+
+impl EntityEffect for ZoomEffect {
+    fn start(
+        &mut self,
+        owner: Entity,
+        _display: Entity,
+        world: &mut World,
+        tracking: &mut TrackingScope,
+    ) {
+        let zoom = self.zoom;
+        let mut cx = Cx::new(world, owner, tracking);
+        cx.create_effect(move |cx| {
+            let zoom = Zoom(zoom.get(cx).max(0.));
+            let mut entt = cx.world_mut().entity_mut(owner);
+            match entt.get_mut::<Zoom>() {
+                Some(mut current) => {
+                    if *current != zoom {
+                        *current = zoom;
+                    }
+                }
+                None => {
+                    entt.insert(zoom);
+                }
+            }
+        });
+    }
+}
+
+/// Returns the combined zoom of the views enclosing `entity`, which is a view entity or one of
+/// its reactions, or `None` if none of them are zoomed.
+pub(crate) fn view_zoom(world: &World, entity: Entity) -> Option<f32> {
+    let mut result = None;
+    let mut ancestor = Some(entity);
+    while let Some(e) = ancestor {
+        let entt = world.entity(e);
+        if let Some(zoom) = entt.get::<Zoom>() {
+            result = Some(result.unwrap_or(1.) * zoom.0);
+        }
+        ancestor = entt.get::<Parent>().map(Parent::get);
+    }
+    result
+}
+
+/// True if two zoom factors are close enough that rescaling is not worthwhile.
+fn same_zoom(a: f32, b: f32) -> bool {
+    (a - b).abs() <= 1e-4 * a.abs().max(b.abs()).max(1.)
+}
+
+/// The lengths in a `Style` which are scaled by the zoom.
+fn lengths(style: &mut Style) -> [&mut Val; 25] {
+    [
+        &mut style.left,
+        &mut style.right,
+        &mut style.top,
+        &mut style.bottom,
+        &mut style.width,
+        &mut style.height,
+        &mut style.min_width,
+        &mut style.min_height,
+        &mut style.max_width,
+        &mut style.max_height,
+        &mut style.flex_basis,
+        &mut style.row_gap,
+        &mut style.column_gap,
+        &mut style.margin.left,
+        &mut style.margin.right,
+        &mut style.margin.top,
+        &mut style.margin.bottom,
+        &mut style.padding.left,
+        &mut style.padding.right,
+        &mut style.padding.top,
+        &mut style.padding.bottom,
+        &mut style.border.left,
+        &mut style.border.right,
+        &mut style.border.top,
+        &mut style.border.bottom,
+    ]
+}
+
+/// Records which lengths of a node's `Style` were produced by a style builder under a zoomed
+/// view, along with their unscaled values, so that they can be rescaled when the zoom changes.
+#[derive(Component)]
+pub(crate) struct ZoomedValues {
+    /// The view entity whose zoom applies to the node.
+    view: Entity,
+    /// The zoom that the lengths are currently scaled by.
+    pub(crate) zoom: f32,
+    /// Index (into [`lengths`]) and unscaled value of each builder-produced pixel length.
+    originals: Vec<(u8, f32)>,
+}
+
+impl ZoomedValues {
+    pub(crate) fn new(view: Entity) -> Self {
+        Self {
+            view,
+            zoom: 1.,
+            originals: Vec::new(),
+        }
+    }
+
+    /// Replace the scaled lengths in `style` with their unscaled values, so that styles can
+    /// be applied on top of them. Lengths which have been overwritten by something else since
+    /// they were scaled are no longer considered to be produced by the builder.
+    pub(crate) fn unscale(&mut self, style: &mut Style) {
+        let mut fields = lengths(style);
+        let zoom = self.zoom;
+        self.originals.retain(|&(index, original)| {
+            let field = &mut fields[index as usize];
+            if **field == Val::Px(original * zoom) {
+                **field = Val::Px(original);
+                true
+            } else {
+                false
+            }
+        });
+    }
+
+    /// Scale the lengths which the builder produced. `before` is the unscaled style which the
+    /// builder was applied to, and `style` is the result.
+    pub(crate) fn scale(&mut self, before: &Style, style: &mut Style, zoom: f32) {
+        let mut base = before.clone();
+        let base = lengths(&mut base);
+        let previous = std::mem::take(&mut self.originals);
+        for (index, field) in lengths(style).into_iter().enumerate() {
+            let owned = previous.iter().any(|&(i, _)| i as usize == index);
+            if *field == *base[index] && !owned {
+                continue;
+            }
+            if let Val::Px(px) = *field {
+                self.originals.push((index as u8, px));
+                *field = Val::Px(px * zoom);
+            }
+        }
+        self.zoom = zoom;
+    }
+
+    /// Rescale the lengths from the current zoom to `zoom`.
+    fn rescale(&mut self, style: &mut Style, zoom: f32) {
+        self.unscale(style);
+        let mut fields = lengths(style);
+        for &(index, original) in self.originals.iter() {
+            *fields[index as usize] = Val::Px(original * zoom);
+        }
+        self.zoom = zoom;
+    }
+}
+
+/// Marker for text whose font size was scaled by the zoom of an enclosing view.
+#[derive(Component)]
+pub(crate) struct ZoomedText;
+
+/// Command which rescales the styled nodes of zoomed views after a [`Zoom`] has changed.
+struct RescaleZoomedNodes;
+
+impl Command for RescaleZoomedNodes {
+    fn apply(self, world: &mut World) {
+        let mut nodes = world.query::<(Entity, &ZoomedValues)>();
+        let updates: Vec<(Entity, f32)> = nodes
+            .iter(world)
+            .filter_map(|(entity, values)| {
+                let zoom = view_zoom(world, values.view).unwrap_or(1.);
+                (!same_zoom(zoom, values.zoom)).then_some((entity, zoom))
+            })
+            .collect();
+        for (entity, zoom) in updates {
+            let mut entt = world.entity_mut(entity);
+            let Some(mut style) = entt.get::<Style>().cloned() else {
+                continue;
+            };
+            entt.get_mut::<ZoomedValues>()
+                .unwrap()
+                .rescale(&mut style, zoom);
+            entt.insert(style);
+        }
+
+        // Recompute the font sizes of text in zoomed views.
+        let mut texts = world.query_filtered::<Entity, With<ZoomedText>>();
+        let texts: Vec<Entity> = texts.iter(world).collect();
+        for text in texts {
+            world.entity_mut(text).insert(TextStyleChanged);
+        }
+    }
+}
+
+/// Rescales zoomed views whenever a [`Zoom`] is changed, added or removed.
+pub(crate) fn update_zoom(
+    mut commands: Commands,
+    changed: Query<(), Changed<Zoom>>,
+    mut removed: RemovedComponents<Zoom>,
+) {
+    let removed = removed.read().count() > 0;
+    if removed || !changed.is_empty() {
+        commands.add(RescaleZoomedNodes);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zoomed_values() {
+        let view = Entity::PLACEHOLDER;
+        let mut values = ZoomedValues::new(view);
+        let before = Style::default();
+        let mut style = Style {
+            width: Val::Px(100.),
+            height: Val::Percent(50.),
+            padding: UiRect::all(Val::Px(4.)),
+            ..default()
+        };
+        values.scale(&before, &mut style, 2.);
+        assert_eq!(style.width, Val::Px(200.));
+        assert_eq!(style.height, Val::Percent(50.));
+        assert_eq!(style.padding, UiRect::all(Val::Px(8.)));
+
+        // A length written by another system, such as a scroll offset, is left alone.
+        style.left = Val::Px(-30.);
+        values.rescale(&mut style, 0.5);
+        assert_eq!(style.width, Val::Px(50.));
+        assert_eq!(style.padding, UiRect::all(Val::Px(2.)));
+        assert_eq!(style.left, Val::Px(-30.));
+
+        // A builder-produced length which is overwritten by something else is released.
+        style.width = Val::Px(75.);
+        values.rescale(&mut style, 1.);
+        assert_eq!(style.width, Val::Px(75.));
+        assert_eq!(style.padding, UiRect::all(Val::Px(4.)));
+
+        // Restyling applies on top of the unscaled values.
+        values.rescale(&mut style, 3.);
+        values.unscale(&mut style);
+        let before = style.clone();
+        style.height = Val::Px(10.);
+        values.scale(&before, &mut style, 3.);
+        assert_eq!(style.height, Val::Px(30.));
+        assert_eq!(style.padding, UiRect::all(Val::Px(12.)));
+        assert_eq!(style.left, Val::Px(-30.));
+    }
+}